@@ -1,3 +1,6 @@
+use std::fs;
+use std::path::Path;
+
 fn main() {
     // Run tauri build first
     tauri_build::build();
@@ -7,6 +10,110 @@ fn main() {
     println!("cargo:rustc-link-lib=vorbis");
     println!("cargo:rustc-link-lib=vorbisenc");
 
+    // Manifest of the registered commands (see `commands::manifest`)
+    write_command_manifest();
+
     // Tell cargo to invalidate the built crate whenever any of these files change
     println!("cargo:rerun-if-changed=src/audio/");
+    println!("cargo:rerun-if-changed=src/commands/");
+    println!("cargo:rerun-if-changed=src/lib.rs");
+}
+
+/// Write `$OUT_DIR/command_manifest.rs`: the commands registered in
+/// `generate_handler!`, in order, with a hash of their signature
+fn write_command_manifest() {
+    let lib = fs::read_to_string("src/lib.rs").expect("Failed to read src/lib.rs");
+    let handler_start = lib
+        .find("generate_handler![")
+        .expect("generate_handler! not found in src/lib.rs")
+        + "generate_handler![".len();
+    let handler_end = handler_start + lib[handler_start..].find(']').unwrap();
+    let registered: Vec<&str> = lib[handler_start..handler_end]
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    let mut signatures = Vec::new();
+    collect_command_signatures(Path::new("src"), &mut signatures);
+
+    let mut entries = String::new();
+    let mut manifest_hash = FNV_OFFSET_BASIS;
+    for name in &registered {
+        let signature = signatures
+            .iter()
+            .find(|(command, _)| command == name)
+            .map(|(_, signature)| signature.as_str())
+            .unwrap_or_default();
+        let hash = fnv1a(FNV_OFFSET_BASIS, signature.as_bytes());
+        manifest_hash = fnv1a(manifest_hash, format!("{}:{:016x};", name, hash).as_bytes());
+        entries.push_str(&format!("    (\"{}\", \"{:016x}\"),\n", name, hash));
+    }
+
+    let manifest = format!(
+        "/// Registered commands and the hash of their signature, in registration order\n\
+         pub const COMMAND_SIGNATURES: &[(&str, &str)] = &[\n{}];\n\n\
+         /// Hash of the whole command manifest\n\
+         pub const COMMAND_MANIFEST_HASH: &str = \"{:016x}\";\n",
+        entries, manifest_hash
+    );
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR not set");
+    fs::write(Path::new(&out_dir).join("command_manifest.rs"), manifest)
+        .expect("Failed to write the command manifest");
+}
+
+/// Collect `(name, signature)` of every `#[tauri::command]` function under `dir`
+///
+/// The signature runs from `fn` to the opening brace, whitespace normalized.
+fn collect_command_signatures(dir: &Path, signatures: &mut Vec<(String, String)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_command_signatures(&path, signatures);
+            continue;
+        }
+        if path.extension().is_none_or(|extension| extension != "rs") {
+            continue;
+        }
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        let mut rest = source.as_str();
+        while let Some(attribute) = rest.find("#[tauri::command]") {
+            rest = &rest[attribute..];
+            let Some(start) = rest.find("fn ") else {
+                break;
+            };
+            let end = rest[start..]
+                .find('{')
+                .map_or(rest.len(), |end| start + end);
+            let signature = rest[start..end]
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            let name = signature["fn ".len()..]
+                .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            signatures.push((name, signature));
+            rest = &rest[end..];
+        }
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// 64-bit FNV-1a hash, stable across builds and platforms
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
 }
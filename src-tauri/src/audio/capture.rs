@@ -1,17 +1,105 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::{Device, StreamConfig, SupportedStreamConfig};
+use cpal::{Device, StreamConfig, SupportedStreamConfig, SupportedStreamConfigRange};
 use hound::{WavSpec, WavWriter};
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter};
 use tempfile::NamedTempFile;
 use thiserror::Error;
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::{mpsc, oneshot, watch, Mutex};
+
+use crate::audio::encoder::{EncoderOptions, OggVorbisEncoder};
+use crate::audio::interview::find_loopback_device;
+use crate::audio::loudness::normalize_wav;
+use crate::audio::stream_encoder::StreamingEncoder;
+use crate::services::event_targets::emit_to_main;
+use crate::services::media_control;
+use crate::services::notifier::{Notifier, TauriNotifierService};
+use crate::services::size_guard::{
+    SizeForecast, SizeForecaster, EARLY_WARNING_PERCENTS, WHISPER_UPLOAD_LIMIT_BYTES,
+};
 
 /// Type alias for RMS callback to reduce complexity
 type RmsCallback = Arc<Mutex<Option<Box<dyn Fn(f32) + Send + Sync>>>>;
 
+/// Handles each block of a recording, already in the recording format
+type BlockProcessor = Arc<std::sync::Mutex<dyn FnMut(Vec<f32>) + Send>>;
+
+/// Sender told the generation of a recording stream whose device was lost
+type DeviceLostSender = (mpsc::UnboundedSender<u32>, u32);
+
+/// Sample rate of the recorded WAV files
+pub const CAPTURE_SAMPLE_RATE: u32 = 48000;
+
+/// Rates tried, in order, when the device does not support the requested one
+const FALLBACK_SAMPLE_RATES: [u32; 5] = [48000, 44100, 32000, 22050, 16000];
+
+/// Seconds of recent audio kept in memory for live processing (e.g. captions)
+pub const RECENT_AUDIO_SECONDS: u32 = 30;
+
+/// Largest gain (boost or cut) applied to the input, in dB
+pub const MAX_INPUT_GAIN_DB: f32 = 30.0;
+
+/// Level the automatic gain control aims for (RMS, about -20 dBFS)
+const AGC_TARGET_RMS: f32 = 0.1;
+
+/// Blocks quieter than this are treated as silence and do not move the AGC
+const AGC_NOISE_FLOOR_RMS: f32 = 0.002;
+
+/// Largest AGC gain change per callback block, in dB (keeps the gain from pumping)
+const AGC_MAX_STEP_DB: f32 = 0.5;
+
+/// Sample magnitude treated as clipped (full scale after gain)
+const CLIPPING_LEVEL: f32 = 0.99;
+
+/// Share of clipped samples in a window that counts as clipping
+const CLIPPING_RATIO: f32 = 0.01;
+
+/// Window level (RMS after gain, about -9 dBFS) above which input is too hot,
+/// with peaks about to clip
+const OVER_LEVEL_RMS: f32 = 0.35;
+
+/// Consecutive warning windows (of half a second) before a warning is reported
+const INPUT_WARNING_SUSTAINED_WINDOWS: u32 = 2;
+
+/// Seconds of audio between two input warnings
+const INPUT_WARNING_INTERVAL_SECONDS: u64 = 10;
+
+/// Level (RMS after gain) above which a window counts as speech
+const VAD_SPEECH_RMS: f32 = 0.02;
+
+/// Length of the voice activity detection windows, in milliseconds
+const VAD_WINDOW_MS: u64 = 100;
+
+/// Length of audio covered by one `waveform` event, in milliseconds
+pub const WAVEFORM_WINDOW_MS: u64 = 100;
+
+/// Points per `waveform` event
+pub const WAVEFORM_POINTS: usize = 50;
+
+/// `rms` events per second unless the subscriber asks for another rate
+pub const DEFAULT_RMS_RATE_HZ: u32 = 30;
+
+/// Highest rate of `rms` events (one per display frame)
+pub const MAX_RMS_RATE_HZ: u32 = 60;
+
+/// Shortest pre-roll (audio kept from before the recording starts), in seconds
+pub const MIN_PRE_ROLL_SECONDS: f32 = 0.5;
+
+/// Longest pre-roll, in seconds
+pub const MAX_PRE_ROLL_SECONDS: f32 = 2.0;
+
+/// Loopback audio queued for mixing, in milliseconds (older audio is dropped)
+const LOOPBACK_QUEUE_MS: u64 = 500;
+
+/// Time between two `rms` events at `rate_hz` (clamped to 1-`MAX_RMS_RATE_HZ`)
+pub fn rms_emit_interval(rate_hz: u32) -> std::time::Duration {
+    std::time::Duration::from_secs(1) / rate_hz.clamp(1, MAX_RMS_RATE_HZ)
+}
+
 /// Error types for audio capture operations
 #[derive(Error, Debug)]
 pub enum AudioCaptureError {
@@ -32,108 +120,1455 @@ pub enum AudioCaptureError {
 /// Result type for audio capture operations
 pub type AudioCaptureResult<T> = Result<T, AudioCaptureError>;
 
-/// Trait defining audio capture operations
-#[async_trait::async_trait]
-pub trait AudioCapture: Send + Sync {
-    /// Start capturing audio to a temporary WAV file
-    async fn start_capture(&self) -> AudioCaptureResult<PathBuf>;
+/// Trait defining audio capture operations
+#[async_trait::async_trait]
+pub trait AudioCapture: Send + Sync {
+    /// Start capturing audio to a temporary WAV file
+    async fn start_capture(&self) -> AudioCaptureResult<PathBuf>;
+
+    /// Stop audio capture and return the path to the recorded file
+    async fn stop_capture(&self) -> AudioCaptureResult<PathBuf>;
+
+    /// Subscribe to RMS level updates (callback will be called at ≥10 Hz)
+    #[allow(dead_code)]
+    fn subscribe_rms(&self, callback: Box<dyn Fn(f32) + Send + Sync>);
+
+    /// Check if currently recording
+    fn is_recording(&self) -> bool;
+}
+
+/// Input gain applied in the capture callback, adjustable while recording
+///
+/// In manual mode the configured gain is applied as is. In AGC mode the gain
+/// follows the input level towards a fixed target, within ±`MAX_INPUT_GAIN_DB`.
+#[derive(Debug, Default)]
+pub struct GainControl {
+    /// Manual gain in dB (f32 bits)
+    gain_db: AtomicU32,
+    auto_gain: AtomicBool,
+    /// Gain applied to the last block in dB (f32 bits)
+    applied_gain_db: AtomicU32,
+}
+
+impl GainControl {
+    /// Set the manual gain in dB (clamped to ±`MAX_INPUT_GAIN_DB`)
+    pub fn set_gain_db(&self, gain_db: f32) {
+        let gain_db = if gain_db.is_finite() {
+            gain_db.clamp(-MAX_INPUT_GAIN_DB, MAX_INPUT_GAIN_DB)
+        } else {
+            0.0
+        };
+        self.gain_db.store(gain_db.to_bits(), Ordering::Relaxed);
+        if !self.auto_gain() {
+            self.applied_gain_db
+                .store(gain_db.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    /// Manual gain in dB
+    pub fn gain_db(&self) -> f32 {
+        f32::from_bits(self.gain_db.load(Ordering::Relaxed))
+    }
+
+    /// Enable or disable automatic gain control
+    ///
+    /// AGC starts from the manual gain so switching modes does not jump.
+    pub fn set_auto_gain(&self, enabled: bool) {
+        self.auto_gain.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            self.applied_gain_db
+                .store(self.gain_db().to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    /// Whether automatic gain control is enabled
+    pub fn auto_gain(&self) -> bool {
+        self.auto_gain.load(Ordering::Relaxed)
+    }
+
+    /// Gain applied to the most recent audio block in dB
+    pub fn applied_gain_db(&self) -> f32 {
+        f32::from_bits(self.applied_gain_db.load(Ordering::Relaxed))
+    }
+
+    /// Apply the gain to a block of samples in place
+    pub fn process(&self, samples: &mut [f32]) {
+        let gain_db = if self.auto_gain() {
+            let current = self.applied_gain_db();
+            let input_rms = LiveAudioCapture::calculate_rms(samples);
+            let next = if input_rms > AGC_NOISE_FLOOR_RMS {
+                let wanted = 20.0 * (AGC_TARGET_RMS / input_rms).log10();
+                current + (wanted - current).clamp(-AGC_MAX_STEP_DB, AGC_MAX_STEP_DB)
+            } else {
+                current
+            };
+            next.clamp(-MAX_INPUT_GAIN_DB, MAX_INPUT_GAIN_DB)
+        } else {
+            self.gain_db()
+        };
+        self.applied_gain_db
+            .store(gain_db.to_bits(), Ordering::Relaxed);
+
+        if gain_db != 0.0 {
+            let factor = 10f32.powf(gain_db / 20.0);
+            for sample in samples.iter_mut() {
+                *sample *= factor;
+            }
+        }
+    }
+}
+
+/// Input level problem reported by `recording-input-warning`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputWarningKind {
+    /// Level so high that peaks are about to clip
+    OverLevel,
+    /// Samples at full scale: the audio is distorted
+    Clipping,
+}
+
+/// Sustained input level problem
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct InputWarning {
+    pub kind: InputWarningKind,
+    /// Percentage of clipped samples in the last half second
+    pub clipped_percent: f32,
+    /// Level of the last half second (RMS, in dBFS)
+    pub rms_dbfs: f32,
+}
+
+/// Detects sustained clipping and over-level input in the capture callback
+///
+/// Audio is analysed in half-second windows; a warning is reported when
+/// consecutive windows clip or are over-level, at most once every
+/// `INPUT_WARNING_INTERVAL_SECONDS` of audio (clipping is still reported
+/// after an over-level warning).
+#[derive(Debug)]
+pub struct InputLevelDetector {
+    window_samples: u64,
+    samples_in_window: u64,
+    clipped_in_window: u64,
+    energy_in_window: f32,
+    warning_windows: u32,
+    samples_seen: u64,
+    last_report: Option<(u64, InputWarningKind)>,
+    report_interval_samples: u64,
+}
+
+impl InputLevelDetector {
+    /// Create a detector for interleaved samples at the given rate
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        let samples_per_second = sample_rate.max(1) as u64 * channels.max(1) as u64;
+        Self {
+            window_samples: samples_per_second / 2,
+            samples_in_window: 0,
+            clipped_in_window: 0,
+            energy_in_window: 0.0,
+            warning_windows: 0,
+            samples_seen: 0,
+            last_report: None,
+            report_interval_samples: samples_per_second * INPUT_WARNING_INTERVAL_SECONDS,
+        }
+    }
+
+    /// Feed a block of samples; returns a warning when one should be reported
+    pub fn observe(&mut self, samples: &[f32]) -> Option<InputWarning> {
+        let mut report = None;
+        for &sample in samples {
+            self.samples_seen += 1;
+            self.samples_in_window += 1;
+            self.energy_in_window += sample * sample;
+            if sample.abs() >= CLIPPING_LEVEL {
+                self.clipped_in_window += 1;
+            }
+            if self.samples_in_window < self.window_samples {
+                continue;
+            }
+
+            let ratio = self.clipped_in_window as f32 / self.samples_in_window as f32;
+            let rms = (self.energy_in_window / self.samples_in_window as f32).sqrt();
+            self.samples_in_window = 0;
+            self.clipped_in_window = 0;
+            self.energy_in_window = 0.0;
+            let kind = if ratio >= CLIPPING_RATIO {
+                InputWarningKind::Clipping
+            } else if rms >= OVER_LEVEL_RMS {
+                InputWarningKind::OverLevel
+            } else {
+                self.warning_windows = 0;
+                continue;
+            };
+
+            self.warning_windows += 1;
+            let throttled = self.last_report.is_some_and(|(last, last_kind)| {
+                last_kind >= kind && self.samples_seen - last < self.report_interval_samples
+            });
+            if self.warning_windows >= INPUT_WARNING_SUSTAINED_WINDOWS && !throttled {
+                self.last_report = Some((self.samples_seen, kind));
+                report = Some(InputWarning {
+                    kind,
+                    clipped_percent: ratio * 100.0,
+                    rms_dbfs: 20.0 * rms.max(f32::EPSILON).log10(),
+                });
+            }
+        }
+        report
+    }
+}
+
+/// Voice activity detection for the silence auto-stop
+///
+/// Audio is analysed in windows of `VAD_WINDOW_MS`; a window louder than
+/// `VAD_SPEECH_RMS` is speech. Once speech has been heard, the detector fires
+/// (once) after the configured seconds of uninterrupted silence, so a
+/// recording is never stopped before the user starts talking.
+#[derive(Debug)]
+pub struct SilenceDetector {
+    window_samples: u64,
+    samples_in_window: u64,
+    energy_in_window: f32,
+    silent_samples: u64,
+    silence_limit_samples: u64,
+    heard_speech: bool,
+    fired: bool,
+}
+
+impl SilenceDetector {
+    /// Create a detector for interleaved samples at the given rate
+    pub fn new(sample_rate: u32, channels: u16, silence_seconds: u32) -> Self {
+        let samples_per_second = sample_rate.max(1) as u64 * channels.max(1) as u64;
+        Self {
+            window_samples: (samples_per_second * VAD_WINDOW_MS / 1000).max(1),
+            samples_in_window: 0,
+            energy_in_window: 0.0,
+            silent_samples: 0,
+            silence_limit_samples: samples_per_second * silence_seconds as u64,
+            heard_speech: false,
+            fired: false,
+        }
+    }
+
+    /// Feed a block of samples; returns true once the silence limit is reached
+    pub fn observe(&mut self, samples: &[f32]) -> bool {
+        if self.fired {
+            return false;
+        }
+        for &sample in samples {
+            self.samples_in_window += 1;
+            self.energy_in_window += sample * sample;
+            if self.samples_in_window < self.window_samples {
+                continue;
+            }
+
+            let rms = (self.energy_in_window / self.samples_in_window as f32).sqrt();
+            if rms >= VAD_SPEECH_RMS {
+                self.heard_speech = true;
+                self.silent_samples = 0;
+            } else if self.heard_speech {
+                self.silent_samples += self.samples_in_window;
+            }
+            self.samples_in_window = 0;
+            self.energy_in_window = 0.0;
+
+            if self.heard_speech && self.silent_samples >= self.silence_limit_samples {
+                self.fired = true;
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Downsamples the input into waveform frames for the UI
+///
+/// Channels are mixed down to mono; each point is the sample with the largest
+/// magnitude (sign kept) of its slice of the window, so short peaks stay visible.
+#[derive(Debug)]
+pub struct WaveformDownsampler {
+    channels: usize,
+    frames_per_point: u64,
+    frames_in_point: u64,
+    peak: f32,
+    points: Vec<f32>,
+}
+
+impl WaveformDownsampler {
+    /// Create a downsampler for interleaved samples at the given rate
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        let frames_per_window = sample_rate.max(1) as u64 * WAVEFORM_WINDOW_MS / 1000;
+        Self {
+            channels: channels.max(1) as usize,
+            frames_per_point: (frames_per_window / WAVEFORM_POINTS as u64).max(1),
+            frames_in_point: 0,
+            peak: 0.0,
+            points: Vec::with_capacity(WAVEFORM_POINTS),
+        }
+    }
+
+    /// Feed a block of samples; returns the windows completed by it
+    pub fn observe(&mut self, samples: &[f32]) -> Vec<Vec<f32>> {
+        let mut frames = Vec::new();
+        for frame in samples.chunks(self.channels) {
+            let sample = frame.iter().sum::<f32>() / frame.len() as f32;
+            if sample.abs() > self.peak.abs() {
+                self.peak = sample;
+            }
+            self.frames_in_point += 1;
+            if self.frames_in_point < self.frames_per_point {
+                continue;
+            }
+
+            self.points.push(self.peak.clamp(-1.0, 1.0));
+            self.frames_in_point = 0;
+            self.peak = 0.0;
+            if self.points.len() == WAVEFORM_POINTS {
+                frames.push(std::mem::replace(
+                    &mut self.points,
+                    Vec::with_capacity(WAVEFORM_POINTS),
+                ));
+            }
+        }
+        frames
+    }
+}
+
+/// Rolling buffer of the most recent input, prepended to the next recording
+///
+/// Holds raw interleaved samples in the format of the stream that filled it,
+/// so they can only be used by a recording with the same format.
+#[derive(Debug)]
+pub struct PreRollBuffer {
+    samples: VecDeque<f32>,
+    capacity: usize,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl PreRollBuffer {
+    /// Create a buffer holding `seconds` of interleaved audio at the given rate
+    pub fn new(seconds: f32, sample_rate: u32, channels: u16) -> Self {
+        let capacity = (seconds.max(0.0) * sample_rate as f32) as usize * channels.max(1) as usize;
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+            sample_rate,
+            channels,
+        }
+    }
+
+    /// Append samples, dropping the oldest beyond the capacity
+    pub fn push(&mut self, samples: &[f32]) {
+        self.samples.extend(samples.iter().copied());
+        let overflow = self.samples.len().saturating_sub(self.capacity);
+        self.samples.drain(..overflow);
+    }
+
+    /// Take the buffered samples if they match the given stream format
+    pub fn take_for(&mut self, sample_rate: u32, channels: u16) -> Vec<f32> {
+        let samples: Vec<f32> = self.samples.drain(..).collect();
+        if sample_rate == self.sample_rate && channels == self.channels {
+            samples
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// What a recording captures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureSource {
+    /// The input device (microphone)
+    #[default]
+    Microphone,
+    /// The system output (loopback), e.g. the audio of a meeting
+    System,
+    /// The microphone with the system output mixed in
+    Mixed,
+}
+
+/// Mixes a loopback source (system output) into the microphone stream
+///
+/// The loopback stream runs on its own clock: its audio is downmixed to mono,
+/// resampled to the microphone rate and queued, then added to every channel of
+/// the microphone frames. The queue is bounded, so clock drift drops audio
+/// instead of building up latency.
+#[derive(Debug)]
+pub struct LoopbackMixer {
+    queue: VecDeque<f32>,
+    capacity: usize,
+    source_channels: usize,
+    /// Source frames per output sample
+    step: f64,
+    /// Position of the next output sample in the next source block
+    position: f64,
+}
+
+impl LoopbackMixer {
+    /// Create a mixer from a loopback stream format to the microphone rate
+    pub fn new(source_rate: u32, source_channels: u16, target_rate: u32) -> Self {
+        let capacity = (target_rate as u64 * LOOPBACK_QUEUE_MS / 1000) as usize;
+        Self {
+            queue: VecDeque::with_capacity(capacity),
+            capacity,
+            source_channels: source_channels.max(1) as usize,
+            step: source_rate.max(1) as f64 / target_rate.max(1) as f64,
+            position: 0.0,
+        }
+    }
+
+    /// Queue a block of interleaved loopback audio
+    pub fn push(&mut self, data: &[f32]) {
+        let frames: Vec<f32> = data
+            .chunks(self.source_channels)
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect();
+        while (self.position as usize) < frames.len() {
+            self.queue.push_back(frames[self.position as usize]);
+            self.position += self.step;
+        }
+        self.position -= frames.len() as f64;
+
+        let overflow = self.queue.len().saturating_sub(self.capacity);
+        self.queue.drain(..overflow);
+    }
+
+    /// Add the queued loopback audio to interleaved microphone samples
+    pub fn mix_into(&mut self, samples: &mut [f32], channels: u16) {
+        for frame in samples.chunks_mut(channels.max(1) as usize) {
+            let Some(loopback) = self.queue.pop_front() else {
+                break;
+            };
+            for sample in frame {
+                *sample = (*sample + loopback).clamp(-1.0, 1.0);
+            }
+        }
+    }
+}
+
+/// Converts the device stream to the recording format (mono at
+/// [`CAPTURE_SAMPLE_RATE`])
+///
+/// Frames are downmixed to mono, then resampled with linear interpolation. The
+/// last frame of each block is kept so interpolation continues across blocks.
+#[derive(Debug)]
+pub struct FormatConverter {
+    source_channels: usize,
+    /// Source frames per output sample
+    step: f64,
+    /// Position of the next output sample in the next block (-1 = last frame
+    /// of the previous block)
+    position: f64,
+    previous: f32,
+}
+
+impl FormatConverter {
+    /// Create a converter from a device stream format to `target_rate` mono
+    pub fn new(source_rate: u32, source_channels: u16, target_rate: u32) -> Self {
+        Self {
+            source_channels: source_channels.max(1) as usize,
+            step: source_rate.max(1) as f64 / target_rate.max(1) as f64,
+            position: 0.0,
+            previous: 0.0,
+        }
+    }
+
+    /// Whether the stream is already in the recording format
+    pub fn is_passthrough(&self) -> bool {
+        self.source_channels == 1 && self.step == 1.0
+    }
+
+    /// Convert a block of interleaved samples
+    pub fn process(&mut self, data: &[f32]) -> Vec<f32> {
+        if self.is_passthrough() {
+            return data.to_vec();
+        }
+
+        let frames: Vec<f32> = data
+            .chunks(self.source_channels)
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect();
+        let Some(&last) = frames.last() else {
+            return Vec::new();
+        };
+        if self.step == 1.0 {
+            return frames;
+        }
+
+        let frame_at = |index: isize| {
+            if index < 0 {
+                self.previous
+            } else {
+                frames[index as usize]
+            }
+        };
+        let mut output = Vec::with_capacity((frames.len() as f64 / self.step) as usize + 1);
+        while self.position < frames.len() as f64 - 1.0 {
+            let base = self.position.floor();
+            let fraction = (self.position - base) as f32;
+            let base = base as isize;
+            output.push(frame_at(base) * (1.0 - fraction) + frame_at(base + 1) * fraction);
+            self.position += self.step;
+        }
+        self.position -= frames.len() as f64;
+        self.previous = last;
+        output
+    }
+}
+
+/// The input device disconnected during a recording (`recording-device-lost`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordingDeviceLost {
+    /// Device that disconnected
+    pub device: String,
+    /// Device the recording goes on with (None = the recording is stalled)
+    pub switched_to: Option<String>,
+    /// Why no other device could take over
+    pub reason: Option<String>,
+}
+
+/// Recording state shared by the streams of one recording
+struct RecordingStreamState {
+    is_recording: Arc<AtomicBool>,
+    is_paused: Arc<AtomicBool>,
+    pre_roll: Arc<std::sync::Mutex<Option<PreRollBuffer>>>,
+}
+
+/// Stream format negotiated with the input device (`get_encoder_info`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CaptureFormat {
+    /// Rate asked for in settings
+    pub requested_sample_rate: u32,
+    /// Rate and channels the device records at
+    pub device_sample_rate: u32,
+    pub device_channels: u16,
+    /// Format of the recorded WAV file
+    pub output_sample_rate: u32,
+    pub output_channels: u16,
+    /// Whether the device audio is resampled to the output rate
+    pub resampled: bool,
+}
+
+impl CaptureFormat {
+    /// Format of a recording from a device stream configuration
+    pub fn new(requested_sample_rate: u32, device_config: &SupportedStreamConfig) -> Self {
+        Self {
+            requested_sample_rate,
+            device_sample_rate: device_config.sample_rate().0,
+            device_channels: device_config.channels(),
+            output_sample_rate: CAPTURE_SAMPLE_RATE,
+            output_channels: 1,
+            resampled: device_config.sample_rate().0 != CAPTURE_SAMPLE_RATE,
+        }
+    }
+}
+
+/// Pick a stream format among the supported `(channels, min_rate, max_rate)`
+/// ranges: the requested rate first, then the fallback rates, mono preferred
+///
+/// Returns the index of the range and the rate to use.
+pub fn pick_stream_format(
+    supported: &[(u16, u32, u32)],
+    requested_rate: u32,
+) -> Option<(usize, u32)> {
+    let supports = |&(_, min_rate, max_rate): &(u16, u32, u32), rate: u32| {
+        min_rate <= rate && rate <= max_rate
+    };
+    std::iter::once(requested_rate)
+        .chain(FALLBACK_SAMPLE_RATES)
+        .find_map(|rate| {
+            supported
+                .iter()
+                .position(|range| range.0 == 1 && supports(range, rate))
+                .or_else(|| supported.iter().position(|range| supports(range, rate)))
+                .map(|index| (index, rate))
+        })
+}
+
+/// Pre-roll length for a setting: 0 (off) or 0.5-2 seconds
+pub fn pre_roll_seconds(setting: f32) -> f32 {
+    if setting > 0.0 {
+        setting.clamp(MIN_PRE_ROLL_SECONDS, MAX_PRE_ROLL_SECONDS)
+    } else {
+        0.0
+    }
+}
+
+/// Audio capture state that can be safely shared across threads
+pub struct AudioCaptureState {
+    pub is_recording: Arc<AtomicBool>,
+    /// Samples are dropped (nothing written) while paused
+    pub is_paused: Arc<AtomicBool>,
+    pub current_file_path: Arc<Mutex<Option<PathBuf>>>,
+    pub rms_callback: RmsCallback,
+    pub stop_sender: Arc<Mutex<Option<mpsc::UnboundedSender<()>>>>,
+    /// Most recent samples of the current recording (bounded ring buffer)
+    pub recent_samples: Arc<std::sync::Mutex<VecDeque<i16>>>,
+    /// Input gain, shared with the stream callback
+    pub gain: Arc<GainControl>,
+    /// Emit `waveform` frames while recording
+    pub waveform_enabled: Arc<AtomicBool>,
+    /// Frontends subscribed to `rms` events (none: nothing is emitted)
+    pub rms_subscribers: Arc<AtomicU32>,
+    /// Most `rms` events per second
+    pub rms_rate_hz: Arc<AtomicU32>,
+    /// Latest input level: the audio callback overwrites it and the publisher
+    /// emits it at most `rms_rate_hz` times per second (levels in between are
+    /// dropped, so a slow frontend never builds up a backlog)
+    pub rms_level: Arc<watch::Sender<f32>>,
+    /// Recording the running RMS publisher belongs to (an older one stops)
+    pub rms_publisher: Arc<AtomicU32>,
+    /// Signalled once the WAV file of the current recording is finalized
+    pub wav_finalized: Arc<Mutex<Option<oneshot::Receiver<()>>>>,
+    /// Milliseconds of audio written to the current recording (pauses excluded)
+    pub recorded_ms: Arc<AtomicU64>,
+    /// Bookmarks of the current recording, in seconds of audio
+    pub bookmarks: Arc<std::sync::Mutex<Vec<f64>>>,
+}
+
+impl Default for AudioCaptureState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioCaptureState {
+    pub fn new() -> Self {
+        Self {
+            is_recording: Arc::new(AtomicBool::new(false)),
+            is_paused: Arc::new(AtomicBool::new(false)),
+            current_file_path: Arc::new(Mutex::new(None)),
+            rms_callback: Arc::new(Mutex::new(None)),
+            stop_sender: Arc::new(Mutex::new(None)),
+            recent_samples: Arc::new(std::sync::Mutex::new(VecDeque::new())),
+            gain: Arc::new(GainControl::default()),
+            waveform_enabled: Arc::new(AtomicBool::new(false)),
+            rms_subscribers: Arc::new(AtomicU32::new(0)),
+            rms_rate_hz: Arc::new(AtomicU32::new(DEFAULT_RMS_RATE_HZ)),
+            rms_level: Arc::new(watch::Sender::new(0.0)),
+            rms_publisher: Arc::new(AtomicU32::new(0)),
+            wav_finalized: Arc::new(Mutex::new(None)),
+            recorded_ms: Arc::new(AtomicU64::new(0)),
+            bookmarks: Arc::new(std::sync::Mutex::new(Vec::new())),
+        }
+    }
+}
+
+/// Live audio capture implementation using CPAL
+pub struct LiveAudioCapture {
+    device: Device,
+    config: SupportedStreamConfig,
+    app_handle: AppHandle,
+    state: Arc<AudioCaptureState>,
+    /// Input device requested for the next recording (None = system default)
+    input_device: std::sync::Mutex<Option<String>>,
+    /// Source recorded from the next recording on
+    capture_source: std::sync::Mutex<CaptureSource>,
+    /// Loopback device for the system source (None = auto-detect)
+    loopback_device: std::sync::Mutex<Option<String>>,
+    /// Pause the playing media players while recording
+    pause_media: AtomicBool,
+    /// Players paused for the current recording, resumed when it stops
+    paused_players: Arc<std::sync::Mutex<Vec<String>>>,
+    /// Seconds of silence after which the recording stops itself (0 = never)
+    auto_stop_silence_seconds: AtomicU32,
+    /// Recorded seconds after which the recording stops itself (0 = no limit)
+    max_recording_seconds: AtomicU32,
+    /// Pre-roll length in milliseconds (0 = off)
+    pre_roll_ms: AtomicU32,
+    /// Audio heard while idle, filled by the pre-roll monitor stream
+    pre_roll: Arc<std::sync::Mutex<Option<PreRollBuffer>>>,
+    /// Keeps the pre-roll monitor stream alive (dropping it stops the stream)
+    pre_roll_stop: std::sync::Mutex<Option<mpsc::UnboundedSender<()>>>,
+    /// Loudness (LUFS) recordings are normalized to when stopped (None = off)
+    loudness_target: std::sync::Mutex<Option<f32>>,
+    /// Options of the encoder fed while recording (None = encode after the stop)
+    stream_encoding: std::sync::Mutex<Option<EncoderOptions>>,
+    /// Sample rate asked of the input device
+    requested_sample_rate: AtomicU32,
+    /// Format of the last recording (of the default device before the first)
+    capture_format: Arc<std::sync::Mutex<CaptureFormat>>,
+}
+
+impl LiveAudioCapture {
+    /// Create a new LiveAudioCapture instance
+    pub fn new(app_handle: AppHandle) -> AudioCaptureResult<Self> {
+        let host = cpal::default_host();
+
+        let device = host.default_input_device().ok_or_else(|| {
+            AudioCaptureError::AudioDeviceUnavailable(
+                "No default input device available".to_string(),
+            )
+        })?;
+
+        let supported_config = Self::negotiate_config(&device)?;
+        let capture_format = CaptureFormat::new(CAPTURE_SAMPLE_RATE, &supported_config);
+
+        Ok(Self {
+            device,
+            config: supported_config,
+            app_handle,
+            state: Arc::new(AudioCaptureState::new()),
+            input_device: std::sync::Mutex::new(None),
+            capture_source: std::sync::Mutex::new(CaptureSource::default()),
+            loopback_device: std::sync::Mutex::new(None),
+            pause_media: AtomicBool::new(false),
+            paused_players: Arc::new(std::sync::Mutex::new(Vec::new())),
+            auto_stop_silence_seconds: AtomicU32::new(0),
+            max_recording_seconds: AtomicU32::new(0),
+            pre_roll_ms: AtomicU32::new(0),
+            pre_roll: Arc::new(std::sync::Mutex::new(None)),
+            pre_roll_stop: std::sync::Mutex::new(None),
+            loudness_target: std::sync::Mutex::new(None),
+            stream_encoding: std::sync::Mutex::new(None),
+            requested_sample_rate: AtomicU32::new(CAPTURE_SAMPLE_RATE),
+            capture_format: Arc::new(std::sync::Mutex::new(capture_format)),
+        })
+    }
+
+    /// Names of the available input devices
+    pub fn list_input_devices() -> Vec<String> {
+        cpal::default_host()
+            .input_devices()
+            .map(|devices| devices.filter_map(|device| device.name().ok()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Pin the input device used from the next recording on (None = system default)
+    pub fn set_input_device(&self, device_name: Option<String>) {
+        if let Ok(mut input_device) = self.input_device.lock() {
+            *input_device = device_name;
+        }
+        if !self.is_recording() {
+            self.restart_pre_roll_monitor();
+        }
+    }
+
+    /// Input device requested for the next recording
+    pub fn input_device(&self) -> Option<String> {
+        self.input_device.lock().ok().and_then(|name| name.clone())
+    }
+
+    /// Record the microphone, the system output (loopback) or both, from the
+    /// next recording on
+    ///
+    /// `loopback_device` names the device recording the system output (None =
+    /// auto-detect, see `interview.loopback_device`).
+    pub fn set_capture_source(&self, source: CaptureSource, loopback_device: Option<String>) {
+        if let Ok(mut capture_source) = self.capture_source.lock() {
+            *capture_source = source;
+        }
+        if let Ok(mut device) = self.loopback_device.lock() {
+            *device = loopback_device;
+        }
+        if !self.is_recording() {
+            self.restart_pre_roll_monitor();
+        }
+    }
+
+    /// Source recorded from the next recording on
+    pub fn capture_source(&self) -> CaptureSource {
+        self.capture_source
+            .lock()
+            .map(|source| *source)
+            .unwrap_or_default()
+    }
+
+    /// Pause the playing media players while recording (resumed on stop)
+    pub fn set_pause_media(&self, enabled: bool) {
+        self.pause_media.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Stop recordings after this many seconds of silence, from the next
+    /// recording on (0 = never)
+    pub fn set_auto_stop_silence_seconds(&self, seconds: u32) {
+        self.auto_stop_silence_seconds
+            .store(seconds, Ordering::Relaxed);
+    }
+
+    /// Stop recordings once this many seconds were recorded (pauses excluded),
+    /// from the next recording on (0 = no limit)
+    pub fn set_max_recording_seconds(&self, seconds: u32) {
+        self.max_recording_seconds.store(seconds, Ordering::Relaxed);
+    }
+
+    /// Keep the last `seconds` (0.5-2, 0 = off) of input while idle and
+    /// prepend them to the next recording, so the first words are not cut off
+    ///
+    /// The microphone stays open while the pre-roll is on.
+    pub fn set_pre_roll_seconds(&self, seconds: f32) {
+        let seconds = pre_roll_seconds(seconds);
+        self.pre_roll_ms
+            .store((seconds * 1000.0) as u32, Ordering::Relaxed);
+        if !self.is_recording() {
+            self.restart_pre_roll_monitor();
+        }
+    }
+
+    /// Release the input device while idle: stops the stream filling the
+    /// pre-roll (the next recording opens the device again)
+    pub fn release_device(&self) {
+        // Dropping the sender stops the stream
+        if let Ok(mut stop) = self.pre_roll_stop.lock() {
+            stop.take();
+        }
+        if let Ok(mut pre_roll) = self.pre_roll.lock() {
+            *pre_roll = None;
+        }
+    }
+
+    /// (Re)start the idle stream that fills the pre-roll, or stop it when off
+    fn restart_pre_roll_monitor(&self) {
+        // Dropping the previous sender stops the previous stream
+        if let Ok(mut stop) = self.pre_roll_stop.lock() {
+            stop.take();
+        }
+        let seconds = self.pre_roll_ms.load(Ordering::Relaxed) as f32 / 1000.0;
+        if seconds <= 0.0 {
+            if let Ok(mut pre_roll) = self.pre_roll.lock() {
+                *pre_roll = None;
+            }
+            return;
+        }
+
+        let (device, device_config) = self.resolve_device();
+        let config = StreamConfig {
+            channels: device_config.channels(),
+            sample_rate: device_config.sample_rate(),
+            buffer_size: cpal::BufferSize::Default,
+        };
+        if let Ok(mut pre_roll) = self.pre_roll.lock() {
+            *pre_roll = Some(PreRollBuffer::new(
+                seconds,
+                config.sample_rate.0,
+                config.channels,
+            ));
+        }
+
+        let (stop_tx, mut stop_rx) = mpsc::unbounded_channel::<()>();
+        if let Ok(mut stop) = self.pre_roll_stop.lock() {
+            *stop = Some(stop_tx);
+        }
+        let pre_roll = Arc::clone(&self.pre_roll);
+
+        tokio::task::spawn_blocking(move || {
+            let stream = device.build_input_stream(
+                &config,
+                move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                    if let Ok(mut pre_roll) = pre_roll.try_lock() {
+                        if let Some(pre_roll) = pre_roll.as_mut() {
+                            pre_roll.push(data);
+                        }
+                    }
+                },
+                |err| {
+                    eprintln!("Pre-roll stream error: {}", err);
+                },
+                None,
+            );
+
+            match stream.map_err(|e| e.to_string()).and_then(|stream| {
+                stream.play().map_err(|e| e.to_string())?;
+                Ok(stream)
+            }) {
+                Ok(stream) => {
+                    // Runs until the sender is used or dropped
+                    let rt = tokio::runtime::Runtime::new().unwrap();
+                    rt.block_on(async {
+                        stop_rx.recv().await;
+                    });
+                    drop(stream);
+                }
+                Err(e) => eprintln!("Failed to start the pre-roll stream: {}", e),
+            }
+        });
+    }
+
+    /// Ask the input device for `sample_rate` from the next recording on
+    ///
+    /// Devices that do not support it fall back to another rate; the audio is
+    /// resampled to the recording rate either way.
+    pub fn set_requested_sample_rate(&self, sample_rate: u32) {
+        self.requested_sample_rate
+            .store(sample_rate, Ordering::Relaxed);
+        if self.is_recording() {
+            return;
+        }
+        match Self::negotiate_config_for(&self.device, sample_rate) {
+            Ok(config) => self.set_capture_format(CaptureFormat::new(sample_rate, &config)),
+            Err(e) => eprintln!("⚠️ [AUDIO] Failed to negotiate {} Hz: {}", sample_rate, e),
+        }
+    }
+
+    /// Stream format of the last recording (of the default device before the
+    /// first recording)
+    pub fn capture_format(&self) -> CaptureFormat {
+        self.capture_format
+            .lock()
+            .map(|format| *format)
+            .unwrap_or_else(|_| CaptureFormat::new(CAPTURE_SAMPLE_RATE, &self.config))
+    }
+
+    fn set_capture_format(&self, format: CaptureFormat) {
+        if let Ok(mut current) = self.capture_format.lock() {
+            *current = format;
+        }
+    }
+
+    /// Normalize recordings to `target_lufs` when they stop (None = off)
+    pub fn set_loudness_target(&self, target_lufs: Option<f32>) {
+        if let Ok(mut loudness_target) = self.loudness_target.lock() {
+            *loudness_target = target_lufs;
+        }
+    }
+
+    /// Encode recordings with `options` while they are captured, so they are
+    /// ready to upload when they stop (None = encode after the stop)
+    pub fn set_stream_encoding(&self, options: Option<EncoderOptions>) {
+        if let Ok(mut stream_encoding) = self.stream_encoding.lock() {
+            *stream_encoding = options;
+        }
+    }
+
+    /// Encoder fed with the recording written to `wav_path`, unless its
+    /// options need the whole file or the file is leveled after the stop
+    fn start_stream_encoder(&self, wav_path: &Path) -> Option<StreamingEncoder> {
+        let options = self
+            .stream_encoding
+            .lock()
+            .ok()
+            .and_then(|options| *options)?;
+        if self
+            .loudness_target
+            .lock()
+            .ok()
+            .and_then(|target| *target)
+            .is_some()
+        {
+            return None;
+        }
+        let encoder = OggVorbisEncoder::new().with_options(options);
+        StreamingEncoder::start(&encoder, wav_path, CAPTURE_SAMPLE_RATE)
+    }
+
+    /// Forecast of the encoded size of a mono recording at `sample_rate`,
+    /// with the encoder options of the transcription step
+    fn size_forecaster(&self, sample_rate: u32) -> SizeForecaster {
+        let options = self
+            .stream_encoding
+            .lock()
+            .ok()
+            .and_then(|options| *options)
+            .unwrap_or_default();
+        let encoder = OggVorbisEncoder::new().with_options(options);
+        SizeForecaster::new(
+            encoder.estimate_file_size(1.0, 1, sample_rate) as f64,
+            WHISPER_UPLOAD_LIMIT_BYTES,
+        )
+    }
+
+    /// Emit downsampled `waveform` frames while recording (in addition to `rms`)
+    pub fn set_waveform_enabled(&self, enabled: bool) {
+        self.state
+            .waveform_enabled
+            .store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether `waveform` frames are emitted
+    pub fn is_waveform_enabled(&self) -> bool {
+        self.state.waveform_enabled.load(Ordering::Relaxed)
+    }
+
+    /// Add a subscriber to `rms` events, emitted at most `rate_hz` times per
+    /// second (the latest subscription sets the rate)
+    pub fn subscribe_rms_events(&self, rate_hz: u32) {
+        self.state
+            .rms_rate_hz
+            .store(rate_hz.clamp(1, MAX_RMS_RATE_HZ), Ordering::Relaxed);
+        self.state.rms_subscribers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Remove a subscriber to `rms` events; returns the subscribers left
+    pub fn unsubscribe_rms_events(&self) -> u32 {
+        let previous = self
+            .state
+            .rms_subscribers
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                Some(count.saturating_sub(1))
+            })
+            .unwrap_or_default();
+        previous.saturating_sub(1)
+    }
+
+    /// Emit the latest input level as `rms` events while recording, at the
+    /// subscribed rate and only to subscribers
+    fn spawn_rms_publisher(&self) {
+        let is_recording = Arc::clone(&self.state.is_recording);
+        let subscribers = Arc::clone(&self.state.rms_subscribers);
+        let rate_hz = Arc::clone(&self.state.rms_rate_hz);
+        let mut level = self.state.rms_level.subscribe();
+        let app_handle = self.app_handle.clone();
+        let publisher = Arc::clone(&self.state.rms_publisher);
+        let generation = publisher.fetch_add(1, Ordering::Relaxed).wrapping_add(1);
+        tokio::spawn(async move {
+            level.mark_unchanged();
+            while is_recording.load(Ordering::Relaxed)
+                && publisher.load(Ordering::Relaxed) == generation
+            {
+                tokio::time::sleep(rms_emit_interval(rate_hz.load(Ordering::Relaxed))).await;
+                if subscribers.load(Ordering::Relaxed) == 0 || !level.has_changed().unwrap_or(false)
+                {
+                    continue;
+                }
+                let rms = *level.borrow_and_update();
+                if let Err(e) = emit_to_main(&app_handle, "rms", rms) {
+                    eprintln!("Failed to emit RMS event: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Pause the recording: audio is dropped until [`Self::resume`], and the
+    /// session still ends up in a single WAV file
+    pub fn pause(&self) -> AudioCaptureResult<()> {
+        if !self.state.is_recording.load(Ordering::Relaxed) {
+            return Err(AudioCaptureError::StreamCreation(
+                "Not currently recording".to_string(),
+            ));
+        }
+        self.state.is_paused.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Resume a paused recording
+    pub fn resume(&self) -> AudioCaptureResult<()> {
+        if !self.state.is_recording.load(Ordering::Relaxed) {
+            return Err(AudioCaptureError::StreamCreation(
+                "Not currently recording".to_string(),
+            ));
+        }
+        self.state.is_paused.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Whether the recording is paused
+    pub fn is_paused(&self) -> bool {
+        self.state.is_paused.load(Ordering::Relaxed)
+    }
+
+    /// Seconds of audio in the current recording, as the transcript times it
+    /// (pre-roll included, pauses excluded)
+    pub fn recorded_seconds(&self) -> f64 {
+        self.state.recorded_ms.load(Ordering::Relaxed) as f64 / 1000.0
+    }
+
+    /// Bookmark the current position of the recording; returns it in seconds
+    pub fn add_bookmark(&self) -> AudioCaptureResult<f64> {
+        if !self.state.is_recording.load(Ordering::Relaxed) {
+            return Err(AudioCaptureError::StreamCreation(
+                "Not currently recording".to_string(),
+            ));
+        }
+        let seconds = self.recorded_seconds();
+        if let Ok(mut bookmarks) = self.state.bookmarks.lock() {
+            bookmarks.push(seconds);
+        }
+        Ok(seconds)
+    }
+
+    /// Take the bookmarks of the last recording
+    pub fn take_bookmarks(&self) -> Vec<f64> {
+        self.state
+            .bookmarks
+            .lock()
+            .map(|mut bookmarks| std::mem::take(&mut *bookmarks))
+            .unwrap_or_default()
+    }
 
-    /// Stop audio capture and return the path to the recorded file
-    async fn stop_capture(&self) -> AudioCaptureResult<PathBuf>;
+    /// Pause the media players in the background so the recording starts at once
+    fn pause_media_players(&self) {
+        if !self.pause_media.load(Ordering::Relaxed) {
+            return;
+        }
+        let is_recording = self.state.is_recording.clone();
+        let paused_players = self.paused_players.clone();
+        tokio::task::spawn_blocking(move || {
+            let players = media_control::pause_playing_media();
+            let Ok(mut paused) = paused_players.lock() else {
+                return;
+            };
+            if is_recording.load(Ordering::Relaxed) {
+                paused.extend(players);
+            } else {
+                // The recording was already stopped: nobody else will resume them
+                media_control::resume_media(&players);
+            }
+        });
+    }
 
-    /// Subscribe to RMS level updates (callback will be called at ≥10 Hz)
-    #[allow(dead_code)]
-    fn subscribe_rms(&self, callback: Box<dyn Fn(f32) + Send + Sync>);
+    /// Resume the media players paused for the recording
+    fn resume_media_players(&self) {
+        let players = self
+            .paused_players
+            .lock()
+            .map(|mut paused| std::mem::take(&mut *paused))
+            .unwrap_or_default();
+        if !players.is_empty() {
+            tokio::task::spawn_blocking(move || media_control::resume_media(&players));
+        }
+    }
 
-    /// Check if currently recording
-    fn is_recording(&self) -> bool;
-}
+    /// Resolve the device to record from, falling back to the default device
+    /// when the requested one is missing (e.g. headset unplugged)
+    ///
+    /// With the system source, this is the loopback device (the microphone
+    /// when no loopback device is available).
+    fn resolve_device(&self) -> (Device, SupportedStreamConfig) {
+        if self.capture_source() == CaptureSource::System {
+            if let Some(loopback) = self.resolve_loopback() {
+                return loopback;
+            }
+        }
 
-/// Audio capture state that can be safely shared across threads
-pub struct AudioCaptureState {
-    pub is_recording: Arc<AtomicBool>,
-    pub current_file_path: Arc<Mutex<Option<PathBuf>>>,
-    pub rms_callback: RmsCallback,
-    pub stop_sender: Arc<Mutex<Option<mpsc::UnboundedSender<()>>>>,
-}
+        let sample_rate = self.requested_sample_rate.load(Ordering::Relaxed);
+        let default_device = || {
+            let config = Self::negotiate_config_for(&self.device, sample_rate)
+                .unwrap_or_else(|_| self.config.clone());
+            (self.device.clone(), config)
+        };
+        let Some(requested) = self.input_device() else {
+            return default_device();
+        };
 
-impl Default for AudioCaptureState {
-    fn default() -> Self {
-        Self::new()
+        let found = cpal::default_host()
+            .input_devices()
+            .ok()
+            .and_then(|mut devices| {
+                devices.find(|device| device.name().map(|n| n == requested).unwrap_or(false))
+            });
+
+        match found.map(|device| {
+            Self::negotiate_config_for(&device, sample_rate).map(|config| (device, config))
+        }) {
+            Some(Ok(resolved)) => {
+                eprintln!("🎙️ [AUDIO] Using input device: {}", requested);
+                resolved
+            }
+            other => {
+                let reason = match other {
+                    Some(Err(e)) => e.to_string(),
+                    _ => "device not found".to_string(),
+                };
+                let fallback = self.device.name().unwrap_or_else(|_| "default".to_string());
+                eprintln!(
+                    "⚠️ [AUDIO] Input device '{}' unavailable ({}), falling back to '{}'",
+                    requested, reason, fallback
+                );
+                let _ = self.app_handle.emit(
+                    "input-device-fallback",
+                    serde_json::json!({
+                        "requested": requested,
+                        "fallback": fallback,
+                        "reason": reason,
+                    }),
+                );
+                default_device()
+            }
+        }
     }
-}
 
-impl AudioCaptureState {
-    pub fn new() -> Self {
-        Self {
-            is_recording: Arc::new(AtomicBool::new(false)),
-            current_file_path: Arc::new(Mutex::new(None)),
-            rms_callback: Arc::new(Mutex::new(None)),
-            stop_sender: Arc::new(Mutex::new(None)),
+    /// Resolve the loopback device, reporting `loopback-unavailable` when
+    /// there is none (the recording then only captures the microphone)
+    fn resolve_loopback(&self) -> Option<(Device, SupportedStreamConfig)> {
+        let requested = self
+            .loopback_device
+            .lock()
+            .ok()
+            .and_then(|name| name.clone());
+        match find_loopback_device(requested.as_deref()) {
+            Ok((device, config)) => {
+                eprintln!(
+                    "🔊 [AUDIO] Recording system audio from: {}",
+                    device.name().unwrap_or_default()
+                );
+                Some((device, config))
+            }
+            Err(e) => {
+                eprintln!(
+                    "⚠️ [AUDIO] System audio unavailable ({}), recording the microphone only",
+                    e
+                );
+                let _ = self.app_handle.emit(
+                    "loopback-unavailable",
+                    serde_json::json!({
+                        "requested": requested,
+                        "reason": e.to_string(),
+                    }),
+                );
+                None
+            }
         }
     }
-}
 
-/// Live audio capture implementation using CPAL
-pub struct LiveAudioCapture {
-    device: Device,
-    config: SupportedStreamConfig,
-    app_handle: AppHandle,
-    state: Arc<AudioCaptureState>,
-}
+    /// Pick a stream configuration for the device, preferring 48kHz mono
+    pub(crate) fn negotiate_config(device: &Device) -> AudioCaptureResult<SupportedStreamConfig> {
+        Self::negotiate_config_for(device, CAPTURE_SAMPLE_RATE)
+    }
 
-impl LiveAudioCapture {
-    /// Create a new LiveAudioCapture instance
-    pub fn new(app_handle: AppHandle) -> AudioCaptureResult<Self> {
-        let host = cpal::default_host();
+    /// Pick a stream configuration for the device at `requested_rate`, falling
+    /// back to another supported rate (see [`pick_stream_format`]) and then to
+    /// the device default
+    pub(crate) fn negotiate_config_for(
+        device: &Device,
+        requested_rate: u32,
+    ) -> AudioCaptureResult<SupportedStreamConfig> {
+        let ranges: Vec<SupportedStreamConfigRange> = device
+            .supported_input_configs()
+            .map(|configs| configs.collect())
+            .unwrap_or_default();
+        let formats: Vec<(u16, u32, u32)> = ranges
+            .iter()
+            .map(|range| {
+                (
+                    range.channels(),
+                    range.min_sample_rate().0,
+                    range.max_sample_rate().0,
+                )
+            })
+            .collect();
 
-        let device = host.default_input_device().ok_or_else(|| {
-            AudioCaptureError::AudioDeviceUnavailable(
-                "No default input device available".to_string(),
-            )
-        })?;
+        if let Some((index, rate)) = pick_stream_format(&formats, requested_rate) {
+            if rate != requested_rate {
+                eprintln!(
+                    "⚠️ [AUDIO] {} Hz not supported by the device, recording at {} Hz",
+                    requested_rate, rate
+                );
+            }
+            return Ok(ranges[index].with_sample_rate(cpal::SampleRate(rate)));
+        }
 
-        let config = device.default_input_config().map_err(|e| {
+        // Some backends do not list their formats: use the device default
+        device.default_input_config().map_err(|e| {
             AudioCaptureError::StreamConfig(format!("Failed to get default config: {}", e))
-        })?;
+        })
+    }
+
+    /// Build and start the recording stream of a device
+    ///
+    /// Its audio is converted to the recording format and handed to
+    /// `process`. On the first stream of a recording, `pre_roll_stop` hands
+    /// over from the pre-roll monitor. A lost device is reported on
+    /// `device_lost`.
+    fn build_recording_stream(
+        device: &Device,
+        device_config: &SupportedStreamConfig,
+        state: &RecordingStreamState,
+        mut pre_roll_stop: Option<mpsc::UnboundedSender<()>>,
+        process: BlockProcessor,
+        device_lost: DeviceLostSender,
+    ) -> Result<cpal::Stream, String> {
+        let config = StreamConfig {
+            channels: device_config.channels(),
+            sample_rate: device_config.sample_rate(),
+            buffer_size: cpal::BufferSize::Default,
+        };
+        let (stream_rate, stream_channels) = (config.sample_rate.0, config.channels);
+        let mut converter = FormatConverter::new(stream_rate, stream_channels, CAPTURE_SAMPLE_RATE);
+        let is_recording = Arc::clone(&state.is_recording);
+        let is_paused = Arc::clone(&state.is_paused);
+        let pre_roll = Arc::clone(&state.pre_roll);
+        let (lost_tx, generation) = device_lost;
 
-        // Ensure we're using 16-bit 48kHz mono as specified
-        let desired_sample_rate = 48000;
-        let desired_channels = 1;
+        let stream = device
+            .build_input_stream(
+                &config,
+                move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                    if !is_recording.load(Ordering::Relaxed) || is_paused.load(Ordering::Relaxed) {
+                        return;
+                    }
 
-        // Check if the device supports our desired configuration
-        let mut supported_config = config;
-        if supported_config.sample_rate().0 != desired_sample_rate
-            || supported_config.channels() != desired_channels
-        {
-            // Try to find a supported configuration close to our requirements
-            let supported_configs = device.supported_input_configs().map_err(|e| {
-                AudioCaptureError::StreamConfig(format!("Failed to get supported configs: {}", e))
-            })?;
+                    // On the first block, hand over from the pre-roll monitor
+                    // and prepend the audio heard just before the start
+                    let data = match pre_roll_stop.take() {
+                        Some(stop) => {
+                            let _ = stop.send(());
+                            let mut samples = pre_roll
+                                .lock()
+                                .ok()
+                                .and_then(|mut pre_roll| {
+                                    pre_roll.as_mut().map(|pre_roll| {
+                                        pre_roll.take_for(stream_rate, stream_channels)
+                                    })
+                                })
+                                .unwrap_or_default();
+                            samples.extend_from_slice(data);
+                            samples
+                        }
+                        None => data.to_vec(),
+                    };
 
-            let mut best_config = None;
-            for config in supported_configs {
-                if config.channels() == desired_channels
-                    && config.min_sample_rate().0 <= desired_sample_rate
-                    && config.max_sample_rate().0 >= desired_sample_rate
-                {
-                    best_config =
-                        Some(config.with_sample_rate(cpal::SampleRate(desired_sample_rate)));
-                    break;
-                }
-            }
+                    let data = converter.process(&data);
+                    if let Ok(mut process) = process.lock() {
+                        (*process)(data);
+                    }
+                },
+                move |err| {
+                    eprintln!("Audio stream error: {}", err);
+                    if matches!(err, cpal::StreamError::DeviceNotAvailable) {
+                        let _ = lost_tx.send(generation);
+                    }
+                },
+                None,
+            )
+            .map_err(|e| e.to_string())?;
+        stream.play().map_err(|e| e.to_string())?;
+        Ok(stream)
+    }
+
+    /// Continue a recording on the default input device after `device_name`
+    /// was lost
+    ///
+    /// Returns the new stream and format (None when no device could take
+    /// over) and the event reporting the loss.
+    fn switch_lost_device(
+        device_name: &mut String,
+        requested_rate: u32,
+        state: &RecordingStreamState,
+        process: BlockProcessor,
+        device_lost: DeviceLostSender,
+    ) -> (Option<(cpal::Stream, CaptureFormat)>, RecordingDeviceLost) {
+        eprintln!("🔌 [AUDIO] Input device '{}' disconnected", device_name);
+        let switched = cpal::default_host()
+            .default_input_device()
+            .ok_or_else(|| "No input device available".to_string())
+            .and_then(|device| {
+                let config = Self::negotiate_config_for(&device, requested_rate)
+                    .map_err(|e| e.to_string())?;
+                let stream = Self::build_recording_stream(
+                    &device,
+                    &config,
+                    state,
+                    None,
+                    process,
+                    device_lost,
+                )?;
+                let name = device.name().unwrap_or_else(|_| "default".to_string());
+                Ok((name, stream, CaptureFormat::new(requested_rate, &config)))
+            });
 
-            if let Some(config) = best_config {
-                supported_config = config;
+        match switched {
+            Ok((name, stream, format)) => {
+                eprintln!("🎙️ [AUDIO] Recording continues on '{}'", name);
+                let lost = std::mem::replace(device_name, name.clone());
+                let event = RecordingDeviceLost {
+                    device: lost,
+                    switched_to: Some(name),
+                    reason: None,
+                };
+                (Some((stream, format)), event)
+            }
+            Err(reason) => {
+                eprintln!("❌ [AUDIO] No device to continue the recording: {}", reason);
+                let event = RecordingDeviceLost {
+                    device: device_name.clone(),
+                    switched_to: None,
+                    reason: Some(reason),
+                };
+                (None, event)
             }
         }
+    }
 
-        Ok(Self {
-            device,
-            config: supported_config,
+    /// Input gain control (takes effect immediately, also while recording)
+    pub fn gain(&self) -> &GainControl {
+        &self.state.gain
+    }
+
+    /// Copy of the last `seconds` of recorded audio (16-bit mono at 48kHz)
+    pub fn recent_samples(&self, seconds: u32) -> Vec<i16> {
+        let wanted = (seconds.min(RECENT_AUDIO_SECONDS) * CAPTURE_SAMPLE_RATE) as usize;
+        match self.state.recent_samples.lock() {
+            Ok(samples) => samples
+                .iter()
+                .skip(samples.len().saturating_sub(wanted))
+                .copied()
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Append samples to the ring buffer, dropping the oldest beyond capacity
+    fn push_recent_samples(buffer: &mut VecDeque<i16>, samples: &[i16]) {
+        let capacity = (RECENT_AUDIO_SECONDS * CAPTURE_SAMPLE_RATE) as usize;
+        buffer.extend(samples.iter().copied());
+        let overflow = buffer.len().saturating_sub(capacity);
+        buffer.drain(..overflow);
+    }
+
+    /// Emit `recording-input-warning` (and `clipping-detected` for clipping)
+    /// and suggest lowering the gain
+    fn report_input_warning(app_handle: &AppHandle, warning: InputWarning, gain_db: f32) {
+        eprintln!(
+            "⚠️ [AUDIO] Input level warning {:?} ({:.1}% of samples clipped, {:.1} dBFS at {:.1} dB gain)",
+            warning.kind, warning.clipped_percent, warning.rms_dbfs, gain_db
+        );
+        let _ = emit_to_main(
             app_handle,
-            state: Arc::new(AudioCaptureState::new()),
-        })
+            "recording-input-warning",
+            serde_json::json!({
+                "kind": warning.kind,
+                "clipped_percent": warning.clipped_percent,
+                "rms_dbfs": warning.rms_dbfs,
+                "gain_db": gain_db,
+            }),
+        );
+        if warning.kind == InputWarningKind::Clipping {
+            let _ = emit_to_main(
+                app_handle,
+                "clipping-detected",
+                serde_json::json!({
+                    "clipped_ratio": warning.clipped_percent / 100.0,
+                    "gain_db": gain_db,
+                }),
+            );
+        }
+
+        let message = match warning.kind {
+            InputWarningKind::Clipping => {
+                "Your microphone is clipping. Lower the input gain for better transcriptions."
+            }
+            InputWarningKind::OverLevel => {
+                "Your input level is very high. Lower the input gain before the audio distorts."
+            }
+        };
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let notifier = TauriNotifierService::new(app_handle);
+            let _ = notifier.warning(message).await;
+        });
+    }
+
+    /// Emit `recording-size-warning` when the predicted upload size crosses an
+    /// early warning share, with a notification at the last one
+    fn report_size_forecast(app_handle: &AppHandle, forecast: SizeForecast) {
+        eprintln!(
+            "📦 [AUDIO] Predicted upload size {:.1}MB, {}% of the {:.0}MB limit",
+            forecast.predicted_bytes as f64 / (1024.0 * 1024.0),
+            forecast.percent,
+            forecast.limit_bytes as f64 / (1024.0 * 1024.0)
+        );
+        let last_warning = EARLY_WARNING_PERCENTS.last() == Some(&forecast.percent);
+        let _ = emit_to_main(app_handle, "recording-size-warning", &forecast);
+        if last_warning {
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let notifier = TauriNotifierService::new(app_handle);
+                let _ = notifier
+                    .warning("This recording is close to the upload limit: longer recordings are transcribed in parts.")
+                    .await;
+            });
+        }
     }
 
     /// Calculate RMS (Root Mean Square) of audio samples
@@ -175,7 +1610,7 @@ impl AudioCapture for LiveAudioCapture {
         // Create WAV writer
         let wav_spec = WavSpec {
             channels: 1,
-            sample_rate: 48000,
+            sample_rate: CAPTURE_SAMPLE_RATE,
             bits_per_sample: 16,
             sample_format: hound::SampleFormat::Int,
         };
@@ -183,15 +1618,32 @@ impl AudioCapture for LiveAudioCapture {
         let writer = WavWriter::create(&temp_path, wav_spec)?;
         let writer = Arc::new(Mutex::new(Some(writer)));
 
+        // Encode while recording, so the upload can start right after the stop
+        let stream_encoder = Arc::new(std::sync::Mutex::new(self.start_stream_encoder(&temp_path)));
+
         // Store the current file path
         *self.state.current_file_path.lock().await = Some(temp_path.clone());
+        if let Ok(mut recent) = self.state.recent_samples.lock() {
+            recent.clear();
+        }
+        self.state.recorded_ms.store(0, Ordering::Relaxed);
+        if let Ok(mut bookmarks) = self.state.bookmarks.lock() {
+            bookmarks.clear();
+        }
 
-        // Create stream configuration
-        let config = StreamConfig {
-            channels: self.config.channels(),
-            sample_rate: self.config.sample_rate(),
-            buffer_size: cpal::BufferSize::Default,
-        };
+        // Switch to the requested input device (if any) at recording start
+        let (device, device_config) = self.resolve_device();
+        let format = CaptureFormat::new(
+            self.requested_sample_rate.load(Ordering::Relaxed),
+            &device_config,
+        );
+        if format.resampled || format.device_channels != 1 {
+            eprintln!(
+                "🎙️ [AUDIO] Converting {} Hz / {} ch to {} Hz mono",
+                format.device_sample_rate, format.device_channels, format.output_sample_rate
+            );
+        }
+        self.set_capture_format(format);
 
         // Create a channel to signal when to stop recording
         let (stop_tx, mut stop_rx) = mpsc::unbounded_channel();
@@ -199,76 +1651,271 @@ impl AudioCapture for LiveAudioCapture {
 
         // Clone necessary data for the stream callback
         let writer_clone = Arc::clone(&writer);
+        let stream_encoder_clone = Arc::clone(&stream_encoder);
         let app_handle = self.app_handle.clone();
         let rms_callback = Arc::clone(&self.state.rms_callback);
-        let is_recording = Arc::clone(&self.state.is_recording);
+        let rms_level = Arc::clone(&self.state.rms_level);
+        let recent_samples = Arc::clone(&self.state.recent_samples);
+        let gain = Arc::clone(&self.state.gain);
+        let sample_rate = format.output_sample_rate as u64;
+        let mut frames_recorded: u64 = 0;
+        let recorded_ms = Arc::clone(&self.state.recorded_ms);
+        let mut last_tick_second: u64 = 0;
+        let mut size_forecaster = self.size_forecaster(format.output_sample_rate);
+        let mut input_level_detector = InputLevelDetector::new(format.output_sample_rate, 1);
+        let mut waveform = WaveformDownsampler::new(format.output_sample_rate, 1);
+        let waveform_enabled = Arc::clone(&self.state.waveform_enabled);
+        let auto_stop_seconds = self.auto_stop_silence_seconds.load(Ordering::Relaxed);
+        let mut silence_detector = (auto_stop_seconds > 0)
+            .then(|| SilenceDetector::new(format.output_sample_rate, 1, auto_stop_seconds));
+        let max_recording_seconds = self.max_recording_seconds.load(Ordering::Relaxed) as u64;
+        let mut max_duration_reached = false;
         let is_recording_for_task = Arc::clone(&self.state.is_recording);
+        let (finalized_tx, finalized_rx) = oneshot::channel();
+        *self.state.wav_finalized.lock().await = Some(finalized_rx);
+        // The pre-roll monitor runs until the recording stream delivers audio
+        let pre_roll_stop = self
+            .pre_roll_stop
+            .lock()
+            .ok()
+            .and_then(|mut stop| stop.take());
+        let stream_state = RecordingStreamState {
+            is_recording: Arc::clone(&self.state.is_recording),
+            is_paused: Arc::clone(&self.state.is_paused),
+            pre_roll: Arc::clone(&self.pre_roll),
+        };
+        let stream_app_handle = self.app_handle.clone();
+        let capture_format = Arc::clone(&self.capture_format);
 
-        // Set recording state
-        self.state.is_recording.store(true, Ordering::Relaxed);
+        // In mixed mode, a second stream feeds the system output to the mixer
+        let loopback = match self.capture_source() {
+            CaptureSource::Mixed => self.resolve_loopback().map(|(device, device_config)| {
+                let mixer = LoopbackMixer::new(
+                    device_config.sample_rate().0,
+                    device_config.channels(),
+                    format.output_sample_rate,
+                );
+                let config = StreamConfig {
+                    channels: device_config.channels(),
+                    sample_rate: device_config.sample_rate(),
+                    buffer_size: cpal::BufferSize::Default,
+                };
+                (device, config, Arc::new(std::sync::Mutex::new(mixer)))
+            }),
+            _ => None,
+        };
+        let mixer = loopback.as_ref().map(|(_, _, mixer)| Arc::clone(mixer));
 
-        // Spawn a task to handle the audio stream
-        // This runs in a separate thread to avoid Send/Sync issues
-        let device = self.device.clone();
-        tokio::task::spawn_blocking(move || {
-            // Create the input stream
-            let stream = device.build_input_stream(
-                &config,
-                move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    if !is_recording.load(Ordering::Relaxed) {
-                        return;
-                    }
+        // Audio in the recording format, whichever device it comes from
+        let process: BlockProcessor = Arc::new(std::sync::Mutex::new(move |mut data: Vec<f32>| {
+            // Apply input gain (manual or AGC) before metering and writing
+            gain.process(&mut data);
+
+            // Add the system output in mixed mode (after the gain, which
+            // is meant for the microphone)
+            if let Some(mixer) = mixer.as_ref() {
+                if let Ok(mut mixer) = mixer.lock() {
+                    mixer.mix_into(&mut data, 1);
+                }
+            }
+            let data = data.as_slice();
+
+            // Calculate RMS for VU meter
+            let rms = Self::calculate_rms(data);
+
+            // Warn about sustained clipping or over-level input (throttled)
+            if let Some(warning) = input_level_detector.observe(data) {
+                Self::report_input_warning(&app_handle, warning, gain.applied_gain_db());
+            }
+
+            // Ask for the stop workflow once the speaker has gone silent
+            if silence_detector
+                .as_mut()
+                .is_some_and(|detector| detector.observe(data))
+            {
+                eprintln!(
+                    "🤫 [AUDIO] {}s of silence, stopping the recording",
+                    auto_stop_seconds
+                );
+                if let Err(e) = app_handle.emit("vad_silence_stop", ()) {
+                    eprintln!("Failed to emit silence stop event: {}", e);
+                }
+            }
+
+            // Once per second of audio, report the recording telemetry
+            frames_recorded += data.len() as u64;
+            recorded_ms.store(frames_recorded * 1000 / sample_rate, Ordering::Relaxed);
+            let elapsed_seconds = frames_recorded / sample_rate;
+            if elapsed_seconds > last_tick_second {
+                last_tick_second = elapsed_seconds;
+                let _ = emit_to_main(
+                    &app_handle,
+                    "recording-tick",
+                    serde_json::json!({
+                        "elapsed_seconds": elapsed_seconds,
+                        "rms": rms,
+                        "gain_db": gain.applied_gain_db(),
+                        "auto_gain": gain.auto_gain(),
+                    }),
+                );
+                if let Some(forecast) = size_forecaster.observe(elapsed_seconds as f64) {
+                    Self::report_size_forecast(&app_handle, forecast);
+                }
+            }
+
+            // Ask for the stop workflow once the maximum duration is recorded
+            if max_recording_seconds > 0
+                && !max_duration_reached
+                && elapsed_seconds >= max_recording_seconds
+            {
+                max_duration_reached = true;
+                eprintln!(
+                    "⏱️ [AUDIO] Maximum recording duration ({}s) reached, stopping the recording",
+                    max_recording_seconds
+                );
+                if let Err(e) = app_handle.emit("max_duration_stop", max_recording_seconds) {
+                    eprintln!("Failed to emit max duration stop event: {}", e);
+                }
+            }
 
-                    // Calculate RMS for VU meter
-                    let rms = Self::calculate_rms(data);
+            // Hand the level to the RMS publisher (latest value wins)
+            rms_level.send_replace(rms);
 
-                    // Emit RMS event to frontend
-                    if let Err(e) = app_handle.emit("rms", rms) {
-                        eprintln!("Failed to emit RMS event: {}", e);
+            // Emit waveform frames to subscribers
+            if waveform_enabled.load(Ordering::Relaxed) {
+                for frame in waveform.observe(data) {
+                    if let Err(e) = emit_to_main(&app_handle, "waveform", frame) {
+                        eprintln!("Failed to emit waveform event: {}", e);
                     }
+                }
+            }
+
+            // Call RMS callback if set
+            if let Ok(callback_guard) = rms_callback.try_lock() {
+                if let Some(ref callback) = *callback_guard {
+                    callback(rms);
+                }
+            }
+
+            let samples_i16 = Self::samples_to_i16(data);
+
+            // Keep recent audio available for live processing
+            if let Ok(mut recent) = recent_samples.try_lock() {
+                Self::push_recent_samples(&mut recent, &samples_i16);
+            }
 
-                    // Call RMS callback if set
-                    if let Ok(callback_guard) = rms_callback.try_lock() {
-                        if let Some(ref callback) = *callback_guard {
-                            callback(rms);
+            // Write audio data to file
+            if let Ok(mut writer_guard) = writer_clone.try_lock() {
+                if let Some(ref mut writer) = *writer_guard {
+                    for sample in samples_i16 {
+                        if let Err(e) = writer.write_sample(sample) {
+                            eprintln!("Failed to write audio sample: {}", e);
+                            break;
                         }
                     }
+                }
+            }
 
-                    // Write audio data to file
-                    if let Ok(mut writer_guard) = writer_clone.try_lock() {
-                        if let Some(ref mut writer) = *writer_guard {
-                            let samples_i16 = Self::samples_to_i16(data);
-                            for sample in samples_i16 {
-                                if let Err(e) = writer.write_sample(sample) {
-                                    eprintln!("Failed to write audio sample: {}", e);
-                                    break;
-                                }
+            // Feed the encoder running alongside
+            if let Ok(stream_encoder) = stream_encoder_clone.lock() {
+                if let Some(ref encoder) = *stream_encoder {
+                    encoder.push(data);
+                }
+            }
+        }));
+
+        // Set recording state
+        self.state.is_paused.store(false, Ordering::Relaxed);
+        self.state.is_recording.store(true, Ordering::Relaxed);
+        self.pause_media_players();
+        self.spawn_rms_publisher();
+
+        // Spawn a task to handle the audio stream
+        // This runs in a separate thread to avoid Send/Sync issues
+        tokio::task::spawn_blocking(move || {
+            // Start the loopback stream first, so the mixer has audio queued
+            let loopback_stream = loopback.and_then(|(device, config, mixer)| {
+                let stream = device
+                    .build_input_stream(
+                        &config,
+                        move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                            if let Ok(mut mixer) = mixer.lock() {
+                                mixer.push(data);
                             }
-                        }
+                        },
+                        |err| {
+                            eprintln!("Loopback stream error: {}", err);
+                        },
+                        None,
+                    )
+                    .map_err(|e| e.to_string())
+                    .and_then(|stream| {
+                        stream.play().map_err(|e| e.to_string())?;
+                        Ok(stream)
+                    });
+                match stream {
+                    Ok(stream) => Some(stream),
+                    Err(e) => {
+                        eprintln!("Failed to start the loopback stream: {}", e);
+                        None
                     }
-                },
-                |err| {
-                    eprintln!("Audio stream error: {}", err);
-                },
-                None,
+                }
+            });
+
+            // Each stream reports its own generation when its device is lost,
+            // so errors of a replaced stream are ignored
+            let (lost_tx, mut lost_rx) = mpsc::unbounded_channel::<u32>();
+            let mut generation = 0;
+            let mut device_name = device.name().unwrap_or_else(|_| "default".to_string());
+
+            let stream = Self::build_recording_stream(
+                &device,
+                &device_config,
+                &stream_state,
+                pre_roll_stop,
+                Arc::clone(&process),
+                (lost_tx.clone(), generation),
             );
 
             match stream {
                 Ok(stream) => {
-                    // Start the stream
-                    if let Err(e) = stream.play() {
-                        eprintln!("Failed to start stream: {}", e);
-                        return;
-                    }
+                    let mut stream = Some(stream);
 
                     // Keep the stream alive until we receive a stop signal
                     let rt = tokio::runtime::Runtime::new().unwrap();
                     rt.block_on(async {
-                        stop_rx.recv().await;
+                        loop {
+                            tokio::select! {
+                                _ = stop_rx.recv() => break,
+                                Some(lost) = lost_rx.recv() => {
+                                    if lost != generation || stream.is_none() {
+                                        continue;
+                                    }
+                                    // Release the dead stream before opening another one
+                                    stream = None;
+                                    generation += 1;
+                                    let (next, event) = Self::switch_lost_device(
+                                        &mut device_name,
+                                        format.requested_sample_rate,
+                                        &stream_state,
+                                        Arc::clone(&process),
+                                        (lost_tx.clone(), generation),
+                                    );
+                                    if let Some((next_stream, next_format)) = next {
+                                        stream = Some(next_stream);
+                                        if let Ok(mut current) = capture_format.lock() {
+                                            *current = next_format;
+                                        }
+                                    }
+                                    let _ = stream_app_handle.emit("recording-device-lost", event);
+                                }
+                            }
+                        }
                     });
 
-                    // Stream will be dropped here, stopping the recording
+                    // Streams will be dropped here, stopping the recording
                     drop(stream);
+                    drop(loopback_stream);
                 }
                 Err(e) => {
                     eprintln!("Failed to create stream: {}", e);
@@ -292,9 +1939,19 @@ impl AudioCapture for LiveAudioCapture {
                 }
             }
 
+            // Let the streamed encoding write its last block
+            if let Some(encoder) = stream_encoder
+                .lock()
+                .ok()
+                .and_then(|mut encoder| encoder.take())
+            {
+                encoder.finish();
+            }
+
             // Convert temp file to permanent file
             let (file, _path) = temp_file.keep().unwrap();
             drop(file); // Close the file handle
+            let _ = finalized_tx.send(());
         });
 
         Ok(temp_path)
@@ -309,6 +1966,8 @@ impl AudioCapture for LiveAudioCapture {
 
         // Stop recording
         self.state.is_recording.store(false, Ordering::Relaxed);
+        self.state.is_paused.store(false, Ordering::Relaxed);
+        self.resume_media_players();
 
         // Send stop signal to the stream task
         if let Some(stop_sender) = self.state.stop_sender.lock().await.take() {
@@ -329,6 +1988,26 @@ impl AudioCapture for LiveAudioCapture {
                 AudioCaptureError::StreamCreation("No recording file available".to_string())
             })?;
 
+        // Level the recording once its WAV file is complete
+        let loudness_target = self.loudness_target.lock().ok().and_then(|target| *target);
+        let finalized = self.state.wav_finalized.lock().await.take();
+        if let (Some(target_lufs), Some(finalized)) = (loudness_target, finalized) {
+            let _ = tokio::time::timeout(tokio::time::Duration::from_secs(2), finalized).await;
+            let wav_path = path.clone();
+            match tokio::task::spawn_blocking(move || normalize_wav(&wav_path, target_lufs)).await {
+                Ok(Ok(Some(gain_db))) => eprintln!(
+                    "🔊 [AUDIO] Recording normalized to {} LUFS ({:+.1} dB)",
+                    target_lufs, gain_db
+                ),
+                Ok(Ok(None)) => eprintln!("🔇 [AUDIO] Silent recording, not normalized"),
+                Ok(Err(e)) => eprintln!("⚠️ [AUDIO] Loudness normalization failed: {}", e),
+                Err(e) => eprintln!("⚠️ [AUDIO] Loudness normalization failed: {}", e),
+            }
+        }
+
+        // Fill the pre-roll for the next recording
+        self.restart_pre_roll_monitor();
+
         Ok(path)
     }
 
@@ -381,4 +2060,211 @@ mod tests {
         assert_eq!(converted[5], i16::MAX); // 1.5 clamped to 1.0 -> 32767
         assert_eq!(converted[6], -i16::MAX); // -1.5 clamped to -1.0 -> -32767
     }
+
+    #[test]
+    fn test_gain_control_manual_and_auto() {
+        let gain = GainControl::default();
+        gain.set_gain_db(6.0);
+        let mut samples = vec![0.1, -0.1];
+        gain.process(&mut samples);
+        assert!((samples[0] - 0.1995).abs() < 0.001);
+        assert_eq!(gain.applied_gain_db(), 6.0);
+
+        gain.set_gain_db(100.0);
+        assert_eq!(gain.gain_db(), MAX_INPUT_GAIN_DB);
+
+        // AGC boosts a quiet input by at most one step per block, from the manual gain
+        gain.set_gain_db(0.0);
+        gain.set_auto_gain(true);
+        let mut quiet = vec![0.01; 480];
+        gain.process(&mut quiet);
+        assert_eq!(gain.applied_gain_db(), AGC_MAX_STEP_DB);
+
+        // Silence does not move the gain
+        gain.process(&mut [0.0; 480]);
+        assert_eq!(gain.applied_gain_db(), AGC_MAX_STEP_DB);
+
+        gain.set_auto_gain(false);
+        assert_eq!(gain.applied_gain_db(), 0.0);
+    }
+
+    #[test]
+    fn test_silence_detector_waits_for_speech_then_fires_once() {
+        // 1000 samples per second: windows of 100 samples, 2 seconds limit
+        let mut detector = SilenceDetector::new(1000, 1, 2);
+
+        // Silence before any speech never stops the recording
+        assert!(!detector.observe(&[0.0; 5000]));
+
+        assert!(!detector.observe(&[0.1; 500]));
+        assert!(!detector.observe(&[0.0; 1500]));
+        // Speech resets the silence count
+        assert!(!detector.observe(&[0.1; 100]));
+        assert!(!detector.observe(&[0.0; 1900]));
+        assert!(detector.observe(&[0.001; 100]));
+        assert!(!detector.observe(&[0.0; 5000]));
+    }
+
+    #[test]
+    fn test_waveform_downsampler_emits_fixed_size_frames() {
+        // 5000 frames per second, stereo: 10 frames per point, 500 per window
+        let mut downsampler = WaveformDownsampler::new(5000, 2);
+
+        let mut samples = vec![0.0; 600];
+        samples[20] = -0.5;
+        samples[21] = -0.3;
+        assert!(downsampler.observe(&samples).is_empty());
+
+        let frames = downsampler.observe(&[0.2; 1200]);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].len(), WAVEFORM_POINTS);
+        // Channels are mixed down and the peak keeps its sign
+        assert_eq!(frames[0][1], -0.4);
+        assert_eq!(frames[0][0], 0.0);
+        assert_eq!(frames[0][WAVEFORM_POINTS - 1], 0.2);
+    }
+
+    #[test]
+    fn test_pre_roll_buffer_keeps_latest_samples() {
+        // 0.5 s of stereo audio at 8 samples per second
+        let mut buffer = PreRollBuffer::new(0.5, 8, 2);
+        buffer.push(&[1.0, 1.0, 2.0, 2.0, 3.0, 3.0]);
+        buffer.push(&[4.0, 4.0, 5.0, 5.0]);
+        assert_eq!(
+            buffer.take_for(8, 2),
+            vec![2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 5.0, 5.0]
+        );
+        assert!(buffer.take_for(8, 2).is_empty());
+
+        // Audio in another format is dropped
+        buffer.push(&[1.0, 1.0]);
+        assert!(buffer.take_for(8, 1).is_empty());
+
+        assert_eq!(pre_roll_seconds(0.0), 0.0);
+        assert_eq!(pre_roll_seconds(0.1), MIN_PRE_ROLL_SECONDS);
+        assert_eq!(pre_roll_seconds(1.5), 1.5);
+        assert_eq!(pre_roll_seconds(5.0), MAX_PRE_ROLL_SECONDS);
+    }
+
+    #[test]
+    fn test_loopback_mixer_downmixes_resamples_and_mixes() {
+        // Stereo loopback at twice the microphone rate
+        let mut mixer = LoopbackMixer::new(16, 2, 8);
+        mixer.push(&[0.2, 0.4, 0.0, 0.0, 0.4, 0.6, 0.0, 0.0, 0.9, 0.9]);
+
+        // Mono frames 0.3, 0.5 and 0.9 were kept, added to both mic channels
+        let mut samples = [0.1, 0.1, 0.0, -0.5, 0.5, 0.5, 0.2, 0.2];
+        mixer.mix_into(&mut samples, 2);
+        assert_eq!(samples[..2], [0.4, 0.4]);
+        assert_eq!(samples[2..4], [0.5, 0.0]);
+        // Clamped to full scale
+        assert_eq!(samples[4..6], [1.0, 1.0]);
+        // Nothing queued anymore: left untouched
+        assert_eq!(samples[6..], [0.2, 0.2]);
+
+        // The queue keeps LOOPBACK_QUEUE_MS of the latest audio
+        let mut mixer = LoopbackMixer::new(8, 1, 8);
+        mixer.push(&[0.1; 8]);
+        mixer.push(&[0.2; 8]);
+        let mut samples = [0.0; 8];
+        mixer.mix_into(&mut samples, 1);
+        assert_eq!(samples[..4], [0.2; 4]);
+        assert_eq!(samples[4..], [0.0; 4]);
+    }
+
+    #[test]
+    fn test_format_converter_downmixes_and_resamples_across_blocks() {
+        // Stereo at twice the recording rate: every other mono frame
+        let mut converter = FormatConverter::new(16, 2, 8);
+        assert!(!converter.is_passthrough());
+        let output = converter.process(&[0.2, 0.4, 0.0, 0.0, 0.4, 0.6, 0.0, 0.0]);
+        assert_eq!(output, vec![0.3, 0.5]);
+
+        // Upsampling interpolates, also between the last frame of a block
+        // and the first of the next one
+        let mut converter = FormatConverter::new(1, 1, 2);
+        assert_eq!(converter.process(&[0.0, 1.0]), vec![0.0, 0.5]);
+        assert_eq!(converter.process(&[0.0]), vec![1.0, 0.5]);
+
+        let mut converter = FormatConverter::new(CAPTURE_SAMPLE_RATE, 1, CAPTURE_SAMPLE_RATE);
+        assert!(converter.is_passthrough());
+        assert_eq!(converter.process(&[0.1, 0.2]), vec![0.1, 0.2]);
+    }
+
+    #[test]
+    fn test_pick_stream_format_falls_back_to_supported_rates() {
+        let supported = [(2, 8000, 48000), (1, 44100, 44100)];
+        // Requested rate, mono preferred
+        assert_eq!(pick_stream_format(&supported, 44100), Some((1, 44100)));
+        assert_eq!(pick_stream_format(&supported, 16000), Some((0, 16000)));
+        // Unsupported rate: first supported fallback rate
+        assert_eq!(pick_stream_format(&supported, 96000), Some((0, 48000)));
+        // Nothing usable: the caller uses the device default
+        assert_eq!(pick_stream_format(&[(1, 96000, 96000)], 44100), None);
+        assert_eq!(pick_stream_format(&[], 48000), None);
+    }
+
+    #[test]
+    fn test_input_level_detector_reports_sustained_clipping_once() {
+        // 1000 samples per second: windows of 500 samples
+        let mut detector = InputLevelDetector::new(1000, 1);
+
+        // A single clipping window is not sustained
+        assert_eq!(detector.observe(&[1.0; 500]), None);
+        assert_eq!(detector.observe(&[0.1; 500]), None);
+
+        let warning = detector.observe(&[1.0; 1000]).unwrap();
+        assert_eq!(warning.kind, InputWarningKind::Clipping);
+        assert_eq!(warning.clipped_percent, 100.0);
+        assert!(warning.rms_dbfs.abs() < 0.01);
+        // Throttled for the next seconds of audio
+        assert_eq!(detector.observe(&[1.0; 5000]), None);
+        assert!(detector.observe(&[1.0; 5000]).is_some());
+    }
+
+    #[test]
+    fn test_input_level_detector_warns_about_over_level_input() {
+        let mut detector = InputLevelDetector::new(1000, 1);
+
+        // Hot but unclipped input (about -6 dBFS)
+        let warning = detector.observe(&[0.5; 1000]).unwrap();
+        assert_eq!(warning.kind, InputWarningKind::OverLevel);
+        assert_eq!(warning.clipped_percent, 0.0);
+        assert!((warning.rms_dbfs + 6.02).abs() < 0.01);
+        assert_eq!(detector.observe(&[0.5; 1000]), None);
+
+        // Clipping right after is still reported
+        assert_eq!(
+            detector.observe(&[1.0; 1000]).map(|warning| warning.kind),
+            Some(InputWarningKind::Clipping)
+        );
+    }
+
+    #[test]
+    fn test_rms_emit_interval_is_clamped() {
+        use std::time::Duration;
+        assert_eq!(
+            rms_emit_interval(DEFAULT_RMS_RATE_HZ),
+            Duration::from_secs(1) / 30
+        );
+        assert_eq!(rms_emit_interval(0), Duration::from_secs(1));
+        assert_eq!(
+            rms_emit_interval(1000),
+            Duration::from_secs(1) / MAX_RMS_RATE_HZ
+        );
+    }
+
+    #[test]
+    fn test_recent_samples_ring_buffer_is_bounded() {
+        let capacity = (RECENT_AUDIO_SECONDS * CAPTURE_SAMPLE_RATE) as usize;
+        let mut buffer = VecDeque::new();
+
+        LiveAudioCapture::push_recent_samples(&mut buffer, &vec![1; capacity - 10]);
+        LiveAudioCapture::push_recent_samples(&mut buffer, &[2; 20]);
+
+        assert_eq!(buffer.len(), capacity);
+        assert_eq!(buffer.front(), Some(&1));
+        assert_eq!(buffer.back(), Some(&2));
+        assert_eq!(buffer.iter().filter(|&&s| s == 2).count(), 20);
+    }
 }
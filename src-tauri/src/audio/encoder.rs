@@ -1,23 +1,32 @@
 use async_trait::async_trait;
+use flacenc::component::BitRepr;
+use flacenc::error::Verify;
 use hound::WavReader;
+use mp3lame_encoder::{DualPcm, FlushNoGap, MonoPcm};
 use serde::{Deserialize, Serialize};
 use std::fs::File;
-use std::io::BufWriter;
+use std::io::{BufReader, BufWriter};
 use std::num::NonZero;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tokio::sync::mpsc;
 use vorbis_rs::{VorbisBitrateManagementStrategy, VorbisEncoderBuilder};
 
-/// Information about the encoded OGG file
+/// Information about the encoded file (OGG, MP3 or FLAC)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OggInfo {
     /// Estimated final file size in bytes
     pub size_estimate: u64,
-    /// Path to the encoded OGG file
+    /// Path to the encoded file
     pub path: PathBuf,
+    /// Format of the encoded file
+    #[serde(default)]
+    pub format: EncoderFormat,
     /// Actual file size (if encoding is complete)
     pub actual_size: Option<u64>,
+    /// Whether the noise suppressor ran before encoding
+    #[serde(default)]
+    pub noise_suppressed: bool,
 }
 
 /// Events emitted during encoding process
@@ -45,6 +54,12 @@ pub enum EncodingError {
     #[error("Failed to create Vorbis encoder: {0}")]
     VorbisError(#[from] vorbis_rs::VorbisError),
 
+    #[error("MP3 encoding failed: {0}")]
+    Mp3Error(String),
+
+    #[error("FLAC encoding failed: {0}")]
+    FlacError(String),
+
     #[error("I/O error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -53,16 +68,378 @@ pub enum EncodingError {
 
     #[error("File size exceeds limit: {estimated} bytes")]
     FileSizeExceedsLimit { estimated: u64 },
+
+    #[error("Encoded file failed verification: {0}")]
+    VerificationFailed(String),
+}
+
+/// Properties of an OGG/Vorbis stream, read back from the encoded file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OggStreamInfo {
+    pub sample_rate: u32,
+    pub channels: u8,
+    /// Duration from the final granule position
+    pub duration_seconds: f64,
+    /// Number of packets, including the three Vorbis headers
+    pub packets: u64,
+}
+
+/// Largest accepted difference between the WAV and OGG durations (seconds)
+const DURATION_TOLERANCE_SECONDS: f64 = 0.5;
+
+/// Parse an OGG/Vorbis file and check the stream is complete
+///
+/// Every page checksum is validated, the first packet must be a Vorbis
+/// identification header and the stream must end with an end-of-stream page.
+pub fn verify_ogg_file(path: &Path) -> Result<OggStreamInfo, EncodingError> {
+    let file = File::open(path)?;
+    let mut reader = ogg::PacketReader::new(BufReader::new(file));
+    let corrupt = |e: ogg::OggReadError| {
+        EncodingError::VerificationFailed(format!("corrupt OGG stream: {}", e))
+    };
+
+    let header = reader.read_packet().map_err(corrupt)?.ok_or_else(|| {
+        EncodingError::VerificationFailed("file contains no OGG pages".to_string())
+    })?;
+    if !header.first_in_stream()
+        || header.data.len() < 30
+        || header.data[0] != 1
+        || &header.data[1..7] != b"vorbis"
+    {
+        return Err(EncodingError::VerificationFailed(
+            "missing Vorbis identification header".to_string(),
+        ));
+    }
+    let channels = header.data[11];
+    let sample_rate = u32::from_le_bytes([
+        header.data[12],
+        header.data[13],
+        header.data[14],
+        header.data[15],
+    ]);
+    if channels == 0 || sample_rate == 0 {
+        return Err(EncodingError::VerificationFailed(format!(
+            "invalid stream parameters ({} channels at {} Hz)",
+            channels, sample_rate
+        )));
+    }
+
+    let mut packets = 1;
+    let mut last_granule = 0;
+    let mut ended = header.last_in_stream();
+    while let Some(packet) = reader.read_packet().map_err(corrupt)? {
+        if packet.stream_serial() != header.stream_serial() {
+            return Err(EncodingError::VerificationFailed(
+                "unexpected second logical stream".to_string(),
+            ));
+        }
+        packets += 1;
+        last_granule = packet.absgp_page();
+        ended = packet.last_in_stream();
+    }
+
+    if !ended {
+        return Err(EncodingError::VerificationFailed(
+            "stream is truncated (no end-of-stream page)".to_string(),
+        ));
+    }
+    if packets < 3 {
+        return Err(EncodingError::VerificationFailed(format!(
+            "stream has {} packet(s), expected at least the 3 Vorbis headers",
+            packets
+        )));
+    }
+
+    Ok(OggStreamInfo {
+        sample_rate,
+        channels,
+        duration_seconds: last_granule as f64 / sample_rate as f64,
+        packets,
+    })
+}
+
+/// Properties of a FLAC stream, read from its STREAMINFO block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlacStreamInfo {
+    pub sample_rate: u32,
+    pub channels: u8,
+    pub bits_per_sample: u8,
+    /// Duration from the total sample count
+    pub duration_seconds: f64,
+}
+
+/// Parse the STREAMINFO block of a FLAC file
+pub fn verify_flac_file(path: &Path) -> Result<FlacStreamInfo, EncodingError> {
+    let mut header = [0u8; 42];
+    std::io::Read::read_exact(&mut File::open(path)?, &mut header).map_err(|_| {
+        EncodingError::VerificationFailed("file is too short for a FLAC stream".to_string())
+    })?;
+    // "fLaC", then the STREAMINFO metadata block (type 0, 34 bytes)
+    if &header[0..4] != b"fLaC" || header[4] & 0x7F != 0 || header[5..8] != [0, 0, 34] {
+        return Err(EncodingError::VerificationFailed(
+            "missing FLAC STREAMINFO header".to_string(),
+        ));
+    }
+
+    let info = &header[8..];
+    let sample_rate =
+        (u32::from(info[10]) << 12) | (u32::from(info[11]) << 4) | (u32::from(info[12]) >> 4);
+    let channels = ((info[12] >> 1) & 0x07) + 1;
+    let bits_per_sample = (((info[12] & 0x01) << 4) | (info[13] >> 4)) + 1;
+    let total_samples = (u64::from(info[13] & 0x0F) << 32)
+        | u64::from(u32::from_be_bytes([info[14], info[15], info[16], info[17]]));
+    if sample_rate == 0 {
+        return Err(EncodingError::VerificationFailed(
+            "invalid FLAC sample rate".to_string(),
+        ));
+    }
+
+    Ok(FlacStreamInfo {
+        sample_rate,
+        channels,
+        bits_per_sample,
+        duration_seconds: total_samples as f64 / sample_rate as f64,
+    })
+}
+
+/// Sample rate and channel count from the first MP3 frame header
+fn read_mp3_frame_header(path: &Path) -> Result<(u32, u8), EncodingError> {
+    let data = std::fs::read(path)?;
+    // Skip an ID3v2 tag (its size is stored as four 7-bit bytes)
+    let mut offset = 0;
+    if data.len() >= 10 && &data[0..3] == b"ID3" {
+        offset = 10
+            + data[6..10]
+                .iter()
+                .fold(0usize, |size, byte| (size << 7) | usize::from(byte & 0x7F));
+    }
+
+    let header = data.get(offset..offset + 4).ok_or_else(|| {
+        EncodingError::VerificationFailed("file contains no MP3 frame".to_string())
+    })?;
+    let version = (header[1] >> 3) & 0x03;
+    let layer = (header[1] >> 1) & 0x03;
+    let rate_index = usize::from((header[2] >> 2) & 0x03);
+    if header[0] != 0xFF
+        || header[1] & 0xE0 != 0xE0
+        || version == 1
+        || layer != 1
+        || rate_index == 3
+    {
+        return Err(EncodingError::VerificationFailed(
+            "missing MPEG layer III frame header".to_string(),
+        ));
+    }
+
+    let mpeg1_rate = [44100, 48000, 32000][rate_index];
+    let sample_rate = match version {
+        3 => mpeg1_rate,
+        2 => mpeg1_rate / 2,
+        _ => mpeg1_rate / 4,
+    };
+    let channels = if header[3] >> 6 == 3 { 1 } else { 2 };
+    Ok((sample_rate, channels))
+}
+
+/// Check the encoded stream matches the source audio
+pub(crate) fn verify_encoded_output(
+    path: &Path,
+    format: EncoderFormat,
+    expected_channels: u8,
+    expected_sample_rate: u32,
+    expected_duration: f64,
+) -> Result<(), EncodingError> {
+    let (channels, sample_rate, duration_seconds) = match format {
+        EncoderFormat::Ogg => {
+            let stream = verify_ogg_file(path)?;
+            (
+                stream.channels,
+                stream.sample_rate,
+                Some(stream.duration_seconds),
+            )
+        }
+        EncoderFormat::Flac => {
+            let stream = verify_flac_file(path)?;
+            (
+                stream.channels,
+                stream.sample_rate,
+                Some(stream.duration_seconds),
+            )
+        }
+        EncoderFormat::Mp3 => {
+            // LAME may pick a lower output sample rate to suit the bitrate
+            let (_, channels) = read_mp3_frame_header(path)?;
+            (channels, expected_sample_rate, None)
+        }
+    };
+
+    if channels != expected_channels || sample_rate != expected_sample_rate {
+        return Err(EncodingError::VerificationFailed(format!(
+            "expected {} channel(s) at {} Hz, found {} channel(s) at {} Hz",
+            expected_channels, expected_sample_rate, channels, sample_rate
+        )));
+    }
+    if let Some(duration_seconds) = duration_seconds {
+        if (duration_seconds - expected_duration).abs() > DURATION_TOLERANCE_SECONDS {
+            return Err(EncodingError::VerificationFailed(format!(
+                "duration {:.2}s does not match the recording ({:.2}s)",
+                duration_seconds, expected_duration
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Sample rate used for speech (`EncoderApplication::Voip`), Whisper's native rate
+pub const VOIP_SAMPLE_RATE: u32 = 16000;
+
+/// Highest bitrate requested in speech mode (libvorbis' 16kHz modes top out lower)
+const VOIP_MAX_BITRATE: i32 = 24000;
+
+/// Blocks quieter than this are encoded as digital silence when DTX is on
+const DTX_SILENCE_RMS: f32 = 0.003;
+
+/// Frame length of the noise suppressor, in milliseconds (as in RNNoise)
+const DENOISE_FRAME_MS: u32 = 10;
+
+/// Strongest attenuation of noise-only frames (about -24 dB)
+const DENOISE_MIN_GAIN: f32 = 0.063;
+
+/// Noise power subtracted per unit of estimated noise floor
+const DENOISE_OVER_SUBTRACTION: f32 = 2.0;
+
+/// Factor the noise floor estimate may rise by per frame (it falls at once)
+const DENOISE_FLOOR_RISE: f32 = 1.02;
+
+/// Share of the previous gain kept per frame when the gain falls
+const DENOISE_RELEASE: f32 = 0.7;
+
+/// Intended use of the encoded audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EncoderApplication {
+    /// Speech: resampled to 16kHz, less CPU and smaller files
+    Voip,
+    /// Full bandwidth at the recording sample rate
+    #[default]
+    Audio,
+}
+
+/// Compressed size of 16-bit speech in FLAC, relative to the PCM size
+const FLAC_COMPRESSION_RATIO: f64 = 0.6;
+
+/// Output format of the encoder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EncoderFormat {
+    /// OGG/Vorbis: small, the default upload format
+    #[default]
+    Ogg,
+    /// MP3 (LAME), for players and tools without OGG support
+    Mp3,
+    /// FLAC: lossless, several times larger
+    Flac,
 }
 
-/// The Encoder trait for converting WAV to OGG/Vorbis
+impl EncoderFormat {
+    /// File extension of the format
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Ogg => "ogg",
+            Self::Mp3 => "mp3",
+            Self::Flac => "flac",
+        }
+    }
+
+    /// MIME type sent with uploads
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Ogg => "audio/ogg",
+            Self::Mp3 => "audio/mpeg",
+            Self::Flac => "audio/flac",
+        }
+    }
+
+    /// Format of a file extension
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "ogg" | "oga" => Some(Self::Ogg),
+            "mp3" => Some(Self::Mp3),
+            "flac" => Some(Self::Flac),
+            _ => None,
+        }
+    }
+
+    /// Most channels the format encodes (more are downmixed to mono)
+    fn max_channels(self) -> u16 {
+        match self {
+            Self::Ogg => 255,
+            Self::Mp3 => 2,
+            Self::Flac => 8,
+        }
+    }
+}
+
+/// Encoder tuning knobs
+///
+/// The names follow the Opus encoder options. On OGG/Vorbis they map to:
+/// * `application`: `Voip` encodes at `VOIP_SAMPLE_RATE`, `Audio` at the recording rate
+/// * `complexity` (0-10): quality of the `Voip` resampling filter, 0 being the cheapest
+/// * `dtx`: near-silent blocks are encoded as digital silence, which costs almost no bits
+/// * `vbr`: the bitrate follows the signal around the target (smaller speech
+///   files); off, it is capped at the target (predictable sizes)
+///
+/// The same knobs apply to the MP3 and FLAC outputs selected by `format`.
+///
+/// `noise_suppression` runs the noise suppressor on the audio before encoding.
+/// `downmix_to_mono` averages the channels of a multi-channel recording into
+/// one; otherwise every channel is encoded, each at the target bitrate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncoderOptions {
+    pub complexity: u8,
+    pub application: EncoderApplication,
+    pub dtx: bool,
+    #[serde(default)]
+    pub noise_suppression: bool,
+    #[serde(default = "default_downmix_to_mono")]
+    pub downmix_to_mono: bool,
+    #[serde(default)]
+    pub format: EncoderFormat,
+    #[serde(default = "default_vbr")]
+    pub vbr: bool,
+}
+
+fn default_downmix_to_mono() -> bool {
+    true
+}
+
+fn default_vbr() -> bool {
+    true
+}
+
+impl Default for EncoderOptions {
+    fn default() -> Self {
+        Self {
+            complexity: 10,
+            application: EncoderApplication::Audio,
+            dtx: false,
+            noise_suppression: false,
+            downmix_to_mono: default_downmix_to_mono(),
+            format: EncoderFormat::default(),
+            vbr: default_vbr(),
+        }
+    }
+}
+
+/// The Encoder trait for converting WAV to a compressed format
 #[async_trait]
 pub trait Encoder: Send + Sync {
-    /// Encode a WAV file to OGG/Vorbis format
+    /// Encode a WAV file
     ///
     /// # Arguments
     /// * `wav_path` - Path to the input WAV file
-    /// * `output_path` - Optional output path (if None, will use input path with .ogg extension)
+    /// * `output_path` - Optional output path (if None, will use input path with the extension of the format)
     /// * `event_sender` - Optional channel to send progress events
     ///
     /// # Returns
@@ -75,12 +452,15 @@ pub trait Encoder: Send + Sync {
     ) -> Result<OggInfo, EncodingError>;
 }
 
-/// Default implementation of the Encoder trait using OGG/Vorbis
+/// Default implementation of the Encoder trait: OGG/Vorbis, or MP3/FLAC
+/// when selected by `EncoderOptions::format`
 pub struct OggVorbisEncoder {
     /// Target bitrate in bits per second (default: 32000)
     bitrate: i32,
     /// Size limit in bytes before warning (~23MB)
     size_limit: u64,
+    /// Application / complexity / DTX tuning
+    options: EncoderOptions,
 }
 
 impl Default for OggVorbisEncoder {
@@ -95,6 +475,7 @@ impl OggVorbisEncoder {
         Self {
             bitrate: 32000,               // 32 kbps as specified in requirements
             size_limit: 23 * 1024 * 1024, // ~23MB limit as mentioned in requirements
+            options: EncoderOptions::default(),
         }
     }
 
@@ -103,6 +484,37 @@ impl OggVorbisEncoder {
         Self {
             bitrate,
             size_limit: 23 * 1024 * 1024,
+            options: EncoderOptions::default(),
+        }
+    }
+
+    /// Set the application / complexity / DTX / noise suppression options
+    pub fn with_options(mut self, options: EncoderOptions) -> Self {
+        self.options = EncoderOptions {
+            complexity: options.complexity.min(10),
+            ..options
+        };
+        self
+    }
+
+    /// Set the output format
+    pub fn with_format(mut self, format: EncoderFormat) -> Self {
+        self.options.format = format;
+        self
+    }
+
+    /// Encoder options in use
+    pub fn options(&self) -> EncoderOptions {
+        self.options
+    }
+
+    /// Sample rate and bitrate actually used for a recording at `input_rate`
+    pub(crate) fn encoding_params(&self, input_rate: u32) -> (u32, i32) {
+        match self.options.application {
+            EncoderApplication::Voip if input_rate > VOIP_SAMPLE_RATE => {
+                (VOIP_SAMPLE_RATE, self.bitrate.min(VOIP_MAX_BITRATE))
+            }
+            _ => (input_rate, self.bitrate),
         }
     }
 
@@ -112,16 +524,263 @@ impl OggVorbisEncoder {
         self
     }
 
-    /// Estimate final file size based on duration and bitrate
-    fn estimate_file_size(&self, duration_seconds: f64) -> u64 {
-        // Basic estimation: (bitrate * duration) / 8 + overhead
-        let bits_total = (self.bitrate as f64 * duration_seconds) as u64;
+    /// Channels encoded for a recording with `input_channels`
+    fn output_channels(&self, input_channels: u16) -> u16 {
+        if self.options.downmix_to_mono || input_channels > self.options.format.max_channels() {
+            1
+        } else {
+            input_channels.max(1)
+        }
+    }
+
+    /// Estimate final file size based on duration and bitrate (per channel),
+    /// or on the PCM size for lossless FLAC
+    pub(crate) fn estimate_file_size(
+        &self,
+        duration_seconds: f64,
+        channels: u16,
+        sample_rate: u32,
+    ) -> u64 {
+        if self.options.format == EncoderFormat::Flac {
+            let pcm_bytes = duration_seconds * sample_rate as f64 * channels as f64 * 2.0;
+            return (pcm_bytes * FLAC_COMPRESSION_RATIO) as u64;
+        }
+
+        // Basic estimation: (bitrate * channels * duration) / 8 + overhead
+        let bits_total = (self.bitrate as f64 * channels as f64 * duration_seconds) as u64;
         let bytes_audio = bits_total / 8;
 
         // Add approximately 5% overhead for OGG container and metadata
         let overhead = bytes_audio / 20;
         bytes_audio + overhead
     }
+
+    /// Write the channels as OGG/Vorbis
+    fn write_ogg(
+        &self,
+        channel_samples: &[Vec<f32>],
+        sample_rate: u32,
+        bitrate: i32,
+        output_path: &Path,
+        progress: &mut ProgressReporter,
+    ) -> Result<(), EncodingError> {
+        let channels = channel_samples.len() as u32;
+        let output_writer = BufWriter::new(File::create(output_path)?);
+
+        // Create Vorbis encoder with target bitrate
+        let target_bitrate = NonZero::new(bitrate as u32 * channels).unwrap();
+        let mut encoder = VorbisEncoderBuilder::new(
+            NonZero::new(sample_rate).unwrap(),
+            NonZero::new(channels as u8).unwrap(),
+            output_writer,
+        )?
+        .bitrate_management_strategy(vorbis_strategy(self.options.vbr, target_bitrate))
+        .build()?;
+
+        // Process samples in chunks for better memory management and progress reporting
+        let frames = channel_samples[0].len();
+        for start in (0..frames).step_by(chunk_frames(sample_rate)) {
+            let end = (start + chunk_frames(sample_rate)).min(frames);
+            // Convert to the format expected by vorbis_rs (Vec<Vec<f32>> for multi-channel)
+            let block: Vec<Vec<f32>> = channel_samples
+                .iter()
+                .map(|samples| samples[start..end].to_vec())
+                .collect();
+            encoder.encode_audio_block(block)?;
+            progress.report(end as f64 / frames as f64);
+        }
+
+        // Finalize the encoder (this writes remaining data and closes the stream)
+        encoder.finish()?;
+        Ok(())
+    }
+
+    /// Write the channels (one or two) as MP3
+    fn write_mp3(
+        &self,
+        channel_samples: &[Vec<f32>],
+        sample_rate: u32,
+        bitrate: i32,
+        output_path: &Path,
+        progress: &mut ProgressReporter,
+    ) -> Result<(), EncodingError> {
+        let mut builder = mp3lame_encoder::Builder::new()
+            .ok_or_else(|| EncodingError::Mp3Error("failed to allocate LAME".to_string()))?;
+        builder
+            .set_num_channels(channel_samples.len() as u8)
+            .map_err(mp3_error)?;
+        builder.set_sample_rate(sample_rate).map_err(mp3_error)?;
+        builder
+            .set_brate(mp3_bitrate(bitrate as u32 * channel_samples.len() as u32))
+            .map_err(mp3_error)?;
+        builder
+            .set_quality(mp3_quality(self.options.complexity))
+            .map_err(mp3_error)?;
+        let mut encoder = builder.build().map_err(mp3_error)?;
+
+        let pcm: Vec<Vec<i16>> = channel_samples
+            .iter()
+            .map(|samples| samples.iter().map(|&sample| to_i16(sample)).collect())
+            .collect();
+        let frames = pcm[0].len();
+        let mut output = Vec::new();
+        for start in (0..frames).step_by(chunk_frames(sample_rate)) {
+            let end = (start + chunk_frames(sample_rate)).min(frames);
+            match pcm.as_slice() {
+                [mono] => encoder.encode_to_vec(MonoPcm(&mono[start..end]), &mut output),
+                [left, right] => encoder.encode_to_vec(
+                    DualPcm {
+                        left: &left[start..end],
+                        right: &right[start..end],
+                    },
+                    &mut output,
+                ),
+                _ => unreachable!("MP3 encodes one or two channels"),
+            }
+            .map_err(mp3_error)?;
+            progress.report(end as f64 / frames as f64);
+        }
+        encoder
+            .flush_to_vec::<FlushNoGap>(&mut output)
+            .map_err(mp3_error)?;
+
+        std::fs::write(output_path, output)?;
+        Ok(())
+    }
+}
+
+/// Write the channels as 16-bit FLAC
+fn write_flac(
+    channel_samples: &[Vec<f32>],
+    sample_rate: u32,
+    output_path: &Path,
+    progress: &mut ProgressReporter,
+) -> Result<(), EncodingError> {
+    let frames = channel_samples[0].len();
+    let interleaved: Vec<i32> = (0..frames)
+        .flat_map(|frame| {
+            channel_samples
+                .iter()
+                .map(move |samples| i32::from(to_i16(samples[frame])))
+        })
+        .collect();
+
+    let config = flacenc::config::Encoder::default()
+        .into_verified()
+        .map_err(|(_, e)| flac_error(e))?;
+    let source = flacenc::source::MemSource::from_samples(
+        &interleaved,
+        channel_samples.len(),
+        16,
+        sample_rate as usize,
+    );
+    let stream = flacenc::encode_with_fixed_block_size(&config, source, config.block_size)
+        .map_err(flac_error)?;
+    let mut sink = flacenc::bitsink::ByteSink::new();
+    stream.write(&mut sink).map_err(flac_error)?;
+
+    std::fs::write(output_path, sink.as_slice())?;
+    progress.report(1.0);
+    Ok(())
+}
+
+fn mp3_error(e: impl std::fmt::Display) -> EncodingError {
+    EncodingError::Mp3Error(e.to_string())
+}
+
+fn flac_error(e: impl std::fmt::Debug) -> EncodingError {
+    EncodingError::FlacError(format!("{:?}", e))
+}
+
+/// Vorbis bitrate management: around the target with `vbr`, else capped at it
+pub(crate) fn vorbis_strategy(
+    vbr: bool,
+    target_bitrate: NonZero<u32>,
+) -> VorbisBitrateManagementStrategy {
+    if vbr {
+        VorbisBitrateManagementStrategy::Vbr { target_bitrate }
+    } else {
+        VorbisBitrateManagementStrategy::ConstrainedAbr {
+            maximum_bitrate: target_bitrate,
+        }
+    }
+}
+
+/// Frames per encoded chunk (100ms)
+pub(crate) fn chunk_frames(sample_rate: u32) -> usize {
+    (sample_rate as usize / 10).max(1)
+}
+
+/// Convert a sample to 16 bits
+fn to_i16(sample: f32) -> i16 {
+    (sample * i16::MAX as f32)
+        .round()
+        .clamp(i16::MIN as f32, i16::MAX as f32) as i16
+}
+
+/// LAME bitrate closest above `bitrate` (bits per second)
+fn mp3_bitrate(bitrate: u32) -> mp3lame_encoder::Bitrate {
+    use mp3lame_encoder::Bitrate;
+    match bitrate / 1000 {
+        0..=8 => Bitrate::Kbps8,
+        9..=16 => Bitrate::Kbps16,
+        17..=24 => Bitrate::Kbps24,
+        25..=32 => Bitrate::Kbps32,
+        33..=40 => Bitrate::Kbps40,
+        41..=48 => Bitrate::Kbps48,
+        49..=64 => Bitrate::Kbps64,
+        65..=80 => Bitrate::Kbps80,
+        81..=96 => Bitrate::Kbps96,
+        97..=112 => Bitrate::Kbps112,
+        113..=128 => Bitrate::Kbps128,
+        129..=160 => Bitrate::Kbps160,
+        161..=192 => Bitrate::Kbps192,
+        193..=224 => Bitrate::Kbps224,
+        225..=256 => Bitrate::Kbps256,
+        _ => Bitrate::Kbps320,
+    }
+}
+
+/// LAME quality for an encoder complexity (0-10)
+fn mp3_quality(complexity: u8) -> mp3lame_encoder::Quality {
+    use mp3lame_encoder::Quality;
+    match complexity {
+        0..=2 => Quality::Decent,
+        3..=5 => Quality::Good,
+        6..=8 => Quality::NearBest,
+        _ => Quality::Best,
+    }
+}
+
+/// Progress events of one encoding
+struct ProgressReporter {
+    sender: Option<mpsc::UnboundedSender<EncodingEvent>>,
+    estimated_total: u64,
+    size_limit: u64,
+    size_warning_sent: bool,
+}
+
+impl ProgressReporter {
+    /// Report that `ratio` (0-1) of the audio is encoded
+    fn report(&mut self, ratio: f64) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        // Estimate current file size (rough estimation during encoding)
+        let estimated_current_size = (self.estimated_total as f64 * ratio) as u64;
+        let _ = sender.send(EncodingEvent::Progress {
+            bytes_processed: estimated_current_size,
+            estimated_total: self.estimated_total,
+        });
+
+        // Send size warning if approaching limit
+        if !self.size_warning_sent && estimated_current_size > self.size_limit {
+            self.size_warning_sent = true;
+            let _ = sender.send(EncodingEvent::SizeAlmostLimit {
+                estimated_size: estimated_current_size,
+            });
+        }
+    }
 }
 
 #[async_trait]
@@ -132,10 +791,12 @@ impl Encoder for OggVorbisEncoder {
         output_path: Option<&Path>,
         event_sender: Option<mpsc::UnboundedSender<EncodingEvent>>,
     ) -> Result<OggInfo, EncodingError> {
+        let format = self.options.format;
+
         // Determine output path
         let output_path = match output_path {
             Some(path) => path.to_path_buf(),
-            None => wav_path.with_extension("ogg"),
+            None => wav_path.with_extension(format.extension()),
         };
 
         // Read WAV file header to get format information
@@ -143,83 +804,88 @@ impl Encoder for OggVorbisEncoder {
         let wav_spec = wav_reader.spec();
 
         // Validate format requirements
-        if wav_spec.channels != 1 {
-            return Err(EncodingError::InvalidFormat(format!(
-                "Expected mono audio, got {} channels",
-                wav_spec.channels
-            )));
+        if wav_spec.channels == 0 {
+            return Err(EncodingError::InvalidFormat(
+                "Audio without channels".to_string(),
+            ));
         }
+        let channels = self.output_channels(wav_spec.channels);
 
-        // Calculate duration and initial size estimate
-        let total_samples = wav_reader.len();
-        let duration_seconds = total_samples as f64 / wav_spec.sample_rate as f64;
-        let _initial_estimate = self.estimate_file_size(duration_seconds);
+        // Calculate duration
+        let total_frames = wav_reader.len() / wav_spec.channels as u32;
+        let duration_seconds = total_frames as f64 / wav_spec.sample_rate as f64;
+        let (sample_rate, bitrate) = self.encoding_params(wav_spec.sample_rate);
 
-        // Create OGG output file
-        let output_file = File::create(&output_path)?;
-        let output_writer = BufWriter::new(output_file);
-
-        // Create Vorbis encoder with target bitrate
-        let mut encoder = VorbisEncoderBuilder::new(
-            NonZero::new(wav_spec.sample_rate).unwrap(),
-            NonZero::new(1u8).unwrap(), // mono (1 channel)
-            output_writer,
-        )?
-        .bitrate_management_strategy(VorbisBitrateManagementStrategy::Vbr {
-            target_bitrate: NonZero::new(self.bitrate as u32).unwrap(),
-        })
-        .build()?;
-
-        // Read all samples into memory for processing
+        // Read all samples into memory for processing, one buffer per channel
         let samples: Result<Vec<f32>, _> = wav_reader
             .samples::<i16>()
             .map(|s| s.map(|sample| sample as f32 / i16::MAX as f32))
             .collect();
-        let samples = samples?;
-
-        // Process samples in chunks for better memory management and progress reporting
-        let chunk_size = wav_spec.sample_rate as usize / 10; // 100ms chunks
-        let total_chunks = samples.len().div_ceil(chunk_size);
-        let mut chunks_processed = 0;
-        let mut size_warning_sent = false;
-
-        for chunk in samples.chunks(chunk_size) {
-            // Convert to the format expected by vorbis_rs (Vec<Vec<f32>> for multi-channel)
-            let mono_samples = vec![chunk.to_vec()];
-
-            // Encode the chunk
-            encoder.encode_audio_block(mono_samples)?;
-
-            chunks_processed += 1;
+        let mut channel_samples =
+            deinterleave(&samples?, wav_spec.channels as usize, channels == 1);
+        for samples in channel_samples.iter_mut() {
+            if sample_rate != wav_spec.sample_rate {
+                *samples = resample(
+                    samples,
+                    wav_spec.sample_rate,
+                    sample_rate,
+                    self.options.complexity,
+                );
+            }
+            if self.options.noise_suppression {
+                *samples = suppress_noise(samples, sample_rate);
+            }
+        }
+        if self.options.dtx {
+            silence_quiet_chunks(&mut channel_samples, chunk_frames(sample_rate));
+        }
 
-            // Estimate current file size (rough estimation during encoding)
-            let progress_ratio = chunks_processed as f64 / total_chunks as f64;
-            let estimated_current_size =
-                (self.estimate_file_size(duration_seconds) as f64 * progress_ratio) as u64;
+        let estimated_total = self.estimate_file_size(duration_seconds, channels, sample_rate);
+        let mut progress = ProgressReporter {
+            sender: event_sender.clone(),
+            estimated_total,
+            size_limit: self.size_limit,
+            size_warning_sent: false,
+        };
+        match format {
+            EncoderFormat::Ogg => self.write_ogg(
+                &channel_samples,
+                sample_rate,
+                bitrate,
+                &output_path,
+                &mut progress,
+            )?,
+            EncoderFormat::Mp3 => self.write_mp3(
+                &channel_samples,
+                sample_rate,
+                bitrate,
+                &output_path,
+                &mut progress,
+            )?,
+            EncoderFormat::Flac => {
+                write_flac(&channel_samples, sample_rate, &output_path, &mut progress)?
+            }
+        }
 
-            // Send progress event
+        // Fail fast on a corrupt file rather than uploading it
+        if let Err(e) = verify_encoded_output(
+            &output_path,
+            format,
+            channels as u8,
+            sample_rate,
+            duration_seconds,
+        ) {
             if let Some(ref sender) = event_sender {
-                let _ = sender.send(EncodingEvent::Progress {
-                    bytes_processed: estimated_current_size,
-                    estimated_total: self.estimate_file_size(duration_seconds),
+                let _ = sender.send(EncodingEvent::Error {
+                    message: e.to_string(),
                 });
-
-                // Send size warning if approaching limit
-                if !size_warning_sent && estimated_current_size > self.size_limit {
-                    size_warning_sent = true;
-                    let _ = sender.send(EncodingEvent::SizeAlmostLimit {
-                        estimated_size: estimated_current_size,
-                    });
-                }
             }
+            return Err(e);
         }
 
-        // Finalize the encoder (this writes remaining data and closes the stream)
-        encoder.finish()?;
-
         // Get actual file size
         let actual_size = std::fs::metadata(&output_path)?.len();
-        let final_estimate = self.estimate_file_size(duration_seconds);
+        let final_estimate = estimated_total;
 
         // Check forecast accuracy (should be ≤2% error)
         let forecast_error = if actual_size > 0 {
@@ -233,7 +899,9 @@ impl Encoder for OggVorbisEncoder {
         let ogg_info = OggInfo {
             size_estimate: final_estimate,
             path: output_path,
+            format,
             actual_size: Some(actual_size),
+            noise_suppressed: self.options.noise_suppression,
         };
 
         // Send completion event
@@ -247,6 +915,159 @@ impl Encoder for OggVorbisEncoder {
     }
 }
 
+/// Split interleaved samples into one buffer per channel, or average them into
+/// a single buffer when `downmix`
+fn deinterleave(samples: &[f32], channels: usize, downmix: bool) -> Vec<Vec<f32>> {
+    if channels <= 1 {
+        return vec![samples.to_vec()];
+    }
+    if downmix {
+        return vec![samples
+            .chunks(channels)
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect()];
+    }
+    (0..channels)
+        .map(|channel| {
+            samples
+                .iter()
+                .skip(channel)
+                .step_by(channels)
+                .copied()
+                .collect()
+        })
+        .collect()
+}
+
+/// Whether a block is quiet enough to be sent as silence (DTX)
+pub(crate) fn is_silent(block: &[f32]) -> bool {
+    if block.is_empty() {
+        return true;
+    }
+    let energy: f32 = block.iter().map(|sample| sample * sample).sum();
+    (energy / block.len() as f32).sqrt() < DTX_SILENCE_RMS
+}
+
+/// Replace the near-silent chunks (across all channels) with digital silence
+fn silence_quiet_chunks(channel_samples: &mut [Vec<f32>], chunk_size: usize) {
+    let frames = channel_samples.first().map_or(0, Vec::len);
+    for start in (0..frames).step_by(chunk_size) {
+        let end = (start + chunk_size).min(frames);
+        if channel_samples
+            .iter()
+            .all(|samples| is_silent(&samples[start..end]))
+        {
+            for samples in channel_samples.iter_mut() {
+                samples[start..end].fill(0.0);
+            }
+        }
+    }
+}
+
+/// Noise suppression in the spirit of RNNoise: a gain per 10ms frame
+///
+/// The noise floor is tracked as the minimum frame energy (it falls at once and
+/// rises slowly, so steady hum or fan noise is learned while speech is not).
+/// Each frame is attenuated by a Wiener-like gain from its energy over that
+/// floor, down to `DENOISE_MIN_GAIN`. The gain rises at once on speech onsets
+/// and falls smoothly, and is interpolated across each frame to avoid clicks.
+fn suppress_noise(samples: &[f32], sample_rate: u32) -> Vec<f32> {
+    let frame_len = (sample_rate * DENOISE_FRAME_MS / 1000).max(1) as usize;
+    let mut output = Vec::with_capacity(samples.len());
+    let mut noise_floor: Option<f32> = None;
+    let mut previous_gain = 1.0;
+
+    for frame in samples.chunks(frame_len) {
+        let energy = frame.iter().map(|sample| sample * sample).sum::<f32>() / frame.len() as f32;
+        let floor = noise_floor
+            .map_or(energy, |floor| energy.min(floor * DENOISE_FLOOR_RISE))
+            .max(f32::EPSILON);
+        noise_floor = Some(floor);
+
+        let target = if energy > 0.0 {
+            (1.0 - DENOISE_OVER_SUBTRACTION * floor / energy)
+                .max(0.0)
+                .sqrt()
+                .max(DENOISE_MIN_GAIN)
+        } else {
+            DENOISE_MIN_GAIN
+        };
+        let gain = if target >= previous_gain {
+            target
+        } else {
+            previous_gain * DENOISE_RELEASE + target * (1.0 - DENOISE_RELEASE)
+        };
+
+        let step = (gain - previous_gain) / frame.len() as f32;
+        output.extend(
+            frame
+                .iter()
+                .enumerate()
+                .map(|(index, sample)| sample * (previous_gain + step * (index + 1) as f32)),
+        );
+        previous_gain = gain;
+    }
+
+    output
+}
+
+/// Downsample mono audio
+///
+/// Complexity 0-4 averages the input samples covered by each output sample;
+/// 5-10 uses a windowed-sinc low-pass with more taps as complexity grows.
+fn resample(samples: &[f32], from_rate: u32, to_rate: u32, complexity: u8) -> Vec<f32> {
+    if samples.is_empty() || from_rate == to_rate {
+        return samples.to_vec();
+    }
+
+    let step = from_rate as f64 / to_rate as f64;
+    let output_len = (samples.len() as f64 / step) as usize;
+
+    if complexity < 5 {
+        return (0..output_len)
+            .map(|index| {
+                let start = (index as f64 * step) as usize;
+                let end = (((index + 1) as f64 * step) as usize).clamp(start + 1, samples.len());
+                samples[start..end].iter().sum::<f32>() / (end - start) as f32
+            })
+            .collect();
+    }
+
+    let half_taps = 2 * complexity as i64;
+    let cutoff = (to_rate as f64 / from_rate as f64).min(1.0);
+    (0..output_len)
+        .map(|index| {
+            let position = index as f64 * step;
+            let center = position.floor() as i64;
+            let mut sum = 0.0;
+            let mut weights = 0.0;
+            for k in (center - half_taps + 1)..=(center + half_taps) {
+                if k < 0 || k as usize >= samples.len() {
+                    continue;
+                }
+                let distance = position - k as f64;
+                let sinc = if distance == 0.0 {
+                    1.0
+                } else {
+                    let x = std::f64::consts::PI * cutoff * distance;
+                    x.sin() / x
+                };
+                // Hann window over the filter span
+                let window =
+                    0.5 + 0.5 * (std::f64::consts::PI * distance / (half_taps as f64 + 1.0)).cos();
+                let weight = sinc * window;
+                sum += samples[k as usize] as f64 * weight;
+                weights += weight;
+            }
+            if weights.abs() > f64::EPSILON {
+                (sum / weights) as f32
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -328,13 +1149,215 @@ mod tests {
         assert_eq!(custom_encoder.size_limit, 50 * 1024 * 1024);
 
         // Test size estimation
-        let estimate = encoder.estimate_file_size(10.0); // 10 seconds
+        let estimate = encoder.estimate_file_size(10.0, 1, 48000); // 10 seconds
         assert!(estimate > 0);
         assert!(estimate < 1024 * 1024); // Should be reasonable for 10s at 32kbps
 
         Ok(())
     }
 
+    #[test]
+    fn test_encoder_options_voip_resampling_and_dtx() {
+        let encoder = OggVorbisEncoder::new().with_options(EncoderOptions {
+            complexity: 42,
+            application: EncoderApplication::Voip,
+            dtx: true,
+            noise_suppression: false,
+            downmix_to_mono: true,
+            format: EncoderFormat::Ogg,
+            vbr: true,
+        });
+        assert_eq!(encoder.options().complexity, 10);
+        assert_eq!(encoder.encoding_params(48000), (VOIP_SAMPLE_RATE, 24000));
+        assert_eq!(
+            OggVorbisEncoder::new().encoding_params(48000),
+            (48000, 32000)
+        );
+
+        // A constant signal survives both resamplers with its length divided by 3
+        let constant = vec![0.5; 4800];
+        for complexity in [0, 10] {
+            let resampled = resample(&constant, 48000, 16000, complexity);
+            assert_eq!(resampled.len(), 1600);
+            assert!(resampled.iter().all(|s| (s - 0.5).abs() < 1e-4));
+        }
+
+        assert!(is_silent(&[0.001; 100]));
+        assert!(!is_silent(&[0.1; 100]));
+    }
+
+    #[test]
+    fn test_downmix_to_mono() {
+        let stereo = [0.5, -0.5, 0.2, 0.4, 1.0, 0.0];
+        assert_eq!(deinterleave(&stereo, 2, true), vec![vec![0.0, 0.3, 0.5]]);
+        assert_eq!(
+            deinterleave(&stereo, 2, false),
+            vec![vec![0.5, 0.2, 1.0], vec![-0.5, 0.4, 0.0]]
+        );
+        assert_eq!(deinterleave(&[0.1, 0.2], 1, true), vec![vec![0.1, 0.2]]);
+
+        let downmixing = OggVorbisEncoder::new();
+        assert_eq!(downmixing.output_channels(2), 1);
+        let keeping = OggVorbisEncoder::new().with_options(EncoderOptions {
+            downmix_to_mono: false,
+            ..EncoderOptions::default()
+        });
+        assert_eq!(keeping.output_channels(2), 2);
+        assert_eq!(
+            keeping.estimate_file_size(10.0, 2, 48000),
+            2 * keeping.estimate_file_size(10.0, 1, 48000)
+        );
+    }
+
+    #[test]
+    fn test_noise_suppression_attenuates_noise_and_keeps_speech() {
+        let rms = |samples: &[f32]| {
+            (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+        };
+
+        // 0.5 s of low-level noise, then 0.5 s of a loud tone over the same noise
+        let mut seed: u32 = 1;
+        let mut noise = || {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (seed >> 8) as f32 / (1 << 24) as f32 * 0.02 - 0.01
+        };
+        let mut samples: Vec<f32> = (0..8000).map(|_| noise()).collect();
+        samples.extend((0..8000).map(|index| {
+            (index as f32 * 440.0 * std::f32::consts::TAU / 16000.0).sin() * 0.5 + noise()
+        }));
+
+        let denoised = suppress_noise(&samples, 16000);
+        assert_eq!(denoised.len(), samples.len());
+
+        // Noise is cut by more than 20 dB once the floor is learned
+        assert!(rms(&denoised[1600..8000]) < rms(&samples[1600..8000]) * 0.1);
+        // The tone keeps its level
+        let tone = rms(&denoised[8800..]) / rms(&samples[8800..]);
+        assert!(tone > 0.95 && tone <= 1.0, "tone gain {}", tone);
+    }
+
+    /// Write an OGG stream with a fake Vorbis identification header
+    fn write_test_ogg(path: &Path, sample_rate: u32, granules: &[u64], end_stream: bool) {
+        use ogg::writing::PacketWriteEndInfo;
+
+        let mut identification = vec![1];
+        identification.extend_from_slice(b"vorbis");
+        identification.extend_from_slice(&0u32.to_le_bytes());
+        identification.push(1);
+        identification.extend_from_slice(&sample_rate.to_le_bytes());
+        identification.resize(30, 0);
+
+        let mut writer = ogg::PacketWriter::new(File::create(path).unwrap());
+        writer
+            .write_packet(identification, 7, PacketWriteEndInfo::EndPage, 0)
+            .unwrap();
+        for (index, &granule) in granules.iter().enumerate() {
+            let end = if end_stream && index == granules.len() - 1 {
+                PacketWriteEndInfo::EndStream
+            } else {
+                PacketWriteEndInfo::EndPage
+            };
+            writer.write_packet(vec![0u8; 16], 7, end, granule).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_verify_ogg_file_reads_stream_info() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("valid.ogg");
+        write_test_ogg(&path, 48000, &[0, 0, 48000, 96000], true);
+
+        let stream = verify_ogg_file(&path).unwrap();
+        assert_eq!(stream.sample_rate, 48000);
+        assert_eq!(stream.channels, 1);
+        assert_eq!(stream.duration_seconds, 2.0);
+        assert_eq!(stream.packets, 5);
+
+        assert!(verify_encoded_output(&path, EncoderFormat::Ogg, 1, 48000, 2.1).is_ok());
+        assert!(matches!(
+            verify_encoded_output(&path, EncoderFormat::Ogg, 1, 48000, 10.0),
+            Err(EncodingError::VerificationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_ogg_file_rejects_corrupt_streams() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let truncated = temp_dir.path().join("truncated.ogg");
+        write_test_ogg(&truncated, 48000, &[0, 0, 48000], false);
+        let error = verify_ogg_file(&truncated).unwrap_err().to_string();
+        assert!(error.contains("truncated"), "{}", error);
+
+        let flipped = temp_dir.path().join("flipped.ogg");
+        write_test_ogg(&flipped, 48000, &[0, 0, 48000], true);
+        let mut bytes = std::fs::read(&flipped).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+        std::fs::write(&flipped, bytes).unwrap();
+        let error = verify_ogg_file(&flipped).unwrap_err().to_string();
+        assert!(error.contains("corrupt OGG stream"), "{}", error);
+
+        let not_ogg = temp_dir.path().join("not.ogg");
+        std::fs::write(&not_ogg, b"RIFF....WAVE").unwrap();
+        assert!(verify_ogg_file(&not_ogg).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_encode_flac() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let wav_path = temp_dir.path().join("lossless.wav");
+        create_test_wav(&wav_path, 1.0, 16000)?;
+
+        let encoder = OggVorbisEncoder::new().with_format(EncoderFormat::Flac);
+        let result = encoder.encode(&wav_path, None, None).await?;
+        assert_eq!(result.path, temp_dir.path().join("lossless.flac"));
+        assert_eq!(result.format, EncoderFormat::Flac);
+
+        let stream = verify_flac_file(&result.path)?;
+        assert_eq!(stream.sample_rate, 16000);
+        assert_eq!(stream.channels, 1);
+        assert_eq!(stream.bits_per_sample, 16);
+        assert_eq!(stream.duration_seconds, 1.0);
+        assert!(result.actual_size.unwrap() < std::fs::metadata(&wav_path)?.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_encoder_formats() {
+        assert_eq!(EncoderFormat::default(), EncoderFormat::Ogg);
+        assert_eq!(
+            EncoderFormat::from_extension("MP3"),
+            Some(EncoderFormat::Mp3)
+        );
+        assert_eq!(EncoderFormat::from_extension("wav"), None);
+        assert_eq!(EncoderFormat::Flac.extension(), "flac");
+        assert_eq!(EncoderFormat::Mp3.mime_type(), "audio/mpeg");
+
+        // MP3 holds two channels at most: more are downmixed
+        let mp3 = OggVorbisEncoder::new().with_options(EncoderOptions {
+            downmix_to_mono: false,
+            format: EncoderFormat::Mp3,
+            ..EncoderOptions::default()
+        });
+        assert_eq!(mp3.output_channels(2), 2);
+        assert_eq!(mp3.output_channels(4), 1);
+
+        // FLAC is estimated from the PCM size, not the bitrate
+        let flac = OggVorbisEncoder::new().with_format(EncoderFormat::Flac);
+        assert_eq!(flac.estimate_file_size(1.0, 1, 16000), 19200);
+
+        // MPEG-2 layer III, 16 kHz, mono, after an empty ID3v2 tag
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("frame.mp3");
+        let mut bytes = b"ID3\x04\x00\x00\x00\x00\x00\x00".to_vec();
+        bytes.extend_from_slice(&[0xFF, 0xF3, 0x88, 0xC4]);
+        std::fs::write(&path, &bytes).unwrap();
+        assert_eq!(read_mp3_frame_header(&path).unwrap(), (16000, 1));
+        std::fs::write(&path, b"not an mp3").unwrap();
+        assert!(read_mp3_frame_header(&path).is_err());
+    }
+
     #[tokio::test]
     #[ignore] // Temporarily disabled due to memory corruption in vorbis_rs
     async fn test_encode_long_wav() -> Result<(), Box<dyn std::error::Error>> {
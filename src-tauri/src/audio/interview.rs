@@ -0,0 +1,440 @@
+//! Dual-source capture for interview recordings
+//!
+//! Records the microphone and a loopback source (what the remote party says
+//! through the speakers) at the same time. The result is a single stereo WAV
+//! with the microphone on channel 1 and the loopback on channel 2, which can
+//! be split back into one mono file per speaker for transcription.
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{Device, StreamConfig, SupportedStreamConfig};
+use hound::{WavReader, WavSpec, WavWriter};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+use tempfile::NamedTempFile;
+
+use crate::audio::capture::{
+    AudioCaptureError, AudioCaptureResult, LiveAudioCapture, CAPTURE_SAMPLE_RATE,
+};
+
+/// Device name fragments that identify loopback inputs when none is configured
+/// (PulseAudio/PipeWire monitors, Windows "Stereo Mix", macOS virtual drivers)
+const LOOPBACK_NAME_HINTS: &[&str] = &["monitor", "loopback", "stereo mix", "blackhole"];
+
+/// Mono samples captured from one source
+#[derive(Debug, Default)]
+struct ChannelBuffer {
+    samples: Vec<i16>,
+    sample_rate: u32,
+}
+
+/// Running capture: the stream thread and the buffers it fills
+struct DualSourceSession {
+    stop_sender: mpsc::Sender<()>,
+    worker: JoinHandle<()>,
+    mic: Arc<Mutex<ChannelBuffer>>,
+    loopback: Arc<Mutex<ChannelBuffer>>,
+}
+
+/// Records the microphone and a loopback source into one stereo WAV
+pub struct DualSourceCapture {
+    /// Microphone device name (None = system default)
+    mic_device: Option<String>,
+    /// Loopback device name (None = auto-detect)
+    loopback_device: Option<String>,
+    is_recording: Arc<AtomicBool>,
+    session: Mutex<Option<DualSourceSession>>,
+}
+
+impl DualSourceCapture {
+    /// Create a capture for the given microphone and loopback devices
+    pub fn new(mic_device: Option<String>, loopback_device: Option<String>) -> Self {
+        Self {
+            mic_device,
+            loopback_device,
+            is_recording: Arc::new(AtomicBool::new(false)),
+            session: Mutex::new(None),
+        }
+    }
+
+    /// Check if currently recording
+    pub fn is_recording(&self) -> bool {
+        self.is_recording.load(Ordering::Relaxed)
+    }
+
+    /// Open both devices and start recording
+    pub fn start(&self) -> AudioCaptureResult<()> {
+        let mut session = self
+            .session
+            .lock()
+            .map_err(|_| AudioCaptureError::StreamCreation("Capture state poisoned".to_string()))?;
+        if session.is_some() {
+            return Err(AudioCaptureError::StreamCreation(
+                "Already recording".to_string(),
+            ));
+        }
+
+        let (mic_device, mic_config) = find_input_device(self.mic_device.as_deref())?;
+        let (loopback_device, loopback_config) =
+            find_loopback_device(self.loopback_device.as_deref())?;
+        eprintln!(
+            "🎙️ [INTERVIEW] Recording mic '{}' + loopback '{}'",
+            mic_device.name().unwrap_or_default(),
+            loopback_device.name().unwrap_or_default()
+        );
+
+        let mic = Arc::new(Mutex::new(ChannelBuffer {
+            samples: Vec::new(),
+            sample_rate: mic_config.sample_rate().0,
+        }));
+        let loopback = Arc::new(Mutex::new(ChannelBuffer {
+            samples: Vec::new(),
+            sample_rate: loopback_config.sample_rate().0,
+        }));
+
+        let (stop_sender, stop_receiver) = mpsc::channel();
+        let (ready_sender, ready_receiver) = mpsc::channel();
+        let is_recording = Arc::clone(&self.is_recording);
+        let mic_buffer = Arc::clone(&mic);
+        let loopback_buffer = Arc::clone(&loopback);
+        is_recording.store(true, Ordering::Relaxed);
+
+        // Streams are not Send on every platform: they live on a dedicated thread
+        let worker = std::thread::spawn(move || {
+            let streams = build_stream(&mic_device, &mic_config, mic_buffer, &is_recording)
+                .and_then(|mic_stream| {
+                    let loopback_stream = build_stream(
+                        &loopback_device,
+                        &loopback_config,
+                        loopback_buffer,
+                        &is_recording,
+                    )?;
+                    Ok((mic_stream, loopback_stream))
+                });
+
+            match streams {
+                Ok(streams) => {
+                    let _ = ready_sender.send(Ok(()));
+                    // Keep both streams alive until stop
+                    let _ = stop_receiver.recv();
+                    drop(streams);
+                }
+                Err(e) => {
+                    is_recording.store(false, Ordering::Relaxed);
+                    let _ = ready_sender.send(Err(e));
+                }
+            }
+        });
+
+        match ready_receiver.recv() {
+            Ok(Ok(())) => {
+                *session = Some(DualSourceSession {
+                    stop_sender,
+                    worker,
+                    mic,
+                    loopback,
+                });
+                Ok(())
+            }
+            Ok(Err(e)) => {
+                let _ = worker.join();
+                Err(e)
+            }
+            Err(_) => {
+                self.is_recording.store(false, Ordering::Relaxed);
+                Err(AudioCaptureError::StreamCreation(
+                    "Interview capture thread exited".to_string(),
+                ))
+            }
+        }
+    }
+
+    /// Stop recording and write the stereo WAV (ch1 = mic, ch2 = loopback)
+    pub fn stop(&self) -> AudioCaptureResult<PathBuf> {
+        let session = self
+            .session
+            .lock()
+            .map_err(|_| AudioCaptureError::StreamCreation("Capture state poisoned".to_string()))?
+            .take()
+            .ok_or_else(|| {
+                AudioCaptureError::StreamCreation("Not currently recording".to_string())
+            })?;
+
+        self.is_recording.store(false, Ordering::Relaxed);
+        let _ = session.stop_sender.send(());
+        let _ = session.worker.join();
+
+        let mic = take_channel(&session.mic);
+        let loopback = take_channel(&session.loopback);
+
+        let temp_file = tempfile::Builder::new().suffix(".wav").tempfile()?;
+        let (_, path) = temp_file.keep()?;
+        write_stereo_wav(&path, &mic, &loopback, CAPTURE_SAMPLE_RATE)?;
+        Ok(path)
+    }
+}
+
+/// Split an interview WAV into one mono WAV per channel: (mic, loopback)
+pub fn split_stereo_wav(path: &Path) -> AudioCaptureResult<(NamedTempFile, NamedTempFile)> {
+    let mut reader = WavReader::open(path)?;
+    let spec = reader.spec();
+    if spec.channels != 2 {
+        return Err(AudioCaptureError::StreamConfig(format!(
+            "Expected a stereo recording, found {} channel(s)",
+            spec.channels
+        )));
+    }
+
+    let mut mic = Vec::new();
+    let mut loopback = Vec::new();
+    for (index, sample) in reader.samples::<i16>().enumerate() {
+        if index % 2 == 0 {
+            mic.push(sample?);
+        } else {
+            loopback.push(sample?);
+        }
+    }
+
+    let mic_file = tempfile::Builder::new().suffix(".wav").tempfile()?;
+    let loopback_file = tempfile::Builder::new().suffix(".wav").tempfile()?;
+    write_mono_wav(mic_file.path(), &mic, spec.sample_rate)?;
+    write_mono_wav(loopback_file.path(), &loopback, spec.sample_rate)?;
+    Ok((mic_file, loopback_file))
+}
+
+/// Input device by name, falling back to the default input device
+fn find_input_device(name: Option<&str>) -> AudioCaptureResult<(Device, SupportedStreamConfig)> {
+    let host = cpal::default_host();
+    let named = name.and_then(|name| {
+        host.input_devices()
+            .ok()?
+            .find(|device| device.name().map(|n| n == name).unwrap_or(false))
+    });
+    if name.is_some() && named.is_none() {
+        eprintln!(
+            "⚠️ [INTERVIEW] Microphone '{}' not found, using the default device",
+            name.unwrap_or_default()
+        );
+    }
+
+    let device = named
+        .or_else(|| host.default_input_device())
+        .ok_or_else(|| {
+            AudioCaptureError::AudioDeviceUnavailable(
+                "No default input device available".to_string(),
+            )
+        })?;
+    let config = LiveAudioCapture::negotiate_config(&device)?;
+    Ok((device, config))
+}
+
+/// Loopback device: the configured one, or the first input that looks like a
+/// monitor of the speakers. On Windows the default output device is used
+/// (WASAPI records output devices in loopback mode).
+pub(crate) fn find_loopback_device(
+    name: Option<&str>,
+) -> AudioCaptureResult<(Device, SupportedStreamConfig)> {
+    let host = cpal::default_host();
+
+    if let Some(name) = name {
+        let input = host.input_devices().ok().and_then(|mut devices| {
+            devices.find(|device| device.name().map(|n| n == name).unwrap_or(false))
+        });
+        if let Some(device) = input {
+            let config = LiveAudioCapture::negotiate_config(&device)?;
+            return Ok((device, config));
+        }
+
+        let output = host.output_devices().ok().and_then(|mut devices| {
+            devices.find(|device| device.name().map(|n| n == name).unwrap_or(false))
+        });
+        if let Some(device) = output {
+            let config = output_config(&device)?;
+            return Ok((device, config));
+        }
+
+        return Err(AudioCaptureError::AudioDeviceUnavailable(format!(
+            "Loopback device '{}' not found",
+            name
+        )));
+    }
+
+    let detected = host.input_devices().ok().and_then(|mut devices| {
+        devices.find(|device| device.name().map(|n| is_loopback_name(&n)).unwrap_or(false))
+    });
+    if let Some(device) = detected {
+        let config = LiveAudioCapture::negotiate_config(&device)?;
+        return Ok((device, config));
+    }
+
+    if cfg!(target_os = "windows") {
+        if let Some(device) = host.default_output_device() {
+            let config = output_config(&device)?;
+            return Ok((device, config));
+        }
+    }
+
+    Err(AudioCaptureError::AudioDeviceUnavailable(
+        "No loopback device found, set interview.loopback_device in settings".to_string(),
+    ))
+}
+
+fn output_config(device: &Device) -> AudioCaptureResult<SupportedStreamConfig> {
+    device
+        .default_output_config()
+        .map_err(|e| AudioCaptureError::StreamConfig(format!("Failed to get output config: {}", e)))
+}
+
+fn is_loopback_name(name: &str) -> bool {
+    let name = name.to_lowercase();
+    LOOPBACK_NAME_HINTS.iter().any(|hint| name.contains(hint))
+}
+
+/// Build an input stream that downmixes to mono and appends to `buffer`
+fn build_stream(
+    device: &Device,
+    device_config: &SupportedStreamConfig,
+    buffer: Arc<Mutex<ChannelBuffer>>,
+    is_recording: &Arc<AtomicBool>,
+) -> AudioCaptureResult<cpal::Stream> {
+    let config = StreamConfig {
+        channels: device_config.channels(),
+        sample_rate: device_config.sample_rate(),
+        buffer_size: cpal::BufferSize::Default,
+    };
+    let channels = config.channels as usize;
+    let is_recording = Arc::clone(is_recording);
+
+    let stream = device
+        .build_input_stream(
+            &config,
+            move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                if !is_recording.load(Ordering::Relaxed) {
+                    return;
+                }
+                if let Ok(mut buffer) = buffer.lock() {
+                    buffer.samples.extend(downmix_to_mono(data, channels));
+                }
+            },
+            |err| {
+                eprintln!("Interview stream error: {}", err);
+            },
+            None,
+        )
+        .map_err(|e| AudioCaptureError::StreamCreation(e.to_string()))?;
+    stream
+        .play()
+        .map_err(|e| AudioCaptureError::StreamCreation(e.to_string()))?;
+    Ok(stream)
+}
+
+/// Average interleaved frames into 16-bit mono samples
+fn downmix_to_mono(data: &[f32], channels: usize) -> impl Iterator<Item = i16> + '_ {
+    data.chunks(channels.max(1)).map(|frame| {
+        let mixed = frame.iter().sum::<f32>() / frame.len() as f32;
+        (mixed.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+    })
+}
+
+/// Move the captured samples out, resampled to the recording rate
+fn take_channel(buffer: &Mutex<ChannelBuffer>) -> Vec<i16> {
+    match buffer.lock() {
+        Ok(mut buffer) => {
+            let samples = std::mem::take(&mut buffer.samples);
+            resample_linear(&samples, buffer.sample_rate, CAPTURE_SAMPLE_RATE)
+        }
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Linear-interpolation resampling (devices may not run at 48kHz)
+fn resample_linear(samples: &[i16], from_rate: u32, to_rate: u32) -> Vec<i16> {
+    if from_rate == to_rate || from_rate == 0 || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let output_len = (samples.len() as u64 * to_rate as u64 / from_rate as u64) as usize;
+    let step = from_rate as f64 / to_rate as f64;
+    (0..output_len)
+        .map(|index| {
+            let position = index as f64 * step;
+            let base = position.floor() as usize;
+            let next = (base + 1).min(samples.len() - 1);
+            let fraction = position - base as f64;
+            let value = samples[base] as f64 * (1.0 - fraction) + samples[next] as f64 * fraction;
+            value.round() as i16
+        })
+        .collect()
+}
+
+/// Write two mono channels as one stereo WAV, padding the shorter one with silence
+fn write_stereo_wav(
+    path: &Path,
+    left: &[i16],
+    right: &[i16],
+    sample_rate: u32,
+) -> AudioCaptureResult<()> {
+    let spec = WavSpec {
+        channels: 2,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(path, spec)?;
+    for index in 0..left.len().max(right.len()) {
+        writer.write_sample(left.get(index).copied().unwrap_or(0))?;
+        writer.write_sample(right.get(index).copied().unwrap_or(0))?;
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+fn write_mono_wav(path: &Path, samples: &[i16], sample_rate: u32) -> AudioCaptureResult<()> {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(path, spec)?;
+    for &sample in samples {
+        writer.write_sample(sample)?;
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stereo_round_trip_keeps_channels_apart() {
+        let stereo = NamedTempFile::new().unwrap();
+        write_stereo_wav(stereo.path(), &[1, 2, 3], &[-1, -2], 48000).unwrap();
+
+        let (mic, loopback) = split_stereo_wav(stereo.path()).unwrap();
+
+        let read = |file: &NamedTempFile| -> Vec<i16> {
+            WavReader::open(file.path())
+                .unwrap()
+                .samples::<i16>()
+                .map(|s| s.unwrap())
+                .collect()
+        };
+        assert_eq!(read(&mic), vec![1, 2, 3]);
+        assert_eq!(read(&loopback), vec![-1, -2, 0]);
+    }
+
+    #[test]
+    fn test_downmix_and_resample() {
+        let mono: Vec<i16> = downmix_to_mono(&[0.5, -0.5, 1.0, 1.0], 2).collect();
+        assert_eq!(mono, vec![0, i16::MAX]);
+
+        let upsampled = resample_linear(&[0, 100], 24000, 48000);
+        assert_eq!(upsampled, vec![0, 50, 100, 100]);
+        assert!(is_loopback_name("Monitor of Built-in Audio Analog Stereo"));
+        assert!(!is_loopback_name("USB Microphone"));
+    }
+}
@@ -0,0 +1,245 @@
+//! Loudness normalization of recordings
+//!
+//! Quiet microphones produce poor transcriptions. Once a recording is
+//! finalized, its integrated loudness is measured following ITU-R BS.1770
+//! (K-weighting, 400ms blocks with 75% overlap, absolute gate at -70 LUFS and
+//! relative gate at -10 LU) and a single gain brings it to the target. The
+//! gain is capped so peaks stay under -1 dBFS and background noise of a near
+//! silent recording is not boosted without bound.
+
+use hound::{WavReader, WavWriter};
+use std::path::Path;
+
+use crate::audio::capture::AudioCaptureResult;
+
+/// Default loudness target (LUFS), a common level for speech
+pub const DEFAULT_TARGET_LUFS: f32 = -18.0;
+
+/// Highest gain applied by the normalization
+const MAX_NORMALIZATION_GAIN_DB: f32 = 24.0;
+
+/// Peak level the normalized recording may reach (-1 dBFS)
+const PEAK_CEILING: f32 = 0.891;
+
+/// Gains smaller than this are not worth rewriting the file
+const MIN_NORMALIZATION_GAIN_DB: f32 = 0.1;
+
+/// Measurement block length and step (75% overlap)
+const BLOCK_MS: u32 = 400;
+const BLOCK_STEP_MS: u32 = 100;
+
+/// Blocks quieter than this are ignored (absolute gate)
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+
+/// Blocks this far below the ungated loudness are ignored (relative gate)
+const RELATIVE_GATE_LU: f64 = -10.0;
+
+/// Second-order IIR filter (direct form I)
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 3]) -> Self {
+        Self {
+            b: [b[0] / a[0], b[1] / a[0], b[2] / a[0]],
+            a: [a[1] / a[0], a[2] / a[0]],
+            x: [0.0; 2],
+            y: [0.0; 2],
+        }
+    }
+
+    /// First K-weighting stage: high shelf modelling the head (+4 dB)
+    fn high_shelf(sample_rate: u32) -> Self {
+        let a = 10f64.powf(4.0 / 40.0);
+        let w0 = 2.0 * std::f64::consts::PI * 1500.0 / sample_rate as f64;
+        let alpha = w0.sin() / (2.0 * std::f64::consts::FRAC_1_SQRT_2);
+        let (cos, sqrt_a) = (w0.cos(), a.sqrt());
+        Self::new(
+            [
+                a * ((a + 1.0) + (a - 1.0) * cos + 2.0 * sqrt_a * alpha),
+                -2.0 * a * ((a - 1.0) + (a + 1.0) * cos),
+                a * ((a + 1.0) + (a - 1.0) * cos - 2.0 * sqrt_a * alpha),
+            ],
+            [
+                (a + 1.0) - (a - 1.0) * cos + 2.0 * sqrt_a * alpha,
+                2.0 * ((a - 1.0) - (a + 1.0) * cos),
+                (a + 1.0) - (a - 1.0) * cos - 2.0 * sqrt_a * alpha,
+            ],
+        )
+    }
+
+    /// Second K-weighting stage: high pass at 38 Hz
+    fn high_pass(sample_rate: u32) -> Self {
+        let w0 = 2.0 * std::f64::consts::PI * 38.0 / sample_rate as f64;
+        let alpha = w0.sin() / (2.0 * 0.5);
+        let cos = w0.cos();
+        Self::new(
+            [(1.0 + cos) / 2.0, -(1.0 + cos), (1.0 + cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    fn process(&mut self, input: f64) -> f64 {
+        let output = self.b[0] * input + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+            - self.a[0] * self.y[0]
+            - self.a[1] * self.y[1];
+        self.x = [input, self.x[0]];
+        self.y = [output, self.y[0]];
+        output
+    }
+}
+
+/// Integrated loudness (LUFS) of mono audio, None when it is all silence
+pub fn integrated_loudness(samples: &[f32], sample_rate: u32) -> Option<f32> {
+    let block_len = (sample_rate * BLOCK_MS / 1000) as usize;
+    let step = (sample_rate * BLOCK_STEP_MS / 1000).max(1) as usize;
+    if block_len == 0 || samples.len() < block_len {
+        return None;
+    }
+
+    let mut shelf = Biquad::high_shelf(sample_rate);
+    let mut high_pass = Biquad::high_pass(sample_rate);
+    let squared: Vec<f64> = samples
+        .iter()
+        .map(|&sample| {
+            let weighted = high_pass.process(shelf.process(sample as f64));
+            weighted * weighted
+        })
+        .collect();
+
+    let loudness = |power: f64| -0.691 + 10.0 * power.log10();
+    let blocks: Vec<f64> = (0..=(squared.len() - block_len) / step)
+        .map(|index| {
+            let start = index * step;
+            squared[start..start + block_len].iter().sum::<f64>() / block_len as f64
+        })
+        .filter(|&power| power > 0.0 && loudness(power) > ABSOLUTE_GATE_LUFS)
+        .collect();
+    if blocks.is_empty() {
+        return None;
+    }
+
+    let ungated = loudness(blocks.iter().sum::<f64>() / blocks.len() as f64);
+    let gated: Vec<f64> = blocks
+        .into_iter()
+        .filter(|&power| loudness(power) > ungated + RELATIVE_GATE_LU)
+        .collect();
+    Some(loudness(gated.iter().sum::<f64>() / gated.len() as f64) as f32)
+}
+
+/// Gain (dB) bringing audio at `loudness` to `target_lufs`, capped so the
+/// `peak` (linear, 0-1) stays under -1 dBFS
+pub fn normalization_gain_db(loudness: f32, target_lufs: f32, peak: f32) -> f32 {
+    let gain_db = (target_lufs - loudness).min(MAX_NORMALIZATION_GAIN_DB);
+    if peak <= 0.0 {
+        return gain_db;
+    }
+    let headroom_db = 20.0 * (PEAK_CEILING / peak).log10();
+    gain_db.min(headroom_db)
+}
+
+/// Normalize a mono 16-bit WAV file in place to `target_lufs`
+///
+/// Returns the gain applied in dB (None for silent recordings, left as is).
+pub fn normalize_wav(path: &Path, target_lufs: f32) -> AudioCaptureResult<Option<f32>> {
+    let mut reader = WavReader::open(path)?;
+    let spec = reader.spec();
+    let samples: Vec<f32> = reader
+        .samples::<i16>()
+        .map(|sample| sample.map(|sample| sample as f32 / i16::MAX as f32))
+        .collect::<Result<_, _>>()?;
+    drop(reader);
+
+    let Some(loudness) = integrated_loudness(&samples, spec.sample_rate) else {
+        return Ok(None);
+    };
+    let peak = samples
+        .iter()
+        .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+    let gain_db = normalization_gain_db(loudness, target_lufs, peak);
+    if gain_db.abs() < MIN_NORMALIZATION_GAIN_DB {
+        return Ok(Some(0.0));
+    }
+
+    let gain = 10f32.powf(gain_db / 20.0);
+    let mut writer = WavWriter::create(path, spec)?;
+    for sample in samples {
+        writer.write_sample(((sample * gain).clamp(-1.0, 1.0) * i16::MAX as f32) as i16)?;
+    }
+    writer.finalize()?;
+    Ok(Some(gain_db))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(amplitude: f32, seconds: f32, sample_rate: u32) -> Vec<f32> {
+        (0..(seconds * sample_rate as f32) as usize)
+            .map(|index| {
+                (index as f32 * 1000.0 * std::f32::consts::TAU / sample_rate as f32).sin()
+                    * amplitude
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_integrated_loudness_of_a_1khz_tone() {
+        // BS.1770: a full-scale 1 kHz sine on one channel reads -3.01 LUFS
+        let loudness = integrated_loudness(&sine(1.0, 2.0, 48000), 48000).unwrap();
+        assert!((loudness + 3.01).abs() < 0.2, "loudness {}", loudness);
+        let loudness = integrated_loudness(&sine(0.1, 2.0, 16000), 16000).unwrap();
+        assert!((loudness + 23.01).abs() < 0.2, "loudness {}", loudness);
+
+        // Silence and audio shorter than one block are not measured
+        assert_eq!(integrated_loudness(&[0.0; 48000], 48000), None);
+        assert_eq!(integrated_loudness(&[0.5; 100], 48000), None);
+    }
+
+    #[test]
+    fn test_normalization_gain_is_capped() {
+        assert_eq!(normalization_gain_db(-30.0, -18.0, 0.1), 12.0);
+        assert_eq!(normalization_gain_db(-10.0, -18.0, 0.9), -8.0);
+        // Peak ceiling at -1 dBFS
+        assert!((normalization_gain_db(-30.0, -18.0, 0.5) - 5.0).abs() < 0.05);
+        // Never more than the maximum gain
+        assert_eq!(
+            normalization_gain_db(-60.0, -18.0, 0.001),
+            MAX_NORMALIZATION_GAIN_DB
+        );
+    }
+
+    #[test]
+    fn test_normalize_wav_reaches_the_target() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("quiet.wav");
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = WavWriter::create(&path, spec).unwrap();
+        for sample in sine(0.05, 2.0, 16000) {
+            writer
+                .write_sample((sample * i16::MAX as f32) as i16)
+                .unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let gain_db = normalize_wav(&path, -18.0).unwrap().unwrap();
+        assert!((gain_db - 11.0).abs() < 0.3, "gain {}", gain_db);
+
+        let samples: Vec<f32> = WavReader::open(&path)
+            .unwrap()
+            .samples::<i16>()
+            .map(|sample| sample.unwrap() as f32 / i16::MAX as f32)
+            .collect();
+        let loudness = integrated_loudness(&samples, 16000).unwrap();
+        assert!((loudness + 18.0).abs() < 0.2, "loudness {}", loudness);
+    }
+}
@@ -0,0 +1,196 @@
+//! Probe of the system-wide microphone mute
+//!
+//! A muted microphone (OS mute, laptop kill switch, conferencing app hotkey)
+//! still delivers a silent stream, so the capture itself cannot tell; the
+//! recording would only show up later as an empty transcript. The probe asks
+//! the platform audio stack about the default input instead:
+//!
+//! - Linux: PulseAudio/PipeWire (`pactl`), falling back to the ALSA capture
+//!   switch (`amixer`)
+//! - macOS: the input volume (`osascript`), 0 when muted
+//! - Windows: the mute flag of the default capture endpoint (Core Audio)
+
+use serde::Serialize;
+use std::process::Command;
+
+/// State of the default microphone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MicStatus {
+    /// The microphone picks up sound
+    Live,
+    /// The microphone is muted system-wide
+    Muted,
+    /// The platform could not be asked
+    Unknown,
+}
+
+/// Ask the platform whether the default microphone is muted
+///
+/// Runs a short external command; call it from a blocking task.
+pub fn probe_mic_status() -> MicStatus {
+    let status = platform_probe();
+    eprintln!("🎙️ [AUDIO] Microphone status: {:?}", status);
+    status
+}
+
+#[cfg(target_os = "linux")]
+fn platform_probe() -> MicStatus {
+    match command_output("pactl", &["get-source-mute", "@DEFAULT_SOURCE@"]) {
+        Some(output) => parse_pactl_mute(&output),
+        None => command_output("amixer", &["get", "Capture"])
+            .map_or(MicStatus::Unknown, |output| parse_amixer_capture(&output)),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn platform_probe() -> MicStatus {
+    command_output(
+        "osascript",
+        &["-e", "input volume of (get volume settings)"],
+    )
+    .map_or(MicStatus::Unknown, |output| parse_input_volume(&output))
+}
+
+#[cfg(target_os = "windows")]
+fn platform_probe() -> MicStatus {
+    // IAudioEndpointVolume::GetMute on the default capture endpoint
+    let script = r#"Add-Type -TypeDefinition @'
+using System;
+using System.Runtime.InteropServices;
+[ComImport, Guid("5CDF2C82-841E-4546-9722-0CF74078229A"), InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+interface IAudioEndpointVolume {
+    int f0(); int f1(); int f2(); int f3(); int f4(); int f5(); int f6(); int f7(); int f8(); int f9(); int f10();
+    int SetMute([MarshalAs(UnmanagedType.Bool)] bool mute, ref Guid context);
+    int GetMute([MarshalAs(UnmanagedType.Bool)] out bool mute);
+}
+[ComImport, Guid("D666063F-1587-4E43-81F1-B948E807363F"), InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+interface IMMDevice {
+    int Activate(ref Guid iid, int context, IntPtr parameters, [MarshalAs(UnmanagedType.IUnknown)] out object endpoint);
+}
+[ComImport, Guid("A95664D2-9614-4F35-A746-DE8DB63617E6"), InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+interface IMMDeviceEnumerator {
+    int EnumAudioEndpoints();
+    int GetDefaultAudioEndpoint(int dataFlow, int role, out IMMDevice device);
+}
+[ComImport, Guid("BCDE0395-E52F-467C-8E3D-C4579291692E")]
+class MMDeviceEnumerator {}
+public static class Microphone {
+    public static bool Muted() {
+        IMMDevice device;
+        Marshal.ThrowExceptionForHR(((IMMDeviceEnumerator)new MMDeviceEnumerator()).GetDefaultAudioEndpoint(1, 0, out device));
+        Guid iid = typeof(IAudioEndpointVolume).GUID;
+        object endpoint;
+        Marshal.ThrowExceptionForHR(device.Activate(ref iid, 23, IntPtr.Zero, out endpoint));
+        bool muted;
+        Marshal.ThrowExceptionForHR(((IAudioEndpointVolume)endpoint).GetMute(out muted));
+        return muted;
+    }
+}
+'@
+[Microphone]::Muted()"#;
+    match command_output(
+        "powershell",
+        &["-NoProfile", "-NonInteractive", "-Command", script],
+    )
+    .as_deref()
+    .map(str::trim)
+    {
+        Some("True") => MicStatus::Muted,
+        Some("False") => MicStatus::Live,
+        _ => MicStatus::Unknown,
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn platform_probe() -> MicStatus {
+    MicStatus::Unknown
+}
+
+/// Standard output of a command that succeeded
+#[allow(dead_code)]
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// `pactl get-source-mute` output ("Mute: yes")
+#[allow(dead_code)]
+fn parse_pactl_mute(output: &str) -> MicStatus {
+    match output.trim().strip_prefix("Mute:").map(str::trim) {
+        Some("yes") => MicStatus::Muted,
+        Some("no") => MicStatus::Live,
+        _ => MicStatus::Unknown,
+    }
+}
+
+/// `amixer get Capture` output: muted when every channel switch is `[off]`
+#[allow(dead_code)]
+fn parse_amixer_capture(output: &str) -> MicStatus {
+    let switches: Vec<bool> = output
+        .lines()
+        .filter(|line| line.contains("Capture") && line.contains('['))
+        .filter_map(|line| {
+            if line.contains("[on]") {
+                Some(true)
+            } else if line.contains("[off]") {
+                Some(false)
+            } else {
+                None
+            }
+        })
+        .collect();
+    match switches.as_slice() {
+        [] => MicStatus::Unknown,
+        switches if switches.iter().all(|on| !on) => MicStatus::Muted,
+        _ => MicStatus::Live,
+    }
+}
+
+/// macOS input volume (0-100, or "missing value" without an input device)
+#[allow(dead_code)]
+fn parse_input_volume(output: &str) -> MicStatus {
+    match output.trim().parse::<u32>() {
+        Ok(0) => MicStatus::Muted,
+        Ok(_) => MicStatus::Live,
+        Err(_) => MicStatus::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pactl_and_macos_output() {
+        assert_eq!(parse_pactl_mute("Mute: yes\n"), MicStatus::Muted);
+        assert_eq!(parse_pactl_mute("Mute: no\n"), MicStatus::Live);
+        assert_eq!(
+            parse_pactl_mute("Failed to get source mute"),
+            MicStatus::Unknown
+        );
+
+        assert_eq!(parse_input_volume("0\n"), MicStatus::Muted);
+        assert_eq!(parse_input_volume("75\n"), MicStatus::Live);
+        assert_eq!(parse_input_volume("missing value\n"), MicStatus::Unknown);
+    }
+
+    #[test]
+    fn test_parse_amixer_capture_switches() {
+        let muted = "Simple mixer control 'Capture',0\n  Capabilities: cvolume cswitch\n  \
+                     Front Left: Capture 39 [61%] [12.75dB] [off]\n  \
+                     Front Right: Capture 39 [61%] [12.75dB] [off]\n";
+        assert_eq!(parse_amixer_capture(muted), MicStatus::Muted);
+
+        let live = muted.replacen("[off]", "[on]", 1);
+        assert_eq!(parse_amixer_capture(&live), MicStatus::Live);
+
+        assert_eq!(
+            parse_amixer_capture("Simple mixer control 'Capture',0\n"),
+            MicStatus::Unknown
+        );
+    }
+}
@@ -1,5 +1,20 @@
 pub mod capture;
 pub mod encoder;
+pub mod interview;
+pub mod loudness;
+pub mod mic_status;
+pub mod playback;
+pub mod stream_encoder;
 
-pub use capture::{AudioCapture, LiveAudioCapture};
-pub use encoder::{Encoder, EncodingError, EncodingEvent, OggInfo, OggVorbisEncoder};
+pub use capture::{
+    AudioCapture, CaptureFormat, CaptureSource, InputWarning, InputWarningKind, LiveAudioCapture,
+    RecordingDeviceLost,
+};
+pub use encoder::{
+    verify_flac_file, verify_ogg_file, Encoder, EncoderApplication, EncoderFormat, EncoderOptions,
+    EncodingError, EncodingEvent, FlacStreamInfo, OggInfo, OggStreamInfo, OggVorbisEncoder,
+};
+pub use interview::DualSourceCapture;
+pub use mic_status::{probe_mic_status, MicStatus};
+pub use playback::{AudioClip, PlaybackError, PlaybackHandle};
+pub use stream_encoder::{take_streamed_output, StreamingEncoder};
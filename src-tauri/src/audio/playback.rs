@@ -0,0 +1,401 @@
+//! Audio playback on the default output device
+//!
+//! Decoded audio is held as an [`AudioClip`] of interleaved `f32` samples and
+//! played with [`play`], which converts it to the output device format and
+//! returns a [`PlaybackHandle`] to follow the position or stop playback.
+//! The CPAL stream lives on its own thread because it is not `Send`.
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{SampleFormat, StreamConfig};
+use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Errors that can occur during playback
+#[derive(Error, Debug)]
+pub enum PlaybackError {
+    #[error("No output device available")]
+    NoOutputDevice,
+    #[error("Output device does not support float samples")]
+    UnsupportedOutputFormat,
+    #[error("Failed to decode audio: {0}")]
+    Decode(String),
+    #[error("Output stream error: {0}")]
+    Stream(String),
+}
+
+/// Result type for playback operations
+pub type PlaybackResult<T> = Result<T, PlaybackError>;
+
+/// Decoded audio (interleaved `f32` samples)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioClip {
+    pub samples: Vec<f32>,
+    pub channels: u16,
+    pub sample_rate: u32,
+}
+
+impl AudioClip {
+    /// Decode a WAV file held in memory
+    pub fn from_wav_bytes(bytes: &[u8]) -> PlaybackResult<Self> {
+        let reader = hound::WavReader::new(Cursor::new(bytes))
+            .map_err(|e| PlaybackError::Decode(e.to_string()))?;
+        let spec = reader.spec();
+
+        let samples = match spec.sample_format {
+            hound::SampleFormat::Float => reader
+                .into_samples::<f32>()
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| PlaybackError::Decode(e.to_string()))?,
+            hound::SampleFormat::Int => {
+                let scale = (1i64 << (spec.bits_per_sample.max(1) - 1)) as f32;
+                reader
+                    .into_samples::<i32>()
+                    .map(|sample| sample.map(|s| s as f32 / scale))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| PlaybackError::Decode(e.to_string()))?
+            }
+        };
+
+        Ok(Self {
+            samples,
+            channels: spec.channels.max(1),
+            sample_rate: spec.sample_rate.max(1),
+        })
+    }
+
+    /// Decode an OGG Vorbis file held in memory (e.g. an archived recording)
+    pub fn from_ogg_bytes(bytes: &[u8]) -> PlaybackResult<Self> {
+        let mut decoder = vorbis_rs::VorbisDecoder::new(Cursor::new(bytes.to_vec()))
+            .map_err(|e| PlaybackError::Decode(e.to_string()))?;
+        let channels = u16::from(decoder.channels().get());
+        let sample_rate = decoder.sampling_frequency().get();
+
+        let mut samples = Vec::new();
+        while let Some(block) = decoder
+            .decode_audio_block()
+            .map_err(|e| PlaybackError::Decode(e.to_string()))?
+        {
+            let planes = block.samples();
+            let frames = planes.first().map_or(0, |plane| plane.len());
+            for frame in 0..frames {
+                samples.extend(planes.iter().map(|plane| plane[frame]));
+            }
+        }
+
+        Ok(Self {
+            samples,
+            channels,
+            sample_rate,
+        })
+    }
+
+    /// Number of frames (samples per channel)
+    pub fn frames(&self) -> u64 {
+        self.samples.len() as u64 / u64::from(self.channels.max(1))
+    }
+
+    /// Playback duration
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.frames() as f64 / f64::from(self.sample_rate.max(1)))
+    }
+
+    /// Part of the clip between `start` and `end` (clamped to the clip)
+    pub fn slice(&self, start: Duration, end: Duration) -> AudioClip {
+        let channels = usize::from(self.channels.max(1));
+        let frames = self.frames() as usize;
+        let to_frame = |time: Duration| {
+            ((time.as_secs_f64() * f64::from(self.sample_rate)).round() as usize).min(frames)
+        };
+        let (start, end) = (to_frame(start), to_frame(end));
+
+        AudioClip {
+            samples: self.samples[start.min(end) * channels..end * channels].to_vec(),
+            channels: self.channels,
+            sample_rate: self.sample_rate,
+        }
+    }
+
+    /// Write the clip as a 16-bit WAV file
+    pub fn write_wav(&self, path: &std::path::Path) -> PlaybackResult<()> {
+        let spec = hound::WavSpec {
+            channels: self.channels.max(1),
+            sample_rate: self.sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec)
+            .map_err(|e| PlaybackError::Decode(e.to_string()))?;
+        for sample in &self.samples {
+            writer
+                .write_sample((sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16)
+                .map_err(|e| PlaybackError::Decode(e.to_string()))?;
+        }
+        writer
+            .finalize()
+            .map_err(|e| PlaybackError::Decode(e.to_string()))
+    }
+
+    /// Convert to another channel count and sample rate (linear interpolation)
+    pub fn convert(&self, channels: u16, sample_rate: u32) -> AudioClip {
+        let in_channels = usize::from(self.channels.max(1));
+        let out_channels = usize::from(channels.max(1));
+
+        // Mix down to mono per frame, then spread to the output channels
+        let mono: Vec<f32> = self
+            .samples
+            .chunks(in_channels)
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect();
+
+        let resampled = if sample_rate == self.sample_rate || mono.is_empty() {
+            mono
+        } else {
+            let ratio = f64::from(self.sample_rate) / f64::from(sample_rate);
+            let out_frames = (mono.len() as f64 / ratio).round() as usize;
+            (0..out_frames)
+                .map(|index| {
+                    let position = index as f64 * ratio;
+                    let base = position.floor() as usize;
+                    let next = (base + 1).min(mono.len() - 1);
+                    let fraction = (position - base as f64) as f32;
+                    let base = base.min(mono.len() - 1);
+                    mono[base] + (mono[next] - mono[base]) * fraction
+                })
+                .collect()
+        };
+
+        AudioClip {
+            samples: resampled
+                .iter()
+                .flat_map(|sample| std::iter::repeat_n(*sample, out_channels))
+                .collect(),
+            channels: out_channels as u16,
+            sample_rate,
+        }
+    }
+}
+
+/// Handle of a running playback
+#[derive(Debug, Clone)]
+pub struct PlaybackHandle {
+    stop: Arc<AtomicBool>,
+    finished: Arc<AtomicBool>,
+    frames_played: Arc<AtomicU64>,
+    total_frames: u64,
+    sample_rate: u32,
+}
+
+impl PlaybackHandle {
+    /// Stop playback
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether playback ended or was stopped
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+
+    /// Current playback position
+    pub fn position(&self) -> Duration {
+        let frames = self
+            .frames_played
+            .load(Ordering::Relaxed)
+            .min(self.total_frames);
+        Duration::from_secs_f64(frames as f64 / f64::from(self.sample_rate.max(1)))
+    }
+
+    /// Total duration
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.total_frames as f64 / f64::from(self.sample_rate.max(1)))
+    }
+}
+
+/// Play a clip on the default output device
+pub fn play(clip: AudioClip) -> PlaybackResult<PlaybackHandle> {
+    let device = cpal::default_host()
+        .default_output_device()
+        .ok_or(PlaybackError::NoOutputDevice)?;
+    let config = output_config(&device, &clip)?;
+    let clip = clip.convert(config.channels, config.sample_rate.0);
+
+    let handle = PlaybackHandle {
+        stop: Arc::new(AtomicBool::new(false)),
+        finished: Arc::new(AtomicBool::new(false)),
+        frames_played: Arc::new(AtomicU64::new(0)),
+        total_frames: clip.frames(),
+        sample_rate: clip.sample_rate,
+    };
+
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+    let thread_handle = handle.clone();
+    std::thread::spawn(move || {
+        let channels = u64::from(clip.channels);
+        let samples = clip.samples;
+        let mut cursor = 0usize;
+        let frames_played = Arc::clone(&thread_handle.frames_played);
+
+        let stream = device.build_output_stream(
+            &config,
+            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                for sample in data.iter_mut() {
+                    *sample = samples.get(cursor).copied().unwrap_or(0.0);
+                    cursor += 1;
+                }
+                frames_played.store(cursor as u64 / channels, Ordering::Relaxed);
+            },
+            |err| eprintln!("Audio output stream error: {}", err),
+            None,
+        );
+        let stream = match stream.and_then(|stream| {
+            stream
+                .play()
+                .map(|_| stream)
+                .map_err(|e| cpal::BuildStreamError::BackendSpecific {
+                    err: cpal::BackendSpecificError {
+                        description: e.to_string(),
+                    },
+                })
+        }) {
+            Ok(stream) => {
+                let _ = ready_tx.send(Ok(()));
+                stream
+            }
+            Err(e) => {
+                let _ = ready_tx.send(Err(PlaybackError::Stream(e.to_string())));
+                thread_handle.finished.store(true, Ordering::Relaxed);
+                return;
+            }
+        };
+
+        while !thread_handle.stop.load(Ordering::Relaxed)
+            && thread_handle.frames_played.load(Ordering::Relaxed) < thread_handle.total_frames
+        {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        drop(stream);
+        thread_handle.finished.store(true, Ordering::Relaxed);
+    });
+
+    ready_rx
+        .recv()
+        .map_err(|_| PlaybackError::Stream("playback thread exited".to_string()))??;
+    Ok(handle)
+}
+
+/// Float output configuration, preferring the clip's sample rate
+fn output_config(device: &cpal::Device, clip: &AudioClip) -> PlaybackResult<StreamConfig> {
+    let default_config = device
+        .default_output_config()
+        .map_err(|e| PlaybackError::Stream(e.to_string()))?;
+    if default_config.sample_format() == SampleFormat::F32 {
+        return Ok(default_config.config());
+    }
+
+    let configs = device
+        .supported_output_configs()
+        .map_err(|e| PlaybackError::Stream(e.to_string()))?;
+    configs
+        .filter(|range| range.sample_format() == SampleFormat::F32)
+        .map(|range| {
+            let rate = clip
+                .sample_rate
+                .clamp(range.min_sample_rate().0, range.max_sample_rate().0);
+            range.with_sample_rate(cpal::SampleRate(rate)).config()
+        })
+        .next()
+        .ok_or(PlaybackError::UnsupportedOutputFormat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wav_bytes_decode() {
+        let mut bytes = Cursor::new(Vec::new());
+        {
+            let spec = hound::WavSpec {
+                channels: 1,
+                sample_rate: 16000,
+                bits_per_sample: 16,
+                sample_format: hound::SampleFormat::Int,
+            };
+            let mut writer = hound::WavWriter::new(&mut bytes, spec).unwrap();
+            for sample in [0i16, 16384, -32768] {
+                writer.write_sample(sample).unwrap();
+            }
+            writer.finalize().unwrap();
+        }
+
+        let clip = AudioClip::from_wav_bytes(bytes.get_ref()).unwrap();
+        assert_eq!(clip.channels, 1);
+        assert_eq!(clip.sample_rate, 16000);
+        assert_eq!(clip.samples, vec![0.0, 0.5, -1.0]);
+    }
+
+    #[test]
+    fn test_ogg_bytes_decode() {
+        let mut bytes = Vec::new();
+        {
+            let mut encoder = vorbis_rs::VorbisEncoderBuilder::new(
+                std::num::NonZero::new(16000u32).unwrap(),
+                std::num::NonZero::new(1u8).unwrap(),
+                &mut bytes,
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+            let tone: Vec<f32> = (0..16000)
+                .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / 16000.0).sin() * 0.5)
+                .collect();
+            encoder.encode_audio_block([&tone]).unwrap();
+            encoder.finish().unwrap();
+        }
+
+        let clip = AudioClip::from_ogg_bytes(&bytes).unwrap();
+        assert_eq!(clip.channels, 1);
+        assert_eq!(clip.sample_rate, 16000);
+        assert_eq!(clip.frames(), 16000);
+        assert!(AudioClip::from_ogg_bytes(b"not ogg").is_err());
+    }
+
+    #[test]
+    fn test_slice_and_write_wav() {
+        let clip = AudioClip {
+            samples: (0..10).map(|i| i as f32 / 10.0).collect(),
+            channels: 1,
+            sample_rate: 10,
+        };
+
+        let slice = clip.slice(Duration::from_millis(200), Duration::from_millis(500));
+        assert_eq!(slice.samples, vec![0.2, 0.3, 0.4]);
+        let clamped = clip.slice(Duration::from_millis(800), Duration::from_secs(5));
+        assert_eq!(clamped.frames(), 2);
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("slice.wav");
+        slice.write_wav(&path).unwrap();
+        let decoded = AudioClip::from_wav_bytes(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(decoded.frames(), 3);
+        assert!((decoded.samples[1] - 0.3).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_convert_remixes_and_resamples() {
+        let clip = AudioClip {
+            samples: vec![0.0, 1.0, 1.0, 1.0],
+            channels: 2,
+            sample_rate: 8000,
+        };
+        let converted = clip.convert(2, 16000);
+
+        assert_eq!(converted.sample_rate, 16000);
+        assert_eq!(converted.frames(), 4);
+        assert_eq!(&converted.samples[..4], &[0.5, 0.5, 0.75, 0.75]);
+        assert_eq!(clip.duration(), converted.duration());
+    }
+}
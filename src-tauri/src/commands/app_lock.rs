@@ -0,0 +1,87 @@
+//! Commands for the settings/history lock
+
+use crate::services::{AppLock, LockMethod};
+
+/// Default time an unlock lasts
+const DEFAULT_UNLOCK_SECONDS: u64 = 300;
+
+/// Fail with an "APP_LOCKED" error unless the app is unlocked
+///
+/// Called by the commands that open, read or save settings or expose the
+/// history, so the lock is enforced even when the frontend is bypassed.
+pub fn require_unlocked() -> Result<(), String> {
+    AppLock::global()
+        .ensure_unlocked()
+        .map_err(|e| format!("APP_LOCKED: {}", e))
+}
+
+/// Report whether the lock is enabled, its method and whether the app is unlocked
+#[tauri::command]
+pub async fn get_app_lock_status() -> Result<serde_json::Value, String> {
+    let lock = AppLock::global();
+    let method = lock
+        .method()
+        .map_err(|e| format!("Failed to read app lock: {}", e))?;
+    let unlocked = lock
+        .is_unlocked()
+        .map_err(|e| format!("Failed to read app lock: {}", e))?;
+
+    Ok(serde_json::json!({
+        "enabled": method.is_some(),
+        "method": method,
+        "unlocked": unlocked,
+    }))
+}
+
+/// Unlock with the PIN, or with OS authentication when no PIN is given
+#[tauri::command]
+pub async fn unlock_app(pin: Option<String>) -> Result<String, String> {
+    // OS authentication blocks on the system dialog
+    let result = tokio::task::spawn_blocking(move || match pin {
+        Some(pin) => AppLock::global().unlock_with_pin(&pin),
+        None => AppLock::global().unlock_with_os(),
+    })
+    .await
+    .map_err(|e| format!("Unlock task failed: {}", e))?;
+
+    result.map_err(|e| format!("Failed to unlock: {}", e))?;
+    eprintln!("🔓 [APP-LOCK] App unlocked");
+    Ok("App unlocked".to_string())
+}
+
+/// Lock the app immediately
+#[tauri::command]
+pub async fn lock_app() -> Result<String, String> {
+    AppLock::global().lock();
+    eprintln!("🔒 [APP-LOCK] App locked");
+    Ok("App locked".to_string())
+}
+
+/// Enable or reconfigure the lock ("pin" requires `pin`, "os" uses OS authentication)
+#[tauri::command]
+pub async fn enable_app_lock(
+    method: LockMethod,
+    pin: Option<String>,
+    unlock_seconds: Option<u64>,
+) -> Result<String, String> {
+    let unlock_seconds = unlock_seconds.unwrap_or(DEFAULT_UNLOCK_SECONDS);
+    tokio::task::spawn_blocking(move || {
+        AppLock::global().enable(method, pin.as_deref(), unlock_seconds)
+    })
+    .await
+    .map_err(|e| format!("App lock task failed: {}", e))?
+    .map_err(|e| format!("Failed to enable app lock: {}", e))?;
+
+    eprintln!("🔒 [APP-LOCK] App lock enabled ({:?})", method);
+    Ok("App lock enabled".to_string())
+}
+
+/// Disable the lock (the app must be unlocked)
+#[tauri::command]
+pub async fn disable_app_lock() -> Result<String, String> {
+    AppLock::global()
+        .disable()
+        .map_err(|e| format!("Failed to disable app lock: {}", e))?;
+    eprintln!("🔓 [APP-LOCK] App lock disabled");
+    Ok("App lock disabled".to_string())
+}
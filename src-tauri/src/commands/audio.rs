@@ -1,6 +1,24 @@
-use crate::audio::{AudioCapture, LiveAudioCapture};
+use crate::audio::capture::{
+    DEFAULT_RMS_RATE_HZ, MAX_RMS_RATE_HZ, WAVEFORM_POINTS, WAVEFORM_WINDOW_MS,
+};
+use crate::audio::{
+    probe_mic_status, AudioCapture, LiveAudioCapture, MicStatus, RecordingDeviceLost,
+};
+use crate::commands::encoder::configured_encoder;
+use crate::commands::settings::read_settings;
+use crate::commands::{
+    get_profiles_cached, ClipboardServiceState, ContextMemoryState, FocusSessionState,
+    GptClientState, PlaybackState, ProfileAppState, RecordingLanguageState, WhisperClientState,
+    WorkflowContextState,
+};
+use crate::services::event_targets::emit_to_main;
+use crate::services::notifier::{Notifier, TauriNotifierService};
+use crate::services::{ProfileEngine, WorkflowStage};
+use crate::state::{AppEvent, AppState, AppStateMachineState, StopReason};
+use std::path::PathBuf;
 use std::sync::Arc;
-use tauri::{AppHandle, State};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::Mutex;
 
 /// Global state for the audio capture service
@@ -24,7 +42,7 @@ pub async fn init_audio_capture(
     }
 
     eprintln!("🔧 [AUDIO-INIT] Creating new LiveAudioCapture instance...");
-    let capture = LiveAudioCapture::new(app_handle).map_err(|e| {
+    let capture = LiveAudioCapture::new(app_handle.clone()).map_err(|e| {
         eprintln!("❌ [AUDIO-INIT] Failed to create LiveAudioCapture: {}", e);
         format!("Failed to initialize audio capture: {}", e)
     })?;
@@ -32,13 +50,77 @@ pub async fn init_audio_capture(
     eprintln!("✅ [AUDIO-INIT] LiveAudioCapture created successfully");
     eprintln!("🔒 [AUDIO-INIT] Storing audio capture in global state...");
 
-    let mut state_guard = state.lock().await;
-    *state_guard = Some(Arc::new(capture));
+    {
+        let mut state_guard = state.lock().await;
+        *state_guard = Some(Arc::new(capture));
+    }
+
+    // Apply the persisted input gain
+    if let Ok(settings) = read_settings().await {
+        let stream_options = configured_encoder().await.options();
+        if let Some(capture) = state.lock().await.as_ref() {
+            capture.gain().set_gain_db(settings.audio.gain_db);
+            capture.gain().set_auto_gain(settings.audio.auto_gain);
+            capture.set_pause_media(settings.audio.pause_media);
+            capture.set_auto_stop_silence_seconds(settings.audio.auto_stop_silence_seconds);
+            capture.set_max_recording_seconds(settings.audio.max_recording_seconds);
+            capture.set_pre_roll_seconds(settings.audio.pre_roll_seconds);
+            capture.set_requested_sample_rate(settings.audio.sample_rate);
+            capture.set_capture_source(
+                settings.audio.capture_source,
+                settings.interview.loopback_device.clone(),
+            );
+            capture.set_loudness_target(
+                settings
+                    .audio
+                    .loudness_normalization
+                    .then_some(settings.audio.target_lufs),
+            );
+            capture.set_stream_encoding(Some(stream_options));
+        }
+    }
+
+    // Pin the input device of the active profile (if any)
+    let active_profile_id = app_handle
+        .try_state::<ProfileAppState>()
+        .and_then(|profile_state| profile_state.lock().ok()?.active_profile_id.clone());
+    apply_profile_input_device(&app_handle, active_profile_id.as_deref()).await;
 
     eprintln!("🎉 [AUDIO-INIT] Audio capture initialization completed successfully");
     Ok("Audio capture initialized successfully".to_string())
 }
 
+/// Drop the audio capture and release the input device
+///
+/// Refused while recording: stop the recording first.
+#[tauri::command]
+pub async fn shutdown_audio_capture(state: State<'_, AudioCaptureState>) -> Result<String, String> {
+    let mut state_guard = state.lock().await;
+    match state_guard.as_ref() {
+        Some(capture) if capture.is_recording() => {
+            Err("Cannot shut the audio capture down while recording".to_string())
+        }
+        Some(capture) => {
+            capture.release_device();
+            *state_guard = None;
+            eprintln!("🛑 [AUDIO] Audio capture shut down");
+            Ok("Audio capture shut down".to_string())
+        }
+        None => Ok("Audio capture was not initialized".to_string()),
+    }
+}
+
+/// Shut the audio capture down and initialize it again, e.g. after the input
+/// device or sample rate changed
+#[tauri::command]
+pub async fn reinit_audio_capture(
+    app_handle: AppHandle,
+    state: State<'_, AudioCaptureState>,
+) -> Result<String, String> {
+    shutdown_audio_capture(state.clone()).await?;
+    init_audio_capture(app_handle, state).await
+}
+
 /// Start audio capture
 #[tauri::command]
 pub async fn start_capture(state: State<'_, AudioCaptureState>) -> Result<String, String> {
@@ -90,6 +172,69 @@ pub async fn stop_capture(state: State<'_, AudioCaptureState>) -> Result<String,
     }
 }
 
+/// Pause the recording; resuming continues the same WAV file
+#[tauri::command]
+pub async fn pause_capture(
+    state: State<'_, AudioCaptureState>,
+    state_machine_state: State<'_, AppStateMachineState>,
+) -> Result<String, String> {
+    crate::commands::state_machine::process_event(AppEvent::PauseRecording, &state_machine_state)
+        .await?;
+
+    let state_guard = state.lock().await;
+    let capture = state_guard
+        .as_ref()
+        .ok_or("Audio capture not initialized")?;
+    capture
+        .pause()
+        .map_err(|e| format!("Failed to pause capture: {}", e))?;
+
+    eprintln!("⏸️ [AUDIO] Recording paused");
+    Ok("Recording paused".to_string())
+}
+
+/// Resume a paused recording
+#[tauri::command]
+pub async fn resume_capture(
+    state: State<'_, AudioCaptureState>,
+    state_machine_state: State<'_, AppStateMachineState>,
+) -> Result<String, String> {
+    crate::commands::state_machine::process_event(AppEvent::ResumeRecording, &state_machine_state)
+        .await?;
+
+    let state_guard = state.lock().await;
+    let capture = state_guard
+        .as_ref()
+        .ok_or("Audio capture not initialized")?;
+    capture
+        .resume()
+        .map_err(|e| format!("Failed to resume capture: {}", e))?;
+
+    eprintln!("▶️ [AUDIO] Recording resumed");
+    Ok("Recording resumed".to_string())
+}
+
+/// Bookmark the current position of the recording; returns it in seconds
+///
+/// Bookmarks are placed in the transcript once the recording is processed.
+#[tauri::command]
+pub async fn mark_bookmark(
+    app_handle: AppHandle,
+    state: State<'_, AudioCaptureState>,
+) -> Result<f64, String> {
+    let state_guard = state.lock().await;
+    let capture = state_guard
+        .as_ref()
+        .ok_or("Audio capture not initialized")?;
+    let seconds = capture
+        .add_bookmark()
+        .map_err(|e| format!("Failed to add bookmark: {}", e))?;
+
+    eprintln!("🔖 [AUDIO] Bookmark at {:.1}s", seconds);
+    let _ = emit_to_main(&app_handle, "bookmark-added", seconds);
+    Ok(seconds)
+}
+
 /// Check if currently recording
 #[tauri::command]
 pub async fn is_recording(state: State<'_, AudioCaptureState>) -> Result<bool, String> {
@@ -105,26 +250,464 @@ pub async fn is_recording(state: State<'_, AudioCaptureState>) -> Result<bool, S
     }
 }
 
-/// Subscribe to RMS updates (callback-based, the actual RMS data is sent via events)
+/// Subscribe to RMS updates: while recording, `rms` events carry the input
+/// level (0.0 to 1.0), at most `rate_hz` per second (default
+/// `DEFAULT_RMS_RATE_HZ`); levels in between are coalesced, the latest wins
 #[tauri::command]
-pub async fn subscribe_rms(state: State<'_, AudioCaptureState>) -> Result<String, String> {
+pub async fn subscribe_rms(
+    rate_hz: Option<u32>,
+    state: State<'_, AudioCaptureState>,
+) -> Result<String, String> {
     eprintln!("📡 [RMS-SUB] subscribe_rms called");
 
     let state_guard = state.lock().await;
+    let capture = state_guard.as_ref().ok_or_else(|| {
+        eprintln!("❌ [RMS-SUB] Audio capture not initialized");
+        "Audio capture not initialized".to_string()
+    })?;
+    let rate_hz = rate_hz
+        .unwrap_or(DEFAULT_RMS_RATE_HZ)
+        .clamp(1, MAX_RMS_RATE_HZ);
+    capture.subscribe_rms_events(rate_hz);
+    eprintln!("✅ [RMS-SUB] RMS subscription confirmed ({} Hz)", rate_hz);
+    Ok(format!(
+        "Subscribed to RMS updates ({} per second)",
+        rate_hz
+    ))
+}
 
-    if let Some(ref capture) = *state_guard {
-        let is_recording = capture.is_recording();
+/// Stop emitting `rms` events (e.g. while the meter is hidden) once every
+/// subscriber has unsubscribed
+#[tauri::command]
+pub async fn unsubscribe_rms(state: State<'_, AudioCaptureState>) -> Result<String, String> {
+    let state_guard = state.lock().await;
+    let capture = state_guard
+        .as_ref()
+        .ok_or("Audio capture not initialized")?;
+    let remaining = capture.unsubscribe_rms_events();
+    eprintln!(
+        "📡 [RMS-SUB] RMS unsubscribed ({} subscribers left)",
+        remaining
+    );
+    Ok("Unsubscribed from RMS updates".to_string())
+}
+
+/// Subscribe to waveform frames: while recording, `waveform` events carry
+/// `WAVEFORM_POINTS` downsampled samples (-1.0 to 1.0) per `WAVEFORM_WINDOW_MS`
+#[tauri::command]
+pub async fn subscribe_waveform(state: State<'_, AudioCaptureState>) -> Result<String, String> {
+    let state_guard = state.lock().await;
+    let capture = state_guard
+        .as_ref()
+        .ok_or("Audio capture not initialized")?;
+    capture.set_waveform_enabled(true);
+    Ok(format!(
+        "Subscribed to waveform updates ({} points per {} ms)",
+        WAVEFORM_POINTS, WAVEFORM_WINDOW_MS
+    ))
+}
+
+/// Stop emitting waveform frames
+#[tauri::command]
+pub async fn unsubscribe_waveform(state: State<'_, AudioCaptureState>) -> Result<String, String> {
+    let state_guard = state.lock().await;
+    let capture = state_guard
+        .as_ref()
+        .ok_or("Audio capture not initialized")?;
+    capture.set_waveform_enabled(false);
+    Ok("Unsubscribed from waveform updates".to_string())
+}
+
+/// List the available input devices
+#[tauri::command]
+pub async fn list_input_devices() -> Result<Vec<String>, String> {
+    Ok(LiveAudioCapture::list_input_devices())
+}
+
+/// Set the input gain in dB (takes effect immediately, also while recording)
+#[tauri::command]
+pub async fn set_input_gain(
+    db: f32,
+    state: State<'_, AudioCaptureState>,
+) -> Result<serde_json::Value, String> {
+    let state_guard = state.lock().await;
+    let capture = state_guard
+        .as_ref()
+        .ok_or("Audio capture not initialized")?;
+
+    capture.gain().set_gain_db(db);
+    eprintln!(
+        "🎚️ [AUDIO] Input gain set to {:.1} dB",
+        capture.gain().gain_db()
+    );
+    Ok(gain_status(capture))
+}
+
+/// Enable or disable automatic gain control (AGC)
+#[tauri::command]
+pub async fn set_auto_gain(
+    enabled: bool,
+    state: State<'_, AudioCaptureState>,
+) -> Result<serde_json::Value, String> {
+    let state_guard = state.lock().await;
+    let capture = state_guard
+        .as_ref()
+        .ok_or("Audio capture not initialized")?;
+
+    capture.gain().set_auto_gain(enabled);
+    eprintln!("🎚️ [AUDIO] Automatic gain control: {}", enabled);
+    Ok(gain_status(capture))
+}
+
+/// Get the input gain settings and the gain currently applied
+#[tauri::command]
+pub async fn get_input_gain(
+    state: State<'_, AudioCaptureState>,
+) -> Result<serde_json::Value, String> {
+    let state_guard = state.lock().await;
+    let capture = state_guard
+        .as_ref()
+        .ok_or("Audio capture not initialized")?;
+
+    Ok(gain_status(capture))
+}
+
+/// Whether the default microphone is muted system-wide
+#[tauri::command]
+pub async fn get_microphone_status() -> Result<MicStatus, String> {
+    tokio::task::spawn_blocking(probe_mic_status)
+        .await
+        .map_err(|e| format!("Microphone probe failed: {}", e))
+}
+
+/// Warn right away when a recording starts with the microphone muted
+///
+/// Emits `microphone-muted` and shows a notification instead of letting the
+/// recording end in an empty transcript. Runs in the background so the
+/// recording start is not delayed by the probe.
+pub fn warn_if_microphone_muted(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let status = tokio::task::spawn_blocking(probe_mic_status)
+            .await
+            .unwrap_or(MicStatus::Unknown);
+        if status != MicStatus::Muted {
+            return;
+        }
+
+        eprintln!("⚠️ [AUDIO] Recording started with the microphone muted");
+        let _ = app_handle.emit("microphone-muted", ());
+        let notifier = TauriNotifierService::new(app_handle);
+        let _ = notifier
+            .warning("Your microphone is muted. Unmute it, or this recording will be silent.")
+            .await;
+    });
+}
+
+/// Stop the recording after the silence auto-stop fired
+///
+/// Runs the same workflow as the stop button, with [`StopReason::Silence`] so
+/// the frontend can tell it apart from a manual stop. Recordings made outside
+/// the state machine (e.g. calibration) and paused sessions are left alone.
+pub async fn stop_recording_on_silence(app_handle: &AppHandle) {
+    if let Err(e) = auto_stop_recording(app_handle, StopReason::Silence, None).await {
+        eprintln!("❌ [AUDIO] Processing after the silence stop failed: {}", e);
+    }
+}
+
+/// Stop the recording once it reached `audio.max_recording_seconds`
+///
+/// Same workflow as the silence auto-stop, with [`StopReason::MaxDuration`];
+/// a notification tells the user why the recording ended.
+pub async fn stop_recording_at_max_duration(app_handle: &AppHandle, max_seconds: u32) {
+    let duration = if max_seconds >= 60 && max_seconds.is_multiple_of(60) {
+        format!("{} min", max_seconds / 60)
+    } else {
+        format!("{}s", max_seconds)
+    };
+    let notice = format!(
+        "Recording stopped: it reached the maximum duration of {}. Processing it now.",
+        duration
+    );
+    if let Err(e) = auto_stop_recording(app_handle, StopReason::MaxDuration, Some(&notice)).await {
         eprintln!(
-            "📊 [RMS-SUB] Audio capture found, currently recording: {}",
-            is_recording
+            "❌ [AUDIO] Processing after the max duration stop failed: {}",
+            e
         );
+    }
+}
 
-        // The RMS callback is handled internally and emits events to the frontend
-        // This command just confirms subscription
-        eprintln!("✅ [RMS-SUB] RMS subscription confirmed");
-        Ok("Subscribed to RMS updates".to_string())
-    } else {
-        eprintln!("❌ [RMS-SUB] Audio capture not initialized");
-        Err("Audio capture not initialized".to_string())
+/// Run the stop workflow for an automatic stop, if a recording is running,
+/// after showing `notice` (if any) as a warning
+async fn auto_stop_recording(
+    app_handle: &AppHandle,
+    reason: StopReason,
+    notice: Option<&str>,
+) -> Result<(), String> {
+    let state_machine_state = app_handle.state::<AppStateMachineState>();
+    let recording = match state_machine_state.lock().await.as_ref() {
+        Some(state_machine) => matches!(
+            state_machine.lock().await.current_state(),
+            AppState::Recording { .. }
+        ),
+        None => false,
+    };
+    if !recording {
+        return Ok(());
+    }
+
+    let _ = app_handle.emit("recording-auto-stopped", reason);
+    if let Some(notice) = notice {
+        let notifier = TauriNotifierService::new(app_handle.clone());
+        let _ = notifier.warning(notice).await;
     }
+    crate::commands::stop_recording_and_process_to_clipboard(
+        app_handle.state(),
+        app_handle.state(),
+        app_handle.state(),
+        app_handle.state(),
+        app_handle.state(),
+        state_machine_state,
+        app_handle.state(),
+        app_handle.state(),
+        app_handle.state(),
+        app_handle.state(),
+        app_handle.clone(),
+        Some(reason),
+    )
+    .await
+    .map(|_| ())
+}
+
+/// React to the input device disconnecting during a recording
+///
+/// The state machine is told in every case. When no other device took over,
+/// the recording is stopped so its audio is kept as a failed workflow (to
+/// transcribe from the failed workflows list) and the app shows a recoverable
+/// error.
+pub async fn handle_recording_device_lost(app_handle: &AppHandle, lost: RecordingDeviceLost) {
+    let state_machine_state = app_handle.state::<AppStateMachineState>();
+    if let Err(e) = crate::commands::state_machine::process_event(
+        AppEvent::RecordingDeviceLost {
+            device: lost.device.clone(),
+            switched_to: lost.switched_to.clone(),
+        },
+        &state_machine_state,
+    )
+    .await
+    {
+        eprintln!("⚠️ [AUDIO] Device loss ignored by the state machine: {}", e);
+    }
+
+    let notifier = TauriNotifierService::new(app_handle.clone());
+    if let Some(switched_to) = lost.switched_to {
+        let _ = notifier
+            .warning(&format!(
+                "'{}' was disconnected, recording continues with '{}'.",
+                lost.device, switched_to
+            ))
+            .await;
+        return;
+    }
+
+    let capture = app_handle
+        .state::<AudioCaptureState>()
+        .lock()
+        .await
+        .as_ref()
+        .cloned();
+    if let Some(capture) = capture.filter(|capture| capture.is_recording()) {
+        match capture.stop_capture().await {
+            Ok(wav_path) => {
+                let error = format!(
+                    "Recording device '{}' was disconnected: {}",
+                    lost.device,
+                    lost.reason.as_deref().unwrap_or("no other input device")
+                );
+                crate::commands::failed_workflows::record_failed_workflow(
+                    WorkflowStage::Transcription,
+                    &error,
+                    Some(wav_path),
+                    None,
+                    None,
+                );
+                let _ = app_handle.emit("failed-workflows-updated", ());
+            }
+            Err(e) => eprintln!("⚠️ [AUDIO] Failed to stop the stalled recording: {}", e),
+        }
+    }
+    let _ = notifier
+        .error(&format!(
+            "'{}' was disconnected and no other microphone is available. \
+             The audio recorded so far is kept in failed workflows.",
+            lost.device
+        ))
+        .await;
+}
+
+/// Hold a stopped recording until its transcription is confirmed
+///
+/// Emits `recording-awaiting-confirmation` (`{ wav_path }`): the recording can
+/// be listened to with `play_last_recording`, then sent to Whisper with
+/// `confirm_pending_recording` or dropped with `discard_pending_recording`.
+pub async fn hold_recording_for_confirmation(
+    wav_path: PathBuf,
+    bookmarks: Vec<f64>,
+    state_machine_state: &State<'_, AppStateMachineState>,
+) -> Result<String, String> {
+    crate::commands::state_machine::process_event(
+        AppEvent::HoldForConfirmation {
+            wav_path: wav_path.clone(),
+            bookmarks: bookmarks.into_iter().map(Duration::from_secs_f64).collect(),
+        },
+        state_machine_state,
+    )
+    .await?;
+
+    if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
+        let state_machine_guard = state_machine.lock().await;
+        if let Err(e) = state_machine_guard.emit_event(
+            "recording-awaiting-confirmation",
+            serde_json::json!({ "wav_path": wav_path }),
+        ) {
+            eprintln!(
+                "⚠️  Warning: Failed to emit recording-awaiting-confirmation: {}",
+                e
+            );
+        }
+    }
+    eprintln!(
+        "⏸️  [PROCESSING] Recording {:?} held until its transcription is confirmed",
+        wav_path
+    );
+    Ok("Recording waiting for confirmation".to_string())
+}
+
+/// Recording waiting for its transcription to be confirmed, if any, with
+/// its bookmarks in seconds of audio
+pub async fn pending_recording(
+    state_machine_state: &State<'_, AppStateMachineState>,
+) -> Option<(PathBuf, Vec<f64>)> {
+    let state_machine = state_machine_state.lock().await.as_ref()?.clone();
+    let state_machine_guard = state_machine.lock().await;
+    match state_machine_guard.current_state() {
+        AppState::AwaitingConfirmation {
+            wav_path,
+            bookmarks,
+            ..
+        } => Some((
+            wav_path.clone(),
+            bookmarks.iter().map(Duration::as_secs_f64).collect(),
+        )),
+        _ => None,
+    }
+}
+
+/// Transcribe the recording held for confirmation and run the rest of the
+/// workflow (format, copy to clipboard)
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn confirm_pending_recording(
+    whisper_state: State<'_, WhisperClientState>,
+    clipboard_state: State<'_, ClipboardServiceState>,
+    profile_state: State<'_, ProfileAppState>,
+    gpt_state: State<'_, GptClientState>,
+    state_machine_state: State<'_, AppStateMachineState>,
+    language_state: State<'_, RecordingLanguageState>,
+    context_state: State<'_, ContextMemoryState>,
+    reference_state: State<'_, WorkflowContextState>,
+    focus_state: State<'_, FocusSessionState>,
+    playback_state: State<'_, PlaybackState>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    let (wav_path, bookmarks) = pending_recording(&state_machine_state)
+        .await
+        .ok_or_else(|| "No recording is waiting for confirmation".to_string())?;
+    if let Some(handle) = playback_state.lock().await.take() {
+        handle.stop();
+    }
+    crate::commands::state_machine::process_event(
+        AppEvent::ConfirmTranscription,
+        &state_machine_state,
+    )
+    .await?;
+
+    eprintln!("▶️  [PROCESSING] Transcription of {:?} confirmed", wav_path);
+    crate::commands::process_recording_to_clipboard(
+        wav_path,
+        bookmarks,
+        whisper_state,
+        clipboard_state,
+        profile_state,
+        gpt_state,
+        state_machine_state,
+        language_state,
+        context_state,
+        reference_state,
+        focus_state,
+        app_handle,
+        std::time::Instant::now(),
+    )
+    .await
+}
+
+/// Drop the recording held for confirmation without transcribing it
+#[tauri::command]
+pub async fn discard_pending_recording(
+    state_machine_state: State<'_, AppStateMachineState>,
+    playback_state: State<'_, PlaybackState>,
+) -> Result<String, String> {
+    let (wav_path, _) = pending_recording(&state_machine_state)
+        .await
+        .ok_or_else(|| "No recording is waiting for confirmation".to_string())?;
+    if let Some(handle) = playback_state.lock().await.take() {
+        handle.stop();
+    }
+    // Leaving the confirmation removes the recording (see `with_state_hooks`)
+    crate::commands::state_machine::process_event(AppEvent::DiscardRecording, &state_machine_state)
+        .await?;
+
+    eprintln!("🗑️  [PROCESSING] Recording {:?} discarded", wav_path);
+    Ok("Recording discarded".to_string())
+}
+
+fn gain_status(capture: &LiveAudioCapture) -> serde_json::Value {
+    serde_json::json!({
+        "gain_db": capture.gain().gain_db(),
+        "auto_gain": capture.gain().auto_gain(),
+        "applied_gain_db": capture.gain().applied_gain_db(),
+    })
+}
+
+/// Select the input device for the next recordings based on a profile
+///
+/// The profile's pinned device wins over `audio.input_device` from settings;
+/// the capture layer falls back to the default device if it is missing.
+pub async fn apply_profile_input_device(app_handle: &AppHandle, profile_id: Option<&str>) {
+    let Some(audio_state) = app_handle.try_state::<AudioCaptureState>() else {
+        return;
+    };
+    let Some(capture) = audio_state.lock().await.as_ref().cloned() else {
+        return;
+    };
+
+    let profile_device = match (profile_id, get_profiles_cached().await) {
+        (Some(profile_id), Ok(collection)) => ProfileEngine::new()
+            .find_profile_by_id(&collection, profile_id)
+            .ok()
+            .and_then(|profile| profile.input_device.clone()),
+        _ => None,
+    };
+    let settings_device = read_settings()
+        .await
+        .ok()
+        .and_then(|settings| settings.audio.input_device);
+
+    let device = profile_device.or(settings_device);
+    eprintln!(
+        "🎙️ [AUDIO] Input device for profile {:?}: {}",
+        profile_id,
+        device.as_deref().unwrap_or("system default")
+    );
+    capture.set_input_device(device);
 }
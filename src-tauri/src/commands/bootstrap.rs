@@ -0,0 +1,231 @@
+//! Startup orchestration of the backend services
+//!
+//! `bootstrap_app` initializes every subsystem a recording needs from the
+//! settings, in dependency order, instead of the frontend calling the `init_*`
+//! commands one by one. It is idempotent: subsystems already initialized are
+//! left as they are, and concurrent calls run one after the other.
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::settings::read_settings;
+use crate::commands::{
+    auto_init_shortcut_mgr, init_audio_capture, init_clipboard_service, init_gpt_client,
+    init_state_machine, init_system_tray, init_whisper_client, AudioCaptureState,
+    ClipboardServiceState, GptClientState, ShortcutMgrState, SystemTrayState, WhisperClientState,
+};
+use crate::services::event_targets::emit_to_main;
+use crate::services::WhisperProvider;
+use crate::state::AppStateMachineState;
+
+/// One bootstrap at a time
+static BOOTSTRAP_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Outcome of a subsystem's initialization
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubsystemStatus {
+    Initialized,
+    /// Initialized by an earlier call, left as it was
+    AlreadyInitialized,
+    /// Not configured (e.g. no API key)
+    Skipped,
+    Failed,
+}
+
+/// Status of one subsystem (`bootstrap-status` event payload)
+#[derive(Debug, Clone, Serialize)]
+pub struct SubsystemReport {
+    /// `state_machine`, `audio`, `whisper`, `gpt`, `clipboard`, `tray` or
+    /// `shortcuts`
+    pub subsystem: &'static str,
+    pub status: SubsystemStatus,
+    pub message: String,
+}
+
+/// Status of every subsystem, in initialization order
+#[derive(Debug, Clone, Serialize)]
+pub struct BootstrapReport {
+    /// No subsystem failed
+    pub ok: bool,
+    pub subsystems: Vec<SubsystemReport>,
+}
+
+/// Report of an `init_*` command result
+fn init_report(subsystem: &'static str, result: Result<String, String>) -> SubsystemReport {
+    match result {
+        Ok(message) => SubsystemReport {
+            subsystem,
+            status: SubsystemStatus::Initialized,
+            message,
+        },
+        Err(message) => SubsystemReport {
+            subsystem,
+            status: SubsystemStatus::Failed,
+            message,
+        },
+    }
+}
+
+/// Report of a subsystem that did not need initializing
+fn status_report(
+    subsystem: &'static str,
+    status: SubsystemStatus,
+    message: &str,
+) -> SubsystemReport {
+    SubsystemReport {
+        subsystem,
+        status,
+        message: message.to_string(),
+    }
+}
+
+/// Initialize the state machine, audio capture, Whisper and GPT clients,
+/// clipboard, system tray and global shortcuts from the settings
+///
+/// Emits `bootstrap-status` after each subsystem. Safe to call again (e.g.
+/// after a window reload): only what is missing is initialized.
+/// `is_first_launch` is passed on to the system tray (shows the settings
+/// window instead of hiding the main one).
+#[tauri::command]
+pub async fn bootstrap_app(
+    app_handle: AppHandle,
+    is_first_launch: Option<bool>,
+) -> Result<BootstrapReport, String> {
+    let _bootstrap = BOOTSTRAP_LOCK.lock().await;
+    let settings = read_settings().await?;
+    let api_key = settings.whisper.api_key.trim().to_string();
+    let already = |subsystem: &'static str| {
+        status_report(
+            subsystem,
+            SubsystemStatus::AlreadyInitialized,
+            "Already initialized",
+        )
+    };
+    let no_api_key = |subsystem: &'static str| {
+        status_report(
+            subsystem,
+            SubsystemStatus::Skipped,
+            "No API key in settings",
+        )
+    };
+
+    let mut subsystems = Vec::new();
+    let mut push = |report: SubsystemReport| {
+        eprintln!(
+            "🚀 [BOOTSTRAP] {}: {:?} ({})",
+            report.subsystem, report.status, report.message
+        );
+        if let Err(e) = emit_to_main(&app_handle, "bootstrap-status", &report) {
+            eprintln!("⚠️  [BOOTSTRAP] Failed to emit bootstrap-status: {}", e);
+        }
+        subsystems.push(report);
+    };
+
+    // The state machine first: the workflows of the other subsystems report to it
+    let state_machine_ready = app_handle
+        .state::<AppStateMachineState>()
+        .lock()
+        .await
+        .is_some();
+    push(if state_machine_ready {
+        already("state_machine")
+    } else {
+        init_report(
+            "state_machine",
+            init_state_machine(app_handle.clone(), app_handle.state()).await,
+        )
+    });
+
+    let audio_ready = app_handle
+        .state::<AudioCaptureState>()
+        .lock()
+        .await
+        .is_some();
+    push(if audio_ready {
+        already("audio")
+    } else {
+        init_report(
+            "audio",
+            init_audio_capture(app_handle.clone(), app_handle.state()).await,
+        )
+    });
+
+    let whisper_ready = app_handle
+        .state::<WhisperClientState>()
+        .lock()
+        .await
+        .is_some();
+    push(if whisper_ready {
+        already("whisper")
+    } else if api_key.is_empty() && settings.whisper.provider != WhisperProvider::Local {
+        no_api_key("whisper")
+    } else {
+        init_report(
+            "whisper",
+            init_whisper_client(app_handle.clone(), api_key.clone(), app_handle.state()).await,
+        )
+    });
+
+    let gpt_ready = app_handle.state::<GptClientState>().lock().await.is_some();
+    push(if gpt_ready {
+        already("gpt")
+    } else if api_key.is_empty() {
+        no_api_key("gpt")
+    } else {
+        init_report(
+            "gpt",
+            init_gpt_client(api_key.clone(), app_handle.state()).await,
+        )
+    });
+
+    let clipboard_ready = app_handle
+        .state::<ClipboardServiceState>()
+        .lock()
+        .await
+        .is_some();
+    push(if clipboard_ready {
+        already("clipboard")
+    } else {
+        init_report(
+            "clipboard",
+            init_clipboard_service(app_handle.clone(), app_handle.state()).await,
+        )
+    });
+
+    let tray_ready = app_handle.state::<SystemTrayState>().lock().await.is_some();
+    push(if tray_ready {
+        already("tray")
+    } else {
+        init_report(
+            "tray",
+            init_system_tray(
+                app_handle.clone(),
+                Some(false),
+                Some(settings.global_shortcut.clone()),
+                is_first_launch,
+                app_handle.state(),
+            )
+            .await,
+        )
+    });
+
+    let shortcuts_ready = app_handle
+        .state::<ShortcutMgrState>()
+        .lock()
+        .await
+        .is_some();
+    push(if shortcuts_ready {
+        already("shortcuts")
+    } else {
+        init_report(
+            "shortcuts",
+            auto_init_shortcut_mgr(app_handle.clone(), app_handle.state()).await,
+        )
+    });
+
+    let ok = subsystems
+        .iter()
+        .all(|report| report.status != SubsystemStatus::Failed);
+    Ok(BootstrapReport { ok, subsystems })
+}
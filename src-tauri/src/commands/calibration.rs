@@ -0,0 +1,137 @@
+//! Guided calibration: read a known paragraph and get accuracy feedback
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::audio::AudioCapture;
+use crate::commands::whisper::encode_and_transcribe;
+use crate::commands::{AudioCaptureState, WhisperClientState};
+use crate::services::calibration::{
+    analyze_levels, is_good_accuracy, recommend, word_error_rate, LevelStats, Recommendation,
+    WerReport, CALIBRATION_LANGUAGE, CALIBRATION_TEXT,
+};
+use crate::services::SegmentedWhisperClient;
+
+/// Paragraph to read during calibration
+#[derive(Debug, Clone, Serialize)]
+pub struct CalibrationPrompt {
+    pub text: String,
+    pub language: String,
+}
+
+/// Outcome of a calibration recording
+#[derive(Debug, Clone, Serialize)]
+pub struct CalibrationResult {
+    pub reference: String,
+    pub transcript: String,
+    pub report: WerReport,
+    pub levels: LevelStats,
+    /// Language recognized by the transcription service
+    pub detected_language: Option<String>,
+    /// The accuracy needs no further tuning
+    pub good: bool,
+    pub recommendations: Vec<Recommendation>,
+}
+
+/// Start a calibration recording and return the paragraph to read
+///
+/// The recording is not part of the dictation workflow: stop it with
+/// `finish_calibration` rather than the usual stop commands.
+#[tauri::command]
+pub async fn start_calibration(
+    audio_state: State<'_, AudioCaptureState>,
+) -> Result<CalibrationPrompt, String> {
+    let audio_guard = audio_state.lock().await;
+    let capture = audio_guard
+        .as_ref()
+        .ok_or("Audio capture not initialized")?;
+    if capture.is_recording() {
+        return Err("A recording is in progress".to_string());
+    }
+
+    capture
+        .start_capture()
+        .await
+        .map_err(|e| format!("Failed to start calibration recording: {}", e))?;
+    eprintln!("🎯 [CALIBRATION] Recording the calibration paragraph");
+
+    Ok(CalibrationPrompt {
+        text: CALIBRATION_TEXT.to_string(),
+        language: CALIBRATION_LANGUAGE.to_string(),
+    })
+}
+
+/// Stop the calibration recording, transcribe it and score it against the paragraph
+#[tauri::command]
+pub async fn finish_calibration(
+    audio_state: State<'_, AudioCaptureState>,
+    whisper_state: State<'_, WhisperClientState>,
+) -> Result<CalibrationResult, String> {
+    let (wav_path, gain_db) = {
+        let audio_guard = audio_state.lock().await;
+        let capture = audio_guard
+            .as_ref()
+            .ok_or("Audio capture not initialized")?;
+        let wav_path = capture
+            .stop_capture()
+            .await
+            .map_err(|e| format!("Failed to stop calibration recording: {}", e))?;
+        (wav_path, capture.gain().applied_gain_db())
+    };
+
+    let result = score_recording(&wav_path, gain_db, &whisper_state).await;
+    let _ = tokio::fs::remove_file(&wav_path).await;
+    result
+}
+
+async fn score_recording(
+    wav_path: &Path,
+    gain_db: f32,
+    whisper_state: &WhisperClientState,
+) -> Result<CalibrationResult, String> {
+    let path = wav_path.to_path_buf();
+    let levels = tokio::task::spawn_blocking(move || read_levels(&path))
+        .await
+        .map_err(|e| format!("Level analysis failed: {}", e))??;
+
+    // Verbose transcription, so that the detected language is reported
+    let transcript = {
+        let whisper_guard = whisper_state.lock().await;
+        let client = whisper_guard
+            .as_ref()
+            .ok_or("Whisper client not initialized. Please check your API key in settings.")?;
+        let segmented = SegmentedWhisperClient::new(client.as_ref());
+        encode_and_transcribe(PathBuf::from(wav_path), None, &segmented).await?
+    };
+
+    let report = word_error_rate(CALIBRATION_TEXT, &transcript.text);
+    let recommendations = recommend(&report, &levels, gain_db, transcript.language.as_deref());
+    eprintln!(
+        "🎯 [CALIBRATION] WER {:.1}% ({} recommendation(s))",
+        report.wer * 100.0,
+        recommendations.len()
+    );
+
+    Ok(CalibrationResult {
+        reference: CALIBRATION_TEXT.to_string(),
+        transcript: transcript.text,
+        good: is_good_accuracy(&report) && recommendations.is_empty(),
+        report,
+        levels,
+        detected_language: transcript.language,
+        recommendations,
+    })
+}
+
+/// Levels of the recorded WAV file
+fn read_levels(path: &Path) -> Result<LevelStats, String> {
+    let mut reader =
+        hound::WavReader::open(path).map_err(|e| format!("Failed to read recording: {}", e))?;
+    let sample_rate = reader.spec().sample_rate;
+    let samples = reader
+        .samples::<i16>()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read recording: {}", e))?;
+    Ok(analyze_levels(&samples, sample_rate))
+}
@@ -22,6 +22,28 @@ pub async fn init_clipboard_service(
     Ok("Clipboard service initialized successfully".to_string())
 }
 
+/// Drop the clipboard service (copies fail until it is initialized again)
+#[tauri::command]
+pub async fn shutdown_clipboard_service(
+    state: State<'_, ClipboardServiceState>,
+) -> Result<String, String> {
+    let mut state_guard = state.lock().await;
+    Ok(match state_guard.take() {
+        Some(_) => "Clipboard service shut down".to_string(),
+        None => "Clipboard service was not initialized".to_string(),
+    })
+}
+
+/// Shut the clipboard service down and initialize it again
+#[tauri::command]
+pub async fn reinit_clipboard_service(
+    app_handle: AppHandle,
+    state: State<'_, ClipboardServiceState>,
+) -> Result<String, String> {
+    shutdown_clipboard_service(state.clone()).await?;
+    init_clipboard_service(app_handle, state).await
+}
+
 /// Copy formatted text to system clipboard
 /// This is the main command that will be called from the frontend after profile engine
 #[tauri::command]
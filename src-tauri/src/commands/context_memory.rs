@@ -0,0 +1,61 @@
+//! Commands and workflow helpers for the session context memory
+
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::commands::settings::ContextMemorySettings;
+use crate::services::context_memory::{estimate_tokens, ContextMemory};
+
+/// Final texts of the current session, created by the first remembered text
+pub type ContextMemoryState = Arc<Mutex<Option<ContextMemory>>>;
+
+/// Forget the texts remembered during this session
+#[tauri::command]
+pub async fn clear_context_memory(
+    context_state: State<'_, ContextMemoryState>,
+) -> Result<String, String> {
+    let cleared = match context_state.lock().await.as_mut() {
+        Some(memory) => {
+            let count = memory.len();
+            memory.clear();
+            count
+        }
+        None => 0,
+    };
+    eprintln!("🧽 [CONTEXT] Cleared {} remembered text(s)", cleared);
+    Ok(format!("Cleared {} remembered text(s)", cleared))
+}
+
+/// Context section to append to the GPT instructions, None when disabled or empty
+pub async fn context_prompt(
+    context_state: &ContextMemoryState,
+    settings: &ContextMemorySettings,
+) -> Option<String> {
+    if !settings.enabled {
+        return None;
+    }
+    let guard = context_state.lock().await;
+    let section = guard.as_ref()?.prompt_section(settings.token_budget)?;
+    eprintln!(
+        "🧠 [CONTEXT] Adding {} remembered text(s) to the prompt (~{} tokens)",
+        guard.as_ref().map_or(0, ContextMemory::len),
+        estimate_tokens(&section)
+    );
+    Some(section)
+}
+
+/// Remember the final text of a workflow for the following dictations
+pub async fn remember_final_text(
+    context_state: &ContextMemoryState,
+    settings: &ContextMemorySettings,
+    text: &str,
+) {
+    if !settings.enabled {
+        return;
+    }
+    let mut guard = context_state.lock().await;
+    let memory = guard.get_or_insert_with(|| ContextMemory::new(settings.max_entries));
+    memory.set_max_entries(settings.max_entries);
+    memory.remember(text);
+}
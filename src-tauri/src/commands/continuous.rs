@@ -0,0 +1,465 @@
+//! Continuous transcription sessions: rolling chunks transcribed to a session
+//! transcript (see [`crate::services::continuous_session`])
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::{mpsc, Mutex, Notify};
+use tokio::task::JoinHandle;
+
+use crate::audio::AudioCapture;
+use crate::commands::settings::{read_settings, ContinuousSettings};
+use crate::commands::state_machine::process_event;
+use crate::commands::whisper::encode_and_transcribe;
+use crate::commands::{AudioCaptureState, SystemTrayState, WhisperClientState};
+use crate::services::continuous_session::{
+    chunks_over_budget, directory_usage, SessionProgress, SessionStatus, SessionTranscript,
+};
+use crate::services::event_targets::broadcast;
+use crate::services::notifier::{Notifier, TauriNotifierService};
+use crate::services::DryRunWhisperClient;
+use crate::state::{AppEvent, AppStateMachineState};
+
+/// Global state for the running continuous session
+pub type ContinuousSessionState = Arc<Mutex<Option<Arc<ContinuousSession>>>>;
+
+/// Event sent to every window when the session changes (null once stopped),
+/// so the recording is always visible
+const SESSION_EVENT: &str = "continuous-session-changed";
+
+/// A running continuous session
+pub struct ContinuousSession {
+    transcript: SessionTranscript,
+    started: Instant,
+    progress: std::sync::Mutex<SessionProgress>,
+    stop: Notify,
+    recorder: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl ContinuousSession {
+    /// Time since the session started
+    fn offset(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Current progress
+    pub fn progress(&self) -> SessionProgress {
+        match self.progress.lock() {
+            Ok(progress) => progress.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Update the progress and send it to the windows
+    fn update(&self, app_handle: &AppHandle, change: impl FnOnce(&mut SessionProgress)) {
+        if let Ok(mut progress) = self.progress.lock() {
+            change(&mut progress);
+        }
+        let _ = broadcast(app_handle, SESSION_EVENT, self.progress());
+    }
+}
+
+/// Whether a continuous session is running (dictations wait for its end)
+pub async fn is_session_running(app_handle: &AppHandle) -> bool {
+    match app_handle.try_state::<ContinuousSessionState>() {
+        Some(state) => state.lock().await.is_some(),
+        None => false,
+    }
+}
+
+/// Start a continuous session: the microphone is recorded in rolling chunks
+/// and each chunk is transcribed to the session transcript
+#[tauri::command]
+pub async fn start_continuous_session(
+    app_handle: AppHandle,
+    audio_state: State<'_, AudioCaptureState>,
+    session_state: State<'_, ContinuousSessionState>,
+    state_machine_state: State<'_, AppStateMachineState>,
+) -> Result<SessionProgress, String> {
+    let mut session_guard = session_state.lock().await;
+    if session_guard.is_some() {
+        return Err("A continuous session is already running".to_string());
+    }
+    let capture = audio_state
+        .lock()
+        .await
+        .clone()
+        .ok_or("Audio capture not initialized")?;
+    if capture.is_recording() {
+        return Err("A dictation is already being recorded".to_string());
+    }
+
+    let settings = read_settings()
+        .await
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+    let continuous = settings.continuous.clone();
+    let transcript = SessionTranscript::create(&continuous.session_dir(), chrono::Local::now())
+        .map_err(|e| format!("Failed to create the session transcript: {}", e))?;
+
+    // Chunks are cut by the session: silence and length must not end it
+    capture.set_auto_stop_silence_seconds(0);
+    capture.set_max_recording_seconds(0);
+    capture
+        .start_capture()
+        .await
+        .map_err(|e| format!("Failed to start continuous recording: {}", e))?;
+    if let Err(e) = process_event(AppEvent::StartRecording, &state_machine_state).await {
+        let _ = capture.stop_capture().await;
+        restore_capture_settings(&app_handle).await;
+        return Err(e);
+    }
+
+    let session = Arc::new(ContinuousSession {
+        progress: std::sync::Mutex::new(SessionProgress {
+            status: SessionStatus::Recording,
+            started_at: transcript.started_at().to_rfc3339(),
+            transcript_path: transcript.path().to_path_buf(),
+            chunks_transcribed: 0,
+            chunks_failed: 0,
+            disk_usage_bytes: directory_usage(&continuous.session_dir()),
+            disk_budget_bytes: continuous.disk_budget_mb as u64 * 1024 * 1024,
+        }),
+        transcript,
+        started: Instant::now(),
+        stop: Notify::new(),
+        recorder: Mutex::new(None),
+    });
+
+    let (chunks, pending) = mpsc::unbounded_channel();
+    let chunk_duration = Duration::from_secs(continuous.chunk_minutes.clamp(1, 30) as u64 * 60);
+    tokio::spawn(transcribe_chunks(
+        app_handle.clone(),
+        Arc::clone(&session),
+        pending,
+        continuous,
+        settings.workflow.dry_run,
+    ));
+    *session.recorder.lock().await = Some(tokio::spawn(record_chunks(
+        app_handle.clone(),
+        Arc::clone(&session),
+        chunk_duration,
+        chunks,
+    )));
+    *session_guard = Some(Arc::clone(&session));
+    drop(session_guard);
+
+    eprintln!(
+        "🎙️ [CONTINUOUS] Session started, transcribing to {:?}",
+        session.transcript.path()
+    );
+    set_tray_status(&app_handle, "Recording continuously").await;
+    session.update(&app_handle, |_| {});
+    let notifier = TauriNotifierService::new(app_handle.clone());
+    let _ = notifier
+        .success("Continuous recording started: the microphone is transcribed until you stop the session")
+        .await;
+    Ok(session.progress())
+}
+
+/// Pause the continuous session (nothing is recorded until it resumes)
+#[tauri::command]
+pub async fn pause_continuous_session(
+    app_handle: AppHandle,
+    session_state: State<'_, ContinuousSessionState>,
+) -> Result<SessionProgress, String> {
+    let session = session_state
+        .lock()
+        .await
+        .clone()
+        .ok_or("No continuous session is running")?;
+    pause_session(&app_handle, &session, "Paused").await?;
+    Ok(session.progress())
+}
+
+/// Resume a paused continuous session
+#[tauri::command]
+pub async fn resume_continuous_session(
+    app_handle: AppHandle,
+    audio_state: State<'_, AudioCaptureState>,
+    session_state: State<'_, ContinuousSessionState>,
+    state_machine_state: State<'_, AppStateMachineState>,
+) -> Result<SessionProgress, String> {
+    let session = session_state
+        .lock()
+        .await
+        .clone()
+        .ok_or("No continuous session is running")?;
+    if session.progress().status == SessionStatus::Recording {
+        return Ok(session.progress());
+    }
+
+    process_event(AppEvent::ResumeRecording, &state_machine_state).await?;
+    if let Some(capture) = audio_state.lock().await.as_ref() {
+        capture
+            .resume()
+            .map_err(|e| format!("Failed to resume capture: {}", e))?;
+    }
+    let _ = session.transcript.append_note(session.offset(), "Resumed");
+    set_tray_status(&app_handle, "Recording continuously").await;
+    session.update(&app_handle, |progress| {
+        progress.status = SessionStatus::Recording
+    });
+    eprintln!("▶️ [CONTINUOUS] Session resumed");
+    Ok(session.progress())
+}
+
+/// Stop the continuous session; the last chunk is transcribed in the
+/// background. Returns the transcript path.
+#[tauri::command]
+pub async fn stop_continuous_session(
+    app_handle: AppHandle,
+    session_state: State<'_, ContinuousSessionState>,
+    state_machine_state: State<'_, AppStateMachineState>,
+) -> Result<String, String> {
+    let session = session_state
+        .lock()
+        .await
+        .take()
+        .ok_or("No continuous session is running")?;
+
+    session.stop.notify_one();
+    if let Some(recorder) = session.recorder.lock().await.take() {
+        let _ = recorder.await;
+    }
+    // The session's chunks do not go through the dictation workflow
+    if let Err(e) = process_event(AppEvent::CancelRecording, &state_machine_state).await {
+        eprintln!("⚠️ [CONTINUOUS] Failed to return to idle: {}", e);
+    }
+    restore_capture_settings(&app_handle).await;
+    set_tray_status(&app_handle, "").await;
+    let _ = broadcast(&app_handle, SESSION_EVENT, serde_json::Value::Null);
+
+    eprintln!("⏹️ [CONTINUOUS] Session stopped");
+    Ok(session.transcript.path().to_string_lossy().into_owned())
+}
+
+/// Progress of the running continuous session (None when none runs)
+#[tauri::command]
+pub async fn get_continuous_session(
+    session_state: State<'_, ContinuousSessionState>,
+) -> Result<Option<SessionProgress>, String> {
+    Ok(session_state
+        .lock()
+        .await
+        .as_ref()
+        .map(|session| session.progress()))
+}
+
+/// Pause the session, noting `reason` in the transcript
+async fn pause_session(
+    app_handle: &AppHandle,
+    session: &ContinuousSession,
+    reason: &str,
+) -> Result<(), String> {
+    if session.progress().status == SessionStatus::Paused {
+        return Ok(());
+    }
+    process_event(AppEvent::PauseRecording, &app_handle.state()).await?;
+    if let Some(capture) = app_handle
+        .state::<AudioCaptureState>()
+        .lock()
+        .await
+        .as_ref()
+    {
+        capture
+            .pause()
+            .map_err(|e| format!("Failed to pause capture: {}", e))?;
+    }
+    let _ = session.transcript.append_note(session.offset(), reason);
+    set_tray_status(app_handle, "Continuous session paused").await;
+    session.update(app_handle, |progress| {
+        progress.status = SessionStatus::Paused
+    });
+    eprintln!("⏸️ [CONTINUOUS] Session paused: {}", reason);
+    Ok(())
+}
+
+/// Recording loop: close a chunk every `chunk_duration` and start the next,
+/// until the session stops
+async fn record_chunks(
+    app_handle: AppHandle,
+    session: Arc<ContinuousSession>,
+    chunk_duration: Duration,
+    chunks: mpsc::UnboundedSender<(Duration, PathBuf)>,
+) {
+    let mut chunk_offset = Duration::ZERO;
+    loop {
+        let stopping = tokio::select! {
+            _ = tokio::time::sleep(chunk_duration) => false,
+            _ = session.stop.notified() => true,
+        };
+        let Some(capture) = app_handle.state::<AudioCaptureState>().lock().await.clone() else {
+            break;
+        };
+        match capture.stop_capture().await {
+            Ok(wav_path) => {
+                let _ = chunks.send((chunk_offset, wav_path));
+            }
+            Err(e) => eprintln!("⚠️ [CONTINUOUS] Failed to close chunk: {}", e),
+        }
+        if stopping {
+            break;
+        }
+
+        chunk_offset = session.offset();
+        if let Err(e) = capture.start_capture().await {
+            eprintln!("❌ [CONTINUOUS] Failed to start the next chunk: {}", e);
+            let _ = session
+                .transcript
+                .append_note(chunk_offset, "Recording interrupted");
+            let notifier = TauriNotifierService::new(app_handle.clone());
+            let _ = notifier
+                .error(&format!(
+                    "Continuous recording interrupted ({}): stop the session to save it",
+                    e
+                ))
+                .await;
+            break;
+        }
+        if session.progress().status == SessionStatus::Paused {
+            let _ = capture.pause();
+        }
+    }
+}
+
+/// Transcription loop: transcribe the closed chunks in order and append them
+/// to the transcript, within the disk budget
+async fn transcribe_chunks(
+    app_handle: AppHandle,
+    session: Arc<ContinuousSession>,
+    mut pending: mpsc::UnboundedReceiver<(Duration, PathBuf)>,
+    settings: ContinuousSettings,
+    dry_run: bool,
+) {
+    let session_dir = settings.session_dir();
+    let budget = settings.disk_budget_mb as u64 * 1024 * 1024;
+    let mut kept_chunks: Vec<(PathBuf, u64)> = Vec::new();
+
+    while let Some((offset, wav_path)) = pending.recv().await {
+        match transcribe_chunk(&app_handle, &wav_path, dry_run).await {
+            Ok(text) => {
+                if let Err(e) = session.transcript.append_chunk(offset, &text) {
+                    eprintln!("❌ [CONTINUOUS] Failed to write the transcript: {}", e);
+                }
+                session.update(&app_handle, |progress| progress.chunks_transcribed += 1);
+            }
+            Err(e) => {
+                eprintln!("⚠️ [CONTINUOUS] Chunk transcription failed: {}", e);
+                let _ = session
+                    .transcript
+                    .append_note(offset, "Transcription failed");
+                session.update(&app_handle, |progress| progress.chunks_failed += 1);
+            }
+        }
+
+        if settings.keep_audio {
+            let kept_path = session.transcript.chunk_audio_path(offset, "wav");
+            match move_file(&wav_path, &kept_path) {
+                Ok(size) => kept_chunks.push((kept_path, size)),
+                Err(e) => eprintln!("⚠️ [CONTINUOUS] Failed to keep chunk audio: {}", e),
+            }
+        } else {
+            let _ = std::fs::remove_file(&wav_path);
+        }
+
+        // Oldest kept chunks go first; the session pauses when they are not enough
+        let evicted = match chunks_over_budget(directory_usage(&session_dir), budget, &kept_chunks)
+        {
+            Some(evicted) => evicted,
+            None => {
+                if session.progress().status == SessionStatus::Recording {
+                    let notifier = TauriNotifierService::new(app_handle.clone());
+                    let _ = notifier
+                        .warning(
+                            "Disk budget of the continuous session reached: the session is paused",
+                        )
+                        .await;
+                    if let Err(e) =
+                        pause_session(&app_handle, &session, "Paused: disk budget reached").await
+                    {
+                        eprintln!("❌ [CONTINUOUS] {}", e);
+                    }
+                }
+                kept_chunks.iter().map(|(path, _)| path.clone()).collect()
+            }
+        };
+        for path in &evicted {
+            let _ = std::fs::remove_file(path);
+        }
+        kept_chunks.retain(|(path, _)| !evicted.contains(path));
+        session.update(&app_handle, |progress| {
+            progress.disk_usage_bytes = directory_usage(&session_dir)
+        });
+    }
+
+    // The recording loop ended: every chunk is transcribed
+    let _ = session
+        .transcript
+        .append_note(session.offset(), "Session ended");
+    let notifier = TauriNotifierService::new(app_handle.clone());
+    let _ = notifier
+        .success(&format!(
+            "Session transcript saved to {}",
+            session.transcript.path().display()
+        ))
+        .await;
+}
+
+/// Transcribe a chunk with the configured Whisper client
+async fn transcribe_chunk(
+    app_handle: &AppHandle,
+    wav_path: &Path,
+    dry_run: bool,
+) -> Result<String, String> {
+    let transcript = if dry_run {
+        encode_and_transcribe(wav_path.to_path_buf(), None, &DryRunWhisperClient::new(0)).await?
+    } else {
+        let whisper_state = app_handle
+            .try_state::<WhisperClientState>()
+            .ok_or("Whisper client state not available")?;
+        // Clone the client so dictations are not blocked while a chunk uploads
+        let client = whisper_state
+            .lock()
+            .await
+            .as_ref()
+            .cloned()
+            .ok_or("Whisper client not initialized")?;
+        encode_and_transcribe(wav_path.to_path_buf(), None, client.as_ref()).await?
+    };
+    Ok(transcript.text)
+}
+
+/// Move `from` to `to` (copying across file systems); returns the size
+fn move_file(from: &Path, to: &Path) -> std::io::Result<u64> {
+    if std::fs::rename(from, to).is_err() {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)?;
+    }
+    Ok(std::fs::metadata(to)?.len())
+}
+
+/// Apply the auto-stop settings the session turned off
+async fn restore_capture_settings(app_handle: &AppHandle) {
+    let Ok(settings) = read_settings().await else {
+        return;
+    };
+    if let Some(capture) = app_handle
+        .state::<AudioCaptureState>()
+        .lock()
+        .await
+        .as_ref()
+    {
+        capture.set_auto_stop_silence_seconds(settings.audio.auto_stop_silence_seconds);
+        capture.set_max_recording_seconds(settings.audio.max_recording_seconds);
+    }
+}
+
+/// Show the session in the tray tooltip ("" = no status)
+async fn set_tray_status(app_handle: &AppHandle, status: &str) {
+    if let Some(service) = app_handle.state::<SystemTrayState>().lock().await.as_ref() {
+        if let Err(e) = service.update_tray_status(status).await {
+            eprintln!("⚠️ [CONTINUOUS] Failed to update tray: {}", e);
+        }
+    }
+}
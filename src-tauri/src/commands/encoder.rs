@@ -1,18 +1,36 @@
-use crate::audio::{Encoder, EncodingEvent, OggInfo, OggVorbisEncoder};
+use crate::audio::{
+    Encoder, EncoderFormat, EncoderOptions, EncodingEvent, OggInfo, OggVorbisEncoder,
+};
+use crate::commands::settings::read_settings;
+use crate::commands::AudioCaptureState;
 use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
 use tokio::sync::mpsc;
 
 /// Encode a WAV file to OGG/Vorbis format
-/// This command encodes a WAV file using the OggVorbisEncoder
+/// Kept for compatibility: same as `encode_wav` with the "ogg" format
 #[tauri::command]
 pub async fn encode_wav_to_ogg(
     wav_path: String,
     output_path: Option<String>,
+) -> Result<OggInfo, String> {
+    encode_wav(wav_path, output_path, Some(EncoderFormat::Ogg)).await
+}
+
+/// Encode a WAV file to OGG, MP3 or FLAC (`encoding.format` when not given)
+#[tauri::command]
+pub async fn encode_wav(
+    wav_path: String,
+    output_path: Option<String>,
+    format: Option<EncoderFormat>,
 ) -> Result<OggInfo, String> {
     let input_path = PathBuf::from(wav_path);
     let output_path = output_path.map(PathBuf::from);
 
-    let encoder = OggVorbisEncoder::new();
+    let mut encoder = configured_encoder().await;
+    if let Some(format) = format {
+        encoder = encoder.with_format(format);
+    }
 
     // Create a channel for progress events (optional for this command)
     let (tx, mut rx) = mpsc::unbounded_channel();
@@ -51,12 +69,47 @@ pub async fn encode_wav_to_ogg(
 
 /// Get encoder configuration and capabilities
 #[tauri::command]
-pub fn get_encoder_info() -> serde_json::Value {
+pub async fn get_encoder_info(app_handle: AppHandle) -> serde_json::Value {
+    let options = configured_encoder().await.options();
+    // Negotiated input format (null before the audio capture is initialized)
+    let capture_format = match app_handle.try_state::<AudioCaptureState>() {
+        Some(state) => state
+            .lock()
+            .await
+            .as_ref()
+            .map(|capture| capture.capture_format()),
+        None => None,
+    };
     serde_json::json!({
         "default_bitrate": 32000,
         "supported_formats": ["WAV"],
-        "output_format": "OGG/Vorbis",
+        "output_format": options.format,
+        "supported_output_formats": ["ogg", "mp3", "flac"],
         "size_limit_mb": 23,
-        "forecast_accuracy": "≤2%"
+        "forecast_accuracy": "≤2%",
+        "complexity": options.complexity,
+        "application": options.application,
+        "dtx": options.dtx,
+        "vbr": options.vbr,
+        "noise_suppression": options.noise_suppression,
+        "downmix_to_mono": options.downmix_to_mono,
+        "capture_format": capture_format,
+        "supported_applications": ["voip", "audio"]
     })
 }
+
+/// Encoder with the format / complexity / application / DTX / noise
+/// suppression options from settings
+pub async fn configured_encoder() -> OggVorbisEncoder {
+    let options = match read_settings().await {
+        Ok(settings) => EncoderOptions {
+            noise_suppression: settings.audio.noise_suppression,
+            ..settings.encoding.encoder_options()
+        },
+        Err(e) => {
+            eprintln!("⚠️  Warning: Failed to load encoder settings: {}", e);
+            EncoderOptions::default()
+        }
+    };
+    OggVorbisEncoder::new().with_options(options)
+}
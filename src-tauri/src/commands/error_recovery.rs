@@ -104,6 +104,7 @@ pub async fn get_error_state(
                 | crate::state::recording_state_machine::AppState::GPTFormattingError { .. }
                 | crate::state::recording_state_machine::AppState::ClipboardError { .. }
                 | crate::state::recording_state_machine::AppState::ProfileValidationError { .. }
+                | crate::state::recording_state_machine::AppState::RecordingDeviceError { .. }
         );
 
         if is_error_state {
@@ -0,0 +1,274 @@
+//! Commands for listing and retrying failed processing workflows
+
+use crate::commands::settings::read_settings;
+use crate::commands::whisper::encode_and_transcribe;
+use crate::commands::{
+    get_profiles_cached, ClipboardServiceState, GptClientState, WhisperClientState,
+};
+use crate::services::duplicate_detection::{AudioFingerprint, DuplicateDetector};
+use crate::services::profile_engine::ProfileBehavior;
+use crate::services::workflow_trace;
+use crate::services::{FailedWorkflow, FailedWorkflowStore, ProfileEngine, WorkflowStage};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::workflow_log;
+
+/// Default number of manual retries allowed per failed workflow
+const DEFAULT_RETRY_BUDGET: u32 = 3;
+
+/// Record a failed workflow so it can be retried later
+///
+/// Persistence errors are only logged: failing to record a failure must not
+/// mask the original error returned to the caller.
+pub fn record_failed_workflow(
+    stage: WorkflowStage,
+    error: &str,
+    wav_path: Option<std::path::PathBuf>,
+    text: Option<String>,
+    profile_id: Option<String>,
+) -> Option<FailedWorkflow> {
+    let store = FailedWorkflowStore::default_location();
+    match store.record(stage, error.to_string(), wav_path, text, profile_id) {
+        Ok(job) => {
+            eprintln!(
+                "🗂️  [FAILED-JOBS] Recorded failed workflow {} ({:?})",
+                job.id, job.stage
+            );
+            Some(job)
+        }
+        Err(e) => {
+            eprintln!("⚠️  [FAILED-JOBS] Failed to record failed workflow: {}", e);
+            None
+        }
+    }
+}
+
+/// Seconds since the previous recording when `wav_path` repeats it within
+/// `window_seconds` (e.g. a double shortcut press)
+///
+/// Fingerprinting errors are only logged: the recording is then processed.
+pub fn duplicate_of_previous_recording(wav_path: &Path, window_seconds: u32) -> Option<u64> {
+    let fingerprint = match AudioFingerprint::from_wav(wav_path) {
+        Ok(fingerprint) => fingerprint,
+        Err(e) => {
+            eprintln!(
+                "⚠️  [DUPLICATES] Failed to fingerprint the recording: {}",
+                e
+            );
+            return None;
+        }
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    DuplicateDetector::global().check(fingerprint, now, u64::from(window_seconds))
+}
+
+/// List failed workflows together with the configured retry budget
+#[tauri::command]
+pub async fn list_failed_workflows() -> Result<serde_json::Value, String> {
+    let retry_budget = retry_budget().await;
+    let jobs = FailedWorkflowStore::default_location()
+        .list()
+        .map_err(|e| format!("Failed to load failed workflows: {}", e))?;
+
+    let jobs: Vec<serde_json::Value> = jobs
+        .into_iter()
+        .map(|job| {
+            let retries_left = retry_budget.saturating_sub(job.retry_count);
+            serde_json::json!({
+                "job": job,
+                "retries_left": retries_left,
+            })
+        })
+        .collect();
+
+    Ok(serde_json::json!({
+        "retry_budget": retry_budget,
+        "failed_workflows": jobs,
+    }))
+}
+
+/// Retry a failed workflow from the stage where it failed
+#[tauri::command]
+pub async fn retry_failed_workflow(
+    id: String,
+    app_handle: AppHandle,
+    whisper_state: State<'_, WhisperClientState>,
+    gpt_state: State<'_, GptClientState>,
+    clipboard_state: State<'_, ClipboardServiceState>,
+) -> Result<String, String> {
+    let store = FailedWorkflowStore::default_location();
+    let mut job = store
+        .get(&id)
+        .map_err(|e| format!("Failed to load failed workflow: {}", e))?;
+
+    let retry_budget = retry_budget().await;
+    if job.retry_count >= retry_budget {
+        return Err(format!(
+            "Retry budget exhausted for workflow {} ({} of {} attempts used)",
+            id, job.retry_count, retry_budget
+        ));
+    }
+
+    // Trace the retry under the workflow that failed
+    if let Some(workflow_id) = &job.workflow_id {
+        workflow_trace::resume_workflow(workflow_id);
+    }
+    workflow_log!(
+        "🔁 [FAILED-JOBS] Retrying workflow {} from {:?} (attempt {}/{})",
+        id,
+        job.stage,
+        job.retry_count + 1,
+        retry_budget
+    );
+    job.retry_count += 1;
+
+    let result = run_failed_workflow(&mut job, &whisper_state, &gpt_state, &clipboard_state).await;
+    let _ = app_handle.emit("failed-workflows-updated", &id);
+
+    match result {
+        Ok(final_text) => {
+            store
+                .remove(&id)
+                .map_err(|e| format!("Failed to update failed workflows: {}", e))?;
+            if let Some(wav_path) = &job.wav_path {
+                let _ = tokio::fs::remove_file(wav_path).await;
+            }
+
+            eprintln!("✅ [FAILED-JOBS] Workflow {} recovered", id);
+            Ok(final_text)
+        }
+        Err(error) => {
+            job.error = error.clone();
+            store
+                .update(&job)
+                .map_err(|e| format!("Failed to update failed workflows: {}", e))?;
+
+            eprintln!(
+                "❌ [FAILED-JOBS] Retry of workflow {} failed: {}",
+                id, error
+            );
+            Err(error)
+        }
+    }
+}
+
+/// Discard a failed workflow and delete its artifacts
+#[tauri::command]
+pub async fn discard_failed_workflow(id: String, app_handle: AppHandle) -> Result<String, String> {
+    let job = FailedWorkflowStore::default_location()
+        .remove(&id)
+        .map_err(|e| format!("Failed to discard workflow: {}", e))?;
+
+    if let Some(wav_path) = &job.wav_path {
+        let _ = tokio::fs::remove_file(wav_path).await;
+    }
+    let _ = app_handle.emit("failed-workflows-updated", &id);
+
+    Ok(format!("Failed workflow {} discarded", id))
+}
+
+/// Run the remaining stages of a failed workflow
+///
+/// On failure `job.stage` is moved forward to the stage that failed this time,
+/// so a transcription that succeeded is not repeated on the next retry.
+async fn run_failed_workflow(
+    job: &mut FailedWorkflow,
+    whisper_state: &State<'_, WhisperClientState>,
+    gpt_state: &State<'_, GptClientState>,
+    clipboard_state: &State<'_, ClipboardServiceState>,
+) -> Result<String, String> {
+    if job.stage == WorkflowStage::Transcription {
+        let wav_path = job
+            .wav_path
+            .clone()
+            .ok_or_else(|| "Failed workflow has no recording to transcribe".to_string())?;
+        if !wav_path.exists() {
+            return Err(format!(
+                "Recording no longer exists: {}",
+                wav_path.display()
+            ));
+        }
+
+        let profile = load_job_profile(job).await;
+        let prompt = profile
+            .as_ref()
+            .and_then(crate::commands::whisper::profile_whisper_prompt)
+            .map(|prompt| prompt.text);
+
+        let transcript = {
+            let whisper_guard = whisper_state.lock().await;
+            let client = whisper_guard.as_ref().ok_or_else(|| {
+                "Whisper client not initialized. Please check your API key in settings.".to_string()
+            })?;
+            encode_and_transcribe(wav_path, prompt, client.as_ref()).await?
+        };
+
+        let formatting_enabled = read_settings()
+            .await
+            .map(|settings| settings.formatting.enabled)
+            .unwrap_or(true);
+
+        let final_text = match profile {
+            Some(profile) if formatting_enabled && profile.should_use_gpt_formatting() => {
+                let gpt_guard = gpt_state.lock().await;
+                match gpt_guard.as_ref() {
+                    Some(client) => client
+                        .format_text(
+                            &transcript.text,
+                            profile.prompt.as_deref().unwrap_or_default(),
+                            profile.example_input.as_deref().unwrap_or_default(),
+                            profile.example_output.as_deref().unwrap_or_default(),
+                        )
+                        .await
+                        .unwrap_or_else(|e| {
+                            eprintln!(
+                                "⚠️  [FAILED-JOBS] GPT formatting failed, using transcript: {}",
+                                e
+                            );
+                            transcript.text.clone()
+                        }),
+                    None => transcript.text.clone(),
+                }
+            }
+            _ => transcript.text.clone(),
+        };
+
+        job.stage = WorkflowStage::Clipboard;
+        job.text = Some(final_text);
+    }
+
+    let text = job.text.clone().unwrap_or_default();
+    let clipboard_guard = clipboard_state.lock().await;
+    let clipboard = clipboard_guard
+        .as_ref()
+        .ok_or_else(|| "Clipboard service not initialized".to_string())?;
+    clipboard
+        .copy(&text)
+        .await
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+
+    Ok(text)
+}
+
+/// Load the profile that was active when the workflow failed
+async fn load_job_profile(job: &FailedWorkflow) -> Option<crate::services::Profile> {
+    let profile_id = job.profile_id.as_ref()?;
+    let collection = get_profiles_cached().await.ok()?;
+    ProfileEngine::new()
+        .find_profile_by_id(&collection, profile_id)
+        .ok()
+        .cloned()
+}
+
+/// Configured number of manual retries per failed workflow
+async fn retry_budget() -> u32 {
+    read_settings()
+        .await
+        .map(|settings| settings.workflow.retry_budget)
+        .unwrap_or(DEFAULT_RETRY_BUDGET)
+}
@@ -0,0 +1,59 @@
+//! Commands for the experimental feature flags
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::commands::settings::{read_settings, save_settings};
+use crate::services::event_targets;
+use crate::services::feature_flags::{FeatureFlag, FeatureRegistry};
+
+/// A feature flag and its state (`list_feature_flags` entry)
+#[derive(Debug, Clone, Serialize)]
+pub struct FeatureFlagInfo {
+    pub flag: FeatureFlag,
+    pub description: &'static str,
+    pub enabled: bool,
+}
+
+/// Load the flags from the settings into the registry (called at startup)
+pub async fn load_feature_flags() -> Result<(), String> {
+    let settings = read_settings().await?;
+    FeatureRegistry::global().load(settings.features);
+    Ok(())
+}
+
+/// List the feature flags and whether they are on
+#[tauri::command]
+pub fn list_feature_flags() -> Vec<FeatureFlagInfo> {
+    let flags = FeatureRegistry::global().flags();
+    FeatureFlag::ALL
+        .iter()
+        .map(|&flag| FeatureFlagInfo {
+            flag,
+            description: flag.description(),
+            enabled: flags.is_enabled(flag),
+        })
+        .collect()
+}
+
+/// Turn a feature flag on or off and persist it in settings
+#[tauri::command]
+pub async fn set_feature_flag(
+    flag: FeatureFlag,
+    enabled: bool,
+    app_handle: AppHandle,
+) -> Result<Vec<FeatureFlagInfo>, String> {
+    let mut settings = read_settings().await?;
+    settings.features.set(flag, enabled);
+    // Saving the settings updates the registry
+    save_settings(settings).await?;
+    eprintln!(
+        "🚩 [FEATURES] {:?} {}",
+        flag,
+        if enabled { "on" } else { "off" }
+    );
+
+    let flags = list_feature_flags();
+    let _ = event_targets::broadcast(&app_handle, "feature-flags-changed", &flags);
+    Ok(flags)
+}
@@ -0,0 +1,164 @@
+//! Commands for timed focus sessions (dictation sprints)
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::Mutex;
+
+use crate::commands::ProfileAppState;
+use crate::services::focus_session::{FocusDigest, FocusSession};
+use crate::services::notifier::{Notifier, TauriNotifierService};
+
+/// Global state for the running focus session
+pub type FocusSessionState = Arc<Mutex<Option<FocusSession>>>;
+
+/// Longest focus session, in minutes
+const MAX_SESSION_MINUTES: u32 = 240;
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Start a focus session bound to the active profile
+///
+/// The session ends by itself after `minutes` and emits `focus-session-ended`
+/// with its digest.
+#[tauri::command]
+pub async fn start_focus_session(
+    minutes: u32,
+    app_handle: AppHandle,
+    state: State<'_, FocusSessionState>,
+    profile_state: State<'_, ProfileAppState>,
+) -> Result<FocusSession, String> {
+    if minutes == 0 || minutes > MAX_SESSION_MINUTES {
+        return Err(format!(
+            "Focus sessions last 1 to {} minutes",
+            MAX_SESSION_MINUTES
+        ));
+    }
+
+    let session = {
+        let mut state_guard = state.lock().await;
+        if state_guard.is_some() {
+            return Err("A focus session is already running".to_string());
+        }
+        let profile_id = profile_state
+            .lock()
+            .map_err(|e| format!("Failed to read the active profile: {}", e))?
+            .active_profile_id
+            .clone();
+        let session = FocusSession::new(profile_id, minutes, now_secs());
+        *state_guard = Some(session.clone());
+        session
+    };
+    eprintln!(
+        "🍅 [FOCUS] Session {} started for {} min (profile: {:?})",
+        session.id, minutes, session.profile_id
+    );
+
+    // End the session when its time is up, unless it was ended before
+    let session_id = session.id.clone();
+    let timer_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(u64::from(minutes) * 60)).await;
+        let state = timer_handle.state::<FocusSessionState>();
+        let session = {
+            let mut state_guard = state.lock().await;
+            match state_guard.as_ref() {
+                Some(session) if session.id == session_id => state_guard.take(),
+                _ => None,
+            }
+        };
+        if let Some(session) = session {
+            finish_session(&timer_handle, session).await;
+        }
+    });
+
+    let _ = app_handle.emit("focus-session-started", &session);
+    Ok(session)
+}
+
+/// End the running focus session early and return its digest
+#[tauri::command]
+pub async fn end_focus_session(
+    app_handle: AppHandle,
+    state: State<'_, FocusSessionState>,
+) -> Result<FocusDigest, String> {
+    let session = state
+        .lock()
+        .await
+        .take()
+        .ok_or("No focus session is running")?;
+    Ok(finish_session(&app_handle, session).await)
+}
+
+/// Running focus session with its remaining time (null when none)
+#[tauri::command]
+pub async fn get_focus_session(
+    state: State<'_, FocusSessionState>,
+) -> Result<serde_json::Value, String> {
+    Ok(match state.lock().await.as_ref() {
+        Some(session) => serde_json::json!({
+            "session": session,
+            "remaining_seconds": session.remaining_seconds(now_secs()),
+        }),
+        None => serde_json::Value::Null,
+    })
+}
+
+/// Tag a completed recording to the running session, returning the session ID
+pub async fn tag_recording(
+    state: &FocusSessionState,
+    text: &str,
+    profile_id: Option<String>,
+) -> Option<String> {
+    let mut state_guard = state.lock().await;
+    let session = state_guard.as_mut()?;
+    session.add_recording(text, profile_id, now_secs());
+    Some(session.id.clone())
+}
+
+/// Whether a profile shortcut may switch to `profile_id` (not during a
+/// focus session bound to another profile)
+pub async fn profile_shortcut_allowed(app_handle: &AppHandle, profile_id: &str) -> bool {
+    let Some(state) = app_handle.try_state::<FocusSessionState>() else {
+        return true;
+    };
+    let allowed = state
+        .lock()
+        .await
+        .as_ref()
+        .is_none_or(|session| session.allows_profile(profile_id));
+    if !allowed {
+        eprintln!(
+            "🍅 [FOCUS] Profile shortcut for '{}' suppressed during the focus session",
+            profile_id
+        );
+    }
+    allowed
+}
+
+/// Build the digest of a session that ended, emit it and notify the user
+async fn finish_session(app_handle: &AppHandle, session: FocusSession) -> FocusDigest {
+    let digest = session.digest(now_secs());
+    eprintln!(
+        "🍅 [FOCUS] Session {} ended: {} recording(s), {} words",
+        digest.session_id, digest.recordings, digest.total_words
+    );
+
+    if let Err(e) = app_handle.emit("focus-session-ended", &digest) {
+        eprintln!("⚠️  Warning: Failed to emit focus-session-ended: {}", e);
+    }
+    let notifier = TauriNotifierService::new(app_handle.clone());
+    let _ = notifier
+        .success(&format!(
+            "Focus session complete: {} words in {} recording(s)",
+            digest.total_words, digest.recordings
+        ))
+        .await;
+
+    digest
+}
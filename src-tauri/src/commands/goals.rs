@@ -0,0 +1,82 @@
+//! Commands for the dictation word count goals
+
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::Local;
+
+use crate::commands::history::history_store;
+use crate::commands::settings::read_settings;
+use crate::services::goals::{compute_progress, GoalProgress};
+use crate::state::AppStateMachineState;
+
+/// Unix timestamp the current session (app run) started at
+pub fn session_started_at() -> u64 {
+    static STARTED_AT: OnceLock<u64> = OnceLock::new();
+    *STARTED_AT.get_or_init(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    })
+}
+
+/// Progress towards the configured goals, computed from the history
+pub async fn goal_progress() -> Result<GoalProgress, String> {
+    let settings = read_settings().await?;
+    if !settings.goals.enabled {
+        return Err("Dictation goals are disabled in settings".to_string());
+    }
+    if !settings.history.enabled {
+        return Err("Dictation goals need the history to be enabled".to_string());
+    }
+
+    let entries = history_store(&settings.history)?
+        .list()
+        .map_err(|e| format!("Failed to load history: {}", e))?;
+    Ok(compute_progress(
+        &entries,
+        settings.goals.daily_words,
+        settings.goals.session_words,
+        session_started_at(),
+        Local::now(),
+    ))
+}
+
+/// Emit `goal-progress` after a workflow was recorded in the history
+///
+/// Does nothing when the goals are disabled; other errors are only logged.
+pub async fn emit_goal_progress(state_machine_state: &AppStateMachineState) {
+    let enabled = read_settings()
+        .await
+        .map(|settings| settings.goals.enabled)
+        .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+
+    let progress = match goal_progress().await {
+        Ok(progress) => progress,
+        Err(e) => {
+            eprintln!("⚠️  [GOALS] {}", e);
+            return;
+        }
+    };
+    eprintln!(
+        "🎯 [GOALS] {} / {} words today (streak: {} days)",
+        progress.today_words, progress.daily_goal, progress.streak_days
+    );
+
+    if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
+        let state_machine_guard = state_machine.lock().await;
+        if let Err(e) = state_machine_guard.emit_event("goal-progress", &progress) {
+            eprintln!("⚠️  Warning: Failed to emit goal-progress: {}", e);
+        }
+    }
+}
+
+/// Current progress towards the dictation goals
+#[tauri::command]
+pub async fn get_goal_progress() -> Result<GoalProgress, String> {
+    goal_progress().await
+}
@@ -2,6 +2,7 @@ use std::sync::Arc;
 use tauri::State;
 use tokio::sync::Mutex;
 
+use crate::commands::settings::read_settings;
 use crate::services::GptClient;
 
 /// Global state for the GPT client service
@@ -17,7 +18,13 @@ pub async fn init_gpt_client(
         return Err("API key cannot be empty".to_string());
     }
 
-    let client = GptClient::new(api_key);
+    let (network, formatting) = read_settings()
+        .await
+        .map(|settings| (settings.network, settings.formatting))
+        .unwrap_or_default();
+    let client = GptClient::with_network(api_key, &network)
+        .map_err(|e| e.to_string())?
+        .with_retry_policy(formatting.retry_policy());
     let mut state_guard = state.lock().await;
     *state_guard = Some(client);
 
@@ -25,6 +32,31 @@ pub async fn init_gpt_client(
     Ok("GPT client initialized successfully".to_string())
 }
 
+/// Drop the GPT client (formatting is skipped until it is initialized again)
+///
+/// Waits for a formatting request in flight to finish.
+#[tauri::command]
+pub async fn shutdown_gpt_client(state: State<'_, GptClientState>) -> Result<String, String> {
+    let mut state_guard = state.lock().await;
+    Ok(match state_guard.take() {
+        Some(_) => "GPT client shut down".to_string(),
+        None => "GPT client was not initialized".to_string(),
+    })
+}
+
+/// Initialize the GPT client again from the settings (API key, network)
+///
+/// The previous client is kept when the new one cannot be created.
+#[tauri::command]
+pub async fn reinit_gpt_client(state: State<'_, GptClientState>) -> Result<String, String> {
+    let api_key = read_settings().await?.whisper.api_key;
+    if api_key.trim().is_empty() {
+        shutdown_gpt_client(state).await?;
+        return Err("No API key in settings, GPT client shut down".to_string());
+    }
+    init_gpt_client(api_key, state).await
+}
+
 /// Check if the GPT client is initialized
 #[tauri::command]
 pub async fn is_gpt_initialized(state: State<'_, GptClientState>) -> Result<bool, String> {
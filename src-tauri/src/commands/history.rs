@@ -0,0 +1,473 @@
+//! Commands for the clipboard history, the audio archive and data retention
+
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::audio::{Encoder, EncoderFormat};
+use crate::commands::app_lock::require_unlocked;
+use crate::commands::encoder::configured_encoder;
+use crate::commands::gpt::format_text_with_gpt;
+use crate::commands::settings::{read_settings, HistorySettings, RecordingArchiveSettings};
+use crate::commands::{get_profiles_cached, ClipboardServiceState, GptClientState};
+use crate::services::corrections::{Correction, CorrectionStore};
+use crate::services::profile_engine::ProfileBehavior;
+use crate::services::recording_archive::RecordingArchive;
+use crate::services::webhook::{post_text, WebhookPayload};
+use crate::services::{
+    DataKey, FilenameContext, FilenameTemplate, HistoryEntry, HistoryStore, PurgeReport,
+    RetentionPolicy, TranscriptionSegment,
+};
+use crate::state::recording_state_machine::AppState;
+use crate::state::AppStateMachineState;
+use crate::workflow_log;
+
+/// Where a re-rendered history entry is delivered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum RenderTarget {
+    /// Copy to the clipboard
+    Clipboard,
+    /// Save to a new file in the output directory (named by the output template)
+    File,
+    /// POST to a webhook
+    Webhook { url: String },
+}
+
+/// Text rendered from a history entry
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderedEntry {
+    pub text: String,
+    pub profile_id: String,
+    /// File written for the `file` target
+    pub path: Option<String>,
+}
+
+/// A term mis-transcribed in a history entry, flagged while correcting it
+#[derive(Debug, Clone, Deserialize)]
+pub struct FlaggedTerm {
+    /// Term as transcribed
+    pub heard: String,
+    /// Term as it should be written
+    pub correct: String,
+}
+
+/// Interval between scheduled retention purges
+const RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Data key from the OS keyring, loaded once per process
+fn data_key() -> Result<DataKey, String> {
+    static DATA_KEY: Mutex<Option<DataKey>> = Mutex::new(None);
+
+    let mut cached = DATA_KEY.lock().unwrap();
+    if let Some(key) = cached.as_ref() {
+        return Ok(key.clone());
+    }
+    let key = DataKey::from_keyring()
+        .map_err(|e| format!("Failed to load the data encryption key: {}", e))?;
+    *cached = Some(key.clone());
+    Ok(key)
+}
+
+/// History store configured by the history settings
+pub fn history_store(settings: &HistorySettings) -> Result<HistoryStore, String> {
+    if settings.encrypt_at_rest {
+        return Ok(HistoryStore::default_location(Some(data_key()?)));
+    }
+
+    // Data encrypted before encryption was switched off still needs the key
+    let store = HistoryStore::default_location(None);
+    let has_encrypted_data = store
+        .contains_encrypted_data()
+        .map_err(|e| format!("Failed to inspect history: {}", e))?;
+    if has_encrypted_data {
+        Ok(store.with_decryption_key(data_key()?))
+    } else {
+        Ok(store)
+    }
+}
+
+/// Record a completed workflow in the history, archiving its recording when enabled
+///
+/// Errors are only logged: the workflow has already succeeded at this point.
+pub async fn record_workflow_history(
+    settings: &HistorySettings,
+    final_text: String,
+    original_transcript: String,
+    segments: Vec<TranscriptionSegment>,
+    profile_id: Option<String>,
+    focus_session_id: Option<String>,
+    wav_path: &Path,
+) {
+    if !settings.enabled {
+        return;
+    }
+    let store = match history_store(settings) {
+        Ok(store) => store,
+        Err(e) => {
+            workflow_log!("⚠️  [HISTORY] {}", e);
+            return;
+        }
+    };
+
+    // Archive the recording as OGG, the format playback decodes
+    let ogg_path = if settings.archive_audio {
+        match configured_encoder()
+            .await
+            .with_format(EncoderFormat::Ogg)
+            .encode(wav_path, None, None)
+            .await
+        {
+            Ok(ogg_info) => Some(ogg_info.path),
+            Err(e) => {
+                workflow_log!(
+                    "⚠️  [HISTORY] Failed to encode recording for the archive: {}",
+                    e
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    match store.record(
+        final_text,
+        original_transcript,
+        segments,
+        profile_id,
+        ogg_path.as_deref(),
+    ) {
+        Ok(entry) => {
+            workflow_log!(
+                "🗂️  [HISTORY] Recorded history entry {} (audio archived: {})",
+                entry.id,
+                entry.has_audio
+            );
+            if let Some(session_id) = focus_session_id {
+                if let Err(e) = store.tag_focus_session(&entry.id, &session_id) {
+                    workflow_log!("⚠️  [HISTORY] Failed to tag focus session: {}", e);
+                }
+            }
+        }
+        Err(e) => workflow_log!("⚠️  [HISTORY] Failed to record history entry: {}", e),
+    }
+
+    if let Some(ogg_path) = ogg_path {
+        let _ = tokio::fs::remove_file(ogg_path).await;
+    }
+}
+
+/// Keep a copy of a workflow's recording in the recording archive (when
+/// enabled), then prune the oldest archived recordings
+///
+/// Errors are only logged: the workflow has already succeeded at this point.
+pub async fn archive_recording(settings: &RecordingArchiveSettings, wav_path: &Path) {
+    if !settings.enabled {
+        return;
+    }
+    let archive = RecordingArchive::new(settings.archive_dir(), settings.retention_count);
+
+    let encoded = match settings.format.encoder_format() {
+        None => None,
+        Some(format) => match configured_encoder()
+            .await
+            .with_format(format)
+            .encode(wav_path, None, None)
+            .await
+        {
+            Ok(ogg_info) => Some(ogg_info.path),
+            Err(e) => {
+                workflow_log!(
+                    "⚠️  [ARCHIVE] Failed to encode recording, archiving the WAV: {}",
+                    e
+                );
+                None
+            }
+        },
+    };
+    match archive.archive(encoded.as_deref().unwrap_or(wav_path)) {
+        Ok(path) => workflow_log!("🗄️  [ARCHIVE] Recording archived to {}", path.display()),
+        Err(e) => workflow_log!("⚠️  [ARCHIVE] Failed to archive recording: {}", e),
+    }
+    if let Some(ogg_path) = encoded {
+        let _ = tokio::fs::remove_file(ogg_path).await;
+    }
+
+    match archive.prune() {
+        Ok(pruned) if !pruned.is_empty() => workflow_log!(
+            "🧹 [ARCHIVE] Deleted {} archived recording(s) beyond the retention count",
+            pruned.len()
+        ),
+        Ok(_) => {}
+        Err(e) => workflow_log!("⚠️  [ARCHIVE] Failed to prune archived recordings: {}", e),
+    }
+}
+
+/// History entry recorded by the workflow shown in ProcessingComplete
+pub async fn last_workflow_entry(
+    store: &HistoryStore,
+    state_machine_state: &AppStateMachineState,
+) -> Result<HistoryEntry, String> {
+    let original_transcript = {
+        let state_machine = state_machine_state.lock().await;
+        let state_machine = state_machine
+            .as_ref()
+            .ok_or_else(|| "State machine not available".to_string())?;
+        let state_machine_guard = state_machine.lock().await;
+        match state_machine_guard.current_state() {
+            AppState::ProcessingComplete {
+                original_transcript,
+                ..
+            } => original_transcript.clone(),
+            _ => return Err("No completed workflow: not in ProcessingComplete state".to_string()),
+        }
+    };
+
+    store
+        .list()
+        .map_err(|e| format!("Failed to load history: {}", e))?
+        .into_iter()
+        .find(|entry| entry.original_transcript == original_transcript)
+        .ok_or_else(|| "The last workflow is not in the history".to_string())
+}
+
+/// Correction dictionary built from the flagged terms (empty when unreadable)
+pub fn correction_dictionary() -> Vec<Correction> {
+    CorrectionStore::default_location()
+        .list()
+        .unwrap_or_else(|e| {
+            eprintln!(
+                "⚠️  [CORRECTIONS] Failed to load the correction dictionary: {}",
+                e
+            );
+            Vec::new()
+        })
+}
+
+/// Apply the retention settings: purge expired data and (re-)encrypt as configured
+pub async fn apply_retention_policy() -> Result<PurgeReport, String> {
+    let settings = read_settings().await?.history;
+    let store = history_store(&settings)?;
+
+    let rewritten = store
+        .reencrypt()
+        .map_err(|e| format!("Failed to update history encryption: {}", e))?;
+    if rewritten > 0 {
+        eprintln!(
+            "🔐 [HISTORY] Rewrote {} file(s) (encrypted: {})",
+            rewritten, settings.encrypt_at_rest
+        );
+    }
+
+    let policy = retention_policy(&settings).await;
+    if policy.keeps_everything() {
+        return Ok(PurgeReport::default());
+    }
+    let report = store
+        .purge(&policy)
+        .map_err(|e| format!("Failed to purge history: {}", e))?;
+    if report.entries > 0 || report.audio_files > 0 {
+        eprintln!(
+            "🧹 [HISTORY] Purged {} entries and {} recordings past their retention ({} days, {} profile override(s))",
+            report.entries,
+            report.audio_files,
+            settings.retention_days,
+            policy.profile_max_age_secs.len()
+        );
+    }
+    Ok(report)
+}
+
+/// Retention of the history: `retention_days` (0 = forever), overridden by
+/// the profiles declaring their own retention in hours
+async fn retention_policy(settings: &HistorySettings) -> RetentionPolicy {
+    let mut policy = RetentionPolicy::uniform(
+        (settings.retention_days > 0).then(|| u64::from(settings.retention_days) * 24 * 60 * 60),
+    );
+    match get_profiles_cached().await {
+        Ok(collection) => {
+            policy.profile_max_age_secs = collection
+                .profiles
+                .iter()
+                .filter_map(|profile| {
+                    let hours = profile.history_retention_hours?;
+                    Some((
+                        profile.id.clone(),
+                        (hours > 0).then(|| u64::from(hours) * 60 * 60),
+                    ))
+                })
+                .collect();
+        }
+        Err(e) => eprintln!(
+            "⚠️  [HISTORY] Profiles unavailable, applying the global retention only: {}",
+            e
+        ),
+    }
+    policy
+}
+
+/// Apply the retention policy at startup and then every hour
+pub async fn run_retention_schedule() {
+    let mut interval = tokio::time::interval(RETENTION_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(e) = apply_retention_policy().await {
+            eprintln!("⚠️  [HISTORY] Retention purge failed: {}", e);
+        }
+    }
+}
+
+/// List the history, newest first
+#[tauri::command]
+pub async fn list_history() -> Result<serde_json::Value, String> {
+    require_unlocked()?;
+    let settings = read_settings().await?.history;
+    let entries = history_store(&settings)?
+        .list()
+        .map_err(|e| format!("Failed to load history: {}", e))?;
+
+    Ok(serde_json::json!({
+        "entries": entries,
+        "retention_days": settings.retention_days,
+        "encrypted": settings.encrypt_at_rest,
+    }))
+}
+
+/// Delete the whole history and audio archive
+#[tauri::command]
+pub async fn clear_history() -> Result<PurgeReport, String> {
+    require_unlocked()?;
+    let settings = read_settings().await?.history;
+    let report = history_store(&settings)?
+        .clear()
+        .map_err(|e| format!("Failed to clear history: {}", e))?;
+    eprintln!(
+        "🗑️  [HISTORY] Cleared {} entries and {} recordings",
+        report.entries, report.audio_files
+    );
+    Ok(report)
+}
+
+/// Replace the final text of a history entry with a corrected one
+///
+/// `flagged_terms` are added to the correction dictionary: later recordings
+/// teach them to Whisper and fix them in the transcript.
+#[tauri::command]
+pub async fn update_history_entry_text(
+    id: String,
+    text: String,
+    flagged_terms: Option<Vec<FlaggedTerm>>,
+) -> Result<HistoryEntry, String> {
+    require_unlocked()?;
+    let settings = read_settings().await?.history;
+    let entry = history_store(&settings)?
+        .update_text(&id, text)
+        .map_err(|e| format!("Failed to update history entry: {}", e))?;
+
+    let store = CorrectionStore::default_location();
+    for term in flagged_terms.unwrap_or_default() {
+        store
+            .add(&term.heard, &term.correct)
+            .map_err(|e| format!("Failed to save correction '{}': {}", term.heard, e))?;
+    }
+    eprintln!("✏️  [HISTORY] Corrected entry {}", entry.id);
+    Ok(entry)
+}
+
+/// Render a history entry's original transcript with a profile and deliver it
+///
+/// Unlike `reformat_with_profile`, this works on any stored dictation and
+/// leaves the state machine alone.
+#[tauri::command]
+pub async fn render_history_entry(
+    id: String,
+    profile_id: String,
+    target: RenderTarget,
+    gpt_state: State<'_, GptClientState>,
+    clipboard_state: State<'_, ClipboardServiceState>,
+) -> Result<RenderedEntry, String> {
+    require_unlocked()?;
+    let settings = read_settings().await?;
+    let entry = history_store(&settings.history)?
+        .get(&id)
+        .map_err(|e| format!("Failed to load history entry: {}", e))?;
+
+    let profile = get_profiles_cached()
+        .await?
+        .profiles
+        .into_iter()
+        .find(|profile| profile.id == profile_id)
+        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+
+    let text = if profile.should_use_gpt_formatting() {
+        format_text_with_gpt(
+            entry.original_transcript.clone(),
+            profile.prompt.clone().unwrap_or_default(),
+            profile.example_input.clone().unwrap_or_default(),
+            profile.example_output.clone().unwrap_or_default(),
+            gpt_state,
+        )
+        .await?
+    } else {
+        entry.original_transcript.clone()
+    };
+    eprintln!(
+        "🗂️  [HISTORY] Rendered entry {} with profile '{}' to {:?}",
+        entry.id, profile.name, target
+    );
+
+    let mut path = None;
+    match target {
+        RenderTarget::Clipboard => {
+            let clipboard_guard = clipboard_state.lock().await;
+            let clipboard = clipboard_guard
+                .as_ref()
+                .ok_or("Clipboard service not initialized")?;
+            clipboard
+                .copy(&text)
+                .await
+                .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+        }
+        RenderTarget::File => {
+            let template = FilenameTemplate::parse(&settings.output.filename_template)
+                .map_err(|e| format!("Invalid output filename template: {}", e))?;
+            let context = FilenameContext {
+                timestamp: i64::try_from(entry.created_at)
+                    .ok()
+                    .and_then(|created_at| Local.timestamp_opt(created_at, 0).single())
+                    .unwrap_or_else(Local::now),
+                ..FilenameContext::now(&profile.name, &text)
+            };
+            let written = template
+                .write_unique(&settings.output.output_dir(), &context, &text)
+                .map_err(|e| format!("Failed to save output file: {}", e))?;
+            path = Some(written.to_string_lossy().to_string());
+        }
+        RenderTarget::Webhook { url } => {
+            let payload = WebhookPayload {
+                text: text.clone(),
+                profile_id: Some(profile.id.clone()),
+                history_entry_id: Some(entry.id.clone()),
+                created_at: Some(entry.created_at),
+            };
+            post_text(&url, &payload).await.map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(RenderedEntry {
+        text,
+        profile_id: profile.id,
+        path,
+    })
+}
+
+/// Purge expired history entries and recordings now
+#[tauri::command]
+pub async fn purge_expired_history() -> Result<PurgeReport, String> {
+    apply_retention_policy().await
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+use tokio::sync::Mutex;
+
+use crate::commands::settings::read_settings;
+use crate::services::InputIntegration;
+
+/// Global state for the input integration service
+pub type InputIntegrationState = Arc<Mutex<Option<InputIntegration>>>;
+
+/// Apply the `input_integration` settings (grab or release the media keys)
+#[tauri::command]
+pub async fn init_input_integration(
+    app_handle: AppHandle,
+    state: State<'_, InputIntegrationState>,
+) -> Result<String, String> {
+    start_input_integration_from_settings(&app_handle, &state).await
+}
+
+/// Start (or update) the input integration according to the settings
+pub async fn start_input_integration_from_settings(
+    app_handle: &AppHandle,
+    state: &InputIntegrationState,
+) -> Result<String, String> {
+    let settings = read_settings()
+        .await
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+
+    let mut state_guard = state.lock().await;
+    let service = state_guard.get_or_insert_with(|| InputIntegration::new(app_handle.clone()));
+
+    if settings.input_integration.media_key_toggle {
+        service
+            .enable_media_key_toggle()
+            .await
+            .map_err(|e| format!("Failed to grab the media keys: {}", e))?;
+        Ok("Media keys toggle recording while the window is hidden".to_string())
+    } else {
+        service
+            .disable_media_key_toggle()
+            .await
+            .map_err(|e| format!("Failed to release the media keys: {}", e))?;
+        Ok("Media key toggle disabled in settings".to_string())
+    }
+}
@@ -0,0 +1,309 @@
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::audio::interview::split_stereo_wav;
+use crate::audio::{AudioCapture, DualSourceCapture};
+use crate::commands::settings::read_settings;
+use crate::commands::state_machine::process_event;
+use crate::commands::whisper::encode_and_transcribe;
+use crate::commands::{AudioCaptureState, ClipboardServiceState, WhisperClientState};
+use crate::services::{
+    format_interview_transcript, interleave_turns, metrics, DryRunWhisperClient,
+    SegmentedWhisperClient, WhisperClient,
+};
+use crate::state::{AppEvent, AppStateMachineState, StopReason};
+
+/// Global state for the running interview recording
+pub type InterviewCaptureState = Arc<Mutex<Option<Arc<DualSourceCapture>>>>;
+
+/// Start an interview recording: microphone on channel 1, loopback on channel 2
+#[tauri::command]
+pub async fn start_interview_recording(
+    audio_state: State<'_, AudioCaptureState>,
+    interview_state: State<'_, InterviewCaptureState>,
+    state_machine_state: State<'_, AppStateMachineState>,
+) -> Result<String, String> {
+    eprintln!("🎤 [INTERVIEW] start_interview_recording called");
+
+    let mut interview_guard = interview_state.lock().await;
+    if interview_guard.is_some() {
+        return Err("Interview already recording".to_string());
+    }
+
+    // Same microphone as dictation (profile pin or settings), if audio is initialized
+    let pinned_mic = match audio_state.lock().await.as_ref() {
+        Some(capture) if capture.is_recording() => {
+            return Err("A dictation is already being recorded".to_string());
+        }
+        Some(capture) => capture.input_device(),
+        None => None,
+    };
+    let settings = read_settings()
+        .await
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+    let mic_device = pinned_mic.or(settings.audio.input_device);
+
+    let capture = Arc::new(DualSourceCapture::new(
+        mic_device,
+        settings.interview.loopback_device,
+    ));
+    let starting = Arc::clone(&capture);
+    tokio::task::spawn_blocking(move || starting.start())
+        .await
+        .map_err(|e| format!("Interview capture task failed: {}", e))?
+        .map_err(|e| {
+            eprintln!("❌ [INTERVIEW] Failed to start capture: {}", e);
+            format!("Failed to start interview recording: {}", e)
+        })?;
+
+    // Registered before the transition so that entering Recording leaves the
+    // dictation capture alone
+    *interview_guard = Some(Arc::clone(&capture));
+    drop(interview_guard);
+
+    if let Err(e) = process_event(AppEvent::StartRecording, &state_machine_state).await {
+        interview_state.lock().await.take();
+        let _ = capture.stop();
+        return Err(e);
+    }
+
+    eprintln!("✅ [INTERVIEW] Interview recording started");
+    Ok("Interview recording started".to_string())
+}
+
+/// Interview workflow: Stop recording → Transcribe each channel → Interleave turns → Clipboard
+///
+/// Interview transcripts skip GPT formatting: the "Me:" / "Them:" turns are the result.
+#[tauri::command]
+pub async fn stop_interview_and_process(
+    interview_state: State<'_, InterviewCaptureState>,
+    whisper_state: State<'_, WhisperClientState>,
+    clipboard_state: State<'_, ClipboardServiceState>,
+    state_machine_state: State<'_, AppStateMachineState>,
+) -> Result<String, String> {
+    eprintln!("🔄 [INTERVIEW] Starting interview workflow...");
+    let workflow_started = std::time::Instant::now();
+
+    // 1. Stop recording and write the stereo WAV
+    let capture = interview_state
+        .lock()
+        .await
+        .take()
+        .ok_or("Interview is not recording")?;
+
+    if let Err(e) = process_event(
+        AppEvent::StopRecording {
+            reason: StopReason::Manual,
+        },
+        &state_machine_state,
+    )
+    .await
+    {
+        eprintln!(
+            "⚠️  Warning: Failed to transition to processing state: {}",
+            e
+        );
+    }
+
+    let stereo_path = match tokio::task::spawn_blocking(move || capture.stop()).await {
+        Ok(Ok(path)) => path,
+        Ok(Err(e)) => {
+            return fail_transcription(
+                format!("Failed to stop interview recording: {}", e),
+                &state_machine_state,
+            )
+            .await
+        }
+        Err(e) => {
+            return fail_transcription(
+                format!("Interview capture task failed: {}", e),
+                &state_machine_state,
+            )
+            .await
+        }
+    };
+    eprintln!(
+        "✅ [INTERVIEW] Step 1 complete: stereo WAV saved to {:?}",
+        stereo_path
+    );
+
+    let (clipboard_auto_copy, dry_run) = match read_settings().await {
+        Ok(settings) => (settings.clipboard.auto_copy, settings.workflow.dry_run),
+        Err(e) => {
+            eprintln!(
+                "⚠️  Warning: Failed to load settings, using default stage switches: {}",
+                e
+            );
+            (true, false)
+        }
+    };
+
+    // 2. Split the channels and transcribe them concurrently
+    let split_path = stereo_path.clone();
+    let (mic_wav, loopback_wav) =
+        match tokio::task::spawn_blocking(move || split_stereo_wav(&split_path)).await {
+            Ok(Ok(files)) => files,
+            Ok(Err(e)) => {
+                return fail_transcription(
+                    format!("Failed to split interview channels: {}", e),
+                    &state_machine_state,
+                )
+                .await
+            }
+            Err(e) => {
+                return fail_transcription(
+                    format!("Interview split task failed: {}", e),
+                    &state_machine_state,
+                )
+                .await
+            }
+        };
+
+    let client: Arc<dyn WhisperClient + Send + Sync> = if dry_run {
+        Arc::new(DryRunWhisperClient::new(500))
+    } else {
+        match whisper_state.lock().await.as_ref() {
+            Some(client) => Arc::clone(client),
+            None => {
+                return fail_transcription(
+                    "Whisper client not initialized. Please check your API key in settings."
+                        .to_string(),
+                    &state_machine_state,
+                )
+                .await
+            }
+        }
+    };
+    let segmented = SegmentedWhisperClient::new(client.as_ref());
+
+    eprintln!("🎙️  [INTERVIEW] Step 2: Transcribing both channels...");
+    let transcription_started = std::time::Instant::now();
+    let (me, them) = tokio::join!(
+        encode_and_transcribe(mic_wav.path().to_path_buf(), None, &segmented),
+        encode_and_transcribe(loopback_wav.path().to_path_buf(), None, &segmented),
+    );
+    metrics::observe_stage_duration("transcription", transcription_started.elapsed());
+    let (me, them) = match (me, them) {
+        (Ok(me), Ok(them)) => (me, them),
+        (Err(e), _) | (_, Err(e)) => {
+            metrics::record_stage_failure("transcription");
+            return fail_transcription(
+                format!(
+                    "Transcription failed: {} (recording kept at {})",
+                    e,
+                    stereo_path.to_string_lossy()
+                ),
+                &state_machine_state,
+            )
+            .await;
+        }
+    };
+
+    // 3. Interleave both channels into "Me:" / "Them:" turns
+    let turns = interleave_turns(&me, &them);
+    let transcript = format_interview_transcript(&turns);
+    eprintln!(
+        "✅ [INTERVIEW] Step 3 complete: {} turns, {} characters",
+        turns.len(),
+        transcript.len()
+    );
+
+    if let Err(e) = process_event(
+        AppEvent::SkipFormattingToClipboard {
+            transcript: transcript.clone(),
+        },
+        &state_machine_state,
+    )
+    .await
+    {
+        eprintln!("⚠️  Warning: Failed to emit skip formatting event: {}", e);
+    }
+
+    if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
+        let state_machine_guard = state_machine.lock().await;
+        if let Err(e) = state_machine_guard.emit_event(
+            "processing-data-updated",
+            serde_json::json!({
+                "original_transcript": transcript.clone(),
+                "final_text": transcript.clone(),
+                "profile_id": null,
+                "dry_run": dry_run,
+                "interview_turns": turns,
+            }),
+        ) {
+            eprintln!("⚠️  Warning: Failed to emit processing-data-updated: {}", e);
+        }
+    }
+
+    // 4. Copy to clipboard (unless auto-copy is disabled globally)
+    if clipboard_auto_copy && !dry_run {
+        let copy_result = match clipboard_state.lock().await.as_ref() {
+            Some(clipboard) => clipboard
+                .copy(&transcript)
+                .await
+                .map_err(|e| format!("Failed to copy to clipboard: {}", e)),
+            None => Err("Clipboard service not initialized".to_string()),
+        };
+        if let Err(error_msg) = copy_result {
+            eprintln!("❌ Error: {}", error_msg);
+            metrics::record_stage_failure("clipboard");
+            if let Err(e) = process_event(
+                AppEvent::ClipboardError {
+                    error: error_msg.clone(),
+                },
+                &state_machine_state,
+            )
+            .await
+            {
+                eprintln!("⚠️  Warning: Failed to emit clipboard error: {}", e);
+            }
+            return Err(error_msg);
+        }
+    }
+
+    // 5. Clean up and complete
+    if let Err(e) = tokio::fs::remove_file(&stereo_path).await {
+        eprintln!("⚠️  Warning: Failed to clean up interview WAV file: {}", e);
+    }
+
+    let completion_event = if clipboard_auto_copy {
+        AppEvent::ClipboardCopyComplete
+    } else {
+        AppEvent::SkipClipboardCopy
+    };
+    if let Err(e) = process_event(completion_event, &state_machine_state).await {
+        eprintln!(
+            "⚠️  Warning: Failed to transition to processing complete: {}",
+            e
+        );
+    }
+
+    metrics::observe_stage_duration("total", workflow_started.elapsed());
+    metrics::MetricsRegistry::global().inc_counter(metrics::RECORDINGS_TOTAL, &[]);
+    eprintln!("🎉 [INTERVIEW] Workflow complete");
+    Ok(if clipboard_auto_copy && !dry_run {
+        "Interview transcript copied to clipboard".to_string()
+    } else {
+        "Interview transcript ready for review".to_string()
+    })
+}
+
+/// Report a failed interview transcription through the state machine
+async fn fail_transcription(
+    error_msg: String,
+    state_machine_state: &State<'_, AppStateMachineState>,
+) -> Result<String, String> {
+    eprintln!("❌ [INTERVIEW] Error: {}", error_msg);
+    if let Err(e) = process_event(
+        AppEvent::TranscriptionError {
+            error: error_msg.clone(),
+        },
+        state_machine_state,
+    )
+    .await
+    {
+        eprintln!("⚠️  Warning: Failed to emit transcription error: {}", e);
+    }
+    Err(error_msg)
+}
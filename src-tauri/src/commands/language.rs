@@ -0,0 +1,68 @@
+//! Commands for forcing the spoken language of the next recording
+
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::Mutex;
+
+use crate::services::normalize_language_code;
+
+/// Language (ISO-639-1 code) forced for the next transcription, None to let Whisper detect it
+pub type RecordingLanguageState = Arc<Mutex<Option<String>>>;
+
+/// Payload of the `recording-started` event
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingStarted {
+    /// Path of the WAV file being recorded
+    pub path: String,
+    /// Language forced for this recording, if any
+    pub language: Option<String>,
+}
+
+/// Force the spoken language of the next recording only
+///
+/// `language` is an ISO-639-1 code such as "fr"; `None` (or an empty string)
+/// clears it. The language is consumed by the next transcription, so settings
+/// are left untouched. Returns the language now in effect.
+#[tauri::command]
+pub async fn set_next_recording_language(
+    language: Option<String>,
+    language_state: State<'_, RecordingLanguageState>,
+) -> Result<Option<String>, String> {
+    let language = match language.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(code) => Some(
+            normalize_language_code(code)
+                .ok_or_else(|| format!("Invalid language code '{}'", code))?,
+        ),
+    };
+
+    eprintln!(
+        "🌐 [LANGUAGE] Next recording language: {}",
+        language.as_deref().unwrap_or("auto-detect")
+    );
+    *language_state.lock().await = language.clone();
+    Ok(language)
+}
+
+/// Take the language forced for the recording being transcribed
+pub async fn take_recording_language(language_state: &RecordingLanguageState) -> Option<String> {
+    language_state.lock().await.take()
+}
+
+/// Emit `recording-started`, with the language forced for the recording
+pub async fn emit_recording_started(app_handle: &AppHandle, path: &Path) {
+    let language = match app_handle.try_state::<RecordingLanguageState>() {
+        Some(state) => state.lock().await.clone(),
+        None => None,
+    };
+    let _ = app_handle.emit(
+        "recording-started",
+        RecordingStarted {
+            path: path.to_string_lossy().to_string(),
+            language,
+        },
+    );
+}
@@ -0,0 +1,202 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::Mutex;
+
+use crate::audio::capture::CAPTURE_SAMPLE_RATE;
+use crate::audio::AudioCapture;
+use crate::commands::settings::read_settings;
+use crate::commands::voice_commands::run_live_command;
+use crate::commands::whisper::encode_and_transcribe;
+use crate::commands::{AudioCaptureState, WhisperClientState};
+use crate::services::event_targets::emit_to_main;
+use crate::services::voice_commands::trailing_command;
+use crate::services::{DryRunWhisperClient, LiveCaptionConfig, LiveCaptionWriter};
+
+/// Global state for the live caption task
+pub type LiveCaptionState = Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>;
+
+/// Minimum amount of audio (seconds) worth transcribing for a caption
+const MIN_CAPTION_AUDIO_SECONDS: f32 = 1.0;
+
+/// Start live caption output if it is enabled in settings
+#[tauri::command]
+pub async fn init_live_captions(
+    app_handle: AppHandle,
+    state: State<'_, LiveCaptionState>,
+) -> Result<String, String> {
+    start_live_captions_from_settings(&app_handle, &state).await
+}
+
+/// Stop live caption output
+#[tauri::command]
+pub async fn stop_live_captions(state: State<'_, LiveCaptionState>) -> Result<String, String> {
+    match state.lock().await.take() {
+        Some(task) => {
+            task.abort();
+            Ok("Live captions stopped".to_string())
+        }
+        None => Ok("Live captions are not running".to_string()),
+    }
+}
+
+/// Start (or restart) the caption task according to the `live_captions` settings
+pub async fn start_live_captions_from_settings(
+    app_handle: &AppHandle,
+    state: &LiveCaptionState,
+) -> Result<String, String> {
+    let settings = read_settings()
+        .await
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+
+    let mut state_guard = state.lock().await;
+    if let Some(task) = state_guard.take() {
+        task.abort();
+    }
+
+    if !settings.live_captions.enabled {
+        return Ok("Live captions disabled in settings".to_string());
+    }
+
+    let mut config = LiveCaptionConfig {
+        cadence: Duration::from_secs(settings.live_captions.cadence_seconds.max(1) as u64),
+        window_seconds: settings.live_captions.window_seconds,
+        ..LiveCaptionConfig::default()
+    };
+    if let Some(output_path) = settings.live_captions.output_path {
+        config.output_path = PathBuf::from(output_path);
+    }
+
+    let writer = LiveCaptionWriter::new(config);
+    let output_path = writer.config().output_path.clone();
+    let dry_run = settings.workflow.dry_run;
+    *state_guard = Some(tokio::spawn(run_live_captions(
+        app_handle.clone(),
+        writer,
+        dry_run,
+        settings.voice_commands.enabled,
+    )));
+
+    Ok(format!(
+        "Live captions writing to {}",
+        output_path.to_string_lossy()
+    ))
+}
+
+/// Caption loop: while recording, transcribe the latest audio window at each tick
+///
+/// With voice commands enabled, a caption ending in a command ("stop recording",
+/// "scratch that") acts on the recording, once per recording.
+async fn run_live_captions(
+    app_handle: AppHandle,
+    writer: LiveCaptionWriter,
+    dry_run: bool,
+    voice_commands: bool,
+) {
+    let mut interval = tokio::time::interval(writer.config().cadence);
+    let mut was_recording = false;
+    let mut command_sent = false;
+
+    loop {
+        interval.tick().await;
+
+        let samples = match app_handle.try_state::<AudioCaptureState>() {
+            Some(audio_state) => match audio_state.lock().await.as_ref() {
+                Some(capture) if capture.is_recording() => {
+                    Some(capture.recent_samples(writer.config().window_seconds))
+                }
+                _ => None,
+            },
+            None => None,
+        };
+
+        let samples = match samples {
+            Some(samples) => {
+                was_recording = true;
+                samples
+            }
+            None => {
+                // Recording ended: blank the caption so it does not linger on stream
+                if was_recording {
+                    was_recording = false;
+                    command_sent = false;
+                    if let Err(e) = writer.clear() {
+                        eprintln!("⚠️  [CAPTIONS] Failed to clear caption file: {}", e);
+                    }
+                }
+                continue;
+            }
+        };
+
+        if (samples.len() as f32) < MIN_CAPTION_AUDIO_SECONDS * CAPTURE_SAMPLE_RATE as f32 {
+            continue;
+        }
+
+        match transcribe_window(&app_handle, &samples, dry_run).await {
+            Ok(text) => {
+                if let Err(e) = writer.write_caption(&text) {
+                    eprintln!("⚠️  [CAPTIONS] Failed to write caption file: {}", e);
+                }
+                let _ = emit_to_main(&app_handle, "live-caption", &text);
+
+                if voice_commands && !command_sent {
+                    if let Some(found) = trailing_command(&text) {
+                        command_sent = true;
+                        run_live_command(&app_handle, found.command).await;
+                    }
+                }
+            }
+            Err(e) => eprintln!("⚠️  [CAPTIONS] Partial transcription failed: {}", e),
+        }
+    }
+}
+
+/// Write the audio window to a temporary WAV file and transcribe it
+async fn transcribe_window(
+    app_handle: &AppHandle,
+    samples: &[i16],
+    dry_run: bool,
+) -> Result<String, String> {
+    let wav_file = tempfile::Builder::new()
+        .suffix(".wav")
+        .tempfile()
+        .map_err(|e| format!("Failed to create caption audio file: {}", e))?;
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: CAPTURE_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    {
+        let mut writer = hound::WavWriter::create(wav_file.path(), spec)
+            .map_err(|e| format!("Failed to write caption audio: {}", e))?;
+        for &sample in samples {
+            writer
+                .write_sample(sample)
+                .map_err(|e| format!("Failed to write caption audio: {}", e))?;
+        }
+        writer
+            .finalize()
+            .map_err(|e| format!("Failed to write caption audio: {}", e))?;
+    }
+
+    let wav_path = wav_file.path().to_path_buf();
+    let transcript = if dry_run {
+        encode_and_transcribe(wav_path, None, &DryRunWhisperClient::new(0)).await?
+    } else {
+        let whisper_state = app_handle
+            .try_state::<WhisperClientState>()
+            .ok_or("Whisper client state not available")?;
+        // Clone the client so the main workflow is not blocked while we transcribe
+        let client = whisper_state
+            .lock()
+            .await
+            .as_ref()
+            .cloned()
+            .ok_or("Whisper client not initialized")?;
+        encode_and_transcribe(wav_path, None, client.as_ref()).await?
+    };
+
+    Ok(transcript.text)
+}
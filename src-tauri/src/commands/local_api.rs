@@ -0,0 +1,251 @@
+use async_trait::async_trait;
+use std::net::{IpAddr, UdpSocket};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, EventId, Listener, Manager, State};
+use tokio::sync::{broadcast, Mutex};
+
+use crate::commands::settings::read_settings;
+use crate::services::{LocalApiConfig, LocalApiServer, PairingStore, RemoteAccess, RemoteControl};
+use crate::state::{AppState, AppStateMachineState};
+
+/// Global state for the local HTTP API server
+pub type LocalApiState = Arc<Mutex<Option<LocalApiServer>>>;
+
+/// Start the local HTTP API if it is enabled in settings
+#[tauri::command]
+pub async fn init_local_api(
+    app_handle: AppHandle,
+    state: State<'_, LocalApiState>,
+) -> Result<String, String> {
+    start_local_api_from_settings(&app_handle, &state).await
+}
+
+/// Stop the local HTTP API
+#[tauri::command]
+pub async fn stop_local_api(state: State<'_, LocalApiState>) -> Result<String, String> {
+    let mut state_guard = state.lock().await;
+    match state_guard.take() {
+        Some(mut server) => {
+            server.stop();
+            Ok("Local API stopped".to_string())
+        }
+        None => Ok("Local API is not running".to_string()),
+    }
+}
+
+/// Get local HTTP API status
+#[tauri::command]
+pub async fn get_local_api_status(
+    state: State<'_, LocalApiState>,
+) -> Result<serde_json::Value, String> {
+    let state_guard = state.lock().await;
+
+    match state_guard.as_ref() {
+        Some(server) if server.is_running() => {
+            let addr = server.local_addr().map(|addr| addr.to_string());
+            Ok(serde_json::json!({
+                "running": true,
+                "address": addr,
+                "metrics_url": addr.as_ref().map(|addr| format!("http://{}/metrics", addr)),
+                "lan_pairing": server.remote_access().is_some(),
+            }))
+        }
+        _ => Ok(serde_json::json!({ "running": false })),
+    }
+}
+
+/// Pair a new remote device and return the URL to encode in the pairing QR code
+#[tauri::command]
+pub async fn create_remote_pairing(
+    label: Option<String>,
+    state: State<'_, LocalApiState>,
+) -> Result<serde_json::Value, String> {
+    let state_guard = state.lock().await;
+    let server = state_guard
+        .as_ref()
+        .filter(|server| server.is_running())
+        .ok_or("Local API is not running")?;
+    let remote = server
+        .remote_access()
+        .ok_or("LAN pairing is disabled in settings")?;
+    let port = server
+        .local_addr()
+        .map(|addr| addr.port())
+        .ok_or("Local API is not running")?;
+    let lan_ip = lan_ip_address().ok_or("Could not determine the LAN address of this computer")?;
+
+    let pairing = remote
+        .pairings
+        .create(label)
+        .map_err(|e| format!("Failed to create pairing: {}", e))?;
+
+    eprintln!("📱 [REMOTE] New device paired ({:?})", pairing.label);
+    Ok(serde_json::json!({
+        "pairing_url": format!("http://{}:{}/remote?token={}", lan_ip, port, pairing.token),
+        "token": pairing.token,
+        "label": pairing.label,
+    }))
+}
+
+/// Revoke every paired remote device
+#[tauri::command]
+pub async fn revoke_remote_pairings() -> Result<String, String> {
+    let count = PairingStore::default_location()
+        .revoke_all()
+        .map_err(|e| format!("Failed to revoke pairings: {}", e))?;
+    Ok(format!("Revoked {} paired device(s)", count))
+}
+
+/// Start (or restart) the server according to the `local_api` settings
+pub async fn start_local_api_from_settings(
+    app_handle: &AppHandle,
+    state: &LocalApiState,
+) -> Result<String, String> {
+    let settings = read_settings()
+        .await
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+
+    let mut state_guard = state.lock().await;
+    if let Some(mut server) = state_guard.take() {
+        server.stop();
+    }
+
+    if !settings.local_api.enabled {
+        return Ok("Local API disabled in settings".to_string());
+    }
+
+    let mut server = if settings.local_api.lan_pairing {
+        let control = Arc::new(TauriRemoteControl::new(app_handle.clone()));
+        let remote = RemoteAccess {
+            state_events: control.state_events.clone(),
+            control,
+            pairings: PairingStore::default_location(),
+        };
+
+        LocalApiServer::new(LocalApiConfig {
+            bind_address: "0.0.0.0".to_string(),
+            port: settings.local_api.port,
+        })
+        .with_remote_access(remote)
+    } else {
+        LocalApiServer::new(LocalApiConfig {
+            port: settings.local_api.port,
+            ..LocalApiConfig::default()
+        })
+    };
+    let addr = server
+        .start()
+        .await
+        .map_err(|e| format!("Failed to start local API: {}", e))?;
+    *state_guard = Some(server);
+
+    Ok(format!("Local API listening on http://{}", addr))
+}
+
+/// Remote control backed by the running app
+///
+/// Toggling reuses the global shortcut path so remote and keyboard behave the
+/// same. State changes and results are forwarded to the push channel.
+struct TauriRemoteControl {
+    app_handle: AppHandle,
+    last_result: Arc<std::sync::Mutex<Option<String>>>,
+    state_events: broadcast::Sender<String>,
+    listeners: Vec<EventId>,
+}
+
+impl TauriRemoteControl {
+    fn new(app_handle: AppHandle) -> Self {
+        let (state_events, _) = broadcast::channel(32);
+        let last_result = Arc::new(std::sync::Mutex::new(None));
+
+        let sender = state_events.clone();
+        let state_listener = app_handle.listen("app-state-changed", move |event| {
+            let _ = sender.send(event.payload().to_string());
+        });
+
+        let sender = state_events.clone();
+        let result_cache = last_result.clone();
+        let result_listener = app_handle.listen("processing-data-updated", move |event| {
+            if let Ok(data) = serde_json::from_str::<serde_json::Value>(event.payload()) {
+                if let Some(final_text) = data.get("final_text").and_then(|v| v.as_str()) {
+                    if let Ok(mut cache) = result_cache.lock() {
+                        *cache = Some(final_text.to_string());
+                    }
+                    let _ = sender.send(event.payload().to_string());
+                }
+            }
+        });
+
+        Self {
+            app_handle,
+            last_result,
+            state_events,
+            listeners: vec![state_listener, result_listener],
+        }
+    }
+}
+
+impl Drop for TauriRemoteControl {
+    fn drop(&mut self) {
+        for listener in self.listeners.drain(..) {
+            self.app_handle.unlisten(listener);
+        }
+    }
+}
+
+#[async_trait]
+impl RemoteControl for TauriRemoteControl {
+    async fn toggle_recording(&self) -> Result<String, String> {
+        self.app_handle
+            .emit("global_shortcut_toggle_record", ())
+            .map_err(|e| format!("Failed to toggle recording: {}", e))?;
+        Ok("Recording toggled".to_string())
+    }
+
+    async fn status(&self) -> serde_json::Value {
+        let mut status = serde_json::json!({ "state": "Unavailable" });
+
+        if let Some(state_machine_state) = self.app_handle.try_state::<AppStateMachineState>() {
+            if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
+                let state_machine_guard = state_machine.lock().await;
+                let current_state = state_machine_guard.current_state();
+                if let AppState::ProcessingComplete { final_text, .. } = current_state {
+                    if let Ok(mut cache) = self.last_result.lock() {
+                        *cache = Some(final_text.clone());
+                    }
+                }
+                status = serde_json::json!({
+                    "state": state_name(current_state),
+                    "is_recording": state_machine_guard.is_recording(),
+                    "is_processing": state_machine_guard.is_processing(),
+                });
+            }
+        }
+
+        status["last_result"] = self
+            .last_result
+            .lock()
+            .ok()
+            .and_then(|cache| cache.clone())
+            .into();
+        status
+    }
+}
+
+/// Variant name of a state without its payload (e.g. "Recording")
+fn state_name(state: &AppState) -> String {
+    let debug = format!("{:?}", state);
+    debug
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Best-effort LAN address of this machine (no packets are sent)
+fn lan_ip_address() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:80").ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_loopback() && !ip.is_unspecified()).then_some(ip)
+}
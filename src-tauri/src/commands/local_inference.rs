@@ -0,0 +1,53 @@
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::commands::settings::read_settings;
+use crate::services::LocalInferenceCapabilities;
+
+/// Global state for the capabilities probed at startup
+pub type LocalInferenceCapabilitiesState = Arc<Mutex<Option<LocalInferenceCapabilities>>>;
+
+/// Probe local inference devices and store the result (called at startup)
+pub async fn probe_local_inference_capabilities(
+    state: &LocalInferenceCapabilitiesState,
+) -> Result<LocalInferenceCapabilities, String> {
+    let capabilities = tokio::task::spawn_blocking(LocalInferenceCapabilities::probe)
+        .await
+        .map_err(|e| format!("Capability probe task failed: {}", e))?;
+    eprintln!(
+        "🖥️  [LOCAL] {} CPU threads, CUDA: {} ({} GPU(s)), Metal: {}",
+        capabilities.cpu_threads,
+        capabilities.cuda_available,
+        capabilities.gpus.len(),
+        capabilities.metal_available
+    );
+
+    *state.lock().await = Some(capabilities.clone());
+    Ok(capabilities)
+}
+
+/// Report local inference devices and the device the current settings resolve to
+#[tauri::command]
+pub async fn get_local_inference_capabilities(
+    state: State<'_, LocalInferenceCapabilitiesState>,
+) -> Result<serde_json::Value, String> {
+    let probed = state.lock().await.clone();
+    let capabilities = match probed {
+        Some(capabilities) => capabilities,
+        None => probe_local_inference_capabilities(&state).await?,
+    };
+
+    let settings = read_settings()
+        .await
+        .map_err(|e| format!("Failed to load settings: {}", e))?
+        .local_inference;
+    let selection = capabilities.resolve(settings.device, settings.gpu_index);
+
+    Ok(serde_json::json!({
+        "capabilities": capabilities,
+        "requested_device": settings.device,
+        "requested_gpu_index": settings.gpu_index,
+        "selected": selection,
+    }))
+}
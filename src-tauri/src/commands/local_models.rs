@@ -0,0 +1,86 @@
+//! Commands for managing local Whisper models
+
+use tauri::{AppHandle, Manager};
+
+use crate::services::event_targets::emit_to_settings;
+use crate::services::ModelStore;
+
+/// Sub-directory of the app data directory holding the models
+const MODELS_DIR: &str = "models";
+
+/// Model store in the app data directory
+pub fn model_store(app_handle: &AppHandle) -> Result<ModelStore, String> {
+    let data_dir = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))?;
+    Ok(ModelStore::new(data_dir.join(MODELS_DIR)))
+}
+
+/// List known local models, their install state and the disk usage of the store
+#[tauri::command]
+pub async fn list_local_models(app_handle: AppHandle) -> Result<serde_json::Value, String> {
+    let store = model_store(&app_handle)?;
+    let models = store
+        .list()
+        .map_err(|e| format!("Failed to list local models: {}", e))?;
+    let disk_usage_bytes = store
+        .disk_usage()
+        .map_err(|e| format!("Failed to compute model disk usage: {}", e))?;
+
+    Ok(serde_json::json!({
+        "models": models,
+        "models_dir": store.dir(),
+        "disk_usage_bytes": disk_usage_bytes,
+    }))
+}
+
+/// Download a local model, emitting "local-model-download-progress" events
+#[tauri::command]
+pub async fn download_local_model(app_handle: AppHandle, name: String) -> Result<String, String> {
+    eprintln!("⬇️  [MODELS] Downloading local model {}", name);
+    let store = model_store(&app_handle)?;
+
+    // One event per percent (or per MB without a content length)
+    let mut last_step = None;
+    let path = store
+        .download(&name, |progress| {
+            let step = match progress.total_bytes {
+                Some(total) if total > 0 => progress.downloaded_bytes * 100 / total,
+                _ => progress.downloaded_bytes / (1024 * 1024),
+            };
+            if last_step == Some(step) {
+                return;
+            }
+            last_step = Some(step);
+            if let Err(e) = emit_to_settings(
+                &app_handle,
+                "local-model-download-progress",
+                serde_json::json!({
+                    "name": name,
+                    "downloaded_bytes": progress.downloaded_bytes,
+                    "total_bytes": progress.total_bytes,
+                }),
+            ) {
+                eprintln!("⚠️  Warning: Failed to emit download progress: {}", e);
+            }
+        })
+        .await
+        .map_err(|e| {
+            eprintln!("❌ [MODELS] Download of {} failed: {}", name, e);
+            format!("Failed to download model {}: {}", name, e)
+        })?;
+
+    eprintln!("✅ [MODELS] Model {} installed at {:?}", name, path);
+    Ok(format!("Model {} downloaded and verified", name))
+}
+
+/// Delete an installed local model
+#[tauri::command]
+pub async fn delete_local_model(app_handle: AppHandle, name: String) -> Result<String, String> {
+    model_store(&app_handle)?
+        .delete(&name)
+        .map_err(|e| format!("Failed to delete model {}: {}", name, e))?;
+    eprintln!("🗑️  [MODELS] Model {} deleted", name);
+    Ok(format!("Model {} deleted", name))
+}
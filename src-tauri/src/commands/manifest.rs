@@ -0,0 +1,77 @@
+//! Manifest of the registered commands
+//!
+//! The build script lists the commands registered in `generate_handler!` with a
+//! hash of their signature. The frontend compares it with the commands it was
+//! built against: after a partial update it can ask the user to restart or
+//! update instead of failing on a missing or changed command.
+
+use serde::Serialize;
+
+include!(concat!(env!("OUT_DIR"), "/command_manifest.rs"));
+
+/// A registered command (`get_command_manifest` entry)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommandSignature {
+    pub name: &'static str,
+    /// Hash of the command's Rust signature (changes with its arguments)
+    pub signature_hash: &'static str,
+}
+
+/// Registered commands of this build
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommandManifest {
+    /// Application version
+    pub version: &'static str,
+    /// Hash of all the names and signatures, for a quick comparison
+    pub manifest_hash: &'static str,
+    pub commands: Vec<CommandSignature>,
+}
+
+/// Registered command names and signature hashes (generated at compile time)
+#[tauri::command]
+pub fn get_command_manifest() -> CommandManifest {
+    CommandManifest {
+        version: env!("CARGO_PKG_VERSION"),
+        manifest_hash: COMMAND_MANIFEST_HASH,
+        commands: COMMAND_SIGNATURES
+            .iter()
+            .map(|&(name, signature_hash)| CommandSignature {
+                name,
+                signature_hash,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_lists_registered_commands() {
+        let manifest = get_command_manifest();
+        let names: Vec<&str> = manifest
+            .commands
+            .iter()
+            .map(|command| command.name)
+            .collect();
+        assert!(names.contains(&"get_command_manifest"));
+        assert!(names.contains(&"stop_recording_and_process_to_clipboard"));
+
+        let mut unique = names.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), names.len());
+
+        // Every registered command had its signature found
+        assert!(manifest
+            .commands
+            .iter()
+            .all(|command| command.signature_hash.len() == 16));
+        let empty_signature = format!("{:016x}", 0xcbf2_9ce4_8422_2325u64);
+        assert!(manifest
+            .commands
+            .iter()
+            .all(|command| command.signature_hash != empty_signature));
+    }
+}
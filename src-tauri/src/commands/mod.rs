@@ -1,44 +1,119 @@
+pub mod app_lock;
 pub mod audio;
+pub mod bootstrap;
+pub mod calibration;
 pub mod clipboard;
+pub mod context_memory;
+pub mod continuous;
 pub mod encoder;
 pub mod error_recovery;
+pub mod failed_workflows;
+pub mod feature_flags;
+pub mod focus_session;
+pub mod goals;
 pub mod gpt;
+pub mod history;
+pub mod input_integration;
+pub mod interview;
+pub mod language;
+pub mod live_captions;
+pub mod local_api;
+pub mod local_inference;
+pub mod local_models;
+pub mod manifest;
+pub mod paths;
+pub mod playback;
+pub mod profile_comparison;
+pub mod profile_sync;
 pub mod profiles;
+pub mod providers;
+pub mod readiness;
+pub mod retranscription;
 pub mod settings;
 pub mod shortcut;
 pub mod state_machine;
 pub mod system_tray;
+pub mod transcription_queue;
+pub mod tts;
+pub mod usage;
+pub mod vocabulary;
+pub mod voice_commands;
+pub mod weekly_report;
 pub mod whisper;
+pub mod window_events;
+pub mod workflow_context;
 
+pub use app_lock::{disable_app_lock, enable_app_lock, get_app_lock_status, lock_app, unlock_app};
 pub use audio::{
-    init_audio_capture, is_recording, start_capture, stop_capture, subscribe_rms, AudioCaptureState,
+    confirm_pending_recording, discard_pending_recording, get_input_gain, get_microphone_status,
+    init_audio_capture, is_recording, list_input_devices, mark_bookmark, pause_capture,
+    reinit_audio_capture, resume_capture, set_auto_gain, set_input_gain, shutdown_audio_capture,
+    start_capture, stop_capture, subscribe_rms, subscribe_waveform, unsubscribe_rms,
+    unsubscribe_waveform, AudioCaptureState,
 };
+pub use bootstrap::bootstrap_app;
+pub use calibration::{finish_calibration, start_calibration};
 pub use clipboard::{
     copy_to_clipboard, get_clipboard_info, init_clipboard_service, is_clipboard_initialized,
-    ClipboardServiceState,
+    reinit_clipboard_service, shutdown_clipboard_service, ClipboardServiceState,
 };
-pub use encoder::{encode_wav_to_ogg, get_encoder_info};
+pub use context_memory::{clear_context_memory, ContextMemoryState};
+pub use continuous::{
+    get_continuous_session, pause_continuous_session, resume_continuous_session,
+    start_continuous_session, stop_continuous_session, ContinuousSessionState,
+};
+pub use encoder::{encode_wav, encode_wav_to_ogg, get_encoder_info};
 pub use error_recovery::{
     acknowledge_error_via_state_machine, disable_auto_recovery_via_state_machine,
     enable_auto_recovery_via_state_machine, get_error_state, reset_app_state_via_state_machine,
     retry_backend_connection,
 };
+pub use failed_workflows::{discard_failed_workflow, list_failed_workflows, retry_failed_workflow};
+pub use feature_flags::{list_feature_flags, set_feature_flag};
+pub use focus_session::{
+    end_focus_session, get_focus_session, start_focus_session, FocusSessionState,
+};
+pub use goals::get_goal_progress;
 pub use gpt::{
-    format_text_with_gpt, get_gpt_info, init_gpt_client, is_gpt_initialized, GptClientState,
+    format_text_with_gpt, get_gpt_info, init_gpt_client, is_gpt_initialized, reinit_gpt_client,
+    shutdown_gpt_client, GptClientState,
+};
+pub use history::{
+    clear_history, list_history, purge_expired_history, render_history_entry,
+    update_history_entry_text,
 };
+pub use input_integration::{init_input_integration, InputIntegrationState};
+pub use interview::{start_interview_recording, stop_interview_and_process, InterviewCaptureState};
+pub use language::{set_next_recording_language, RecordingLanguageState};
+pub use live_captions::{init_live_captions, stop_live_captions, LiveCaptionState};
+pub use local_api::{
+    create_remote_pairing, get_local_api_status, init_local_api, revoke_remote_pairings,
+    stop_local_api, LocalApiState,
+};
+pub use local_inference::{get_local_inference_capabilities, LocalInferenceCapabilitiesState};
+pub use local_models::{delete_local_model, download_local_model, list_local_models};
+pub use manifest::get_command_manifest;
+pub use paths::get_data_locations;
+pub use playback::{play_last_recording, stop_playback, PlaybackState};
+pub use profile_comparison::compare_profiles;
+pub use profile_sync::sync_profiles_now;
 pub use profiles::{
-    apply_profile_to_text, get_active_profile, load_profiles, select_profile, ProfileAppState,
+    apply_profile_to_text, get_active_profile, get_profiles_cached, lint_profile, load_profiles,
+    select_profile, ProfileAppState,
 };
+pub use providers::get_provider_capabilities;
+pub use readiness::validate_ready_to_record;
+pub use retranscription::retranscribe_low_confidence;
 pub use settings::{
-    load_settings, save_profiles, save_settings, v1_save_profiles, v1_save_settings,
-    validate_shortcut_conflict,
+    export_settings_sanitized, load_settings, save_profiles, save_settings, v1_save_profiles,
+    v1_save_settings, validate_shortcut_conflict,
 };
 pub use shortcut::{
     auto_init_shortcut_mgr, check_shortcut_available, get_shortcut_status, init_shortcut_mgr,
     register_all_profile_shortcuts, register_global_shortcut, register_profile_shortcut,
-    toggle_record, toggle_record_with_tray, unregister_all_profile_shortcuts,
-    unregister_global_shortcut, unregister_profile_shortcut, update_global_shortcut,
-    ShortcutMgrState,
+    reinit_shortcut_mgr, shutdown_shortcut_mgr, toggle_record, toggle_record_with_tray,
+    unregister_all_profile_shortcuts, unregister_global_shortcut, unregister_profile_shortcut,
+    update_global_shortcut, ShortcutMgrState,
 };
 pub use state_machine::{
     get_current_state, has_modal_window_open, init_state_machine, is_app_processing,
@@ -51,17 +126,49 @@ pub use system_tray::{
     show_window_and_start_recording, toggle_main_window, update_tray_global_shortcut,
     update_tray_status, SystemTrayState,
 };
+pub use transcription_queue::{
+    cancel_transcription_job, clear_finished_transcription_jobs, enqueue_transcriptions,
+    get_transcription_jobs, TranscriptionQueueState,
+};
+pub use tts::{speak_result, stop_speaking, SpeechState};
+pub use usage::get_usage_stats;
+pub use vocabulary::{get_vocabulary, save_vocabulary};
+pub use weekly_report::generate_weekly_report;
 pub use whisper::{
-    get_whisper_info, init_whisper_client, is_whisper_initialized, test_api_key, transcribe_audio,
-    transcribe_recorded_audio, WhisperClientState,
+    get_whisper_info, init_whisper_client, is_whisper_initialized, reinit_whisper_client,
+    shutdown_whisper_client, test_api_key, transcribe_audio, transcribe_recorded_audio,
+    WhisperClientState,
 };
+pub use window_events::{subscribe_window_events, unsubscribe_window_events};
+pub use workflow_context::{set_workflow_context, WorkflowContextState};
 
 // New orchestration command for complete workflow
 use crate::audio::AudioCapture;
-use crate::services::ProfileEngine;
+use crate::services::bookmarks::{apply_bookmarks, BookmarkMode};
+use crate::services::chapters::{self, ChapterMode};
+use crate::services::corrections::{apply_corrections, boost_prompt};
+use crate::services::event_targets::EventAudience;
+use crate::services::mixed_language::{self, MixedLanguageMode};
+use crate::services::profile_engine::ProfileBehavior;
+use crate::services::profile_suggestion::SuggestionMode;
+use crate::services::punctuation::{needs_restoration, restore_segments, restore_sentence};
+use crate::services::retranscription::transcript_confidence;
+use crate::services::size_guard::UploadCheck;
+use crate::services::transcript_format::{paragraphize, timestamped_transcript};
+use crate::services::voice_commands::{take_voice_commands, VoiceCommand};
+use crate::services::{
+    metrics, ChunkProgress, FilenameContext, FilenameTemplate, ProfileEngine,
+    SegmentedWhisperClient, WhisperMode,
+};
+use std::collections::HashMap;
 use tauri::State;
 
+use crate::workflow_log;
+
 /// Complete workflow: Stop recording → Transcribe → GPT-4 Format → Copy to clipboard
+///
+/// `stop_reason` is reported with the stop transition (manual when omitted).
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
 pub async fn stop_recording_and_process_to_clipboard(
     audio_state: State<'_, AudioCaptureState>,
@@ -70,35 +177,48 @@ pub async fn stop_recording_and_process_to_clipboard(
     profile_state: State<'_, ProfileAppState>,
     gpt_state: State<'_, GptClientState>,
     state_machine_state: State<'_, crate::state::AppStateMachineState>,
+    language_state: State<'_, RecordingLanguageState>,
+    context_state: State<'_, ContextMemoryState>,
+    reference_state: State<'_, WorkflowContextState>,
+    focus_state: State<'_, FocusSessionState>,
+    app_handle: tauri::AppHandle,
+    stop_reason: Option<crate::state::StopReason>,
 ) -> Result<String, String> {
-    eprintln!("🔄 [PROCESSING] Starting complete workflow...");
-    eprintln!(
+    if continuous::is_session_running(&app_handle).await {
+        return Err("A continuous session is recording: stop it first".to_string());
+    }
+    workflow_log!("🔄 [PROCESSING] Starting complete workflow...");
+    workflow_log!(
         "📊 [PROCESSING] Function called from: {}",
         std::backtrace::Backtrace::force_capture()
     );
 
+    let workflow_started = std::time::Instant::now();
+
     // 1. Stop recording and get WAV file path
-    eprintln!("📱 [PROCESSING] Step 1: Stopping recording...");
+    workflow_log!("📱 [PROCESSING] Step 1: Stopping recording...");
 
     // Emit state transition to ProcessingTranscription
     if let Err(e) = crate::commands::state_machine::process_event(
-        crate::state::AppEvent::StopRecording,
+        crate::state::AppEvent::StopRecording {
+            reason: stop_reason.unwrap_or_default(),
+        },
         &state_machine_state,
     )
     .await
     {
-        eprintln!(
+        workflow_log!(
             "⚠️  Warning: Failed to transition to processing state: {}",
             e
         );
     }
 
-    let wav_path = {
+    let (wav_path, bookmarks) = {
         let audio_guard = audio_state.lock().await;
         if let Some(ref capture) = *audio_guard {
             if !capture.is_recording() {
                 let error_msg = "Not currently recording";
-                eprintln!("❌ [PROCESSING] Error: {}", error_msg);
+                workflow_log!("❌ [PROCESSING] Error: {}", error_msg);
 
                 // Emit error state
                 if let Err(e) = crate::commands::state_machine::process_event(
@@ -109,24 +229,25 @@ pub async fn stop_recording_and_process_to_clipboard(
                 )
                 .await
                 {
-                    eprintln!("⚠️  Warning: Failed to emit error state: {}", e);
+                    workflow_log!("⚠️  Warning: Failed to emit error state: {}", e);
                 }
 
                 return Err(error_msg.to_string());
             }
-            eprintln!("🛑 [PROCESSING] Stopping audio capture...");
-            capture.stop_capture().await.map_err(|e| {
+            workflow_log!("🛑 [PROCESSING] Stopping audio capture...");
+            let wav_path = capture.stop_capture().await.map_err(|e| {
                 let error_msg = format!("Failed to stop recording: {}", e);
-                eprintln!("❌ [PROCESSING] Error: {}", error_msg);
+                workflow_log!("❌ [PROCESSING] Error: {}", error_msg);
                 error_msg
-            })?
+            })?;
+            (wav_path, capture.take_bookmarks())
         } else {
             let error_msg = "Audio capture not initialized";
-            eprintln!("❌ [PROCESSING] Error: {}", error_msg);
+            workflow_log!("❌ [PROCESSING] Error: {}", error_msg);
             return Err(error_msg.to_string());
         }
     };
-    eprintln!(
+    workflow_log!(
         "✅ [PROCESSING] Step 1 complete: WAV file saved to {:?}",
         wav_path
     );
@@ -134,17 +255,17 @@ pub async fn stop_recording_and_process_to_clipboard(
     // Debug: Additional WAV file information
     match tokio::fs::metadata(&wav_path).await {
         Ok(metadata) => {
-            eprintln!("🔍 DEBUG: WAV file details:");
-            eprintln!(
+            workflow_log!("🔍 DEBUG: WAV file details:");
+            workflow_log!(
                 "   📁 Full path: {:?}",
                 wav_path.canonicalize().unwrap_or_else(|_| wav_path.clone())
             );
-            eprintln!(
+            workflow_log!(
                 "   📊 File size: {} bytes ({:.2} KB)",
                 metadata.len(),
                 metadata.len() as f64 / 1024.0
             );
-            eprintln!(
+            workflow_log!(
                 "   ⏰ Modified: {:?}",
                 metadata
                     .modified()
@@ -152,76 +273,360 @@ pub async fn stop_recording_and_process_to_clipboard(
             );
         }
         Err(e) => {
-            eprintln!("⚠️  Warning: Could not read WAV file metadata: {}", e);
+            workflow_log!("⚠️  Warning: Could not read WAV file metadata: {}", e);
         }
     }
 
+    // Hold the recording so it can be listened to before it is transcribed
+    // (not in tray-only mode: there is no window to confirm from)
+    let confirm_before_transcription = settings::read_settings()
+        .await
+        .map(|settings| settings.workflow.confirm_before_transcription && !settings.ui.tray_only)
+        .unwrap_or(false);
+    if confirm_before_transcription {
+        return audio::hold_recording_for_confirmation(wav_path, bookmarks, &state_machine_state)
+            .await;
+    }
+
+    process_recording_to_clipboard(
+        wav_path,
+        bookmarks,
+        whisper_state,
+        clipboard_state,
+        profile_state,
+        gpt_state,
+        state_machine_state,
+        language_state,
+        context_state,
+        reference_state,
+        focus_state,
+        app_handle,
+        workflow_started,
+    )
+    .await
+}
+
+/// Transcribe → GPT-4 Format → Copy to clipboard for a stopped recording
+///
+/// Rest of [`stop_recording_and_process_to_clipboard`], also run when a held
+/// recording is confirmed.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn process_recording_to_clipboard(
+    wav_path: std::path::PathBuf,
+    bookmarks: Vec<f64>,
+    whisper_state: State<'_, WhisperClientState>,
+    clipboard_state: State<'_, ClipboardServiceState>,
+    profile_state: State<'_, ProfileAppState>,
+    gpt_state: State<'_, GptClientState>,
+    state_machine_state: State<'_, crate::state::AppStateMachineState>,
+    language_state: State<'_, RecordingLanguageState>,
+    context_state: State<'_, ContextMemoryState>,
+    reference_state: State<'_, WorkflowContextState>,
+    focus_state: State<'_, FocusSessionState>,
+    app_handle: tauri::AppHandle,
+    workflow_started: std::time::Instant,
+) -> Result<String, String> {
     // 2. Get active profile ID first
-    eprintln!("👤 Step 2: Getting active profile...");
-    let active_profile_id = {
+    workflow_log!("👤 Step 2: Getting active profile...");
+    let mut active_profile_id = {
         let profile_guard = profile_state.lock().unwrap();
         profile_guard.active_profile_id.clone()
     }; // Guard is dropped here
-    eprintln!(
+    workflow_log!(
         "✅ Step 2 complete: Active profile ID: {:?}",
         active_profile_id
     );
 
     // 3. Load profile data if available
-    eprintln!("💭 Step 3: Loading profile data...");
-    let (profile_data, prompt) = if let Some(profile_id) = &active_profile_id {
+    workflow_log!("💭 Step 3: Loading profile data...");
+    let (mut profile_data, prompt) = if let Some(profile_id) = &active_profile_id {
         // Load profiles to get the profile data
-        match load_profiles().await {
+        match get_profiles_cached().await {
             Ok(profile_collection) => {
                 let engine = ProfileEngine::new();
                 match engine.find_profile_by_id(&profile_collection, profile_id) {
                     Ok(profile) => {
-                        let prompt = profile.prompt.as_ref().map(|s| s.to_string());
-                        eprintln!("✅ Found profile: {} (ID: {})", profile.name, profile.id);
+                        let prompt = whisper::profile_whisper_prompt(profile);
+                        workflow_log!("✅ Found profile: {} (ID: {})", profile.name, profile.id);
                         (Some(profile.clone()), prompt)
                     }
                     Err(e) => {
-                        eprintln!("⚠️  Warning: Profile not found: {}", e);
+                        workflow_log!("⚠️  Warning: Profile not found: {}", e);
                         (None, None)
                     }
                 }
             }
             Err(e) => {
-                eprintln!("⚠️  Warning: Failed to load profiles: {}", e);
+                workflow_log!("⚠️  Warning: Failed to load profiles: {}", e);
                 (None, None)
             }
         }
     } else {
         (None, None)
     };
-    eprintln!("✅ Step 3 complete: Profile loaded");
+    // Report a Whisper prompt trimmed to its limit instead of letting
+    // Whisper drop the overflow silently
+    if let Some(truncation) = prompt.as_ref().and_then(|p| p.truncation.as_ref()) {
+        if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
+            let state_machine_guard = state_machine.lock().await;
+            if let Err(e) = state_machine_guard.emit_event_to(
+                EventAudience::MainOnly,
+                "whisper-prompt-truncated",
+                truncation,
+            ) {
+                workflow_log!(
+                    "⚠️  Warning: Failed to emit whisper-prompt-truncated: {}",
+                    e
+                );
+            }
+        }
+    }
+    // The custom vocabulary and the terms corrected in the history bias Whisper
+    let custom_vocabulary = vocabulary::load_vocabulary();
+    let flagged_terms = history::correction_dictionary();
+    let prompt = boost_prompt(
+        prompt.map(|prompt| prompt.text),
+        &custom_vocabulary.bias_terms(&flagged_terms),
+    );
+    let corrections = [custom_vocabulary.corrections(), flagged_terms].concat();
+    workflow_log!("✅ Step 3 complete: Profile loaded");
+    let mut profile_name = profile_data
+        .as_ref()
+        .map(|profile| profile.name.clone())
+        .unwrap_or_else(|| "dictation".to_string());
+
+    // Global stage switches (formatting / clipboard / dry run) from settings.json
+    let (
+        formatting_enabled,
+        clipboard_auto_copy,
+        min_confidence,
+        dry_run,
+        output_settings,
+        history_settings,
+        archive_settings,
+        context_settings,
+        suggestion_settings,
+        voice_commands_enabled,
+        duplicate_window_seconds,
+        bookmark_mode,
+        long_recordings,
+    ) = match settings::read_settings().await {
+        Ok(settings) => (
+            settings.formatting.enabled,
+            // The clipboard is the only output in tray-only mode
+            settings.clipboard.auto_copy || settings.ui.tray_only,
+            settings.clipboard.min_confidence,
+            settings.workflow.dry_run,
+            settings.output,
+            settings.history,
+            settings.recording_archive,
+            settings.context_memory,
+            settings.profile_suggestion,
+            settings.voice_commands.enabled,
+            settings.workflow.duplicate_window_seconds,
+            settings.bookmarks.mode,
+            settings.long_recordings,
+        ),
+        Err(e) => {
+            workflow_log!(
+                "⚠️  Warning: Failed to load settings, using default stage switches: {}",
+                e
+            );
+            (
+                true,
+                true,
+                0.0,
+                false,
+                settings::OutputSettings::default(),
+                settings::HistorySettings::default(),
+                settings::RecordingArchiveSettings::default(),
+                settings::ContextMemorySettings::default(),
+                settings::ProfileSuggestionSettings::default(),
+                false,
+                settings::WorkflowSettings::default().duplicate_window_seconds,
+                BookmarkMode::default(),
+                settings::LongRecordingSettings::default(),
+            )
+        }
+    };
+    workflow_log!(
+        "⚙️  Stage switches: formatting.enabled={}, clipboard.auto_copy={}, workflow.dry_run={}",
+        formatting_enabled,
+        clipboard_auto_copy,
+        dry_run
+    );
+    if dry_run {
+        workflow_log!(
+            "🧪 [PROCESSING] Dry run: API calls are mocked and the clipboard is untouched"
+        );
+    }
+
+    // Hold a recording repeating the previous one (double shortcut press)
+    // rather than billing Whisper twice: it can be retried or discarded
+    if !dry_run && duplicate_window_seconds > 0 {
+        if let Some(seconds_since) =
+            failed_workflows::duplicate_of_previous_recording(&wav_path, duplicate_window_seconds)
+        {
+            let message = format!(
+                "Possible duplicate of the recording made {}s earlier, held instead of transcribed",
+                seconds_since
+            );
+            workflow_log!("♊ [PROCESSING] {}", message);
+            if let Some(job) = failed_workflows::record_failed_workflow(
+                crate::services::WorkflowStage::Transcription,
+                &message,
+                Some(wav_path.clone()),
+                None,
+                active_profile_id.clone(),
+            ) {
+                emit_failed_workflows_updated(&state_machine_state, &job.id).await;
+                if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
+                    let state_machine_guard = state_machine.lock().await;
+                    if let Err(e) = state_machine_guard.emit_event(
+                        "duplicate-recording-detected",
+                        serde_json::json!({
+                            "job_id": job.id,
+                            "seconds_since_previous": seconds_since,
+                        }),
+                    ) {
+                        workflow_log!(
+                            "⚠️  Warning: Failed to emit duplicate-recording-detected: {}",
+                            e
+                        );
+                    }
+                }
+                if let Err(e) = crate::commands::state_machine::process_event(
+                    crate::state::AppEvent::Reset,
+                    &state_machine_state,
+                )
+                .await
+                {
+                    workflow_log!("⚠️  Warning: Failed to reset after a duplicate: {}", e);
+                }
+                return Ok(message);
+            }
+        }
+    }
 
     // 4. Check if whisper client is initialized
-    eprintln!("🤖 Step 4: Checking Whisper client...");
-    {
+    workflow_log!("🤖 Step 4: Checking Whisper client...");
+    if !dry_run {
         let whisper_guard = whisper_state.lock().await;
         if whisper_guard.is_none() {
             let error_msg =
                 "Whisper client not initialized. Please check your API key in settings.";
-            eprintln!("❌ Error: {}", error_msg);
+            workflow_log!("❌ Error: {}", error_msg);
             return Err(error_msg.to_string());
         }
     } // Drop the guard here
-    eprintln!("✅ Step 4 complete: Whisper client is ready");
+    workflow_log!("✅ Step 4 complete: Whisper client is ready");
+
+    // Consult SizeGuard before uploading: oversized recordings are split into
+    // chunks, those that cannot be split are refused
+    let upload_check = if dry_run {
+        Ok(UploadCheck::Fits)
+    } else {
+        whisper::check_upload_size(&wav_path, &app_handle).await
+    };
+    if let Ok(UploadCheck::Split { chunks }) = upload_check {
+        workflow_log!(
+            "📦 [PROCESSING] Recording over the upload limit, transcribing it in {} parts",
+            chunks
+        );
+        if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
+            let state_machine_guard = state_machine.lock().await;
+            if let Err(e) = state_machine_guard.emit_event_to(
+                EventAudience::MainOnly,
+                "recording-oversized",
+                serde_json::json!({ "chunks": chunks }),
+            ) {
+                workflow_log!("⚠️  Warning: Failed to emit recording-oversized: {}", e);
+            }
+        }
+    }
 
     // 5. Transcribe the WAV file using Whisper
-    eprintln!("🎙️  Step 5: Transcribing audio...");
-    let transcript = match transcribe_recorded_audio(
-        wav_path.to_string_lossy().to_string(),
-        prompt,
-        whisper_state,
-    )
-    .await
-    {
+    workflow_log!("🎙️  Step 5: Transcribing audio...");
+    let language = language::take_recording_language(&language_state).await;
+    if let Some(ref language) = language {
+        workflow_log!("🌐 Language forced for this recording: {}", language);
+    }
+    let transcription_started = std::time::Instant::now();
+    let transcription_result = if let Err(e) = upload_check {
+        Err(e)
+    } else if dry_run {
+        let client = crate::services::DryRunWhisperClient::new(500);
+        whisper::encode_and_transcribe(wav_path.clone(), prompt, &client).await
+    } else {
+        // Segment timestamps are kept with the history entry (e.g. for re-transcription)
+        let whisper_mode = profile_data
+            .as_ref()
+            .and_then(|profile| profile.whisper_mode);
+        if whisper_mode == Some(WhisperMode::Translate) {
+            workflow_log!("🌐 Profile translates the recording to English");
+        }
+        // Progress of long recordings transcribed in chunks, for the main window
+        let (chunk_progress, mut chunk_updates) = tokio::sync::mpsc::unbounded_channel();
+        {
+            let state_machine_state = (*state_machine_state).clone();
+            tauri::async_runtime::spawn(async move {
+                while let Some(progress) = chunk_updates.recv().await {
+                    if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
+                        let state_machine_guard = state_machine.lock().await;
+                        if let Err(e) = state_machine_guard.emit_event_to(
+                            EventAudience::MainOnly,
+                            "transcription-chunk-progress",
+                            &progress,
+                        ) {
+                            workflow_log!(
+                                "⚠️  Warning: Failed to emit transcription-chunk-progress: {}",
+                                e
+                            );
+                        }
+                    }
+                }
+            });
+        }
+        let whisper_guard = whisper_state.lock().await;
+        match whisper_guard.as_ref() {
+            Some(client) => {
+                let segmented = SegmentedWhisperClient::new(client.as_ref())
+                    .with_language(language)
+                    .with_mode(whisper_mode);
+                whisper::encode_and_transcribe_long(
+                    wav_path.clone(),
+                    prompt,
+                    &segmented,
+                    &long_recordings,
+                    |progress: ChunkProgress| {
+                        let _ = chunk_progress.send(progress);
+                    },
+                )
+                .await
+            }
+            None => Err("Whisper client not initialized".to_string()),
+        }
+    };
+    metrics::observe_stage_duration("transcription", transcription_started.elapsed());
+    let mut transcript = match transcription_result {
         Ok(transcript) => transcript,
         Err(e) => {
             let error_msg = format!("Transcription failed: {}", e);
-            eprintln!("❌ Error: {}", error_msg);
+            workflow_log!("❌ Error: {}", error_msg);
+            metrics::record_stage_failure("transcription");
+
+            // Keep the recording so the workflow can be retried later
+            if !dry_run {
+                if let Some(job) = failed_workflows::record_failed_workflow(
+                    crate::services::WorkflowStage::Transcription,
+                    &error_msg,
+                    Some(wav_path.clone()),
+                    None,
+                    active_profile_id.clone(),
+                ) {
+                    emit_failed_workflows_updated(&state_machine_state, &job.id).await;
+                }
+            }
 
             // Emit transcription error state
             if let Err(e) = crate::commands::state_machine::process_event(
@@ -232,31 +637,228 @@ pub async fn stop_recording_and_process_to_clipboard(
             )
             .await
             {
-                eprintln!("⚠️  Warning: Failed to emit transcription error: {}", e);
+                workflow_log!("⚠️  Warning: Failed to emit transcription error: {}", e);
             }
 
             return Err(error_msg);
         }
     };
-    eprintln!(
+    workflow_log!(
         "✅ Step 5 complete: Transcribed {} characters",
         transcript.text.len()
     );
+    // The audio is billed once sent, whatever happens to the transcript
+    if !dry_run {
+        usage::record_transcription_usage(transcript.duration, &wav_path).await;
+    }
+    // Overall confidence of the transcript, for the clipboard gate of step 7
+    let confidence = transcript
+        .segments
+        .as_deref()
+        .and_then(transcript_confidence);
 
-    // Emit transcription complete event
-    if let Err(e) = crate::commands::state_machine::process_event(
-        crate::state::AppEvent::TranscriptionComplete {
-            transcript: transcript.text.clone(),
-        },
-        &state_machine_state,
-    )
-    .await
+    // Spoken commands at the start or end of the transcript
+    let voice_commands = if voice_commands_enabled {
+        take_voice_commands(&mut transcript)
+    } else {
+        Vec::new()
+    };
+    let mut profile_chosen_by_voice = false;
+    for command in voice_commands {
+        workflow_log!("🗣️  Voice command: {:?}", command);
+        if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
+            let state_machine_guard = state_machine.lock().await;
+            if let Err(e) = state_machine_guard.emit_event("voice-command", &command) {
+                workflow_log!("⚠️  Warning: Failed to emit voice-command: {}", e);
+            }
+        }
+
+        match command {
+            VoiceCommand::Cancel => {
+                // Discard the dictation: nothing is formatted, copied or kept in history
+                if let Err(e) = crate::commands::state_machine::process_event(
+                    crate::state::AppEvent::Reset,
+                    &state_machine_state,
+                )
+                .await
+                {
+                    workflow_log!("⚠️  Warning: Failed to reset after cancellation: {}", e);
+                }
+                let _ = tokio::fs::remove_file(&wav_path).await;
+                return Ok("Dictation cancelled by voice command".to_string());
+            }
+            VoiceCommand::UseProfile(name) => {
+                match profiles::find_profile_by_spoken_name(&name).await {
+                    Some(profile) => {
+                        workflow_log!("🔀 Switching to profile {} by voice command", profile.name);
+                        profile_state.lock().unwrap().active_profile_id = Some(profile.id.clone());
+                        active_profile_id = Some(profile.id.clone());
+                        profile_name = profile.name.clone();
+                        profile_data = Some(profile);
+                        profile_chosen_by_voice = true;
+                    }
+                    None => workflow_log!("⚠️  Warning: No profile named '{}'", name),
+                }
+            }
+            // Already handled while recording; only stripped from the transcript here
+            VoiceCommand::StopRecording => {}
+        }
+    }
+
+    // Suggest the profile matching the transcript content (and switch to it in auto mode)
+    if suggestion_settings.mode != SuggestionMode::Off && !profile_chosen_by_voice {
+        if let Some((suggestion, profile)) = profiles::suggest_profile_for_transcript(
+            &transcript.text,
+            active_profile_id.as_deref(),
+            suggestion_settings.min_matches,
+        )
+        .await
+        {
+            let applied = suggestion_settings.mode == SuggestionMode::Auto;
+            workflow_log!(
+                "💡 Suggested profile: {} (matched {:?}, applied: {})",
+                suggestion.profile_name,
+                suggestion.matched,
+                applied
+            );
+            if applied {
+                profile_state.lock().unwrap().active_profile_id = Some(profile.id.clone());
+                active_profile_id = Some(profile.id.clone());
+                profile_name = profile.name.clone();
+                profile_data = Some(profile);
+            }
+            if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
+                let state_machine_guard = state_machine.lock().await;
+                if let Err(e) = state_machine_guard.emit_event(
+                    "profile-suggestion",
+                    serde_json::json!({
+                        "profile_id": suggestion.profile_id,
+                        "profile_name": suggestion.profile_name,
+                        "matched": suggestion.matched,
+                        "applied": applied
+                    }),
+                ) {
+                    workflow_log!("⚠️  Warning: Failed to emit profile-suggestion: {}", e);
+                }
+            }
+        }
+    }
+
+    // Code-switched speech: each segment transcribed again in its own language
+    if let Some(mode) = profile_data
+        .as_ref()
+        .and_then(|profile| profile.mixed_language)
+    {
+        if dry_run {
+            workflow_log!("🧪 Dry run: mixed-language handling skipped");
+        } else {
+            let client = whisper_state.lock().await.as_ref().cloned();
+            if let Some(client) = client {
+                handle_mixed_language(
+                    &mut transcript,
+                    &wav_path,
+                    mode,
+                    client.as_ref(),
+                    gpt_state.clone(),
+                )
+                .await;
+            }
+        }
+    }
+
+    // Terms still heard wrong are replaced from the vocabulary and the correction dictionary
+    if !corrections.is_empty() {
+        let (text, replaced) = apply_corrections(&transcript.text, &corrections);
+        if replaced > 0 {
+            transcript.text = text;
+            for segment in transcript.segments.iter_mut().flatten() {
+                segment.text = apply_corrections(&segment.text, &corrections).0;
+            }
+            workflow_log!(
+                "📖 Corrected {} term(s) from the vocabulary and correction dictionary",
+                replaced
+            );
+        }
+    }
+
+    // Local clean-up stages for transcripts that skip GPT formatting
+    let uses_gpt = formatting_enabled
+        && profile_data
+            .as_ref()
+            .is_some_and(|profile| profile.should_use_gpt_formatting());
+
+    // Punctuation and casing for providers that return raw lowercase text
+    if !uses_gpt && output_settings.restore_punctuation && needs_restoration(&transcript.text) {
+        match transcript.segments.as_mut() {
+            Some(segments) if !segments.is_empty() => {
+                restore_segments(segments);
+                transcript.text = segments
+                    .iter()
+                    .map(|segment| segment.text.trim())
+                    .filter(|text| !text.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+            }
+            _ => transcript.text = restore_sentence(&transcript.text),
+        }
+        workflow_log!("🔤 Restored punctuation and casing of the transcript");
+    }
+
+    // Timestamps from segment timing (the profile option overrides the global one)
+    let timestamp_mode = profile_data
+        .as_ref()
+        .and_then(|profile| profile.timestamps)
+        .unwrap_or(output_settings.timestamps);
+    // Bookmarks lay the transcript out instead of timestamps and paragraphs
+    // (marks and timestamps are kept out of the chapters split at pauses)
+    let marked = if let Some(bookmarked) = transcript
+        .segments
+        .as_deref()
+        .and_then(|segments| apply_bookmarks(segments, &bookmarks, bookmark_mode))
+    {
+        workflow_log!(
+            "🔖 Placed {} bookmark(s) in the transcript ({:?})",
+            bookmarks.len(),
+            bookmark_mode
+        );
+        transcript.text = bookmarked;
+        true
+    } else if let Some(timestamped) = transcript
+        .segments
+        .as_deref()
+        .and_then(|segments| timestamped_transcript(segments, timestamp_mode))
     {
-        eprintln!("⚠️  Warning: Failed to emit transcription complete: {}", e);
+        workflow_log!("🕒 Added {:?} timestamps to the transcript", timestamp_mode);
+        transcript.text = timestamped;
+        true
+    } else if output_settings.paragraphize && !uses_gpt {
+        // Unformatted transcripts are split into paragraphs locally
+        if let Some(paragraphs) = transcript.segments.as_deref().and_then(paragraphize) {
+            workflow_log!("📑 Split the transcript into paragraphs");
+            transcript.text = paragraphs;
+        }
+        false
+    } else {
+        false
+    };
+
+    // Emit transcription complete event (skipped when formatting is disabled globally,
+    // in which case we go straight from transcription to clipboard in step 6)
+    if formatting_enabled {
+        if let Err(e) = crate::commands::state_machine::process_event(
+            crate::state::AppEvent::TranscriptionComplete {
+                transcript: transcript.text.clone(),
+            },
+            &state_machine_state,
+        )
+        .await
+        {
+            workflow_log!("⚠️  Warning: Failed to emit transcription complete: {}", e);
+        }
     }
 
     // Emit processing data updated event so frontend gets the transcript data
-    eprintln!("📊 [PROCESSING] Emitting processing-data-updated event with transcript");
+    workflow_log!("📊 [PROCESSING] Emitting processing-data-updated event with transcript");
     let transcript_text = transcript.text.clone(); // Clone early to avoid borrowing issues
     if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
         let state_machine_guard = state_machine.lock().await;
@@ -265,21 +867,52 @@ pub async fn stop_recording_and_process_to_clipboard(
             serde_json::json!({
                 "original_transcript": transcript_text.clone(),
                 "final_text": null,
-                "profile_id": active_profile_id
+                "profile_id": active_profile_id,
+                "language": transcript.language.clone(),
+                "duration": transcript.duration,
+                "segments": transcript.segments.clone(),
+                "words": transcript.words.clone()
             }),
         ) {
-            eprintln!("⚠️  Warning: Failed to emit processing-data-updated: {}", e);
+            workflow_log!("⚠️  Warning: Failed to emit processing-data-updated: {}", e);
         }
     } else {
-        eprintln!("⚠️  Warning: State machine not available for processing-data-updated event");
+        workflow_log!("⚠️  Warning: State machine not available for processing-data-updated event");
     }
 
+    // A reference attached to this workflow is used once, even when formatting is skipped
+    let reference = if uses_gpt {
+        workflow_context::take_reference_prompt(&reference_state, profile_data.as_ref()).await
+    } else {
+        reference_state.lock().await.take();
+        None
+    };
+
     // 6. Apply GPT-4 formatting (conditional)
-    eprintln!("🤖 Step 6: Checking for GPT-4 formatting...");
-    let final_text = if let Some(profile) = profile_data {
-        if profile.id == "1" {
-            // Profile 1 = clipboard profile - no GPT-4 formatting
-            eprintln!("ℹ️  Using clipboard profile (ID: 1) - skipping GPT-4 formatting");
+    workflow_log!("🤖 Step 6: Checking for GPT-4 formatting...");
+    let final_text = if !formatting_enabled {
+        workflow_log!("ℹ️  Formatting disabled in settings - skipping GPT-4 formatting");
+
+        // Skip GPT formatting and go directly to clipboard
+        if let Err(e) = crate::commands::state_machine::process_event(
+            crate::state::AppEvent::SkipFormattingToClipboard {
+                transcript: transcript_text.clone(),
+            },
+            &state_machine_state,
+        )
+        .await
+        {
+            workflow_log!("⚠️  Warning: Failed to emit skip formatting event: {}", e);
+        }
+
+        transcript_text.clone()
+    } else if let Some(profile) = profile_data {
+        if profile.is_clipboard_profile() {
+            // Clipboard profile - no GPT-4 formatting
+            workflow_log!(
+                "ℹ️  Using clipboard profile (ID: {}) - skipping GPT-4 formatting",
+                profile.id
+            );
 
             // Skip GPT formatting and go directly to clipboard
             if let Err(e) = crate::commands::state_machine::process_event(
@@ -290,28 +923,49 @@ pub async fn stop_recording_and_process_to_clipboard(
             )
             .await
             {
-                eprintln!("⚠️  Warning: Failed to emit skip formatting event: {}", e);
+                workflow_log!("⚠️  Warning: Failed to emit skip formatting event: {}", e);
             }
 
             transcript_text.clone()
         } else if profile.prompt.is_some() && !profile.prompt.as_ref().unwrap().is_empty() {
             // Use GPT-4 formatting
-            eprintln!(
+            workflow_log!(
                 "🧠 Attempting GPT-4 formatting with profile: {}",
                 profile.name
             );
-            match format_text_with_gpt(
-                transcript_text.clone(),
-                profile.prompt.unwrap_or_default(),
-                profile.example_input.unwrap_or_default(),
-                profile.example_output.unwrap_or_default(),
-                gpt_state,
-            )
-            .await
-            {
+            // Reference document and earlier dictations of the session give GPT context
+            let context = context_memory::context_prompt(&context_state, &context_settings).await;
+            let instructions = std::iter::once(profile.prompt.unwrap_or_default())
+                .chain(reference)
+                .chain(context)
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let formatting_started = std::time::Instant::now();
+            let format_result = if dry_run {
+                crate::services::GptClient::new_dry_run()
+                    .format_text(
+                        &transcript_text,
+                        &instructions,
+                        profile.example_input.as_deref().unwrap_or_default(),
+                        profile.example_output.as_deref().unwrap_or_default(),
+                    )
+                    .await
+                    .map_err(|e| e.to_string())
+            } else {
+                format_text_with_gpt(
+                    transcript_text.clone(),
+                    instructions,
+                    profile.example_input.unwrap_or_default(),
+                    profile.example_output.unwrap_or_default(),
+                    gpt_state.clone(),
+                )
+                .await
+            };
+            metrics::observe_stage_duration("formatting", formatting_started.elapsed());
+            match format_result {
                 Ok(formatted) => {
-                    eprintln!("✅ GPT-4 formatting successful");
-                    eprintln!(
+                    workflow_log!("✅ GPT-4 formatting successful");
+                    workflow_log!(
                         "🔍 GPT-4 formatted text: {}",
                         &formatted.chars().take(100).collect::<String>()
                     );
@@ -325,16 +979,17 @@ pub async fn stop_recording_and_process_to_clipboard(
                     )
                     .await
                     {
-                        eprintln!("⚠️  Warning: Failed to emit GPT formatting complete: {}", e);
+                        workflow_log!("⚠️  Warning: Failed to emit GPT formatting complete: {}", e);
                     }
 
                     formatted
                 }
                 Err(e) => {
-                    eprintln!(
+                    workflow_log!(
                         "⚠️  GPT-4 formatting failed, using original transcript: {}",
                         e
                     );
+                    metrics::record_stage_failure("formatting");
 
                     // Emit GPT formatting error but continue with original transcript
                     if let Err(err) = crate::commands::state_machine::process_event(
@@ -345,7 +1000,7 @@ pub async fn stop_recording_and_process_to_clipboard(
                     )
                     .await
                     {
-                        eprintln!("⚠️  Warning: Failed to emit GPT formatting error: {}", err);
+                        workflow_log!("⚠️  Warning: Failed to emit GPT formatting error: {}", err);
                     }
 
                     // Still transition to clipboard with original text
@@ -357,7 +1012,7 @@ pub async fn stop_recording_and_process_to_clipboard(
                     )
                     .await
                     {
-                        eprintln!(
+                        workflow_log!(
                             "⚠️  Warning: Failed to emit skip formatting after error: {}",
                             err
                         );
@@ -368,7 +1023,7 @@ pub async fn stop_recording_and_process_to_clipboard(
             }
         } else {
             // Profile has no prompt - use original transcript
-            eprintln!("ℹ️  Profile has no prompt - using original transcript");
+            workflow_log!("ℹ️  Profile has no prompt - using original transcript");
 
             // Skip GPT formatting and go directly to clipboard
             if let Err(e) = crate::commands::state_machine::process_event(
@@ -379,14 +1034,14 @@ pub async fn stop_recording_and_process_to_clipboard(
             )
             .await
             {
-                eprintln!("⚠️  Warning: Failed to emit skip formatting event: {}", e);
+                workflow_log!("⚠️  Warning: Failed to emit skip formatting event: {}", e);
             }
 
             transcript_text.clone()
         }
     } else {
         // No profile selected - use original transcript
-        eprintln!("ℹ️  No profile selected - using original transcript");
+        workflow_log!("ℹ️  No profile selected - using original transcript");
 
         // Skip GPT formatting and go directly to clipboard
         if let Err(e) = crate::commands::state_machine::process_event(
@@ -397,18 +1052,40 @@ pub async fn stop_recording_and_process_to_clipboard(
         )
         .await
         {
-            eprintln!("⚠️  Warning: Failed to emit skip formatting event: {}", e);
+            workflow_log!("⚠️  Warning: Failed to emit skip formatting event: {}", e);
         }
 
         transcript_text.clone()
     };
-    eprintln!(
+
+    // Long final texts are split into chapters after formatting
+    let final_text = if output_settings.chapters != ChapterMode::Off
+        && chapters::word_count(&final_text) >= output_settings.chapters_min_words as usize
+    {
+        // Pauses only place chapters in the untouched transcript
+        let segments = transcript
+            .segments
+            .as_deref()
+            .filter(|_| final_text == transcript_text && !marked);
+        split_into_chapters(
+            &final_text,
+            segments,
+            output_settings.chapters,
+            dry_run,
+            gpt_state,
+        )
+        .await
+        .unwrap_or(final_text)
+    } else {
+        final_text
+    };
+    workflow_log!(
         "✅ Step 6 complete: Final text ready ({} characters)",
         final_text.len()
     );
 
     // Emit final processing data updated event with both transcript and final text
-    eprintln!("📊 [PROCESSING] Emitting final processing-data-updated event");
+    workflow_log!("📊 [PROCESSING] Emitting final processing-data-updated event");
     if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
         let state_machine_guard = state_machine.lock().await;
         if let Err(e) = state_machine_guard.emit_event(
@@ -416,31 +1093,63 @@ pub async fn stop_recording_and_process_to_clipboard(
             serde_json::json!({
                 "original_transcript": transcript_text.clone(),
                 "final_text": final_text.clone(),
-                "profile_id": active_profile_id
+                "profile_id": active_profile_id,
+                "dry_run": dry_run,
+                "language": transcript.language.clone(),
+                "duration": transcript.duration,
+                "segments": transcript.segments.clone(),
+                "words": transcript.words.clone()
             }),
         ) {
-            eprintln!(
+            workflow_log!(
                 "⚠️  Warning: Failed to emit final processing-data-updated: {}",
                 e
             );
         }
     } else {
-        eprintln!(
+        workflow_log!(
             "⚠️  Warning: State machine not available for final processing-data-updated event"
         );
     }
 
-    // 7. Copy processed text to clipboard
-    eprintln!("📋 Step 7: Copying to clipboard...");
-    eprintln!("🔍 DEBUG: Clipboard content analysis:");
-    eprintln!(
+    // Transcripts below the confidence threshold are kept for review instead
+    let low_confidence = confidence.filter(|&confidence| confidence < min_confidence);
+    let clipboard_auto_copy = clipboard_auto_copy && low_confidence.is_none();
+    if let Some(confidence) = low_confidence {
+        workflow_log!(
+            "⚠️  Low transcript confidence ({:.2} < {:.2}): kept for review instead of copied",
+            confidence,
+            min_confidence
+        );
+        if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
+            let state_machine_guard = state_machine.lock().await;
+            if let Err(e) = state_machine_guard.emit_event_to(
+                EventAudience::MainOnly,
+                "low-confidence-transcript",
+                serde_json::json!({
+                    "confidence": confidence,
+                    "min_confidence": min_confidence
+                }),
+            ) {
+                workflow_log!(
+                    "⚠️  Warning: Failed to emit low-confidence-transcript: {}",
+                    e
+                );
+            }
+        }
+    }
+
+    // 7. Copy processed text to clipboard (unless auto-copy is disabled globally)
+    workflow_log!("📋 Step 7: Copying to clipboard...");
+    workflow_log!("🔍 DEBUG: Clipboard content analysis:");
+    workflow_log!(
         "   📊 Text length: {} characters",
         final_text.chars().count()
     );
-    eprintln!("   📊 Text bytes: {} bytes", final_text.len());
+    workflow_log!("   📊 Text bytes: {} bytes", final_text.len());
     if !final_text.is_empty() {
         let preview_chars = final_text.chars().take(100).collect::<String>();
-        eprintln!(
+        workflow_log!(
             "   📝 First {} chars: {:?}",
             preview_chars.chars().count(),
             preview_chars
@@ -448,27 +1157,45 @@ pub async fn stop_recording_and_process_to_clipboard(
         if final_text.chars().count() > 100 {
             let last_chars = final_text.chars().rev().take(50).collect::<Vec<_>>();
             let last_chars_str: String = last_chars.into_iter().rev().collect();
-            eprintln!("   📝 Last 50 chars: {:?}", last_chars_str);
+            workflow_log!("   📝 Last 50 chars: {:?}", last_chars_str);
         }
-        eprintln!("   🔤 Contains newlines: {}", final_text.contains('\n'));
-        eprintln!("   🔤 Contains tabs: {}", final_text.contains('\t'));
-        eprintln!("   🔤 Non-ASCII chars: {}", !final_text.is_ascii());
+        workflow_log!("   🔤 Contains newlines: {}", final_text.contains('\n'));
+        workflow_log!("   🔤 Contains tabs: {}", final_text.contains('\t'));
+        workflow_log!("   🔤 Non-ASCII chars: {}", !final_text.is_ascii());
     } else {
-        eprintln!("   ⚠️  WARNING: Empty text being copied to clipboard!");
+        workflow_log!("   ⚠️  WARNING: Empty text being copied to clipboard!");
     }
 
-    {
+    if low_confidence.is_some() {
+        workflow_log!("ℹ️  Clipboard copy blocked by low confidence - text kept for review");
+    } else if !clipboard_auto_copy {
+        workflow_log!("ℹ️  Clipboard auto-copy disabled in settings - text kept for review");
+    } else if dry_run {
+        workflow_log!("🧪 Dry run: clipboard left untouched");
+    } else {
         let clipboard_guard = clipboard_state.lock().await;
         if let Some(ref clipboard) = *clipboard_guard {
-            eprintln!("   📋 Attempting clipboard copy...");
+            workflow_log!("   📋 Attempting clipboard copy...");
             match clipboard.copy(&final_text).await {
                 Ok(_) => {
-                    eprintln!("   ✅ Clipboard copy operation completed successfully");
+                    workflow_log!("   ✅ Clipboard copy operation completed successfully");
                     // Note: ClipboardCopyComplete event will be emitted after cleanup
                 }
                 Err(e) => {
                     let error_msg = format!("Failed to copy to clipboard: {}", e);
-                    eprintln!("❌ Error: {}", error_msg);
+                    workflow_log!("❌ Error: {}", error_msg);
+                    metrics::record_stage_failure("clipboard");
+
+                    // Keep the final text so the copy can be retried later
+                    if let Some(job) = failed_workflows::record_failed_workflow(
+                        crate::services::WorkflowStage::Clipboard,
+                        &error_msg,
+                        None,
+                        Some(final_text.clone()),
+                        active_profile_id.clone(),
+                    ) {
+                        emit_failed_workflows_updated(&state_machine_state, &job.id).await;
+                    }
 
                     // Emit clipboard error
                     if let Err(err) = crate::commands::state_machine::process_event(
@@ -479,7 +1206,7 @@ pub async fn stop_recording_and_process_to_clipboard(
                     )
                     .await
                     {
-                        eprintln!("⚠️  Warning: Failed to emit clipboard error: {}", err);
+                        workflow_log!("⚠️  Warning: Failed to emit clipboard error: {}", err);
                     }
 
                     return Err(error_msg);
@@ -487,42 +1214,261 @@ pub async fn stop_recording_and_process_to_clipboard(
             }
         } else {
             let error_msg = "Clipboard service not initialized";
-            eprintln!("❌ Error: {}", error_msg);
+            workflow_log!("❌ Error: {}", error_msg);
             return Err(error_msg.to_string());
         }
     }
-    eprintln!("✅ Step 7 complete: Text copied to clipboard");
+    workflow_log!("✅ Step 7 complete: Clipboard stage done");
 
-    // 8. Clean up temporary WAV file
-    eprintln!("🧹 Step 8: Cleaning up temporary files...");
-    if let Err(e) = tokio::fs::remove_file(&wav_path).await {
-        eprintln!("⚠️  Warning: Failed to clean up temporary WAV file: {}", e);
-    } else {
-        eprintln!("✅ Step 8 complete: Temporary file cleaned up");
+    if !dry_run {
+        context_memory::remember_final_text(&context_state, &context_settings, &final_text).await;
+    }
+
+    // Save the final text to a file (the template was validated when settings were saved)
+    if output_settings.save_to_file && !dry_run {
+        match FilenameTemplate::parse(&output_settings.filename_template) {
+            Ok(template) => {
+                let context = FilenameContext::now(&profile_name, &final_text);
+                match template.write_unique(&output_settings.output_dir(), &context, &final_text) {
+                    Ok(path) => workflow_log!("💾 Final text saved to {}", path.display()),
+                    Err(e) => workflow_log!("⚠️  Warning: Failed to save output file: {}", e),
+                }
+            }
+            Err(e) => workflow_log!("⚠️  Warning: Invalid output filename template: {}", e),
+        }
     }
 
+    // 8. Record the workflow in the history and the recording archive, then
+    // clean up the temporary WAV file
+    workflow_log!("🧹 Step 8: Recording history and cleaning up temporary files...");
+    // Recordings completed during a focus session are tagged to it
+    let focus_session_id = if dry_run {
+        None
+    } else {
+        focus_session::tag_recording(&focus_state, &final_text, active_profile_id.clone()).await
+    };
+    let history_job = {
+        let state_machine_state = (*state_machine_state).clone();
+        let final_text = final_text.clone();
+        let transcript_text = transcript_text.clone();
+        let segments = transcript.segments.clone().unwrap_or_default();
+        let profile_id = active_profile_id.clone();
+        async move {
+            if !dry_run {
+                history::record_workflow_history(
+                    &history_settings,
+                    final_text,
+                    transcript_text,
+                    segments,
+                    profile_id,
+                    focus_session_id,
+                    &wav_path,
+                )
+                .await;
+                // Goal progress is computed from the history, so only once recorded
+                goals::emit_goal_progress(&state_machine_state).await;
+                history::archive_recording(&archive_settings, &wav_path).await;
+            }
+            if let Err(e) = tokio::fs::remove_file(&wav_path).await {
+                workflow_log!("⚠️  Warning: Failed to clean up temporary WAV file: {}", e);
+            } else {
+                workflow_log!("✅ Step 8 complete: Temporary file cleaned up");
+            }
+        }
+    };
+    // Archiving re-encodes the recording, so it must not hold up the completed state
+    tauri::async_runtime::spawn(history_job);
+
     // 9. Transition to processing complete state (stay here for reformatting)
-    eprintln!("🎯 [PROCESSING] Step 9: Transitioning to processing complete state...");
-    if let Err(e) = crate::commands::state_machine::process_event(
-        crate::state::AppEvent::ClipboardCopyComplete,
-        &state_machine_state,
-    )
-    .await
+    workflow_log!("🎯 [PROCESSING] Step 9: Transitioning to processing complete state...");
+    let completion_event = if clipboard_auto_copy {
+        crate::state::AppEvent::ClipboardCopyComplete
+    } else {
+        crate::state::AppEvent::SkipClipboardCopy
+    };
+    if let Err(e) =
+        crate::commands::state_machine::process_event(completion_event, &state_machine_state).await
     {
-        eprintln!(
+        workflow_log!(
             "⚠️  Warning: Failed to transition to processing complete: {}",
             e
         );
     } else {
-        eprintln!("✅ [PROCESSING] Step 9 complete: Transitioned to processing complete state");
+        workflow_log!("✅ [PROCESSING] Step 9 complete: Transitioned to processing complete state");
     }
 
-    let success_msg = "Transcription copied to clipboard";
-    eprintln!("🎉 Workflow complete: {}", success_msg);
-    eprintln!("📊 [PROCESSING] Final state: ProcessingComplete - ready for reformatting with different profiles");
+    let success_msg = if dry_run {
+        "Dry run complete (no API calls, clipboard untouched)"
+    } else if low_confidence.is_some() {
+        "Low transcription confidence: check the text before copying it"
+    } else if clipboard_auto_copy {
+        "Transcription copied to clipboard"
+    } else {
+        "Transcription ready for review"
+    };
+    metrics::observe_stage_duration("total", workflow_started.elapsed());
+    metrics::MetricsRegistry::global().inc_counter(metrics::RECORDINGS_TOTAL, &[]);
+    workflow_log!("🎉 Workflow complete: {}", success_msg);
+    workflow_log!("📊 [PROCESSING] Final state: ProcessingComplete - ready for reformatting with different profiles");
     Ok(success_msg.to_string())
 }
 
+/// Rewrite the minority-language segments of a code-switched transcript
+///
+/// The transcript is kept as is when it has a single segment, too many
+/// segments, or a single language.
+async fn handle_mixed_language(
+    transcript: &mut crate::services::TranscriptionResponse,
+    wav_path: &std::path::Path,
+    mode: MixedLanguageMode,
+    client: &dyn crate::services::WhisperClient,
+    gpt_state: State<'_, GptClientState>,
+) {
+    let Some(segments) = transcript.segments.as_mut() else {
+        return;
+    };
+    if segments.len() < 2 {
+        return;
+    }
+    if segments.len() > mixed_language::MAX_MIXED_LANGUAGE_SEGMENTS {
+        workflow_log!(
+            "⚠️  Mixed-language handling skipped: {} segments (at most {})",
+            segments.len(),
+            mixed_language::MAX_MIXED_LANGUAGE_SEGMENTS
+        );
+        return;
+    }
+
+    let detected = match retranscription::detect_segment_languages(wav_path, segments, client).await
+    {
+        Ok(detected) => detected,
+        Err(e) => {
+            workflow_log!("⚠️  Warning: Mixed-language detection failed: {}", e);
+            return;
+        }
+    };
+    let Some(main) = mixed_language::main_language(segments, &detected) else {
+        return;
+    };
+    let minority = mixed_language::minority_segments(&detected, &main);
+    if minority.is_empty() {
+        workflow_log!("🌐 Single-language recording ({})", main);
+        return;
+    }
+
+    let mut translations = HashMap::new();
+    if mode == MixedLanguageMode::Translate {
+        for found in &minority {
+            match format_text_with_gpt(
+                found.text.trim().to_string(),
+                mixed_language::translation_prompt(&main),
+                String::new(),
+                String::new(),
+                gpt_state.clone(),
+            )
+            .await
+            {
+                Ok(translation) => {
+                    translations.insert(found.id, translation);
+                }
+                Err(e) => {
+                    workflow_log!("⚠️  Warning: Segment {} kept untranslated: {}", found.id, e)
+                }
+            }
+        }
+    }
+    workflow_log!(
+        "🌐 {} segment(s) in another language than {} ({:?})",
+        minority.len(),
+        main,
+        mode
+    );
+
+    mixed_language::apply_languages(segments, &detected, &main, mode, &translations);
+    transcript.text = segments
+        .iter()
+        .map(|segment| segment.text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+}
+
+/// Final text split into chapters with a table of contents (None when it
+/// fits in one chapter)
+///
+/// `segments` are the transcript segments when the final text is the
+/// transcript as Whisper returned it, so its pauses can place the chapters.
+async fn split_into_chapters(
+    final_text: &str,
+    segments: Option<&[crate::services::TranscriptionSegment]>,
+    mode: ChapterMode,
+    dry_run: bool,
+    gpt_state: State<'_, GptClientState>,
+) -> Option<String> {
+    let paragraphs = chapters::text_paragraphs(final_text);
+    if mode == ChapterMode::Llm {
+        let request = chapters::outline_request(&paragraphs);
+        let response = if dry_run {
+            crate::services::GptClient::new_dry_run()
+                .format_text(&request, chapters::OUTLINE_PROMPT, "", "")
+                .await
+                .map_err(|e| e.to_string())
+        } else {
+            format_text_with_gpt(
+                request,
+                chapters::OUTLINE_PROMPT.to_string(),
+                String::new(),
+                String::new(),
+                gpt_state,
+            )
+            .await
+        };
+        let outline = response.map(|response| {
+            chapters::parse_outline(&response)
+                .and_then(|outline| chapters::chapters_from_outline(&paragraphs, &outline))
+        });
+        match outline {
+            Ok(Some(found)) => {
+                workflow_log!("📚 Split the final text into {} chapters", found.len());
+                return Some(chapters::render_chapters(&found));
+            }
+            Ok(None) => {
+                workflow_log!("⚠️  Unusable chapter outline, splitting into chapters locally")
+            }
+            Err(e) => workflow_log!(
+                "⚠️  Chapter outline failed, splitting into chapters locally: {}",
+                e
+            ),
+        }
+    }
+
+    let found = match segments {
+        Some(segments) => chapters::chapters_by_pauses(segments),
+        None => chapters::chapters_by_length(&paragraphs),
+    }?;
+    workflow_log!(
+        "📚 Split the final text into {} chapters locally",
+        found.len()
+    );
+    Some(chapters::render_chapters(&found))
+}
+
+/// Notify the frontend that the failed workflows list changed
+async fn emit_failed_workflows_updated(
+    state_machine_state: &State<'_, crate::state::AppStateMachineState>,
+    job_id: &str,
+) {
+    if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
+        let state_machine_guard = state_machine.lock().await;
+        if let Err(e) = state_machine_guard.emit_event("failed-workflows-updated", job_id) {
+            eprintln!(
+                "⚠️  Warning: Failed to emit failed-workflows-updated: {}",
+                e
+            );
+        }
+    }
+}
+
 /// Reformat the completed text with a different profile
 /// This command is used when processing is complete and the user wants to reformat with a different profile
 #[tauri::command]
@@ -580,7 +1526,7 @@ pub async fn reformat_with_profile(
 
     // Load the selected profile
     eprintln!("💭 [REFORMAT] Loading profile data for: {}", profile_id);
-    let profile = match load_profiles().await {
+    let profile = match get_profiles_cached().await {
         Ok(profile_collection) => {
             let engine = ProfileEngine::new();
             match engine.find_profile_by_id(&profile_collection, &profile_id) {
@@ -599,9 +1545,12 @@ pub async fn reformat_with_profile(
 
     // Apply formatting based on profile
     let final_text = if let Some(profile) = profile {
-        if profile.id == "1" {
-            // Profile 1 = clipboard profile - no GPT-4 formatting
-            eprintln!("ℹ️  [REFORMAT] Using clipboard profile (ID: 1) - skipping GPT-4 formatting");
+        if profile.is_clipboard_profile() {
+            // Clipboard profile - no GPT-4 formatting
+            eprintln!(
+                "ℹ️  [REFORMAT] Using clipboard profile (ID: {}) - skipping GPT-4 formatting",
+                profile.id
+            );
 
             // Skip GPT formatting and go directly to clipboard
             if let Err(e) = crate::commands::state_machine::process_event(
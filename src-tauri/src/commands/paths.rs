@@ -0,0 +1,14 @@
+//! Command reporting where the app keeps its files
+
+use crate::commands::settings::read_settings;
+use crate::paths::DataLocations;
+
+/// Directories and files of the app (for the settings "Data" section)
+#[tauri::command]
+pub async fn get_data_locations() -> Result<DataLocations, String> {
+    let mut locations = DataLocations::current();
+    if let Ok(settings) = read_settings().await {
+        locations.outputs_dir = settings.output.output_dir();
+    }
+    Ok(locations)
+}
@@ -0,0 +1,145 @@
+//! Commands for replaying the last recording (held for confirmation or
+//! archived with the last workflow)
+
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+
+use crate::audio::playback::{self, AudioClip, PlaybackHandle, PlaybackResult};
+use crate::commands::app_lock::require_unlocked;
+use crate::commands::audio::pending_recording;
+use crate::commands::history::{history_store, last_workflow_entry};
+use crate::commands::settings::read_settings;
+use crate::state::AppStateMachineState;
+
+/// Recording currently being played
+pub type PlaybackState = Arc<Mutex<Option<PlaybackHandle>>>;
+
+/// Interval between `playback-position` events
+const POSITION_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Play the recording waiting for confirmation, or else the recording of
+/// the completed workflow
+///
+/// A recording held by `workflow.confirm_before_transcription` is played from
+/// its WAV file; completed recordings are only kept when
+/// `history.archive_audio` is enabled. Emits `playback-position`
+/// (`{ position_ms, duration_ms }`) while playing and `playback-finished` at
+/// the end.
+#[tauri::command]
+pub async fn play_last_recording(
+    app_handle: AppHandle,
+    playback_state: State<'_, PlaybackState>,
+    state_machine_state: State<'_, AppStateMachineState>,
+) -> Result<serde_json::Value, String> {
+    if let Some((wav_path, _)) = pending_recording(&state_machine_state).await {
+        let audio = tokio::fs::read(&wav_path)
+            .await
+            .map_err(|e| format!("Failed to read recording: {}", e))?;
+        let handle = start_playback(app_handle, &playback_state, move || {
+            AudioClip::from_wav_bytes(&audio)
+        })
+        .await?;
+        eprintln!(
+            "▶️  [PLAYBACK] Playing pending recording {:?} ({} ms)",
+            wav_path,
+            handle.duration().as_millis()
+        );
+        return Ok(serde_json::json!({
+            "wav_path": wav_path,
+            "duration_ms": handle.duration().as_millis() as u64,
+        }));
+    }
+
+    // The archived recordings are part of the history
+    require_unlocked()?;
+    let settings = read_settings().await?.history;
+    if !settings.enabled || !settings.archive_audio {
+        return Err(
+            "Recordings are not kept: enable history.archive_audio to replay them".to_string(),
+        );
+    }
+
+    let store = history_store(&settings)?;
+    let entry = last_workflow_entry(&store, &state_machine_state).await?;
+    if !entry.has_audio {
+        return Err("The last recording was not archived".to_string());
+    }
+    let audio = store
+        .read_audio(&entry.id)
+        .map_err(|e| format!("Failed to read recording: {}", e))?;
+
+    let handle = start_playback(app_handle, &playback_state, move || {
+        AudioClip::from_ogg_bytes(&audio)
+    })
+    .await?;
+    let duration_ms = handle.duration().as_millis() as u64;
+    eprintln!(
+        "▶️  [PLAYBACK] Playing recording {} ({} ms)",
+        entry.id, duration_ms
+    );
+
+    Ok(serde_json::json!({
+        "history_id": entry.id,
+        "duration_ms": duration_ms,
+    }))
+}
+
+/// Decode and play a clip in place of the current playback, reporting its
+/// position
+async fn start_playback(
+    app_handle: AppHandle,
+    playback_state: &State<'_, PlaybackState>,
+    decode: impl FnOnce() -> PlaybackResult<AudioClip> + Send + 'static,
+) -> Result<PlaybackHandle, String> {
+    let mut playback_guard = playback_state.lock().await;
+    if let Some(previous) = playback_guard.take() {
+        previous.stop();
+    }
+    let handle = tokio::task::spawn_blocking(move || decode().and_then(playback::play))
+        .await
+        .map_err(|e| format!("Playback task failed: {}", e))?
+        .map_err(|e| format!("Failed to play recording: {}", e))?;
+    *playback_guard = Some(handle.clone());
+    drop(playback_guard);
+
+    tokio::spawn(report_playback_position(app_handle, handle.clone()));
+    Ok(handle)
+}
+
+/// Stop replaying the recording
+#[tauri::command]
+pub async fn stop_playback(playback_state: State<'_, PlaybackState>) -> Result<String, String> {
+    match playback_state.lock().await.take() {
+        Some(handle) if !handle.is_finished() => {
+            handle.stop();
+            eprintln!("⏹️  [PLAYBACK] Playback stopped");
+            Ok("Playback stopped".to_string())
+        }
+        _ => Ok("Nothing is playing".to_string()),
+    }
+}
+
+/// Emit `playback-position` until playback ends, then `playback-finished`
+async fn report_playback_position(app_handle: AppHandle, handle: PlaybackHandle) {
+    let duration_ms = handle.duration().as_millis() as u64;
+    let mut interval = tokio::time::interval(POSITION_INTERVAL);
+    while !handle.is_finished() {
+        interval.tick().await;
+        let _ = app_handle.emit(
+            "playback-position",
+            serde_json::json!({
+                "position_ms": handle.position().as_millis() as u64,
+                "duration_ms": duration_ms,
+            }),
+        );
+    }
+    let _ = app_handle.emit(
+        "playback-finished",
+        serde_json::json!({
+            "position_ms": handle.position().as_millis() as u64,
+            "duration_ms": duration_ms,
+        }),
+    );
+}
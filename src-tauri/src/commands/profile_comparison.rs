@@ -0,0 +1,131 @@
+//! Command formatting one transcript with two profiles side by side
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::commands::app_lock::require_unlocked;
+use crate::commands::history::history_store;
+use crate::commands::settings::read_settings;
+use crate::commands::{get_profiles_cached, GptClientState};
+use crate::services::profile_engine::ProfileBehavior;
+use crate::services::text_diff::{diff_words, DiffSegment};
+use crate::services::{GptClient, Profile};
+use crate::state::recording_state_machine::AppState;
+use crate::state::AppStateMachineState;
+
+/// Output of one side of a comparison
+#[derive(Debug, Clone, Serialize)]
+pub struct ComparedOutput {
+    pub profile_id: String,
+    pub profile_name: String,
+    pub output: String,
+}
+
+/// Same transcript formatted with two profiles
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileComparison {
+    pub transcript: String,
+    pub a: ComparedOutput,
+    pub b: ComparedOutput,
+    /// Word diff from the output of `a` to the output of `b`
+    pub diff: Vec<DiffSegment>,
+}
+
+/// Transcript of the last result: the workflow shown in ProcessingComplete,
+/// else the newest history entry
+async fn last_transcript(state_machine_state: &AppStateMachineState) -> Result<String, String> {
+    if let Some(state_machine) = state_machine_state.lock().await.as_ref() {
+        if let AppState::ProcessingComplete {
+            original_transcript,
+            ..
+        } = state_machine.lock().await.current_state()
+        {
+            return Ok(original_transcript.clone());
+        }
+    }
+
+    require_unlocked()?;
+    let settings = read_settings().await?.history;
+    history_store(&settings)?
+        .list()
+        .map_err(|e| format!("Failed to load history: {}", e))?
+        .into_iter()
+        .next()
+        .map(|entry| entry.original_transcript)
+        .ok_or_else(|| "No transcript to compare: the history is empty".to_string())
+}
+
+/// Format `transcript` with `profile` (unchanged for profiles without a prompt)
+async fn format_with(
+    client: Option<&GptClient>,
+    profile: &Profile,
+    transcript: &str,
+) -> Result<ComparedOutput, String> {
+    let output = if profile.should_use_gpt_formatting() {
+        let client = client.ok_or("GPT client not initialized")?;
+        client
+            .format_text(
+                transcript,
+                profile.prompt.as_deref().unwrap_or_default(),
+                profile.example_input.as_deref().unwrap_or_default(),
+                profile.example_output.as_deref().unwrap_or_default(),
+            )
+            .await
+            .map_err(|e| format!("Formatting with '{}' failed: {}", profile.name, e))?
+    } else {
+        transcript.to_string()
+    };
+    Ok(ComparedOutput {
+        profile_id: profile.id.clone(),
+        profile_name: profile.name.clone(),
+        output,
+    })
+}
+
+/// Format the same transcript with two profiles and diff the results
+///
+/// Uses `transcript` when given, else the transcript of the last result. Both
+/// profiles are formatted concurrently; the state machine is left alone.
+#[tauri::command]
+pub async fn compare_profiles(
+    profile_a: String,
+    profile_b: String,
+    transcript: Option<String>,
+    state_machine_state: State<'_, AppStateMachineState>,
+    gpt_state: State<'_, GptClientState>,
+) -> Result<ProfileComparison, String> {
+    let transcript = match transcript.filter(|text| !text.trim().is_empty()) {
+        Some(text) => text,
+        None => last_transcript(&state_machine_state).await?,
+    };
+
+    let profiles = get_profiles_cached().await?.profiles;
+    let find = |id: &str| {
+        profiles
+            .iter()
+            .find(|profile| profile.id == id)
+            .ok_or_else(|| format!("Profile not found: {}", id))
+    };
+    let (profile_a, profile_b) = (find(&profile_a)?, find(&profile_b)?);
+
+    let client_guard = gpt_state.lock().await;
+    let (a, b) = tokio::join!(
+        format_with(client_guard.as_ref(), profile_a, &transcript),
+        format_with(client_guard.as_ref(), profile_b, &transcript)
+    );
+    drop(client_guard);
+    let (a, b) = (a?, b?);
+
+    eprintln!(
+        "🆚 [COMPARE] Formatted {} characters with '{}' and '{}'",
+        transcript.len(),
+        a.profile_name,
+        b.profile_name
+    );
+    Ok(ProfileComparison {
+        diff: diff_words(&a.output, &b.output),
+        transcript,
+        a,
+        b,
+    })
+}
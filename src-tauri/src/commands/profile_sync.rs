@@ -0,0 +1,94 @@
+//! Commands syncing the profiles with a team folder or git remote
+
+use serde::Serialize;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::commands::profiles::load_profiles;
+use crate::commands::settings::{read_settings, write_profiles};
+use crate::services::profile_sync::{ProfileConflict, ProfileSync, SYNC_BASE_FILE};
+
+/// How often the schedule checks whether sync got enabled
+const SCHEDULE_IDLE_CHECK: Duration = Duration::from_secs(60);
+
+/// One sync at a time (scheduled and on demand)
+static SYNC_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Outcome of a profile sync
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileSyncReport {
+    /// Local profiles were updated from the shared ones
+    pub local_changed: bool,
+    /// Shared profiles were updated from the local ones
+    pub remote_changed: bool,
+    /// Profiles edited on both sides since the last sync
+    pub conflicts: Vec<ProfileConflict>,
+}
+
+/// Sync the profiles with the shared folder or git remote of the settings
+pub async fn sync_profiles(app_handle: &AppHandle) -> Result<ProfileSyncReport, String> {
+    let _sync = SYNC_LOCK.lock().await;
+    let shared = read_settings()
+        .await?
+        .profile_sync
+        .shared_profiles()
+        .ok_or_else(|| {
+            "Profile sync is not configured: set profile_sync.folder or profile_sync.git_remote"
+                .to_string()
+        })?;
+    let local = load_profiles().await?;
+
+    let sync = ProfileSync::new(shared, crate::paths::state_dir().join(SYNC_BASE_FILE));
+    let outcome = tokio::task::spawn_blocking(move || sync.sync(&local))
+        .await
+        .map_err(|e| format!("Profile sync task failed: {}", e))?
+        .map_err(|e| e.to_string())?;
+
+    if outcome.local_changed {
+        write_profiles(outcome.merged, app_handle.clone()).await?;
+    }
+    for conflict in &outcome.conflicts {
+        eprintln!(
+            "⚠️  [PROFILE SYNC] '{}' changed on both sides, kept the {:?} version",
+            conflict.profile_name, conflict.kept
+        );
+    }
+    eprintln!(
+        "🔄 [PROFILE SYNC] Synced (local updated: {}, shared updated: {})",
+        outcome.local_changed, outcome.remote_changed
+    );
+
+    Ok(ProfileSyncReport {
+        local_changed: outcome.local_changed,
+        remote_changed: outcome.remote_changed,
+        conflicts: outcome.conflicts,
+    })
+}
+
+/// Sync the profiles right away
+#[tauri::command]
+pub async fn sync_profiles_now(app_handle: AppHandle) -> Result<ProfileSyncReport, String> {
+    sync_profiles(&app_handle).await
+}
+
+/// Sync the profiles every `profile_sync.interval_minutes` while enabled
+/// (spawned at startup, runs for the app lifetime)
+pub async fn run_profile_sync_schedule(app_handle: AppHandle) {
+    loop {
+        let interval = match read_settings().await {
+            Ok(settings) if settings.profile_sync.enabled => {
+                Some(settings.profile_sync.interval_minutes).filter(|&minutes| minutes > 0)
+            }
+            _ => None,
+        };
+        match interval {
+            Some(minutes) => {
+                tokio::time::sleep(Duration::from_secs(u64::from(minutes) * 60)).await;
+                if let Err(e) = sync_profiles(&app_handle).await {
+                    eprintln!("⚠️  [PROFILE SYNC] Scheduled sync failed: {}", e);
+                }
+            }
+            None => tokio::time::sleep(SCHEDULE_IDLE_CHECK).await,
+        }
+    }
+}
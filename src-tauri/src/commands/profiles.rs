@@ -1,10 +1,13 @@
 use std::sync::Mutex;
 use tauri::{Manager, State};
 
-use crate::services::profile_engine::{
-    ensure_clipboard_profile, ProfileBehavior, ProfileCollection, ProfileEngine,
-};
-use crate::utils::find_config_file_path;
+use crate::paths::find_config_file_path;
+use crate::services::gpt_client::FORMATTING_MODEL;
+use crate::services::profile_engine::{Profile, ProfileBehavior, ProfileCollection, ProfileEngine};
+use crate::services::profile_store::ProfileStore;
+use crate::services::profile_suggestion::{suggest_profile, ProfileSuggestion};
+use crate::services::prompt_lint::{self, LintIssue, LintSeverity, PromptLint};
+use crate::services::reference_context::load_reference;
 
 /// State to hold the active profile ID
 #[derive(Default)]
@@ -15,26 +18,31 @@ pub struct ProfileState {
 pub type ProfileAppState = Mutex<ProfileState>;
 
 /// Load profiles from the profiles.json file
+///
+/// Always reads the file again (and refreshes the profile cache), so the
+/// settings UI sees edits made outside the app.
 #[tauri::command]
 pub async fn load_profiles() -> Result<ProfileCollection, String> {
-    let engine = ProfileEngine::new();
-
-    // Use the new unified config file search logic
-    let profiles_path = find_config_file_path("profiles.json")
-        .ok_or_else(|| "Could not determine profiles.json path".to_string())?;
-
-    let profiles_content = tokio::fs::read_to_string(&profiles_path)
+    let profiles_path = profiles_path()?;
+    ProfileStore::global()
+        .reload(&profiles_path)
         .await
-        .map_err(|e| format!("Failed to read {}: {}", profiles_path.display(), e))?;
-
-    let mut profile_collection = engine
-        .load_profiles_from_json(&profiles_content)
-        .map_err(|e| format!("Failed to parse profiles: {}", e))?;
+        .map_err(|e| e.to_string())
+}
 
-    // Ensure clipboard profile always exists as Profile 1
-    ensure_clipboard_profile(&mut profile_collection.profiles);
+/// Profiles for workflows, served from the cache while profiles.json is unchanged
+pub async fn get_profiles_cached() -> Result<ProfileCollection, String> {
+    let profiles_path = profiles_path()?;
+    ProfileStore::global()
+        .get(&profiles_path)
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    Ok(profile_collection)
+fn profiles_path() -> Result<std::path::PathBuf, String> {
+    // Use the new unified config file search logic
+    find_config_file_path("profiles.json")
+        .ok_or_else(|| "Could not determine profiles.json path".to_string())
 }
 
 /// Select a profile and set it as active
@@ -64,6 +72,9 @@ pub async fn select_profile(
         profile_state.active_profile_id = Some(profile_id.clone());
     }
 
+    // Switch to the profile's pinned input device for the next recording
+    crate::commands::audio::apply_profile_input_device(&app_handle, Some(&profile_id)).await;
+
     // Note: Frontend should listen to app-state-changed events from state machine
     // instead of this direct emission. The following emit is deprecated and will be removed in a future release.
     // app_handle
@@ -92,21 +103,7 @@ pub async fn get_active_profile(
 #[tauri::command]
 pub async fn apply_profile_to_text(profile_id: String, text: String) -> Result<String, String> {
     let engine = ProfileEngine::new();
-
-    // Use the new unified config file search logic
-    let profiles_path = find_config_file_path("profiles.json")
-        .ok_or_else(|| "Could not determine profiles.json path".to_string())?;
-
-    let profiles_content = tokio::fs::read_to_string(&profiles_path)
-        .await
-        .map_err(|e| format!("Failed to read {}: {}", profiles_path.display(), e))?;
-
-    let mut profile_collection = engine
-        .load_profiles_from_json(&profiles_content)
-        .map_err(|e| format!("Failed to parse profiles: {}", e))?;
-
-    // Ensure clipboard profile always exists as Profile 1
-    ensure_clipboard_profile(&mut profile_collection.profiles);
+    let profile_collection = get_profiles_cached().await?;
 
     let profile = engine
         .find_profile_by_id(&profile_collection, &profile_id)
@@ -121,3 +118,82 @@ pub async fn apply_profile_to_text(profile_id: String, text: String) -> Result<S
         .apply_profile(profile, &text)
         .map_err(|e| format!("Failed to apply profile: {}", e))
 }
+
+/// Lint a profile's instructions for `model`, reading its reference file
+pub async fn lint_profile_instructions(profile: &Profile, model: &str) -> PromptLint {
+    let reference = match profile.reference_file.clone() {
+        Some(path) => {
+            tokio::task::spawn_blocking(move || load_reference(std::path::Path::new(&path)))
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|loaded| loaded.map_err(|e| e.to_string()))
+                .map(Some)
+        }
+        None => Ok(None),
+    };
+
+    match reference {
+        Ok(reference) => prompt_lint::lint_profile(profile, model, reference.as_deref()),
+        Err(e) => {
+            let mut lint = prompt_lint::lint_profile(profile, model, None);
+            lint.issues.push(LintIssue {
+                severity: LintSeverity::Warning,
+                message: format!("The reference file is ignored: {}", e),
+            });
+            lint
+        }
+    }
+}
+
+/// Estimate the token size of a profile's instructions and check them for
+/// conflicts (`model` defaults to the formatting model)
+#[tauri::command]
+pub async fn lint_profile(profile: Profile, model: Option<String>) -> Result<PromptLint, String> {
+    let model = model.unwrap_or_else(|| FORMATTING_MODEL.to_string());
+    Ok(lint_profile_instructions(&profile, &model).await)
+}
+
+/// Profile suggested for a transcript, when it differs from the active one
+pub async fn suggest_profile_for_transcript(
+    transcript: &str,
+    active_profile_id: Option<&str>,
+    min_matches: usize,
+) -> Option<(ProfileSuggestion, Profile)> {
+    let collection = match get_profiles_cached().await {
+        Ok(collection) => collection,
+        Err(e) => {
+            eprintln!(
+                "⚠️  Warning: Failed to load profiles for a suggestion: {}",
+                e
+            );
+            return None;
+        }
+    };
+    let suggestion = suggest_profile(transcript, &collection.profiles, min_matches)?;
+    if active_profile_id == Some(suggestion.profile_id.as_str()) {
+        return None;
+    }
+    let profile = collection
+        .profiles
+        .into_iter()
+        .find(|profile| profile.id == suggestion.profile_id)?;
+    Some((suggestion, profile))
+}
+
+/// Profile whose name matches a spoken name (case and punctuation ignored)
+pub async fn find_profile_by_spoken_name(name: &str) -> Option<Profile> {
+    let simplify = |text: &str| {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let spoken = simplify(name);
+    get_profiles_cached()
+        .await
+        .ok()?
+        .profiles
+        .into_iter()
+        .find(|profile| simplify(&profile.name) == spoken)
+}
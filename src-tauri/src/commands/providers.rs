@@ -0,0 +1,40 @@
+//! Capability discovery for the configured providers
+
+use crate::commands::settings::read_settings;
+use crate::services::feature_flags::{self, FeatureFlag};
+use crate::services::gpt_client::FORMATTING_MODEL;
+use crate::services::{ProviderCapabilities, WhisperProvider};
+
+/// Report the features of each configured STT/LLM provider
+///
+/// The frontend uses the reported features to enable options (language
+/// hint, timestamps, streaming, diarization, translation) per provider.
+/// Streaming is only reported while the `streaming_transcription` feature
+/// flag is on.
+#[tauri::command]
+pub async fn get_provider_capabilities() -> Result<serde_json::Value, String> {
+    let settings = read_settings()
+        .await
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+
+    let mut speech_to_text = vec![ProviderCapabilities::speech_to_text(
+        &settings.whisper.endpoint,
+        &settings.whisper.model,
+    )];
+    if settings.local_inference.enabled || settings.whisper.provider == WhisperProvider::Local {
+        speech_to_text.push(ProviderCapabilities::local_speech_to_text(
+            &settings.local_inference.model,
+        ));
+    }
+    if !feature_flags::is_enabled(FeatureFlag::StreamingTranscription) {
+        for provider in &mut speech_to_text {
+            provider.features.streaming = false;
+        }
+    }
+    let llm = vec![ProviderCapabilities::formatting_llm(FORMATTING_MODEL)];
+
+    Ok(serde_json::json!({
+        "stt": speech_to_text,
+        "llm": llm,
+    }))
+}
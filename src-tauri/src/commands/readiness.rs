@@ -0,0 +1,221 @@
+//! Readiness checklist run before the first recording
+//!
+//! Checks in one call everything a recording goes through, so the settings
+//! window can render a readiness panel instead of the user finding out from a
+//! failed workflow.
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::audio::{probe_mic_status, LiveAudioCapture, MicStatus};
+use crate::commands::audio::AudioCaptureState;
+use crate::commands::clipboard::ClipboardServiceState;
+use crate::commands::get_profiles_cached;
+use crate::commands::profiles::ProfileAppState;
+use crate::commands::settings::{read_settings, SettingsConfig};
+use crate::commands::shortcut::ShortcutMgrState;
+use crate::commands::whisper::{test_api_key, WhisperClientState};
+use crate::services::WhisperProvider;
+
+/// Outcome of one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    /// Recording works, with a limitation
+    Warning,
+    /// Recording would fail
+    Failed,
+}
+
+/// One line of the readiness checklist
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessCheck {
+    /// Stable identifier: `api_key`, `transcription`, `profile`, `clipboard`,
+    /// `shortcut` or `microphone`
+    pub id: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+impl ReadinessCheck {
+    fn new(id: &'static str, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            id,
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Readiness checklist
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessReport {
+    /// No check failed
+    pub ready: bool,
+    pub checks: Vec<ReadinessCheck>,
+}
+
+async fn check_api_key(settings: &SettingsConfig) -> ReadinessCheck {
+    if settings.whisper.provider == WhisperProvider::Local {
+        return ReadinessCheck::new(
+            "api_key",
+            CheckStatus::Ok,
+            "Transcribing offline, no API key needed",
+        );
+    }
+    match test_api_key(settings.whisper.api_key.clone()).await {
+        Ok(message) => ReadinessCheck::new("api_key", CheckStatus::Ok, message),
+        Err(e) => ReadinessCheck::new("api_key", CheckStatus::Failed, e),
+    }
+}
+
+async fn check_profile(profile_state: &ProfileAppState) -> ReadinessCheck {
+    let active_profile_id = profile_state.lock().unwrap().active_profile_id.clone();
+    let Some(profile_id) = active_profile_id else {
+        return ReadinessCheck::new(
+            "profile",
+            CheckStatus::Ok,
+            "No profile selected, transcripts are copied as spoken",
+        );
+    };
+    match get_profiles_cached().await {
+        Ok(collection) => match collection.profiles.iter().find(|p| p.id == profile_id) {
+            Some(profile) => ReadinessCheck::new(
+                "profile",
+                CheckStatus::Ok,
+                format!("Profile '{}' selected", profile.name),
+            ),
+            None => ReadinessCheck::new(
+                "profile",
+                CheckStatus::Failed,
+                format!("The selected profile ({}) no longer exists", profile_id),
+            ),
+        },
+        Err(e) => ReadinessCheck::new(
+            "profile",
+            CheckStatus::Failed,
+            format!("Failed to load profiles: {}", e),
+        ),
+    }
+}
+
+async fn check_shortcut(shortcut_state: &ShortcutMgrState) -> ReadinessCheck {
+    let shortcut_guard = shortcut_state.lock().await;
+    match shortcut_guard.as_ref() {
+        Some(mgr) if mgr.is_registered().await => ReadinessCheck::new(
+            "shortcut",
+            CheckStatus::Ok,
+            format!("{} starts a recording", mgr.get_shortcut()),
+        ),
+        Some(mgr) => ReadinessCheck::new(
+            "shortcut",
+            CheckStatus::Warning,
+            format!(
+                "{} is not registered; record from the window instead",
+                mgr.get_shortcut()
+            ),
+        ),
+        None => ReadinessCheck::new(
+            "shortcut",
+            CheckStatus::Warning,
+            "No global shortcut; record from the window instead",
+        ),
+    }
+}
+
+async fn check_microphone(
+    settings: &SettingsConfig,
+    audio_state: &AudioCaptureState,
+) -> ReadinessCheck {
+    if audio_state.lock().await.is_none() {
+        return ReadinessCheck::new(
+            "microphone",
+            CheckStatus::Failed,
+            "Audio capture not initialized",
+        );
+    }
+    let (devices, mic_status) = tokio::task::spawn_blocking(|| {
+        (LiveAudioCapture::list_input_devices(), probe_mic_status())
+    })
+    .await
+    .unwrap_or((Vec::new(), MicStatus::Unknown));
+
+    if devices.is_empty() {
+        return ReadinessCheck::new("microphone", CheckStatus::Failed, "No input device found");
+    }
+    if let Some(device) = settings.audio.input_device.as_ref() {
+        if !devices.contains(device) {
+            return ReadinessCheck::new(
+                "microphone",
+                CheckStatus::Failed,
+                format!("Input device '{}' is not connected", device),
+            );
+        }
+    }
+    match mic_status {
+        MicStatus::Muted => ReadinessCheck::new(
+            "microphone",
+            CheckStatus::Warning,
+            "The microphone is muted",
+        ),
+        _ => ReadinessCheck::new(
+            "microphone",
+            CheckStatus::Ok,
+            settings
+                .audio
+                .input_device
+                .clone()
+                .unwrap_or_else(|| "Default input device".to_string()),
+        ),
+    }
+}
+
+/// Check the API key, transcription client, selected profile, clipboard,
+/// global shortcut and microphone before recording
+///
+/// The checks run regardless of each other; `ready` is false when one of
+/// them failed.
+#[tauri::command]
+pub async fn validate_ready_to_record(
+    whisper_state: State<'_, WhisperClientState>,
+    clipboard_state: State<'_, ClipboardServiceState>,
+    shortcut_state: State<'_, ShortcutMgrState>,
+    audio_state: State<'_, AudioCaptureState>,
+    profile_state: State<'_, ProfileAppState>,
+) -> Result<ReadinessReport, String> {
+    let settings = read_settings().await?;
+    let mut checks = vec![check_api_key(&settings).await];
+
+    checks.push(if whisper_state.lock().await.is_some() {
+        ReadinessCheck::new("transcription", CheckStatus::Ok, "Whisper client ready")
+    } else {
+        ReadinessCheck::new(
+            "transcription",
+            CheckStatus::Failed,
+            "Whisper client not initialized",
+        )
+    });
+    checks.push(check_profile(&profile_state).await);
+    checks.push(if clipboard_state.lock().await.is_some() {
+        ReadinessCheck::new("clipboard", CheckStatus::Ok, "Clipboard available")
+    } else {
+        ReadinessCheck::new(
+            "clipboard",
+            CheckStatus::Failed,
+            "Clipboard service not initialized",
+        )
+    });
+    checks.push(check_shortcut(&shortcut_state).await);
+    checks.push(check_microphone(&settings, &audio_state).await);
+
+    let ready = checks
+        .iter()
+        .all(|check| check.status != CheckStatus::Failed);
+    eprintln!(
+        "🩺 [READINESS] Ready to record: {} ({} checks)",
+        ready,
+        checks.len()
+    );
+    Ok(ReadinessReport { ready, checks })
+}
@@ -0,0 +1,258 @@
+//! Command for re-transcribing the low-confidence segments of a recording, and
+//! per-segment language detection for code-switched recordings
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::audio::{AudioClip, Encoder};
+use crate::commands::app_lock::require_unlocked;
+use crate::commands::encoder::configured_encoder;
+use crate::commands::history::{history_store, last_workflow_entry};
+use crate::commands::settings::read_settings;
+use crate::services::mixed_language::SegmentLanguage;
+use crate::services::retranscription::{
+    low_confidence_segments, slice_range, splice_segments, DEFAULT_MIN_AVG_LOGPROB,
+};
+use crate::services::{
+    OpenAIWhisperClient, SegmentedWhisperClient, TranscriptionResponse, TranscriptionSegment,
+    WhisperClient, WhisperClientConfig, WhisperMode,
+};
+use crate::state::AppStateMachineState;
+
+/// Model used for re-transcription when none is given (more accurate than whisper-1)
+const RETRANSCRIPTION_MODEL: &str = "gpt-4o-transcribe";
+
+/// A segment whose text was re-transcribed
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentReplacement {
+    pub id: u32,
+    pub start: f64,
+    pub end: f64,
+    pub original: String,
+    pub improved: String,
+}
+
+/// Transcript with its low-confidence segments re-transcribed
+#[derive(Debug, Clone, Serialize)]
+pub struct RetranscriptionResult {
+    pub history_id: String,
+    pub transcript: String,
+    pub replaced: Vec<SegmentReplacement>,
+}
+
+/// Re-send only the low-confidence segments of a recording and splice the results back
+///
+/// Works on a history entry (the last workflow by default) whose recording was
+/// archived. Each segment is cut from the recording using its timestamps and
+/// transcribed with `model` (default `gpt-4o-transcribe`), prompted with the
+/// preceding text. The history entry is left untouched; the improved
+/// transcript is returned for the frontend to apply or reformat. Like the
+/// other history commands, it requires the app to be unlocked.
+#[tauri::command]
+pub async fn retranscribe_low_confidence(
+    history_id: Option<String>,
+    model: Option<String>,
+    min_avg_logprob: Option<f64>,
+    state_machine_state: State<'_, AppStateMachineState>,
+) -> Result<RetranscriptionResult, String> {
+    require_unlocked()?;
+    let settings = read_settings().await?;
+    if settings.workflow.dry_run {
+        return Err("Re-transcription is not available in dry run mode".to_string());
+    }
+
+    let store = history_store(&settings.history)?;
+    let entry = match history_id {
+        Some(id) => store
+            .get(&id)
+            .map_err(|e| format!("Failed to load history entry: {}", e))?,
+        None => last_workflow_entry(&store, &state_machine_state).await?,
+    };
+    if entry.segments.is_empty() {
+        return Err("No segment timestamps were recorded for this transcript".to_string());
+    }
+
+    let min_avg_logprob = min_avg_logprob.unwrap_or(DEFAULT_MIN_AVG_LOGPROB);
+    let low_confidence = low_confidence_segments(&entry.segments, min_avg_logprob);
+    if low_confidence.is_empty() {
+        eprintln!(
+            "✅ [RETRANSCRIBE] No low-confidence segments in {}",
+            entry.id
+        );
+        return Ok(RetranscriptionResult {
+            transcript: splice_segments(&entry.segments, &HashMap::new()),
+            history_id: entry.id,
+            replaced: Vec::new(),
+        });
+    }
+    if !entry.has_audio {
+        return Err(
+            "The recording was not archived: enable history.archive_audio to re-transcribe"
+                .to_string(),
+        );
+    }
+
+    let audio = store
+        .read_audio(&entry.id)
+        .map_err(|e| format!("Failed to read recording: {}", e))?;
+    let clip = tokio::task::spawn_blocking(move || AudioClip::from_ogg_bytes(&audio))
+        .await
+        .map_err(|e| format!("Decoding task failed: {}", e))?
+        .map_err(|e| format!("Failed to decode recording: {}", e))?;
+    // The encoder expects mono input
+    let clip = clip.convert(1, clip.sample_rate);
+
+    let client = OpenAIWhisperClient::try_with_config(WhisperClientConfig {
+        api_key: settings.whisper.api_key.clone(),
+        endpoint: settings.whisper.endpoint.clone(),
+        model: model.unwrap_or_else(|| RETRANSCRIPTION_MODEL.to_string()),
+        timeout_seconds: u64::from(settings.whisper.timeout_seconds),
+        min_upload_kbps: settings.whisper.min_upload_kbps,
+        retry: settings.whisper.retry_policy(),
+        network: settings.network.clone(),
+        ..WhisperClientConfig::default()
+    })
+    .map_err(|e| e.to_string())?;
+    let encoder = configured_encoder().await;
+
+    eprintln!(
+        "🔁 [RETRANSCRIBE] Re-transcribing {} of {} segments of {}",
+        low_confidence.len(),
+        entry.segments.len(),
+        entry.id
+    );
+
+    let mut replacements = HashMap::new();
+    let mut replaced = Vec::new();
+    for segment in low_confidence {
+        let (start, end) = slice_range(segment);
+        let wav_path = std::env::temp_dir().join(format!(
+            "dictaclerk_retranscribe_{}_{}.wav",
+            entry.id, segment.id
+        ));
+        clip.slice(start, end)
+            .write_wav(&wav_path)
+            .map_err(|e| format!("Failed to cut segment {}: {}", segment.id, e))?;
+
+        // The text before the segment gives the model its context
+        let prompt = entry
+            .segments
+            .iter()
+            .take_while(|other| other.id != segment.id)
+            .map(|other| other.text.trim())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let prompt = (!prompt.is_empty()).then_some(prompt);
+
+        let result = transcribe_slice(&encoder, &client, wav_path, prompt)
+            .await
+            .map(|response| response.text);
+        match result {
+            Ok(text) if !text.trim().is_empty() => {
+                replaced.push(SegmentReplacement {
+                    id: segment.id,
+                    start: segment.start,
+                    end: segment.end,
+                    original: segment.text.trim().to_string(),
+                    improved: text.trim().to_string(),
+                });
+                replacements.insert(segment.id, text);
+            }
+            Ok(_) => eprintln!(
+                "⚠️  [RETRANSCRIBE] Segment {} came back empty, keeping the original",
+                segment.id
+            ),
+            Err(e) => {
+                return Err(format!(
+                    "Failed to re-transcribe segment {}: {}",
+                    segment.id, e
+                ))
+            }
+        }
+    }
+
+    eprintln!(
+        "✅ [RETRANSCRIBE] Replaced {} segment(s) in {}",
+        replaced.len(),
+        entry.id
+    );
+    Ok(RetranscriptionResult {
+        transcript: splice_segments(&entry.segments, &replacements),
+        history_id: entry.id,
+        replaced,
+    })
+}
+
+/// Transcribe each segment of a recording on its own, detecting its language
+///
+/// Used for code-switched recordings (see [`crate::services::mixed_language`]).
+/// A segment that fails is left out: it keeps the text of the full transcript.
+pub(crate) async fn detect_segment_languages(
+    wav_path: &Path,
+    segments: &[TranscriptionSegment],
+    client: &dyn WhisperClient,
+) -> Result<Vec<SegmentLanguage>, String> {
+    let audio = tokio::fs::read(wav_path)
+        .await
+        .map_err(|e| format!("Failed to read recording: {}", e))?;
+    let clip = tokio::task::spawn_blocking(move || AudioClip::from_wav_bytes(&audio))
+        .await
+        .map_err(|e| format!("Decoding task failed: {}", e))?
+        .map_err(|e| format!("Failed to decode recording: {}", e))?;
+    let clip = clip.convert(1, clip.sample_rate);
+
+    // Transcribed, never translated, with the language left to detection
+    let client = SegmentedWhisperClient::new(client).with_mode(Some(WhisperMode::Transcribe));
+    let encoder = configured_encoder().await;
+    let recording = wav_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut detected = Vec::new();
+    for segment in segments {
+        let (start, end) = slice_range(segment);
+        let slice_path = std::env::temp_dir().join(format!(
+            "dictaclerk_language_{}_{}.wav",
+            recording, segment.id
+        ));
+        if let Err(e) = clip.slice(start, end).write_wav(&slice_path) {
+            eprintln!(
+                "⚠️  [MIXED LANGUAGE] Failed to cut segment {}: {}",
+                segment.id, e
+            );
+            continue;
+        }
+        match transcribe_slice(&encoder, &client, slice_path, None).await {
+            Ok(response) => detected.push(SegmentLanguage {
+                id: segment.id,
+                language: response.language,
+                text: response.text,
+            }),
+            Err(e) => eprintln!(
+                "⚠️  [MIXED LANGUAGE] Segment {} kept as transcribed: {}",
+                segment.id, e
+            ),
+        }
+    }
+    Ok(detected)
+}
+
+/// Encode a cut segment and transcribe it, removing the temporary files
+async fn transcribe_slice(
+    encoder: &impl Encoder,
+    client: &dyn WhisperClient,
+    wav_path: PathBuf,
+    prompt: Option<String>,
+) -> Result<TranscriptionResponse, String> {
+    let encoded = encoder.encode(&wav_path, None, None).await;
+    let _ = tokio::fs::remove_file(&wav_path).await;
+    let ogg_info = encoded.map_err(|e| format!("Encoding failed: {}", e))?;
+
+    let transcript = client.transcribe(&ogg_info.path, prompt).await;
+    let _ = tokio::fs::remove_file(&ogg_info.path).await;
+    transcript.map_err(|e| format!("Transcription failed: {}", e))
+}
@@ -1,13 +1,44 @@
 use serde::{Deserialize, Serialize};
 use std::io::{ErrorKind, Write};
 use std::path::{Path, PathBuf};
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, State};
 use tempfile::NamedTempFile;
 
+use crate::audio::loudness::DEFAULT_TARGET_LUFS;
+use crate::audio::{CaptureSource, EncoderApplication, EncoderFormat, EncoderOptions};
+use crate::commands::app_lock::require_unlocked;
+use crate::commands::profiles::lint_profile_instructions;
 use crate::commands::ShortcutMgrState;
-use crate::services::notifier::{Notifier, TauriNotifierService};
-use crate::services::profile_engine::{ProfileCollection, ProfileEngine};
-use crate::utils::{ensure_config_directory, find_config_file_path};
+use crate::paths::{ensure_config_dir, find_config_file_path};
+use crate::services::api_limiter::{ApiLimiter, ApiLimits};
+use crate::services::bookmarks::BookmarkMode;
+use crate::services::chapters::ChapterMode;
+use crate::services::continuous_session::SESSIONS_DIR;
+use crate::services::event_targets;
+use crate::services::feature_flags::{FeatureFlags, FeatureRegistry};
+use crate::services::gpt_client::FORMATTING_MODEL;
+use crate::services::local_inference::InferenceDevice;
+use crate::services::network::NetworkConfig;
+use crate::services::notifier::{
+    set_notification_routing, NotificationRouting, Notifier, TauriNotifierService,
+};
+use crate::services::output_files::{FilenameTemplate, DEFAULT_FILENAME_TEMPLATE};
+use crate::services::profile_engine::{
+    migrate_profile_kinds, ProfileBehavior, ProfileCollection, ProfileEngine, ProfileKind,
+};
+use crate::services::profile_store::ProfileStore;
+use crate::services::profile_suggestion::SuggestionMode;
+use crate::services::profile_sync::{ProfileSyncBackend, SharedProfiles, GIT_WORKTREE_DIR};
+use crate::services::prompt_lint::LintSeverity;
+use crate::services::recording_archive::{ArchiveFormat, RECORDINGS_DIR};
+use crate::services::resource_guard::ResourceGuardConfig;
+use crate::services::retry_policy::RetryPolicy;
+use crate::services::transcript_format::TimestampMode;
+use crate::services::tts::{TtsOptions, TtsProvider};
+use crate::services::weekly_report::{ReportFormat, REPORTS_DIR};
+use crate::services::whisper_client::{
+    PromptStrategy, WhisperMode, WhisperProvider, WhisperResponseFormat, DEFAULT_MIN_UPLOAD_KBPS,
+};
 
 /// Settings configuration structure matching settings.json
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +47,62 @@ pub struct SettingsConfig {
     pub audio: AudioSettings,
     pub encoding: EncodingSettings,
     pub ui: UiSettings,
+    #[serde(default)]
+    pub formatting: FormattingSettings,
+    #[serde(default)]
+    pub clipboard: ClipboardSettings,
+    #[serde(default)]
+    pub workflow: WorkflowSettings,
+    #[serde(default)]
+    pub local_api: LocalApiSettings,
+    #[serde(default)]
+    pub live_captions: LiveCaptionSettings,
+    #[serde(default)]
+    pub interview: InterviewSettings,
+    #[serde(default)]
+    pub local_inference: LocalInferenceSettings,
+    #[serde(default)]
+    pub output: OutputSettings,
+    #[serde(default)]
+    pub history: HistorySettings,
+    #[serde(default)]
+    pub recording_archive: RecordingArchiveSettings,
+    #[serde(default)]
+    pub tts: TtsSettings,
+    #[serde(default)]
+    pub context_memory: ContextMemorySettings,
+    #[serde(default)]
+    pub profile_suggestion: ProfileSuggestionSettings,
+    #[serde(default)]
+    pub profile_sync: ProfileSyncSettings,
+    #[serde(default)]
+    pub voice_commands: VoiceCommandSettings,
+    #[serde(default)]
+    pub input_integration: InputIntegrationSettings,
+    #[serde(default)]
+    pub goals: GoalSettings,
+    /// Experimental subsystems, off unless turned on
+    #[serde(default)]
+    pub features: FeatureFlags,
+    /// Caps on the Whisper/GPT requests in flight and per minute
+    #[serde(default)]
+    pub api_limits: ApiLimits,
+    /// Channel (toast, system, sound, none) of each notification level
+    #[serde(default)]
+    pub notifications: NotificationRouting,
+    #[serde(default)]
+    pub continuous: ContinuousSettings,
+    #[serde(default)]
+    pub bookmarks: BookmarkSettings,
+    #[serde(default)]
+    pub long_recordings: LongRecordingSettings,
+    #[serde(default)]
+    pub weekly_report: WeeklyReportSettings,
+    /// Proxy and extra CA certificates of the Whisper/GPT clients
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub transcription_queue: TranscriptionQueueSettings,
     pub global_shortcut: String,
 }
 
@@ -26,6 +113,60 @@ pub struct WhisperSettings {
     pub model: String,
     pub timeout_seconds: u32,
     pub max_retries: u32,
+    /// Ask for word timings along with the segments (subtitles, click-to-seek)
+    #[serde(default)]
+    pub word_timestamps: bool,
+    /// Transcribe, or translate to English (profiles can override this)
+    #[serde(default)]
+    pub mode: WhisperMode,
+    /// Transcribe with the API, or offline with whisper.cpp (`local_inference`)
+    #[serde(default)]
+    pub provider: WhisperProvider,
+    /// Sampling temperature, 0.0-1.0 (unset = the API's choice)
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    /// `verbose_json`, `json` or `text`; only `verbose_json` returns segments
+    /// and word timings
+    #[serde(default)]
+    pub response_format: WhisperResponseFormat,
+    /// Send the whole prompt, only its vocabulary, or none
+    #[serde(default)]
+    pub prompt_strategy: PromptStrategy,
+    /// Slowest upload the request timeout allows for, in kbit/s: the upload
+    /// time of the file is added to `timeout_seconds` (0 = fixed timeout)
+    #[serde(default = "default_min_upload_kbps")]
+    pub min_upload_kbps: u32,
+    /// Delay before the first retry in ms, doubled at each retry
+    #[serde(default = "default_retry_base_delay_ms")]
+    pub retry_base_delay_ms: u64,
+    /// Longest delay between two attempts in ms; a rate limit asking to wait
+    /// longer fails right away
+    #[serde(default = "default_retry_max_delay_ms")]
+    pub retry_max_delay_ms: u64,
+}
+
+fn default_min_upload_kbps() -> u32 {
+    DEFAULT_MIN_UPLOAD_KBPS
+}
+
+fn default_retry_base_delay_ms() -> u64 {
+    RetryPolicy::default().base_delay_ms
+}
+
+fn default_retry_max_delay_ms() -> u64 {
+    RetryPolicy::default().max_delay_ms
+}
+
+impl WhisperSettings {
+    /// Retry policy of the transcription requests
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: self.max_retries,
+            base_delay_ms: self.retry_base_delay_ms,
+            max_delay_ms: self.retry_max_delay_ms,
+            ..RetryPolicy::default()
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,18 +174,697 @@ pub struct AudioSettings {
     pub input_device: Option<String>,
     pub sample_rate: u32,
     pub buffer_size: u32,
+    /// Input gain in dB applied while recording
+    #[serde(default)]
+    pub gain_db: f32,
+    /// Adjust the input gain automatically (AGC) instead of using `gain_db`
+    #[serde(default)]
+    pub auto_gain: bool,
+    /// Pause the playing media players while recording, resume them afterwards
+    #[serde(default)]
+    pub pause_media: bool,
+    /// Stop the recording after this many seconds of silence (0 = never)
+    #[serde(default)]
+    pub auto_stop_silence_seconds: u32,
+    /// Stop and process the recording once it is this long (0 = no limit)
+    #[serde(default)]
+    pub max_recording_seconds: u32,
+    /// Seconds of audio kept from before the recording starts (0 = off,
+    /// otherwise 0.5-2); the microphone stays open while idle
+    #[serde(default)]
+    pub pre_roll_seconds: f32,
+    /// Record the microphone, the system output (loopback) or both mixed;
+    /// the loopback device is `interview.loopback_device`
+    #[serde(default)]
+    pub capture_source: CaptureSource,
+    /// Run the noise suppressor on recordings before encoding
+    #[serde(default)]
+    pub noise_suppression: bool,
+    /// Normalize each recording to `target_lufs` once it stops
+    #[serde(default)]
+    pub loudness_normalization: bool,
+    /// Integrated loudness recordings are normalized to (LUFS)
+    #[serde(default = "default_target_lufs")]
+    pub target_lufs: f32,
+}
+
+fn default_target_lufs() -> f32 {
+    DEFAULT_TARGET_LUFS
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EncodingSettings {
     pub bitrate: u32,
     pub size_limit_mb: u32,
+    /// Encoder complexity (0-10): lower values save CPU on low-power machines
+    #[serde(default = "default_encoder_complexity")]
+    pub complexity: u8,
+    /// "voip" (speech, 16kHz) or "audio" (full bandwidth)
+    #[serde(default)]
+    pub application: EncoderApplication,
+    /// Encode near-silent passages as silence
+    #[serde(default)]
+    pub dtx: bool,
+    /// Average multi-channel input into mono (about half the upload size)
+    #[serde(default = "default_downmix_to_mono")]
+    pub downmix_to_mono: bool,
+    /// Upload format: "ogg", "mp3" or "flac" (lossless, larger)
+    #[serde(default)]
+    pub format: EncoderFormat,
+    /// Variable bitrate: smaller speech files; off caps the bitrate at `bitrate`
+    #[serde(default = "default_vbr")]
+    pub vbr: bool,
+}
+
+fn default_encoder_complexity() -> u8 {
+    EncoderOptions::default().complexity
+}
+
+fn default_downmix_to_mono() -> bool {
+    EncoderOptions::default().downmix_to_mono
+}
+
+fn default_vbr() -> bool {
+    EncoderOptions::default().vbr
+}
+
+impl EncodingSettings {
+    /// Encoder options configured by these settings (noise suppression is
+    /// `audio.noise_suppression`, off here)
+    pub fn encoder_options(&self) -> EncoderOptions {
+        EncoderOptions {
+            complexity: self.complexity,
+            application: self.application,
+            dtx: self.dtx,
+            noise_suppression: false,
+            downmix_to_mono: self.downmix_to_mono,
+            format: self.format,
+            vbr: self.vbr,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UiSettings {
     pub theme: String,
     pub auto_start_recording: bool,
+    /// Run from the tray only: the main window is never created, results go
+    /// to the clipboard and feedback to notifications (applied at launch)
+    #[serde(default)]
+    pub tray_only: bool,
+}
+
+/// Global switch and retries of the GPT formatting stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormattingSettings {
+    /// When false, transcripts skip GPT formatting regardless of the active profile
+    pub enabled: bool,
+    /// Retries of server errors, network errors and rate limits
+    #[serde(default = "default_retries")]
+    pub max_retries: u32,
+    /// Delay before the first retry in ms, doubled at each retry
+    #[serde(default = "default_retry_base_delay_ms")]
+    pub retry_base_delay_ms: u64,
+    /// Longest delay between two attempts in ms; a rate limit asking to wait
+    /// longer fails right away
+    #[serde(default = "default_retry_max_delay_ms")]
+    pub retry_max_delay_ms: u64,
+}
+
+fn default_retries() -> u32 {
+    RetryPolicy::default().max_retries
+}
+
+impl Default for FormattingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_retries: default_retries(),
+            retry_base_delay_ms: default_retry_base_delay_ms(),
+            retry_max_delay_ms: default_retry_max_delay_ms(),
+        }
+    }
+}
+
+impl FormattingSettings {
+    /// Retry policy of the formatting requests
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: self.max_retries,
+            base_delay_ms: self.retry_base_delay_ms,
+            max_delay_ms: self.retry_max_delay_ms,
+            ..RetryPolicy::default()
+        }
+    }
+}
+
+/// Global switch for the clipboard stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardSettings {
+    /// When false, the final text is kept for review but not copied automatically
+    pub auto_copy: bool,
+    /// Transcripts whose confidence (0 to 1) is below this are kept for review
+    /// with a warning instead of copied (0 = always copy)
+    #[serde(default)]
+    pub min_confidence: f64,
+}
+
+impl Default for ClipboardSettings {
+    fn default() -> Self {
+        Self {
+            auto_copy: true,
+            min_confidence: 0.0,
+        }
+    }
+}
+
+/// Options for the processing workflow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkflowSettings {
+    /// Capture and encode real audio but mock API calls and leave the clipboard untouched
+    pub dry_run: bool,
+    /// Number of manual retries allowed for each failed workflow
+    pub retry_budget: u32,
+    /// Hold a recording repeating the previous one within this many seconds
+    /// instead of transcribing it (0 = off)
+    pub duplicate_window_seconds: u32,
+    /// Hold each recording until the user confirms its transcription
+    /// (`confirm_pending_recording`), so it can be listened to first
+    pub confirm_before_transcription: bool,
+}
+
+impl Default for WorkflowSettings {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            retry_budget: 3,
+            duplicate_window_seconds: 30,
+            confirm_before_transcription: false,
+        }
+    }
+}
+
+/// Optional local HTTP API (loopback only)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocalApiSettings {
+    /// Serve the local API (e.g. `/metrics`) on 127.0.0.1
+    pub enabled: bool,
+    /// TCP port of the local API
+    pub port: u16,
+    /// Also listen on the LAN so paired phones can use the remote control
+    pub lan_pairing: bool,
+}
+
+impl Default for LocalApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8765,
+            lan_pairing: false,
+        }
+    }
+}
+
+/// Live caption file output for streamers (e.g. OBS text sources)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LiveCaptionSettings {
+    /// Write partial transcripts to a text file while recording
+    pub enabled: bool,
+    /// Caption file path (None = live_captions.txt in the config directory)
+    pub output_path: Option<String>,
+    /// Seconds between caption refreshes
+    pub cadence_seconds: u32,
+    /// Seconds of recent audio transcribed for each caption
+    pub window_seconds: u32,
+}
+
+impl Default for LiveCaptionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            output_path: None,
+            cadence_seconds: 3,
+            window_seconds: 10,
+        }
+    }
+}
+
+/// Dual-source interview recording (microphone + loopback)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InterviewSettings {
+    /// Loopback device recorded as the remote party, also used by the
+    /// `system` and `mixed` capture sources (None = auto-detect)
+    pub loopback_device: Option<String>,
+}
+
+/// Local Whisper/LLM backends and the resources they may use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocalInferenceSettings {
+    /// Run inference locally instead of with the cloud provider
+    pub enabled: bool,
+    /// Local jobs allowed to run at the same time
+    pub max_concurrent_jobs: usize,
+    /// Free memory required to start a local job (MB)
+    pub min_free_memory_mb: u64,
+    /// Highest load average per CPU core at which local jobs start
+    pub max_load_per_core: f64,
+    /// Compute device for local backends (falls back to the CPU when unavailable)
+    pub device: InferenceDevice,
+    /// CUDA GPU used when several are installed
+    pub gpu_index: u32,
+    /// Local Whisper model (see `list_local_models`)
+    pub model: String,
+    /// GGML model file used instead of the downloaded `model`
+    pub model_path: Option<String>,
+    /// whisper.cpp command-line tool (None = `whisper-cli` on the PATH)
+    pub whisper_binary: Option<String>,
+}
+
+impl Default for LocalInferenceSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_concurrent_jobs: 1,
+            min_free_memory_mb: 2048,
+            max_load_per_core: 0.85,
+            device: InferenceDevice::Cpu,
+            gpu_index: 0,
+            model: "base".to_string(),
+            model_path: None,
+            whisper_binary: None,
+        }
+    }
+}
+
+impl LocalInferenceSettings {
+    /// Resource guard limits for these settings
+    pub fn guard_config(&self) -> ResourceGuardConfig {
+        ResourceGuardConfig {
+            max_concurrent_jobs: self.max_concurrent_jobs,
+            min_free_memory_mb: self.min_free_memory_mb,
+            max_load_per_core: self.max_load_per_core,
+        }
+    }
+}
+
+/// Saving final texts to files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputSettings {
+    /// Save each final text to a file in `directory`
+    pub save_to_file: bool,
+    /// Output directory (None = "outputs" in the data directory)
+    pub directory: Option<String>,
+    /// File name template, e.g. `{date}_{profile}_{title}.md`
+    pub filename_template: String,
+    /// Timestamps in the transcript (profiles can override this)
+    pub timestamps: TimestampMode,
+    /// Split transcripts that skip GPT formatting into paragraphs
+    pub paragraphize: bool,
+    /// Restore punctuation and casing of unpunctuated transcripts that skip GPT formatting
+    pub restore_punctuation: bool,
+    /// Split long final texts into titled chapters with a table of contents
+    pub chapters: ChapterMode,
+    /// Words a final text has at least to be split into chapters
+    pub chapters_min_words: u32,
+}
+
+impl Default for OutputSettings {
+    fn default() -> Self {
+        Self {
+            save_to_file: false,
+            directory: None,
+            filename_template: DEFAULT_FILENAME_TEMPLATE.to_string(),
+            timestamps: TimestampMode::Off,
+            paragraphize: true,
+            restore_punctuation: true,
+            chapters: ChapterMode::Off,
+            chapters_min_words: 800,
+        }
+    }
+}
+
+impl OutputSettings {
+    /// Directory where output files are written
+    pub fn output_dir(&self) -> PathBuf {
+        match &self.directory {
+            Some(dir) => PathBuf::from(dir),
+            None => crate::paths::data_dir().join(crate::paths::OUTPUTS_DIR),
+        }
+    }
+}
+
+/// Clipboard history, audio archive and their retention
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistorySettings {
+    /// Keep the final text of each workflow in the history
+    pub enabled: bool,
+    /// Also keep the encoded recording of each workflow (needed to replay it)
+    pub archive_audio: bool,
+    /// Days the history and archived recordings are kept (0 = forever)
+    pub retention_days: u32,
+    /// Encrypt the history and archive with a key from the OS keyring
+    pub encrypt_at_rest: bool,
+}
+
+impl Default for HistorySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            archive_audio: false,
+            retention_days: 30,
+            encrypt_at_rest: false,
+        }
+    }
+}
+
+/// Copies of the recordings kept after their workflow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordingArchiveSettings {
+    /// Keep a copy of each recording instead of only deleting the temporary WAV
+    pub enabled: bool,
+    /// Archive directory (None = "recordings" in the data directory)
+    pub directory: Option<String>,
+    /// Newest recordings kept, older ones are deleted (0 = keep all)
+    pub retention_count: u32,
+    /// "ogg", "mp3", "flac" (encoded) or "wav" (original recording)
+    pub format: ArchiveFormat,
+}
+
+impl Default for RecordingArchiveSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: None,
+            retention_count: 50,
+            format: ArchiveFormat::Ogg,
+        }
+    }
+}
+
+impl RecordingArchiveSettings {
+    /// Directory where recordings are archived
+    pub fn archive_dir(&self) -> PathBuf {
+        match &self.directory {
+            Some(dir) => PathBuf::from(dir),
+            None => crate::paths::data_dir().join(RECORDINGS_DIR),
+        }
+    }
+}
+
+/// Continuous recording in rolling chunks (lectures)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContinuousSettings {
+    /// Minutes of audio transcribed at a time (1-30)
+    pub chunk_minutes: u32,
+    /// Directory of the session transcripts (None = "sessions" in the data
+    /// directory)
+    pub directory: Option<String>,
+    /// Keep the audio of each chunk next to the transcript
+    pub keep_audio: bool,
+    /// Disk space the session directory may take, in MB (0 = no limit)
+    pub disk_budget_mb: u32,
+}
+
+impl Default for ContinuousSettings {
+    fn default() -> Self {
+        Self {
+            chunk_minutes: 5,
+            directory: None,
+            keep_audio: false,
+            disk_budget_mb: 500,
+        }
+    }
+}
+
+impl ContinuousSettings {
+    /// Directory where session transcripts are written
+    pub fn session_dir(&self) -> PathBuf {
+        match &self.directory {
+            Some(dir) => PathBuf::from(dir),
+            None => crate::paths::data_dir().join(SESSIONS_DIR),
+        }
+    }
+}
+
+/// Bookmarks placed while recording
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BookmarkSettings {
+    /// Global shortcut bookmarking the recording (None = command only)
+    pub shortcut: Option<String>,
+    /// `[MARK]` annotations or sections
+    pub mode: BookmarkMode,
+}
+
+/// Very long recordings transcribed in chunks sent concurrently
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LongRecordingSettings {
+    /// Recordings longer than this are transcribed in chunks (0 = never)
+    pub threshold_minutes: u32,
+    /// Length of each chunk (consecutive chunks share a few seconds)
+    pub chunk_minutes: u32,
+    /// Chunks transcribed at the same time
+    pub max_parallel: u32,
+}
+
+impl Default for LongRecordingSettings {
+    fn default() -> Self {
+        Self {
+            threshold_minutes: 20,
+            chunk_minutes: 5,
+            max_parallel: 3,
+        }
+    }
+}
+
+/// Weekly summary of the dictations, compiled from the history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeeklyReportSettings {
+    /// Compile last week's report on schedule (`generate_weekly_report` works regardless)
+    pub enabled: bool,
+    /// "markdown" or "html"
+    pub format: ReportFormat,
+    /// Report directory (None = "reports" in the data directory)
+    pub directory: Option<String>,
+    /// Webhook the report is also POSTed to
+    pub webhook_url: Option<String>,
+    /// Notable transcripts quoted in the report
+    pub highlights: u32,
+}
+
+impl Default for WeeklyReportSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            format: ReportFormat::Markdown,
+            directory: None,
+            webhook_url: None,
+            highlights: 3,
+        }
+    }
+}
+
+impl WeeklyReportSettings {
+    /// Directory where reports are saved
+    pub fn report_dir(&self) -> PathBuf {
+        match &self.directory {
+            Some(dir) => PathBuf::from(dir),
+            None => crate::paths::data_dir().join(REPORTS_DIR),
+        }
+    }
+}
+
+/// Background transcription of imported recordings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranscriptionQueueSettings {
+    /// Queued recordings transcribed at the same time (1-8)
+    pub max_concurrent_jobs: u32,
+}
+
+impl Default for TranscriptionQueueSettings {
+    fn default() -> Self {
+        Self {
+            max_concurrent_jobs: 2,
+        }
+    }
+}
+
+/// Reading the final text aloud
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TtsSettings {
+    /// "system" (OS speech synthesizer) or "openai"
+    pub provider: TtsProvider,
+    /// Voice name (None = backend default)
+    pub voice: Option<String>,
+    /// Speaking rate multiplier (1.0 = normal)
+    pub speed: f32,
+}
+
+impl Default for TtsSettings {
+    fn default() -> Self {
+        Self {
+            provider: TtsProvider::System,
+            voice: None,
+            speed: 1.0,
+        }
+    }
+}
+
+/// Recent final texts of the session given to GPT as context
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContextMemorySettings {
+    /// Include the last final texts of the session in the GPT prompt
+    pub enabled: bool,
+    /// Number of final texts remembered
+    pub max_entries: usize,
+    /// Maximum size of the context added to the prompt, in (estimated) tokens
+    pub token_budget: usize,
+}
+
+impl Default for ContextMemorySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entries: 5,
+            token_budget: 300,
+        }
+    }
+}
+
+/// Suggesting a profile from the transcript content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileSuggestionSettings {
+    /// "off", "suggest" (emit `profile-suggestion`) or "auto" (apply it)
+    pub mode: SuggestionMode,
+    /// Keyword matches needed before a profile is suggested
+    pub min_matches: usize,
+}
+
+impl Default for ProfileSuggestionSettings {
+    fn default() -> Self {
+        Self {
+            mode: SuggestionMode::Off,
+            min_matches: 2,
+        }
+    }
+}
+
+/// Sharing the profiles with a team through a folder or a git remote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileSyncSettings {
+    /// Sync the profiles on schedule (`sync_profiles_now` works regardless)
+    pub enabled: bool,
+    /// "folder" (shared directory) or "git" (git remote)
+    pub backend: ProfileSyncBackend,
+    /// Shared directory holding profiles.json (folder backend)
+    pub folder: Option<String>,
+    /// Remote URL of the repository holding profiles.json (git backend)
+    pub git_remote: Option<String>,
+    /// Branch of the repository (git backend)
+    pub git_branch: String,
+    /// Minutes between scheduled syncs (0 = only on demand)
+    pub interval_minutes: u32,
+}
+
+impl Default for ProfileSyncSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: ProfileSyncBackend::Folder,
+            folder: None,
+            git_remote: None,
+            git_branch: "main".to_string(),
+            interval_minutes: 15,
+        }
+    }
+}
+
+impl ProfileSyncSettings {
+    /// Shared location of the profiles, None until it is configured
+    pub fn shared_profiles(&self) -> Option<SharedProfiles> {
+        match self.backend {
+            ProfileSyncBackend::Folder => self
+                .folder
+                .as_ref()
+                .filter(|folder| !folder.trim().is_empty())
+                .map(|folder| SharedProfiles::Folder(PathBuf::from(folder))),
+            ProfileSyncBackend::Git => self
+                .git_remote
+                .as_ref()
+                .filter(|remote| !remote.trim().is_empty())
+                .map(|remote| SharedProfiles::Git {
+                    remote: remote.clone(),
+                    branch: self.git_branch.clone(),
+                    worktree: crate::paths::data_dir().join(GIT_WORKTREE_DIR),
+                }),
+        }
+    }
+}
+
+/// Hands-free control with spoken commands
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VoiceCommandSettings {
+    /// Act on "use <name> profile", "cancel", "scratch that" and "stop recording"
+    /// (commands while recording need live captions)
+    pub enabled: bool,
+}
+
+/// Recording control from hardware buttons
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputIntegrationSettings {
+    /// Toggle recording with the media play/pause keys (Bluetooth headset
+    /// buttons) while the main window is hidden; media players lose the keys
+    pub media_key_toggle: bool,
+}
+
+/// Word count goals (progress is computed from the history)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GoalSettings {
+    /// Emit `goal-progress` after each workflow
+    pub enabled: bool,
+    /// Words to dictate per day
+    pub daily_words: u32,
+    /// Words to dictate per session, i.e. since the app started (0 = no session goal)
+    pub session_words: u32,
+}
+
+impl Default for GoalSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            daily_words: 500,
+            session_words: 0,
+        }
+    }
+}
+
+impl TtsSettings {
+    /// Voice and rate options configured by these settings
+    pub fn options(&self) -> TtsOptions {
+        TtsOptions {
+            voice: self.voice.clone(),
+            speed: self.speed,
+        }
+    }
 }
 
 /// Custom error type for persistence operations
@@ -92,7 +912,7 @@ impl PersistenceError {
 /// Create default configuration files if they don't exist
 pub async fn ensure_default_configs() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Try to create config directory first
-    let config_dir = match ensure_config_directory() {
+    let config_dir = match ensure_config_dir() {
         Ok(dir) => dir,
         Err(e) => {
             eprintln!("⚠️  Could not create OS config directory: {}", e);
@@ -111,20 +931,72 @@ pub async fn ensure_default_configs() -> Result<(), Box<dyn std::error::Error +
                 model: "whisper-1".to_string(),
                 timeout_seconds: 30,
                 max_retries: 3,
+                word_timestamps: false,
+                mode: WhisperMode::Transcribe,
+                provider: WhisperProvider::OpenAi,
+                temperature: None,
+                response_format: WhisperResponseFormat::VerboseJson,
+                prompt_strategy: PromptStrategy::Full,
+                min_upload_kbps: DEFAULT_MIN_UPLOAD_KBPS,
+                retry_base_delay_ms: default_retry_base_delay_ms(),
+                retry_max_delay_ms: default_retry_max_delay_ms(),
             },
             audio: AudioSettings {
                 input_device: None,
                 sample_rate: 44100,
                 buffer_size: 1024,
+                gain_db: 0.0,
+                auto_gain: false,
+                pause_media: false,
+                auto_stop_silence_seconds: 0,
+                max_recording_seconds: 0,
+                pre_roll_seconds: 0.0,
+                capture_source: CaptureSource::Microphone,
+                noise_suppression: false,
+                loudness_normalization: false,
+                target_lufs: DEFAULT_TARGET_LUFS,
             },
             encoding: EncodingSettings {
                 bitrate: 32000,
                 size_limit_mb: 23,
+                complexity: 10,
+                application: EncoderApplication::Audio,
+                dtx: false,
+                downmix_to_mono: true,
+                format: EncoderFormat::Ogg,
+                vbr: true,
             },
             ui: UiSettings {
                 theme: "auto".to_string(),
                 auto_start_recording: false,
+                tray_only: false,
             },
+            formatting: FormattingSettings::default(),
+            clipboard: ClipboardSettings::default(),
+            workflow: WorkflowSettings::default(),
+            local_api: LocalApiSettings::default(),
+            live_captions: LiveCaptionSettings::default(),
+            interview: InterviewSettings::default(),
+            local_inference: LocalInferenceSettings::default(),
+            output: OutputSettings::default(),
+            history: HistorySettings::default(),
+            recording_archive: RecordingArchiveSettings::default(),
+            tts: TtsSettings::default(),
+            context_memory: ContextMemorySettings::default(),
+            profile_suggestion: ProfileSuggestionSettings::default(),
+            profile_sync: ProfileSyncSettings::default(),
+            voice_commands: VoiceCommandSettings::default(),
+            input_integration: InputIntegrationSettings::default(),
+            goals: GoalSettings::default(),
+            features: FeatureFlags::default(),
+            api_limits: ApiLimits::default(),
+            notifications: NotificationRouting::default(),
+            continuous: ContinuousSettings::default(),
+            bookmarks: BookmarkSettings::default(),
+            long_recordings: LongRecordingSettings::default(),
+            weekly_report: WeeklyReportSettings::default(),
+            network: NetworkConfig::default(),
+            transcription_queue: TranscriptionQueueSettings::default(),
             global_shortcut: "Ctrl+Shift+F9".to_string(),
         };
 
@@ -146,6 +1018,7 @@ pub async fn ensure_default_configs() -> Result<(), Box<dyn std::error::Error +
                 crate::services::profile_engine::Profile {
                     id: "1".to_string(),
                     name: "Clipboard".to_string(),
+                    kind: Some(ProfileKind::Clipboard),
                     description: Some("Copy transcription directly to clipboard without formatting".to_string()),
                     prompt: None, // Important: clipboard profile must have no prompt
                     example_input: None,
@@ -153,12 +1026,21 @@ pub async fn ensure_default_configs() -> Result<(), Box<dyn std::error::Error +
                     active: false,
                     visible: Some(true),
                     shortcut: None,
+                    input_device: None,
+                    timestamps: None,
+                    whisper_mode: None,
+                    mixed_language: None,
+                    history_retention_hours: None,
+                    reference_file: None,
+                    keywords: None,
+                    locked: None,
                     created_at: "2025-01-01T00:00:00Z".to_string(),
                     updated_at: "2025-01-01T00:00:00Z".to_string(),
                 },
                 crate::services::profile_engine::Profile {
                     id: "concise".to_string(),
                     name: "Concise Messages".to_string(),
+                    kind: Some(ProfileKind::Formatting),
                     description: Some("Make messages more concise and avoid repetitions".to_string()),
                     prompt: Some("Make this text more concise and remove unnecessary repetitions while preserving the core meaning".to_string()),
                     example_input: Some("I think that maybe we should probably consider the possibility of potentially implementing this feature".to_string()),
@@ -166,6 +1048,14 @@ pub async fn ensure_default_configs() -> Result<(), Box<dyn std::error::Error +
                     active: true,
                     visible: Some(true),
                     shortcut: Some("Ctrl+Alt+C".to_string()),
+                    input_device: None,
+                    timestamps: None,
+                    whisper_mode: None,
+                    mixed_language: None,
+                    history_retention_hours: None,
+                    reference_file: None,
+                    keywords: None,
+                    locked: None,
                     created_at: "2025-01-01T00:00:00Z".to_string(),
                     updated_at: "2025-01-01T00:00:00Z".to_string(),
                 },
@@ -282,9 +1172,15 @@ pub async fn restore_from_backup(
     Ok(())
 }
 
-/// Load settings from settings.json file
+/// Load settings from settings.json file (the app must be unlocked)
 #[tauri::command]
 pub async fn load_settings() -> Result<SettingsConfig, String> {
+    require_unlocked()?;
+    read_settings().await
+}
+
+/// Read settings.json for the backend's own use, locked app or not
+pub async fn read_settings() -> Result<SettingsConfig, String> {
     // Use the new unified config file search logic
     let settings_path = find_config_file_path("settings.json")
         .ok_or_else(|| "Could not determine settings.json path".to_string())?;
@@ -297,12 +1193,22 @@ pub async fn load_settings() -> Result<SettingsConfig, String> {
         .map_err(|e| format!("Failed to parse settings.json: {}", e))
 }
 
-/// Save settings to settings.json
+/// Save settings to settings.json (the app must be unlocked)
 #[tauri::command]
 pub async fn save_settings(settings: SettingsConfig) -> Result<String, String> {
+    require_unlocked()?;
     // Normalize the global shortcut before saving
     let mut normalized_settings = settings;
     normalized_settings.global_shortcut = normalize_shortcut(&normalized_settings.global_shortcut);
+    normalized_settings.bookmarks.shortcut = normalized_settings
+        .bookmarks
+        .shortcut
+        .as_deref()
+        .map(normalize_shortcut);
+
+    // Reject invalid templates now rather than when a workflow writes its output
+    FilenameTemplate::parse(&normalized_settings.output.filename_template)
+        .map_err(|e| format!("Invalid output filename template: {}", e))?;
 
     let settings_path = find_config_file_path("settings.json")
         .ok_or_else(|| "Could not determine settings.json path".to_string())?;
@@ -312,9 +1218,48 @@ pub async fn save_settings(settings: SettingsConfig) -> Result<String, String> {
         .map_err(|e| format!("Failed to save settings: {}", e))?;
 
     println!("Settings saved to: {}", settings_path.display());
+    FeatureRegistry::global().load(normalized_settings.features);
+    ApiLimiter::global().configure(normalized_settings.api_limits);
+    set_notification_routing(normalized_settings.notifications);
+
+    // Apply retention and encryption changes right away rather than at the next scheduled purge
+    tauri::async_runtime::spawn(async {
+        if let Err(e) = crate::commands::history::apply_retention_policy().await {
+            eprintln!("⚠️  [HISTORY] Failed to apply retention settings: {}", e);
+        }
+    });
     Ok(format!("Settings saved to: {}", settings_path.display()))
 }
 
+/// Write a copy of the settings without API keys and other secrets (for bug
+/// reports or sharing a configuration)
+///
+/// Secrets are replaced with a placeholder so the copy stays a valid
+/// settings.json. Returns the written path and the redacted settings.
+#[tauri::command]
+pub async fn export_settings_sanitized(destination: String) -> Result<serde_json::Value, String> {
+    require_unlocked()?;
+    let settings = read_settings().await?;
+    let mut settings = serde_json::to_value(&settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    let redacted = crate::config::sanitize_settings(&mut settings);
+
+    let destination = PathBuf::from(destination);
+    atomic_write_json(&destination, &settings)
+        .await
+        .map_err(|e| format!("Failed to export settings: {}", e))?;
+
+    println!(
+        "Sanitized settings exported to: {} ({} values redacted)",
+        destination.display(),
+        redacted.len()
+    );
+    Ok(serde_json::json!({
+        "path": destination,
+        "redacted": redacted,
+    }))
+}
+
 /// Legacy save settings function for backward compatibility
 #[tauri::command]
 pub async fn v1_save_settings(
@@ -336,6 +1281,56 @@ pub async fn save_profiles(
 /// Save profiles to profiles.json file with atomic writes and proper error handling
 #[tauri::command]
 pub async fn v1_save_profiles(
+    mut profiles: ProfileCollection,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    // Profiles edited by older clients have no kind yet
+    migrate_profile_kinds(&mut profiles.profiles);
+    let current = crate::commands::profiles::get_profiles_cached().await.ok();
+
+    // Locked profiles can only be hidden
+    if let Some(current) = &current {
+        ProfileEngine::new()
+            .validate_locked_profiles(current, &profiles)
+            .map_err(|e| format!("Profile validation failed: {}", e))?;
+    }
+
+    // New or edited instructions must fit the formatting model
+    for profile in &profiles.profiles {
+        let unchanged = current
+            .as_ref()
+            .is_some_and(|current| current.profiles.contains(profile));
+        if unchanged || !profile.should_use_gpt_formatting() {
+            continue;
+        }
+        let lint = lint_profile_instructions(profile, FORMATTING_MODEL).await;
+        for issue in &lint.issues {
+            eprintln!(
+                "⚠️  [PROFILES] '{}' ({:?}): {}",
+                profile.name, issue.severity, issue.message
+            );
+        }
+        if lint.has_errors() {
+            let errors: Vec<&str> = lint
+                .issues
+                .iter()
+                .filter(|issue| issue.severity == LintSeverity::Error)
+                .map(|issue| issue.message.as_str())
+                .collect();
+            return Err(format!(
+                "Profile validation failed: '{}': {}",
+                profile.name,
+                errors.join("; ")
+            ));
+        }
+    }
+
+    write_profiles(profiles, app_handle).await
+}
+
+/// Write profiles.json, replacing locked profiles too (profile sync: locked
+/// profiles are distributed through it)
+pub(crate) async fn write_profiles(
     profiles: ProfileCollection,
     app_handle: AppHandle,
 ) -> Result<String, String> {
@@ -360,9 +1355,10 @@ pub async fn v1_save_profiles(
             if let Some(backup) = backup_path {
                 let _ = tokio::fs::remove_file(backup).await;
             }
+            ProfileStore::global().invalidate();
 
             // Emit profiles-updated event to notify all windows of the change
-            let _ = app_handle.emit("profiles-updated", &profiles);
+            let _ = event_targets::broadcast(&app_handle, "profiles-updated", &profiles);
 
             Ok(format!(
                 "Profiles saved successfully to {}",
@@ -410,8 +1406,8 @@ pub async fn v1_save_profiles(
                     .await;
 
                 // Emit event for frontend to handle
-                let _ = app_handle.emit_to(
-                    "main",
+                let _ = event_targets::emit_to_main(
+                    &app_handle,
                     "disk_full_error",
                     serde_json::json!({
                         "type": "profiles",
@@ -6,7 +6,7 @@ use crate::services::{ShortcutMgr, ShortcutMgrConfig};
 use crate::state::AppStateMachineState;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 use tokio::sync::Mutex;
 
 /// Global state for the shortcut manager
@@ -17,7 +17,7 @@ static LAST_SHORTCUT_CALL: std::sync::Mutex<Option<Instant>> = std::sync::Mutex:
 
 /// Load global shortcut from settings.json
 fn load_global_shortcut_from_settings() -> String {
-    use crate::utils::find_config_file_path;
+    use crate::paths::find_config_file_path;
 
     // Use the proper config file path resolution
     if let Some(settings_path) = find_config_file_path("settings.json") {
@@ -60,6 +60,18 @@ pub async fn init_shortcut_mgr(
         ));
     }
 
+    // The bookmark shortcut is optional: failing to register it is not fatal
+    if let Some(bookmark_shortcut) = crate::commands::settings::read_settings()
+        .await
+        .ok()
+        .and_then(|settings| settings.bookmarks.shortcut)
+        .filter(|shortcut| !shortcut.trim().is_empty())
+    {
+        if let Err(e) = mgr.register_bookmark_shortcut(bookmark_shortcut).await {
+            eprintln!("⚠️ [SHORTCUT] Bookmark shortcut not registered: {}", e);
+        }
+    }
+
     let mut state_guard = state.lock().await;
     *state_guard = Some(mgr);
 
@@ -69,6 +81,38 @@ pub async fn init_shortcut_mgr(
     ))
 }
 
+/// Unregister every global shortcut (recording, bookmark, profiles) and drop
+/// the shortcut manager
+#[tauri::command]
+pub async fn shutdown_shortcut_mgr(state: State<'_, ShortcutMgrState>) -> Result<String, String> {
+    let mut state_guard = state.lock().await;
+    let Some(mgr) = state_guard.take() else {
+        return Ok("Shortcut manager was not initialized".to_string());
+    };
+    mgr.unregister_all()
+        .await
+        .map_err(|e| format!("Failed to unregister shortcuts: {}", e))?;
+    Ok("Shortcut manager shut down".to_string())
+}
+
+/// Shut the shortcut manager down and register the shortcuts of the settings
+/// and profiles again
+#[tauri::command]
+pub async fn reinit_shortcut_mgr(
+    app_handle: AppHandle,
+    state: State<'_, ShortcutMgrState>,
+) -> Result<String, String> {
+    if let Err(e) = shutdown_shortcut_mgr(state.clone()).await {
+        // The manager is dropped regardless; registering again replaces the leftovers
+        eprintln!("⚠️ [SHORTCUT] {}", e);
+    }
+    let message = init_shortcut_mgr(app_handle, None, state.clone()).await?;
+    if let Err(e) = register_all_profile_shortcuts(state).await {
+        eprintln!("⚠️ [SHORTCUT] {}", e);
+    }
+    Ok(message)
+}
+
 /// Auto-initialize shortcut manager during app startup
 #[tauri::command]
 pub async fn auto_init_shortcut_mgr(
@@ -83,7 +127,7 @@ pub async fn auto_init_shortcut_mgr(
 #[allow(clippy::too_many_arguments)]
 #[tauri::command]
 pub async fn toggle_record_with_tray(
-    _app_handle: AppHandle,
+    app_handle: AppHandle,
     state_machine_state: State<'_, AppStateMachineState>,
     tray_state: State<'_, SystemTrayState>,
     audio_state: State<'_, crate::commands::AudioCaptureState>,
@@ -109,6 +153,12 @@ pub async fn toggle_record_with_tray(
         *last_call = Some(now);
     }
 
+    // The microphone belongs to the continuous session until it stops
+    if crate::commands::continuous::is_session_running(&app_handle).await {
+        println!("🚫 [SHORTCUT] Ignored - a continuous session is running");
+        return Ok("Continuous session running".to_string());
+    }
+
     // Check if settings window is open - if so, completely ignore the shortcut
     // COMMENTED OUT: This causes race conditions between window closing and state machine updates
     // if app_handle.get_webview_window("settings").is_some() {
@@ -200,6 +250,10 @@ pub async fn toggle_record_with_tray(
                 }
             );
 
+            if audio_state.lock().await.is_none() {
+                return Err("Audio capture not initialized".to_string());
+            }
+
             // Process start recording event through state machine
             if let Err(e) = crate::commands::state_machine::process_event(
                 crate::state::AppEvent::ToggleRecording,
@@ -210,24 +264,13 @@ pub async fn toggle_record_with_tray(
                 return Err(format!("Failed to process start recording event: {}", e));
             }
 
-            // Actually start the audio capture
-            let audio_guard = audio_state.lock().await;
-            if let Some(ref capture) = *audio_guard {
-                let path = capture
-                    .start_capture()
-                    .await
-                    .map_err(|e| format!("Failed to start audio capture: {}", e))?;
-                println!("✅ [SHORTCUT] Recording started successfully");
-                Ok(format!(
-                    "Recording started. File: {}",
-                    path.to_string_lossy()
-                ))
-            } else {
-                Err("Audio capture not initialized".to_string())
-            }
+            // Entering Recording starts the audio capture
+            println!("✅ [SHORTCUT] Recording started successfully");
+            Ok("Recording started".to_string())
         }
-        crate::state::AppState::Recording { .. } => {
-            // Stop recording
+        crate::state::AppState::Recording { .. }
+        | crate::state::AppState::RecordingPaused { .. } => {
+            // Stop recording (a paused session is stopped as well)
             println!("🛑 [SHORTCUT] Stopping recording from Recording state...");
 
             // Process stop recording event through state machine
@@ -252,6 +295,12 @@ pub async fn toggle_record_with_tray(
                 profile_state,
                 gpt_state,
                 state_machine_state,
+                app_handle.state(),
+                app_handle.state(),
+                app_handle.state(),
+                app_handle.state(),
+                app_handle.clone(),
+                None,
             )
             .await
             {
@@ -469,11 +518,7 @@ pub async fn register_all_profile_shortcuts(
 
     if let Some(ref mgr) = *state_guard {
         // Load profiles from profiles.json
-        let profiles_content = load_profiles_json()?;
-        let profile_engine = crate::services::ProfileEngine::new();
-        let profiles = profile_engine
-            .load_profiles_from_json(&profiles_content)
-            .map_err(|e| format!("Failed to parse profiles: {}", e))?;
+        let profiles = crate::commands::get_profiles_cached().await?;
 
         mgr.register_profile_shortcuts(&profiles)
             .await
@@ -525,14 +570,3 @@ pub async fn check_shortcut_available(
         Ok(true)
     }
 }
-
-/// Helper function to load profiles.json content
-fn load_profiles_json() -> Result<String, String> {
-    use crate::utils::find_config_file_path;
-
-    let profiles_path = find_config_file_path("profiles.json")
-        .ok_or_else(|| "Could not determine profiles.json path".to_string())?;
-
-    std::fs::read_to_string(&profiles_path)
-        .map_err(|e| format!("Failed to read {}: {}", profiles_path.display(), e))
-}
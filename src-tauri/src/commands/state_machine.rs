@@ -1,11 +1,112 @@
 //! State machine related commands for managing global application state
 
 use crate::audio::capture::AudioCapture;
-use crate::state::{AppEvent, AppStateMachineBuilder, AppStateMachineState};
+use crate::commands::{AudioCaptureState, InterviewCaptureState};
+use crate::state::{
+    AppEvent, AppState, AppStateMachineBuilder, AppStateMachineState, StateKind, StateTransition,
+};
+use std::path::Path;
 use std::sync::Arc;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 use tokio::sync::Mutex;
 
+/// Register the side effects of the state transitions on `builder`
+///
+/// Every state machine of the app is built through this, so the side effects
+/// run whichever command sent the event. Hooks run with the state machine
+/// locked: the ones needing another lock defer their work to a task.
+pub fn with_state_hooks(builder: AppStateMachineBuilder) -> AppStateMachineBuilder {
+    builder
+        .with_entry_hook(StateKind::Recording, start_recording_capture)
+        .with_exit_hook(StateKind::AwaitingConfirmation, remove_discarded_recording)
+}
+
+/// Start the audio capture of a new recording once the transition completes
+///
+/// Resuming a paused recording goes on with the running capture.
+async fn start_recording_capture(
+    app_handle: AppHandle,
+    transition: StateTransition,
+) -> Result<(), String> {
+    if !matches!(transition.from, AppState::RecordingPaused { .. }) {
+        tauri::async_runtime::spawn(start_dictation_capture(app_handle));
+    }
+    Ok(())
+}
+
+/// Start the dictation capture, ending the recording in a device error when
+/// it cannot start
+///
+/// The recordings with a capture of their own (continuous session, interview)
+/// start it before sending the event.
+async fn start_dictation_capture(app_handle: AppHandle) {
+    if let Some(interview_state) = app_handle.try_state::<InterviewCaptureState>() {
+        if interview_state.lock().await.is_some() {
+            return;
+        }
+    }
+
+    let capture = match app_handle.try_state::<AudioCaptureState>() {
+        Some(audio_state) => audio_state.lock().await.clone(),
+        None => None,
+    };
+    let started = match capture {
+        Some(capture) if capture.is_recording() => return,
+        Some(capture) => capture.start_capture().await.map_err(|e| e.to_string()),
+        None => Err("Audio capture not initialized".to_string()),
+    };
+    match started {
+        Ok(path) => {
+            eprintln!("🎙️ [STATE-MACHINE] Audio capture started: {:?}", path);
+            crate::commands::language::emit_recording_started(&app_handle, &path).await;
+            crate::commands::audio::warn_if_microphone_muted(&app_handle);
+        }
+        Err(error) => {
+            eprintln!(
+                "❌ [STATE-MACHINE] Failed to start audio capture: {}",
+                error
+            );
+            if let Some(state) = app_handle.try_state::<AppStateMachineState>() {
+                if let Err(e) =
+                    process_event(AppEvent::RecordingStartFailed { error }, &state).await
+                {
+                    eprintln!("⚠️  [STATE-MACHINE] {}", e);
+                }
+            }
+        }
+    }
+}
+
+/// Delete a recording discarded (or reset) while awaiting confirmation
+async fn remove_discarded_recording(
+    _app_handle: AppHandle,
+    transition: StateTransition,
+) -> Result<(), String> {
+    let Some(wav_path) = discarded_recording(&transition) else {
+        return Ok(());
+    };
+    match tokio::fs::remove_file(wav_path).await {
+        Ok(()) => {
+            eprintln!("🗑️  [STATE-MACHINE] Recording {:?} removed", wav_path);
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to remove recording {:?}: {}", wav_path, e)),
+    }
+}
+
+/// Recording dropped by `transition`, if any
+///
+/// Only the held recording is known to the state machine: confirming it moves
+/// on to the transcription, which keeps it, and the processing states carry a
+/// placeholder path (the workflow removes its recording itself).
+fn discarded_recording(transition: &StateTransition) -> Option<&Path> {
+    match (&transition.from, &transition.to) {
+        (AppState::AwaitingConfirmation { wav_path, .. }, AppState::Idle { .. }) => Some(wav_path),
+        _ => None,
+    }
+}
+
 /// Initialize the application state machine
 #[tauri::command]
 pub async fn init_state_machine(
@@ -13,7 +114,7 @@ pub async fn init_state_machine(
     state: State<'_, AppStateMachineState>,
 ) -> Result<String, String> {
     let state_machine = Arc::new(Mutex::new(
-        AppStateMachineBuilder::new().build(app_handle.clone()),
+        with_state_hooks(AppStateMachineBuilder::new()).build(app_handle.clone()),
     ));
 
     let mut state_guard = state.lock().await;
@@ -111,6 +212,7 @@ pub async fn has_modal_window_open(state: State<'_, AppStateMachineState>) -> Re
 /// Start recording through state machine
 #[tauri::command]
 pub async fn start_recording_via_state_machine(
+    _app_handle: tauri::AppHandle,
     state: State<'_, AppStateMachineState>,
     audio_state: State<'_, crate::commands::AudioCaptureState>,
 ) -> Result<String, String> {
@@ -133,7 +235,12 @@ pub async fn start_recording_via_state_machine(
         }
     };
 
-    // Process event through state machine first
+    if audio_state.lock().await.is_none() {
+        eprintln!("❌ [STATE-MACHINE] Audio capture not initialized");
+        return Err("Audio capture not initialized".to_string());
+    }
+
+    // Entering Recording starts the audio capture (see `with_state_hooks`)
     eprintln!("🚀 [STATE-MACHINE] Processing StartRecording event...");
     process_event(crate::state::AppEvent::StartRecording, &state).await?;
     eprintln!("✅ [STATE-MACHINE] StartRecording event processed successfully");
@@ -151,44 +258,10 @@ pub async fn start_recording_via_state_machine(
         }
     };
 
-    // Then actually start the audio capture
-    eprintln!("🔊 [STATE-MACHINE] Starting audio capture...");
-    let audio_guard = audio_state.lock().await;
-    if let Some(ref capture) = *audio_guard {
-        eprintln!("📡 [STATE-MACHINE] Audio capture found, checking if already recording...");
-        let was_recording = capture.is_recording();
-        eprintln!(
-            "📊 [STATE-MACHINE] Audio capture status before start: {}",
-            was_recording
-        );
-
-        if was_recording {
-            eprintln!("⚠️ [STATE-MACHINE] Warning: Audio capture already recording");
-        }
-
-        let path = capture.start_capture().await.map_err(|e| {
-            eprintln!("❌ [STATE-MACHINE] Failed to start audio capture: {}", e);
-            format!("Failed to start audio capture: {}", e)
-        })?;
-
-        let now_recording = capture.is_recording();
-        eprintln!("✅ [STATE-MACHINE] Audio capture started successfully");
-        eprintln!(
-            "📊 [STATE-MACHINE] Audio capture status after start: {}",
-            now_recording
-        );
-        eprintln!("📁 [STATE-MACHINE] Recording path: {:?}", path);
-
-        Ok(format!(
-            "Recording started. State: {} -> {}. Path: {}",
-            current_state,
-            new_state,
-            path.to_string_lossy()
-        ))
-    } else {
-        eprintln!("❌ [STATE-MACHINE] Audio capture not initialized");
-        Err("Audio capture not initialized".to_string())
-    }
+    Ok(format!(
+        "Recording started. State: {} -> {}",
+        current_state, new_state
+    ))
 }
 
 /// Stop recording through state machine
@@ -198,11 +271,21 @@ pub async fn stop_recording_via_state_machine(
     audio_state: State<'_, crate::commands::AudioCaptureState>,
 ) -> Result<String, String> {
     // Process event through state machine first
-    process_event(crate::state::AppEvent::StopRecording, &state).await?;
+    process_event(
+        crate::state::AppEvent::StopRecording {
+            reason: crate::state::StopReason::Manual,
+        },
+        &state,
+    )
+    .await?;
 
     // Then actually stop the audio capture
     let audio_guard = audio_state.lock().await;
     if let Some(ref capture) = *audio_guard {
+        if !capture.is_recording() {
+            // A repeated stop was ignored by the state machine
+            return Ok("Recording already stopped".to_string());
+        }
         let path = capture
             .stop_capture()
             .await
@@ -215,3 +298,45 @@ pub async fn stop_recording_via_state_machine(
         Err("Audio capture not initialized".to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::recording_state_machine::AppStateMachine;
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    fn transition(from: AppState, event: AppEvent) -> StateTransition {
+        let to = AppStateMachine::validate_transition_static(&from, &event).unwrap();
+        StateTransition { from, to, event }
+    }
+
+    #[test]
+    fn test_discarded_recording_is_the_held_one() {
+        let held = AppState::AwaitingConfirmation {
+            wav_path: PathBuf::from("/tmp/.tmpHeld.wav"),
+            bookmarks: vec![Duration::from_secs(2)],
+            stopped_at: SystemTime::now(),
+        };
+        let discarded = transition(held.clone(), AppEvent::DiscardRecording);
+        assert_eq!(
+            discarded_recording(&discarded),
+            Some(Path::new("/tmp/.tmpHeld.wav"))
+        );
+        let confirmed = transition(held, AppEvent::ConfirmTranscription);
+        assert_eq!(discarded_recording(&confirmed), None);
+    }
+
+    #[test]
+    fn test_cancel_during_transcription_leaves_the_placeholder_path() {
+        // The transcription carries a placeholder: removing it could delete
+        // an unrelated file, the workflow removes its recording itself
+        let transcribing = AppState::ProcessingTranscription {
+            wav_path: PathBuf::from("/tmp/recording.wav"),
+            started_at: SystemTime::now(),
+        };
+        let cancelled = transition(transcribing, AppEvent::Reset);
+        assert!(matches!(cancelled.to, AppState::Idle { .. }));
+        assert_eq!(discarded_recording(&cancelled), None);
+    }
+}
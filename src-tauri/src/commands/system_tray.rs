@@ -1,6 +1,7 @@
 //! System tray related commands for managing tray functionality
 
 use crate::commands::state_machine::process_event;
+use crate::services::event_targets::{self, MAIN_WINDOW, SETTINGS_WINDOW};
 use crate::services::{SystemTrayConfig, SystemTrayService};
 use crate::state::{AppEvent, AppStateMachineState};
 use std::sync::Arc;
@@ -24,6 +25,7 @@ pub async fn init_system_tray(
         global_shortcut: global_shortcut.unwrap_or_else(|| "CmdOrCtrl+Shift+F9".to_string()),
         persist_window_position: true,
         is_first_launch: is_first_launch.unwrap_or(false),
+        tray_only: false,
     };
 
     let service = Arc::new(SystemTrayService::new(app_handle, config));
@@ -41,6 +43,92 @@ pub async fn init_system_tray(
     Ok("System tray initialized successfully".to_string())
 }
 
+/// Create the main window declared in `tauri.conf.json` (not created at
+/// launch, as tray-only mode goes without it)
+pub fn create_main_window(app_handle: &AppHandle) -> Result<(), String> {
+    let config = app_handle
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|window| window.label == MAIN_WINDOW)
+        .ok_or_else(|| "Main window not configured".to_string())?;
+    WebviewWindowBuilder::from_config(app_handle, config)
+        .and_then(|builder| builder.build())
+        .map_err(|e| format!("Failed to create main window: {}", e))?;
+    Ok(())
+}
+
+/// Start tray-only mode: the services the main window initializes are
+/// initialized here, and the tray menu replaces the window
+pub async fn start_tray_only(app_handle: AppHandle) {
+    println!("🗂️ [TRAY-ONLY] Starting without the main window");
+
+    if let Err(e) =
+        crate::commands::init_audio_capture(app_handle.clone(), app_handle.state()).await
+    {
+        eprintln!("❌ [TRAY-ONLY] Failed to initialize audio capture: {}", e);
+    }
+
+    let settings = crate::commands::settings::read_settings().await.ok();
+    let config = SystemTrayConfig {
+        show_startup_notification: true,
+        global_shortcut: settings
+            .as_ref()
+            .map(|settings| settings.global_shortcut.clone())
+            .unwrap_or_else(|| "CmdOrCtrl+Shift+F9".to_string()),
+        persist_window_position: false,
+        is_first_launch: false,
+        tray_only: true,
+    };
+    let service = Arc::new(SystemTrayService::new(app_handle.clone(), config));
+    match service.initialize().await {
+        Ok(()) => {
+            let state = app_handle.state::<SystemTrayState>();
+            *state.lock().await = Some(service);
+        }
+        Err(e) => eprintln!("❌ [TRAY-ONLY] Failed to initialize system tray: {}", e),
+    }
+
+    if let Err(e) =
+        crate::commands::auto_init_shortcut_mgr(app_handle.clone(), app_handle.state()).await
+    {
+        eprintln!("❌ [TRAY-ONLY] {}", e);
+    }
+
+    let api_key = settings
+        .map(|settings| settings.whisper.api_key)
+        .unwrap_or_default();
+    if api_key.trim().is_empty() {
+        println!("⚠️ [TRAY-ONLY] No API key in settings, clients not initialized");
+    } else {
+        if let Err(e) = crate::commands::init_whisper_client(
+            app_handle.clone(),
+            api_key.clone(),
+            app_handle.state(),
+        )
+        .await
+        {
+            eprintln!("❌ [TRAY-ONLY] Failed to initialize Whisper client: {}", e);
+        }
+        if let Err(e) = crate::commands::init_gpt_client(api_key, app_handle.state()).await {
+            eprintln!("⚠️ [TRAY-ONLY] GPT client initialization failed: {}", e);
+        }
+    }
+
+    if let Err(e) =
+        crate::commands::init_clipboard_service(app_handle.clone(), app_handle.state()).await
+    {
+        eprintln!(
+            "❌ [TRAY-ONLY] Failed to initialize clipboard service: {}",
+            e
+        );
+    }
+    if let Err(e) = crate::commands::load_profiles().await {
+        eprintln!("❌ [TRAY-ONLY] Failed to load profiles: {}", e);
+    }
+}
+
 /// Show the main window
 #[tauri::command]
 pub async fn show_main_window(
@@ -203,6 +291,8 @@ pub async fn open_settings_window(
     state_machine_state: State<'_, AppStateMachineState>,
     tray_state: State<'_, SystemTrayState>,
 ) -> Result<String, String> {
+    crate::commands::app_lock::require_unlocked()?;
+
     // First emit event to state machine - this will handle stopping recording/processing
     // and transition to SettingsWindowOpen{previous_state: Idle}
     if let Err(e) = process_event(AppEvent::OpenSettingsWindow, &state_machine_state).await {
@@ -232,7 +322,7 @@ pub async fn open_settings_window(
     // Create new settings window
     let _settings_window = WebviewWindowBuilder::new(
         &app_handle,
-        "settings",
+        SETTINGS_WINDOW,
         tauri::WebviewUrl::App("index.html".into()),
     )
     .title("DictaClerk Settings")
@@ -251,6 +341,7 @@ pub async fn open_settings_window(
     _settings_window.on_window_event(move |event| {
         if let WindowEvent::CloseRequested { .. } = event {
             println!("🚨 [SETTINGS-WINDOW] Close requested - updating state machine");
+            event_targets::clear_window(SETTINGS_WINDOW);
             let _app_handle = app_handle_clone.clone();
             let state_machine = state_machine_clone.clone();
             let tray_state = tray_state_clone.clone();
@@ -0,0 +1,207 @@
+//! Commands of the background transcription queue (see
+//! [`crate::services::transcription_queue`])
+//!
+//! Queued recordings are transcribed in background tasks, up to
+//! `transcription_queue.max_concurrent_jobs` at a time, without going through
+//! the state machine: dictations keep working while a batch runs.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+
+use crate::commands::settings::read_settings;
+use crate::commands::usage::record_transcription_usage;
+use crate::commands::whisper::encode_and_transcribe;
+use crate::commands::WhisperClientState;
+use crate::services::event_targets::broadcast;
+use crate::services::transcription_queue::{JobPriority, TranscriptionJob, TranscriptionQueue};
+use crate::services::DryRunWhisperClient;
+
+/// Global state for the transcription queue
+pub type TranscriptionQueueState = Arc<std::sync::Mutex<TranscriptionQueue>>;
+
+/// Event sent to every window each time a job changes status
+const JOB_EVENT: &str = "transcription-job-status";
+
+fn emit_job(app_handle: &AppHandle, job: &TranscriptionJob) {
+    if let Err(e) = broadcast(app_handle, JOB_EVENT, job.clone()) {
+        eprintln!("⚠️  [QUEUE] Failed to emit {}: {}", JOB_EVENT, e);
+    }
+}
+
+/// Start queued jobs until the concurrency limit is reached
+///
+/// Each job runs in its own task, which starts the next ones when it ends.
+fn dispatch_jobs(app_handle: &AppHandle) {
+    let Some(queue) = app_handle.try_state::<TranscriptionQueueState>() else {
+        return;
+    };
+    loop {
+        let next = match queue.lock() {
+            Ok(mut queue) => queue.start_next(),
+            Err(_) => None,
+        };
+        let Some(job) = next else {
+            break;
+        };
+        eprintln!(
+            "📥 [QUEUE] Transcribing {} ({:?} priority)",
+            job.path.display(),
+            job.priority
+        );
+        emit_job(app_handle, &job);
+
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let result = run_job(&app_handle, &job).await;
+            match &result {
+                Ok(_) => eprintln!("✅ [QUEUE] Transcribed {}", job.path.display()),
+                Err(e) => eprintln!(
+                    "❌ [QUEUE] Failed to transcribe {}: {}",
+                    job.path.display(),
+                    e
+                ),
+            }
+            let finished = match app_handle.try_state::<TranscriptionQueueState>() {
+                Some(queue) => match queue.lock() {
+                    Ok(mut queue) => queue.complete(&job.id, result).ok(),
+                    Err(_) => None,
+                },
+                None => None,
+            };
+            if let Some(finished) = finished {
+                emit_job(&app_handle, &finished);
+            }
+            dispatch_jobs(&app_handle);
+        });
+    }
+}
+
+/// Transcribe the recording of a job with the configured Whisper client
+async fn run_job(app_handle: &AppHandle, job: &TranscriptionJob) -> Result<String, String> {
+    let dry_run = read_settings()
+        .await
+        .map(|settings| settings.workflow.dry_run)
+        .unwrap_or(false);
+    if dry_run {
+        let transcript = encode_and_transcribe(
+            job.path.clone(),
+            job.prompt.clone(),
+            &DryRunWhisperClient::new(0),
+        )
+        .await?;
+        return Ok(transcript.text);
+    }
+
+    let whisper_state = app_handle
+        .try_state::<WhisperClientState>()
+        .ok_or("Whisper client state not available")?;
+    // Clone the client so dictations are not blocked while a job uploads
+    let client = whisper_state
+        .lock()
+        .await
+        .as_ref()
+        .cloned()
+        .ok_or("Whisper client not initialized")?;
+    let transcript =
+        encode_and_transcribe(job.path.clone(), job.prompt.clone(), client.as_ref()).await?;
+    record_transcription_usage(transcript.duration, &job.path).await;
+    Ok(transcript.text)
+}
+
+/// Queue WAV recordings for transcription in the background
+///
+/// Jobs start by priority (`normal` by default), oldest first, and report
+/// each status change with a `transcription-job-status` event. Nothing is
+/// queued when one of the files does not exist.
+#[tauri::command]
+pub async fn enqueue_transcriptions(
+    app_handle: AppHandle,
+    paths: Vec<String>,
+    priority: Option<JobPriority>,
+    prompt: Option<String>,
+    queue_state: State<'_, TranscriptionQueueState>,
+) -> Result<Vec<TranscriptionJob>, String> {
+    if paths.is_empty() {
+        return Err("No recording to transcribe".to_string());
+    }
+    let missing: Vec<&str> = paths
+        .iter()
+        .map(String::as_str)
+        .filter(|path| !Path::new(path).is_file())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("Recording not found: {}", missing.join(", ")));
+    }
+    let max_concurrent_jobs = read_settings()
+        .await?
+        .transcription_queue
+        .max_concurrent_jobs
+        .clamp(1, 8) as usize;
+
+    let jobs: Vec<TranscriptionJob> = {
+        let mut queue = queue_state
+            .lock()
+            .map_err(|e| format!("Transcription queue unavailable: {}", e))?;
+        queue.set_max_concurrent_jobs(max_concurrent_jobs);
+        paths
+            .into_iter()
+            .map(|path| {
+                queue.enqueue(
+                    PathBuf::from(path),
+                    priority.unwrap_or_default(),
+                    prompt.clone(),
+                )
+            })
+            .collect()
+    };
+    eprintln!(
+        "📥 [QUEUE] Queued {} recording(s), up to {} at a time",
+        jobs.len(),
+        max_concurrent_jobs
+    );
+    for job in &jobs {
+        emit_job(&app_handle, job);
+    }
+    dispatch_jobs(&app_handle);
+    Ok(jobs)
+}
+
+/// Get every job of the transcription queue, in enqueue order
+#[tauri::command]
+pub async fn get_transcription_jobs(
+    queue_state: State<'_, TranscriptionQueueState>,
+) -> Result<Vec<TranscriptionJob>, String> {
+    queue_state
+        .lock()
+        .map(|queue| queue.jobs().to_vec())
+        .map_err(|e| format!("Transcription queue unavailable: {}", e))
+}
+
+/// Cancel a job that has not started yet
+#[tauri::command]
+pub async fn cancel_transcription_job(
+    app_handle: AppHandle,
+    job_id: String,
+    queue_state: State<'_, TranscriptionQueueState>,
+) -> Result<TranscriptionJob, String> {
+    let job = queue_state
+        .lock()
+        .map_err(|e| format!("Transcription queue unavailable: {}", e))?
+        .cancel(&job_id)
+        .map_err(|e| e.to_string())?;
+    emit_job(&app_handle, &job);
+    Ok(job)
+}
+
+/// Remove the completed, failed and cancelled jobs; returns how many were
+/// removed
+#[tauri::command]
+pub async fn clear_finished_transcription_jobs(
+    queue_state: State<'_, TranscriptionQueueState>,
+) -> Result<usize, String> {
+    queue_state
+        .lock()
+        .map(|mut queue| queue.clear_finished())
+        .map_err(|e| format!("Transcription queue unavailable: {}", e))
+}
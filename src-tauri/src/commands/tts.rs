@@ -0,0 +1,115 @@
+//! Commands for reading the final text aloud
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+
+use crate::commands::settings::read_settings;
+use crate::services::tts::{speak_with_openai, speak_with_system, Speech, TtsProvider};
+use crate::state::recording_state_machine::AppState;
+use crate::state::AppStateMachineState;
+
+/// Speech currently being played, with an id to tell successive speeches apart
+pub type SpeechState = Arc<Mutex<Option<(u64, Speech)>>>;
+
+/// Interval at which a running speech is checked for completion
+const SPEECH_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Id of the next speech
+static NEXT_SPEECH_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Read `text` aloud, or the final text of the last workflow when omitted
+///
+/// Any speech already playing is stopped first. Emits `speech-started` and
+/// `speech-finished`.
+#[tauri::command]
+pub async fn speak_result(
+    text: Option<String>,
+    app_handle: AppHandle,
+    speech_state: State<'_, SpeechState>,
+    state_machine_state: State<'_, AppStateMachineState>,
+) -> Result<String, String> {
+    let text = match text {
+        Some(text) => text,
+        None => last_final_text(&state_machine_state).await?,
+    };
+    let settings = read_settings().await?;
+    let options = settings.tts.options();
+
+    stop_current_speech(&speech_state).await;
+
+    let speech = match settings.tts.provider {
+        TtsProvider::System => speak_with_system(&text, &options),
+        TtsProvider::Openai => speak_with_openai(&settings.whisper.api_key, &text, &options).await,
+    }
+    .map_err(|e| format!("Failed to speak text: {}", e))?;
+
+    let id = NEXT_SPEECH_ID.fetch_add(1, Ordering::Relaxed);
+    *speech_state.lock().await = Some((id, speech));
+
+    eprintln!(
+        "🔊 [TTS] Speaking {} characters ({:?})",
+        text.len(),
+        settings.tts.provider
+    );
+    let _ = app_handle.emit("speech-started", id);
+    tokio::spawn(watch_speech(app_handle, speech_state.inner().clone(), id));
+
+    Ok("Speaking".to_string())
+}
+
+/// Stop reading aloud
+#[tauri::command]
+pub async fn stop_speaking(speech_state: State<'_, SpeechState>) -> Result<String, String> {
+    if stop_current_speech(&speech_state).await {
+        eprintln!("🔇 [TTS] Speech stopped");
+        Ok("Speech stopped".to_string())
+    } else {
+        Ok("Nothing is being spoken".to_string())
+    }
+}
+
+/// Final text of the completed workflow
+async fn last_final_text(state_machine_state: &AppStateMachineState) -> Result<String, String> {
+    let state_machine = state_machine_state.lock().await;
+    let state_machine = state_machine
+        .as_ref()
+        .ok_or_else(|| "State machine not available".to_string())?;
+    let state_machine_guard = state_machine.lock().await;
+    match state_machine_guard.current_state() {
+        AppState::ProcessingComplete { final_text, .. } => Ok(final_text.clone()),
+        _ => Err("No final text to speak: not in ProcessingComplete state".to_string()),
+    }
+}
+
+/// Stop the current speech, returning whether one was playing
+async fn stop_current_speech(speech_state: &SpeechState) -> bool {
+    match speech_state.lock().await.take() {
+        Some((_, mut speech)) => {
+            let was_playing = !speech.is_finished();
+            speech.stop();
+            was_playing
+        }
+        None => false,
+    }
+}
+
+/// Emit `speech-finished` once speech `id` ends, is stopped or is replaced
+async fn watch_speech(app_handle: AppHandle, speech_state: SpeechState, id: u64) {
+    loop {
+        tokio::time::sleep(SPEECH_POLL_INTERVAL).await;
+        let mut guard = speech_state.lock().await;
+        match guard.as_mut() {
+            Some((current_id, speech)) if *current_id == id => {
+                if speech.is_finished() {
+                    guard.take();
+                    break;
+                }
+            }
+            _ => break,
+        }
+    }
+    let _ = app_handle.emit("speech-finished", id);
+}
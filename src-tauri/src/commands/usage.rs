@@ -0,0 +1,68 @@
+//! Commands tracking the transcription API usage and its estimated cost
+
+use chrono::Local;
+use std::path::Path;
+
+use crate::commands::settings::read_settings;
+use crate::paths::find_config_file_path;
+use crate::services::usage::{
+    wav_duration_seconds, whisper_price_per_minute, UsageStats, UsageTracker, USAGE_FILE,
+};
+
+/// One read-modify-write of the usage log at a time
+static USAGE_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+fn usage_tracker() -> Result<UsageTracker, String> {
+    find_config_file_path(USAGE_FILE)
+        .map(UsageTracker::new)
+        .ok_or_else(|| format!("Could not determine {} path", USAGE_FILE))
+}
+
+/// Add a transcription to the usage log, priced for the configured provider
+/// and model
+///
+/// `duration` is the one Whisper reported; without it (non-verbose response
+/// formats) the duration of the recording is used. Failures are only logged.
+pub async fn record_transcription_usage(duration: Option<f64>, wav_path: &Path) {
+    let Some(seconds) = duration.or_else(|| wav_duration_seconds(wav_path)) else {
+        eprintln!(
+            "⚠️  [USAGE] Unknown duration of {}, usage not recorded",
+            wav_path.display()
+        );
+        return;
+    };
+    let price = match read_settings().await {
+        Ok(settings) => {
+            whisper_price_per_minute(settings.whisper.provider, &settings.whisper.model)
+        }
+        Err(e) => {
+            eprintln!(
+                "⚠️  [USAGE] Failed to load settings, usage not recorded: {}",
+                e
+            );
+            return;
+        }
+    };
+
+    let _usage = USAGE_LOCK.lock().await;
+    match usage_tracker().and_then(|tracker| {
+        tracker
+            .record(Local::now().date_naive(), seconds, price)
+            .map_err(|e| e.to_string())
+    }) {
+        Ok(cost) => eprintln!(
+            "💵 [USAGE] Recorded {:.1}s of audio (~${:.4})",
+            seconds, cost
+        ),
+        Err(e) => eprintln!("⚠️  [USAGE] Failed to record usage: {}", e),
+    }
+}
+
+/// Get the transcription minutes and estimated cost per day and month
+#[tauri::command]
+pub async fn get_usage_stats() -> Result<UsageStats, String> {
+    let _usage = USAGE_LOCK.lock().await;
+    usage_tracker()?
+        .stats(Local::now().date_naive())
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,36 @@
+//! Commands for the custom vocabulary biasing transcription
+
+use crate::paths::find_config_file_path;
+use crate::services::vocabulary::{Vocabulary, VocabularyStore, VOCABULARY_FILE};
+
+fn vocabulary_store() -> Result<VocabularyStore, String> {
+    find_config_file_path(VOCABULARY_FILE)
+        .map(VocabularyStore::new)
+        .ok_or_else(|| format!("Could not determine {} path", VOCABULARY_FILE))
+}
+
+/// Vocabulary for workflows (empty when it cannot be read)
+pub fn load_vocabulary() -> Vocabulary {
+    vocabulary_store()
+        .and_then(|store| store.load().map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("⚠️  [VOCABULARY] Failed to load the vocabulary: {}", e);
+            Vocabulary::default()
+        })
+}
+
+/// Get the custom vocabulary
+#[tauri::command]
+pub async fn get_vocabulary() -> Result<Vocabulary, String> {
+    vocabulary_store()?.load().map_err(|e| e.to_string())
+}
+
+/// Save the custom vocabulary; returns it normalized (trimmed, without duplicates)
+#[tauri::command]
+pub async fn save_vocabulary(vocabulary: Vocabulary) -> Result<Vocabulary, String> {
+    let saved = vocabulary_store()?
+        .save(vocabulary)
+        .map_err(|e| e.to_string())?;
+    eprintln!("📚 [VOCABULARY] Saved {} term(s)", saved.terms.len());
+    Ok(saved)
+}
@@ -0,0 +1,60 @@
+//! Running spoken commands against the state machine
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::audio::AudioCapture;
+use crate::commands::AudioCaptureState;
+use crate::services::voice_commands::VoiceCommand;
+use crate::state::{AppEvent, AppStateMachineState};
+
+/// Act on a command heard in the live captions while recording
+///
+/// "stop recording" goes through the same path as the global shortcut (stop,
+/// transcribe, format, copy); "cancel recording" discards the recording.
+pub async fn run_live_command(app_handle: &AppHandle, command: VoiceCommand) {
+    eprintln!("🗣️  [VOICE] Live command: {:?}", command);
+    let _ = app_handle.emit("voice-command", &command);
+
+    match command {
+        VoiceCommand::StopRecording => {
+            if let Err(e) = app_handle.emit("global_shortcut_toggle_record", ()) {
+                eprintln!("⚠️  [VOICE] Failed to stop recording: {}", e);
+            }
+        }
+        VoiceCommand::Cancel => cancel_recording(app_handle).await,
+        // Profiles are picked from the start of the transcript, once recorded
+        VoiceCommand::UseProfile(_) => {}
+    }
+}
+
+/// Stop the capture and drop the recording without processing it
+async fn cancel_recording(app_handle: &AppHandle) {
+    if let Some(state_machine_state) = app_handle.try_state::<AppStateMachineState>() {
+        if let Err(e) = crate::commands::state_machine::process_event(
+            AppEvent::CancelRecording,
+            &state_machine_state,
+        )
+        .await
+        {
+            eprintln!("⚠️  [VOICE] Failed to cancel recording: {}", e);
+            return;
+        }
+    }
+
+    let Some(audio_state) = app_handle.try_state::<AudioCaptureState>() else {
+        return;
+    };
+    let audio_guard = audio_state.lock().await;
+    if let Some(capture) = audio_guard
+        .as_ref()
+        .filter(|capture| capture.is_recording())
+    {
+        match capture.stop_capture().await {
+            Ok(wav_path) => {
+                let _ = tokio::fs::remove_file(&wav_path).await;
+                eprintln!("🗑️  [VOICE] Recording cancelled and discarded");
+            }
+            Err(e) => eprintln!("⚠️  [VOICE] Failed to stop capture: {}", e),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Commands compiling the weekly summary report
+
+use chrono::{Days, Local, NaiveDate};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::commands::app_lock::require_unlocked;
+use crate::commands::get_profiles_cached;
+use crate::commands::history::history_store;
+use crate::commands::settings::read_settings;
+use crate::services::webhook::{post_text, WebhookPayload};
+use crate::services::weekly_report::{
+    compile_report, render_report, report_file_name, week_start, WeeklyReport,
+};
+
+/// How often the schedule checks whether last week's report is due
+const SCHEDULE_CHECK: Duration = Duration::from_secs(60 * 60);
+
+/// One report at a time (scheduled and on demand)
+static REPORT_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// A compiled report and where it went
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedReport {
+    pub report: WeeklyReport,
+    /// File the rendered report was saved to
+    pub path: String,
+    /// Whether the report was POSTed to `weekly_report.webhook_url`
+    pub delivered: bool,
+    /// Why the webhook delivery failed (the file is saved regardless)
+    pub webhook_error: Option<String>,
+}
+
+/// Monday of the week before the current one
+fn last_week_start() -> NaiveDate {
+    let this_week = week_start(Local::now().date_naive());
+    this_week
+        .checked_sub_days(Days::new(7))
+        .unwrap_or(this_week)
+}
+
+/// Compile the report of the week starting on `monday`, save it and POST it
+/// to the configured webhook
+pub async fn generate_report(monday: NaiveDate) -> Result<GeneratedReport, String> {
+    let _report = REPORT_LOCK.lock().await;
+    let settings = read_settings().await?;
+    if !settings.history.enabled {
+        return Err("The weekly report needs the history to be enabled".to_string());
+    }
+    let report_settings = settings.weekly_report;
+
+    let entries = history_store(&settings.history)?
+        .list()
+        .map_err(|e| format!("Failed to load history: {}", e))?;
+    let profile_names: HashMap<String, String> = get_profiles_cached()
+        .await
+        .map(|collection| {
+            collection
+                .profiles
+                .into_iter()
+                .map(|profile| (profile.id, profile.name))
+                .collect()
+        })
+        .unwrap_or_default();
+    let report = compile_report(
+        &entries,
+        &profile_names,
+        monday,
+        report_settings.highlights as usize,
+    );
+    let text = render_report(&report, report_settings.format);
+
+    let dir = report_settings.report_dir();
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(report_file_name(monday, report_settings.format));
+    tokio::fs::write(&path, &text)
+        .await
+        .map_err(|e| format!("Failed to save {}: {}", path.display(), e))?;
+    eprintln!(
+        "📊 [WEEKLY REPORT] Saved the report of the week of {} to {}",
+        monday,
+        path.display()
+    );
+
+    let mut delivered = false;
+    let mut webhook_error = None;
+    if let Some(url) = report_settings
+        .webhook_url
+        .as_ref()
+        .filter(|url| !url.trim().is_empty())
+    {
+        let payload = WebhookPayload {
+            text,
+            profile_id: None,
+            history_entry_id: None,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|now| now.as_secs()),
+        };
+        match post_text(url, &payload).await {
+            Ok(()) => delivered = true,
+            Err(e) => {
+                eprintln!("⚠️  [WEEKLY REPORT] Failed to deliver the report: {}", e);
+                webhook_error = Some(e.to_string());
+            }
+        }
+    }
+
+    Ok(GeneratedReport {
+        report,
+        path: path.to_string_lossy().to_string(),
+        delivered,
+        webhook_error,
+    })
+}
+
+/// Compile last week's report once it is due, while `weekly_report.enabled`
+/// (spawned at startup, runs for the app lifetime)
+///
+/// A week is reported once: its report file marks it done.
+pub async fn run_weekly_report_schedule() {
+    let mut interval = tokio::time::interval(SCHEDULE_CHECK);
+    loop {
+        interval.tick().await;
+        let settings = match read_settings().await {
+            Ok(settings) if settings.weekly_report.enabled && settings.history.enabled => {
+                settings.weekly_report
+            }
+            _ => continue,
+        };
+
+        let monday = last_week_start();
+        if settings
+            .report_dir()
+            .join(report_file_name(monday, settings.format))
+            .exists()
+        {
+            continue;
+        }
+        if let Err(e) = generate_report(monday).await {
+            eprintln!("⚠️  [WEEKLY REPORT] Scheduled report failed: {}", e);
+        }
+    }
+}
+
+/// Compile a weekly report now
+///
+/// `week_of` is any day (`YYYY-MM-DD`) of the week to report; last week when
+/// omitted.
+#[tauri::command]
+pub async fn generate_weekly_report(week_of: Option<String>) -> Result<GeneratedReport, String> {
+    require_unlocked()?;
+    let monday = match week_of {
+        Some(day) => week_start(
+            NaiveDate::parse_from_str(day.trim(), "%Y-%m-%d")
+                .map_err(|e| format!("Invalid week_of '{}': {}", day, e))?,
+        ),
+        None => last_week_start(),
+    };
+    generate_report(monday).await
+}
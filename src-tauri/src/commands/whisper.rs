@@ -1,13 +1,54 @@
-use crate::audio::{Encoder, OggVorbisEncoder};
-use crate::services::{OpenAIWhisperClient, TranscriptionResponse, WhisperClient, WhisperError};
-use std::path::PathBuf;
+use crate::audio::{take_streamed_output, Encoder};
+use crate::commands::encoder::configured_encoder;
+use crate::commands::local_inference::LocalInferenceCapabilitiesState;
+use crate::commands::local_models::model_store;
+use crate::commands::settings::{
+    read_settings, LocalInferenceSettings, LongRecordingSettings, SettingsConfig,
+};
+use crate::services::notifier::{Notifier, TauriNotifierService};
+use crate::services::{
+    run_chunk_pipeline, run_concurrent_chunks, split_recording, split_recording_by_duration,
+    stitch_transcriptions, ChunkProgress, GuardedWhisperClient, InferenceDevice,
+    LocalWhisperClient, LocalWhisperConfig, OpenAIWhisperClient, ResourceGuard, SizeGuard,
+    TranscriptionResponse, UploadCheck, WhisperClient, WhisperClientConfig, WhisperError,
+    WhisperProvider, WHISPER_UPLOAD_LIMIT_BYTES,
+};
+use crate::services::{
+    whisper_prompt::{fit_whisper_prompt, WhisperPrompt, WHISPER_PROMPT_MAX_TOKENS},
+    Profile,
+};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tauri::State;
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::Mutex;
 
+use crate::workflow_log;
+
 /// Global state for the Whisper client
 pub type WhisperClientState = Arc<Mutex<Option<Arc<dyn WhisperClient + Send + Sync>>>>;
 
+/// Whisper prompt of `profile`, trimmed to what Whisper reads (its keywords
+/// and the names in the prompt are kept first)
+pub fn profile_whisper_prompt(profile: &Profile) -> Option<WhisperPrompt> {
+    let prompt = profile.prompt.as_deref().filter(|p| !p.trim().is_empty())?;
+    let fitted = fit_whisper_prompt(
+        prompt,
+        profile.keywords.as_deref().unwrap_or_default(),
+        WHISPER_PROMPT_MAX_TOKENS,
+    );
+    if let Some(truncation) = &fitted.truncation {
+        eprintln!(
+            "⚠️  Whisper prompt of '{}' trimmed from {} to {} tokens ({} terms kept, {} dropped)",
+            profile.name,
+            truncation.original_tokens,
+            truncation.kept_tokens,
+            truncation.kept_terms.len(),
+            truncation.dropped_terms.len()
+        );
+    }
+    Some(fitted)
+}
+
 /// Test API key by making a simple request to OpenAI Chat Completions API
 /// This uses the same endpoint that the GPT formatter uses, so it's a more accurate test
 #[tauri::command]
@@ -20,8 +61,16 @@ pub async fn test_api_key(api_key: String) -> Result<String, String> {
         return Err("Invalid API key format. OpenAI API keys should start with 'sk-'".to_string());
     }
 
-    // Create a temporary client for testing
-    let client = reqwest::Client::new();
+    // Create a temporary client for testing, through the configured proxy
+    let network = read_settings()
+        .await
+        .map(|settings| settings.network)
+        .unwrap_or_default();
+    let client = network
+        .apply(reqwest::Client::builder())
+        .map_err(|e| e.to_string())?
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
     // Test the API key by making a minimal chat completion request
     // This is the same endpoint the app uses for GPT formatting
@@ -74,17 +123,56 @@ pub async fn test_api_key(api_key: String) -> Result<String, String> {
 }
 
 /// Initialize the Whisper client with API key
+///
+/// With the local provider, the API key is optional: when set, the cloud
+/// client takes over while the machine is too busy for local inference.
 #[tauri::command]
 pub async fn init_whisper_client(
+    app_handle: AppHandle,
     api_key: String,
     state: State<'_, WhisperClientState>,
 ) -> Result<String, String> {
-    if api_key.is_empty() {
-        return Err("API key cannot be empty".to_string());
-    }
-
-    let client =
-        Arc::new(OpenAIWhisperClient::new(api_key)) as Arc<dyn WhisperClient + Send + Sync>;
+    let settings = read_settings().await.ok();
+    let config = settings
+        .as_ref()
+        .map(|settings| WhisperClientConfig {
+            word_timestamps: settings.whisper.word_timestamps,
+            mode: settings.whisper.mode,
+            temperature: settings.whisper.temperature,
+            response_format: settings.whisper.response_format,
+            prompt_strategy: settings.whisper.prompt_strategy,
+            timeout_seconds: u64::from(settings.whisper.timeout_seconds),
+            min_upload_kbps: settings.whisper.min_upload_kbps,
+            retry: settings.whisper.retry_policy(),
+            network: settings.network.clone(),
+            ..WhisperClientConfig::default()
+        })
+        .unwrap_or_default();
+    let cloud = if api_key.is_empty() {
+        None
+    } else {
+        let client =
+            OpenAIWhisperClient::try_with_config(WhisperClientConfig { api_key, ..config })
+                .map_err(|e| e.to_string())?;
+        Some(Arc::new(client) as Arc<dyn WhisperClient + Send + Sync>)
+    };
+
+    let client = match settings {
+        Some(settings) if settings.whisper.provider == WhisperProvider::Local => {
+            let local = Arc::new(local_whisper_client(&app_handle, &settings).await?)
+                as Arc<dyn WhisperClient + Send + Sync>;
+            match cloud {
+                Some(cloud) => guard_local_whisper_client(
+                    app_handle.clone(),
+                    local,
+                    cloud,
+                    &settings.local_inference,
+                ),
+                None => local,
+            }
+        }
+        _ => cloud.ok_or("API key cannot be empty")?,
+    };
     let mut state_guard = state.lock().await;
     *state_guard = Some(client);
 
@@ -123,141 +211,172 @@ pub async fn transcribe_recorded_audio(
     let state_guard = state.lock().await;
 
     if let Some(ref client) = *state_guard {
-        let wav_path = PathBuf::from(wav_file_path);
-
-        // Debug: Check WAV file details
-        eprintln!("🔍 DEBUG: Input WAV file analysis:");
-        eprintln!("   📁 WAV path: {:?}", wav_path);
-        eprintln!(
-            "   📁 WAV absolute path: {:?}",
-            wav_path.canonicalize().unwrap_or_else(|_| wav_path.clone())
-        );
+        encode_and_transcribe(PathBuf::from(wav_file_path), prompt, client.as_ref()).await
+    } else {
+        Err("Whisper client not initialized. Call init_whisper_client first.".to_string())
+    }
+}
 
-        match tokio::fs::metadata(&wav_path).await {
-            Ok(metadata) => {
-                eprintln!(
-                    "   📊 WAV file size: {} bytes ({:.2} KB)",
-                    metadata.len(),
-                    metadata.len() as f64 / 1024.0
-                );
-                eprintln!("   ✅ WAV file exists and is readable");
-            }
-            Err(e) => {
-                eprintln!("   ❌ WAV file error: {}", e);
-                return Err(format!("WAV file not accessible: {}", e));
-            }
+/// Encode a recorded WAV file to OGG and transcribe it with the given client
+///
+/// Shared by `transcribe_recorded_audio` and the orchestration workflow, which may
+/// substitute a different client (e.g. the dry-run client).
+pub async fn encode_and_transcribe(
+    wav_path: PathBuf,
+    prompt: Option<String>,
+    client: &dyn WhisperClient,
+) -> Result<TranscriptionResponse, String> {
+    // Debug: Check WAV file details
+    workflow_log!("🔍 DEBUG: Input WAV file analysis:");
+    workflow_log!("   📁 WAV path: {:?}", wav_path);
+    workflow_log!(
+        "   📁 WAV absolute path: {:?}",
+        wav_path.canonicalize().unwrap_or_else(|_| wav_path.clone())
+    );
+
+    match tokio::fs::metadata(&wav_path).await {
+        Ok(metadata) => {
+            workflow_log!(
+                "   📊 WAV file size: {} bytes ({:.2} KB)",
+                metadata.len(),
+                metadata.len() as f64 / 1024.0
+            );
+            workflow_log!("   ✅ WAV file exists and is readable");
+        }
+        Err(e) => {
+            workflow_log!("   ❌ WAV file error: {}", e);
+            return Err(format!("WAV file not accessible: {}", e));
         }
+    }
 
-        // Step 1: Encode WAV to OGG
-        eprintln!("🎵 Step 1: Starting WAV to OGG encoding...");
-        let encoder = OggVorbisEncoder::new();
-        let ogg_info = encoder
+    // Step 1: Encode WAV to OGG (already done while recording when streamed)
+    workflow_log!("🎵 Step 1: Starting WAV to OGG encoding...");
+    let encoder = configured_encoder().await;
+    let ogg_info = match take_streamed_output(&wav_path, &encoder.options()).await {
+        Some(ogg_info) => {
+            workflow_log!("   ⚡ Using the OGG encoded while recording");
+            ogg_info
+        }
+        None => encoder
             .encode(&wav_path, None, None)
             .await
-            .map_err(|e| format!("Encoding failed: {}", e))?;
-
-        // Debug: Check OGG file details
-        eprintln!("🔍 DEBUG: Output OGG file analysis:");
-        eprintln!("   📁 OGG path: {:?}", ogg_info.path);
-        eprintln!(
-            "   📁 OGG absolute path: {:?}",
-            ogg_info
-                .path
-                .canonicalize()
-                .unwrap_or_else(|_| ogg_info.path.clone())
-        );
-        eprintln!(
-            "   📊 OGG estimated size: {} bytes ({:.2} KB)",
-            ogg_info.size_estimate,
-            ogg_info.size_estimate as f64 / 1024.0
+            .map_err(|e| format!("Encoding failed: {}", e))?,
+    };
+
+    // Debug: Check OGG file details
+    workflow_log!("🔍 DEBUG: Output OGG file analysis:");
+    workflow_log!("   📁 OGG path: {:?}", ogg_info.path);
+    workflow_log!(
+        "   📁 OGG absolute path: {:?}",
+        ogg_info
+            .path
+            .canonicalize()
+            .unwrap_or_else(|_| ogg_info.path.clone())
+    );
+    workflow_log!(
+        "   📊 OGG estimated size: {} bytes ({:.2} KB)",
+        ogg_info.size_estimate,
+        ogg_info.size_estimate as f64 / 1024.0
+    );
+
+    if let Some(actual_size) = ogg_info.actual_size {
+        workflow_log!(
+            "   📊 OGG actual size: {} bytes ({:.2} KB)",
+            actual_size,
+            actual_size as f64 / 1024.0
         );
+    }
 
-        if let Some(actual_size) = ogg_info.actual_size {
-            eprintln!(
-                "   📊 OGG actual size: {} bytes ({:.2} KB)",
-                actual_size,
-                actual_size as f64 / 1024.0
+    match tokio::fs::metadata(&ogg_info.path).await {
+        Ok(metadata) => {
+            workflow_log!(
+                "   📊 OGG file system size: {} bytes ({:.2} KB)",
+                metadata.len(),
+                metadata.len() as f64 / 1024.0
             );
-        }
+            workflow_log!("   ✅ OGG file exists and is readable");
 
-        match tokio::fs::metadata(&ogg_info.path).await {
-            Ok(metadata) => {
-                eprintln!(
-                    "   📊 OGG file system size: {} bytes ({:.2} KB)",
-                    metadata.len(),
-                    metadata.len() as f64 / 1024.0
-                );
-                eprintln!("   ✅ OGG file exists and is readable");
-
-                // Check file extension
-                if let Some(extension) = ogg_info.path.extension() {
-                    eprintln!("   🏷️  OGG file extension: {:?}", extension);
-                } else {
-                    eprintln!("   ⚠️  OGG file has no extension");
-                }
-            }
-            Err(e) => {
-                eprintln!("   ❌ OGG file error: {}", e);
-                return Err(format!("OGG file not accessible after encoding: {}", e));
+            // Check file extension
+            if let Some(extension) = ogg_info.path.extension() {
+                workflow_log!("   🏷️  OGG file extension: {:?}", extension);
+            } else {
+                workflow_log!("   ⚠️  OGG file has no extension");
             }
         }
-
-        // Try to determine file type using file command (if available)
-        if let Ok(output) = std::process::Command::new("file")
-            .arg(&ogg_info.path)
-            .output()
-        {
-            if let Ok(file_info) = String::from_utf8(output.stdout) {
-                eprintln!("   🔍 File type detection: {}", file_info.trim());
-            }
+        Err(e) => {
+            workflow_log!("   ❌ OGG file error: {}", e);
+            return Err(format!("OGG file not accessible after encoding: {}", e));
         }
+    }
 
-        eprintln!("🎵 Encoding completed successfully!");
-        eprintln!("📂 Files for manual inspection:");
-        eprintln!("   Input WAV:  {:?}", wav_path);
-        eprintln!("   Output OGG: {:?}", ogg_info.path);
-        eprintln!("💡 You can now examine these files with audio tools");
+    // Try to determine file type using file command (if available)
+    if let Ok(output) = std::process::Command::new("file")
+        .arg(&ogg_info.path)
+        .output()
+    {
+        if let Ok(file_info) = String::from_utf8(output.stdout) {
+            workflow_log!("   🔍 File type detection: {}", file_info.trim());
+        }
+    }
 
-        // Step 2: Transcribe the OGG file
-        eprintln!("🤖 Step 2: Starting transcription...");
-        eprintln!("   📁 Sending file: {:?}", ogg_info.path);
-        eprintln!("   🎯 Using prompt: {:?}", prompt);
+    workflow_log!("🎵 Encoding completed successfully!");
+    workflow_log!("📂 Files for manual inspection:");
+    workflow_log!("   Input WAV:  {:?}", wav_path);
+    workflow_log!("   Output OGG: {:?}", ogg_info.path);
+    workflow_log!("💡 You can now examine these files with audio tools");
 
-        let transcript = client
-            .transcribe(&ogg_info.path, prompt)
+    // Over the upload limit: transcribe overlapping chunks instead of failing
+    let encoded_size = match ogg_info.actual_size {
+        Some(size) => size,
+        None => tokio::fs::metadata(&ogg_info.path)
             .await
-            .map_err(|e| {
-                eprintln!("❌ Transcription failed for file: {:?}", ogg_info.path);
-                eprintln!("❌ Error details: {}", e);
-                format!("Transcription failed: {}", e)
-            })?;
-
-        eprintln!("✅ Transcription successful!");
-        eprintln!("   📝 Text length: {} characters", transcript.text.len());
-        eprintln!(
-            "   📝 First 100 chars: {:?}",
-            transcript.text.chars().take(100).collect::<String>()
-        );
-
-        // Step 3: Clean up the temporary OGG file (but warn first)
-        eprintln!("🧹 Step 3: Cleaning up temporary OGG file...");
-        eprintln!("   ⚠️  About to delete: {:?}", ogg_info.path);
-        eprintln!("   💡 If you want to keep the file for inspection, interrupt now!");
-
-        // Give a moment for the user to see the message
-        tokio::time::sleep(tokio::time::Duration::from_millis(2000)).await;
-
+            .map(|metadata| metadata.len())
+            .unwrap_or(ogg_info.size_estimate),
+    };
+    if encoded_size > WHISPER_UPLOAD_LIMIT_BYTES {
         if let Err(e) = tokio::fs::remove_file(&ogg_info.path).await {
-            eprintln!("⚠️  Warning: Failed to clean up temporary OGG file: {}", e);
-            eprintln!("   📁 File remains at: {:?}", ogg_info.path);
-        } else {
-            eprintln!("✅ Temporary OGG file cleaned up");
+            workflow_log!("⚠️  Warning: Failed to clean up oversized OGG file: {}", e);
         }
+        return transcribe_oversized(&wav_path, encoded_size, prompt, client).await;
+    }
+
+    // Step 2: Transcribe the OGG file
+    workflow_log!("🤖 Step 2: Starting transcription...");
+    workflow_log!("   📁 Sending file: {:?}", ogg_info.path);
+    workflow_log!("   🎯 Using prompt: {:?}", prompt);
 
-        Ok(transcript)
+    let transcript = client
+        .transcribe(&ogg_info.path, prompt)
+        .await
+        .map_err(|e| {
+            workflow_log!("❌ Transcription failed for file: {:?}", ogg_info.path);
+            workflow_log!("❌ Error details: {}", e);
+            format!("Transcription failed: {}", e)
+        })?;
+
+    workflow_log!("✅ Transcription successful!");
+    workflow_log!("   📝 Text length: {} characters", transcript.text.len());
+    workflow_log!(
+        "   📝 First 100 chars: {:?}",
+        transcript.text.chars().take(100).collect::<String>()
+    );
+
+    // Step 3: Clean up the temporary OGG file (but warn first)
+    workflow_log!("🧹 Step 3: Cleaning up temporary OGG file...");
+    workflow_log!("   ⚠️  About to delete: {:?}", ogg_info.path);
+    workflow_log!("   💡 If you want to keep the file for inspection, interrupt now!");
+
+    // Give a moment for the user to see the message
+    tokio::time::sleep(tokio::time::Duration::from_millis(2000)).await;
+
+    if let Err(e) = tokio::fs::remove_file(&ogg_info.path).await {
+        workflow_log!("⚠️  Warning: Failed to clean up temporary OGG file: {}", e);
+        workflow_log!("   📁 File remains at: {:?}", ogg_info.path);
     } else {
-        Err("Whisper client not initialized. Call init_whisper_client first.".to_string())
+        workflow_log!("✅ Temporary OGG file cleaned up");
     }
+
+    Ok(transcript)
 }
 
 /// Get Whisper client configuration and capabilities
@@ -273,11 +392,40 @@ pub fn get_whisper_info() -> serde_json::Value {
             "language_detection",
             "prompt_guided_transcription",
             "retry_on_failures",
-            "exponential_backoff"
+            "exponential_backoff",
+            "automatic_chunking"
         ]
     })
 }
 
+/// Drop the Whisper client (recordings cannot be transcribed until it is
+/// initialized again)
+///
+/// Waits for a transcription in flight to finish.
+#[tauri::command]
+pub async fn shutdown_whisper_client(
+    state: State<'_, WhisperClientState>,
+) -> Result<String, String> {
+    let mut state_guard = state.lock().await;
+    Ok(match state_guard.take() {
+        Some(_) => "Whisper client shut down".to_string(),
+        None => "Whisper client was not initialized".to_string(),
+    })
+}
+
+/// Initialize the Whisper client again from the settings (provider, API key,
+/// tuning, network)
+///
+/// The previous client is kept when the new one cannot be created.
+#[tauri::command]
+pub async fn reinit_whisper_client(
+    app_handle: AppHandle,
+    state: State<'_, WhisperClientState>,
+) -> Result<String, String> {
+    let api_key = read_settings().await?.whisper.api_key;
+    init_whisper_client(app_handle, api_key.trim().to_string(), state).await
+}
+
 /// Check if Whisper client is initialized
 #[tauri::command]
 pub async fn is_whisper_initialized(state: State<'_, WhisperClientState>) -> Result<bool, String> {
@@ -303,9 +451,311 @@ pub fn format_whisper_error(error: &WhisperError) -> String {
         }
         WhisperError::Api(msg) => format!("API error: {}", msg),
         WhisperError::InvalidResponse(msg) => format!("Invalid response: {}", msg),
-        WhisperError::RateLimit { message } => format!("Rate limited: {}", message),
-        WhisperError::Timeout { timeout_seconds } => {
-            format!("Request timed out after {}s", timeout_seconds)
-        }
+        WhisperError::RateLimit { message, .. } => format!("Rate limited: {}", message),
+        WhisperError::Timeout { timeout_seconds } => format!(
+            "Request timed out after {}s (timeout scaled to the file size; lower whisper.min_upload_kbps on slow connections)",
+            timeout_seconds
+        ),
     }
 }
+
+/// Check the recording at `wav_path` with `SizeGuard` before its upload
+///
+/// Its encoded size is predicted from its duration and the encoder settings.
+/// The user is warned when it will be transcribed in parts; recordings that
+/// cannot be split are refused.
+pub async fn check_upload_size(
+    wav_path: &Path,
+    app_handle: &AppHandle,
+) -> Result<UploadCheck, String> {
+    let reader =
+        hound::WavReader::open(wav_path).map_err(|e| format!("WAV file not accessible: {}", e))?;
+    let spec = reader.spec();
+    let total_frames = u64::from(reader.duration());
+    let duration_seconds = total_frames as f64 / f64::from(spec.sample_rate.max(1));
+    let predicted_bytes = configured_encoder().await.estimate_file_size(
+        duration_seconds,
+        spec.channels,
+        spec.sample_rate,
+    );
+
+    let size_guard = SizeGuard::new(Arc::new(TauriNotifierService::new(app_handle.clone())));
+    size_guard
+        .check_upload(total_frames, spec.sample_rate, predicted_bytes)
+        .await
+        .map_err(|e| format!("Recording refused before upload: {}", e))
+}
+
+/// Split a recording whose encoded file exceeds the Whisper upload limit and
+/// transcribe it chunk by chunk, stitching the transcripts back together
+async fn transcribe_oversized(
+    wav_path: &Path,
+    encoded_size: u64,
+    prompt: Option<String>,
+    client: &dyn WhisperClient,
+) -> Result<TranscriptionResponse, String> {
+    workflow_log!(
+        "✂️  [CHUNKS] Encoded file is {:.1}MB, over the {:.0}MB upload limit: splitting",
+        encoded_size as f64 / (1024.0 * 1024.0),
+        WHISPER_UPLOAD_LIMIT_BYTES as f64 / (1024.0 * 1024.0)
+    );
+    let chunk_dir =
+        tempfile::tempdir().map_err(|e| format!("Failed to create chunk directory: {}", e))?;
+    let chunks = {
+        let wav_path = wav_path.to_path_buf();
+        let output_dir = chunk_dir.path().to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            split_recording(
+                &wav_path,
+                encoded_size,
+                WHISPER_UPLOAD_LIMIT_BYTES,
+                &output_dir,
+            )
+        })
+        .await
+        .map_err(|e| format!("Recording split failed: {}", e))?
+        .map_err(|e| e.to_string())?
+    };
+
+    let offsets: Vec<f64> = chunks.iter().map(|chunk| chunk.offset_seconds).collect();
+    let transcripts = encode_and_transcribe_chunks(
+        chunks.into_iter().map(|chunk| chunk.path).collect(),
+        prompt,
+        client,
+    )
+    .await?;
+
+    let transcript = stitch_transcriptions(offsets.into_iter().zip(transcripts).collect());
+    workflow_log!(
+        "✅ [CHUNKS] Stitched transcript: {} characters",
+        transcript.text.len()
+    );
+    Ok(transcript)
+}
+
+/// Transcribe a recording, in chunks sent concurrently when it is longer than
+/// `settings.threshold_minutes`
+///
+/// Consecutive chunks overlap by a few seconds; their transcripts are merged
+/// in recording order without the repeated words. `progress` is told when
+/// each chunk starts and ends. Shorter recordings go to [`encode_and_transcribe`].
+pub async fn encode_and_transcribe_long(
+    wav_path: PathBuf,
+    prompt: Option<String>,
+    client: &dyn WhisperClient,
+    settings: &LongRecordingSettings,
+    progress: impl Fn(ChunkProgress),
+) -> Result<TranscriptionResponse, String> {
+    let duration_seconds = hound::WavReader::open(&wav_path)
+        .map(|reader| f64::from(reader.duration()) / f64::from(reader.spec().sample_rate.max(1)))
+        .map_err(|e| format!("WAV file not accessible: {}", e))?;
+    if settings.threshold_minutes == 0
+        || duration_seconds <= f64::from(settings.threshold_minutes) * 60.0
+    {
+        return encode_and_transcribe(wav_path, prompt, client).await;
+    }
+
+    workflow_log!(
+        "✂️  [CHUNKS] {:.0}-minute recording: transcribing {}-minute chunks, {} at a time",
+        duration_seconds / 60.0,
+        settings.chunk_minutes,
+        settings.max_parallel
+    );
+    // The chunks are encoded on their own: the file encoded while recording is not used
+    let encoder = configured_encoder().await;
+    if let Some(ogg_info) = take_streamed_output(&wav_path, &encoder.options()).await {
+        let _ = tokio::fs::remove_file(&ogg_info.path).await;
+    }
+
+    let chunk_dir =
+        tempfile::tempdir().map_err(|e| format!("Failed to create chunk directory: {}", e))?;
+    let chunks = {
+        let output_dir = chunk_dir.path().to_path_buf();
+        let chunk_seconds = f64::from(settings.chunk_minutes.max(1)) * 60.0;
+        tokio::task::spawn_blocking(move || {
+            split_recording_by_duration(&wav_path, chunk_seconds, &output_dir)
+        })
+        .await
+        .map_err(|e| format!("Recording split failed: {}", e))?
+        .map_err(|e| e.to_string())?
+    };
+
+    let offsets: Vec<f64> = chunks.iter().map(|chunk| chunk.offset_seconds).collect();
+    let encoder = &encoder;
+    let transcripts = run_concurrent_chunks(
+        chunks.into_iter().map(|chunk| chunk.path).collect(),
+        settings.max_parallel as usize,
+        |chunk_path: PathBuf| {
+            let prompt = prompt.clone();
+            async move {
+                let ogg_info = encoder
+                    .encode(&chunk_path, None, None)
+                    .await
+                    .map_err(|e| format!("Encoding failed: {}", e))?;
+                let transcript = client
+                    .transcribe(&ogg_info.path, prompt)
+                    .await
+                    .map_err(|e| format!("Transcription failed: {}", e));
+                let _ = tokio::fs::remove_file(&ogg_info.path).await;
+                transcript
+            }
+        },
+        progress,
+    )
+    .await?;
+
+    let chunk_count = offsets.len();
+    let transcript = stitch_transcriptions(offsets.into_iter().zip(transcripts).collect());
+    workflow_log!(
+        "✅ [CHUNKS] Merged {} chunk transcripts: {} characters",
+        chunk_count,
+        transcript.text.len()
+    );
+    Ok(transcript)
+}
+
+/// Encode and transcribe the chunks of a long recording, in order
+///
+/// Encoding chunk N+1 overlaps the upload and transcription of chunk N,
+/// which roughly halves the end-to-end latency of long recordings.
+pub async fn encode_and_transcribe_chunks(
+    wav_chunks: Vec<PathBuf>,
+    prompt: Option<String>,
+    client: &dyn WhisperClient,
+) -> Result<Vec<TranscriptionResponse>, String> {
+    let encoder = Arc::new(configured_encoder().await);
+    workflow_log!(
+        "🎵 [CHUNKS] Pipelining encode + transcription of {} chunks",
+        wav_chunks.len()
+    );
+
+    run_chunk_pipeline(
+        wav_chunks,
+        move |wav_path: PathBuf| {
+            let encoder = Arc::clone(&encoder);
+            async move {
+                encoder
+                    .encode(&wav_path, None, None)
+                    .await
+                    .map(|ogg_info| ogg_info.path)
+                    .map_err(|e| format!("Encoding failed: {}", e))
+            }
+        },
+        |ogg_path: PathBuf| {
+            let prompt = prompt.clone();
+            async move {
+                let transcript = client
+                    .transcribe(&ogg_path, prompt)
+                    .await
+                    .map_err(|e| format!("Transcription failed: {}", e));
+                if let Err(e) = tokio::fs::remove_file(&ogg_path).await {
+                    workflow_log!(
+                        "⚠️  Warning: Failed to clean up chunk {:?}: {}",
+                        ogg_path,
+                        e
+                    );
+                }
+                transcript
+            }
+        },
+    )
+    .await
+}
+
+/// whisper.cpp client for the local inference settings, on the device they
+/// resolve to
+pub async fn local_whisper_client(
+    app_handle: &AppHandle,
+    settings: &SettingsConfig,
+) -> Result<LocalWhisperClient, String> {
+    let local = &settings.local_inference;
+    let model_path = match &local.model_path {
+        Some(path) => PathBuf::from(path),
+        None => model_store(app_handle)?
+            .model_path(&local.model)
+            .map_err(|e| {
+                format!(
+                    "Local Whisper model '{}' is not available: {}",
+                    local.model, e
+                )
+            })?,
+    };
+
+    let capabilities = match app_handle.try_state::<LocalInferenceCapabilitiesState>() {
+        Some(state) => state.lock().await.clone(),
+        None => None,
+    };
+    let (use_gpu, gpu_index, threads) = match capabilities {
+        Some(capabilities) => {
+            let selection = capabilities.resolve(local.device, local.gpu_index);
+            if let Some(reason) = &selection.fallback_reason {
+                eprintln!("⚠️  [LOCAL] {}", reason);
+            }
+            (
+                selection.device != InferenceDevice::Cpu,
+                selection.gpu_index,
+                capabilities.cpu_threads,
+            )
+        }
+        None => (
+            local.device != InferenceDevice::Cpu,
+            (local.device == InferenceDevice::Cuda).then_some(local.gpu_index),
+            LocalWhisperConfig::default().threads,
+        ),
+    };
+
+    eprintln!(
+        "🖥️  [LOCAL] Transcribing with whisper.cpp and {:?} (GPU: {})",
+        model_path, use_gpu
+    );
+    Ok(LocalWhisperClient::new(LocalWhisperConfig {
+        binary: local
+            .whisper_binary
+            .as_deref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| LocalWhisperConfig::default().binary),
+        model_path,
+        // whisper.cpp gains little past 8 threads
+        threads: threads.clamp(1, 8),
+        use_gpu,
+        gpu_index,
+        mode: settings.whisper.mode,
+        ..LocalWhisperConfig::default()
+    }))
+}
+
+/// Wrap a local Whisper backend so it falls back to the cloud client under load
+///
+/// Each fallback emits a "local-inference-fallback" event and a warning
+/// notification, so the user knows why the cloud provider was used.
+pub fn guard_local_whisper_client(
+    app_handle: AppHandle,
+    local: Arc<dyn WhisperClient + Send + Sync>,
+    cloud: Arc<dyn WhisperClient + Send + Sync>,
+    settings: &LocalInferenceSettings,
+) -> Arc<dyn WhisperClient + Send + Sync> {
+    let guard = Arc::new(ResourceGuard::new(settings.guard_config()));
+    let client = GuardedWhisperClient::new(local, cloud, guard).on_fallback(move |shortage| {
+        if let Err(e) = app_handle.emit(
+            "local-inference-fallback",
+            serde_json::json!({ "reason": shortage.to_string() }),
+        ) {
+            eprintln!(
+                "⚠️  Warning: Failed to emit local-inference-fallback: {}",
+                e
+            );
+        }
+
+        let notifier = TauriNotifierService::new(app_handle.clone());
+        let message = format!(
+            "Local transcription unavailable ({}), using the cloud provider",
+            shortage
+        );
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = notifier.warning(&message).await {
+                eprintln!("⚠️  Warning: Failed to show fallback notification: {}", e);
+            }
+        });
+    });
+    Arc::new(client)
+}
@@ -0,0 +1,17 @@
+//! Commands for the per-window event subscriptions
+
+use tauri::WebviewWindow;
+
+use crate::services::event_targets;
+
+/// Subscribe the calling window to `events` (one call per listener)
+#[tauri::command]
+pub fn subscribe_window_events(events: Vec<String>, window: WebviewWindow) {
+    event_targets::subscribe_window(window.label(), &events);
+}
+
+/// Remove one listener of `events` from the calling window
+#[tauri::command]
+pub fn unsubscribe_window_events(events: Vec<String>, window: WebviewWindow) {
+    event_targets::unsubscribe_window(window.label(), &events);
+}
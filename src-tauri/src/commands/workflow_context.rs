@@ -0,0 +1,77 @@
+//! Commands for attaching a reference document to the next workflow
+
+use std::path::Path;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::services::profile_engine::Profile;
+use crate::services::reference_context::{
+    load_reference, reference_section, REFERENCE_TOKEN_BUDGET,
+};
+
+/// Reference text attached to the next workflow only
+pub type WorkflowContextState = Arc<Mutex<Option<String>>>;
+
+/// Attach a reference document to the next workflow
+///
+/// Give either the `path` of a text file or the `text` itself; giving neither
+/// detaches the current one. The reference is included (shortened if needed)
+/// in the formatting prompt of the next workflow, in place of the profile's
+/// reference file.
+#[tauri::command]
+pub async fn set_workflow_context(
+    path: Option<String>,
+    text: Option<String>,
+    context_state: State<'_, WorkflowContextState>,
+) -> Result<String, String> {
+    let reference = match (path, text) {
+        (Some(_), Some(_)) => return Err("Give either a file path or a text, not both".to_string()),
+        (Some(path), None) => Some(
+            tokio::task::spawn_blocking(move || load_reference(Path::new(&path)))
+                .await
+                .map_err(|e| format!("Loading task failed: {}", e))?
+                .map_err(|e| e.to_string())?,
+        ),
+        (None, Some(text)) if !text.trim().is_empty() => Some(text),
+        (None, _) => None,
+    };
+
+    let message = match &reference {
+        Some(text) => format!(
+            "Reference attached to the next workflow ({} characters)",
+            text.chars().count()
+        ),
+        None => "Reference detached".to_string(),
+    };
+    eprintln!("📎 [CONTEXT] {}", message);
+    *context_state.lock().await = reference;
+    Ok(message)
+}
+
+/// Reference section for the formatting prompt of the current workflow
+///
+/// Consumes the reference attached with `set_workflow_context`, falling back
+/// to the profile's reference file.
+pub async fn take_reference_prompt(
+    context_state: &WorkflowContextState,
+    profile: Option<&Profile>,
+) -> Option<String> {
+    let reference = match context_state.lock().await.take() {
+        Some(text) => text,
+        None => {
+            let path = profile?.reference_file.clone()?;
+            let loaded = tokio::task::spawn_blocking(move || load_reference(Path::new(&path)))
+                .await
+                .ok()?;
+            match loaded {
+                Ok(text) => text,
+                Err(e) => {
+                    eprintln!("⚠️  Warning: Ignoring the profile reference file: {}", e);
+                    return None;
+                }
+            }
+        }
+    };
+    reference_section(&reference, REFERENCE_TOKEN_BUDGET)
+}
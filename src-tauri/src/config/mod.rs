@@ -1,3 +1,5 @@
+pub mod sanitize;
 pub mod validator;
 
+pub use sanitize::sanitize_settings;
 pub use validator::{validate_config_files, validate_config_files_strict, ConfigError};
@@ -3,7 +3,9 @@
 //! This module validates settings.json and profiles.json against predefined JSON schemas
 //! to ensure the application starts with valid configurations.
 
-use crate::utils::find_config_file_path;
+use crate::paths::find_config_file_path;
+use crate::services::output_files::FilenameTemplate;
+use crate::services::profile_engine::LEGACY_CLIPBOARD_PROFILE_ID;
 use jsonschema::{Draft, JSONSchema};
 use serde_json::Value;
 use std::fs;
@@ -74,14 +76,70 @@ const SETTINGS_SCHEMA: &str = r#"{
           "minimum": 1,
           "maximum": 300,
           "default": 30,
-          "description": "Request timeout in seconds"
+          "description": "Request timeout in seconds, on top of the upload time (see min_upload_kbps)"
         },
         "max_retries": {
           "type": "integer",
           "minimum": 0,
           "maximum": 10,
           "default": 3,
-          "description": "Maximum number of retries for 5xx errors"
+          "description": "Maximum number of retries for server errors, network errors and rate limits"
+        },
+        "word_timestamps": {
+          "type": "boolean",
+          "default": false,
+          "description": "Request word-level timestamps along with segment timestamps"
+        },
+        "mode": {
+          "type": "string",
+          "enum": ["transcribe", "translate"],
+          "default": "transcribe",
+          "description": "Transcribe in the spoken language, or translate the speech to English (profiles can override this)"
+        },
+        "provider": {
+          "type": "string",
+          "enum": ["openai", "local"],
+          "default": "openai",
+          "description": "Transcribe with the API, or offline with whisper.cpp (configured in local_inference)"
+        },
+        "temperature": {
+          "type": ["number", "null"],
+          "minimum": 0.0,
+          "maximum": 1.0,
+          "default": null,
+          "description": "Sampling temperature (null lets the API choose; 0 is deterministic)"
+        },
+        "response_format": {
+          "type": "string",
+          "enum": ["verbose_json", "json", "text"],
+          "default": "verbose_json",
+          "description": "Response format; only verbose_json returns segments and word timings"
+        },
+        "prompt_strategy": {
+          "type": "string",
+          "enum": ["full", "vocabulary", "none"],
+          "default": "full",
+          "description": "Send the whole prompt, only the vocabulary it mentions, or no prompt"
+        },
+        "min_upload_kbps": {
+          "type": "integer",
+          "minimum": 0,
+          "default": 256,
+          "description": "Slowest upload bandwidth (kbit/s) the request timeout allows for; the upload time is added to timeout_seconds (0 = fixed timeout)"
+        },
+        "retry_base_delay_ms": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 60000,
+          "default": 1000,
+          "description": "Delay before the first retry in milliseconds, doubled at each retry (with jitter)"
+        },
+        "retry_max_delay_ms": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 600000,
+          "default": 30000,
+          "description": "Longest delay between two attempts in milliseconds; a rate limit asking to wait longer fails right away"
         }
       },
       "required": ["api_key"],
@@ -99,7 +157,7 @@ const SETTINGS_SCHEMA: &str = r#"{
           "minimum": 8000,
           "maximum": 48000,
           "default": 44100,
-          "description": "Audio sample rate in Hz"
+          "description": "Sample rate asked of the input device in Hz (another supported rate is used otherwise; recordings are resampled to 48 kHz)"
         },
         "buffer_size": {
           "type": "integer",
@@ -107,6 +165,67 @@ const SETTINGS_SCHEMA: &str = r#"{
           "maximum": 8192,
           "default": 1024,
           "description": "Audio buffer size in samples"
+        },
+        "gain_db": {
+          "type": "number",
+          "minimum": -30,
+          "maximum": 30,
+          "default": 0,
+          "description": "Input gain in dB applied while recording"
+        },
+        "auto_gain": {
+          "type": "boolean",
+          "default": false,
+          "description": "Adjust the input gain automatically (AGC) instead of using gain_db"
+        },
+        "pause_media": {
+          "type": "boolean",
+          "default": false,
+          "description": "Pause the playing media players (MPRIS, system media sessions) while recording and resume them afterwards"
+        },
+        "auto_stop_silence_seconds": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 600,
+          "default": 0,
+          "description": "Stop the recording after this many seconds of silence once speech was heard (0 disables the auto-stop)"
+        },
+        "max_recording_seconds": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 86400,
+          "default": 0,
+          "description": "Stop and process the recording once it reaches this many seconds (0 disables the limit)"
+        },
+        "pre_roll_seconds": {
+          "type": "number",
+          "minimum": 0,
+          "maximum": 2,
+          "default": 0,
+          "description": "Seconds of audio kept from before the recording starts, so the first words are not cut off (0 = off, otherwise 0.5-2; keeps the microphone open while idle)"
+        },
+        "capture_source": {
+          "type": "string",
+          "enum": ["microphone", "system", "mixed"],
+          "default": "microphone",
+          "description": "microphone records the input device, system the system output (loopback, e.g. meeting audio), mixed both; the loopback device is interview.loopback_device"
+        },
+        "noise_suppression": {
+          "type": "boolean",
+          "default": false,
+          "description": "Suppress steady background noise (fans, hum) in recordings before encoding"
+        },
+        "loudness_normalization": {
+          "type": "boolean",
+          "default": false,
+          "description": "Normalize each recording to target_lufs once it stops, so quiet microphones are leveled before upload"
+        },
+        "target_lufs": {
+          "type": "number",
+          "minimum": -40,
+          "maximum": -6,
+          "default": -18,
+          "description": "Integrated loudness (LUFS, ITU-R BS.1770) recordings are normalized to; peaks are kept under -1 dBFS"
         }
       },
       "additionalProperties": false
@@ -127,6 +246,40 @@ const SETTINGS_SCHEMA: &str = r#"{
           "maximum": 25,
           "default": 23,
           "description": "Maximum file size in MB"
+        },
+        "complexity": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 10,
+          "default": 10,
+          "description": "Encoder complexity: lower values save CPU (resampling quality in voip mode)"
+        },
+        "application": {
+          "type": "string",
+          "enum": ["voip", "audio"],
+          "default": "audio",
+          "description": "voip encodes speech at 16kHz (less CPU, smaller files), audio keeps full bandwidth"
+        },
+        "dtx": {
+          "type": "boolean",
+          "default": false,
+          "description": "Encode near-silent passages as silence to save bits"
+        },
+        "downmix_to_mono": {
+          "type": "boolean",
+          "default": true,
+          "description": "Average multi-channel input into mono before encoding (about half the upload size of a stereo recording)"
+        },
+        "format": {
+          "type": "string",
+          "enum": ["ogg", "mp3", "flac"],
+          "default": "ogg",
+          "description": "Upload format: ogg (OGG/Vorbis), mp3 or flac (lossless, several times larger)"
+        },
+        "vbr": {
+          "type": "boolean",
+          "default": true,
+          "description": "Variable bitrate (smaller speech recordings); off caps the OGG bitrate at the target for predictable sizes"
         }
       },
       "additionalProperties": false
@@ -144,6 +297,680 @@ const SETTINGS_SCHEMA: &str = r#"{
           "type": "boolean",
           "default": false,
           "description": "Start recording automatically on app launch"
+        },
+        "tray_only": {
+          "type": "boolean",
+          "default": false,
+          "description": "Never create the main window: work from the tray, shortcuts and notifications, with results copied to the clipboard (applied at launch)"
+        }
+      },
+      "additionalProperties": false
+    },
+    "formatting": {
+      "type": "object",
+      "properties": {
+        "enabled": {
+          "type": "boolean",
+          "default": true,
+          "description": "Run GPT formatting after transcription (false = transcription only)"
+        },
+        "max_retries": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 10,
+          "default": 3,
+          "description": "Maximum number of retries of formatting requests for server errors, network errors and rate limits"
+        },
+        "retry_base_delay_ms": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 60000,
+          "default": 1000,
+          "description": "Delay before the first retry in milliseconds, doubled at each retry (with jitter)"
+        },
+        "retry_max_delay_ms": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 600000,
+          "default": 30000,
+          "description": "Longest delay between two attempts in milliseconds; a rate limit asking to wait longer fails right away"
+        }
+      },
+      "additionalProperties": false
+    },
+    "clipboard": {
+      "type": "object",
+      "properties": {
+        "auto_copy": {
+          "type": "boolean",
+          "default": true,
+          "description": "Copy the final text to the clipboard automatically"
+        },
+        "min_confidence": {
+          "type": "number",
+          "minimum": 0,
+          "maximum": 1,
+          "default": 0,
+          "description": "Keep transcripts below this confidence (0 to 1) for review instead of copying them (0 = always copy)"
+        }
+      },
+      "additionalProperties": false
+    },
+    "workflow": {
+      "type": "object",
+      "properties": {
+        "dry_run": {
+          "type": "boolean",
+          "default": false,
+          "description": "Developer mode: mock API calls and leave the clipboard untouched"
+        },
+        "retry_budget": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 20,
+          "default": 3,
+          "description": "Number of manual retries allowed for each failed workflow"
+        },
+        "duplicate_window_seconds": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 600,
+          "default": 30,
+          "description": "Hold a recording that repeats the previous one within this many seconds (e.g. a double shortcut press) as a failed workflow to process or discard, instead of transcribing it twice (0 = off)"
+        },
+        "confirm_before_transcription": {
+          "type": "boolean",
+          "default": false,
+          "description": "Hold each recording until the user confirms it (after listening to it with play_last_recording) before sending it to Whisper"
+        }
+      },
+      "additionalProperties": false
+    },
+    "local_api": {
+      "type": "object",
+      "properties": {
+        "enabled": {
+          "type": "boolean",
+          "default": false,
+          "description": "Serve the local HTTP API (e.g. /metrics) on 127.0.0.1"
+        },
+        "port": {
+          "type": "integer",
+          "minimum": 1024,
+          "maximum": 65535,
+          "default": 8765,
+          "description": "TCP port of the local HTTP API"
+        },
+        "lan_pairing": {
+          "type": "boolean",
+          "default": false,
+          "description": "Also listen on the LAN so paired phones can use the remote control"
+        }
+      },
+      "additionalProperties": false
+    },
+    "live_captions": {
+      "type": "object",
+      "properties": {
+        "enabled": {
+          "type": "boolean",
+          "default": false,
+          "description": "Write partial transcripts to a text file while recording (e.g. for OBS)"
+        },
+        "output_path": {
+          "type": ["string", "null"],
+          "default": null,
+          "description": "Caption file path (null = live_captions.txt in the config directory)"
+        },
+        "cadence_seconds": {
+          "type": "integer",
+          "minimum": 1,
+          "maximum": 30,
+          "default": 3,
+          "description": "Seconds between caption refreshes"
+        },
+        "window_seconds": {
+          "type": "integer",
+          "minimum": 2,
+          "maximum": 30,
+          "default": 10,
+          "description": "Seconds of recent audio transcribed for each caption"
+        }
+      },
+      "additionalProperties": false
+    },
+    "interview": {
+      "type": "object",
+      "properties": {
+        "loopback_device": {
+          "type": ["string", "null"],
+          "default": null,
+          "description": "Loopback device recorded as the remote party in interviews and by the system/mixed capture sources (null = auto-detect)"
+        }
+      },
+      "additionalProperties": false
+    },
+    "local_inference": {
+      "type": "object",
+      "properties": {
+        "enabled": {
+          "type": "boolean",
+          "default": false,
+          "description": "Run Whisper/LLM inference locally instead of with the cloud provider"
+        },
+        "max_concurrent_jobs": {
+          "type": "integer",
+          "minimum": 1,
+          "maximum": 8,
+          "default": 1,
+          "description": "Local inference jobs allowed to run at the same time"
+        },
+        "min_free_memory_mb": {
+          "type": "integer",
+          "minimum": 0,
+          "default": 2048,
+          "description": "Free memory (MB) required to start a local job; otherwise the cloud provider is used"
+        },
+        "max_load_per_core": {
+          "type": "number",
+          "minimum": 0.1,
+          "maximum": 4.0,
+          "default": 0.85,
+          "description": "Highest load average per CPU core at which local jobs start; otherwise the cloud provider is used"
+        },
+        "device": {
+          "type": "string",
+          "enum": ["cpu", "cuda", "metal"],
+          "default": "cpu",
+          "description": "Compute device for local backends (falls back to cpu when unavailable)"
+        },
+        "gpu_index": {
+          "type": "integer",
+          "minimum": 0,
+          "default": 0,
+          "description": "CUDA GPU used when several are installed"
+        },
+        "model": {
+          "type": "string",
+          "minLength": 1,
+          "default": "base",
+          "description": "Local Whisper model (e.g. tiny, base.en, small, large-v3)"
+        },
+        "model_path": {
+          "type": ["string", "null"],
+          "description": "GGML model file used instead of the downloaded model"
+        },
+        "whisper_binary": {
+          "type": ["string", "null"],
+          "description": "whisper.cpp command-line tool (null = whisper-cli on the PATH)"
+        }
+      },
+      "additionalProperties": false
+    },
+    "output": {
+      "type": "object",
+      "properties": {
+        "save_to_file": {
+          "type": "boolean",
+          "default": false,
+          "description": "Save each final text to a file"
+        },
+        "directory": {
+          "type": ["string", "null"],
+          "default": null,
+          "description": "Output directory (null = outputs in the data directory)"
+        },
+        "filename_template": {
+          "type": "string",
+          "minLength": 1,
+          "default": "{date}_{profile}_{title}.md",
+          "description": "File name template; variables: {date}, {time}, {profile}, {title}"
+        },
+        "timestamps": {
+          "type": "string",
+          "enum": ["off", "paragraph", "segment"],
+          "default": "off",
+          "description": "Insert [hh:mm:ss] timestamps at paragraph or segment boundaries of the transcript"
+        },
+        "paragraphize": {
+          "type": "boolean",
+          "default": true,
+          "description": "Split transcripts that skip GPT formatting into paragraphs at pauses and sentence ends"
+        },
+        "restore_punctuation": {
+          "type": "boolean",
+          "default": true,
+          "description": "Restore punctuation and casing when the provider returns lowercase unpunctuated text and GPT formatting is skipped"
+        },
+        "chapters": {
+          "type": "string",
+          "enum": ["off", "local", "llm"],
+          "default": "off",
+          "description": "Split long final texts into titled chapters with a table of contents, at pauses (local) or by topic (llm)"
+        },
+        "chapters_min_words": {
+          "type": "integer",
+          "minimum": 100,
+          "maximum": 100000,
+          "default": 800,
+          "description": "Words a final text has at least to be split into chapters"
+        }
+      },
+      "additionalProperties": false
+    },
+    "history": {
+      "type": "object",
+      "properties": {
+        "enabled": {
+          "type": "boolean",
+          "default": true,
+          "description": "Keep the final text of each workflow in the history"
+        },
+        "archive_audio": {
+          "type": "boolean",
+          "default": false,
+          "description": "Also keep the encoded recording of each workflow"
+        },
+        "retention_days": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 3650,
+          "default": 30,
+          "description": "Days the history and archived recordings are kept before being purged (0 = forever)"
+        },
+        "encrypt_at_rest": {
+          "type": "boolean",
+          "default": false,
+          "description": "Encrypt the history and archived recordings with a key stored in the OS keyring"
+        }
+      },
+      "additionalProperties": false
+    },
+    "recording_archive": {
+      "type": "object",
+      "properties": {
+        "enabled": {
+          "type": "boolean",
+          "default": false,
+          "description": "Keep a copy of each recording instead of only deleting the temporary WAV"
+        },
+        "directory": {
+          "type": ["string", "null"],
+          "default": null,
+          "description": "Archive directory (null = recordings in the data directory)"
+        },
+        "retention_count": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 100000,
+          "default": 50,
+          "description": "Newest archived recordings kept; older ones are deleted (0 = keep all)"
+        },
+        "format": {
+          "type": "string",
+          "enum": ["ogg", "mp3", "flac", "wav"],
+          "default": "ogg",
+          "description": "Archive the recording encoded (ogg, mp3, flac) or the original one (wav)"
+        }
+      },
+      "additionalProperties": false
+    },
+    "tts": {
+      "type": "object",
+      "properties": {
+        "provider": {
+          "type": "string",
+          "enum": ["system", "openai"],
+          "default": "system",
+          "description": "Speech backend used to read the final text aloud"
+        },
+        "voice": {
+          "type": ["string", "null"],
+          "default": null,
+          "description": "Voice name (null = backend default)"
+        },
+        "speed": {
+          "type": "number",
+          "minimum": 0.25,
+          "maximum": 4.0,
+          "default": 1.0,
+          "description": "Speaking rate multiplier (1.0 = normal)"
+        }
+      },
+      "additionalProperties": false
+    },
+    "context_memory": {
+      "type": "object",
+      "properties": {
+        "enabled": {
+          "type": "boolean",
+          "default": false,
+          "description": "Include the last final texts of the session in the GPT prompt to keep names and terminology consistent"
+        },
+        "max_entries": {
+          "type": "integer",
+          "minimum": 1,
+          "maximum": 20,
+          "default": 5,
+          "description": "Number of final texts remembered during the session"
+        },
+        "token_budget": {
+          "type": "integer",
+          "minimum": 50,
+          "maximum": 2000,
+          "default": 300,
+          "description": "Maximum size of the context added to the prompt, in estimated tokens"
+        }
+      },
+      "additionalProperties": false
+    },
+    "profile_suggestion": {
+      "type": "object",
+      "properties": {
+        "mode": {
+          "type": "string",
+          "enum": ["off", "suggest", "auto"],
+          "default": "off",
+          "description": "Suggest a profile from the transcript keywords (suggest) or apply it to the workflow (auto)"
+        },
+        "min_matches": {
+          "type": "integer",
+          "minimum": 1,
+          "default": 2,
+          "description": "Keyword matches needed before a profile is suggested"
+        }
+      },
+      "additionalProperties": false
+    },
+    "profile_sync": {
+      "type": "object",
+      "properties": {
+        "enabled": {
+          "type": "boolean",
+          "default": false,
+          "description": "Sync the profiles with the team on schedule"
+        },
+        "backend": {
+          "type": "string",
+          "enum": ["folder", "git"],
+          "default": "folder",
+          "description": "Share profiles.json through a folder (Dropbox, network share) or a git remote"
+        },
+        "folder": {
+          "type": ["string", "null"],
+          "default": null,
+          "description": "Shared directory holding profiles.json (folder backend)"
+        },
+        "git_remote": {
+          "type": ["string", "null"],
+          "default": null,
+          "description": "Remote URL of the repository holding profiles.json (git backend)"
+        },
+        "git_branch": {
+          "type": "string",
+          "minLength": 1,
+          "default": "main",
+          "description": "Branch of the repository (git backend)"
+        },
+        "interval_minutes": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 1440,
+          "default": 15,
+          "description": "Minutes between scheduled syncs (0 = only on demand)"
+        }
+      },
+      "additionalProperties": false
+    },
+    "voice_commands": {
+      "type": "object",
+      "properties": {
+        "enabled": {
+          "type": "boolean",
+          "default": false,
+          "description": "Act on spoken commands: 'use <name> profile' at the start, 'cancel', 'scratch that' and 'stop recording' at the end (while recording, this needs live captions)"
+        }
+      },
+      "additionalProperties": false
+    },
+    "input_integration": {
+      "type": "object",
+      "properties": {
+        "media_key_toggle": {
+          "type": "boolean",
+          "default": false,
+          "description": "Toggle recording with the media play/pause keys (Bluetooth headset buttons) while the main window is hidden"
+        }
+      },
+      "additionalProperties": false
+    },
+    "goals": {
+      "type": "object",
+      "properties": {
+        "enabled": {
+          "type": "boolean",
+          "default": false,
+          "description": "Track dictation goals and emit goal-progress after each workflow (needs the history)"
+        },
+        "daily_words": {
+          "type": "integer",
+          "minimum": 1,
+          "default": 500,
+          "description": "Words to dictate per day"
+        },
+        "session_words": {
+          "type": "integer",
+          "minimum": 0,
+          "default": 0,
+          "description": "Words to dictate since the app started (0 = no session goal)"
+        }
+      },
+      "additionalProperties": false
+    },
+    "features": {
+      "type": "object",
+      "properties": {
+        "streaming_transcription": {
+          "type": "boolean",
+          "default": false,
+          "description": "Experimental: partial transcripts while recording (streaming providers)"
+        },
+        "wake_word": {
+          "type": "boolean",
+          "default": false,
+          "description": "Experimental: start recording when a wake word is spoken"
+        },
+        "local_llm": {
+          "type": "boolean",
+          "default": false,
+          "description": "Experimental: format transcripts with a local language model"
+        }
+      },
+      "additionalProperties": false
+    },
+    "api_limits": {
+      "type": "object",
+      "properties": {
+        "max_concurrent_requests": {
+          "type": "integer",
+          "minimum": 1,
+          "maximum": 32,
+          "default": 4,
+          "description": "Whisper/GPT requests allowed in flight at the same time"
+        },
+        "requests_per_minute": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 10000,
+          "default": 50,
+          "description": "Whisper/GPT requests allowed to start per minute (0 = no cap)"
+        }
+      },
+      "additionalProperties": false
+    },
+    "notifications": {
+      "type": "object",
+      "properties": {
+        "success": {
+          "type": "string",
+          "enum": ["toast", "system", "sound", "none"],
+          "default": "system",
+          "description": "Channel of success notifications"
+        },
+        "warning": {
+          "type": "string",
+          "enum": ["toast", "system", "sound", "none"],
+          "default": "system",
+          "description": "Channel of warning notifications"
+        },
+        "error": {
+          "type": "string",
+          "enum": ["toast", "system", "sound", "none"],
+          "default": "system",
+          "description": "Channel of error notifications"
+        },
+        "progress": {
+          "type": "string",
+          "enum": ["toast", "system", "sound", "none"],
+          "default": "toast",
+          "description": "Channel of progress notifications"
+        }
+      },
+      "additionalProperties": false
+    },
+    "continuous": {
+      "type": "object",
+      "properties": {
+        "chunk_minutes": {
+          "type": "integer",
+          "minimum": 1,
+          "maximum": 30,
+          "default": 5,
+          "description": "Minutes of audio transcribed at a time in continuous sessions"
+        },
+        "directory": {
+          "type": ["string", "null"],
+          "default": null,
+          "description": "Session transcript directory (null = sessions in the data directory)"
+        },
+        "keep_audio": {
+          "type": "boolean",
+          "default": false,
+          "description": "Keep the audio of each chunk next to the session transcript"
+        },
+        "disk_budget_mb": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 1000000,
+          "default": 500,
+          "description": "Disk space the session directory may take, in MB; the oldest chunks are deleted first (0 = no limit)"
+        }
+      },
+      "additionalProperties": false
+    },
+    "bookmarks": {
+      "type": "object",
+      "properties": {
+        "shortcut": {
+          "type": ["string", "null"],
+          "default": null,
+          "description": "Global shortcut bookmarking the recording (null = no shortcut)"
+        },
+        "mode": {
+          "type": "string",
+          "enum": ["annotate", "sections"],
+          "default": "annotate",
+          "description": "Bookmarks become [MARK] annotations or start new sections of the transcript"
+        }
+      },
+      "additionalProperties": false
+    },
+    "long_recordings": {
+      "type": "object",
+      "properties": {
+        "threshold_minutes": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 1440,
+          "default": 20,
+          "description": "Recordings longer than this are transcribed in chunks sent concurrently (0 = never)"
+        },
+        "chunk_minutes": {
+          "type": "integer",
+          "minimum": 1,
+          "maximum": 30,
+          "default": 5,
+          "description": "Length of each chunk in minutes"
+        },
+        "max_parallel": {
+          "type": "integer",
+          "minimum": 1,
+          "maximum": 8,
+          "default": 3,
+          "description": "Chunks transcribed at the same time"
+        }
+      },
+      "additionalProperties": false
+    },
+    "weekly_report": {
+      "type": "object",
+      "properties": {
+        "enabled": {
+          "type": "boolean",
+          "default": false,
+          "description": "Compile last week's report from the history every Monday (needs the history)"
+        },
+        "format": {
+          "type": "string",
+          "enum": ["markdown", "html"],
+          "default": "markdown",
+          "description": "Format of the report file"
+        },
+        "directory": {
+          "type": ["string", "null"],
+          "default": null,
+          "description": "Report directory (null = reports in the data directory)"
+        },
+        "webhook_url": {
+          "type": ["string", "null"],
+          "default": null,
+          "description": "Webhook the report is also POSTed to (null = only saved to disk)"
+        },
+        "highlights": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 20,
+          "default": 3,
+          "description": "Notable transcripts quoted in the report"
+        }
+      },
+      "additionalProperties": false
+    },
+    "network": {
+      "type": "object",
+      "properties": {
+        "proxy_url": {
+          "type": ["string", "null"],
+          "default": null,
+          "description": "Proxy of the Whisper/GPT requests, e.g. http://proxy:3128 (null = system proxy)"
+        },
+        "ca_bundle_path": {
+          "type": ["string", "null"],
+          "default": null,
+          "description": "PEM file of CA certificates trusted in addition to the system ones"
+        }
+      },
+      "additionalProperties": false
+    },
+    "transcription_queue": {
+      "type": "object",
+      "properties": {
+        "max_concurrent_jobs": {
+          "type": "integer",
+          "minimum": 1,
+          "maximum": 8,
+          "default": 2,
+          "description": "Queued recordings transcribed at the same time"
         }
       },
       "additionalProperties": false
@@ -180,6 +1007,10 @@ const PROFILES_SCHEMA: &str = r#"{
             "minLength": 1,
             "description": "Human-readable profile name"
           },
+          "kind": {
+            "enum": ["clipboard", "formatting", "extraction", null],
+            "description": "Copy the transcript as is (one clipboard profile), format it or extract information from it (null = clipboard for the legacy ID '1', formatting otherwise)"
+          },
           "description": {
             "type": ["string", "null"],
             "description": "Profile description"
@@ -210,6 +1041,45 @@ const PROFILES_SCHEMA: &str = r#"{
             "type": ["string", "null"],
             "description": "Optional keyboard shortcut for quick profile selection"
           },
+          "input_device": {
+            "type": ["string", "null"],
+            "description": "Optional input device pinned for this profile"
+          },
+          "timestamps": {
+            "enum": ["off", "paragraph", "segment", null],
+            "description": "Timestamps in the final text (null = global output.timestamps setting)"
+          },
+          "whisper_mode": {
+            "enum": ["transcribe", "translate", null],
+            "description": "Transcribe or translate to English (null = global whisper.mode setting)"
+          },
+          "mixed_language": {
+            "enum": ["annotate", "normalize", "translate", null],
+            "description": "Code-switched speech: annotate, keep as spoken or translate the minority-language segments (null = off)"
+          },
+          "history_retention_hours": {
+            "type": ["integer", "null"],
+            "minimum": 0,
+            "maximum": 87600,
+            "description": "Hours this profile's history entries are kept, 0 = forever (null = global history.retention_days setting)"
+          },
+          "reference_file": {
+            "type": ["string", "null"],
+            "description": "Optional text file (style guide, prior email thread) included in the formatting prompt"
+          },
+          "keywords": {
+            "type": ["array", "null"],
+            "items": {
+              "type": "string",
+              "minLength": 1
+            },
+            "description": "Words or phrases in a transcript that suggest this profile (null = words of its name and description)"
+          },
+          "locked": {
+            "type": ["boolean", "null"],
+            "default": false,
+            "description": "Profile distributed by an admin: it cannot be edited or deleted locally, only hidden"
+          },
           "created_at": {
             "type": "string",
             "format": "date-time",
@@ -312,6 +1182,23 @@ fn validate_settings_file<P: AsRef<Path>>(path: P) -> Result<(), ConfigError> {
         });
     }
 
+    validate_output_filename_template(&json, &path_str)?;
+
+    Ok(())
+}
+
+/// Validates the output filename template, which the schema cannot check
+fn validate_output_filename_template(json: &Value, path: &str) -> Result<(), ConfigError> {
+    if let Some(template) = json
+        .get("output")
+        .and_then(|output| output.get("filename_template"))
+        .and_then(|template| template.as_str())
+    {
+        FilenameTemplate::parse(template).map_err(|e| ConfigError::ValidationError {
+            path: path.to_string(),
+            message: format!("/output/filename_template: {}", e),
+        })?;
+    }
     Ok(())
 }
 
@@ -445,55 +1332,67 @@ fn validate_shortcut_conflicts(json: &Value) -> Result<(), ConfigError> {
 }
 
 /// Validates clipboard profile specific constraints
+///
+/// The clipboard profile is the profile of the `clipboard` kind (the legacy
+/// clipboard ID in profiles written before profile kinds); there is at most one.
 fn validate_clipboard_profile_constraints(json: &Value) -> Result<(), ConfigError> {
     if let Some(profiles) = json.get("profiles").and_then(|p| p.as_array()) {
+        let mut clipboard_profiles = 0;
         for profile in profiles {
-            if let Some(id) = profile.get("id").and_then(|v| v.as_str()) {
-                if id == "1" {
-                    // This is the clipboard profile - validate its constraints
-
-                    // Name must be "Clipboard"
-                    if let Some(name) = profile.get("name").and_then(|v| v.as_str()) {
-                        if name != "Clipboard" {
-                            return Err(ConfigError::ValidationError {
-                                path: "profiles".to_string(),
-                                message: "Clipboard profile (ID='1') must be named 'Clipboard'"
-                                    .to_string(),
-                            });
-                        }
-                    }
+            let is_clipboard = match profile.get("kind").and_then(|v| v.as_str()) {
+                Some(kind) => kind == "clipboard",
+                None => {
+                    profile.get("id").and_then(|v| v.as_str()) == Some(LEGACY_CLIPBOARD_PROFILE_ID)
+                }
+            };
+            if !is_clipboard {
+                continue;
+            }
 
-                    // Prompt must be null or absent
-                    if let Some(prompt) = profile.get("prompt") {
-                        if !prompt.is_null() {
-                            return Err(ConfigError::ValidationError {
-                                path: "profiles".to_string(),
-                                message: "Clipboard profile (ID='1') cannot have a prompt"
-                                    .to_string(),
-                            });
-                        }
-                    }
+            clipboard_profiles += 1;
+            if clipboard_profiles > 1 {
+                return Err(ConfigError::ValidationError {
+                    path: "profiles".to_string(),
+                    message: "Only one profile can be of the clipboard kind".to_string(),
+                });
+            }
 
-                    // Example fields must be null or absent
-                    if let Some(example_input) = profile.get("example_input") {
-                        if !example_input.is_null() {
-                            return Err(ConfigError::ValidationError {
-                                path: "profiles".to_string(),
-                                message: "Clipboard profile (ID='1') cannot have example_input"
-                                    .to_string(),
-                            });
-                        }
-                    }
+            // Name must be "Clipboard"
+            if let Some(name) = profile.get("name").and_then(|v| v.as_str()) {
+                if name != "Clipboard" {
+                    return Err(ConfigError::ValidationError {
+                        path: "profiles".to_string(),
+                        message: "Clipboard profile must be named 'Clipboard'".to_string(),
+                    });
+                }
+            }
 
-                    if let Some(example_output) = profile.get("example_output") {
-                        if !example_output.is_null() {
-                            return Err(ConfigError::ValidationError {
-                                path: "profiles".to_string(),
-                                message: "Clipboard profile (ID='1') cannot have example_output"
-                                    .to_string(),
-                            });
-                        }
-                    }
+            // Prompt must be null or absent
+            if let Some(prompt) = profile.get("prompt") {
+                if !prompt.is_null() {
+                    return Err(ConfigError::ValidationError {
+                        path: "profiles".to_string(),
+                        message: "Clipboard profile cannot have a prompt".to_string(),
+                    });
+                }
+            }
+
+            // Example fields must be null or absent
+            if let Some(example_input) = profile.get("example_input") {
+                if !example_input.is_null() {
+                    return Err(ConfigError::ValidationError {
+                        path: "profiles".to_string(),
+                        message: "Clipboard profile cannot have example_input".to_string(),
+                    });
+                }
+            }
+
+            if let Some(example_output) = profile.get("example_output") {
+                if !example_output.is_null() {
+                    return Err(ConfigError::ValidationError {
+                        path: "profiles".to_string(),
+                        message: "Clipboard profile cannot have example_output".to_string(),
+                    });
                 }
             }
         }
@@ -542,6 +1441,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_invalid_output_filename_template() {
+        let invalid_settings = r#"{
+            "whisper": {
+                "api_key": "sk-test123"
+            },
+            "output": {
+                "filename_template": "{date}_{speaker}.md"
+            }
+        }"#;
+
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+        fs::write(&settings_path, invalid_settings).unwrap();
+
+        match validate_settings_file(&settings_path).unwrap_err() {
+            ConfigError::ValidationError { message, .. } => {
+                assert!(message.contains("filename_template"));
+                assert!(message.contains("speaker"));
+            }
+            _ => panic!("Expected ValidationError"),
+        }
+    }
+
     #[test]
     fn test_valid_profiles() {
         let valid_profiles = r#"{
@@ -560,6 +1483,46 @@ mod tests {
         assert!(validate_profiles_file(&profiles_path).is_ok());
     }
 
+    #[test]
+    fn test_clipboard_profile_found_by_kind() {
+        let temp_dir = TempDir::new().unwrap();
+        let profiles_path = temp_dir.path().join("profiles.json");
+
+        // Legacy clipboard ID without a kind
+        fs::write(
+            &profiles_path,
+            r#"{"profiles": [{"id": "1", "name": "Clipboard", "prompt": "Format"}]}"#,
+        )
+        .unwrap();
+        assert!(validate_profiles_file(&profiles_path).is_err());
+
+        // Formatting profile holding the legacy ID
+        fs::write(
+            &profiles_path,
+            r#"{"profiles": [{"id": "1", "name": "Email", "kind": "formatting", "prompt": "Format"}]}"#,
+        )
+        .unwrap();
+        assert!(validate_profiles_file(&profiles_path).is_ok());
+
+        // Clipboard profile with another ID
+        fs::write(
+            &profiles_path,
+            r#"{"profiles": [{"id": "copy", "name": "Copy", "kind": "clipboard"}]}"#,
+        )
+        .unwrap();
+        assert!(validate_profiles_file(&profiles_path).is_err());
+
+        fs::write(
+            &profiles_path,
+            r#"{"profiles": [
+                {"id": "copy", "name": "Clipboard", "kind": "clipboard"},
+                {"id": "1", "name": "Clipboard"}
+            ]}"#,
+        )
+        .unwrap();
+        assert!(validate_profiles_file(&profiles_path).is_err());
+    }
+
     #[test]
     fn test_profiles_with_complete_example() {
         let valid_profiles = r#"{
@@ -604,6 +1567,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_profile_history_retention() {
+        let temp_dir = TempDir::new().unwrap();
+        let profiles_path = temp_dir.path().join("profiles.json");
+        let profiles = |retention: &str| {
+            format!(
+                r#"{{"profiles": [{{"id": "medical", "name": "Medical", "history_retention_hours": {}}}]}}"#,
+                retention
+            )
+        };
+
+        fs::write(&profiles_path, profiles("24")).unwrap();
+        assert!(validate_profiles_file(&profiles_path).is_ok());
+        fs::write(&profiles_path, profiles("null")).unwrap();
+        assert!(validate_profiles_file(&profiles_path).is_ok());
+
+        fs::write(&profiles_path, profiles("-1")).unwrap();
+        assert!(matches!(
+            validate_profiles_file(&profiles_path),
+            Err(ConfigError::ValidationError { .. })
+        ));
+    }
+
     #[test]
     fn test_malformed_json() {
         let malformed_json = r#"{
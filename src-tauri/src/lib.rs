@@ -1,33 +1,59 @@
 pub mod audio;
 pub mod commands;
 pub mod config;
+pub mod paths;
 pub mod services;
 pub mod state;
-pub mod utils;
 
 use commands::{
     acknowledge_error_via_state_machine, apply_profile_to_text, auto_init_shortcut_mgr,
-    check_shortcut_available, close_settings_window, copy_to_clipboard,
-    disable_auto_recovery_via_state_machine, enable_auto_recovery_via_state_machine,
-    encode_wav_to_ogg, format_text_with_gpt, get_active_profile, get_clipboard_info,
-    get_current_state, get_encoder_info, get_error_state, get_gpt_info, get_shortcut_status,
-    get_whisper_info, handle_window_close, has_modal_window_open, hide_main_window,
-    init_audio_capture, init_clipboard_service, init_gpt_client, init_shortcut_mgr,
-    init_state_machine, init_system_tray, init_whisper_client, is_app_processing, is_app_recording,
-    is_clipboard_initialized, is_gpt_initialized, is_recording, is_settings_window_open,
-    is_whisper_initialized, is_window_hidden, load_profiles, load_settings, open_settings_window,
-    reformat_with_profile, register_all_profile_shortcuts, register_global_shortcut,
-    register_profile_shortcut, reset_app_state_via_state_machine, retry_backend_connection,
-    save_profiles, save_settings, select_profile, settings::ensure_default_configs,
-    should_main_window_be_visible, show_main_window, show_window_and_start_recording,
-    start_capture, start_recording_via_state_machine, stop_capture,
-    stop_recording_and_process_to_clipboard, stop_recording_via_state_machine, subscribe_rms,
-    test_api_key, toggle_main_window, toggle_record, toggle_record_with_tray, transcribe_audio,
-    transcribe_recorded_audio, unregister_all_profile_shortcuts, unregister_global_shortcut,
-    unregister_profile_shortcut, update_global_shortcut, update_tray_global_shortcut,
-    update_tray_status, v1_save_profiles, v1_save_settings, validate_shortcut_conflict,
-    AudioCaptureState, ClipboardServiceState, GptClientState, ProfileAppState, ShortcutMgrState,
-    SystemTrayState, WhisperClientState,
+    bootstrap_app, cancel_transcription_job, check_shortcut_available, clear_context_memory,
+    clear_finished_transcription_jobs, clear_history, close_settings_window, compare_profiles,
+    confirm_pending_recording, copy_to_clipboard, create_remote_pairing, delete_local_model,
+    disable_app_lock, disable_auto_recovery_via_state_machine, discard_failed_workflow,
+    discard_pending_recording, download_local_model, enable_app_lock,
+    enable_auto_recovery_via_state_machine, encode_wav, encode_wav_to_ogg, end_focus_session,
+    enqueue_transcriptions, export_settings_sanitized, finish_calibration, format_text_with_gpt,
+    generate_weekly_report, get_active_profile, get_app_lock_status, get_clipboard_info,
+    get_command_manifest, get_continuous_session, get_current_state, get_data_locations,
+    get_encoder_info, get_error_state, get_focus_session, get_goal_progress, get_gpt_info,
+    get_input_gain, get_local_api_status, get_local_inference_capabilities, get_microphone_status,
+    get_provider_capabilities, get_shortcut_status, get_transcription_jobs, get_usage_stats,
+    get_vocabulary, get_whisper_info, handle_window_close, has_modal_window_open, hide_main_window,
+    init_audio_capture, init_clipboard_service, init_gpt_client, init_input_integration,
+    init_live_captions, init_local_api, init_shortcut_mgr, init_state_machine, init_system_tray,
+    init_whisper_client, is_app_processing, is_app_recording, is_clipboard_initialized,
+    is_gpt_initialized, is_recording, is_settings_window_open, is_whisper_initialized,
+    is_window_hidden, lint_profile, list_failed_workflows, list_feature_flags, list_history,
+    list_input_devices, list_local_models, load_profiles, load_settings, lock_app, mark_bookmark,
+    open_settings_window, pause_capture, pause_continuous_session, play_last_recording,
+    purge_expired_history, reformat_with_profile, register_all_profile_shortcuts,
+    register_global_shortcut, register_profile_shortcut, reinit_audio_capture,
+    reinit_clipboard_service, reinit_gpt_client, reinit_shortcut_mgr, reinit_whisper_client,
+    render_history_entry, reset_app_state_via_state_machine, resume_capture,
+    resume_continuous_session, retranscribe_low_confidence, retry_backend_connection,
+    retry_failed_workflow, revoke_remote_pairings, save_profiles, save_settings, save_vocabulary,
+    select_profile, set_auto_gain, set_feature_flag, set_input_gain, set_next_recording_language,
+    set_workflow_context, settings::ensure_default_configs, should_main_window_be_visible,
+    show_main_window, show_window_and_start_recording, shutdown_audio_capture,
+    shutdown_clipboard_service, shutdown_gpt_client, shutdown_shortcut_mgr,
+    shutdown_whisper_client, speak_result, start_calibration, start_capture,
+    start_continuous_session, start_focus_session, start_interview_recording,
+    start_recording_via_state_machine, stop_capture, stop_continuous_session,
+    stop_interview_and_process, stop_live_captions, stop_local_api, stop_playback,
+    stop_recording_and_process_to_clipboard, stop_recording_via_state_machine, stop_speaking,
+    subscribe_rms, subscribe_waveform, subscribe_window_events, sync_profiles_now, test_api_key,
+    toggle_main_window, toggle_record, toggle_record_with_tray, transcribe_audio,
+    transcribe_recorded_audio, unlock_app, unregister_all_profile_shortcuts,
+    unregister_global_shortcut, unregister_profile_shortcut, unsubscribe_rms, unsubscribe_waveform,
+    unsubscribe_window_events, update_global_shortcut, update_history_entry_text,
+    update_tray_global_shortcut, update_tray_status, v1_save_profiles, v1_save_settings,
+    validate_ready_to_record, validate_shortcut_conflict, AudioCaptureState, ClipboardServiceState,
+    ContextMemoryState, ContinuousSessionState, FocusSessionState, GptClientState,
+    InputIntegrationState, InterviewCaptureState, LiveCaptionState, LocalApiState,
+    LocalInferenceCapabilitiesState, PlaybackState, ProfileAppState, RecordingLanguageState,
+    ShortcutMgrState, SpeechState, SystemTrayState, TranscriptionQueueState, WhisperClientState,
+    WorkflowContextState,
 };
 use config::validate_config_files;
 use state::{AppStateMachineBuilder, AppStateMachineState};
@@ -97,6 +123,11 @@ fn setup_backend_event_listeners(app_handle: AppHandle) {
             // Access the payload directly as Value since it's already JSON
             if let Ok(data) = serde_json::from_str::<serde_json::Value>(event.payload()) {
                 if let Some(profile_id) = data.get("profile_id").and_then(|v| v.as_str()) {
+                    if !commands::focus_session::profile_shortcut_allowed(&app_handle, profile_id)
+                        .await
+                    {
+                        return;
+                    }
                     match app_handle.try_state::<ProfileAppState>() {
                         Some(profile_state) => {
                             if let Err(e) = select_profile(
@@ -118,6 +149,69 @@ fn setup_backend_event_listeners(app_handle: AppHandle) {
         });
     });
 
+    // Listen for the bookmark shortcut
+    let app_handle_clone = app_handle.clone();
+    app_handle.listen("global_shortcut_mark_bookmark", move |_event| {
+        let app_handle = app_handle_clone.clone();
+        tauri::async_runtime::spawn(async move {
+            match app_handle.try_state::<AudioCaptureState>() {
+                Some(audio_state) => {
+                    if let Err(e) = mark_bookmark(app_handle.clone(), audio_state).await {
+                        eprintln!("Failed to bookmark from global shortcut: {}", e);
+                    }
+                }
+                None => eprintln!("Audio capture state not found for bookmark shortcut"),
+            }
+        });
+    });
+
+    // Listen for the silence auto-stop of the audio capture
+    let app_handle_clone = app_handle.clone();
+    app_handle.listen("vad_silence_stop", move |_event| {
+        let app_handle = app_handle_clone.clone();
+        tauri::async_runtime::spawn(async move {
+            crate::commands::audio::stop_recording_on_silence(&app_handle).await;
+        });
+    });
+
+    // Listen for the maximum duration auto-stop of the audio capture
+    let app_handle_clone = app_handle.clone();
+    app_handle.listen("max_duration_stop", move |event| {
+        let app_handle = app_handle_clone.clone();
+        let max_seconds = serde_json::from_str::<u32>(event.payload()).unwrap_or_default();
+        tauri::async_runtime::spawn(async move {
+            crate::commands::audio::stop_recording_at_max_duration(&app_handle, max_seconds).await;
+        });
+    });
+
+    // Listen for the input device disconnecting mid-recording
+    let app_handle_clone = app_handle.clone();
+    app_handle.listen("recording-device-lost", move |event| {
+        let app_handle = app_handle_clone.clone();
+        match serde_json::from_str::<crate::audio::RecordingDeviceLost>(event.payload()) {
+            Ok(lost) => {
+                tauri::async_runtime::spawn(async move {
+                    crate::commands::audio::handle_recording_device_lost(&app_handle, lost).await;
+                });
+            }
+            Err(e) => eprintln!("Failed to parse recording-device-lost payload: {}", e),
+        }
+    });
+
+    // Listen for the tray menu's settings item (tray-only mode)
+    let app_handle_clone = app_handle.clone();
+    app_handle.listen("tray_open_settings", move |_event| {
+        let app_handle = app_handle_clone.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) =
+                open_settings_window(app_handle.clone(), app_handle.state(), app_handle.state())
+                    .await
+            {
+                eprintln!("Failed to open settings from tray: {}", e);
+            }
+        });
+    });
+
     // Listen for tray double click events
     let app_handle_clone = app_handle.clone();
     app_handle.listen("tray_double_click_show_and_record", move |_event| {
@@ -157,6 +251,9 @@ fn setup_backend_event_listeners(app_handle: AppHandle) {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Move files of older versions to the current locations
+    paths::migrate_legacy_files();
+
     // Ensure default configuration files exist
     if let Err(e) = tokio::runtime::Runtime::new()
         .unwrap()
@@ -184,6 +281,24 @@ pub fn run() {
         .manage(Arc::new(Mutex::new(None)) as ClipboardServiceState)
         .manage(Arc::new(Mutex::new(None)) as SystemTrayState)
         .manage(Arc::new(Mutex::new(None)) as AppStateMachineState)
+        .manage(Arc::new(Mutex::new(None)) as LocalApiState)
+        .manage(Arc::new(Mutex::new(None)) as LiveCaptionState)
+        .manage(Arc::new(Mutex::new(None)) as InterviewCaptureState)
+        .manage(Arc::new(Mutex::new(None)) as LocalInferenceCapabilitiesState)
+        .manage(Arc::new(Mutex::new(None)) as SpeechState)
+        .manage(Arc::new(Mutex::new(None)) as PlaybackState)
+        .manage(Arc::new(Mutex::new(None)) as RecordingLanguageState)
+        .manage(Arc::new(Mutex::new(None)) as ContextMemoryState)
+        .manage(Arc::new(Mutex::new(None)) as WorkflowContextState)
+        .manage(Arc::new(Mutex::new(None)) as InputIntegrationState)
+        .manage(Arc::new(Mutex::new(None)) as FocusSessionState)
+        .manage(Arc::new(Mutex::new(None)) as ContinuousSessionState)
+        .manage(Arc::new(std::sync::Mutex::new(
+            services::transcription_queue::TranscriptionQueue::new(
+                commands::settings::TranscriptionQueueSettings::default().max_concurrent_jobs
+                    as usize,
+            ),
+        )) as TranscriptionQueueState)
         .manage(
             std::sync::Mutex::new(commands::profiles::ProfileState::default()) as ProfileAppState,
         )
@@ -191,14 +306,27 @@ pub fn run() {
             let app_handle = app.handle().clone();
             let app_handle_for_setup = app_handle.clone(); // Clone for setup function
 
+            // Tray-only mode never creates the main window
+            let tray_only = tauri::async_runtime::block_on(commands::settings::read_settings())
+                .map(|settings| settings.ui.tray_only)
+                .unwrap_or(false);
+            if tray_only {
+                tauri::async_runtime::spawn(commands::system_tray::start_tray_only(
+                    app.handle().clone(),
+                ));
+            } else {
+                commands::system_tray::create_main_window(app.handle())?;
+            }
+
             // Initialize state machine with proper initial state (hidden)
             tauri::async_runtime::spawn(async move {
                 // Initialize the state machine with window initially hidden
                 let state_machine = Arc::new(Mutex::new(
-                    AppStateMachineBuilder::new()
+                    commands::state_machine::with_state_hooks(AppStateMachineBuilder::new())
                         .with_initial_state(crate::state::AppState::Idle {
                             main_window_visible: false, // Start hidden as expected
                         })
+                        .with_tray_only(tray_only)
                         .build(app_handle.clone()),
                 ));
 
@@ -209,6 +337,95 @@ pub fn run() {
                 }
             });
 
+            // Start the local HTTP API when enabled in settings
+            let app_handle_for_api = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Some(state) = app_handle_for_api.try_state::<LocalApiState>() {
+                    if let Err(e) = commands::local_api::start_local_api_from_settings(
+                        &app_handle_for_api,
+                        &state,
+                    )
+                    .await
+                    {
+                        eprintln!("Failed to start local API: {}", e);
+                    }
+                }
+            });
+
+            // Start live caption output when enabled in settings
+            let app_handle_for_captions = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Some(state) = app_handle_for_captions.try_state::<LiveCaptionState>() {
+                    if let Err(e) = commands::live_captions::start_live_captions_from_settings(
+                        &app_handle_for_captions,
+                        &state,
+                    )
+                    .await
+                    {
+                        eprintln!("Failed to start live captions: {}", e);
+                    }
+                }
+            });
+
+            // Grab the media keys for headset buttons when enabled in settings
+            let app_handle_for_input = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Some(state) = app_handle_for_input.try_state::<InputIntegrationState>() {
+                    if let Err(e) =
+                        commands::input_integration::start_input_integration_from_settings(
+                            &app_handle_for_input,
+                            &state,
+                        )
+                        .await
+                    {
+                        eprintln!("Failed to start input integration: {}", e);
+                    }
+                }
+            });
+
+            // Probe local inference devices (CPU/CUDA/Metal)
+            let app_handle_for_probe = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Some(state) =
+                    app_handle_for_probe.try_state::<LocalInferenceCapabilitiesState>()
+                {
+                    if let Err(e) =
+                        commands::local_inference::probe_local_inference_capabilities(&state).await
+                    {
+                        eprintln!("Failed to probe local inference capabilities: {}", e);
+                    }
+                }
+            });
+
+            // Experimental subsystems stay dark unless flagged on in settings
+            tauri::async_runtime::spawn(async {
+                if let Err(e) = commands::feature_flags::load_feature_flags().await {
+                    eprintln!("Failed to load feature flags: {}", e);
+                }
+            });
+
+            // Outbound API limits (defaults until the settings are loaded)
+            tauri::async_runtime::spawn(async {
+                if let Ok(settings) = commands::settings::read_settings().await {
+                    services::api_limiter::ApiLimiter::global().configure(settings.api_limits);
+                    services::notifier::set_notification_routing(settings.notifications);
+                }
+            });
+
+            // Session goals count the words dictated from now on
+            commands::goals::session_started_at();
+
+            // Purge expired history and archived recordings on schedule
+            tauri::async_runtime::spawn(commands::history::run_retention_schedule());
+
+            // Compile last week's report on schedule when enabled
+            tauri::async_runtime::spawn(commands::weekly_report::run_weekly_report_schedule());
+
+            // Sync the profiles with the team on schedule when enabled
+            tauri::async_runtime::spawn(commands::profile_sync::run_profile_sync_schedule(
+                app.handle().clone(),
+            ));
+
             // Setup event listeners for backend communication
             setup_backend_event_listeners(app_handle_for_setup);
 
@@ -219,9 +436,14 @@ pub fn run() {
             init_audio_capture,
             start_capture,
             stop_capture,
+            pause_capture,
+            resume_capture,
             stop_recording_and_process_to_clipboard,
             is_recording,
             subscribe_rms,
+            subscribe_waveform,
+            unsubscribe_rms,
+            unsubscribe_waveform,
             encode_wav_to_ogg,
             get_encoder_info,
             init_whisper_client,
@@ -287,7 +509,93 @@ pub fn run() {
             reset_app_state_via_state_machine,
             retry_backend_connection,
             test_api_key,
-            reformat_with_profile
+            reformat_with_profile,
+            list_failed_workflows,
+            retry_failed_workflow,
+            discard_failed_workflow,
+            init_local_api,
+            stop_local_api,
+            get_local_api_status,
+            create_remote_pairing,
+            revoke_remote_pairings,
+            init_live_captions,
+            init_input_integration,
+            start_calibration,
+            finish_calibration,
+            get_goal_progress,
+            start_focus_session,
+            end_focus_session,
+            get_focus_session,
+            stop_live_captions,
+            list_input_devices,
+            start_interview_recording,
+            stop_interview_and_process,
+            set_input_gain,
+            set_auto_gain,
+            get_input_gain,
+            get_microphone_status,
+            get_local_inference_capabilities,
+            list_local_models,
+            download_local_model,
+            delete_local_model,
+            get_provider_capabilities,
+            list_history,
+            clear_history,
+            purge_expired_history,
+            render_history_entry,
+            update_history_entry_text,
+            get_app_lock_status,
+            unlock_app,
+            lock_app,
+            enable_app_lock,
+            disable_app_lock,
+            speak_result,
+            stop_speaking,
+            play_last_recording,
+            stop_playback,
+            retranscribe_low_confidence,
+            set_next_recording_language,
+            clear_context_memory,
+            set_workflow_context,
+            get_command_manifest,
+            list_feature_flags,
+            set_feature_flag,
+            get_data_locations,
+            confirm_pending_recording,
+            discard_pending_recording,
+            export_settings_sanitized,
+            sync_profiles_now,
+            get_vocabulary,
+            save_vocabulary,
+            generate_weekly_report,
+            get_usage_stats,
+            validate_ready_to_record,
+            bootstrap_app,
+            shutdown_audio_capture,
+            reinit_audio_capture,
+            shutdown_whisper_client,
+            reinit_whisper_client,
+            shutdown_gpt_client,
+            reinit_gpt_client,
+            shutdown_clipboard_service,
+            reinit_clipboard_service,
+            shutdown_shortcut_mgr,
+            reinit_shortcut_mgr,
+            enqueue_transcriptions,
+            get_transcription_jobs,
+            cancel_transcription_job,
+            clear_finished_transcription_jobs,
+            compare_profiles,
+            encode_wav,
+            lint_profile,
+            subscribe_window_events,
+            unsubscribe_window_events,
+            start_continuous_session,
+            pause_continuous_session,
+            resume_continuous_session,
+            stop_continuous_session,
+            get_continuous_session,
+            mark_bookmark
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
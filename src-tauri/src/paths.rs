@@ -0,0 +1,223 @@
+//! Locations of DictaClerk's files
+//!
+//! Every file the app keeps is placed following the platform conventions:
+//!
+//! - Linux (XDG): configuration in `$XDG_CONFIG_HOME/DictaClerk`, data in
+//!   `$XDG_DATA_HOME/DictaClerk`, state and logs in `$XDG_STATE_HOME/DictaClerk`
+//! - macOS: `~/Library/Application Support/DictaClerk`, logs in
+//!   `~/Library/Logs/DictaClerk`
+//! - Windows: configuration and data in `%APPDATA%\DictaClerk`, state and logs
+//!   in `%LOCALAPPDATA%\DictaClerk`
+//!
+//! Older versions read their configuration from the current directory and kept
+//! the history next to it in the configuration directory;
+//! [`migrate_legacy_files`] moves those files to their new place at startup.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::services::failed_workflows::FAILED_WORKFLOWS_FILE;
+use crate::services::history::HISTORY_DIR;
+
+/// Name of the application directory inside the platform directories
+pub const APP_DIR_NAME: &str = "DictaClerk";
+
+/// Directory of the default output files, inside the data directory
+pub const OUTPUTS_DIR: &str = "outputs";
+
+/// Window position and visibility file, inside the state directory
+pub const WINDOW_STATE_FILE: &str = "window_state.json";
+
+/// Configuration files older versions read from the current directory
+const LEGACY_CONFIG_FILES: [&str; 2] = ["settings.json", "profiles.json"];
+
+/// Data older versions kept in the configuration directory
+const LEGACY_DATA_ENTRIES: [&str; 3] = [HISTORY_DIR, FAILED_WORKFLOWS_FILE, OUTPUTS_DIR];
+
+fn app_dir(base: Option<PathBuf>) -> PathBuf {
+    base.or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DIR_NAME)
+}
+
+/// Configuration directory (settings.json, profiles.json, pairings)
+pub fn config_dir() -> PathBuf {
+    app_dir(dirs::config_dir())
+}
+
+/// Data directory (history, failed workflows, default outputs)
+pub fn data_dir() -> PathBuf {
+    app_dir(dirs::data_dir())
+}
+
+/// State directory (window state); the local data directory outside Linux
+pub fn state_dir() -> PathBuf {
+    app_dir(dirs::state_dir().or_else(dirs::data_local_dir))
+}
+
+/// Log directory
+pub fn log_dir() -> PathBuf {
+    #[cfg(target_os = "macos")]
+    return app_dir(dirs::home_dir().map(|home| home.join("Library").join("Logs")));
+
+    #[cfg(not(target_os = "macos"))]
+    return state_dir().join("logs");
+}
+
+/// Ensure configuration directory exists
+pub fn ensure_config_dir() -> Result<PathBuf, std::io::Error> {
+    let config_dir = config_dir();
+    if !config_dir.exists() {
+        std::fs::create_dir_all(&config_dir)?;
+    }
+    Ok(config_dir)
+}
+
+/// Find the target path for a config file
+pub fn find_config_file_path(filename: &str) -> Option<PathBuf> {
+    // OS config dir first, then current directory as fallback
+    let os_config_path = config_dir().join(filename);
+    let fallback_path = PathBuf::from(filename);
+
+    // Look for existing files first
+    for path in [&os_config_path, &fallback_path] {
+        if path.exists() {
+            return Some(path.clone());
+        }
+    }
+
+    // If no existing files found, prefer OS config directory for new files
+    // But check if we can write to it
+    if ensure_config_dir().is_ok() {
+        Some(os_config_path)
+    } else {
+        eprintln!("⚠️  Cannot write to OS config directory, using current directory fallback");
+        Some(fallback_path)
+    }
+}
+
+/// Path of the window state file
+pub fn window_state_file() -> PathBuf {
+    state_dir().join(WINDOW_STATE_FILE)
+}
+
+/// Where the app keeps its files (`get_data_locations`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DataLocations {
+    pub config_dir: PathBuf,
+    pub data_dir: PathBuf,
+    pub state_dir: PathBuf,
+    pub log_dir: PathBuf,
+    pub settings_file: Option<PathBuf>,
+    pub profiles_file: Option<PathBuf>,
+    pub history_dir: PathBuf,
+    pub failed_workflows_file: PathBuf,
+    /// Default output directory (`output.directory` overrides it)
+    pub outputs_dir: PathBuf,
+    pub window_state_file: PathBuf,
+}
+
+impl DataLocations {
+    /// Current locations
+    pub fn current() -> Self {
+        Self {
+            config_dir: config_dir(),
+            data_dir: data_dir(),
+            state_dir: state_dir(),
+            log_dir: log_dir(),
+            settings_file: find_config_file_path("settings.json"),
+            profiles_file: find_config_file_path("profiles.json"),
+            history_dir: data_dir().join(HISTORY_DIR),
+            failed_workflows_file: data_dir().join(FAILED_WORKFLOWS_FILE),
+            outputs_dir: data_dir().join(OUTPUTS_DIR),
+            window_state_file: window_state_file(),
+        }
+    }
+}
+
+/// A file or directory moved from its legacy location
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MigratedPath {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+/// Move the files of older versions to their current location
+///
+/// Configuration files found in the current directory are copied (the
+/// original stays, it may be a checked-in template); data kept in the
+/// configuration directory is moved. Nothing is overwritten: a file already
+/// at its new location wins.
+pub fn migrate_legacy_files() -> Vec<MigratedPath> {
+    let mut migrated = Vec::new();
+    let mut migrate = |from: PathBuf, to: PathBuf, keep_source: bool| match migrate_entry(
+        &from,
+        &to,
+        keep_source,
+    ) {
+        Ok(true) => {
+            println!("📦 Migrated {} to {}", from.display(), to.display());
+            migrated.push(MigratedPath { from, to });
+        }
+        Ok(false) => {}
+        Err(e) => eprintln!("⚠️  Could not migrate {}: {}", from.display(), e),
+    };
+
+    for name in LEGACY_CONFIG_FILES {
+        migrate(PathBuf::from(name), config_dir().join(name), true);
+    }
+    for name in LEGACY_DATA_ENTRIES {
+        migrate(config_dir().join(name), data_dir().join(name), false);
+    }
+    migrated
+}
+
+/// Copy or move `from` to `to` unless `to` already exists; returns whether
+/// anything was migrated
+fn migrate_entry(from: &Path, to: &Path, keep_source: bool) -> std::io::Result<bool> {
+    if from == to || !from.exists() || to.exists() {
+        return Ok(false);
+    }
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if keep_source {
+        std::fs::copy(from, to)?;
+    } else {
+        std::fs::rename(from, to)?;
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_entry_never_overwrites() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let legacy = dir.path().join("settings.json");
+        let target = dir.path().join("config").join("settings.json");
+        std::fs::write(&legacy, "legacy").unwrap();
+
+        // Copied: the legacy file stays
+        assert!(migrate_entry(&legacy, &target, true).unwrap());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "legacy");
+        assert!(legacy.exists());
+
+        // The file at the new location wins
+        std::fs::write(&legacy, "older").unwrap();
+        assert!(!migrate_entry(&legacy, &target, true).unwrap());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "legacy");
+
+        // Directories are moved
+        let history = dir.path().join("config").join("history");
+        std::fs::create_dir_all(&history).unwrap();
+        std::fs::write(history.join("entry.json"), "{}").unwrap();
+        let new_history = dir.path().join("data").join("history");
+        assert!(migrate_entry(&history, &new_history, false).unwrap());
+        assert!(!history.exists());
+        assert!(new_history.join("entry.json").exists());
+        assert!(!migrate_entry(&history, &new_history, false).unwrap());
+    }
+}
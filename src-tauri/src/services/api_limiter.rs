@@ -0,0 +1,210 @@
+//! ApiLimiter service for outbound provider calls
+//!
+//! Queues and batches can start many Whisper/GPT requests at once. Every call
+//! first takes a permit from the process-wide limiter, which caps the requests
+//! in flight and the requests started per minute: a large batch waits its turn
+//! instead of hitting the provider rate limits (or running up the bill).
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::api_limiter::{ApiLimiter, ApiLimits};
+//!
+//! # async fn example() {
+//! let limiter = ApiLimiter::global();
+//! limiter.configure(ApiLimits {
+//!     max_concurrent_requests: 2,
+//!     requests_per_minute: 30,
+//! });
+//!
+//! let _permit = limiter.acquire().await;
+//! // ... send the request while the permit is held
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Window of the per-minute cap
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Limits for outbound API calls (`api_limits` section of the settings)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiLimits {
+    /// Requests allowed in flight at the same time
+    pub max_concurrent_requests: usize,
+    /// Requests allowed to start per minute (0 = no cap)
+    pub requests_per_minute: u32,
+}
+
+impl Default for ApiLimits {
+    fn default() -> Self {
+        Self {
+            max_concurrent_requests: 4,
+            // Lowest OpenAI tier for the audio endpoints
+            requests_per_minute: 50,
+        }
+    }
+}
+
+/// Held while a request runs; releases its slot when dropped
+#[derive(Debug)]
+pub struct ApiPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    limits: ApiLimits,
+    slots: Arc<Semaphore>,
+    /// Start times of the requests of the last minute
+    started: VecDeque<Instant>,
+}
+
+/// Admission control for outbound API calls
+#[derive(Debug)]
+pub struct ApiLimiter {
+    state: Mutex<LimiterState>,
+}
+
+impl ApiLimiter {
+    /// Create a limiter with the given limits
+    pub fn new(limits: ApiLimits) -> Self {
+        Self {
+            state: Mutex::new(LimiterState {
+                limits,
+                slots: Arc::new(Semaphore::new(limits.max_concurrent_requests.max(1))),
+                started: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Process-wide limiter (default limits until configured from the settings)
+    pub fn global() -> &'static ApiLimiter {
+        static GLOBAL: OnceLock<ApiLimiter> = OnceLock::new();
+        GLOBAL.get_or_init(|| ApiLimiter::new(ApiLimits::default()))
+    }
+
+    /// Change the limits (requests in flight keep their permit)
+    pub fn configure(&self, limits: ApiLimits) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if state.limits.max_concurrent_requests != limits.max_concurrent_requests {
+            state.slots = Arc::new(Semaphore::new(limits.max_concurrent_requests.max(1)));
+        }
+        state.limits = limits;
+    }
+
+    /// Current limits
+    pub fn limits(&self) -> ApiLimits {
+        self.state
+            .lock()
+            .map(|state| state.limits)
+            .unwrap_or_default()
+    }
+
+    /// Wait for a free slot and for room under the per-minute cap
+    pub async fn acquire(&self) -> ApiPermit {
+        let slots = match self.state.lock() {
+            Ok(state) => Arc::clone(&state.slots),
+            Err(_) => Arc::new(Semaphore::new(1)),
+        };
+        let permit = slots
+            .acquire_owned()
+            .await
+            .expect("API limiter semaphore is never closed");
+
+        while let Some(wait) = self.reserve_at(Instant::now()) {
+            eprintln!(
+                "⏳ [API] Requests per minute cap reached, waiting {:.1}s",
+                wait.as_secs_f32()
+            );
+            tokio::time::sleep(wait).await;
+        }
+        ApiPermit { _permit: permit }
+    }
+
+    /// Record a request starting at `now`, or return how long to wait for
+    /// room under the per-minute cap
+    fn reserve_at(&self, now: Instant) -> Option<Duration> {
+        let mut state = self.state.lock().ok()?;
+        let cap = state.limits.requests_per_minute as usize;
+        if cap == 0 {
+            state.started.clear();
+            return None;
+        }
+
+        while state
+            .started
+            .front()
+            .is_some_and(|&started| now.duration_since(started) >= RATE_WINDOW)
+        {
+            state.started.pop_front();
+        }
+        if state.started.len() >= cap {
+            let oldest = state.started[state.started.len() - cap];
+            return Some(RATE_WINDOW.saturating_sub(now.duration_since(oldest)));
+        }
+        state.started.push_back(now);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_limiter_caps_concurrent_requests() {
+        let limiter = ApiLimiter::new(ApiLimits {
+            max_concurrent_requests: 1,
+            requests_per_minute: 0,
+        });
+        let permit = limiter.acquire().await;
+        let waiting = tokio::time::timeout(Duration::from_millis(20), limiter.acquire()).await;
+        assert!(waiting.is_err());
+
+        drop(permit);
+        let acquired = tokio::time::timeout(Duration::from_millis(20), limiter.acquire()).await;
+        assert!(acquired.is_ok());
+    }
+
+    #[test]
+    fn test_limiter_caps_requests_per_minute() {
+        let limiter = ApiLimiter::new(ApiLimits {
+            max_concurrent_requests: 4,
+            requests_per_minute: 2,
+        });
+        let start = Instant::now();
+        assert_eq!(limiter.reserve_at(start), None);
+        assert_eq!(limiter.reserve_at(start + Duration::from_secs(10)), None);
+        // Third request: wait until the first one leaves the window
+        assert_eq!(
+            limiter.reserve_at(start + Duration::from_secs(20)),
+            Some(Duration::from_secs(40))
+        );
+        assert_eq!(limiter.reserve_at(start + Duration::from_secs(60)), None);
+
+        // Lowering the cap applies to the next request (both recent requests
+        // count against it), 0 removes it
+        limiter.configure(ApiLimits {
+            max_concurrent_requests: 4,
+            requests_per_minute: 1,
+        });
+        assert_eq!(
+            limiter.reserve_at(start + Duration::from_secs(61)),
+            Some(Duration::from_secs(59))
+        );
+        limiter.configure(ApiLimits {
+            max_concurrent_requests: 4,
+            requests_per_minute: 0,
+        });
+        assert_eq!(limiter.reserve_at(start + Duration::from_secs(61)), None);
+        assert_eq!(limiter.limits().requests_per_minute, 0);
+    }
+}
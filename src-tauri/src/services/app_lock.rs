@@ -0,0 +1,477 @@
+//! AppLock service protecting settings and history behind a PIN or OS authentication
+//!
+//! When the lock is enabled, commands that open, read or save settings or
+//! expose the history call [`AppLock::ensure_unlocked`] and fail until the user
+//! unlocks the app with their PIN or with OS authentication (polkit on Linux,
+//! Touch ID on macOS, Windows Hello on Windows). An unlock lasts for a configurable period. Repeated wrong PINs
+//! trigger a short lockout.
+//!
+//! The lock configuration lives in its own file rather than in settings.json,
+//! so saving settings from a locked app cannot switch the lock off. PINs are
+//! stored as salted PBKDF2-HMAC-SHA256 hashes.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use std::path::PathBuf;
+//! use dicta_clerk_lib::services::{AppLock, LockMethod};
+//!
+//! let lock = AppLock::new(PathBuf::from("app_lock.json"));
+//! lock.enable(LockMethod::Pin, Some("4711"), 300).unwrap();
+//! lock.lock();
+//!
+//! assert!(lock.ensure_unlocked().is_err());
+//! lock.unlock_with_pin("4711").unwrap();
+//! assert!(lock.ensure_unlocked().is_ok());
+//! ```
+
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+/// File name of the lock configuration inside the config directory
+pub const APP_LOCK_FILE: &str = "app_lock.json";
+
+/// Minimum PIN length
+pub const MIN_PIN_LENGTH: usize = 4;
+
+/// PBKDF2 iterations for PIN hashes
+const PBKDF2_ITERATIONS: u32 = 100_000;
+
+/// Wrong PINs allowed before a lockout
+const MAX_FAILED_ATTEMPTS: u32 = 5;
+
+/// Duration of the lockout after too many wrong PINs
+const LOCKOUT: Duration = Duration::from_secs(30);
+
+/// Errors that can occur while checking or configuring the app lock
+#[derive(Error, Debug)]
+pub enum AppLockError {
+    #[error("The app is locked")]
+    Locked,
+    #[error("Wrong PIN")]
+    WrongPin,
+    #[error("Too many wrong PINs, try again in {seconds} seconds")]
+    LockedOut { seconds: u64 },
+    #[error("PIN must be at least {MIN_PIN_LENGTH} digits")]
+    InvalidPin,
+    #[error("OS authentication is not available: {0}")]
+    OsAuthUnavailable(String),
+    #[error("OS authentication failed")]
+    OsAuthFailed,
+    #[error("Failed to generate random bytes")]
+    Random,
+    #[error("IO error on {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Invalid app lock file: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Result type for app lock operations
+pub type AppLockResult<T> = Result<T, AppLockError>;
+
+/// How the user unlocks the app
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LockMethod {
+    /// Numeric PIN
+    Pin,
+    /// OS authentication (polkit / Touch ID / Windows Hello)
+    Os,
+}
+
+/// Persisted lock configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LockConfig {
+    method: LockMethod,
+    pin_salt: Option<String>,
+    pin_hash: Option<String>,
+    unlock_seconds: u64,
+}
+
+#[derive(Debug, Default)]
+struct UnlockState {
+    unlocked_until: Option<Instant>,
+    failed_attempts: u32,
+    locked_out_until: Option<Instant>,
+}
+
+/// App lock backed by a JSON configuration file
+#[derive(Debug)]
+pub struct AppLock {
+    path: PathBuf,
+    state: Mutex<UnlockState>,
+}
+
+impl AppLock {
+    /// Create a lock backed by the given configuration file
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            state: Mutex::new(UnlockState::default()),
+        }
+    }
+
+    /// Process-wide lock stored in the OS config directory
+    pub fn global() -> &'static AppLock {
+        static GLOBAL: OnceLock<AppLock> = OnceLock::new();
+        GLOBAL.get_or_init(|| Self::new(crate::paths::config_dir().join(APP_LOCK_FILE)))
+    }
+
+    /// Configured unlock method, or None when the lock is disabled
+    pub fn method(&self) -> AppLockResult<Option<LockMethod>> {
+        Ok(self.load()?.map(|config| config.method))
+    }
+
+    /// Whether protected commands may run now
+    pub fn is_unlocked(&self) -> AppLockResult<bool> {
+        if self.load()?.is_none() {
+            return Ok(true);
+        }
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .unlocked_until
+            .is_some_and(|until| Instant::now() < until))
+    }
+
+    /// Fail with [`AppLockError::Locked`] unless protected commands may run
+    pub fn ensure_unlocked(&self) -> AppLockResult<()> {
+        if self.is_unlocked()? {
+            Ok(())
+        } else {
+            Err(AppLockError::Locked)
+        }
+    }
+
+    /// Unlock with the PIN
+    pub fn unlock_with_pin(&self, pin: &str) -> AppLockResult<()> {
+        let Some(config) = self.load()? else {
+            return Ok(());
+        };
+
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        if let Some(until) = state.locked_out_until.filter(|until| now < *until) {
+            return Err(AppLockError::LockedOut {
+                seconds: (until - now).as_secs().max(1),
+            });
+        }
+
+        if !verify_pin(&config, pin) {
+            state.failed_attempts += 1;
+            if state.failed_attempts >= MAX_FAILED_ATTEMPTS {
+                state.failed_attempts = 0;
+                state.locked_out_until = Some(now + LOCKOUT);
+            }
+            return Err(AppLockError::WrongPin);
+        }
+
+        state.failed_attempts = 0;
+        state.locked_out_until = None;
+        state.unlocked_until = Some(now + Duration::from_secs(config.unlock_seconds));
+        Ok(())
+    }
+
+    /// Unlock with OS authentication
+    pub fn unlock_with_os(&self) -> AppLockResult<()> {
+        let Some(config) = self.load()? else {
+            return Ok(());
+        };
+        os_authenticate("Unlock DictaClerk settings and history")?;
+        self.state.lock().unwrap().unlocked_until =
+            Some(Instant::now() + Duration::from_secs(config.unlock_seconds));
+        Ok(())
+    }
+
+    /// Lock the app again immediately
+    pub fn lock(&self) {
+        self.state.lock().unwrap().unlocked_until = None;
+    }
+
+    /// Enable or reconfigure the lock (the app must be unlocked)
+    pub fn enable(
+        &self,
+        method: LockMethod,
+        pin: Option<&str>,
+        unlock_seconds: u64,
+    ) -> AppLockResult<()> {
+        self.ensure_unlocked()?;
+
+        let (pin_salt, pin_hash) = match method {
+            LockMethod::Pin => {
+                let pin = pin.filter(|pin| is_valid_pin(pin));
+                let pin = pin.ok_or(AppLockError::InvalidPin)?;
+                let (salt, hash) = hash_pin(pin)?;
+                (Some(salt), Some(hash))
+            }
+            LockMethod::Os => {
+                // Make sure OS authentication works before relying on it
+                os_authenticate("Protect DictaClerk settings and history")?;
+                (None, None)
+            }
+        };
+
+        self.save(&LockConfig {
+            method,
+            pin_salt,
+            pin_hash,
+            unlock_seconds,
+        })?;
+        self.state.lock().unwrap().unlocked_until =
+            Some(Instant::now() + Duration::from_secs(unlock_seconds));
+        Ok(())
+    }
+
+    /// Disable the lock (the app must be unlocked)
+    pub fn disable(&self) -> AppLockResult<()> {
+        self.ensure_unlocked()?;
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(self.io_error(e)),
+        }
+    }
+
+    fn load(&self) -> AppLockResult<Option<LockConfig>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&self.path).map_err(|e| self.io_error(e))?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    fn save(&self, config: &LockConfig) -> AppLockResult<()> {
+        let parent_dir = self.path.parent().unwrap_or_else(|| Path::new("."));
+        if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
+            std::fs::create_dir_all(parent_dir).map_err(|e| self.io_error(e))?;
+        }
+
+        let content = serde_json::to_string_pretty(config)?;
+        let mut temp_file = NamedTempFile::new_in(parent_dir).map_err(|e| self.io_error(e))?;
+        temp_file
+            .write_all(content.as_bytes())
+            .map_err(|e| self.io_error(e))?;
+        temp_file
+            .persist(&self.path)
+            .map_err(|e| self.io_error(e.error))?;
+        Ok(())
+    }
+
+    fn io_error(&self, source: std::io::Error) -> AppLockError {
+        AppLockError::Io {
+            path: self.path.to_string_lossy().to_string(),
+            source,
+        }
+    }
+}
+
+fn is_valid_pin(pin: &str) -> bool {
+    pin.len() >= MIN_PIN_LENGTH && pin.chars().all(|c| c.is_ascii_digit())
+}
+
+fn pbkdf2_iterations() -> NonZeroU32 {
+    NonZeroU32::new(PBKDF2_ITERATIONS).expect("PBKDF2 iterations must be non-zero")
+}
+
+/// Salt and PBKDF2 hash of a PIN (hex encoded)
+fn hash_pin(pin: &str) -> AppLockResult<(String, String)> {
+    let mut salt = [0u8; 16];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| AppLockError::Random)?;
+
+    let mut hash = [0u8; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        pbkdf2_iterations(),
+        &salt,
+        pin.as_bytes(),
+        &mut hash,
+    );
+    Ok((hex::encode(salt), hex::encode(hash)))
+}
+
+/// Constant-time check of a PIN against the stored hash
+fn verify_pin(config: &LockConfig, pin: &str) -> bool {
+    let (Some(salt), Some(hash)) = (&config.pin_salt, &config.pin_hash) else {
+        return false;
+    };
+    let (Ok(salt), Ok(hash)) = (hex::decode(salt), hex::decode(hash)) else {
+        return false;
+    };
+    pbkdf2::verify(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        pbkdf2_iterations(),
+        &salt,
+        pin.as_bytes(),
+        &hash,
+    )
+    .is_ok()
+}
+
+/// Ask the OS to authenticate the current user
+#[cfg(target_os = "linux")]
+fn os_authenticate(_reason: &str) -> AppLockResult<()> {
+    // polkit shows its own authentication dialog for the calling process
+    let status = std::process::Command::new("pkcheck")
+        .args([
+            "--action-id",
+            "org.freedesktop.policykit.exec",
+            "--process",
+            &std::process::id().to_string(),
+            "--allow-user-interaction",
+        ])
+        .status()
+        .map_err(|e| AppLockError::OsAuthUnavailable(format!("pkcheck: {}", e)))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(AppLockError::OsAuthFailed)
+    }
+}
+
+/// Ask the OS to authenticate the current user
+#[cfg(target_os = "windows")]
+fn os_authenticate(reason: &str) -> AppLockResult<()> {
+    // Windows Hello through the WinRT UserConsentVerifier
+    let script = format!(
+        r#"Add-Type -AssemblyName System.Runtime.WindowsRuntime
+$asTask = ([System.WindowsRuntimeSystemExtensions].GetMethods() | Where-Object {{ $_.Name -eq 'AsTask' -and $_.GetParameters().Count -eq 1 -and $_.GetParameters()[0].ParameterType.Name -eq 'IAsyncOperation`1' }})[0]
+$null = [Windows.Security.Credentials.UI.UserConsentVerifier, Windows.Security.Credentials.UI, ContentType = WindowsRuntime]
+$operation = [Windows.Security.Credentials.UI.UserConsentVerifier]::RequestVerificationAsync('{}')
+$task = $asTask.MakeGenericMethod([Windows.Security.Credentials.UI.UserConsentVerificationResult]).Invoke($null, @($operation))
+$null = $task.Wait()
+if ($task.Result -eq 'Verified') {{ exit 0 }} elseif ($task.Result -eq 'Canceled' -or $task.Result -eq 'RetriesExhausted') {{ exit 1 }} else {{ exit 2 }}"#,
+        reason.replace('\'', "''")
+    );
+    let status = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .status()
+        .map_err(|e| AppLockError::OsAuthUnavailable(format!("powershell: {}", e)))?;
+    match status.code() {
+        Some(0) => Ok(()),
+        Some(1) => Err(AppLockError::OsAuthFailed),
+        _ => Err(AppLockError::OsAuthUnavailable(
+            "Windows Hello is not set up".to_string(),
+        )),
+    }
+}
+
+/// Ask the OS to authenticate the current user
+#[cfg(target_os = "macos")]
+fn os_authenticate(reason: &str) -> AppLockResult<()> {
+    // Touch ID (or the account password) through LocalAuthentication, from
+    // JavaScript for Automation: the reply block is awaited on the run loop
+    let script = format!(
+        r#"ObjC.import('stdlib')
+ObjC.import('LocalAuthentication')
+const policy = 2 // LAPolicyDeviceOwnerAuthentication
+const context = $.LAContext.alloc.init
+if (!context.canEvaluatePolicyError(policy, null)) $.exit(2)
+let verified = null
+context.evaluatePolicyLocalizedReasonReply(policy, {}, (success) => {{ verified = success }})
+while (verified === null) $.NSRunLoop.currentRunLoop.runUntilDate($.NSDate.dateWithTimeIntervalSinceNow(0.1))
+$.exit(verified ? 0 : 1)"#,
+        serde_json::Value::from(reason)
+    );
+    let status = std::process::Command::new("osascript")
+        .args(["-l", "JavaScript", "-e", &script])
+        .status()
+        .map_err(|e| AppLockError::OsAuthUnavailable(format!("osascript: {}", e)))?;
+    match status.code() {
+        Some(0) => Ok(()),
+        Some(1) => Err(AppLockError::OsAuthFailed),
+        _ => Err(AppLockError::OsAuthUnavailable(
+            "Touch ID and the account password are not available".to_string(),
+        )),
+    }
+}
+
+/// Ask the OS to authenticate the current user
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn os_authenticate(_reason: &str) -> AppLockResult<()> {
+    Err(AppLockError::OsAuthUnavailable(
+        "not supported on this platform, use a PIN".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_lock(temp_dir: &TempDir) -> AppLock {
+        AppLock::new(temp_dir.path().join(APP_LOCK_FILE))
+    }
+
+    #[test]
+    fn test_disabled_lock_is_always_unlocked() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock = create_lock(&temp_dir);
+
+        assert_eq!(lock.method().unwrap(), None);
+        lock.lock();
+        assert!(lock.ensure_unlocked().is_ok());
+    }
+
+    #[test]
+    fn test_pin_lock_and_unlock() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock = create_lock(&temp_dir);
+
+        assert!(matches!(
+            lock.enable(LockMethod::Pin, Some("12"), 300),
+            Err(AppLockError::InvalidPin)
+        ));
+        lock.enable(LockMethod::Pin, Some("4711"), 300).unwrap();
+        assert!(
+            !std::fs::read_to_string(temp_dir.path().join(APP_LOCK_FILE))
+                .unwrap()
+                .contains("4711")
+        );
+
+        // A fresh process starts locked
+        let lock = create_lock(&temp_dir);
+        assert!(matches!(lock.ensure_unlocked(), Err(AppLockError::Locked)));
+        assert!(matches!(lock.disable(), Err(AppLockError::Locked)));
+        assert!(matches!(
+            lock.unlock_with_pin("0000"),
+            Err(AppLockError::WrongPin)
+        ));
+
+        lock.unlock_with_pin("4711").unwrap();
+        assert!(lock.ensure_unlocked().is_ok());
+
+        lock.lock();
+        assert!(lock.ensure_unlocked().is_err());
+    }
+
+    #[test]
+    fn test_repeated_wrong_pins_lock_out() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock = create_lock(&temp_dir);
+        lock.enable(LockMethod::Pin, Some("4711"), 300).unwrap();
+        lock.lock();
+
+        for _ in 0..MAX_FAILED_ATTEMPTS {
+            assert!(matches!(
+                lock.unlock_with_pin("0000"),
+                Err(AppLockError::WrongPin)
+            ));
+        }
+        assert!(matches!(
+            lock.unlock_with_pin("4711"),
+            Err(AppLockError::LockedOut { .. })
+        ));
+    }
+}
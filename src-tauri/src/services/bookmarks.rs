@@ -0,0 +1,151 @@
+//! Bookmarks placed in the transcript
+//!
+//! While recording, a shortcut bookmarks the current position of the audio
+//! (seconds from the recording start, as Whisper times its segments). Once
+//! transcribed, each bookmark goes to the segment boundary nearest to it: a
+//! bookmark pressed in the middle of a sentence lands before or after that
+//! sentence, never inside it. The bookmarks are then either written as
+//! `[MARK]` annotations or used to split the transcript into sections.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::bookmarks::{apply_bookmarks, BookmarkMode};
+//! use dicta_clerk_lib::services::TranscriptionSegment;
+//!
+//! let segment = |start: f64, end: f64, text: &str| TranscriptionSegment {
+//!     id: 0,
+//!     start,
+//!     end,
+//!     text: text.to_string(),
+//!     avg_logprob: None,
+//!     compression_ratio: None,
+//!     no_speech_prob: None,
+//! };
+//! let segments = vec![segment(0.0, 4.0, " Agenda."), segment(4.0, 9.0, " Budget is approved.")];
+//!
+//! let text = apply_bookmarks(&segments, &[4.5], BookmarkMode::Annotate).unwrap();
+//! assert_eq!(text, "Agenda. [MARK] Budget is approved.");
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::services::whisper_client::TranscriptionSegment;
+
+/// Annotation written at each bookmark
+pub const MARK_LABEL: &str = "[MARK]";
+
+/// What bookmarks become in the transcript
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BookmarkMode {
+    /// A `[MARK]` annotation at each bookmark
+    #[default]
+    Annotate,
+    /// A new section (paragraph) at each bookmark
+    Sections,
+}
+
+/// Number of segments before each bookmark (sorted, without duplicates)
+///
+/// A segment is before a bookmark when its middle is.
+pub fn bookmark_positions(segments: &[TranscriptionSegment], bookmarks: &[f64]) -> Vec<usize> {
+    let mut positions: Vec<usize> = bookmarks
+        .iter()
+        .map(|&mark| {
+            segments
+                .iter()
+                .take_while(|segment| (segment.start + segment.end) / 2.0 < mark)
+                .count()
+        })
+        .collect();
+    positions.sort_unstable();
+    positions.dedup();
+    positions
+}
+
+/// Transcript text with the bookmarks placed (None without bookmarks or segments)
+pub fn apply_bookmarks(
+    segments: &[TranscriptionSegment],
+    bookmarks: &[f64],
+    mode: BookmarkMode,
+) -> Option<String> {
+    if bookmarks.is_empty() || segments.is_empty() {
+        return None;
+    }
+
+    let positions = bookmark_positions(segments, bookmarks);
+    let mut parts: Vec<Vec<&str>> = vec![Vec::new()];
+    for (index, segment) in segments.iter().enumerate() {
+        if positions.contains(&index) {
+            parts.push(Vec::new());
+        }
+        let text = segment.text.trim();
+        if !text.is_empty() {
+            parts.last_mut().unwrap().push(text);
+        }
+    }
+    if positions.contains(&segments.len()) {
+        parts.push(Vec::new());
+    }
+    let parts: Vec<String> = parts.iter().map(|part| part.join(" ")).collect();
+
+    let text = match mode {
+        BookmarkMode::Annotate => parts
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(&format!(" {} ", MARK_LABEL))
+            .trim()
+            .to_string(),
+        BookmarkMode::Sections => parts
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    };
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start: f64, end: f64, text: &str) -> TranscriptionSegment {
+        TranscriptionSegment {
+            id: 0,
+            start,
+            end,
+            text: text.to_string(),
+            avg_logprob: None,
+            compression_ratio: None,
+            no_speech_prob: None,
+        }
+    }
+
+    #[test]
+    fn test_bookmarks_go_to_the_nearest_boundary() {
+        let segments = vec![
+            segment(0.0, 4.0, " First point."),
+            segment(4.0, 10.0, " Second point."),
+            segment(10.0, 12.0, " Third point."),
+        ];
+        // Early in the second segment: before it; late: after it
+        assert_eq!(bookmark_positions(&segments, &[5.0, 9.0]), vec![1, 2]);
+        assert_eq!(bookmark_positions(&segments, &[0.5, 1.0, 30.0]), vec![0, 3]);
+
+        assert_eq!(
+            apply_bookmarks(&segments, &[9.0, 30.0], BookmarkMode::Annotate).unwrap(),
+            "First point. Second point. [MARK] Third point. [MARK]"
+        );
+        assert_eq!(
+            apply_bookmarks(&segments, &[0.2, 9.0], BookmarkMode::Sections).unwrap(),
+            "First point. Second point.\n\nThird point."
+        );
+        assert_eq!(
+            apply_bookmarks(&segments, &[], BookmarkMode::Annotate),
+            None
+        );
+        assert_eq!(apply_bookmarks(&[], &[3.0], BookmarkMode::Annotate), None);
+    }
+}
@@ -0,0 +1,345 @@
+//! Onboarding calibration: accuracy of a sample recording
+//!
+//! The user reads a known paragraph aloud; the transcript is compared with it
+//! word by word (word error rate) and the recording levels are measured. Both
+//! are turned into setting recommendations: input gain, noise, language and,
+//! when the levels look fine but the accuracy does not, the input device.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::calibration::word_error_rate;
+//!
+//! let report = word_error_rate("the quick brown fox", "The quick brown box.");
+//! assert_eq!(report.substitutions, 1);
+//! assert_eq!(report.wer, 0.25);
+//! ```
+
+use serde::Serialize;
+
+/// Paragraph read aloud during calibration (pangrams, numbers and names)
+pub const CALIBRATION_TEXT: &str = "The quick brown fox jumps over the lazy dog. \
+On Tuesday the fourteenth, Doctor Emily Carter ordered twelve boxes of printer paper \
+and asked the team to review the quarterly budget before the meeting. \
+Please schedule a follow-up call with the marketing department next week.";
+
+/// Language of [`CALIBRATION_TEXT`] (ISO-639-1)
+pub const CALIBRATION_LANGUAGE: &str = "en";
+
+/// Window used to measure the levels, in seconds
+const LEVEL_WINDOW_SECONDS: f32 = 0.05;
+/// Sample magnitude counted as clipped
+const CLIPPING_LEVEL: f32 = 0.99;
+/// Clipped sample ratio above which the gain is too high
+const MAX_CLIPPED_RATIO: f32 = 0.001;
+/// Speech level (RMS) below which the gain is too low
+const MIN_SPEECH_RMS: f32 = 0.03;
+/// Speech level aimed at when raising the gain
+const TARGET_SPEECH_RMS: f32 = 0.1;
+/// Signal-to-noise ratio (dB) below which the background is too loud
+const MIN_SNR_DB: f32 = 15.0;
+/// Word error rate considered good
+const GOOD_WER: f32 = 0.05;
+/// Word error rate above which a poor device is suspected
+const POOR_WER: f32 = 0.15;
+
+/// Word-level comparison of a transcript with the reference text
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WerReport {
+    /// (substitutions + deletions + insertions) / reference words
+    pub wer: f32,
+    pub substitutions: usize,
+    pub deletions: usize,
+    pub insertions: usize,
+    pub reference_words: usize,
+}
+
+/// Levels measured on the recording
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LevelStats {
+    /// Highest sample magnitude (0.0-1.0)
+    pub peak: f32,
+    /// Level of the speech (RMS of the loudest windows)
+    pub speech_rms: f32,
+    /// Level of the background (RMS of the quietest windows)
+    pub noise_rms: f32,
+    /// Share of clipped samples
+    pub clipped_ratio: f32,
+}
+
+impl LevelStats {
+    /// Signal-to-noise ratio in dB
+    pub fn snr_db(&self) -> f32 {
+        20.0 * (self.speech_rms.max(1e-6) / self.noise_rms.max(1e-6)).log10()
+    }
+}
+
+/// Setting a recommendation is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecommendationKind {
+    Gain,
+    Denoise,
+    Language,
+    Device,
+}
+
+/// Suggested settings change
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Recommendation {
+    pub kind: RecommendationKind,
+    pub message: String,
+    /// Suggested `audio.gain_db` for gain recommendations
+    pub suggested_gain_db: Option<f32>,
+}
+
+/// Word error rate of `hypothesis` against `reference` (case and punctuation ignored)
+pub fn word_error_rate(reference: &str, hypothesis: &str) -> WerReport {
+    let reference = normalized_words(reference);
+    let hypothesis = normalized_words(hypothesis);
+
+    // Edit distance table, each cell holding (cost, substitutions, deletions, insertions)
+    let mut previous: Vec<(usize, usize, usize, usize)> =
+        (0..=hypothesis.len()).map(|j| (j, 0, 0, j)).collect();
+    for (i, reference_word) in reference.iter().enumerate() {
+        let mut current = vec![(i + 1, 0, i + 1, 0)];
+        for (j, hypothesis_word) in hypothesis.iter().enumerate() {
+            let (cost, s, d, n) = previous[j];
+            let diagonal = if reference_word == hypothesis_word {
+                (cost, s, d, n)
+            } else {
+                (cost + 1, s + 1, d, n)
+            };
+            let (cost, s, d, n) = previous[j + 1];
+            let deletion = (cost + 1, s, d + 1, n);
+            let (cost, s, d, n) = current[j];
+            let insertion = (cost + 1, s, d, n + 1);
+            current.push(
+                [diagonal, deletion, insertion]
+                    .into_iter()
+                    .min_by_key(|(cost, ..)| *cost)
+                    .unwrap_or(diagonal),
+            );
+        }
+        previous = current;
+    }
+
+    let (errors, substitutions, deletions, insertions) = previous[hypothesis.len()];
+    WerReport {
+        wer: if reference.is_empty() {
+            0.0
+        } else {
+            errors as f32 / reference.len() as f32
+        },
+        substitutions,
+        deletions,
+        insertions,
+        reference_words: reference.len(),
+    }
+}
+
+/// Measure the levels of a mono recording
+pub fn analyze_levels(samples: &[i16], sample_rate: u32) -> LevelStats {
+    let window = ((sample_rate as f32 * LEVEL_WINDOW_SECONDS) as usize).max(1);
+    let mut window_rms: Vec<f32> = samples
+        .chunks(window)
+        .map(|chunk| {
+            let sum: f32 = chunk
+                .iter()
+                .map(|&s| {
+                    let s = s as f32 / i16::MAX as f32;
+                    s * s
+                })
+                .sum();
+            (sum / chunk.len() as f32).sqrt()
+        })
+        .collect();
+    window_rms.sort_by(|a, b| a.total_cmp(b));
+
+    let percentile = |p: f32| {
+        window_rms
+            .get(((window_rms.len().saturating_sub(1)) as f32 * p) as usize)
+            .copied()
+            .unwrap_or(0.0)
+    };
+    let clipped = samples
+        .iter()
+        .filter(|&&s| (s as f32 / i16::MAX as f32).abs() >= CLIPPING_LEVEL)
+        .count();
+
+    LevelStats {
+        peak: samples
+            .iter()
+            .map(|&s| (s as f32 / i16::MAX as f32).abs())
+            .fold(0.0, f32::max)
+            .min(1.0),
+        speech_rms: percentile(0.9),
+        noise_rms: percentile(0.1),
+        clipped_ratio: if samples.is_empty() {
+            0.0
+        } else {
+            clipped as f32 / samples.len() as f32
+        },
+    }
+}
+
+/// Settings changes suggested by a calibration result
+///
+/// `gain_db` is the input gain the recording was made with and
+/// `detected_language` the language the transcription service recognized.
+pub fn recommend(
+    report: &WerReport,
+    levels: &LevelStats,
+    gain_db: f32,
+    detected_language: Option<&str>,
+) -> Vec<Recommendation> {
+    let mut recommendations = Vec::new();
+
+    if levels.clipped_ratio > MAX_CLIPPED_RATIO {
+        let suggested = (gain_db - 6.0).max(-crate::audio::capture::MAX_INPUT_GAIN_DB);
+        recommendations.push(Recommendation {
+            kind: RecommendationKind::Gain,
+            message: format!(
+                "The recording clips ({:.1}% of samples). Lower the input gain to {:.0} dB or move away from the microphone.",
+                levels.clipped_ratio * 100.0,
+                suggested
+            ),
+            suggested_gain_db: Some(suggested),
+        });
+    } else if levels.speech_rms < MIN_SPEECH_RMS {
+        let boost = 20.0 * (TARGET_SPEECH_RMS / levels.speech_rms.max(1e-4)).log10();
+        let suggested = (gain_db + boost).min(crate::audio::capture::MAX_INPUT_GAIN_DB);
+        recommendations.push(Recommendation {
+            kind: RecommendationKind::Gain,
+            message: format!(
+                "Your voice is quiet. Raise the input gain to {:.0} dB or enable automatic gain.",
+                suggested
+            ),
+            suggested_gain_db: Some(suggested),
+        });
+    }
+
+    if levels.snr_db() < MIN_SNR_DB {
+        recommendations.push(Recommendation {
+            kind: RecommendationKind::Denoise,
+            message: format!(
+                "Background noise is high ({:.0} dB below your voice). Turn on the noise suppression of your headset or OS, or record in a quieter place.",
+                levels.snr_db()
+            ),
+            suggested_gain_db: None,
+        });
+    }
+
+    if let Some(language) = detected_language {
+        let language = language.trim().to_lowercase();
+        if language != CALIBRATION_LANGUAGE && language != "english" {
+            recommendations.push(Recommendation {
+                kind: RecommendationKind::Language,
+                message: format!(
+                    "The speech was recognized as '{}'. Set the recording language when dictating, so it is not guessed.",
+                    language
+                ),
+                suggested_gain_db: None,
+            });
+        }
+    }
+
+    if report.wer > POOR_WER && recommendations.is_empty() {
+        recommendations.push(Recommendation {
+            kind: RecommendationKind::Device,
+            message: format!(
+                "{:.0}% of the words were wrong although the levels look fine. Try another microphone (a headset works best).",
+                report.wer * 100.0
+            ),
+            suggested_gain_db: None,
+        });
+    }
+
+    recommendations
+}
+
+/// Whether the accuracy needs no further tuning
+pub fn is_good_accuracy(report: &WerReport) -> bool {
+    report.wer <= GOOD_WER
+}
+
+/// Lowercase words without punctuation
+fn normalized_words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_error_rate_counts_each_kind_of_error() {
+        let perfect = word_error_rate(CALIBRATION_TEXT, &CALIBRATION_TEXT.to_uppercase());
+        assert_eq!(perfect.wer, 0.0);
+
+        let report = word_error_rate("one two three four", "One, too three four five.");
+        assert_eq!(report.substitutions, 1);
+        assert_eq!(report.deletions, 0);
+        assert_eq!(report.insertions, 1);
+        assert_eq!(report.reference_words, 4);
+        assert_eq!(report.wer, 0.5);
+
+        let report = word_error_rate("one two three four", "one three four");
+        assert_eq!((report.substitutions, report.deletions), (0, 1));
+
+        assert_eq!(word_error_rate("one two", "").deletions, 2);
+        assert_eq!(word_error_rate("", "").wer, 0.0);
+    }
+
+    #[test]
+    fn test_recommendations_from_levels_and_language() {
+        let good = WerReport {
+            wer: 0.02,
+            substitutions: 1,
+            deletions: 0,
+            insertions: 0,
+            reference_words: 50,
+        };
+
+        // Quiet voice in a quiet room
+        let mut samples = vec![30i16; 48000];
+        samples.resize(96000, 600i16);
+        let quiet = analyze_levels(&samples, 48000);
+        let recommendations = recommend(&good, &quiet, 0.0, Some("english"));
+        assert_eq!(recommendations.len(), 1);
+        assert_eq!(recommendations[0].kind, RecommendationKind::Gain);
+        assert!(recommendations[0].suggested_gain_db.unwrap() > 0.0);
+
+        // Clipping, noisy and in the wrong language
+        let mut samples = vec![8000i16; 48000];
+        samples.resize(96000, i16::MAX);
+        let loud = analyze_levels(&samples, 48000);
+        let kinds: Vec<_> = recommend(&good, &loud, 6.0, Some("french"))
+            .into_iter()
+            .map(|r| r.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                RecommendationKind::Gain,
+                RecommendationKind::Denoise,
+                RecommendationKind::Language
+            ]
+        );
+
+        // Poor accuracy with good levels points at the device
+        let mut samples = vec![100i16; 48000];
+        samples.resize(96000, 4000i16);
+        let fine = analyze_levels(&samples, 48000);
+        let poor = WerReport { wer: 0.3, ..good };
+        let recommendations = recommend(&poor, &fine, 0.0, Some("en"));
+        assert_eq!(recommendations.len(), 1);
+        assert_eq!(recommendations[0].kind, RecommendationKind::Device);
+    }
+}
@@ -0,0 +1,338 @@
+//! Chapters for long transcripts
+//!
+//! Meeting and lecture transcripts are easier to navigate in titled sections.
+//! Once formatted, a final text long enough is split into chapters, preceded
+//! by a table of contents. The split is either local, at long pauses of
+//! the recording (or by length when the text was reworded by GPT), or asked
+//! from the LLM: GPT only returns an outline (a title and the paragraph each
+//! chapter starts at), so the text itself is never rewritten.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::chapters::{chapters_from_outline, parse_outline, render_chapters};
+//!
+//! let paragraphs = vec!["Welcome.".to_string(), "The budget.".to_string(), "Questions.".to_string()];
+//! let outline = parse_outline(r#"{"chapters": [{"title": "Intro", "start": 1}, {"title": "Budget", "start": 2}]}"#).unwrap();
+//! let chapters = chapters_from_outline(&paragraphs, &outline).unwrap();
+//!
+//! let text = render_chapters(&chapters);
+//! assert!(text.starts_with("Contents\n1. Intro\n2. Budget"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::services::transcript_format::{paragraphs, PARAGRAPH_PAUSE_SECONDS};
+use crate::services::whisper_client::TranscriptionSegment;
+
+/// Words a chapter has at least before a pause can end it
+const MIN_CHAPTER_WORDS: usize = 150;
+
+/// Silence (seconds) between two paragraphs that can start a new chapter
+const CHAPTER_PAUSE_SECONDS: f64 = 5.0;
+
+/// Words after which a chapter ends at the next paragraph
+const CHAPTER_WORDS: usize = 400;
+
+/// Words of a chapter's first sentence used as its local title
+const TITLE_WORDS: usize = 6;
+
+/// Instructions sent with the numbered paragraphs to get an outline
+pub const OUTLINE_PROMPT: &str = "Split the numbered paragraphs of this transcript into chapters by topic. \
+Answer only with JSON: {\"chapters\": [{\"title\": \"short title\", \"start\": <number of the chapter's first paragraph>}]}. \
+Chapters follow the paragraph order, the first one starts at paragraph 1, and titles are in the language of the transcript.";
+
+/// How long final texts are split into chapters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChapterMode {
+    /// No chapters
+    #[default]
+    Off,
+    /// Chapters at long pauses (or by length), titled from their first words
+    Local,
+    /// Chapters and titles from the LLM, local split when it fails
+    Llm,
+}
+
+/// A titled chapter of the final text
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chapter {
+    pub title: String,
+    pub paragraphs: Vec<String>,
+}
+
+/// Chapter of an LLM outline (`start` is the 1-based paragraph number)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OutlineEntry {
+    pub title: String,
+    pub start: usize,
+}
+
+#[derive(Deserialize)]
+struct Outline {
+    chapters: Vec<OutlineEntry>,
+}
+
+/// Number of words of `text`
+pub fn word_count(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+/// Paragraphs of a text (separated by blank lines)
+pub fn text_paragraphs(text: &str) -> Vec<String> {
+    text.split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Chapters at the pauses of the recording: a chapter ends at a long pause
+/// once it has `MIN_CHAPTER_WORDS`, or at the next paragraph once it has
+/// `CHAPTER_WORDS`
+///
+/// Returns None when the transcript fits in one chapter.
+pub fn chapters_by_pauses(segments: &[TranscriptionSegment]) -> Option<Vec<Chapter>> {
+    let mut texts = Vec::new();
+    let mut starts = vec![0];
+    let mut words = 0;
+    let mut previous_end: Option<f64> = None;
+    for paragraph in paragraphs(segments, PARAGRAPH_PAUSE_SECONDS) {
+        let text = paragraph
+            .iter()
+            .map(|segment| segment.text.trim())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let pause = previous_end.map_or(0.0, |end| paragraph[0].start - end);
+        previous_end = paragraph.last().map(|last| last.end);
+        if text.is_empty() {
+            continue;
+        }
+        let ends_chapter = (words >= MIN_CHAPTER_WORDS && pause >= CHAPTER_PAUSE_SECONDS)
+            || words >= CHAPTER_WORDS;
+        if ends_chapter {
+            starts.push(texts.len());
+            words = 0;
+        }
+        words += word_count(&text);
+        texts.push(text);
+    }
+    chapters_at(&texts, &starts)
+}
+
+/// Chapters of about `CHAPTER_WORDS` words each, ending at paragraph breaks
+///
+/// Returns None when the text fits in one chapter.
+pub fn chapters_by_length(paragraphs: &[String]) -> Option<Vec<Chapter>> {
+    let mut starts = vec![0];
+    let mut words = 0;
+    for (index, paragraph) in paragraphs.iter().enumerate() {
+        if words >= CHAPTER_WORDS {
+            starts.push(index);
+            words = 0;
+        }
+        words += word_count(paragraph);
+    }
+    chapters_at(paragraphs, &starts)
+}
+
+/// Chapters starting at the given paragraph indexes, titled from their first words
+fn chapters_at(paragraphs: &[String], starts: &[usize]) -> Option<Vec<Chapter>> {
+    if starts.len() < 2 {
+        return None;
+    }
+    let chapters = starts
+        .iter()
+        .enumerate()
+        .map(|(index, &start)| {
+            let end = starts.get(index + 1).copied().unwrap_or(paragraphs.len());
+            let paragraphs = paragraphs[start..end].to_vec();
+            Chapter {
+                title: local_title(&paragraphs[0]),
+                paragraphs,
+            }
+        })
+        .collect();
+    Some(chapters)
+}
+
+/// Title from the first words of the first sentence
+fn local_title(paragraph: &str) -> String {
+    let sentence = paragraph
+        .split_inclusive(['.', '!', '?', '…'])
+        .next()
+        .unwrap_or(paragraph);
+    let words: Vec<&str> = sentence.split_whitespace().collect();
+    let title = words[..words.len().min(TITLE_WORDS)]
+        .join(" ")
+        .trim_end_matches(|c: char| c.is_ascii_punctuation() || c == '…')
+        .to_string();
+    if words.len() > TITLE_WORDS {
+        format!("{}…", title)
+    } else {
+        title
+    }
+}
+
+/// Numbered paragraphs sent to the LLM with [`OUTLINE_PROMPT`]
+pub fn outline_request(paragraphs: &[String]) -> String {
+    paragraphs
+        .iter()
+        .enumerate()
+        .map(|(index, paragraph)| format!("[{}] {}", index + 1, paragraph))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Outline in an LLM response (the JSON may be wrapped in a code block)
+pub fn parse_outline(response: &str) -> Option<Vec<OutlineEntry>> {
+    let start = response.find('{')?;
+    let end = response.rfind('}')?;
+    let outline: Outline = serde_json::from_str(response.get(start..=end)?).ok()?;
+    Some(outline.chapters)
+}
+
+/// Chapters of an outline, or None when it does not fit the paragraphs
+/// (starts out of order or out of range, empty titles, a single chapter)
+pub fn chapters_from_outline(
+    paragraphs: &[String],
+    outline: &[OutlineEntry],
+) -> Option<Vec<Chapter>> {
+    let valid = outline.first().is_some_and(|first| first.start == 1)
+        && outline.windows(2).all(|pair| pair[0].start < pair[1].start)
+        && outline
+            .iter()
+            .all(|entry| entry.start <= paragraphs.len() && !entry.title.trim().is_empty());
+    if !valid {
+        return None;
+    }
+    let starts: Vec<usize> = outline.iter().map(|entry| entry.start - 1).collect();
+    let mut chapters = chapters_at(paragraphs, &starts)?;
+    for (chapter, entry) in chapters.iter_mut().zip(outline) {
+        chapter.title = entry.title.trim().to_string();
+    }
+    Some(chapters)
+}
+
+/// Final text with a table of contents followed by the titled chapters
+pub fn render_chapters(chapters: &[Chapter]) -> String {
+    let contents: Vec<String> = chapters
+        .iter()
+        .enumerate()
+        .map(|(index, chapter)| format!("{}. {}", index + 1, chapter.title))
+        .collect();
+    let sections: Vec<String> = chapters
+        .iter()
+        .enumerate()
+        .map(|(index, chapter)| {
+            format!(
+                "## {}. {}\n\n{}",
+                index + 1,
+                chapter.title,
+                chapter.paragraphs.join("\n\n")
+            )
+        })
+        .collect();
+    format!(
+        "Contents\n{}\n\n{}",
+        contents.join("\n"),
+        sections.join("\n\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentence(words: usize) -> String {
+        format!("{}.", vec!["word"; words].join(" "))
+    }
+
+    fn segment(start: f64, end: f64, text: &str) -> TranscriptionSegment {
+        TranscriptionSegment {
+            id: 0,
+            start,
+            end,
+            text: text.to_string(),
+            avg_logprob: None,
+            compression_ratio: None,
+            no_speech_prob: None,
+        }
+    }
+
+    #[test]
+    fn test_chapters_by_pauses_split_at_the_longest_pause() {
+        // Paragraphs of 100 words: only the 12 s pause ends a chapter
+        let pauses = [0.0, 3.0, 3.0, 12.0, 3.0, 3.0];
+        let mut time = 0.0;
+        let segments: Vec<_> = pauses
+            .iter()
+            .enumerate()
+            .map(|(index, pause)| {
+                time += pause;
+                let text = format!("Part {} {}", index, sentence(98));
+                let segment = segment(time, time + 30.0, &text);
+                time += 30.0;
+                segment
+            })
+            .collect();
+
+        let chapters = chapters_by_pauses(&segments).unwrap();
+        assert_eq!(chapters.len(), 2);
+        assert_eq!(chapters[0].paragraphs.len(), 3);
+        assert_eq!(chapters[1].title, "Part 3 word word word word…");
+        assert_eq!(chapters_by_pauses(&segments[..1]), None);
+    }
+
+    #[test]
+    fn test_chapters_by_length() {
+        let paragraphs: Vec<String> = (0..5).map(|_| sentence(150)).collect();
+        let chapters = chapters_by_length(&paragraphs).unwrap();
+        assert_eq!(
+            chapters
+                .iter()
+                .map(|chapter| chapter.paragraphs.len())
+                .collect::<Vec<_>>(),
+            vec![3, 2]
+        );
+        assert_eq!(chapters_by_length(&paragraphs[..2]), None);
+    }
+
+    #[test]
+    fn test_outline() {
+        let paragraphs = text_paragraphs("Welcome.\n\nThe budget.\n\n\nMore budget.\n\nQuestions.");
+        assert_eq!(paragraphs.len(), 4);
+        assert!(outline_request(&paragraphs).starts_with("[1] Welcome.\n\n[2] The budget."));
+
+        let response = "```json\n{\"chapters\": [{\"title\": \"Intro\", \"start\": 1}, {\"title\": \" Budget \", \"start\": 2}, {\"title\": \"Q&A\", \"start\": 4}]}\n```";
+        let outline = parse_outline(response).unwrap();
+        let chapters = chapters_from_outline(&paragraphs, &outline).unwrap();
+        assert_eq!(chapters[1].title, "Budget");
+        assert_eq!(chapters[1].paragraphs.len(), 2);
+        assert_eq!(
+            render_chapters(&chapters[..2]),
+            "Contents\n1. Intro\n2. Budget\n\n## 1. Intro\n\nWelcome.\n\n## 2. Budget\n\nThe budget.\n\nMore budget."
+        );
+
+        // Outlines that do not fit the paragraphs are rejected
+        let entry = |title: &str, start| OutlineEntry {
+            title: title.to_string(),
+            start,
+        };
+        assert_eq!(
+            chapters_from_outline(&paragraphs, &[entry("A", 1), entry("B", 9)]),
+            None
+        );
+        assert_eq!(
+            chapters_from_outline(&paragraphs, &[entry("A", 2), entry("B", 3)]),
+            None
+        );
+        assert_eq!(
+            chapters_from_outline(&paragraphs, &[entry("A", 1), entry("B", 1)]),
+            None
+        );
+        assert_eq!(parse_outline("No chapters"), None);
+    }
+}
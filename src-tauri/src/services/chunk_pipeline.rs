@@ -0,0 +1,248 @@
+//! Two-stage pipeline for chunked recordings
+//!
+//! Long recordings are processed chunk by chunk. Encoding is CPU bound and
+//! transcription is network bound, so both run at the same time: while
+//! chunk N is uploaded and transcribed, chunk N+1 is already being encoded.
+//! The number of encoded chunks waiting for upload is bounded so a slow
+//! network does not pile encoded files up on disk.
+//!
+//! Very long recordings are instead cut into chunks processed concurrently
+//! (encoded and transcribed each), a bounded number at a time, with the
+//! progress of every chunk reported as it starts and ends.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::run_chunk_pipeline;
+//!
+//! # async fn example() -> Result<(), String> {
+//! let transcripts = run_chunk_pipeline(
+//!     vec!["chunk-1.wav", "chunk-2.wav"],
+//!     |wav| async move { Ok(format!("{}.ogg", wav)) },
+//!     |ogg| async move { Ok(format!("transcript of {}", ogg)) },
+//! )
+//! .await?;
+//! assert_eq!(transcripts.len(), 2);
+//! # Ok(())
+//! # }
+//! ```
+
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::Serialize;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::mpsc;
+
+/// Encoded chunks allowed to wait for the transcription stage
+pub const PIPELINE_DEPTH: usize = 1;
+
+/// Stage of a chunk processed concurrently
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkStatus {
+    Started,
+    Done,
+    Failed,
+}
+
+/// Progress of one chunk of a recording processed concurrently
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChunkProgress {
+    /// Chunk index (0-based, in recording order)
+    pub chunk: usize,
+    pub total: usize,
+    pub status: ChunkStatus,
+    /// Chunks done so far, this one included
+    pub completed: usize,
+}
+
+/// Encode and transcribe chunks as an ordered, bounded pipeline
+///
+/// Results are returned in chunk order. The first error stops both stages.
+pub async fn run_chunk_pipeline<I, E, R, EF, EFut, TF, TFut>(
+    chunks: Vec<I>,
+    mut encode: EF,
+    mut transcribe: TF,
+) -> Result<Vec<R>, String>
+where
+    I: Send + 'static,
+    E: Send + 'static,
+    EF: FnMut(I) -> EFut + Send + 'static,
+    EFut: Future<Output = Result<E, String>> + Send,
+    TF: FnMut(E) -> TFut,
+    TFut: Future<Output = Result<R, String>>,
+{
+    let chunk_count = chunks.len();
+    let (encoded_tx, mut encoded_rx) = mpsc::channel::<Result<E, String>>(PIPELINE_DEPTH);
+
+    let encoder = tokio::spawn(async move {
+        for chunk in chunks {
+            let encoded = encode(chunk).await;
+            let failed = encoded.is_err();
+            // The receiver is gone when transcription failed: stop encoding
+            if encoded_tx.send(encoded).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    let mut results = Vec::with_capacity(chunk_count);
+    while let Some(encoded) = encoded_rx.recv().await {
+        let outcome = match encoded {
+            Ok(encoded) => transcribe(encoded).await,
+            Err(e) => Err(e),
+        };
+        match outcome {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                drop(encoded_rx);
+                encoder.abort();
+                return Err(format!(
+                    "Chunk {} of {}: {}",
+                    results.len() + 1,
+                    chunk_count,
+                    e
+                ));
+            }
+        }
+    }
+
+    encoder
+        .await
+        .map_err(|e| format!("Encoding stage failed: {}", e))?;
+    Ok(results)
+}
+
+/// Process chunks concurrently, at most `max_parallel` at a time
+///
+/// A new chunk starts as soon as any running one ends, and results are
+/// returned in chunk order whatever order the chunks finish in. `progress` is
+/// told when each chunk starts and ends. The first error stops the chunks
+/// still running or not started yet.
+pub async fn run_concurrent_chunks<I, R, F, Fut>(
+    chunks: Vec<I>,
+    max_parallel: usize,
+    process: F,
+    progress: impl Fn(ChunkProgress),
+) -> Result<Vec<R>, String>
+where
+    F: Fn(I) -> Fut,
+    Fut: Future<Output = Result<R, String>>,
+{
+    let total = chunks.len();
+    let completed = AtomicUsize::new(0);
+    let process = &process;
+    let progress = &progress;
+    let completed = &completed;
+
+    stream::iter(chunks.into_iter().enumerate())
+        .map(|(chunk, input)| async move {
+            progress(ChunkProgress {
+                chunk,
+                total,
+                status: ChunkStatus::Started,
+                completed: completed.load(Ordering::Relaxed),
+            });
+            let result = process(input).await;
+            let (status, completed) = match result {
+                Ok(_) => (
+                    ChunkStatus::Done,
+                    completed.fetch_add(1, Ordering::Relaxed) + 1,
+                ),
+                Err(_) => (ChunkStatus::Failed, completed.load(Ordering::Relaxed)),
+            };
+            progress(ChunkProgress {
+                chunk,
+                total,
+                status,
+                completed,
+            });
+            result
+                .map(|result| (chunk, result))
+                .map_err(|e| format!("Chunk {} of {}: {}", chunk + 1, total, e))
+        })
+        .buffer_unordered(max_parallel.max(1))
+        .try_collect::<Vec<_>>()
+        .await
+        .map(|mut results| {
+            results.sort_by_key(|(chunk, _)| *chunk);
+            results.into_iter().map(|(_, result)| result).collect()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_pipeline_overlaps_encoding_and_transcription() {
+        let started = tokio::time::Instant::now();
+
+        let results = run_chunk_pipeline(
+            vec![1, 2, 3],
+            |chunk: u32| async move {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                Ok(chunk * 10)
+            },
+            |encoded: u32| async move {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                Ok(encoded + 1)
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results, vec![11, 21, 31]);
+        // Sequential processing would take 6s; pipelined it takes 4s
+        assert_eq!(started.elapsed(), Duration::from_secs(4));
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_stops_at_first_error() {
+        let result = run_chunk_pipeline(
+            vec![1, 2, 3],
+            |chunk: u32| async move { Ok(chunk) },
+            |encoded: u32| async move {
+                if encoded == 2 {
+                    Err("upload failed".to_string())
+                } else {
+                    Ok(encoded)
+                }
+            },
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err(), "Chunk 2 of 3: upload failed");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_concurrent_chunks_keep_their_order() {
+        let started = tokio::time::Instant::now();
+        let events = std::sync::Mutex::new(Vec::new());
+
+        // The first chunk is the slowest
+        let results = run_concurrent_chunks(
+            vec![3, 1, 1, 1],
+            2,
+            |seconds: u64| async move {
+                tokio::time::sleep(Duration::from_secs(seconds)).await;
+                Ok(seconds * 10)
+            },
+            |progress| events.lock().unwrap().push(progress),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results, vec![30, 10, 10, 10]);
+        // Two at a time: the short chunks run while the first one transcribes
+        assert_eq!(started.elapsed(), Duration::from_secs(3));
+        let events = events.into_inner().unwrap();
+        assert_eq!(events.len(), 8);
+        let last = events.last().unwrap();
+        assert_eq!(
+            (last.status, last.completed, last.total),
+            (ChunkStatus::Done, 4, 4)
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! Session-scoped memory of recent final texts for GPT formatting
+//!
+//! Follow-up dictations often refer to the same people, products and jargon
+//! as the previous ones. When enabled, the last few final texts of the session
+//! are condensed into a short prompt section (the distinctive terms they use,
+//! then excerpts, newest first) that is appended to the profile instructions.
+//! The section never exceeds its token budget, and the memory lives only in
+//! memory: it is lost on restart or when cleared.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::context_memory::{estimate_tokens, ContextMemory};
+//!
+//! let mut memory = ContextMemory::new(5);
+//! memory.remember("Meeting with Anna Kowalski about the Orion rollout.");
+//!
+//! let section = memory.prompt_section(200).unwrap();
+//! assert!(section.contains("Anna, Kowalski, Orion"));
+//! assert!(estimate_tokens(&section) <= 200);
+//! ```
+
+use std::collections::VecDeque;
+
+/// Average characters per token used to estimate prompt sizes
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Largest excerpt kept from a single earlier dictation
+const MAX_EXCERPT_TOKENS: usize = 60;
+
+/// Instructions that introduce the remembered context
+const HEADER: &str = "Context from earlier dictations in this session. Keep names and \
+terminology consistent with it, but do not include it in your output.";
+
+/// Rough token count of `text` (about four characters per token)
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Last final texts of the current session
+#[derive(Debug, Clone, Default)]
+pub struct ContextMemory {
+    texts: VecDeque<String>,
+    max_entries: usize,
+}
+
+impl ContextMemory {
+    /// Create an empty memory keeping at most `max_entries` texts
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            texts: VecDeque::new(),
+            max_entries,
+        }
+    }
+
+    /// Change how many texts are kept, forgetting the oldest ones if needed
+    pub fn set_max_entries(&mut self, max_entries: usize) {
+        self.max_entries = max_entries;
+        while self.texts.len() > max_entries {
+            self.texts.pop_front();
+        }
+    }
+
+    /// Remember a final text, forgetting the oldest one when full
+    pub fn remember(&mut self, text: &str) {
+        let text = text.trim();
+        if text.is_empty() || self.max_entries == 0 {
+            return;
+        }
+        if self.texts.len() == self.max_entries {
+            self.texts.pop_front();
+        }
+        self.texts.push_back(text.to_string());
+    }
+
+    /// Forget every remembered text
+    pub fn clear(&mut self) {
+        self.texts.clear();
+    }
+
+    /// Number of remembered texts
+    pub fn len(&self) -> usize {
+        self.texts.len()
+    }
+
+    /// Whether nothing is remembered
+    pub fn is_empty(&self) -> bool {
+        self.texts.is_empty()
+    }
+
+    /// Prompt section summarizing the remembered texts within `token_budget`
+    ///
+    /// Returns None when nothing is remembered or the budget cannot fit
+    /// anything beyond the header.
+    pub fn prompt_section(&self, token_budget: usize) -> Option<String> {
+        let mut section = HEADER.to_string();
+        if self.texts.is_empty() || estimate_tokens(&section) >= token_budget {
+            return None;
+        }
+        let base_len = section.len();
+
+        // Distinctive terms first: they matter most for consistency
+        let terms = key_terms(self.texts.iter().rev());
+        let mut line = String::new();
+        for term in terms {
+            let candidate = if line.is_empty() {
+                format!("\nTerms: {}", term)
+            } else {
+                format!("{}, {}", line, term)
+            };
+            if estimate_tokens(&section) + estimate_tokens(&candidate) > token_budget {
+                break;
+            }
+            line = candidate;
+        }
+        section.push_str(&line);
+
+        // Then excerpts of the latest dictations with what is left
+        let mut heading_added = false;
+        for text in self.texts.iter().rev() {
+            let heading = if heading_added {
+                ""
+            } else {
+                "\nRecent dictations:"
+            };
+            let used = estimate_tokens(&section) + estimate_tokens(heading);
+            // "\n- " and a possible ellipsis
+            let available = token_budget
+                .saturating_sub(used + 2)
+                .min(MAX_EXCERPT_TOKENS);
+            let Some(excerpt) = excerpt(text, available) else {
+                break;
+            };
+            section.push_str(heading);
+            section.push_str("\n- ");
+            section.push_str(&excerpt);
+            heading_added = true;
+        }
+
+        (section.len() > base_len).then_some(section)
+    }
+}
+
+/// Start of `text` within `max_tokens`, cut at a word boundary
+fn excerpt(text: &str, max_tokens: usize) -> Option<String> {
+    if max_tokens == 0 {
+        return None;
+    }
+    if estimate_tokens(text) <= max_tokens {
+        return Some(text.to_string());
+    }
+
+    let max_chars = max_tokens * CHARS_PER_TOKEN;
+    let mut excerpt = String::new();
+    for word in text.split_whitespace() {
+        if excerpt.chars().count() + word.chars().count() + 1 > max_chars {
+            break;
+        }
+        if !excerpt.is_empty() {
+            excerpt.push(' ');
+        }
+        excerpt.push_str(word);
+    }
+    (!excerpt.is_empty()).then(|| format!("{}…", excerpt))
+}
+
+/// Names, acronyms and codes used in `texts`, in order of first appearance
+///
+/// Capitalized words are only kept when they do not start a sentence, so that
+/// ordinary sentence openers are not mistaken for names.
+fn key_terms<'a>(texts: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for text in texts {
+        let mut sentence_start = true;
+        for raw in text.split_whitespace() {
+            let word = raw.trim_matches(|c: char| !c.is_alphanumeric());
+            let is_term = !word.is_empty()
+                && (word.chars().any(|c| c.is_ascii_digit())
+                    && word.chars().any(char::is_alphabetic)
+                    || word.chars().count() > 1 && word.chars().all(|c| c.is_uppercase())
+                    || !sentence_start && word.chars().next().is_some_and(char::is_uppercase));
+            if is_term && !terms.iter().any(|term| term == word) {
+                terms.push(word.to_string());
+            }
+            sentence_start = raw.ends_with(['.', '!', '?', ':']);
+        }
+    }
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_keeps_last_entries() {
+        let mut memory = ContextMemory::new(2);
+        memory.remember("First.");
+        memory.remember("   ");
+        memory.remember("Second.");
+        memory.remember("Third.");
+        assert_eq!(memory.len(), 2);
+        assert_eq!(memory.texts, ["Second.", "Third."]);
+
+        memory.set_max_entries(1);
+        assert_eq!(memory.texts, ["Third."]);
+        memory.clear();
+        assert!(memory.is_empty());
+        assert_eq!(memory.prompt_section(500), None);
+    }
+
+    #[test]
+    fn test_key_terms_skip_sentence_openers() {
+        let texts = [
+            "Please send the Q3 report to Anna Nguyen. Thanks, NASA team.".to_string(),
+            "Nguyen approved it. Then Paul did.".to_string(),
+        ];
+        assert_eq!(
+            key_terms(texts.iter()),
+            vec!["Q3", "Anna", "Nguyen", "NASA", "Paul"]
+        );
+    }
+
+    #[test]
+    fn test_prompt_section_respects_budget() {
+        let mut memory = ContextMemory::new(5);
+        memory.remember(&"We reviewed the Helios migration plan with Marta. ".repeat(20));
+        memory.remember("Call Marta about Helios tomorrow.");
+
+        let section = memory.prompt_section(1000).unwrap();
+        assert!(section.contains("Terms: Marta, Helios"));
+        assert!(section.contains("\n- Call Marta about Helios tomorrow.\n- We reviewed"));
+        assert!(section.ends_with('…'));
+
+        for budget in [0, 20, 40, 60, 120] {
+            if let Some(section) = memory.prompt_section(budget) {
+                assert!(estimate_tokens(&section) <= budget, "budget {}", budget);
+            }
+        }
+        assert_eq!(memory.prompt_section(10), None);
+    }
+}
@@ -0,0 +1,218 @@
+//! Continuous transcription sessions for long lectures
+//!
+//! A continuous session records in rolling chunks of a few minutes: each
+//! chunk is transcribed once closed, while the next one records, and its text
+//! is appended to the session transcript under the time it was recorded at
+//! (from the session start). The transcript and the chunks kept with it stay
+//! within a disk budget: the oldest chunks are deleted first, and the session
+//! is paused when the transcript alone no longer fits.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use std::path::Path;
+//! use std::time::Duration;
+//! use dicta_clerk_lib::services::continuous_session::SessionTranscript;
+//!
+//! let transcript = SessionTranscript::create(Path::new("/tmp/sessions"), chrono::Local::now()).unwrap();
+//! transcript.append_chunk(Duration::from_secs(300), "Today we look at entropy.").unwrap();
+//! println!("Transcript at {}", transcript.path().display());
+//! ```
+
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Directory of the session transcripts, inside the data directory
+pub const SESSIONS_DIR: &str = "sessions";
+
+/// Whether a session records or is paused
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionStatus {
+    Recording,
+    Paused,
+}
+
+/// Progress of a continuous session, sent to the windows on every change
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionProgress {
+    pub status: SessionStatus,
+    /// RFC 3339 start time
+    pub started_at: String,
+    pub transcript_path: PathBuf,
+    pub chunks_transcribed: u32,
+    pub chunks_failed: u32,
+    pub disk_usage_bytes: u64,
+    /// 0 = no limit
+    pub disk_budget_bytes: u64,
+}
+
+/// Text file the transcripts of a session are appended to
+#[derive(Debug, Clone)]
+pub struct SessionTranscript {
+    path: PathBuf,
+    started_at: DateTime<Local>,
+}
+
+impl SessionTranscript {
+    /// Create the transcript of a session started at `started_at` in `dir`
+    pub fn create(dir: &Path, started_at: DateTime<Local>) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!(
+            "session_{}.txt",
+            started_at.format("%Y-%m-%d_%H-%M-%S")
+        ));
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        writeln!(
+            file,
+            "Session started {}",
+            started_at.format("%Y-%m-%d %H:%M")
+        )?;
+        Ok(Self { path, started_at })
+    }
+
+    /// Path of the transcript file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Start of the session
+    pub fn started_at(&self) -> DateTime<Local> {
+        self.started_at
+    }
+
+    /// Path for the audio of the chunk recorded at `offset`, next to the
+    /// transcript
+    pub fn chunk_audio_path(&self, offset: Duration, extension: &str) -> PathBuf {
+        let stem = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.path.with_file_name(format!(
+            "{}_{}.{}",
+            stem,
+            format_offset(offset).replace(':', "-"),
+            extension
+        ))
+    }
+
+    /// Append the text of the chunk recorded at `offset` (nothing for a
+    /// silent chunk)
+    pub fn append_chunk(&self, offset: Duration, text: &str) -> std::io::Result<()> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(());
+        }
+        self.append(&format!("\n[{}] {}\n", format_offset(offset), text))
+    }
+
+    /// Append a note (pause, failed chunk...) at `offset`
+    pub fn append_note(&self, offset: Duration, note: &str) -> std::io::Result<()> {
+        self.append(&format!("\n[{}] -- {} --\n", format_offset(offset), note))
+    }
+
+    fn append(&self, content: &str) -> std::io::Result<()> {
+        let mut file = OpenOptions::new().append(true).open(&self.path)?;
+        file.write_all(content.as_bytes())
+    }
+}
+
+/// `offset` as HH:MM:SS
+pub fn format_offset(offset: Duration) -> String {
+    let seconds = offset.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Bytes taken by the files directly in `dir`
+pub fn directory_usage(dir: &Path) -> u64 {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.metadata().ok())
+                .filter(|metadata| metadata.is_file())
+                .map(|metadata| metadata.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Kept chunks (oldest first, with their size) to delete so `usage` fits
+/// `budget` (0 = no limit); None when deleting all of them is not enough
+pub fn chunks_over_budget(
+    usage: u64,
+    budget: u64,
+    kept_chunks: &[(PathBuf, u64)],
+) -> Option<Vec<PathBuf>> {
+    let mut usage = usage;
+    let mut evicted = Vec::new();
+    for (path, size) in kept_chunks {
+        if budget == 0 || usage <= budget {
+            break;
+        }
+        usage = usage.saturating_sub(*size);
+        evicted.push(path.clone());
+    }
+    (budget == 0 || usage <= budget).then_some(evicted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_session_transcript() {
+        let temp_dir = TempDir::new().unwrap();
+        let started_at = Local::now();
+        let transcript = SessionTranscript::create(temp_dir.path(), started_at).unwrap();
+        transcript
+            .append_chunk(Duration::ZERO, "  Welcome to the lecture. ")
+            .unwrap();
+        transcript
+            .append_chunk(Duration::from_secs(300), "")
+            .unwrap();
+        transcript
+            .append_note(Duration::from_secs(3_725), "Paused")
+            .unwrap();
+
+        let content = std::fs::read_to_string(transcript.path()).unwrap();
+        let lines: Vec<&str> = content.lines().filter(|line| !line.is_empty()).collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], "[00:00:00] Welcome to the lecture.");
+        assert_eq!(lines[2], "[01:02:05] -- Paused --");
+        assert!(transcript
+            .chunk_audio_path(Duration::from_secs(300), "wav")
+            .to_string_lossy()
+            .ends_with("_00-05-00.wav"));
+
+        // A transcript is never overwritten
+        assert!(SessionTranscript::create(temp_dir.path(), started_at).is_err());
+        assert_eq!(directory_usage(temp_dir.path()), content.len() as u64);
+    }
+
+    #[test]
+    fn test_chunks_over_budget() {
+        let chunks = vec![(PathBuf::from("a.wav"), 40), (PathBuf::from("b.wav"), 40)];
+        assert_eq!(chunks_over_budget(90, 100, &chunks), Some(vec![]));
+        assert_eq!(chunks_over_budget(500, 0, &chunks), Some(vec![]));
+        assert_eq!(
+            chunks_over_budget(120, 100, &chunks),
+            Some(vec![PathBuf::from("a.wav")])
+        );
+        assert_eq!(chunks_over_budget(190, 100, &chunks), None);
+    }
+}
@@ -0,0 +1,313 @@
+//! CorrectionStore service for the correction dictionary
+//!
+//! When a stored transcript is corrected, the user can flag the terms Whisper
+//! got wrong (what was heard and what was said). Flagged terms are kept in a
+//! dictionary that improves the next transcriptions twice: the correct
+//! spellings are added to the Whisper prompt as vocabulary, and terms still
+//! heard wrong are replaced in the transcript. Terms flagged more often come
+//! first in the vocabulary, and are kept when the prompt has to be trimmed.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use std::path::PathBuf;
+//! use dicta_clerk_lib::services::corrections::{apply_corrections, CorrectionStore};
+//!
+//! let store = CorrectionStore::new(PathBuf::from("corrections.json"));
+//! store.add("dicta clerk", "DictaClerk").unwrap();
+//!
+//! let corrections = store.list().unwrap();
+//! let (text, replaced) = apply_corrections("I use Dicta Clerk daily.", &corrections);
+//! assert_eq!(text, "I use DictaClerk daily.");
+//! assert_eq!(replaced, 1);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+use crate::services::whisper_prompt::{fit_whisper_prompt, WHISPER_PROMPT_MAX_TOKENS};
+
+/// File name of the correction dictionary inside the config directory
+pub const CORRECTIONS_FILE: &str = "corrections.json";
+
+/// Correct spellings added to the Whisper prompt at most
+pub const MAX_VOCABULARY_TERMS: usize = 30;
+
+/// Errors that can occur while persisting the correction dictionary
+#[derive(Error, Debug)]
+pub enum CorrectionError {
+    #[error("IO error on {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Invalid corrections file: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("A correction needs both the term heard and the correct term")]
+    EmptyTerm,
+}
+
+/// Result type for correction dictionary operations
+pub type CorrectionResult<T> = Result<T, CorrectionError>;
+
+/// A term Whisper got wrong, flagged by the user
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Correction {
+    /// Term as transcribed (matched ignoring ASCII case)
+    pub heard: String,
+    /// Term as it should be written
+    pub correct: String,
+    /// Number of times the term was flagged
+    pub count: u32,
+    /// Unix timestamp (seconds) of the last flag
+    pub updated_at: u64,
+}
+
+/// Persistent correction dictionary backed by a JSON file
+#[derive(Debug, Clone)]
+pub struct CorrectionStore {
+    path: PathBuf,
+}
+
+impl CorrectionStore {
+    /// Create a store backed by the given JSON file
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Create a store in the OS config directory
+    pub fn default_location() -> Self {
+        Self::new(crate::paths::config_dir().join(CORRECTIONS_FILE))
+    }
+
+    /// List all corrections, most flagged first
+    pub fn list(&self) -> CorrectionResult<Vec<Correction>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(&self.path).map_err(|e| self.io_error(e))?;
+        if content.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut corrections: Vec<Correction> = serde_json::from_str(&content)?;
+        corrections.sort_by_key(|correction| std::cmp::Reverse(correction.count));
+        Ok(corrections)
+    }
+
+    /// Flag `heard` as a mis-transcription of `correct`
+    ///
+    /// Flagging a term again counts it once more and keeps the latest spelling.
+    pub fn add(&self, heard: &str, correct: &str) -> CorrectionResult<Correction> {
+        let (heard, correct) = (heard.trim(), correct.trim());
+        if heard.is_empty() || correct.is_empty() {
+            return Err(CorrectionError::EmptyTerm);
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let mut corrections = self.list()?;
+        let correction = match corrections
+            .iter_mut()
+            .find(|correction| correction.heard.eq_ignore_ascii_case(heard))
+        {
+            Some(correction) => {
+                correction.correct = correct.to_string();
+                correction.count += 1;
+                correction.updated_at = now;
+                correction.clone()
+            }
+            None => {
+                let correction = Correction {
+                    heard: heard.to_string(),
+                    correct: correct.to_string(),
+                    count: 1,
+                    updated_at: now,
+                };
+                corrections.push(correction.clone());
+                correction
+            }
+        };
+        self.save(&corrections)?;
+        Ok(correction)
+    }
+
+    /// Atomically write the dictionary to disk
+    fn save(&self, corrections: &[Correction]) -> CorrectionResult<()> {
+        let parent_dir = self.path.parent().unwrap_or_else(|| Path::new("."));
+        if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
+            std::fs::create_dir_all(parent_dir).map_err(|e| self.io_error(e))?;
+        }
+
+        let content = serde_json::to_string_pretty(corrections)?;
+        let mut temp_file = NamedTempFile::new_in(parent_dir).map_err(|e| self.io_error(e))?;
+        temp_file
+            .write_all(content.as_bytes())
+            .map_err(|e| self.io_error(e))?;
+        temp_file
+            .persist(&self.path)
+            .map_err(|e| self.io_error(e.error))?;
+        Ok(())
+    }
+
+    fn io_error(&self, source: std::io::Error) -> CorrectionError {
+        CorrectionError::Io {
+            path: self.path.to_string_lossy().to_string(),
+            source,
+        }
+    }
+}
+
+/// Replace the terms heard wrong in `text` (whole words, ASCII case ignored);
+/// returns the corrected text and the number of replacements
+pub fn apply_corrections(text: &str, corrections: &[Correction]) -> (String, usize) {
+    let mut text = text.to_string();
+    let mut replaced = 0;
+    for correction in corrections {
+        let heard = correction.heard.as_str();
+        if heard.is_empty() || heard == correction.correct {
+            continue;
+        }
+        let mut result = String::with_capacity(text.len());
+        let mut position = 0;
+        while position < text.len() {
+            let found = text
+                .get(position..position + heard.len())
+                .is_some_and(|candidate| candidate.eq_ignore_ascii_case(heard));
+            let at_boundary = found
+                && !text[..position]
+                    .chars()
+                    .next_back()
+                    .is_some_and(char::is_alphanumeric)
+                && !text[position + heard.len()..]
+                    .chars()
+                    .next()
+                    .is_some_and(char::is_alphanumeric);
+            if at_boundary {
+                result.push_str(&correction.correct);
+                position += heard.len();
+                replaced += 1;
+            } else {
+                let next = text[position..].chars().next().unwrap_or_default();
+                result.push(next);
+                position += next.len_utf8();
+            }
+        }
+        text = result;
+    }
+    (text, replaced)
+}
+
+/// Correct spellings to teach Whisper, most flagged first, without duplicates
+pub fn vocabulary(corrections: &[Correction]) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for correction in corrections {
+        if !terms.contains(&correction.correct) {
+            terms.push(correction.correct.clone());
+        }
+        if terms.len() == MAX_VOCABULARY_TERMS {
+            break;
+        }
+    }
+    terms
+}
+
+/// Whisper prompt ending with the `vocabulary` terms it does not mention yet,
+/// trimmed to what Whisper reads (the vocabulary is kept first)
+pub fn boost_prompt(prompt: Option<String>, vocabulary: &[String]) -> Option<String> {
+    let prompt = prompt.filter(|prompt| !prompt.trim().is_empty());
+    let missing: Vec<&str> = vocabulary
+        .iter()
+        .map(String::as_str)
+        .filter(|term| {
+            !prompt
+                .as_deref()
+                .is_some_and(|prompt| prompt.contains(term))
+        })
+        .collect();
+    if missing.is_empty() {
+        return prompt;
+    }
+
+    let boosted = match prompt {
+        Some(prompt) => format!("{}\n{}", prompt.trim_end(), missing.join(", ")),
+        None => missing.join(", "),
+    };
+    Some(fit_whisper_prompt(&boosted, vocabulary, WHISPER_PROMPT_MAX_TOKENS).text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_flagged_terms_are_counted() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = CorrectionStore::new(temp_dir.path().join(CORRECTIONS_FILE));
+
+        store.add("cooper netties", "Kubernetes").unwrap();
+        store.add("acme 42", "ACME-42").unwrap();
+        let again = store.add("ACME 42", "ACME-42").unwrap();
+        assert_eq!(again.count, 2);
+        assert!(matches!(
+            store.add(" ", "ACME-42"),
+            Err(CorrectionError::EmptyTerm)
+        ));
+
+        let corrections = store.list().unwrap();
+        assert_eq!(corrections.len(), 2);
+        assert_eq!(vocabulary(&corrections), vec!["ACME-42", "Kubernetes"]);
+    }
+
+    #[test]
+    fn test_apply_corrections_whole_words() {
+        let correction = |heard: &str, correct: &str| Correction {
+            heard: heard.to_string(),
+            correct: correct.to_string(),
+            count: 1,
+            updated_at: 0,
+        };
+        let corrections = vec![
+            correction("cooper netties", "Kubernetes"),
+            correction("sql", "SQL"),
+        ];
+
+        let (text, replaced) = apply_corrections(
+            "Deploy on Cooper Netties, then check the sql logs (not mysqlx). Déjà sql.",
+            &corrections,
+        );
+        assert_eq!(
+            text,
+            "Deploy on Kubernetes, then check the SQL logs (not mysqlx). Déjà SQL."
+        );
+        assert_eq!(replaced, 3);
+    }
+
+    #[test]
+    fn test_boost_prompt() {
+        let vocabulary = vec!["Kubernetes".to_string(), "ACME-42".to_string()];
+        assert_eq!(
+            boost_prompt(Some("Notes about ACME-42.".to_string()), &vocabulary).unwrap(),
+            "Notes about ACME-42.\nKubernetes"
+        );
+        assert_eq!(
+            boost_prompt(None, &vocabulary).unwrap(),
+            "Kubernetes, ACME-42"
+        );
+        assert_eq!(boost_prompt(Some(" ".to_string()), &[]), None);
+
+        // Over the limit, the vocabulary is kept at the end
+        let long = "Write clean prose. ".repeat(100);
+        assert!(boost_prompt(Some(long), &vocabulary)
+            .unwrap()
+            .ends_with("Kubernetes, ACME-42"));
+    }
+}
@@ -0,0 +1,237 @@
+//! Duplicate-recording detection
+//!
+//! A double shortcut press can slip past the debounce and produce two
+//! recordings of the same speech, each billed by Whisper. Every recording gets
+//! an [`AudioFingerprint`]: a SHA-256 of its samples (exact copies) and its
+//! loudness envelope in 100ms windows (the same speech captured twice, shifted
+//! by a few hundred milliseconds). A recording matching the previous one within
+//! a short window is reported as a duplicate.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::duplicate_detection::{AudioFingerprint, DuplicateDetector};
+//!
+//! let samples: Vec<i16> = (0..48000).map(|i| ((i % 480) as i16 - 240) * 40).collect();
+//! let detector = DuplicateDetector::new();
+//!
+//! let first = AudioFingerprint::from_samples(&samples, 48000);
+//! assert_eq!(detector.check(first, 1_700_000_000, 30), None);
+//!
+//! // Same audio 4 seconds later
+//! let second = AudioFingerprint::from_samples(&samples, 48000);
+//! assert_eq!(detector.check(second, 1_700_000_004, 30), Some(4));
+//! ```
+
+use hound::WavReader;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+/// Length of an envelope window, in milliseconds
+const ENVELOPE_WINDOW_MS: u32 = 100;
+
+/// Level of digital silence in the envelope (dBFS)
+const SILENCE_DB: f32 = -60.0;
+
+/// Largest shift (in windows) tried when aligning two envelopes
+const MAX_ALIGNMENT_WINDOWS: usize = 5;
+
+/// Mean envelope difference (dB) under which two recordings are the same speech
+const MAX_ENVELOPE_DISTANCE_DB: f32 = 3.0;
+
+/// Shortest recording (in windows) compared by envelope; shorter ones only
+/// match exact copies
+const MIN_ENVELOPE_WINDOWS: usize = 10;
+
+/// Smallest length ratio between two recordings of the same speech
+const MIN_LENGTH_RATIO: f32 = 0.9;
+
+/// What a recording sounds like, for comparison with the next one
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioFingerprint {
+    /// Hex SHA-256 of the samples
+    pub hash: String,
+    /// Loudness per 100ms window (dBFS)
+    pub envelope: Vec<f32>,
+}
+
+impl AudioFingerprint {
+    /// Fingerprint mono 16-bit samples
+    pub fn from_samples(samples: &[i16], sample_rate: u32) -> Self {
+        let mut hasher = Sha256::new();
+        for sample in samples {
+            hasher.update(sample.to_le_bytes());
+        }
+
+        let window = (sample_rate * ENVELOPE_WINDOW_MS / 1000).max(1) as usize;
+        let envelope = samples
+            .chunks(window)
+            .map(|chunk| {
+                let energy = chunk
+                    .iter()
+                    .map(|&sample| {
+                        let sample = sample as f32 / i16::MAX as f32;
+                        sample * sample
+                    })
+                    .sum::<f32>()
+                    / chunk.len() as f32;
+                (10.0 * energy.log10()).max(SILENCE_DB)
+            })
+            .collect();
+
+        Self {
+            hash: hex::encode(hasher.finalize()),
+            envelope,
+        }
+    }
+
+    /// Fingerprint a recorded WAV file
+    pub fn from_wav(path: &Path) -> Result<Self, hound::Error> {
+        let mut reader = WavReader::open(path)?;
+        let sample_rate = reader.spec().sample_rate;
+        let samples = reader.samples::<i16>().collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_samples(&samples, sample_rate))
+    }
+
+    /// Whether both fingerprints are (essentially) the same recording
+    pub fn matches(&self, other: &AudioFingerprint) -> bool {
+        if self.hash == other.hash {
+            return true;
+        }
+
+        let (shorter, longer) = if self.envelope.len() <= other.envelope.len() {
+            (&self.envelope, &other.envelope)
+        } else {
+            (&other.envelope, &self.envelope)
+        };
+        if shorter.len() < MIN_ENVELOPE_WINDOWS
+            || (shorter.len() as f32) < longer.len() as f32 * MIN_LENGTH_RATIO
+        {
+            return false;
+        }
+
+        // Best alignment of the shorter envelope within the longer one
+        (0..=MAX_ALIGNMENT_WINDOWS)
+            .flat_map(|shift| [(shift, 0), (0, shift)])
+            .filter_map(|(shorter_start, longer_start)| {
+                let overlap = (shorter.len().saturating_sub(shorter_start))
+                    .min(longer.len().saturating_sub(longer_start));
+                if overlap < MIN_ENVELOPE_WINDOWS {
+                    return None;
+                }
+                let distance: f32 = shorter[shorter_start..shorter_start + overlap]
+                    .iter()
+                    .zip(&longer[longer_start..longer_start + overlap])
+                    .map(|(a, b)| (a - b).abs())
+                    .sum();
+                Some(distance / overlap as f32)
+            })
+            .any(|distance| distance < MAX_ENVELOPE_DISTANCE_DB)
+    }
+}
+
+/// Remembers the previous recording to spot duplicates of it
+#[derive(Debug, Default)]
+pub struct DuplicateDetector {
+    /// Previous recording and the Unix timestamp (seconds) it was checked at
+    previous: Mutex<Option<(AudioFingerprint, u64)>>,
+}
+
+impl DuplicateDetector {
+    /// Create a detector with no previous recording
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process-wide detector used by the workflow
+    pub fn global() -> &'static DuplicateDetector {
+        static GLOBAL: OnceLock<DuplicateDetector> = OnceLock::new();
+        GLOBAL.get_or_init(DuplicateDetector::new)
+    }
+
+    /// Compare a recording with the previous one
+    ///
+    /// Returns the seconds since the previous recording when this one repeats
+    /// it within `window_seconds`. Otherwise this recording becomes the one the
+    /// next is compared with.
+    pub fn check(
+        &self,
+        fingerprint: AudioFingerprint,
+        now: u64,
+        window_seconds: u64,
+    ) -> Option<u64> {
+        let Ok(mut previous) = self.previous.lock() else {
+            return None;
+        };
+        if let Some((previous_fingerprint, checked_at)) = previous.as_ref() {
+            let elapsed = now.saturating_sub(*checked_at);
+            if elapsed <= window_seconds && previous_fingerprint.matches(&fingerprint) {
+                return Some(elapsed);
+            }
+        }
+        *previous = Some((fingerprint, now));
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Speech-like test signal: 150ms bursts of a tone at pseudo-random levels
+    fn speech(seconds: f32, seed: u32) -> Vec<i16> {
+        let sample_rate = 16000.0;
+        (0..(seconds * sample_rate) as usize)
+            .map(|index| {
+                let mut hash = (index / 2400) as u32 ^ seed.wrapping_mul(0x9E37_79B9);
+                hash = (hash ^ (hash >> 16)).wrapping_mul(0x85EB_CA6B);
+                hash ^= hash >> 13;
+                let level = (hash % 8) as f32 * 0.1;
+                let tone = (index as f32 * 300.0 * std::f32::consts::TAU / sample_rate).sin();
+                (tone * level * i16::MAX as f32) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fingerprints_match_copies_and_shifted_speech() {
+        let samples = speech(3.0, 0);
+        let original = AudioFingerprint::from_samples(&samples, 16000);
+        assert_eq!(original.envelope.len(), 30);
+        assert!(original.matches(&AudioFingerprint::from_samples(&samples, 16000)));
+
+        // The same speech captured 200ms later, with a little extra at the end
+        let mut shifted = samples[3200..].to_vec();
+        shifted.extend(speech(0.3, 0));
+        let shifted = AudioFingerprint::from_samples(&shifted, 16000);
+        assert_ne!(shifted.hash, original.hash);
+        assert!(original.matches(&shifted));
+        assert!(shifted.matches(&original));
+
+        // Other speech, or much shorter audio
+        assert!(!original.matches(&AudioFingerprint::from_samples(&speech(3.0, 2), 16000)));
+        assert!(!original.matches(&AudioFingerprint::from_samples(&samples[..16000], 16000)));
+    }
+
+    #[test]
+    fn test_detector_reports_duplicates_within_the_window() {
+        let detector = DuplicateDetector::new();
+        let fingerprint = || AudioFingerprint::from_samples(&speech(2.0, 0), 16000);
+
+        assert_eq!(detector.check(fingerprint(), 1000, 30), None);
+        assert_eq!(detector.check(fingerprint(), 1005, 30), Some(5));
+        // The duplicate did not replace the original
+        assert_eq!(detector.check(fingerprint(), 1030, 30), Some(30));
+        // Too late: becomes the new reference
+        assert_eq!(detector.check(fingerprint(), 1100, 30), None);
+        assert_eq!(
+            detector.check(
+                AudioFingerprint::from_samples(&speech(2.0, 1), 16000),
+                1101,
+                30
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Encryption at rest for persisted user data
+//!
+//! Clipboard history and archived recordings can be encrypted with
+//! AES-256-GCM. The data key is generated once and kept in the OS keyring
+//! (Keychain, Windows Credential Manager or the Secret Service on Linux), so
+//! it never touches the config directory.
+//!
+//! Encrypted blobs start with a short magic header, which lets readers accept
+//! both plain and encrypted files while encryption is being switched on or off.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::DataKey;
+//!
+//! let key = DataKey::from_keyring().unwrap();
+//! let sealed = key.encrypt(b"final text").unwrap();
+//! assert_eq!(key.decrypt(&sealed).unwrap(), b"final text");
+//! ```
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use thiserror::Error;
+
+/// Keyring service name of the data key
+pub const KEYRING_SERVICE: &str = "DictaClerk";
+
+/// Keyring account name of the data key
+pub const KEYRING_USER: &str = "data-encryption-key";
+
+/// Header of encrypted blobs (format version 1)
+const MAGIC: &[u8] = b"DCENC1";
+
+/// AES-256 key length
+const KEY_LEN: usize = 32;
+
+/// Errors that can occur while encrypting or decrypting data
+#[derive(Error, Debug)]
+pub enum EncryptionError {
+    #[error("OS keyring error: {0}")]
+    Keyring(String),
+    #[error("Invalid data key in the OS keyring")]
+    InvalidKey,
+    #[error("Failed to generate random bytes")]
+    Random,
+    #[error("Encryption failed")]
+    Encrypt,
+    #[error("Decryption failed (wrong key or corrupted data)")]
+    Decrypt,
+}
+
+/// Result type for encryption operations
+pub type EncryptionResult<T> = Result<T, EncryptionError>;
+
+/// AES-256-GCM key used to encrypt data at rest
+#[derive(Clone)]
+pub struct DataKey {
+    bytes: [u8; KEY_LEN],
+}
+
+impl std::fmt::Debug for DataKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DataKey(..)")
+    }
+}
+
+impl DataKey {
+    /// Generate a new random key
+    pub fn generate() -> EncryptionResult<Self> {
+        let mut bytes = [0u8; KEY_LEN];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| EncryptionError::Random)?;
+        Ok(Self { bytes })
+    }
+
+    /// Load the key from the OS keyring, creating it on first use
+    pub fn from_keyring() -> EncryptionResult<Self> {
+        let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+            .map_err(|e| EncryptionError::Keyring(e.to_string()))?;
+
+        match entry.get_password() {
+            Ok(encoded) => {
+                let bytes = hex::decode(encoded.trim()).map_err(|_| EncryptionError::InvalidKey)?;
+                let bytes: [u8; KEY_LEN] =
+                    bytes.try_into().map_err(|_| EncryptionError::InvalidKey)?;
+                Ok(Self { bytes })
+            }
+            Err(keyring::Error::NoEntry) => {
+                let key = Self::generate()?;
+                entry
+                    .set_password(&hex::encode(key.bytes))
+                    .map_err(|e| EncryptionError::Keyring(e.to_string()))?;
+                Ok(key)
+            }
+            Err(e) => Err(EncryptionError::Keyring(e.to_string())),
+        }
+    }
+
+    /// Encrypt `plaintext` into a self-describing blob (header, nonce, ciphertext, tag)
+    pub fn encrypt(&self, plaintext: &[u8]) -> EncryptionResult<Vec<u8>> {
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce_bytes)
+            .map_err(|_| EncryptionError::Random)?;
+
+        let mut in_out = plaintext.to_vec();
+        self.aead_key()?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce_bytes),
+                Aad::from(MAGIC),
+                &mut in_out,
+            )
+            .map_err(|_| EncryptionError::Encrypt)?;
+
+        let mut blob = Vec::with_capacity(MAGIC.len() + NONCE_LEN + in_out.len());
+        blob.extend_from_slice(MAGIC);
+        blob.extend_from_slice(&nonce_bytes);
+        blob.extend_from_slice(&in_out);
+        Ok(blob)
+    }
+
+    /// Decrypt a blob produced by [`DataKey::encrypt`]
+    pub fn decrypt(&self, blob: &[u8]) -> EncryptionResult<Vec<u8>> {
+        if !is_encrypted(blob) || blob.len() < MAGIC.len() + NONCE_LEN {
+            return Err(EncryptionError::Decrypt);
+        }
+
+        let (nonce_bytes, ciphertext) = blob[MAGIC.len()..].split_at(NONCE_LEN);
+        let nonce =
+            Nonce::try_assume_unique_for_key(nonce_bytes).map_err(|_| EncryptionError::Decrypt)?;
+        let mut in_out = ciphertext.to_vec();
+        let plaintext_len = self
+            .aead_key()?
+            .open_in_place(nonce, Aad::from(MAGIC), &mut in_out)
+            .map_err(|_| EncryptionError::Decrypt)?
+            .len();
+        in_out.truncate(plaintext_len);
+        Ok(in_out)
+    }
+
+    fn aead_key(&self) -> EncryptionResult<LessSafeKey> {
+        let unbound =
+            UnboundKey::new(&AES_256_GCM, &self.bytes).map_err(|_| EncryptionError::InvalidKey)?;
+        Ok(LessSafeKey::new(unbound))
+    }
+}
+
+/// Whether `data` is an encrypted blob
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let key = DataKey::generate().unwrap();
+        let sealed = key.encrypt(b"confidential dictation").unwrap();
+
+        assert!(is_encrypted(&sealed));
+        assert!(!sealed
+            .windows(b"confidential".len())
+            .any(|window| window == b"confidential"));
+        assert_eq!(key.decrypt(&sealed).unwrap(), b"confidential dictation");
+    }
+
+    #[test]
+    fn test_decrypt_rejects_wrong_key_and_tampering() {
+        let key = DataKey::generate().unwrap();
+        let mut sealed = key.encrypt(b"text").unwrap();
+
+        let other = DataKey::generate().unwrap();
+        assert!(matches!(
+            other.decrypt(&sealed),
+            Err(EncryptionError::Decrypt)
+        ));
+
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0x01;
+        assert!(matches!(
+            key.decrypt(&sealed),
+            Err(EncryptionError::Decrypt)
+        ));
+        assert!(matches!(
+            key.decrypt(b"plain json"),
+            Err(EncryptionError::Decrypt)
+        ));
+    }
+}
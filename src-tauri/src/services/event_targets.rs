@@ -0,0 +1,212 @@
+//! Events sent to the windows that need them
+//!
+//! `AppHandle::emit` reaches every window: the settings window receives the
+//! RMS levels and waveform frames of a recording it does not show. Events
+//! emitted here name their audience instead (the main window, the settings
+//! window, or every window), and a window can subscribe to the events it
+//! listens to so other events are not sent to it at all.
+//!
+//! Only listeners bound to a window (`getCurrentWebviewWindow().listen` in
+//! the frontend) are filtered; global `listen` listeners still receive every
+//! event.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::event_targets::{
+//!     subscribe_window, window_receives, EventAudience,
+//! };
+//!
+//! // Windows without subscriptions receive every event of their audience
+//! assert!(window_receives("settings", "profiles-updated"));
+//!
+//! subscribe_window("settings", &["profiles-updated".to_string()]);
+//! assert!(!window_receives("settings", "local-model-download-progress"));
+//! assert!(EventAudience::MainOnly.includes("main"));
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, EventTarget, Manager, Runtime};
+
+/// Label of the recording window
+pub const MAIN_WINDOW: &str = "main";
+
+/// Label of the settings window
+pub const SETTINGS_WINDOW: &str = "settings";
+
+/// Windows an event is meant for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventAudience {
+    /// Recording feedback (RMS, waveform, captions...)
+    MainOnly,
+    /// Settings feedback (model downloads...)
+    SettingsOnly,
+    /// State shared by every window (app state, profiles, flags...)
+    Broadcast,
+}
+
+impl EventAudience {
+    /// Whether the window labelled `label` is part of the audience
+    pub fn includes(&self, label: &str) -> bool {
+        match self {
+            EventAudience::MainOnly => label == MAIN_WINDOW,
+            EventAudience::SettingsOnly => label == SETTINGS_WINDOW,
+            EventAudience::Broadcast => true,
+        }
+    }
+}
+
+/// Events each window subscribed to, with their listener count
+static SUBSCRIPTIONS: Mutex<BTreeMap<String, BTreeMap<String, u32>>> = Mutex::new(BTreeMap::new());
+
+/// Subscribe the window labelled `label` to `events` (once per listener)
+pub fn subscribe_window(label: &str, events: &[String]) {
+    let Ok(mut subscriptions) = SUBSCRIPTIONS.lock() else {
+        return;
+    };
+    let window = subscriptions.entry(label.to_string()).or_default();
+    for event in events {
+        *window.entry(event.clone()).or_insert(0) += 1;
+    }
+}
+
+/// Remove one listener of `events` from the window labelled `label`
+pub fn unsubscribe_window(label: &str, events: &[String]) {
+    let Ok(mut subscriptions) = SUBSCRIPTIONS.lock() else {
+        return;
+    };
+    let Some(window) = subscriptions.get_mut(label) else {
+        return;
+    };
+    for event in events {
+        if let Some(count) = window.get_mut(event) {
+            *count -= 1;
+            if *count == 0 {
+                window.remove(event);
+            }
+        }
+    }
+}
+
+/// Forget the subscriptions of a closed window
+pub fn clear_window(label: &str) {
+    if let Ok(mut subscriptions) = SUBSCRIPTIONS.lock() {
+        subscriptions.remove(label);
+    }
+}
+
+/// Whether `event` is sent to the window labelled `label`: windows that never
+/// subscribed receive every event
+pub fn window_receives(label: &str, event: &str) -> bool {
+    let Ok(subscriptions) = SUBSCRIPTIONS.lock() else {
+        return true;
+    };
+    match subscriptions.get(label) {
+        Some(window) if !window.is_empty() => window.contains_key(event),
+        _ => true,
+    }
+}
+
+/// Labels of the open windows that `event` is sent to
+fn recipients<'a>(
+    audience: EventAudience,
+    event: &str,
+    open_windows: impl IntoIterator<Item = &'a str>,
+) -> Vec<String> {
+    open_windows
+        .into_iter()
+        .filter(|label| audience.includes(label) && window_receives(label, event))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Emit `event` to the open windows of `audience` that receive it
+///
+/// Backend listeners (`AppHandle::listen`) always receive the event.
+pub fn emit_targeted<R: Runtime, S: Serialize + Clone>(
+    app_handle: &AppHandle<R>,
+    audience: EventAudience,
+    event: &str,
+    payload: S,
+) -> tauri::Result<()> {
+    let windows = app_handle.webview_windows();
+    let recipients = recipients(audience, event, windows.keys().map(String::as_str));
+    app_handle.emit_filter(event, payload, |target| match target {
+        EventTarget::AnyLabel { label }
+        | EventTarget::Window { label }
+        | EventTarget::Webview { label }
+        | EventTarget::WebviewWindow { label } => recipients.contains(label),
+        EventTarget::Any | EventTarget::App => true,
+        _ => false,
+    })
+}
+
+/// Emit `event` to the main window only
+pub fn emit_to_main<R: Runtime, S: Serialize + Clone>(
+    app_handle: &AppHandle<R>,
+    event: &str,
+    payload: S,
+) -> tauri::Result<()> {
+    emit_targeted(app_handle, EventAudience::MainOnly, event, payload)
+}
+
+/// Emit `event` to the settings window only
+pub fn emit_to_settings<R: Runtime, S: Serialize + Clone>(
+    app_handle: &AppHandle<R>,
+    event: &str,
+    payload: S,
+) -> tauri::Result<()> {
+    emit_targeted(app_handle, EventAudience::SettingsOnly, event, payload)
+}
+
+/// Emit `event` to every window that receives it
+pub fn broadcast<R: Runtime, S: Serialize + Clone>(
+    app_handle: &AppHandle<R>,
+    event: &str,
+    payload: S,
+) -> tauri::Result<()> {
+    emit_targeted(app_handle, EventAudience::Broadcast, event, payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recipients() {
+        let open = [MAIN_WINDOW, SETTINGS_WINDOW];
+        assert_eq!(
+            recipients(EventAudience::MainOnly, "waveform", open),
+            vec![MAIN_WINDOW]
+        );
+        assert_eq!(
+            recipients(EventAudience::SettingsOnly, "waveform", [MAIN_WINDOW]),
+            Vec::<String>::new()
+        );
+
+        // A subscribed window only receives its events, until it unsubscribes
+        let events = vec!["test-profiles".to_string()];
+        subscribe_window("test-window", &events);
+        subscribe_window("test-window", &events);
+        let open = [MAIN_WINDOW, "test-window"];
+        assert_eq!(
+            recipients(EventAudience::Broadcast, "test-other", open),
+            vec![MAIN_WINDOW]
+        );
+        assert_eq!(
+            recipients(EventAudience::Broadcast, "test-profiles", open).len(),
+            2
+        );
+        unsubscribe_window("test-window", &events);
+        assert!(!window_receives("test-window", "test-other"));
+        unsubscribe_window("test-window", &events);
+        assert!(window_receives("test-window", "test-other"));
+
+        subscribe_window("test-window", &events);
+        clear_window("test-window");
+        assert!(window_receives("test-window", "test-other"));
+    }
+}
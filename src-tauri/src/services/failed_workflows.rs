@@ -0,0 +1,288 @@
+//! FailedWorkflowStore service for persisting failed processing workflows
+//!
+//! When a workflow fails after the recording has been captured (e.g. the
+//! Whisper API is down), the artifacts needed to resume it are recorded in a
+//! "failed jobs" list so the user can retry later instead of re-dictating.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use std::path::PathBuf;
+//! use dicta_clerk_lib::services::{FailedWorkflowStore, WorkflowStage};
+//!
+//! let store = FailedWorkflowStore::new(PathBuf::from("failed_workflows.json"));
+//!
+//! let job = store.record(
+//!     WorkflowStage::Transcription,
+//!     "Network error: connection refused".to_string(),
+//!     Some(PathBuf::from("/tmp/recording.wav")),
+//!     None,
+//!     Some("concise".to_string()),
+//! ).unwrap();
+//!
+//! for failed in store.list().unwrap() {
+//!     println!("{} failed at {:?}: {}", failed.id, failed.stage, failed.error);
+//! }
+//!
+//! store.remove(&job.id).unwrap();
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+use crate::services::workflow_trace;
+
+/// File name of the failed workflows list inside the data directory
+pub const FAILED_WORKFLOWS_FILE: &str = "failed_workflows.json";
+
+/// Errors that can occur while persisting failed workflows
+#[derive(Error, Debug)]
+pub enum FailedWorkflowError {
+    #[error("Failed workflow not found: {id}")]
+    NotFound { id: String },
+    #[error("IO error on {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Invalid failed workflows file: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Result type for failed workflow operations
+pub type FailedWorkflowResult<T> = Result<T, FailedWorkflowError>;
+
+/// Pipeline stage at which a workflow failed
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowStage {
+    /// Encoding or Whisper transcription failed (WAV artifact kept)
+    Transcription,
+    /// Clipboard copy failed (final text kept)
+    Clipboard,
+}
+
+/// A failed workflow together with the artifacts needed to resume it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FailedWorkflow {
+    /// Unique identifier
+    pub id: String,
+    /// Stage that failed
+    pub stage: WorkflowStage,
+    /// Last error message
+    pub error: String,
+    /// Recorded WAV file (for transcription failures)
+    pub wav_path: Option<PathBuf>,
+    /// Final text produced before the failure (for clipboard failures)
+    pub text: Option<String>,
+    /// Profile active when the workflow ran
+    pub profile_id: Option<String>,
+    /// Unix timestamp (seconds) of the original failure
+    pub failed_at: u64,
+    /// Number of manual retries attempted so far
+    pub retry_count: u32,
+    /// Workflow that failed (correlation ID of its logs), resumed on retry
+    #[serde(default)]
+    pub workflow_id: Option<String>,
+}
+
+/// Persistent list of failed workflows backed by a JSON file
+#[derive(Debug, Clone)]
+pub struct FailedWorkflowStore {
+    path: PathBuf,
+}
+
+impl FailedWorkflowStore {
+    /// Create a store backed by the given JSON file
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Create a store in the data directory
+    pub fn default_location() -> Self {
+        Self::new(crate::paths::data_dir().join(FAILED_WORKFLOWS_FILE))
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// List all failed workflows, oldest first
+    pub fn list(&self) -> FailedWorkflowResult<Vec<FailedWorkflow>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(&self.path).map_err(|e| self.io_error(e))?;
+        if content.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Get a failed workflow by ID
+    pub fn get(&self, id: &str) -> FailedWorkflowResult<FailedWorkflow> {
+        self.list()?
+            .into_iter()
+            .find(|job| job.id == id)
+            .ok_or_else(|| FailedWorkflowError::NotFound { id: id.to_string() })
+    }
+
+    /// Record a new failed workflow
+    pub fn record(
+        &self,
+        stage: WorkflowStage,
+        error: String,
+        wav_path: Option<PathBuf>,
+        text: Option<String>,
+        profile_id: Option<String>,
+    ) -> FailedWorkflowResult<FailedWorkflow> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let job = FailedWorkflow {
+            id: format!("{}-{:09}", now.as_secs(), now.subsec_nanos()),
+            stage,
+            error,
+            wav_path,
+            text,
+            profile_id,
+            failed_at: now.as_secs(),
+            retry_count: 0,
+            workflow_id: workflow_trace::current_workflow_id(),
+        };
+
+        let mut jobs = self.list()?;
+        jobs.push(job.clone());
+        self.save(&jobs)?;
+
+        Ok(job)
+    }
+
+    /// Replace a stored failed workflow (e.g. after a failed retry)
+    pub fn update(&self, job: &FailedWorkflow) -> FailedWorkflowResult<()> {
+        let mut jobs = self.list()?;
+        let existing = jobs
+            .iter_mut()
+            .find(|existing| existing.id == job.id)
+            .ok_or_else(|| FailedWorkflowError::NotFound { id: job.id.clone() })?;
+        *existing = job.clone();
+        self.save(&jobs)
+    }
+
+    /// Remove a failed workflow from the list
+    pub fn remove(&self, id: &str) -> FailedWorkflowResult<FailedWorkflow> {
+        let mut jobs = self.list()?;
+        let index = jobs
+            .iter()
+            .position(|job| job.id == id)
+            .ok_or_else(|| FailedWorkflowError::NotFound { id: id.to_string() })?;
+        let removed = jobs.remove(index);
+        self.save(&jobs)?;
+        Ok(removed)
+    }
+
+    /// Atomically write the list to disk
+    fn save(&self, jobs: &[FailedWorkflow]) -> FailedWorkflowResult<()> {
+        let parent_dir = self.path.parent().unwrap_or_else(|| Path::new("."));
+        if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
+            std::fs::create_dir_all(parent_dir).map_err(|e| self.io_error(e))?;
+        }
+
+        let content = serde_json::to_string_pretty(jobs)?;
+        let mut temp_file = NamedTempFile::new_in(parent_dir).map_err(|e| self.io_error(e))?;
+        temp_file
+            .write_all(content.as_bytes())
+            .map_err(|e| self.io_error(e))?;
+        temp_file
+            .persist(&self.path)
+            .map_err(|e| self.io_error(e.error))?;
+
+        Ok(())
+    }
+
+    fn io_error(&self, source: std::io::Error) -> FailedWorkflowError {
+        FailedWorkflowError::Io {
+            path: self.path.to_string_lossy().to_string(),
+            source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_store(temp_dir: &TempDir) -> FailedWorkflowStore {
+        FailedWorkflowStore::new(temp_dir.path().join(FAILED_WORKFLOWS_FILE))
+    }
+
+    #[test]
+    fn test_empty_store_lists_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = create_store(&temp_dir);
+
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_record_persists_across_instances() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = create_store(&temp_dir);
+
+        let job = store
+            .record(
+                WorkflowStage::Transcription,
+                "Network error".to_string(),
+                Some(PathBuf::from("/tmp/recording.wav")),
+                None,
+                Some("concise".to_string()),
+            )
+            .unwrap();
+
+        let reloaded = create_store(&temp_dir);
+        let jobs = reloaded.list().unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0], job);
+        assert_eq!(jobs[0].retry_count, 0);
+    }
+
+    #[test]
+    fn test_update_and_remove() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = create_store(&temp_dir);
+
+        let mut job = store
+            .record(
+                WorkflowStage::Clipboard,
+                "Clipboard unavailable".to_string(),
+                None,
+                Some("final text".to_string()),
+                None,
+            )
+            .unwrap();
+
+        job.retry_count += 1;
+        job.error = "Still unavailable".to_string();
+        store.update(&job).unwrap();
+        assert_eq!(store.get(&job.id).unwrap().retry_count, 1);
+
+        store.remove(&job.id).unwrap();
+        assert!(matches!(
+            store.get(&job.id),
+            Err(FailedWorkflowError::NotFound { .. })
+        ));
+        assert!(matches!(
+            store.remove(&job.id),
+            Err(FailedWorkflowError::NotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+//! Feature flags for experimental subsystems
+//!
+//! Experimental code ships dark: it only runs when its flag is on. The flags
+//! are stored in the `features` section of `settings.json` and mirrored in a
+//! process-wide [`FeatureRegistry`], so services can consult them without
+//! loading the settings.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::feature_flags::{FeatureFlag, FeatureFlags, FeatureRegistry};
+//!
+//! let registry = FeatureRegistry::new();
+//! assert!(!registry.is_enabled(FeatureFlag::WakeWord));
+//!
+//! registry.load(FeatureFlags {
+//!     wake_word: true,
+//!     ..FeatureFlags::default()
+//! });
+//! assert!(registry.is_enabled(FeatureFlag::WakeWord));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::sync::{OnceLock, RwLock};
+
+/// An experimental subsystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureFlag {
+    /// Partial transcripts while recording
+    StreamingTranscription,
+    /// Start recording on a spoken wake word
+    WakeWord,
+    /// Format with a local language model instead of the cloud one
+    LocalLlm,
+}
+
+impl FeatureFlag {
+    /// Every flag, in display order
+    pub const ALL: [FeatureFlag; 3] = [
+        FeatureFlag::StreamingTranscription,
+        FeatureFlag::WakeWord,
+        FeatureFlag::LocalLlm,
+    ];
+
+    /// What the flag turns on, for the settings UI
+    pub fn description(self) -> &'static str {
+        match self {
+            FeatureFlag::StreamingTranscription => {
+                "Show partial transcripts while recording (streaming providers)"
+            }
+            FeatureFlag::WakeWord => "Start recording when a wake word is spoken",
+            FeatureFlag::LocalLlm => "Format transcripts with a local language model",
+        }
+    }
+}
+
+/// State of every flag (`features` section of the settings)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeatureFlags {
+    pub streaming_transcription: bool,
+    pub wake_word: bool,
+    pub local_llm: bool,
+}
+
+impl FeatureFlags {
+    /// Whether a flag is on
+    pub fn is_enabled(&self, flag: FeatureFlag) -> bool {
+        match flag {
+            FeatureFlag::StreamingTranscription => self.streaming_transcription,
+            FeatureFlag::WakeWord => self.wake_word,
+            FeatureFlag::LocalLlm => self.local_llm,
+        }
+    }
+
+    /// Turn a flag on or off
+    pub fn set(&mut self, flag: FeatureFlag, enabled: bool) {
+        match flag {
+            FeatureFlag::StreamingTranscription => self.streaming_transcription = enabled,
+            FeatureFlag::WakeWord => self.wake_word = enabled,
+            FeatureFlag::LocalLlm => self.local_llm = enabled,
+        }
+    }
+}
+
+/// Current feature flags, shared by commands and services
+#[derive(Debug, Default)]
+pub struct FeatureRegistry {
+    flags: RwLock<FeatureFlags>,
+}
+
+impl FeatureRegistry {
+    /// Create a registry with every flag off
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process-wide registry, loaded from the settings at startup
+    pub fn global() -> &'static FeatureRegistry {
+        static GLOBAL: OnceLock<FeatureRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(FeatureRegistry::new)
+    }
+
+    /// Replace every flag (e.g. after the settings were saved)
+    pub fn load(&self, flags: FeatureFlags) {
+        if let Ok(mut current) = self.flags.write() {
+            *current = flags;
+        }
+    }
+
+    /// Current state of every flag
+    pub fn flags(&self) -> FeatureFlags {
+        self.flags.read().map(|flags| *flags).unwrap_or_default()
+    }
+
+    /// Whether a flag is on
+    pub fn is_enabled(&self, flag: FeatureFlag) -> bool {
+        self.flags().is_enabled(flag)
+    }
+}
+
+/// Whether a flag is on in the process-wide registry
+pub fn is_enabled(flag: FeatureFlag) -> bool {
+    FeatureRegistry::global().is_enabled(flag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_set_and_serialize_by_key() {
+        let mut flags = FeatureFlags::default();
+        assert!(FeatureFlag::ALL.iter().all(|&flag| !flags.is_enabled(flag)));
+
+        flags.set(FeatureFlag::LocalLlm, true);
+        assert!(flags.is_enabled(FeatureFlag::LocalLlm));
+        assert!(!flags.is_enabled(FeatureFlag::WakeWord));
+
+        assert_eq!(
+            serde_json::to_value(FeatureFlag::StreamingTranscription).unwrap(),
+            "streaming_transcription"
+        );
+        let parsed: FeatureFlags = serde_json::from_str(r#"{"wake_word": true}"#).unwrap();
+        assert!(parsed.wake_word && !parsed.local_llm);
+
+        let registry = FeatureRegistry::new();
+        registry.load(flags);
+        assert!(registry.is_enabled(FeatureFlag::LocalLlm));
+        assert_eq!(registry.flags(), flags);
+    }
+}
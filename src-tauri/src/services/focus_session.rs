@@ -0,0 +1,202 @@
+//! Focus sessions: timed dictation sprints (Pomodoro style)
+//!
+//! A session runs for a fixed number of minutes. Recordings completed while it
+//! runs are tagged with its ID, and shortcuts for profiles other than the one
+//! the session was started with are suppressed, so a sprint is not derailed by
+//! a stray hotkey. When the session ends, a digest sums up what was dictated.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::focus_session::FocusSession;
+//!
+//! let mut session = FocusSession::new(Some("blog".to_string()), 25, 1_700_000_000);
+//! assert!(!session.allows_profile("email"));
+//!
+//! session.add_recording("First draft of the introduction.", Some("blog".to_string()), 1_700_000_300);
+//! let digest = session.digest(1_700_001_500);
+//! assert_eq!(digest.recordings, 1);
+//! assert_eq!(digest.total_words, 5);
+//! ```
+
+use chrono::{Local, TimeZone};
+use serde::Serialize;
+
+use crate::services::goals::count_words;
+
+/// A recording completed during a focus session
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FocusRecording {
+    /// Unix timestamp (seconds) the workflow completed at
+    pub recorded_at: u64,
+    pub words: u64,
+    pub text: String,
+    pub profile_id: Option<String>,
+}
+
+/// A running focus session
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FocusSession {
+    pub id: String,
+    /// Profile the session is bound to (shortcuts for others are suppressed)
+    pub profile_id: Option<String>,
+    /// Unix timestamp (seconds) the session started at
+    pub started_at: u64,
+    pub duration_minutes: u32,
+    pub recordings: Vec<FocusRecording>,
+}
+
+/// Summary of a finished focus session (`focus-session-ended` payload)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FocusDigest {
+    pub session_id: String,
+    pub profile_id: Option<String>,
+    pub started_at: u64,
+    pub ended_at: u64,
+    pub planned_minutes: u32,
+    /// Minutes the session actually lasted (shorter when ended early)
+    pub actual_minutes: u32,
+    pub recordings: usize,
+    pub total_words: u64,
+    pub words_per_minute: f32,
+    /// Markdown summary with the dictated texts
+    pub summary: String,
+}
+
+impl FocusSession {
+    /// Start a session of `duration_minutes` at `now` (Unix seconds)
+    pub fn new(profile_id: Option<String>, duration_minutes: u32, now: u64) -> Self {
+        Self {
+            id: format!("focus-{}", now),
+            profile_id,
+            started_at: now,
+            duration_minutes,
+            recordings: Vec::new(),
+        }
+    }
+
+    /// Unix timestamp the session is planned to end at
+    pub fn ends_at(&self) -> u64 {
+        self.started_at + u64::from(self.duration_minutes) * 60
+    }
+
+    /// Seconds left at `now`
+    pub fn remaining_seconds(&self, now: u64) -> u64 {
+        self.ends_at().saturating_sub(now)
+    }
+
+    /// Whether a shortcut may switch to `profile_id` during the session
+    pub fn allows_profile(&self, profile_id: &str) -> bool {
+        self.profile_id
+            .as_deref()
+            .is_none_or(|session_profile| session_profile == profile_id)
+    }
+
+    /// Tag a completed recording to the session
+    pub fn add_recording(&mut self, text: &str, profile_id: Option<String>, now: u64) {
+        self.recordings.push(FocusRecording {
+            recorded_at: now,
+            words: count_words(text),
+            text: text.to_string(),
+            profile_id,
+        });
+    }
+
+    /// Digest of the session ended at `ended_at`
+    pub fn digest(&self, ended_at: u64) -> FocusDigest {
+        let total_words: u64 = self
+            .recordings
+            .iter()
+            .map(|recording| recording.words)
+            .sum();
+        let elapsed_seconds = ended_at.saturating_sub(self.started_at);
+        let actual_minutes = elapsed_seconds.div_ceil(60) as u32;
+        let words_per_minute = if elapsed_seconds == 0 {
+            0.0
+        } else {
+            total_words as f32 * 60.0 / elapsed_seconds as f32
+        };
+
+        let mut summary = format!(
+            "# Focus session ({} of {} min)\n\n{} recording(s), {} words ({:.0} words/min)\n",
+            actual_minutes,
+            self.duration_minutes,
+            self.recordings.len(),
+            total_words,
+            words_per_minute
+        );
+        for recording in &self.recordings {
+            summary.push_str(&format!(
+                "\n## {}\n\n{}\n",
+                clock_time(recording.recorded_at),
+                recording.text.trim()
+            ));
+        }
+
+        FocusDigest {
+            session_id: self.id.clone(),
+            profile_id: self.profile_id.clone(),
+            started_at: self.started_at,
+            ended_at,
+            planned_minutes: self.duration_minutes,
+            actual_minutes,
+            recordings: self.recordings.len(),
+            total_words,
+            words_per_minute,
+            summary,
+        }
+    }
+}
+
+/// Local time of day (HH:MM) of a Unix timestamp
+fn clock_time(timestamp: u64) -> String {
+    i64::try_from(timestamp)
+        .ok()
+        .and_then(|timestamp| Local.timestamp_opt(timestamp, 0).single())
+        .map(|time| time.format("%H:%M").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_profile_restriction() {
+        let session = FocusSession::new(Some("blog".to_string()), 25, 1000);
+        assert!(session.allows_profile("blog"));
+        assert!(!session.allows_profile("email"));
+        assert_eq!(session.ends_at(), 1000 + 25 * 60);
+        assert_eq!(session.remaining_seconds(1060), 24 * 60);
+        assert_eq!(session.remaining_seconds(5000), 0);
+
+        let unbound = FocusSession::new(None, 25, 1000);
+        assert!(unbound.allows_profile("email"));
+    }
+
+    #[test]
+    fn test_digest_sums_up_recordings() {
+        let mut session = FocusSession::new(None, 25, 1000);
+        session.add_recording("One two three.", Some("blog".to_string()), 1100);
+        session.add_recording("  Four five six seven. ", None, 1500);
+
+        // Ended early, after 10 minutes
+        let digest = session.digest(1600);
+        assert_eq!(digest.recordings, 2);
+        assert_eq!(digest.total_words, 7);
+        assert_eq!(digest.actual_minutes, 10);
+        assert_eq!(digest.planned_minutes, 25);
+        assert_eq!(digest.words_per_minute, 0.7);
+        assert!(digest
+            .summary
+            .starts_with("# Focus session (10 of 25 min)\n\n2 recording(s), 7 words"));
+        assert!(digest.summary.contains("\n\nFour five six seven.\n"));
+
+        assert_eq!(
+            FocusSession::new(None, 25, 1000)
+                .digest(1000)
+                .words_per_minute,
+            0.0
+        );
+    }
+}
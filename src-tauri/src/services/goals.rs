@@ -0,0 +1,182 @@
+//! Dictation goals: words per day and per session
+//!
+//! Progress is computed from the history, so it survives restarts and counts
+//! exactly what was kept. The words of an entry are those of its final text;
+//! days follow the local calendar. A session is the current run of the app.
+//! The streak counts the consecutive days the daily goal was reached, ending
+//! today (or yesterday, while today's goal is still open).
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use chrono::Local;
+//! use dicta_clerk_lib::services::goals::{compute_progress, count_words};
+//!
+//! assert_eq!(count_words("Dear team, thanks!"), 3);
+//!
+//! let progress = compute_progress(&[], 500, 0, 0, Local::now());
+//! assert_eq!(progress.today_words, 0);
+//! assert!(!progress.daily_reached);
+//! ```
+
+use chrono::{DateTime, Days, Local, NaiveDate, TimeZone};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::services::HistoryEntry;
+
+/// Progress towards the dictation goals (`goal-progress` payload)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GoalProgress {
+    /// Words dictated today
+    pub today_words: u64,
+    pub daily_goal: u32,
+    /// Share of the daily goal reached (0.0-1.0)
+    pub daily_progress: f32,
+    pub daily_reached: bool,
+    /// The latest dictation completed the daily goal
+    pub daily_just_reached: bool,
+    /// Words dictated since the app started
+    pub session_words: u64,
+    /// Session goal (0 = none)
+    pub session_goal: u32,
+    pub session_reached: bool,
+    /// Consecutive days the daily goal was reached
+    pub streak_days: u32,
+}
+
+/// Words in a text
+pub fn count_words(text: &str) -> u64 {
+    text.split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .count() as u64
+}
+
+/// Goal progress from the history entries
+///
+/// `session_started_at` is the Unix timestamp the app started at.
+pub fn compute_progress(
+    entries: &[HistoryEntry],
+    daily_goal: u32,
+    session_goal: u32,
+    session_started_at: u64,
+    now: DateTime<Local>,
+) -> GoalProgress {
+    let today = now.date_naive();
+    let mut words_per_day: HashMap<NaiveDate, u64> = HashMap::new();
+    let mut session_words = 0;
+    let mut latest_today: Option<(u64, u64)> = None;
+
+    for entry in entries {
+        let Some(day) = local_day(entry.created_at) else {
+            continue;
+        };
+        let words = count_words(&entry.final_text);
+        *words_per_day.entry(day).or_default() += words;
+        if entry.created_at >= session_started_at {
+            session_words += words;
+        }
+        if day == today && latest_today.is_none_or(|(at, _)| entry.created_at >= at) {
+            latest_today = Some((entry.created_at, words));
+        }
+    }
+
+    let today_words = words_per_day.get(&today).copied().unwrap_or(0);
+    let daily_goal_words = u64::from(daily_goal.max(1));
+    let daily_reached = today_words >= daily_goal_words;
+    let latest_words = latest_today.map(|(_, words)| words).unwrap_or(0);
+
+    // Count back from today, or from yesterday while today is still open
+    let reached =
+        |day: &NaiveDate| words_per_day.get(day).copied().unwrap_or(0) >= daily_goal_words;
+    let mut day = if daily_reached {
+        Some(today)
+    } else {
+        today.checked_sub_days(Days::new(1))
+    };
+    let mut streak_days = 0;
+    while let Some(current) = day.filter(reached) {
+        streak_days += 1;
+        day = current.checked_sub_days(Days::new(1));
+    }
+
+    GoalProgress {
+        today_words,
+        daily_goal,
+        daily_progress: (today_words as f32 / daily_goal_words as f32).min(1.0),
+        daily_reached,
+        daily_just_reached: daily_reached && today_words - latest_words < daily_goal_words,
+        session_words,
+        session_goal,
+        session_reached: session_goal > 0 && session_words >= u64::from(session_goal),
+        streak_days,
+    }
+}
+
+/// Local calendar day of a Unix timestamp
+pub(crate) fn local_day(timestamp: u64) -> Option<NaiveDate> {
+    Local
+        .timestamp_opt(i64::try_from(timestamp).ok()?, 0)
+        .single()
+        .map(|time| time.date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(created_at: DateTime<Local>, text: &str) -> HistoryEntry {
+        HistoryEntry {
+            id: created_at.timestamp().to_string(),
+            created_at: created_at.timestamp() as u64,
+            final_text: text.to_string(),
+            original_transcript: text.to_string(),
+            profile_id: None,
+            has_audio: false,
+            segments: Vec::new(),
+            focus_session_id: None,
+            workflow_id: None,
+            edited_at: None,
+        }
+    }
+
+    #[test]
+    fn test_count_words_ignores_punctuation() {
+        assert_eq!(count_words("Hello,  world - this is\na test."), 6);
+        assert_eq!(count_words(""), 0);
+    }
+
+    #[test]
+    fn test_progress_per_day_session_and_streak() {
+        let now = Local.with_ymd_and_hms(2026, 3, 12, 15, 0, 0).unwrap();
+        let hours_ago = |hours| now - chrono::Duration::hours(hours);
+        let entries = vec![
+            // Three days ago: goal missed, breaks the streak
+            entry(hours_ago(75), "one two"),
+            // Two days ago and yesterday: goal reached
+            entry(hours_ago(50), "one two three four"),
+            entry(hours_ago(26), "one two three"),
+            entry(hours_ago(25), "four five"),
+            // Today, before and during the session
+            entry(hours_ago(5), "one two"),
+            entry(hours_ago(1), "three four five"),
+        ];
+
+        let progress = compute_progress(&entries, 4, 10, hours_ago(2).timestamp() as u64, now);
+        assert_eq!(progress.today_words, 5);
+        assert!(progress.daily_reached);
+        assert!(progress.daily_just_reached);
+        assert_eq!(progress.daily_progress, 1.0);
+        assert_eq!(progress.session_words, 3);
+        assert!(!progress.session_reached);
+        assert_eq!(progress.streak_days, 3);
+
+        // Today's goal still open: the streak ends yesterday
+        let progress = compute_progress(&entries, 6, 0, 0, now);
+        assert!(!progress.daily_reached);
+        assert!(!progress.daily_just_reached);
+        assert_eq!(progress.streak_days, 0);
+        let progress = compute_progress(&entries[..4], 5, 0, 0, now);
+        assert_eq!(progress.streak_days, 1);
+    }
+}
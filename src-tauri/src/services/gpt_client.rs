@@ -1,7 +1,16 @@
-use reqwest::Client;
+use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use thiserror::Error;
 
+use crate::services::api_limiter::ApiLimiter;
+use crate::services::network::{NetworkConfig, NetworkError};
+use crate::services::retry_policy::{self, RetryPolicy};
+use crate::services::workflow_trace;
+
+/// Model used to format transcripts (fast and cost-effective)
+pub const FORMATTING_MODEL: &str = "gpt-4o";
+
 /// Errors that can occur during GPT API operations
 #[derive(Error, Debug)]
 pub enum GptError {
@@ -11,6 +20,16 @@ pub enum GptError {
     #[error("API error: {message}")]
     ApiError { message: String },
 
+    #[error("Server error ({status}): {message}")]
+    Server { status: u16, message: String },
+
+    #[error("Rate limited: {message}")]
+    RateLimit {
+        message: String,
+        /// Delay asked for by the server before retrying
+        retry_after: Option<Duration>,
+    },
+
     #[error("Invalid response format: {message}")]
     InvalidResponse { message: String },
 
@@ -19,6 +38,24 @@ pub enum GptError {
 
     #[error("API key not configured")]
     ApiKeyNotConfigured,
+
+    #[error(transparent)]
+    Network(#[from] NetworkError),
+}
+
+impl GptError {
+    /// Whether the request may be retried (None when it may not), with the
+    /// delay asked for by the server
+    fn retry_after(&self) -> Option<Option<Duration>> {
+        match self {
+            GptError::Server { .. } => Some(None),
+            GptError::RequestFailed(e) if e.is_timeout() || e.is_connect() => Some(None),
+            // An exhausted quota does not come back by waiting
+            GptError::RateLimit { message, .. } if message.contains("insufficient_quota") => None,
+            GptError::RateLimit { retry_after, .. } => Some(*retry_after),
+            _ => None,
+        }
+    }
 }
 
 pub type GptResult<T> = Result<T, GptError>;
@@ -76,6 +113,10 @@ struct GptErrorDetail {
 pub struct GptClient {
     client: Client,
     api_key: String,
+    /// When true, formatting is simulated locally and no request is sent
+    dry_run: bool,
+    /// Retries of server errors, network errors and rate limits
+    retry: RetryPolicy,
 }
 
 impl GptClient {
@@ -86,7 +127,44 @@ impl GptClient {
             .build()
             .unwrap_or_else(|_| Client::new());
 
-        Self { client, api_key }
+        Self {
+            client,
+            api_key,
+            dry_run: false,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Create a new GPT client going through the configured proxy and
+    /// trusting the configured CA certificates
+    pub fn with_network(api_key: String, network: &NetworkConfig) -> GptResult<Self> {
+        let client = network
+            .apply(Client::builder().timeout(std::time::Duration::from_secs(10)))?
+            .build()?;
+
+        Ok(Self {
+            client,
+            api_key,
+            dry_run: false,
+            retry: RetryPolicy::default(),
+        })
+    }
+
+    /// Create a GPT client for the workflow dry-run mode (never calls the API)
+    pub fn new_dry_run() -> Self {
+        Self {
+            client: Client::new(),
+            api_key: String::new(),
+            dry_run: true,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Use `retry` for the failed requests (default: 3 retries from 1s,
+    /// with jitter)
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
     }
 
     /// Format text using GPT-4o with profile instructions
@@ -106,6 +184,15 @@ impl GptClient {
         input_example: &str,
         output_example: &str,
     ) -> GptResult<String> {
+        if self.dry_run {
+            log::debug!("Dry run: skipping GPT request");
+            return Ok(format!(
+                "[Dry run] Formatted with \"{}\":\n{}",
+                profile_prompt.chars().take(60).collect::<String>(),
+                text
+            ));
+        }
+
         if self.api_key.is_empty() {
             return Err(GptError::ApiKeyNotConfigured);
         }
@@ -115,7 +202,7 @@ impl GptClient {
 
         // Construct the request
         let request = GptRequest {
-            model: "gpt-4o".to_string(), // Fast and cost-effective
+            model: FORMATTING_MODEL.to_string(),
             messages: vec![
                 GptMessage {
                     role: "system".to_string(),
@@ -153,37 +240,93 @@ impl GptClient {
         prompt
     }
 
-    /// Send the GPT request and handle the response
+    /// Send the GPT request, retrying server errors, network errors and rate
+    /// limits (see [`RetryPolicy`])
     async fn send_request(&self, request: GptRequest) -> GptResult<String> {
+        let mut attempt = 0;
+        loop {
+            let error = match self.send_single_request(&request).await {
+                Ok(formatted_text) => return Ok(formatted_text),
+                Err(error) => error,
+            };
+
+            let Some(retry_after) = error.retry_after() else {
+                return Err(error);
+            };
+            if attempt >= self.retry.max_retries {
+                return Err(error);
+            }
+            let Some(delay) = self.retry.retry_delay(attempt, retry_after) else {
+                log::debug!(
+                    "{}Retry-After of {:?} exceeds the longest retry delay, giving up",
+                    workflow_trace::log_prefix(),
+                    retry_after
+                );
+                return Err(error);
+            };
+            log::debug!(
+                "{}Formatting attempt {} failed ({}), retrying in {:?}",
+                workflow_trace::log_prefix(),
+                attempt + 1,
+                error,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Send the GPT request once and handle the response
+    async fn send_single_request(&self, request: &GptRequest) -> GptResult<String> {
         log::debug!("Sending GPT-4 request with model: {}", request.model);
 
-        let response = self
+        // Held until the response is read (concurrency and per-minute caps)
+        let _permit = ApiLimiter::global().acquire().await;
+
+        let mut request_builder = self
             .client
             .post("https://api.openai.com/v1/chat/completions")
             .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await?;
+            .header("Content-Type", "application/json");
+        if let Some(workflow_id) = workflow_trace::current_workflow_id() {
+            request_builder =
+                request_builder.header(workflow_trace::REQUEST_ID_HEADER, workflow_id);
+        }
+        let response = request_builder.json(request).send().await?;
 
         let status = response.status();
+        workflow_trace::log_api_response(
+            "Chat completions",
+            status.as_u16(),
+            response
+                .headers()
+                .get("x-request-id")
+                .and_then(|value| value.to_str().ok()),
+        );
+        let retry_after = retry_policy::retry_after(response.headers());
         let response_text = response.text().await?;
 
         if !status.is_success() {
             // Try to parse error response
-            if let Ok(error_response) = serde_json::from_str::<GptErrorResponse>(&response_text) {
-                return Err(GptError::ApiError {
-                    message: format!(
-                        "{} ({})",
-                        error_response.error.message,
-                        error_response.error.error_type.unwrap_or_default()
-                    ),
-                });
-            } else {
-                return Err(GptError::ApiError {
-                    message: format!("HTTP {}: {}", status, response_text),
-                });
-            }
+            let message = match serde_json::from_str::<GptErrorResponse>(&response_text) {
+                Ok(error_response) => format!(
+                    "{} ({})",
+                    error_response.error.message,
+                    error_response.error.error_type.unwrap_or_default()
+                ),
+                Err(_) => format!("HTTP {}: {}", status, response_text),
+            };
+            return Err(match status {
+                StatusCode::TOO_MANY_REQUESTS => GptError::RateLimit {
+                    message,
+                    retry_after,
+                },
+                status if status.is_server_error() => GptError::Server {
+                    status: status.as_u16(),
+                    message,
+                },
+                _ => GptError::ApiError { message },
+            });
         }
 
         // Parse successful response
@@ -260,4 +403,41 @@ mod tests {
 
         assert!(matches!(result, Err(GptError::ApiKeyNotConfigured)));
     }
+
+    #[test]
+    fn test_retryable_errors() {
+        let rate_limit = GptError::RateLimit {
+            message: "Rate limit reached (requests)".to_string(),
+            retry_after: Some(Duration::from_secs(2)),
+        };
+        assert_eq!(rate_limit.retry_after(), Some(Some(Duration::from_secs(2))));
+        let server = GptError::Server {
+            status: 503,
+            message: "Overloaded".to_string(),
+        };
+        assert_eq!(server.retry_after(), Some(None));
+
+        let quota = GptError::RateLimit {
+            message: "You exceeded your current quota (insufficient_quota)".to_string(),
+            retry_after: None,
+        };
+        assert_eq!(quota.retry_after(), None);
+        let client_error = GptError::ApiError {
+            message: "Invalid model".to_string(),
+        };
+        assert_eq!(client_error.retry_after(), None);
+    }
+
+    #[tokio::test]
+    async fn test_format_text_dry_run() {
+        let client = GptClient::new_dry_run();
+        let result = client
+            .format_text("raw text", "Format as email", "", "")
+            .await
+            .unwrap();
+
+        assert!(result.starts_with("[Dry run]"));
+        assert!(result.contains("Format as email"));
+        assert!(result.ends_with("raw text"));
+    }
 }
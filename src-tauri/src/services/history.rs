@@ -0,0 +1,670 @@
+//! HistoryStore service for the clipboard history and the audio archive
+//!
+//! Every completed workflow can be kept in a history list (final text,
+//! original transcript and profile) and, when archiving is enabled, together
+//! with its encoded recording. With a [`DataKey`] both the list and the
+//! archived audio are encrypted at rest. A retention period applies to the
+//! history and the archive: [`HistoryStore::purge_older_than`] removes the
+//! expired entries and their recordings together. With a [`RetentionPolicy`],
+//! profiles can keep their entries for a shorter or longer time (or forever).
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use std::path::{Path, PathBuf};
+//! use dicta_clerk_lib::services::{DataKey, HistoryStore};
+//!
+//! let store = HistoryStore::new(PathBuf::from("history"), Some(DataKey::from_keyring().unwrap()));
+//!
+//! let entry = store.record(
+//!     "Final text".to_string(),
+//!     "final text".to_string(),
+//!     Vec::new(),
+//!     Some("concise".to_string()),
+//!     Some(Path::new("/tmp/recording.ogg")),
+//! ).unwrap();
+//!
+//! let audio = store.read_audio(&entry.id).unwrap();
+//! println!("{} bytes of audio", audio.len());
+//!
+//! // Keep 30 days
+//! let report = store.purge_older_than(30 * 24 * 60 * 60).unwrap();
+//! println!("Purged {} entries", report.entries);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+use crate::services::encryption::{is_encrypted, DataKey, EncryptionError};
+use crate::services::whisper_client::TranscriptionSegment;
+use crate::services::workflow_trace;
+
+/// Directory of the history inside the data directory
+pub const HISTORY_DIR: &str = "history";
+
+/// File name of the history list
+const HISTORY_FILE: &str = "history.json";
+
+/// Sub-directory of archived recordings
+const AUDIO_DIR: &str = "audio";
+
+/// Errors that can occur while persisting the history
+#[derive(Error, Debug)]
+pub enum HistoryError {
+    #[error("History entry not found: {id}")]
+    NotFound { id: String },
+    #[error("No archived audio for history entry {id}")]
+    NoAudio { id: String },
+    #[error("IO error on {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Invalid history file: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("{path} is encrypted but no data key is available")]
+    KeyRequired { path: String },
+    #[error(transparent)]
+    Encryption(#[from] EncryptionError),
+}
+
+/// Result type for history operations
+pub type HistoryResult<T> = Result<T, HistoryError>;
+
+/// A completed workflow kept in the history
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryEntry {
+    /// Unique identifier (also names the archived audio file)
+    pub id: String,
+    /// Unix timestamp (seconds) of the workflow
+    pub created_at: u64,
+    /// Text copied to the clipboard
+    pub final_text: String,
+    /// Transcript before formatting
+    pub original_transcript: String,
+    /// Profile used
+    pub profile_id: Option<String>,
+    /// Whether the recording was archived
+    pub has_audio: bool,
+    /// Timed segments of the transcript (empty when the provider returned none)
+    #[serde(default)]
+    pub segments: Vec<TranscriptionSegment>,
+    /// Focus session the recording was made in
+    #[serde(default)]
+    pub focus_session_id: Option<String>,
+    /// Workflow that produced the entry (correlation ID of its logs)
+    #[serde(default)]
+    pub workflow_id: Option<String>,
+    /// Unix timestamp (seconds) of the last correction of the final text
+    #[serde(default)]
+    pub edited_at: Option<u64>,
+}
+
+/// What a purge removed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PurgeReport {
+    /// History entries removed
+    pub entries: usize,
+    /// Archived recordings removed
+    pub audio_files: usize,
+}
+
+/// How long history entries are kept, in seconds (None = forever)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RetentionPolicy {
+    /// Maximum age of entries without a profile override
+    pub max_age_secs: Option<u64>,
+    /// Maximum age of the entries of a profile, by profile ID
+    pub profile_max_age_secs: HashMap<String, Option<u64>>,
+}
+
+impl RetentionPolicy {
+    /// The same maximum age for every entry
+    pub fn uniform(max_age_secs: Option<u64>) -> Self {
+        Self {
+            max_age_secs,
+            profile_max_age_secs: HashMap::new(),
+        }
+    }
+
+    /// Maximum age of an entry made with `profile_id`
+    pub fn max_age_for(&self, profile_id: Option<&str>) -> Option<u64> {
+        profile_id
+            .and_then(|id| self.profile_max_age_secs.get(id))
+            .copied()
+            .unwrap_or(self.max_age_secs)
+    }
+
+    /// Whether nothing is ever purged
+    pub fn keeps_everything(&self) -> bool {
+        self.max_age_secs.is_none() && self.profile_max_age_secs.values().all(Option::is_none)
+    }
+}
+
+/// Persistent history list and audio archive in one directory
+#[derive(Debug, Clone)]
+pub struct HistoryStore {
+    dir: PathBuf,
+    key: Option<DataKey>,
+    encrypt: bool,
+}
+
+impl HistoryStore {
+    /// Create a store in `dir`; data is encrypted when a key is given
+    pub fn new(dir: PathBuf, key: Option<DataKey>) -> Self {
+        let encrypt = key.is_some();
+        Self { dir, key, encrypt }
+    }
+
+    /// Read encrypted data with `key` but write new data in plain text
+    ///
+    /// Used after encryption at rest has been switched off, until
+    /// [`HistoryStore::reencrypt`] has rewritten the existing files.
+    pub fn with_decryption_key(mut self, key: DataKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Create a store in the data directory
+    pub fn default_location(key: Option<DataKey>) -> Self {
+        Self::new(crate::paths::data_dir().join(HISTORY_DIR), key)
+    }
+
+    /// Directory of the store
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Whether new data is encrypted
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypt
+    }
+
+    /// Whether any stored file is encrypted
+    pub fn contains_encrypted_data(&self) -> HistoryResult<bool> {
+        let history_path = self.history_path();
+        if history_path.exists() && file_is_encrypted(&history_path)? {
+            return Ok(true);
+        }
+
+        let audio_dir = self.dir.join(AUDIO_DIR);
+        if !audio_dir.exists() {
+            return Ok(false);
+        }
+        for dir_entry in std::fs::read_dir(&audio_dir).map_err(|e| io_error(&audio_dir, e))? {
+            let path = dir_entry.map_err(|e| io_error(&audio_dir, e))?.path();
+            if file_is_encrypted(&path)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// List all entries, newest first
+    pub fn list(&self) -> HistoryResult<Vec<HistoryEntry>> {
+        let path = self.history_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = self.read_file(&path)?;
+        if content.iter().all(u8::is_ascii_whitespace) {
+            return Ok(Vec::new());
+        }
+
+        let mut entries: Vec<HistoryEntry> = serde_json::from_slice(&content)?;
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.created_at));
+        Ok(entries)
+    }
+
+    /// Record a completed workflow, archiving `audio_path` when given
+    pub fn record(
+        &self,
+        final_text: String,
+        original_transcript: String,
+        segments: Vec<TranscriptionSegment>,
+        profile_id: Option<String>,
+        audio_path: Option<&Path>,
+    ) -> HistoryResult<HistoryEntry> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let id = format!("{}-{:09}", now.as_secs(), now.subsec_nanos());
+
+        if let Some(audio_path) = audio_path {
+            let audio = std::fs::read(audio_path).map_err(|e| io_error(audio_path, e))?;
+            self.write_file(&self.audio_path(&id), &audio)?;
+        }
+
+        let entry = HistoryEntry {
+            id,
+            created_at: now.as_secs(),
+            final_text,
+            original_transcript,
+            profile_id,
+            has_audio: audio_path.is_some(),
+            segments,
+            focus_session_id: None,
+            workflow_id: workflow_trace::current_workflow_id(),
+            edited_at: None,
+        };
+
+        let mut entries = self.list()?;
+        entries.insert(0, entry.clone());
+        self.save(&entries)?;
+
+        Ok(entry)
+    }
+
+    /// Get an entry by ID
+    pub fn get(&self, id: &str) -> HistoryResult<HistoryEntry> {
+        self.list()?
+            .into_iter()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| HistoryError::NotFound { id: id.to_string() })
+    }
+
+    /// Tag an entry with the focus session it was recorded in
+    pub fn tag_focus_session(&self, id: &str, session_id: &str) -> HistoryResult<HistoryEntry> {
+        let mut entries = self.list()?;
+        let entry = entries
+            .iter_mut()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| HistoryError::NotFound { id: id.to_string() })?;
+        entry.focus_session_id = Some(session_id.to_string());
+        let entry = entry.clone();
+        self.save(&entries)?;
+        Ok(entry)
+    }
+
+    /// Replace the final text of an entry with a corrected one
+    pub fn update_text(&self, id: &str, text: String) -> HistoryResult<HistoryEntry> {
+        let mut entries = self.list()?;
+        let entry = entries
+            .iter_mut()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| HistoryError::NotFound { id: id.to_string() })?;
+        entry.final_text = text;
+        entry.edited_at = Some(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        );
+        let entry = entry.clone();
+        self.save(&entries)?;
+        Ok(entry)
+    }
+
+    /// Decrypted bytes of the recording archived with an entry
+    pub fn read_audio(&self, id: &str) -> HistoryResult<Vec<u8>> {
+        let path = self.audio_path(id);
+        if !path.exists() {
+            return Err(HistoryError::NoAudio { id: id.to_string() });
+        }
+        self.read_file(&path)
+    }
+
+    /// Remove entries and recordings older than `max_age_secs`
+    pub fn purge_older_than(&self, max_age_secs: u64) -> HistoryResult<PurgeReport> {
+        self.purge(&RetentionPolicy::uniform(Some(max_age_secs)))
+    }
+
+    /// Remove the entries and recordings older than the retention of their profile
+    ///
+    /// Recordings left behind without an entry follow the default retention.
+    pub fn purge(&self, policy: &RetentionPolicy) -> HistoryResult<PurgeReport> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let cutoff = |max_age_secs: Option<u64>| max_age_secs.map(|age| now.saturating_sub(age));
+
+        let entries = self.list()?;
+        let (kept, expired): (Vec<_>, Vec<_>) = entries.into_iter().partition(|entry| {
+            cutoff(policy.max_age_for(entry.profile_id.as_deref()))
+                .is_none_or(|cutoff| entry.created_at >= cutoff)
+        });
+
+        let mut report = PurgeReport {
+            entries: expired.len(),
+            ..PurgeReport::default()
+        };
+        if !expired.is_empty() {
+            self.save(&kept)?;
+        }
+
+        // Remove expired recordings, including ones left behind without an entry
+        let orphan_cutoff = cutoff(policy.max_age_secs);
+        report.audio_files = self.remove_audio_files(|id| {
+            expired.iter().any(|entry| entry.id == id)
+                || (!kept.iter().any(|entry| entry.id == id)
+                    && orphan_cutoff.is_some_and(|cutoff| id_timestamp(id) < cutoff))
+        })?;
+
+        Ok(report)
+    }
+
+    /// Remove every entry and recording
+    pub fn clear(&self) -> HistoryResult<PurgeReport> {
+        let entries = self.list()?.len();
+        if entries > 0 {
+            self.save(&[])?;
+        }
+        let audio_files = self.remove_audio_files(|_| true)?;
+        Ok(PurgeReport {
+            entries,
+            audio_files,
+        })
+    }
+
+    /// Rewrite files stored with a different encryption setting than the store's
+    ///
+    /// Returns the number of files rewritten.
+    pub fn reencrypt(&self) -> HistoryResult<usize> {
+        let mut paths = vec![self.history_path()];
+        for entry in self.list()?.iter().filter(|entry| entry.has_audio) {
+            paths.push(self.audio_path(&entry.id));
+        }
+
+        let mut rewritten = 0;
+        for path in paths.iter().filter(|path| path.exists()) {
+            if file_is_encrypted(path)? != self.is_encrypted() {
+                let content = self.read_file(path)?;
+                self.write_file(path, &content)?;
+                rewritten += 1;
+            }
+        }
+        Ok(rewritten)
+    }
+
+    fn history_path(&self) -> PathBuf {
+        self.dir.join(HISTORY_FILE)
+    }
+
+    fn audio_path(&self, id: &str) -> PathBuf {
+        self.dir.join(AUDIO_DIR).join(format!("{}.ogg", id))
+    }
+
+    fn save(&self, entries: &[HistoryEntry]) -> HistoryResult<()> {
+        let content = serde_json::to_vec_pretty(entries)?;
+        self.write_file(&self.history_path(), &content)
+    }
+
+    /// Delete archived recordings whose ID matches `predicate`
+    fn remove_audio_files(&self, predicate: impl Fn(&str) -> bool) -> HistoryResult<usize> {
+        let audio_dir = self.dir.join(AUDIO_DIR);
+        if !audio_dir.exists() {
+            return Ok(0);
+        }
+
+        let mut removed = 0;
+        for dir_entry in std::fs::read_dir(&audio_dir).map_err(|e| io_error(&audio_dir, e))? {
+            let path = dir_entry.map_err(|e| io_error(&audio_dir, e))?.path();
+            let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            if predicate(id) {
+                std::fs::remove_file(&path).map_err(|e| io_error(&path, e))?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Read a file, decrypting it when it is encrypted
+    fn read_file(&self, path: &Path) -> HistoryResult<Vec<u8>> {
+        let data = std::fs::read(path).map_err(|e| io_error(path, e))?;
+        if !is_encrypted(&data) {
+            return Ok(data);
+        }
+        match &self.key {
+            Some(key) => Ok(key.decrypt(&data)?),
+            None => Err(HistoryError::KeyRequired {
+                path: path.to_string_lossy().to_string(),
+            }),
+        }
+    }
+
+    /// Atomically write a file, encrypting it when the store has a key
+    fn write_file(&self, path: &Path, content: &[u8]) -> HistoryResult<()> {
+        let parent_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
+            std::fs::create_dir_all(parent_dir).map_err(|e| io_error(parent_dir, e))?;
+        }
+
+        let data = match &self.key {
+            Some(key) if self.encrypt => key.encrypt(content)?,
+            _ => content.to_vec(),
+        };
+        let mut temp_file = NamedTempFile::new_in(parent_dir).map_err(|e| io_error(path, e))?;
+        temp_file.write_all(&data).map_err(|e| io_error(path, e))?;
+        temp_file
+            .persist(path)
+            .map_err(|e| io_error(path, e.error))?;
+        Ok(())
+    }
+}
+
+/// Whether a file starts with the encryption header (without reading all of it)
+fn file_is_encrypted(path: &Path) -> HistoryResult<bool> {
+    let mut header = Vec::new();
+    std::fs::File::open(path)
+        .and_then(|file| file.take(16).read_to_end(&mut header))
+        .map_err(|e| io_error(path, e))?;
+    Ok(is_encrypted(&header))
+}
+
+/// Unix timestamp encoded in an entry ID (0 when the ID is not one of ours)
+fn id_timestamp(id: &str) -> u64 {
+    id.split('-')
+        .next()
+        .and_then(|secs| secs.parse().ok())
+        .unwrap_or(0)
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> HistoryError {
+    HistoryError::Io {
+        path: path.to_string_lossy().to_string(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn record_text(store: &HistoryStore, text: &str) -> HistoryEntry {
+        store
+            .record(
+                text.to_string(),
+                text.to_lowercase(),
+                Vec::new(),
+                None,
+                None,
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_encrypted_history_and_audio_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let key = DataKey::generate().unwrap();
+        let store = HistoryStore::new(temp_dir.path().to_path_buf(), Some(key.clone()));
+
+        let audio_path = temp_dir.path().join("recording.ogg");
+        std::fs::write(&audio_path, b"OggS fake audio").unwrap();
+        let entry = store
+            .record(
+                "Secret Text".to_string(),
+                "secret text".to_string(),
+                Vec::new(),
+                Some("concise".to_string()),
+                Some(&audio_path),
+            )
+            .unwrap();
+
+        // Nothing readable on disk
+        let raw = std::fs::read(temp_dir.path().join(HISTORY_FILE)).unwrap();
+        assert!(is_encrypted(&raw));
+        assert!(!String::from_utf8_lossy(&raw).contains("Secret"));
+
+        let reloaded = HistoryStore::new(temp_dir.path().to_path_buf(), Some(key.clone()));
+        assert_eq!(reloaded.list().unwrap(), vec![entry.clone()]);
+        assert_eq!(reloaded.read_audio(&entry.id).unwrap(), b"OggS fake audio");
+
+        let without_key = HistoryStore::new(temp_dir.path().to_path_buf(), None);
+        assert!(without_key.contains_encrypted_data().unwrap());
+        assert!(matches!(
+            without_key.list(),
+            Err(HistoryError::KeyRequired { .. })
+        ));
+
+        // Switching encryption off rewrites the files in plain text
+        let decrypting = without_key.with_decryption_key(key);
+        assert_eq!(decrypting.reencrypt().unwrap(), 2);
+        assert!(!decrypting.contains_encrypted_data().unwrap());
+    }
+
+    #[test]
+    fn test_reencrypt_switches_plain_store_to_encrypted() {
+        let temp_dir = TempDir::new().unwrap();
+        let plain = HistoryStore::new(temp_dir.path().to_path_buf(), None);
+        let entry = record_text(&plain, "Hello");
+
+        let encrypted = HistoryStore::new(
+            temp_dir.path().to_path_buf(),
+            Some(DataKey::generate().unwrap()),
+        );
+        // Plain files stay readable until they are rewritten
+        assert_eq!(encrypted.list().unwrap(), vec![entry.clone()]);
+        assert_eq!(encrypted.reencrypt().unwrap(), 1);
+        assert_eq!(encrypted.reencrypt().unwrap(), 0);
+
+        let raw = std::fs::read(temp_dir.path().join(HISTORY_FILE)).unwrap();
+        assert!(is_encrypted(&raw));
+        assert_eq!(encrypted.get(&entry.id).unwrap(), entry);
+    }
+
+    #[test]
+    fn test_tag_focus_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = HistoryStore::new(temp_dir.path().to_path_buf(), None);
+        let entry = record_text(&store, "Sprint");
+        assert_eq!(entry.focus_session_id, None);
+
+        store.tag_focus_session(&entry.id, "focus-1").unwrap();
+        assert_eq!(
+            store.get(&entry.id).unwrap().focus_session_id.as_deref(),
+            Some("focus-1")
+        );
+        assert!(matches!(
+            store.tag_focus_session("missing", "focus-1"),
+            Err(HistoryError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_update_text() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = HistoryStore::new(temp_dir.path().to_path_buf(), None);
+        let entry = record_text(&store, "Deploy on cooper netties");
+        assert_eq!(entry.edited_at, None);
+
+        store
+            .update_text(&entry.id, "Deploy on Kubernetes".to_string())
+            .unwrap();
+        let updated = store.get(&entry.id).unwrap();
+        assert_eq!(updated.final_text, "Deploy on Kubernetes");
+        assert_eq!(updated.original_transcript, entry.original_transcript);
+        assert!(updated.edited_at.is_some());
+        assert!(matches!(
+            store.update_text("missing", String::new()),
+            Err(HistoryError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_purge_removes_expired_entries_and_audio() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = HistoryStore::new(temp_dir.path().to_path_buf(), None);
+
+        let mut old = record_text(&store, "Old");
+        let recent = record_text(&store, "Recent");
+
+        // Age the first entry and give it an archived recording
+        old.created_at -= 10 * 24 * 60 * 60;
+        old.id = format!("{}-000000000", old.created_at);
+        old.has_audio = true;
+        store.save(&[recent.clone(), old.clone()]).unwrap();
+        store
+            .write_file(&store.audio_path(&old.id), b"audio")
+            .unwrap();
+
+        let report = store.purge_older_than(7 * 24 * 60 * 60).unwrap();
+        assert_eq!(
+            report,
+            PurgeReport {
+                entries: 1,
+                audio_files: 1
+            }
+        );
+        assert_eq!(store.list().unwrap(), vec![recent]);
+
+        let report = store.clear().unwrap();
+        assert_eq!(report.entries, 1);
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_purge_applies_profile_retention() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = HistoryStore::new(temp_dir.path().to_path_buf(), None);
+
+        // Entries of two days ago made with three profiles
+        let aged = |text: &str, profile: &str| {
+            let mut entry = record_text(&store, text);
+            entry.created_at -= 2 * 24 * 60 * 60;
+            entry.id = format!("{}-{}", entry.created_at, text);
+            entry.profile_id = Some(profile.to_string());
+            entry
+        };
+        let medical = aged("Medical", "medical");
+        let blog = aged("Blog", "blog");
+        let general = aged("General", "general");
+        store
+            .save(&[medical.clone(), blog.clone(), general.clone()])
+            .unwrap();
+
+        let policy = RetentionPolicy {
+            max_age_secs: Some(7 * 24 * 60 * 60),
+            profile_max_age_secs: HashMap::from([
+                ("medical".to_string(), Some(24 * 60 * 60)),
+                ("blog".to_string(), None),
+            ]),
+        };
+        assert!(!policy.keeps_everything());
+        assert_eq!(policy.max_age_for(Some("blog")), None);
+        assert_eq!(policy.max_age_for(None), Some(7 * 24 * 60 * 60));
+
+        let report = store.purge(&policy).unwrap();
+        assert_eq!(report.entries, 1);
+        assert_eq!(store.list().unwrap(), vec![blog.clone(), general]);
+
+        // Blog entries are kept forever
+        let report = store
+            .purge(&RetentionPolicy {
+                max_age_secs: Some(60),
+                ..policy
+            })
+            .unwrap();
+        assert_eq!(report.entries, 1);
+        assert_eq!(store.list().unwrap(), vec![blog]);
+    }
+}
@@ -0,0 +1,108 @@
+//! Input integration service for hardware buttons outside the keyboard
+//!
+//! Bluetooth headsets forward their button presses (AVRCP) to the OS as the
+//! media play/pause keys. This service grabs those keys through the global
+//! shortcut plugin so that a press toggles recording while the main window is
+//! hidden, the same way the global hotkey does. While the window is visible
+//! presses are ignored.
+//!
+//! Grabbing the keys takes them away from media players for as long as the
+//! integration is enabled, which is why it is opt-in.
+
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+use tokio::sync::Mutex;
+
+use crate::services::shortcut_mgr::{ShortcutError, ShortcutResult};
+
+/// Media keys sent by headset buttons (X11 has separate play and pause keys)
+#[cfg(target_os = "linux")]
+const MEDIA_KEYS: &[&str] = &["MediaPlay", "MediaPause"];
+#[cfg(not(target_os = "linux"))]
+const MEDIA_KEYS: &[&str] = &["MediaPlayPause"];
+
+/// Input integration service
+pub struct InputIntegration {
+    /// Tauri app handle for the shortcut plugin and events
+    app_handle: AppHandle,
+    /// Media keys currently grabbed
+    media_keys: Arc<Mutex<Vec<Shortcut>>>,
+}
+
+impl InputIntegration {
+    /// Create a new InputIntegration instance
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self {
+            app_handle,
+            media_keys: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Toggle recording with the media play/pause keys while the window is hidden
+    pub async fn enable_media_key_toggle(&self) -> ShortcutResult<()> {
+        let mut media_keys = self.media_keys.lock().await;
+        if !media_keys.is_empty() {
+            return Ok(());
+        }
+
+        for key in MEDIA_KEYS {
+            let shortcut: Shortcut = key
+                .parse()
+                .map_err(|e| ShortcutError::ParseError(format!("{}", e)))?;
+
+            let registration_result = self.app_handle.global_shortcut().on_shortcut(
+                shortcut,
+                move |app_handle, _shortcut, event| {
+                    if event.state() != ShortcutState::Pressed || main_window_visible(app_handle) {
+                        return;
+                    }
+                    eprintln!("🎧 [INPUT] Media key pressed, toggling recording");
+                    if let Err(e) = app_handle.emit("global_shortcut_toggle_record", ()) {
+                        eprintln!("Failed to emit media key toggle record event: {}", e);
+                    }
+                },
+            );
+
+            if let Err(e) = registration_result {
+                // Leave no half-registered set behind
+                for registered in media_keys.drain(..) {
+                    let _ = self.app_handle.global_shortcut().unregister(registered);
+                }
+                eprintln!("Failed to register media key '{}': {}", key, e);
+                return Err(ShortcutError::ShortcutUnavailable {
+                    shortcut: key.to_string(),
+                });
+            }
+            media_keys.push(shortcut);
+        }
+
+        println!("Media keys now toggle recording: {}", MEDIA_KEYS.join(", "));
+        Ok(())
+    }
+
+    /// Give the media keys back to the OS and media players
+    pub async fn disable_media_key_toggle(&self) -> ShortcutResult<()> {
+        let mut media_keys = self.media_keys.lock().await;
+        for shortcut in media_keys.drain(..) {
+            self.app_handle
+                .global_shortcut()
+                .unregister(shortcut)
+                .map_err(|e| ShortcutError::UnregistrationFailed(format!("{}", e)))?;
+        }
+        Ok(())
+    }
+
+    /// Whether the media keys are grabbed
+    pub async fn is_media_key_toggle_enabled(&self) -> bool {
+        !self.media_keys.lock().await.is_empty()
+    }
+}
+
+/// Whether the main window is shown (presses are left alone then)
+fn main_window_visible(app_handle: &AppHandle) -> bool {
+    app_handle
+        .get_webview_window("main")
+        .and_then(|window| window.is_visible().ok())
+        .unwrap_or(false)
+}
@@ -0,0 +1,191 @@
+//! Interview transcript assembly
+//!
+//! Interview recordings keep the microphone and the loopback source (the
+//! remote party) on separate channels. Each channel is transcribed on its own
+//! and the timestamped segments are merged back into a conversation of
+//! "Me:" / "Them:" turns.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::{format_interview_transcript, interleave_turns};
+//! # use dicta_clerk_lib::services::TranscriptionResponse;
+//! # fn transcripts() -> (TranscriptionResponse, TranscriptionResponse) { unimplemented!() }
+//!
+//! let (me, them) = transcripts();
+//! let turns = interleave_turns(&me, &them);
+//! println!("{}", format_interview_transcript(&turns));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::services::TranscriptionResponse;
+
+/// Who is speaking in a turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Speaker {
+    /// Microphone channel (the user)
+    Me,
+    /// Loopback channel (the remote party)
+    Them,
+}
+
+impl Speaker {
+    /// Label written in front of the turn
+    pub fn label(&self) -> &'static str {
+        match self {
+            Speaker::Me => "Me",
+            Speaker::Them => "Them",
+        }
+    }
+}
+
+/// One uninterrupted turn of a speaker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterviewTurn {
+    pub speaker: Speaker,
+    /// Start time in seconds from the beginning of the recording
+    pub start: f64,
+    /// End time in seconds from the beginning of the recording
+    pub end: f64,
+    pub text: String,
+}
+
+/// Merge both channel transcripts into chronological turns
+///
+/// Segments are ordered by start time and consecutive segments of the same
+/// speaker are joined. A transcript without segments counts as one segment
+/// spanning the whole recording.
+pub fn interleave_turns(
+    me: &TranscriptionResponse,
+    them: &TranscriptionResponse,
+) -> Vec<InterviewTurn> {
+    let mut segments: Vec<InterviewTurn> = channel_segments(Speaker::Me, me)
+        .chain(channel_segments(Speaker::Them, them))
+        .collect();
+    // Stable sort: on equal start times the microphone comes first
+    segments.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    let mut turns: Vec<InterviewTurn> = Vec::new();
+    for segment in segments {
+        match turns.last_mut() {
+            Some(turn) if turn.speaker == segment.speaker => {
+                turn.text.push(' ');
+                turn.text.push_str(&segment.text);
+                turn.end = turn.end.max(segment.end);
+            }
+            _ => turns.push(segment),
+        }
+    }
+    turns
+}
+
+/// Render turns as "Me: ..." / "Them: ..." paragraphs
+pub fn format_interview_transcript(turns: &[InterviewTurn]) -> String {
+    turns
+        .iter()
+        .map(|turn| format!("{}: {}", turn.speaker.label(), turn.text))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Non-empty segments of one channel
+fn channel_segments(
+    speaker: Speaker,
+    transcript: &TranscriptionResponse,
+) -> impl Iterator<Item = InterviewTurn> + '_ {
+    let segments: Vec<(f64, f64, &str)> = match transcript.segments.as_deref() {
+        Some(segments) if !segments.is_empty() => segments
+            .iter()
+            .map(|segment| (segment.start, segment.end, segment.text.as_str()))
+            .collect(),
+        _ => vec![(
+            0.0,
+            transcript.duration.unwrap_or(0.0),
+            transcript.text.as_str(),
+        )],
+    };
+
+    segments.into_iter().filter_map(move |(start, end, text)| {
+        let text = text.trim();
+        (!text.is_empty()).then(|| InterviewTurn {
+            speaker,
+            start,
+            end,
+            text: text.to_string(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::TranscriptionSegment;
+
+    fn transcript(segments: &[(f64, f64, &str)]) -> TranscriptionResponse {
+        TranscriptionResponse {
+            text: segments
+                .iter()
+                .map(|(_, _, text)| *text)
+                .collect::<Vec<_>>()
+                .join(" "),
+            language: None,
+            duration: segments.last().map(|(_, end, _)| *end),
+            segments: Some(
+                segments
+                    .iter()
+                    .enumerate()
+                    .map(|(id, (start, end, text))| TranscriptionSegment {
+                        id: id as u32,
+                        start: *start,
+                        end: *end,
+                        text: text.to_string(),
+                        avg_logprob: None,
+                        compression_ratio: None,
+                        no_speech_prob: None,
+                    })
+                    .collect(),
+            ),
+            words: None,
+        }
+    }
+
+    #[test]
+    fn test_interleave_merges_consecutive_segments_of_a_speaker() {
+        let me = transcript(&[
+            (0.0, 2.0, " Hi, thanks for joining."),
+            (2.5, 4.0, "First question?"),
+        ]);
+        let them = transcript(&[
+            (4.5, 6.0, "Sure."),
+            (6.0, 7.0, "  "),
+            (9.0, 10.0, "Go ahead."),
+        ]);
+
+        let turns = interleave_turns(&me, &them);
+
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].speaker, Speaker::Me);
+        assert_eq!(turns[0].text, "Hi, thanks for joining. First question?");
+        assert_eq!(turns[1].speaker, Speaker::Them);
+        assert_eq!(turns[1].end, 10.0);
+        assert_eq!(
+            format_interview_transcript(&turns),
+            "Me: Hi, thanks for joining. First question?\n\nThem: Sure. Go ahead."
+        );
+    }
+
+    #[test]
+    fn test_interleave_without_segments_uses_whole_channel() {
+        let me = transcript(&[(1.0, 3.0, "Question one"), (8.0, 9.0, "Question two")]);
+        let mut them = transcript(&[]);
+        them.text = "Answer without timestamps".to_string();
+        them.segments = None;
+
+        let turns = interleave_turns(&me, &them);
+
+        let speakers: Vec<Speaker> = turns.iter().map(|turn| turn.speaker).collect();
+        assert_eq!(speakers, vec![Speaker::Them, Speaker::Me]);
+        assert_eq!(turns[1].text, "Question one Question two");
+    }
+}
@@ -0,0 +1,169 @@
+//! LiveCaptionWriter service for streamer caption output
+//!
+//! While dictating, the most recent seconds of audio are re-transcribed at a
+//! fixed cadence and the partial transcript is written to a plain text file.
+//! Streaming software such as OBS can display that file with a "Text (read
+//! from file)" source. Captions are also published on [`caption_events`] so
+//! the local API can push them to browser sources.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use std::path::PathBuf;
+//! use dicta_clerk_lib::services::{LiveCaptionConfig, LiveCaptionWriter};
+//!
+//! let writer = LiveCaptionWriter::new(LiveCaptionConfig {
+//!     output_path: PathBuf::from("captions.txt"),
+//!     ..LiveCaptionConfig::default()
+//! });
+//!
+//! writer.write_caption("Hello everyone and welcome").unwrap();
+//! writer.clear().unwrap();
+//! ```
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tempfile::NamedTempFile;
+use tokio::sync::broadcast;
+
+/// Default caption file name inside the config directory
+pub const LIVE_CAPTIONS_FILE: &str = "live_captions.txt";
+
+/// Configuration for live caption output
+#[derive(Debug, Clone)]
+pub struct LiveCaptionConfig {
+    /// Text file rewritten with the latest caption
+    pub output_path: PathBuf,
+    /// How often captions are refreshed
+    pub cadence: Duration,
+    /// Seconds of recent audio transcribed for each caption
+    pub window_seconds: u32,
+    /// Maximum caption length (older text is trimmed from the start)
+    pub max_chars: usize,
+}
+
+impl Default for LiveCaptionConfig {
+    fn default() -> Self {
+        Self {
+            output_path: crate::paths::config_dir().join(LIVE_CAPTIONS_FILE),
+            cadence: Duration::from_secs(3),
+            window_seconds: 10,
+            max_chars: 200,
+        }
+    }
+}
+
+/// Writes captions to the caption file and publishes them
+#[derive(Debug, Clone)]
+pub struct LiveCaptionWriter {
+    config: LiveCaptionConfig,
+}
+
+impl LiveCaptionWriter {
+    /// Create a writer with the given configuration
+    pub fn new(config: LiveCaptionConfig) -> Self {
+        Self { config }
+    }
+
+    /// Writer configuration
+    pub fn config(&self) -> &LiveCaptionConfig {
+        &self.config
+    }
+
+    /// Replace the caption file content and publish the caption
+    ///
+    /// The file is replaced atomically so readers never see a partial write.
+    pub fn write_caption(&self, text: &str) -> std::io::Result<()> {
+        let caption = tail_chars(text.trim(), self.config.max_chars);
+        self.replace_file(caption)?;
+        let _ = caption_events().send(serde_json::json!({ "text": caption }).to_string());
+        Ok(())
+    }
+
+    /// Empty the caption file (e.g. when recording stops)
+    pub fn clear(&self) -> std::io::Result<()> {
+        self.write_caption("")
+    }
+
+    fn replace_file(&self, content: &str) -> std::io::Result<()> {
+        let parent_dir = self
+            .config
+            .output_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."));
+        if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
+            std::fs::create_dir_all(parent_dir)?;
+        }
+
+        let mut temp_file = NamedTempFile::new_in(parent_dir)?;
+        temp_file.write_all(content.as_bytes())?;
+        temp_file
+            .persist(&self.config.output_path)
+            .map_err(|e| e.error)?;
+        Ok(())
+    }
+}
+
+/// Process-wide channel of caption updates (JSON payloads `{"text": ...}`)
+pub fn caption_events() -> &'static broadcast::Sender<String> {
+    static EVENTS: OnceLock<broadcast::Sender<String>> = OnceLock::new();
+    EVENTS.get_or_init(|| broadcast::channel(16).0)
+}
+
+/// Last `max_chars` characters of `text`, starting at a word boundary when possible
+fn tail_chars(text: &str, max_chars: usize) -> &str {
+    let char_count = text.chars().count();
+    if char_count <= max_chars {
+        return text;
+    }
+
+    let start = text
+        .char_indices()
+        .nth(char_count - max_chars)
+        .map(|(index, _)| index)
+        .unwrap_or(0);
+    let tail = &text[start..];
+    if text[..start].ends_with(char::is_whitespace) {
+        return tail;
+    }
+    match tail.find(char::is_whitespace) {
+        Some(space) if space + 1 < tail.len() => tail[space..].trim_start(),
+        _ => tail,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_tail_chars_trims_at_word_boundary() {
+        assert_eq!(tail_chars("short caption", 50), "short caption");
+        assert_eq!(tail_chars("one two three four", 10), "three four");
+        assert_eq!(tail_chars("one two three four", 9), "four");
+        assert_eq!(tail_chars("héllo wörld", 5), "wörld");
+    }
+
+    #[test]
+    fn test_write_and_clear_caption_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let writer = LiveCaptionWriter::new(LiveCaptionConfig {
+            output_path: temp_dir.path().join(LIVE_CAPTIONS_FILE),
+            max_chars: 12,
+            ..LiveCaptionConfig::default()
+        });
+        let mut events = caption_events().subscribe();
+
+        writer.write_caption("  hello there streamers  ").unwrap();
+        let content = std::fs::read_to_string(&writer.config().output_path).unwrap();
+        assert_eq!(content, "streamers");
+        assert!(events.try_recv().unwrap().contains("streamers"));
+
+        writer.clear().unwrap();
+        let content = std::fs::read_to_string(&writer.config().output_path).unwrap();
+        assert!(content.is_empty());
+    }
+}
@@ -0,0 +1,665 @@
+//! LocalApiServer service for the optional local HTTP API
+//!
+//! When enabled in settings, a small HTTP/1.1 server listens on the loopback
+//! interface so local tooling can observe the app. It currently serves:
+//!
+//! - `GET /health`  - liveness probe
+//! - `GET /metrics` - Prometheus text exposition of [`MetricsRegistry::global`]
+//! - `GET /captions` - server-sent events with live captions (see [`caption_events`])
+//!
+//! With LAN pairing enabled the server also binds the LAN interface and
+//! exposes a remote control for paired devices (see [`PairingStore`]):
+//!
+//! - `GET /remote`                 - minimal phone-friendly control page
+//! - `GET /api/remote/status`      - current state and last result
+//! - `POST /api/remote/toggle`     - start/stop recording
+//! - `GET /api/remote/events`      - server-sent events with state changes
+//!
+//! Remote routes (and every route requested from another machine, except
+//! `/health`) require a pairing token, passed either as
+//! `Authorization: Bearer <token>` or as a `token` query parameter.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::{LocalApiConfig, LocalApiServer};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut server = LocalApiServer::new(LocalApiConfig::default());
+//! let addr = server.start().await?;
+//! println!("Scrape http://{}/metrics", addr);
+//!
+//! server.stop();
+//! # Ok(())
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, oneshot};
+
+use crate::services::{caption_events, MetricsRegistry, PairingStore};
+
+/// Capacity of the state push channel (slow subscribers skip old events)
+const STATE_CHANNEL_CAPACITY: usize = 32;
+
+/// Maximum size of the request head we are willing to read
+const MAX_REQUEST_HEAD_BYTES: usize = 8 * 1024;
+
+/// Errors that can occur in the local API server
+#[derive(Error, Debug)]
+pub enum LocalApiError {
+    #[error("Local API is already running on {0}")]
+    AlreadyRunning(SocketAddr),
+    #[error("Failed to bind local API to {address}: {source}")]
+    Bind {
+        address: String,
+        source: std::io::Error,
+    },
+}
+
+/// Result type for local API operations
+pub type LocalApiResult<T> = Result<T, LocalApiError>;
+
+/// Configuration for the local API server
+#[derive(Debug, Clone)]
+pub struct LocalApiConfig {
+    /// Address to bind to (loopback only by default)
+    pub bind_address: String,
+    /// TCP port (0 picks a free port)
+    pub port: u16,
+}
+
+impl Default for LocalApiConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: "127.0.0.1".to_string(),
+            port: 8765,
+        }
+    }
+}
+
+/// Parsed HTTP request head
+#[derive(Debug, Clone, Default)]
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub headers: HashMap<String, String>,
+    /// Remote peer address (None in tests)
+    pub peer: Option<SocketAddr>,
+}
+
+impl HttpRequest {
+    /// Parse the request head (request line and headers)
+    pub fn parse(head: &str, peer: Option<SocketAddr>) -> Option<Self> {
+        let mut lines = head.lines();
+        let mut request_line = lines.next()?.split_whitespace();
+        let method = request_line.next()?.to_string();
+        let target = request_line.next()?;
+
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path, query),
+            None => (target, ""),
+        };
+        let query = query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+
+        let headers = lines
+            .take_while(|line| !line.is_empty())
+            .filter_map(|line| line.split_once(':'))
+            .map(|(k, v)| (k.trim().to_ascii_lowercase(), v.trim().to_string()))
+            .collect();
+
+        Some(Self {
+            method,
+            path: path.to_string(),
+            query,
+            headers,
+            peer,
+        })
+    }
+
+    /// Pairing token from the Authorization header or the `token` query parameter
+    pub fn token(&self) -> Option<&str> {
+        self.headers
+            .get("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .or_else(|| self.query.get("token").map(|token| token.as_str()))
+    }
+
+    /// Whether the request comes from this machine
+    pub fn is_loopback(&self) -> bool {
+        self.peer.is_none_or(|peer| peer.ip().is_loopback())
+    }
+}
+
+/// Minimal HTTP response produced by the router
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl HttpResponse {
+    fn text(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: body.into(),
+        }
+    }
+
+    fn json(status: u16, body: serde_json::Value) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: body.to_string(),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Bridge between the local API and the running app, used by remote routes
+#[async_trait]
+pub trait RemoteControl: Send + Sync {
+    /// Start recording when idle, stop and process when recording
+    async fn toggle_recording(&self) -> Result<String, String>;
+
+    /// Current app state and the last processed result
+    async fn status(&self) -> serde_json::Value;
+}
+
+/// Remote-control access for paired devices
+#[derive(Clone)]
+pub struct RemoteAccess {
+    /// App bridge
+    pub control: Arc<dyn RemoteControl>,
+    /// Tokens of paired devices
+    pub pairings: PairingStore,
+    /// State changes pushed to `/api/remote/events` subscribers (JSON payloads)
+    pub state_events: broadcast::Sender<String>,
+}
+
+impl RemoteAccess {
+    /// Create remote access with a fresh state push channel
+    pub fn new(control: Arc<dyn RemoteControl>, pairings: PairingStore) -> Self {
+        let (state_events, _) = broadcast::channel(STATE_CHANNEL_CAPACITY);
+        Self {
+            control,
+            pairings,
+            state_events,
+        }
+    }
+
+    fn is_authorized(&self, request: &HttpRequest) -> bool {
+        request
+            .token()
+            .map(|token| self.pairings.verify(token).unwrap_or(false))
+            .unwrap_or(false)
+    }
+}
+
+impl std::fmt::Debug for RemoteAccess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteAccess")
+            .field("pairings", &self.pairings)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Outcome of routing a request
+enum RouteOutcome {
+    Response(HttpResponse),
+    EventStream(broadcast::Receiver<String>, serde_json::Value),
+}
+
+/// Local HTTP API server
+#[derive(Debug)]
+pub struct LocalApiServer {
+    config: LocalApiConfig,
+    remote: Option<RemoteAccess>,
+    local_addr: Option<SocketAddr>,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+}
+
+impl LocalApiServer {
+    /// Create a stopped server with the given configuration
+    pub fn new(config: LocalApiConfig) -> Self {
+        Self {
+            config,
+            remote: None,
+            local_addr: None,
+            shutdown_tx: None,
+        }
+    }
+
+    /// Enable the remote-control routes for paired devices
+    pub fn with_remote_access(mut self, remote: RemoteAccess) -> Self {
+        self.remote = Some(remote);
+        self
+    }
+
+    /// Server configuration
+    pub fn config(&self) -> &LocalApiConfig {
+        &self.config
+    }
+
+    /// Remote-control access, if enabled
+    pub fn remote_access(&self) -> Option<&RemoteAccess> {
+        self.remote.as_ref()
+    }
+
+    /// Address the server is listening on, if running
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    /// Check whether the server is running
+    pub fn is_running(&self) -> bool {
+        self.shutdown_tx.is_some()
+    }
+
+    /// Bind the listener and start serving requests in the background
+    pub async fn start(&mut self) -> LocalApiResult<SocketAddr> {
+        if let Some(addr) = self.local_addr.filter(|_| self.is_running()) {
+            return Err(LocalApiError::AlreadyRunning(addr));
+        }
+
+        let address = format!("{}:{}", self.config.bind_address, self.config.port);
+        let listener = TcpListener::bind(&address)
+            .await
+            .map_err(|source| LocalApiError::Bind {
+                address: address.clone(),
+                source,
+            })?;
+        let local_addr = listener
+            .local_addr()
+            .map_err(|source| LocalApiError::Bind {
+                address: address.clone(),
+                source,
+            })?;
+
+        let remote = self.remote.clone();
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown_rx => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, peer)) => {
+                            tokio::spawn(handle_connection(stream, peer, remote.clone()));
+                        }
+                        Err(e) => eprintln!("⚠️  [LOCAL-API] Failed to accept connection: {}", e),
+                    },
+                }
+            }
+            eprintln!("🛑 [LOCAL-API] Server on {} stopped", local_addr);
+        });
+
+        eprintln!("🌐 [LOCAL-API] Listening on http://{}", local_addr);
+        self.local_addr = Some(local_addr);
+        self.shutdown_tx = Some(shutdown_tx);
+        Ok(local_addr)
+    }
+
+    /// Stop serving requests
+    pub fn stop(&mut self) {
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            let _ = shutdown_tx.send(());
+        }
+        self.local_addr = None;
+    }
+}
+
+impl Drop for LocalApiServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Route a request to its handler
+async fn route(request: &HttpRequest, remote: Option<&RemoteAccess>) -> RouteOutcome {
+    let method = request.method.as_str();
+    let path = request.path.as_str();
+
+    if path == "/health" {
+        return RouteOutcome::Response(match method {
+            "GET" => HttpResponse::text(200, "ok\n"),
+            _ => HttpResponse::text(405, "Method not allowed\n"),
+        });
+    }
+
+    let authorized = remote.is_some_and(|remote| remote.is_authorized(request));
+
+    if path == "/metrics" {
+        if !request.is_loopback() && !authorized {
+            return RouteOutcome::Response(HttpResponse::text(401, "Unauthorized\n"));
+        }
+        return RouteOutcome::Response(match method {
+            "GET" => HttpResponse {
+                status: 200,
+                content_type: "text/plain; version=0.0.4; charset=utf-8",
+                body: MetricsRegistry::global().render(),
+            },
+            _ => HttpResponse::text(405, "Method not allowed\n"),
+        });
+    }
+
+    if path == "/captions" {
+        if !request.is_loopback() && !authorized {
+            return RouteOutcome::Response(HttpResponse::text(401, "Unauthorized\n"));
+        }
+        return match method {
+            "GET" => RouteOutcome::EventStream(
+                caption_events().subscribe(),
+                serde_json::json!({ "text": "" }),
+            ),
+            _ => RouteOutcome::Response(HttpResponse::text(405, "Method not allowed\n")),
+        };
+    }
+
+    let is_remote_route = path == "/remote" || path.starts_with("/api/remote/");
+    let remote = match remote {
+        Some(remote) if is_remote_route => remote,
+        _ => return RouteOutcome::Response(HttpResponse::text(404, "Not found\n")),
+    };
+    if !authorized {
+        return RouteOutcome::Response(HttpResponse::text(401, "Unauthorized\n"));
+    }
+
+    match (method, path) {
+        ("GET", "/remote") => RouteOutcome::Response(HttpResponse {
+            status: 200,
+            content_type: "text/html; charset=utf-8",
+            body: REMOTE_PAGE.to_string(),
+        }),
+        ("GET", "/api/remote/status") => {
+            RouteOutcome::Response(HttpResponse::json(200, remote.control.status().await))
+        }
+        ("POST", "/api/remote/toggle") => {
+            RouteOutcome::Response(match remote.control.toggle_recording().await {
+                Ok(message) => HttpResponse::json(200, serde_json::json!({ "message": message })),
+                Err(error) => HttpResponse::json(503, serde_json::json!({ "error": error })),
+            })
+        }
+        ("GET", "/api/remote/events") => RouteOutcome::EventStream(
+            remote.state_events.subscribe(),
+            remote.control.status().await,
+        ),
+        _ => RouteOutcome::Response(HttpResponse::text(405, "Method not allowed\n")),
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, peer: SocketAddr, remote: Option<RemoteAccess>) {
+    let request = read_request_head(&mut stream)
+        .await
+        .and_then(|head| HttpRequest::parse(&head, Some(peer)));
+
+    let outcome = match &request {
+        Some(request) => route(request, remote.as_ref()).await,
+        None => RouteOutcome::Response(HttpResponse::text(400, "Bad request\n")),
+    };
+
+    match outcome {
+        RouteOutcome::Response(response) => write_response(&mut stream, &response).await,
+        RouteOutcome::EventStream(receiver, initial) => {
+            stream_events(&mut stream, receiver, initial).await
+        }
+    }
+    let _ = stream.shutdown().await;
+}
+
+async fn write_response(stream: &mut TcpStream, response: &HttpResponse) {
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.reason(),
+        response.content_type,
+        response.body.len()
+    );
+
+    if let Err(e) = stream.write_all(head.as_bytes()).await {
+        eprintln!("⚠️  [LOCAL-API] Failed to write response: {}", e);
+        return;
+    }
+    let _ = stream.write_all(response.body.as_bytes()).await;
+}
+
+/// Push state changes as server-sent events until the client goes away
+async fn stream_events(
+    stream: &mut TcpStream,
+    mut receiver: broadcast::Receiver<String>,
+    initial: serde_json::Value,
+) {
+    let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n";
+    if stream.write_all(head.as_bytes()).await.is_err() {
+        return;
+    }
+    if write_event(stream, &initial.to_string()).await.is_err() {
+        return;
+    }
+
+    loop {
+        match receiver.recv().await {
+            Ok(payload) => {
+                if write_event(stream, &payload).await.is_err() {
+                    break;
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+async fn write_event(stream: &mut TcpStream, payload: &str) -> std::io::Result<()> {
+    stream
+        .write_all(format!("data: {}\n\n", payload.replace('\n', " ")).as_bytes())
+        .await
+}
+
+/// Read the request head (up to the blank line)
+async fn read_request_head(stream: &mut TcpStream) -> Option<String> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+
+    while !buffer.windows(4).any(|w| w == b"\r\n\r\n") {
+        if buffer.len() >= MAX_REQUEST_HEAD_BYTES {
+            return None;
+        }
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+
+    Some(String::from_utf8_lossy(&buffer).to_string())
+}
+
+/// Phone-friendly remote control page (token is read from the page URL)
+const REMOTE_PAGE: &str = r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>DictaClerk Remote</title>
+<style>
+body { font-family: sans-serif; margin: 1.5em; text-align: center; }
+button { font-size: 1.5em; padding: 1em 2em; border-radius: 1em; }
+pre { text-align: left; white-space: pre-wrap; background: #f2f2f2; padding: 1em; }
+</style>
+</head>
+<body>
+<h1>DictaClerk</h1>
+<p id="state">Connecting...</p>
+<button id="toggle">Start / Stop</button>
+<h2>Last result</h2>
+<pre id="result"></pre>
+<script>
+const token = new URLSearchParams(location.search).get("token");
+const auth = { headers: { Authorization: "Bearer " + token } };
+function render(status) {
+  if (status.state) document.getElementById("state").textContent = status.state;
+  if (status.last_result) document.getElementById("result").textContent = status.last_result;
+}
+function refresh() {
+  fetch("/api/remote/status", auth).then(r => r.json()).then(render);
+}
+document.getElementById("toggle").onclick = () =>
+  fetch("/api/remote/toggle", Object.assign({ method: "POST" }, auth)).then(refresh);
+const events = new EventSource("/api/remote/events?token=" + encodeURIComponent(token));
+events.onmessage = () => refresh();
+refresh();
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::metrics::RECORDINGS_TOTAL;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    #[derive(Default)]
+    struct MockRemoteControl {
+        toggles: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl RemoteControl for MockRemoteControl {
+        async fn toggle_recording(&self) -> Result<String, String> {
+            self.toggles.fetch_add(1, Ordering::SeqCst);
+            Ok("toggled".to_string())
+        }
+
+        async fn status(&self) -> serde_json::Value {
+            serde_json::json!({ "state": "Idle", "last_result": null })
+        }
+    }
+
+    async fn respond(request: &HttpRequest, remote: Option<&RemoteAccess>) -> HttpResponse {
+        match route(request, remote).await {
+            RouteOutcome::Response(response) => response,
+            RouteOutcome::EventStream(..) => panic!("unexpected event stream"),
+        }
+    }
+
+    fn request(method: &str, target: &str, peer: &str) -> HttpRequest {
+        let head = format!("{} {} HTTP/1.1\r\nHost: localhost\r\n\r\n", method, target);
+        HttpRequest::parse(&head, Some(peer.parse().unwrap())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_routing() {
+        let local = "127.0.0.1:50000";
+        assert_eq!(
+            respond(&request("GET", "/health", local), None)
+                .await
+                .status,
+            200
+        );
+        assert_eq!(
+            respond(&request("GET", "/metrics?format=text", local), None)
+                .await
+                .status,
+            200
+        );
+        assert_eq!(
+            respond(&request("POST", "/metrics", local), None)
+                .await
+                .status,
+            405
+        );
+        assert_eq!(
+            respond(&request("GET", "/unknown", local), None)
+                .await
+                .status,
+            404
+        );
+        // Remote routes do not exist unless pairing is enabled
+        assert_eq!(
+            respond(&request("GET", "/remote", local), None)
+                .await
+                .status,
+            404
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remote_routes_require_pairing_token() {
+        let temp_dir = TempDir::new().unwrap();
+        let pairings = PairingStore::new(temp_dir.path().join("pairings.json"));
+        let pairing = pairings.create(None).unwrap();
+        let control = Arc::new(MockRemoteControl::default());
+        let remote = RemoteAccess::new(control.clone(), pairings);
+        let phone = "192.168.1.20:50000";
+
+        let denied = respond(&request("POST", "/api/remote/toggle", phone), Some(&remote)).await;
+        assert_eq!(denied.status, 401);
+        let metrics = respond(&request("GET", "/metrics", phone), Some(&remote)).await;
+        assert_eq!(metrics.status, 401);
+
+        let target = format!("/api/remote/toggle?token={}", pairing.token);
+        let allowed = respond(&request("POST", &target, phone), Some(&remote)).await;
+        assert_eq!(allowed.status, 200);
+        assert_eq!(control.toggles.load(Ordering::SeqCst), 1);
+
+        let mut status = request("GET", "/api/remote/status", phone);
+        status.headers.insert(
+            "authorization".to_string(),
+            format!("Bearer {}", pairing.token),
+        );
+        let status = respond(&status, Some(&remote)).await;
+        assert_eq!(status.status, 200);
+        assert!(status.body.contains("Idle"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_serves_registry() {
+        MetricsRegistry::global().inc_counter(RECORDINGS_TOTAL, &[]);
+
+        let mut server = LocalApiServer::new(LocalApiConfig {
+            bind_address: "127.0.0.1".to_string(),
+            port: 0,
+        });
+        let addr = server.start().await.unwrap();
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("# TYPE dictaclerk_recordings_total counter"));
+
+        server.stop();
+        assert!(!server.is_running());
+    }
+}
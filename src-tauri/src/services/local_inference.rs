@@ -0,0 +1,187 @@
+//! Hardware capabilities of local inference backends
+//!
+//! Local Whisper/LLM backends can run on the CPU, on an NVIDIA GPU (CUDA) or
+//! on Apple Silicon (Metal). Capabilities are probed once at startup; the
+//! configured device is then resolved against them so a missing GPU falls
+//! back to the CPU instead of failing the first transcription.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::{InferenceDevice, LocalInferenceCapabilities};
+//!
+//! let capabilities = LocalInferenceCapabilities::probe();
+//! let selection = capabilities.resolve(InferenceDevice::Cuda, 0);
+//! println!("Running local inference on {:?}", selection.device);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+/// Compute device used by local backends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InferenceDevice {
+    #[default]
+    Cpu,
+    Cuda,
+    Metal,
+}
+
+/// GPU found while probing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GpuInfo {
+    pub index: u32,
+    pub name: String,
+    pub memory_mb: Option<u64>,
+}
+
+/// Devices available to local backends on this machine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalInferenceCapabilities {
+    pub cpu_threads: usize,
+    pub cuda_available: bool,
+    pub metal_available: bool,
+    /// CUDA GPUs, by index
+    pub gpus: Vec<GpuInfo>,
+}
+
+/// Device actually used for a requested device/GPU pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceSelection {
+    pub device: InferenceDevice,
+    /// GPU index (CUDA only)
+    pub gpu_index: Option<u32>,
+    /// Why the requested device could not be used
+    pub fallback_reason: Option<String>,
+}
+
+impl LocalInferenceCapabilities {
+    /// Probe the CPU, CUDA (via `nvidia-smi`) and Metal support
+    pub fn probe() -> Self {
+        let cpu_threads = std::thread::available_parallelism()
+            .map(|count| count.get())
+            .unwrap_or(1);
+
+        let gpus = Command::new("nvidia-smi")
+            .args([
+                "--query-gpu=index,name,memory.total",
+                "--format=csv,noheader,nounits",
+            ])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| parse_nvidia_smi(&String::from_utf8_lossy(&output.stdout)))
+            .unwrap_or_default();
+
+        Self {
+            cpu_threads,
+            cuda_available: !gpus.is_empty(),
+            metal_available: cfg!(all(target_os = "macos", target_arch = "aarch64")),
+            gpus,
+        }
+    }
+
+    /// Whether a device can be used at all
+    pub fn supports(&self, device: InferenceDevice) -> bool {
+        match device {
+            InferenceDevice::Cpu => true,
+            InferenceDevice::Cuda => self.cuda_available,
+            InferenceDevice::Metal => self.metal_available,
+        }
+    }
+
+    /// Resolve the configured device, falling back to the CPU when unavailable
+    pub fn resolve(&self, device: InferenceDevice, gpu_index: u32) -> DeviceSelection {
+        let cpu = |reason: String| DeviceSelection {
+            device: InferenceDevice::Cpu,
+            gpu_index: None,
+            fallback_reason: Some(reason),
+        };
+
+        match device {
+            InferenceDevice::Cpu => DeviceSelection {
+                device,
+                gpu_index: None,
+                fallback_reason: None,
+            },
+            InferenceDevice::Metal if self.metal_available => DeviceSelection {
+                device,
+                gpu_index: None,
+                fallback_reason: None,
+            },
+            InferenceDevice::Metal => cpu("Metal is not available".to_string()),
+            InferenceDevice::Cuda if self.gpus.iter().any(|gpu| gpu.index == gpu_index) => {
+                DeviceSelection {
+                    device,
+                    gpu_index: Some(gpu_index),
+                    fallback_reason: None,
+                }
+            }
+            InferenceDevice::Cuda if self.cuda_available => cpu(format!(
+                "CUDA GPU {} not found ({} GPU(s) available)",
+                gpu_index,
+                self.gpus.len()
+            )),
+            InferenceDevice::Cuda => cpu("CUDA is not available".to_string()),
+        }
+    }
+}
+
+/// Parse `nvidia-smi --query-gpu=index,name,memory.total --format=csv,noheader,nounits`
+fn parse_nvidia_smi(output: &str) -> Vec<GpuInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(',').map(str::trim);
+            let index = fields.next()?.parse().ok()?;
+            let name = fields.next()?.to_string();
+            let memory_mb = fields.next().and_then(|memory| memory.parse().ok());
+            Some(GpuInfo {
+                index,
+                name,
+                memory_mb,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nvidia_smi() {
+        let gpus = parse_nvidia_smi(
+            "0, NVIDIA GeForce RTX 3080, 10240\n1, NVIDIA RTX A4000, [N/A]\nNo devices\n",
+        );
+
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[0].name, "NVIDIA GeForce RTX 3080");
+        assert_eq!(gpus[0].memory_mb, Some(10240));
+        assert_eq!(gpus[1].index, 1);
+        assert_eq!(gpus[1].memory_mb, None);
+    }
+
+    #[test]
+    fn test_resolve_falls_back_to_cpu() {
+        let capabilities = LocalInferenceCapabilities {
+            cpu_threads: 8,
+            cuda_available: true,
+            metal_available: false,
+            gpus: parse_nvidia_smi("0, NVIDIA GeForce RTX 3080, 10240"),
+        };
+
+        let cuda = capabilities.resolve(InferenceDevice::Cuda, 0);
+        assert_eq!(cuda.device, InferenceDevice::Cuda);
+        assert_eq!(cuda.gpu_index, Some(0));
+
+        let missing_gpu = capabilities.resolve(InferenceDevice::Cuda, 1);
+        assert_eq!(missing_gpu.device, InferenceDevice::Cpu);
+        assert!(missing_gpu.fallback_reason.unwrap().contains("GPU 1"));
+
+        let metal = capabilities.resolve(InferenceDevice::Metal, 0);
+        assert_eq!(metal.device, InferenceDevice::Cpu);
+        assert!(metal.fallback_reason.is_some());
+    }
+}
@@ -0,0 +1,366 @@
+//! LocalWhisperClient: offline transcription with whisper.cpp
+//!
+//! Runs the whisper.cpp command-line tool (`whisper-cli`) on the encoded
+//! recording with a local GGML model, so transcription works without network
+//! access and at no cost. whisper.cpp 1.7 or later decodes WAV, MP3, FLAC and
+//! OGG Vorbis itself. The tool writes its result as JSON next to a temporary
+//! output prefix; segments come from its millisecond offsets.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use std::path::{Path, PathBuf};
+//! use dicta_clerk_lib::services::{LocalWhisperClient, LocalWhisperConfig, WhisperClient};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = LocalWhisperClient::new(LocalWhisperConfig {
+//!         model_path: PathBuf::from("models/ggml-base.bin"),
+//!         ..Default::default()
+//!     });
+//!
+//!     let transcript = client.transcribe(Path::new("audio.ogg"), None).await?;
+//!     println!("Transcript: {}", transcript.text);
+//!     Ok(())
+//! }
+//! ```
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::services::whisper_client::{
+    TranscriptionResponse, TranscriptionSegment, WhisperClient, WhisperError, WhisperMode,
+    WhisperResult,
+};
+
+/// Command run when no binary is configured (looked up on the PATH)
+pub const DEFAULT_WHISPER_BINARY: &str = "whisper-cli";
+
+/// Configuration for LocalWhisperClient
+#[derive(Debug, Clone)]
+pub struct LocalWhisperConfig {
+    /// whisper.cpp command-line tool (default: `whisper-cli` on the PATH)
+    pub binary: PathBuf,
+    /// GGML model file
+    pub model_path: PathBuf,
+    /// CPU threads used by whisper.cpp (default: 4)
+    pub threads: usize,
+    /// Use the GPU whisper.cpp was built for (default: false)
+    pub use_gpu: bool,
+    /// CUDA GPU used when several are installed
+    pub gpu_index: Option<u32>,
+    /// Longest run before the transcription is abandoned, in seconds (default: 300)
+    pub timeout_seconds: u64,
+    /// Transcribe or translate to English (default: transcribe)
+    pub mode: WhisperMode,
+}
+
+impl Default for LocalWhisperConfig {
+    fn default() -> Self {
+        Self {
+            binary: PathBuf::from(DEFAULT_WHISPER_BINARY),
+            model_path: PathBuf::new(),
+            threads: 4,
+            use_gpu: false,
+            gpu_index: None,
+            timeout_seconds: 300,
+            mode: WhisperMode::Transcribe,
+        }
+    }
+}
+
+/// JSON written by `whisper-cli --output-json`
+#[derive(Debug, Deserialize)]
+struct CliOutput {
+    #[serde(default)]
+    result: Option<CliResult>,
+    transcription: Vec<CliSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CliResult {
+    language: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CliSegment {
+    offsets: CliOffsets,
+    text: String,
+}
+
+/// Segment bounds in milliseconds
+#[derive(Debug, Deserialize)]
+struct CliOffsets {
+    from: u64,
+    to: u64,
+}
+
+/// Offline Whisper client backed by whisper.cpp
+pub struct LocalWhisperClient {
+    config: LocalWhisperConfig,
+}
+
+impl LocalWhisperClient {
+    /// Create a client with the given configuration
+    pub fn new(config: LocalWhisperConfig) -> Self {
+        Self { config }
+    }
+
+    /// Command-line arguments transcribing `file_path` into `output_prefix`.json
+    fn build_args(
+        &self,
+        file_path: &Path,
+        output_prefix: &Path,
+        prompt: Option<&str>,
+        language: Option<&str>,
+        mode: WhisperMode,
+    ) -> Vec<String> {
+        let mut args = vec![
+            "--model".to_string(),
+            self.config.model_path.to_string_lossy().into_owned(),
+            "--file".to_string(),
+            file_path.to_string_lossy().into_owned(),
+            "--threads".to_string(),
+            self.config.threads.max(1).to_string(),
+            "--output-json".to_string(),
+            "--output-file".to_string(),
+            output_prefix.to_string_lossy().into_owned(),
+            "--no-prints".to_string(),
+            "--language".to_string(),
+            match mode {
+                WhisperMode::Transcribe => language.unwrap_or("auto").to_string(),
+                // The spoken language is detected when translating
+                WhisperMode::Translate => "auto".to_string(),
+            },
+        ];
+        if mode == WhisperMode::Translate {
+            args.push("--translate".to_string());
+        }
+        if let Some(prompt) = prompt.filter(|prompt| !prompt.is_empty()) {
+            args.push("--prompt".to_string());
+            args.push(prompt.to_string());
+        }
+        if !self.config.use_gpu {
+            args.push("--no-gpu".to_string());
+        }
+        args
+    }
+
+    /// Run whisper.cpp on a file
+    async fn run(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+        language: Option<String>,
+        mode: WhisperMode,
+    ) -> WhisperResult<TranscriptionResponse> {
+        if !file_path.is_file() {
+            return Err(WhisperError::FileIo(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Audio file not found: {}", file_path.display()),
+            )));
+        }
+        if !self.config.model_path.is_file() {
+            return Err(WhisperError::Api(format!(
+                "Local Whisper model not found at {}: download it in the settings",
+                self.config.model_path.display()
+            )));
+        }
+
+        let output_dir = tempfile::tempdir()?;
+        let output_prefix = output_dir.path().join("transcript");
+        let args = self.build_args(
+            file_path,
+            &output_prefix,
+            prompt.as_deref(),
+            language.as_deref(),
+            mode,
+        );
+
+        let mut command = tokio::process::Command::new(&self.config.binary);
+        command.args(&args).kill_on_drop(true);
+        if let (true, Some(gpu_index)) = (self.config.use_gpu, self.config.gpu_index) {
+            command.env("CUDA_VISIBLE_DEVICES", gpu_index.to_string());
+        }
+        let output = tokio::time::timeout(
+            Duration::from_secs(self.config.timeout_seconds),
+            command.output(),
+        )
+        .await
+        .map_err(|_| WhisperError::Timeout {
+            timeout_seconds: self.config.timeout_seconds,
+        })?
+        .map_err(|e| {
+            WhisperError::Api(format!(
+                "Failed to run whisper.cpp ({}): {}",
+                self.config.binary.display(),
+                e
+            ))
+        })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let last_lines: Vec<&str> = stderr.lines().rev().take(3).collect();
+            return Err(WhisperError::Api(format!(
+                "whisper.cpp failed ({}): {}",
+                output.status,
+                last_lines.into_iter().rev().collect::<Vec<_>>().join(" ")
+            )));
+        }
+
+        let json = tokio::fs::read_to_string(output_prefix.with_extension("json")).await?;
+        parse_output(&json)
+    }
+}
+
+/// Transcript from the JSON output of whisper.cpp
+fn parse_output(json: &str) -> WhisperResult<TranscriptionResponse> {
+    let output: CliOutput = serde_json::from_str(json).map_err(|e| {
+        WhisperError::InvalidResponse(format!("Failed to parse whisper.cpp output: {}", e))
+    })?;
+
+    let segments: Vec<TranscriptionSegment> = output
+        .transcription
+        .into_iter()
+        .enumerate()
+        .map(|(index, segment)| TranscriptionSegment {
+            id: index as u32,
+            start: segment.offsets.from as f64 / 1000.0,
+            end: segment.offsets.to as f64 / 1000.0,
+            text: segment.text,
+            avg_logprob: None,
+            compression_ratio: None,
+            no_speech_prob: None,
+        })
+        .collect();
+    let text = segments
+        .iter()
+        .map(|segment| segment.text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Ok(TranscriptionResponse {
+        text,
+        language: output.result.and_then(|result| result.language),
+        duration: segments.last().map(|segment| segment.end),
+        segments: Some(segments),
+        words: None,
+    })
+}
+
+#[async_trait]
+impl WhisperClient for LocalWhisperClient {
+    async fn transcribe(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+    ) -> WhisperResult<TranscriptionResponse> {
+        self.run(file_path, prompt, None, self.config.mode).await
+    }
+
+    async fn transcribe_with_language(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+        language: Option<String>,
+    ) -> WhisperResult<TranscriptionResponse> {
+        self.run(file_path, prompt, language, self.config.mode)
+            .await
+    }
+
+    async fn transcribe_with_mode(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+        language: Option<String>,
+        mode: WhisperMode,
+    ) -> WhisperResult<TranscriptionResponse> {
+        self.run(file_path, prompt, language, mode).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_args() {
+        let client = LocalWhisperClient::new(LocalWhisperConfig {
+            model_path: PathBuf::from("ggml-base.bin"),
+            ..Default::default()
+        });
+        let args = client.build_args(
+            Path::new("audio.ogg"),
+            Path::new("out"),
+            Some("Glossary: DictaClerk"),
+            Some("fr"),
+            WhisperMode::Transcribe,
+        );
+        let value = |flag: &str| {
+            let index = args.iter().position(|arg| arg == flag).unwrap();
+            args[index + 1].clone()
+        };
+        assert_eq!(value("--model"), "ggml-base.bin");
+        assert_eq!(value("--language"), "fr");
+        assert_eq!(value("--prompt"), "Glossary: DictaClerk");
+        assert!(args.contains(&"--no-gpu".to_string()));
+        assert!(!args.contains(&"--translate".to_string()));
+
+        let args = client.build_args(
+            Path::new("audio.ogg"),
+            Path::new("out"),
+            None,
+            Some("fr"),
+            WhisperMode::Translate,
+        );
+        assert!(args.contains(&"--translate".to_string()));
+        assert!(args.contains(&"auto".to_string()));
+        assert!(!args.contains(&"--prompt".to_string()));
+    }
+
+    #[test]
+    fn test_parse_output() {
+        let json = r#"{
+            "systeminfo": "AVX = 1",
+            "model": {"type": "base"},
+            "result": {"language": "fr"},
+            "transcription": [
+                {"timestamps": {"from": "00:00:00,000", "to": "00:00:02,500"},
+                 "offsets": {"from": 0, "to": 2500}, "text": " Bonjour à tous."},
+                {"timestamps": {"from": "00:00:02,500", "to": "00:00:04,000"},
+                 "offsets": {"from": 2500, "to": 4000}, "text": " Commençons."}
+            ]
+        }"#;
+        let transcript = parse_output(json).unwrap();
+        assert_eq!(transcript.text, "Bonjour à tous. Commençons.");
+        assert_eq!(transcript.language.as_deref(), Some("fr"));
+        assert_eq!(transcript.duration, Some(4.0));
+        let segments = transcript.segments.unwrap();
+        assert_eq!(segments[1].id, 1);
+        assert_eq!(segments[1].start, 2.5);
+
+        assert!(matches!(
+            parse_output("not json"),
+            Err(WhisperError::InvalidResponse(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_missing_model_is_reported() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("audio.ogg");
+        std::fs::write(&file_path, b"fake ogg data").unwrap();
+
+        let client = LocalWhisperClient::new(LocalWhisperConfig {
+            model_path: temp_dir.path().join("missing.bin"),
+            ..Default::default()
+        });
+        assert!(matches!(
+            client.transcribe(&file_path, None).await,
+            Err(WhisperError::Api(message)) if message.contains("missing.bin")
+        ));
+    }
+}
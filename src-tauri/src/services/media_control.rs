@@ -0,0 +1,244 @@
+//! Pausing media players while recording
+//!
+//! Music playing in the background bleeds into the microphone and ends up in
+//! transcriptions. When a recording starts, the players that are playing are
+//! paused and remembered; when it stops, exactly those players are resumed, so
+//! a player the user had paused on purpose stays paused.
+//!
+//! - Linux: MPRIS players on the session bus (through `dbus-send`)
+//! - Windows: the system media transport control sessions (through PowerShell)
+//! - macOS: Music and Spotify (through `osascript`)
+//!
+//! The functions run external commands; call them from a blocking task.
+
+use std::process::Command;
+
+/// Pause the players that are playing, returning their identifiers
+pub fn pause_playing_media() -> Vec<String> {
+    let paused = platform_pause();
+    if !paused.is_empty() {
+        eprintln!("⏸️  [MEDIA] Paused while recording: {}", paused.join(", "));
+    }
+    paused
+}
+
+/// Resume the players paused by [`pause_playing_media`]
+pub fn resume_media(players: &[String]) {
+    if players.is_empty() {
+        return;
+    }
+    eprintln!("▶️  [MEDIA] Resuming: {}", players.join(", "));
+    platform_resume(players);
+}
+
+#[cfg(target_os = "linux")]
+fn platform_pause() -> Vec<String> {
+    let Some(names) = command_output(
+        "dbus-send",
+        &[
+            "--session",
+            "--print-reply",
+            "--dest=org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus.ListNames",
+        ],
+    ) else {
+        return Vec::new();
+    };
+
+    parse_mpris_names(&names)
+        .into_iter()
+        .filter(|player| {
+            let status = command_output(
+                "dbus-send",
+                &[
+                    "--session",
+                    "--print-reply",
+                    &format!("--dest={}", player),
+                    "/org/mpris/MediaPlayer2",
+                    "org.freedesktop.DBus.Properties.Get",
+                    "string:org.mpris.MediaPlayer2.Player",
+                    "string:PlaybackStatus",
+                ],
+            );
+            status.as_deref().and_then(parse_playback_status) == Some("Playing")
+                && mpris_call(player, "Pause")
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn platform_resume(players: &[String]) {
+    for player in players {
+        mpris_call(player, "Play");
+    }
+}
+
+/// Call a method of the MPRIS player interface
+#[cfg(target_os = "linux")]
+fn mpris_call(player: &str, method: &str) -> bool {
+    command_output(
+        "dbus-send",
+        &[
+            "--session",
+            "--print-reply",
+            &format!("--dest={}", player),
+            "/org/mpris/MediaPlayer2",
+            &format!("org.mpris.MediaPlayer2.Player.{}", method),
+        ],
+    )
+    .is_some()
+}
+
+/// Players known to the system media transport controls
+#[cfg(target_os = "windows")]
+const SESSIONS_SCRIPT: &str = r#"Add-Type -AssemblyName System.Runtime.WindowsRuntime
+$asTask = ([System.WindowsRuntimeSystemExtensions].GetMethods() | Where-Object { $_.Name -eq 'AsTask' -and $_.GetParameters().Count -eq 1 -and $_.GetParameters()[0].ParameterType.Name -eq 'IAsyncOperation`1' })[0]
+function Await($operation, $type) { $task = $asTask.MakeGenericMethod($type).Invoke($null, @($operation)); $null = $task.Wait(); $task.Result }
+$null = [Windows.Media.Control.GlobalSystemMediaTransportControlsSessionManager, Windows.Media.Control, ContentType = WindowsRuntime]
+$manager = Await ([Windows.Media.Control.GlobalSystemMediaTransportControlsSessionManager]::RequestAsync()) ([Windows.Media.Control.GlobalSystemMediaTransportControlsSessionManager])
+"#;
+
+#[cfg(target_os = "windows")]
+fn platform_pause() -> Vec<String> {
+    let script = format!(
+        "{}foreach ($session in $manager.GetSessions()) {{ if ($session.GetPlaybackInfo().PlaybackStatus -eq 'Playing' -and (Await ($session.TryPauseAsync()) ([bool]))) {{ $session.SourceAppUserModelId }} }}",
+        SESSIONS_SCRIPT
+    );
+    powershell(&script)
+        .map(|output| {
+            output
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "windows")]
+fn platform_resume(players: &[String]) {
+    let ids = players
+        .iter()
+        .map(|player| format!("'{}'", player.replace('\'', "''")))
+        .collect::<Vec<_>>()
+        .join(",");
+    let script = format!(
+        "{}$ids = @({})\nforeach ($session in $manager.GetSessions()) {{ if ($ids -contains $session.SourceAppUserModelId) {{ $null = Await ($session.TryPlayAsync()) ([bool]) }} }}",
+        SESSIONS_SCRIPT, ids
+    );
+    powershell(&script);
+}
+
+#[cfg(target_os = "windows")]
+fn powershell(script: &str) -> Option<String> {
+    command_output(
+        "powershell",
+        &["-NoProfile", "-NonInteractive", "-Command", script],
+    )
+}
+
+/// Scriptable players
+#[cfg(target_os = "macos")]
+const MACOS_PLAYERS: &[&str] = &["Music", "Spotify"];
+
+#[cfg(target_os = "macos")]
+fn platform_pause() -> Vec<String> {
+    MACOS_PLAYERS
+        .iter()
+        .filter(|player| {
+            let script = format!(
+                "if application \"{0}\" is running then\n\
+                 tell application \"{0}\"\n\
+                 if player state is playing then\n\
+                 pause\n\
+                 return \"paused\"\n\
+                 end if\n\
+                 end tell\n\
+                 end if\n\
+                 return \"\"",
+                player
+            );
+            command_output("osascript", &["-e", &script]).is_some_and(|out| out.trim() == "paused")
+        })
+        .map(|player| player.to_string())
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn platform_resume(players: &[String]) {
+    for player in players {
+        let script = format!("tell application \"{}\" to play", player);
+        command_output("osascript", &["-e", &script]);
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn platform_pause() -> Vec<String> {
+    Vec::new()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn platform_resume(_players: &[String]) {}
+
+/// Standard output of a command that succeeded
+#[allow(dead_code)]
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// MPRIS bus names in a `ListNames` reply
+#[allow(dead_code)]
+fn parse_mpris_names(reply: &str) -> Vec<String> {
+    reply
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("string \""))
+        .filter_map(|name| name.strip_suffix('"'))
+        .filter(|name| name.starts_with("org.mpris.MediaPlayer2."))
+        .map(str::to_string)
+        .collect()
+}
+
+/// `PlaybackStatus` value in a `Properties.Get` reply ("Playing", "Paused", "Stopped")
+#[allow(dead_code)]
+fn parse_playback_status(reply: &str) -> Option<&str> {
+    let (_, value) = reply.split_once("variant")?;
+    value.trim().strip_prefix("string \"")?.strip_suffix('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mpris_names() {
+        let reply = "method return time=1.2 sender=org.freedesktop.DBus -> destination=:1.9 serial=3 reply_serial=2\n   \
+                     array [\n      string \"org.freedesktop.DBus\"\n      \
+                     string \"org.mpris.MediaPlayer2.spotify\"\n      string \":1.42\"\n      \
+                     string \"org.mpris.MediaPlayer2.firefox.instance_1_12\"\n   ]\n";
+        assert_eq!(
+            parse_mpris_names(reply),
+            vec![
+                "org.mpris.MediaPlayer2.spotify",
+                "org.mpris.MediaPlayer2.firefox.instance_1_12"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_playback_status() {
+        let reply =
+            "method return time=1.2 sender=:1.42 -> destination=:1.9 serial=8 reply_serial=2\n   \
+                     variant       string \"Playing\"\n";
+        assert_eq!(parse_playback_status(reply), Some("Playing"));
+        assert_eq!(
+            parse_playback_status("Error org.freedesktop.DBus.Error"),
+            None
+        );
+    }
+}
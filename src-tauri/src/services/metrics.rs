@@ -0,0 +1,295 @@
+//! Metrics service for workflow counters and latency histograms
+//!
+//! This service keeps process-wide counters and histograms about the
+//! recording workflow (recordings, failures by stage, stage latencies) and
+//! renders them in the Prometheus text exposition format so they can be
+//! scraped from the local API `/metrics` endpoint.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::MetricsRegistry;
+//!
+//! let metrics = MetricsRegistry::global();
+//! metrics.inc_counter("dictaclerk_recordings_total", &[]);
+//! metrics.inc_counter("dictaclerk_workflow_failures_total", &[("stage", "transcription")]);
+//! metrics.observe("dictaclerk_stage_duration_seconds", &[("stage", "transcription")], 2.4);
+//!
+//! println!("{}", metrics.render());
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Counter of completed recordings
+pub const RECORDINGS_TOTAL: &str = "dictaclerk_recordings_total";
+/// Counter of workflow failures, labelled by stage
+pub const WORKFLOW_FAILURES_TOTAL: &str = "dictaclerk_workflow_failures_total";
+/// Histogram of stage latencies in seconds, labelled by stage
+pub const STAGE_DURATION_SECONDS: &str = "dictaclerk_stage_duration_seconds";
+
+/// Default histogram buckets (seconds), tuned for API round-trips
+pub const DEFAULT_BUCKETS: &[f64] = &[0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0];
+
+/// Sorted label set used as a series key
+type Labels = Vec<(String, String)>;
+
+/// Histogram series state
+#[derive(Debug, Clone)]
+struct Histogram {
+    buckets: Vec<f64>,
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(buckets: &[f64]) -> Self {
+        Self {
+            buckets: buckets.to_vec(),
+            counts: vec![0; buckets.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (bound, count) in self.buckets.iter().zip(self.counts.iter_mut()) {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+#[derive(Debug, Default)]
+struct MetricsInner {
+    counters: BTreeMap<String, BTreeMap<Labels, u64>>,
+    histograms: BTreeMap<String, BTreeMap<Labels, Histogram>>,
+    help: BTreeMap<String, String>,
+}
+
+/// Registry of counters and histograms rendered in Prometheus text format
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    inner: Mutex<MetricsInner>,
+}
+
+impl MetricsRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process-wide registry used by the workflow and the local API
+    pub fn global() -> &'static MetricsRegistry {
+        static GLOBAL: OnceLock<MetricsRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(|| {
+            let registry = MetricsRegistry::new();
+            registry.describe(RECORDINGS_TOTAL, "Number of recordings processed");
+            registry.describe(
+                WORKFLOW_FAILURES_TOTAL,
+                "Number of failed workflows by stage",
+            );
+            registry.describe(STAGE_DURATION_SECONDS, "Workflow stage latency in seconds");
+            registry
+        })
+    }
+
+    /// Set the HELP text for a metric
+    pub fn describe(&self, name: &str, help: &str) {
+        let mut inner = self.lock();
+        inner.help.insert(name.to_string(), help.to_string());
+    }
+
+    /// Increment a counter by one
+    pub fn inc_counter(&self, name: &str, labels: &[(&str, &str)]) {
+        let mut inner = self.lock();
+        *inner
+            .counters
+            .entry(name.to_string())
+            .or_default()
+            .entry(to_labels(labels))
+            .or_insert(0) += 1;
+    }
+
+    /// Record an observation in a histogram (default buckets)
+    pub fn observe(&self, name: &str, labels: &[(&str, &str)], value: f64) {
+        let mut inner = self.lock();
+        inner
+            .histograms
+            .entry(name.to_string())
+            .or_default()
+            .entry(to_labels(labels))
+            .or_insert_with(|| Histogram::new(DEFAULT_BUCKETS))
+            .observe(value);
+    }
+
+    /// Current value of a counter (0 if never incremented)
+    pub fn counter_value(&self, name: &str, labels: &[(&str, &str)]) -> u64 {
+        let inner = self.lock();
+        inner
+            .counters
+            .get(name)
+            .and_then(|series| series.get(&to_labels(labels)))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Render all metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let inner = self.lock();
+        let mut output = String::new();
+
+        for (name, series) in &inner.counters {
+            write_header(&mut output, &inner.help, name, "counter");
+            for (labels, value) in series {
+                output.push_str(&format!(
+                    "{}{} {}\n",
+                    name,
+                    format_labels(labels, None),
+                    value
+                ));
+            }
+        }
+
+        for (name, series) in &inner.histograms {
+            write_header(&mut output, &inner.help, name, "histogram");
+            for (labels, histogram) in series {
+                for (bound, count) in histogram.buckets.iter().zip(&histogram.counts) {
+                    output.push_str(&format!(
+                        "{}_bucket{} {}\n",
+                        name,
+                        format_labels(labels, Some(&bound.to_string())),
+                        count
+                    ));
+                }
+                output.push_str(&format!(
+                    "{}_bucket{} {}\n",
+                    name,
+                    format_labels(labels, Some("+Inf")),
+                    histogram.count
+                ));
+                output.push_str(&format!(
+                    "{}_sum{} {}\n",
+                    name,
+                    format_labels(labels, None),
+                    histogram.sum
+                ));
+                output.push_str(&format!(
+                    "{}_count{} {}\n",
+                    name,
+                    format_labels(labels, None),
+                    histogram.count
+                ));
+            }
+        }
+
+        output
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MetricsInner> {
+        // Metrics must never take the app down: recover from a poisoned lock
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Record how long a workflow stage took
+pub fn observe_stage_duration(stage: &str, duration: std::time::Duration) {
+    MetricsRegistry::global().observe(
+        STAGE_DURATION_SECONDS,
+        &[("stage", stage)],
+        duration.as_secs_f64(),
+    );
+}
+
+/// Count a workflow failure at the given stage
+pub fn record_stage_failure(stage: &str) {
+    MetricsRegistry::global().inc_counter(WORKFLOW_FAILURES_TOTAL, &[("stage", stage)]);
+}
+
+fn to_labels(labels: &[(&str, &str)]) -> Labels {
+    let mut labels: Labels = labels
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    labels.sort();
+    labels
+}
+
+fn write_header(output: &mut String, help: &BTreeMap<String, String>, name: &str, kind: &str) {
+    if let Some(help) = help.get(name) {
+        output.push_str(&format!("# HELP {} {}\n", name, help));
+    }
+    output.push_str(&format!("# TYPE {} {}\n", name, kind));
+}
+
+fn format_labels(labels: &Labels, le: Option<&str>) -> String {
+    let mut parts: Vec<String> = labels
+        .iter()
+        .map(|(k, v)| {
+            format!(
+                "{}=\"{}\"",
+                k,
+                v.replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('\n', "\\n")
+            )
+        })
+        .collect();
+    if let Some(le) = le {
+        parts.push(format!("le=\"{}\"", le));
+    }
+
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", parts.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_rendering() {
+        let metrics = MetricsRegistry::new();
+        metrics.describe(RECORDINGS_TOTAL, "Number of recordings processed");
+        metrics.inc_counter(RECORDINGS_TOTAL, &[]);
+        metrics.inc_counter(RECORDINGS_TOTAL, &[]);
+        metrics.inc_counter(WORKFLOW_FAILURES_TOTAL, &[("stage", "clipboard")]);
+
+        assert_eq!(metrics.counter_value(RECORDINGS_TOTAL, &[]), 2);
+
+        let output = metrics.render();
+        assert!(output.contains("# HELP dictaclerk_recordings_total Number of recordings"));
+        assert!(output.contains("# TYPE dictaclerk_recordings_total counter"));
+        assert!(output.contains("dictaclerk_recordings_total 2\n"));
+        assert!(output.contains("dictaclerk_workflow_failures_total{stage=\"clipboard\"} 1\n"));
+    }
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let metrics = MetricsRegistry::new();
+        metrics.observe(STAGE_DURATION_SECONDS, &[("stage", "transcription")], 0.4);
+        metrics.observe(STAGE_DURATION_SECONDS, &[("stage", "transcription")], 3.0);
+
+        let output = metrics.render();
+        assert!(output.contains("# TYPE dictaclerk_stage_duration_seconds histogram"));
+        assert!(output.contains(
+            "dictaclerk_stage_duration_seconds_bucket{stage=\"transcription\",le=\"0.5\"} 1\n"
+        ));
+        assert!(output.contains(
+            "dictaclerk_stage_duration_seconds_bucket{stage=\"transcription\",le=\"5\"} 2\n"
+        ));
+        assert!(output.contains(
+            "dictaclerk_stage_duration_seconds_bucket{stage=\"transcription\",le=\"+Inf\"} 2\n"
+        ));
+        assert!(
+            output.contains("dictaclerk_stage_duration_seconds_count{stage=\"transcription\"} 2\n")
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! Mixed-language (code-switched) transcripts
+//!
+//! Whisper detects one language per request: when a bilingual speaker
+//! switches language mid-recording, the passages in the other language come
+//! back garbled or translated. With a mixed-language mode on a profile, each
+//! segment is cut from the recording and transcribed again on its own, with
+//! the language detected for that segment. The recording's main language is
+//! the one spoken the longest; segments in another (minority) language are
+//! then either annotated with their language, kept as spoken (normalized
+//! transcript), or translated to the main language.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::mixed_language::{
+//!     apply_languages, main_language, MixedLanguageMode, SegmentLanguage,
+//! };
+//! use dicta_clerk_lib::services::TranscriptionSegment;
+//! use std::collections::HashMap;
+//!
+//! let segment = |id: u32, start: f64, end: f64, text: &str| TranscriptionSegment {
+//!     id,
+//!     start,
+//!     end,
+//!     text: text.to_string(),
+//!     avg_logprob: None,
+//!     compression_ratio: None,
+//!     no_speech_prob: None,
+//! };
+//! let mut segments = vec![segment(0, 0.0, 6.0, " Let's start."), segment(1, 6.0, 8.0, " Thanks.")];
+//! let detected = vec![
+//!     SegmentLanguage { id: 0, language: Some("english".to_string()), text: " Let's start.".to_string() },
+//!     SegmentLanguage { id: 1, language: Some("french".to_string()), text: " Merci.".to_string() },
+//! ];
+//!
+//! let main = main_language(&segments, &detected).unwrap();
+//! apply_languages(&mut segments, &detected, &main, MixedLanguageMode::Annotate, &HashMap::new());
+//! assert_eq!(segments[1].text, " [French] Merci.");
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::services::whisper_client::TranscriptionSegment;
+
+/// Segments transcribed again at most (one request each); longer recordings
+/// keep their single-language transcript
+pub const MAX_MIXED_LANGUAGE_SEGMENTS: usize = 60;
+
+/// What happens to the segments in a minority language
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MixedLanguageMode {
+    /// Kept as spoken, prefixed with their language (e.g. `[French]`)
+    Annotate,
+    /// Kept as spoken, without annotation
+    Normalize,
+    /// Translated to the main language
+    Translate,
+}
+
+/// Language detected for a segment transcribed on its own
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentLanguage {
+    /// Id of the segment in the transcript
+    pub id: u32,
+    /// Language reported by Whisper (e.g. "french"), None when not reported
+    pub language: Option<String>,
+    /// Text of the segment in its own language
+    pub text: String,
+}
+
+/// Language spoken the longest, weighted by segment duration
+pub fn main_language(
+    segments: &[TranscriptionSegment],
+    detected: &[SegmentLanguage],
+) -> Option<String> {
+    let mut durations: HashMap<&str, f64> = HashMap::new();
+    for found in detected {
+        let Some(language) = found.language.as_deref() else {
+            continue;
+        };
+        let duration = segments
+            .iter()
+            .find(|segment| segment.id == found.id)
+            .map_or(0.0, |segment| (segment.end - segment.start).max(0.0));
+        *durations.entry(language).or_insert(0.0) += duration;
+    }
+    durations
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(language, _)| language.to_string())
+}
+
+/// Segments detected in another language than `main`
+pub fn minority_segments<'a>(
+    detected: &'a [SegmentLanguage],
+    main: &str,
+) -> Vec<&'a SegmentLanguage> {
+    detected
+        .iter()
+        .filter(|found| {
+            found
+                .language
+                .as_deref()
+                .is_some_and(|language| !language.eq_ignore_ascii_case(main))
+                && !found.text.trim().is_empty()
+        })
+        .collect()
+}
+
+/// Language name as written in annotations ("french" -> "French")
+pub fn language_label(language: &str) -> String {
+    let mut chars = language.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Instructions translating a minority-language segment to `main`
+pub fn translation_prompt(main: &str) -> String {
+    format!(
+        "Translate this transcript excerpt to {}. Answer with the translation only, without quotes or comments.",
+        language_label(main)
+    )
+}
+
+/// Rewrite the segments in minority languages according to `mode`
+///
+/// Segments in the main language keep their original text. `translations`
+/// holds the translated texts (by segment id) for `MixedLanguageMode::Translate`;
+/// a segment without one keeps the text in its own language.
+pub fn apply_languages(
+    segments: &mut [TranscriptionSegment],
+    detected: &[SegmentLanguage],
+    main: &str,
+    mode: MixedLanguageMode,
+    translations: &HashMap<u32, String>,
+) {
+    for found in minority_segments(detected, main) {
+        let Some(segment) = segments.iter_mut().find(|segment| segment.id == found.id) else {
+            continue;
+        };
+        let spoken = found.text.trim();
+        let text = match mode {
+            MixedLanguageMode::Annotate => format!(
+                "[{}] {}",
+                language_label(found.language.as_deref().unwrap_or_default()),
+                spoken
+            ),
+            MixedLanguageMode::Normalize => spoken.to_string(),
+            MixedLanguageMode::Translate => translations
+                .get(&found.id)
+                .map(|translation| translation.trim())
+                .filter(|translation| !translation.is_empty())
+                .unwrap_or(spoken)
+                .to_string(),
+        };
+        segment.text = format!(" {}", text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(id: u32, start: f64, end: f64, text: &str) -> TranscriptionSegment {
+        TranscriptionSegment {
+            id,
+            start,
+            end,
+            text: text.to_string(),
+            avg_logprob: None,
+            compression_ratio: None,
+            no_speech_prob: None,
+        }
+    }
+
+    fn found(id: u32, language: Option<&str>, text: &str) -> SegmentLanguage {
+        SegmentLanguage {
+            id,
+            language: language.map(str::to_string),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_mixed_language_modes() {
+        // More French segments, but English is spoken longer
+        let segments = vec![
+            segment(0, 0.0, 10.0, " The quarterly numbers are in."),
+            segment(1, 10.0, 12.0, " C'est bon."),
+            segment(2, 12.0, 14.0, " Pas mal."),
+            segment(3, 14.0, 16.0, " Next slide."),
+        ];
+        let detected = vec![
+            found(0, Some("english"), " The quarterly numbers are in."),
+            found(1, Some("french"), " C'est bon."),
+            found(2, Some("french"), " Pas mal."),
+            found(3, None, " Next slide."),
+        ];
+        let main = main_language(&segments, &detected).unwrap();
+        assert_eq!(main, "english");
+        assert_eq!(
+            minority_segments(&detected, &main)
+                .iter()
+                .map(|found| found.id)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+
+        let mut annotated = segments.clone();
+        apply_languages(
+            &mut annotated,
+            &detected,
+            &main,
+            MixedLanguageMode::Annotate,
+            &HashMap::new(),
+        );
+        assert_eq!(annotated[1].text, " [French] C'est bon.");
+        assert_eq!(annotated[3].text, " Next slide.");
+
+        let mut translated = segments.clone();
+        let translations = HashMap::from([(1, "It's good.".to_string())]);
+        apply_languages(
+            &mut translated,
+            &detected,
+            &main,
+            MixedLanguageMode::Translate,
+            &translations,
+        );
+        assert_eq!(translated[1].text, " It's good.");
+        // Without a translation the segment stays as spoken
+        assert_eq!(translated[2].text, " Pas mal.");
+
+        assert_eq!(main_language(&segments, &[]), None);
+        assert!(translation_prompt("french").contains("to French."));
+    }
+}
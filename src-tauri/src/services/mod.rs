@@ -1,31 +1,125 @@
+pub mod api_limiter;
+pub mod app_lock;
+pub mod bookmarks;
+pub mod calibration;
+pub mod chapters;
+pub mod chunk_pipeline;
 pub mod clipboard_svc;
+pub mod context_memory;
+pub mod continuous_session;
+pub mod corrections;
+pub mod duplicate_detection;
+pub mod encryption;
+pub mod event_targets;
+pub mod failed_workflows;
+pub mod feature_flags;
+pub mod focus_session;
+pub mod goals;
 pub mod gpt_client;
+pub mod history;
+pub mod input_integration;
+pub mod interview;
+pub mod live_captions;
+pub mod local_api;
+pub mod local_inference;
+pub mod local_whisper;
+pub mod media_control;
+pub mod metrics;
+pub mod mixed_language;
+pub mod model_store;
+pub mod network;
 pub mod notifier;
+pub mod output_files;
+pub mod pairing;
 pub mod profile_engine;
+pub mod profile_store;
+pub mod profile_suggestion;
+pub mod profile_sync;
+pub mod prompt_lint;
+pub mod providers;
+pub mod punctuation;
+pub mod recording_archive;
+pub mod reference_context;
+pub mod resource_guard;
+pub mod retranscription;
+pub mod retry_policy;
 pub mod shortcut_mgr;
 pub mod size_guard;
 pub mod system_tray;
+pub mod text_diff;
+pub mod transcript_format;
+pub mod transcription_queue;
+pub mod tts;
+pub mod usage;
+pub mod vocabulary;
+pub mod voice_commands;
+pub mod webhook;
+pub mod weekly_report;
 pub mod whisper_client;
+pub mod whisper_prompt;
+pub mod workflow_trace;
 
+pub use app_lock::{AppLock, AppLockError, AppLockResult, LockMethod};
+pub use chunk_pipeline::{run_chunk_pipeline, run_concurrent_chunks, ChunkProgress, ChunkStatus};
 pub use clipboard_svc::{
     ClipboardError, ClipboardResult, ClipboardService, MockClipboardService, TauriClipboardService,
 };
+pub use continuous_session::{SessionProgress, SessionStatus, SessionTranscript};
+pub use corrections::{Correction, CorrectionError, CorrectionResult, CorrectionStore};
+pub use encryption::{DataKey, EncryptionError, EncryptionResult};
+pub use failed_workflows::{
+    FailedWorkflow, FailedWorkflowError, FailedWorkflowResult, FailedWorkflowStore, WorkflowStage,
+};
 pub use gpt_client::{GptClient, GptError, GptResult};
+pub use history::{
+    HistoryEntry, HistoryError, HistoryResult, HistoryStore, PurgeReport, RetentionPolicy,
+};
+pub use input_integration::InputIntegration;
+pub use interview::{format_interview_transcript, interleave_turns, InterviewTurn, Speaker};
+pub use live_captions::{caption_events, LiveCaptionConfig, LiveCaptionWriter};
+pub use local_api::{
+    HttpRequest, HttpResponse, LocalApiConfig, LocalApiError, LocalApiResult, LocalApiServer,
+    RemoteAccess, RemoteControl,
+};
+pub use local_inference::{DeviceSelection, GpuInfo, InferenceDevice, LocalInferenceCapabilities};
+pub use local_whisper::{LocalWhisperClient, LocalWhisperConfig};
+pub use metrics::MetricsRegistry;
+pub use model_store::{
+    DownloadProgress, LocalModelInfo, ModelSpec, ModelStore, ModelStoreError, ModelStoreResult,
+    MODEL_CATALOG,
+};
 pub use notifier::{
     MockNotifierService, NotificationLevel, Notifier, NotifierError, NotifierResult,
     TauriNotifierService,
 };
+pub use output_files::{
+    FilenameContext, FilenameTemplate, FilenameTemplateError, DEFAULT_FILENAME_TEMPLATE,
+};
+pub use pairing::{Pairing, PairingError, PairingResult, PairingStore};
 pub use profile_engine::{
-    Profile, ProfileCollection, ProfileEngine, ProfileEngineConfig, ProfileError, ProfileResult,
+    Profile, ProfileCollection, ProfileEngine, ProfileEngineConfig, ProfileError, ProfileKind,
+    ProfileResult,
+};
+pub use providers::{ProviderCapabilities, ProviderFeatures, ProviderKind};
+pub use resource_guard::{
+    GuardedWhisperClient, LocalInferencePermit, ResourceGuard, ResourceGuardConfig,
+    ResourceShortage, ResourceSnapshot,
 };
 pub use shortcut_mgr::{
     ShortcutError, ShortcutEvent, ShortcutMgr, ShortcutMgrConfig, ShortcutResult,
 };
-pub use size_guard::{SizeGuard, SizeGuardConfig, SizeGuardError};
+pub use size_guard::{
+    split_recording, split_recording_by_duration, stitch_transcriptions, RecordingChunk,
+    SizeForecast, SizeForecaster, SizeGuard, SizeGuardConfig, SizeGuardError, UploadCheck,
+    WHISPER_UPLOAD_LIMIT_BYTES,
+};
 pub use system_tray::{
     SystemTrayConfig, SystemTrayError, SystemTrayResult, SystemTrayService, WindowState,
 };
+pub use tts::{Speech, TtsError, TtsOptions, TtsProvider, TtsResult};
 pub use whisper_client::{
-    OpenAIWhisperClient, TranscriptionResponse, TranscriptionSegment, WhisperClient,
-    WhisperClientConfig, WhisperError, WhisperResult,
+    normalize_language_code, DryRunWhisperClient, OpenAIWhisperClient, PromptStrategy,
+    SegmentedWhisperClient, TranscriptionResponse, TranscriptionSegment, TranscriptionWord,
+    WhisperClient, WhisperClientConfig, WhisperError, WhisperMode, WhisperProvider,
+    WhisperResponseFormat, WhisperResult,
 };
@@ -0,0 +1,424 @@
+//! ModelStore service for local Whisper models
+//!
+//! Local backends need model files (whisper.cpp GGML weights) on disk. The
+//! store lists the known models, downloads them with progress reporting and
+//! SHA-256 verification, deletes them and reports how much disk space they
+//! use. Downloads go to a `.part` file that is only renamed into place once
+//! the checksum matches, so an interrupted download never looks installed.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use std::path::PathBuf;
+//! use dicta_clerk_lib::services::ModelStore;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let store = ModelStore::new(PathBuf::from("models"));
+//!
+//! store
+//!     .download("base.en", |progress| {
+//!         println!("{} / {:?} bytes", progress.downloaded_bytes, progress.total_bytes);
+//!     })
+//!     .await?;
+//!
+//! for model in store.list()? {
+//!     println!("{}: installed = {}", model.name, model.installed);
+//! }
+//! println!("Models use {} bytes", store.disk_usage()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+
+/// Base URL of the whisper.cpp GGML models
+const MODEL_BASE_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+/// Header carrying the SHA-256 of LFS files on Hugging Face
+const LINKED_ETAG_HEADER: &str = "x-linked-etag";
+
+/// A model that can be downloaded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelSpec {
+    pub name: &'static str,
+    /// Approximate download size (MB)
+    pub size_mb: u64,
+    /// Pinned SHA-256 (None = published by the server with the download)
+    pub sha256: Option<&'static str>,
+}
+
+impl ModelSpec {
+    /// File name of the model inside the store
+    pub fn file_name(&self) -> String {
+        format!("ggml-{}.bin", self.name)
+    }
+
+    /// Download URL
+    pub fn url(&self) -> String {
+        format!("{}/{}", MODEL_BASE_URL, self.file_name())
+    }
+}
+
+/// Models known to the store
+pub const MODEL_CATALOG: &[ModelSpec] = &[
+    ModelSpec {
+        name: "tiny",
+        size_mb: 75,
+        sha256: None,
+    },
+    ModelSpec {
+        name: "tiny.en",
+        size_mb: 75,
+        sha256: None,
+    },
+    ModelSpec {
+        name: "base",
+        size_mb: 142,
+        sha256: None,
+    },
+    ModelSpec {
+        name: "base.en",
+        size_mb: 142,
+        sha256: None,
+    },
+    ModelSpec {
+        name: "small",
+        size_mb: 466,
+        sha256: None,
+    },
+    ModelSpec {
+        name: "small.en",
+        size_mb: 466,
+        sha256: None,
+    },
+    ModelSpec {
+        name: "medium",
+        size_mb: 1500,
+        sha256: None,
+    },
+    ModelSpec {
+        name: "large-v3",
+        size_mb: 2900,
+        sha256: None,
+    },
+];
+
+/// Errors that can occur while managing local models
+#[derive(Error, Debug)]
+pub enum ModelStoreError {
+    #[error("Unknown model: {name}")]
+    UnknownModel { name: String },
+    #[error("Model not installed: {name}")]
+    NotInstalled { name: String },
+    #[error("IO error on {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Download failed: {0}")]
+    Download(String),
+    #[error("No checksum available for {name}, refusing to install it")]
+    ChecksumUnavailable { name: String },
+    #[error("Checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+}
+
+/// Result type for model store operations
+pub type ModelStoreResult<T> = Result<T, ModelStoreError>;
+
+/// Catalog entry together with its install state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalModelInfo {
+    pub name: String,
+    pub file_name: String,
+    pub size_mb: u64,
+    pub installed: bool,
+    /// Size on disk when installed
+    pub installed_bytes: Option<u64>,
+}
+
+/// Progress of a running download
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DownloadProgress {
+    pub downloaded_bytes: u64,
+    /// None when the server does not send a content length
+    pub total_bytes: Option<u64>,
+}
+
+/// Model files stored in one directory
+#[derive(Debug, Clone)]
+pub struct ModelStore {
+    dir: PathBuf,
+}
+
+impl ModelStore {
+    /// Create a store in the given directory (created on first download)
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Directory holding the models
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Look a model up in the catalog
+    pub fn spec(name: &str) -> ModelStoreResult<&'static ModelSpec> {
+        MODEL_CATALOG
+            .iter()
+            .find(|spec| spec.name == name)
+            .ok_or_else(|| ModelStoreError::UnknownModel {
+                name: name.to_string(),
+            })
+    }
+
+    /// Path of an installed model, if it is installed
+    pub fn model_path(&self, name: &str) -> ModelStoreResult<PathBuf> {
+        let path = self.dir.join(Self::spec(name)?.file_name());
+        if path.is_file() {
+            Ok(path)
+        } else {
+            Err(ModelStoreError::NotInstalled {
+                name: name.to_string(),
+            })
+        }
+    }
+
+    /// All catalog models with their install state
+    pub fn list(&self) -> ModelStoreResult<Vec<LocalModelInfo>> {
+        MODEL_CATALOG
+            .iter()
+            .map(|spec| {
+                let path = self.dir.join(spec.file_name());
+                let installed_bytes = match std::fs::metadata(&path) {
+                    Ok(metadata) => Some(metadata.len()),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                    Err(e) => return Err(io_error(&path, e)),
+                };
+                Ok(LocalModelInfo {
+                    name: spec.name.to_string(),
+                    file_name: spec.file_name(),
+                    size_mb: spec.size_mb,
+                    installed: installed_bytes.is_some(),
+                    installed_bytes,
+                })
+            })
+            .collect()
+    }
+
+    /// Bytes used by the store directory (models and leftover partial downloads)
+    pub fn disk_usage(&self) -> ModelStoreResult<u64> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(io_error(&self.dir, e)),
+        };
+
+        let mut total = 0;
+        for entry in entries {
+            let entry = entry.map_err(|e| io_error(&self.dir, e))?;
+            let metadata = entry.metadata().map_err(|e| io_error(&entry.path(), e))?;
+            if metadata.is_file() {
+                total += metadata.len();
+            }
+        }
+        Ok(total)
+    }
+
+    /// Delete an installed model
+    pub fn delete(&self, name: &str) -> ModelStoreResult<()> {
+        let path = self.model_path(name)?;
+        std::fs::remove_file(&path).map_err(|e| io_error(&path, e))
+    }
+
+    /// Download a catalog model, verify its checksum and install it
+    pub async fn download(
+        &self,
+        name: &str,
+        progress: impl FnMut(DownloadProgress),
+    ) -> ModelStoreResult<PathBuf> {
+        let spec = Self::spec(name)?;
+        let target = self.dir.join(spec.file_name());
+        self.fetch(spec, &spec.url(), &target, progress).await?;
+        Ok(target)
+    }
+
+    /// Stream `url` to `<target>.part`, verify it and rename it into place
+    async fn fetch(
+        &self,
+        spec: &ModelSpec,
+        url: &str,
+        target: &Path,
+        mut progress: impl FnMut(DownloadProgress),
+    ) -> ModelStoreResult<()> {
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| io_error(&self.dir, e))?;
+
+        let client = Client::builder()
+            .connect_timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| ModelStoreError::Download(e.to_string()))?;
+        let mut response = client
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| ModelStoreError::Download(e.to_string()))?;
+
+        let expected = match spec.sha256 {
+            Some(sha256) => sha256.to_string(),
+            None => published_sha256(response.headers()).ok_or_else(|| {
+                ModelStoreError::ChecksumUnavailable {
+                    name: spec.name.to_string(),
+                }
+            })?,
+        };
+        let total_bytes = response.content_length();
+
+        let part_path = target.with_extension("bin.part");
+        let mut file = tokio::fs::File::create(&part_path)
+            .await
+            .map_err(|e| io_error(&part_path, e))?;
+        let mut hasher = Sha256::new();
+        let mut downloaded_bytes = 0u64;
+
+        let streamed: ModelStoreResult<()> = async {
+            while let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(|e| ModelStoreError::Download(e.to_string()))?
+            {
+                hasher.update(&chunk);
+                file.write_all(&chunk)
+                    .await
+                    .map_err(|e| io_error(&part_path, e))?;
+                downloaded_bytes += chunk.len() as u64;
+                progress(DownloadProgress {
+                    downloaded_bytes,
+                    total_bytes,
+                });
+            }
+            file.flush().await.map_err(|e| io_error(&part_path, e))
+        }
+        .await;
+        drop(file);
+
+        let verified = streamed.and_then(|_| {
+            let actual = hex::encode(hasher.finalize());
+            if actual.eq_ignore_ascii_case(&expected) {
+                Ok(())
+            } else {
+                Err(ModelStoreError::ChecksumMismatch { expected, actual })
+            }
+        });
+        if let Err(e) = verified {
+            let _ = tokio::fs::remove_file(&part_path).await;
+            return Err(e);
+        }
+
+        tokio::fs::rename(&part_path, target)
+            .await
+            .map_err(|e| io_error(target, e))
+    }
+}
+
+/// SHA-256 published by the server (Hugging Face LFS `X-Linked-Etag`)
+fn published_sha256(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    let etag = headers.get(LINKED_ETAG_HEADER)?.to_str().ok()?;
+    let etag = etag.trim_start_matches("W/").trim_matches('"');
+    (etag.len() == 64 && etag.chars().all(|c| c.is_ascii_hexdigit())).then(|| etag.to_string())
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> ModelStoreError {
+    ModelStoreError::Io {
+        path: path.to_string_lossy().to_string(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    /// Serve one HTTP response with the given body and linked etag
+    async fn serve_once(body: &'static [u8], etag: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await;
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nX-Linked-Etag: \"{}\"\r\nConnection: close\r\n\r\n",
+                body.len(),
+                etag
+            );
+            socket.write_all(head.as_bytes()).await.unwrap();
+            socket.write_all(body).await.unwrap();
+        });
+        format!("http://{}/ggml-tiny.bin", addr)
+    }
+
+    #[tokio::test]
+    async fn test_download_verifies_checksum() {
+        let dir = TempDir::new().unwrap();
+        let store = ModelStore::new(dir.path().join("models"));
+        let spec = ModelStore::spec("tiny").unwrap();
+        let target = store.dir().join(spec.file_name());
+        let body: &'static [u8] = b"fake model weights";
+
+        // Matching checksum: installed, progress reported
+        let url = serve_once(body, hex::encode(Sha256::digest(body))).await;
+        let mut last = None;
+        store
+            .fetch(spec, &url, &target, |progress| last = Some(progress))
+            .await
+            .unwrap();
+        assert_eq!(
+            last,
+            Some(DownloadProgress {
+                downloaded_bytes: body.len() as u64,
+                total_bytes: Some(body.len() as u64),
+            })
+        );
+        assert_eq!(store.model_path("tiny").unwrap(), target);
+        assert_eq!(store.disk_usage().unwrap(), body.len() as u64);
+
+        // Wrong checksum: nothing installed, no partial file left behind
+        store.delete("tiny").unwrap();
+        let url = serve_once(body, "0".repeat(64)).await;
+        let result = store.fetch(spec, &url, &target, |_| {}).await;
+        assert!(matches!(
+            result,
+            Err(ModelStoreError::ChecksumMismatch { .. })
+        ));
+        assert!(!store.list().unwrap()[0].installed);
+        assert_eq!(store.disk_usage().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_unknown_and_missing_models() {
+        let dir = TempDir::new().unwrap();
+        let store = ModelStore::new(dir.path().to_path_buf());
+
+        assert!(matches!(
+            store.delete("huge"),
+            Err(ModelStoreError::UnknownModel { .. })
+        ));
+        assert!(matches!(
+            store.delete("base"),
+            Err(ModelStoreError::NotInstalled { .. })
+        ));
+        assert_eq!(store.list().unwrap().len(), MODEL_CATALOG.len());
+    }
+}
@@ -0,0 +1,163 @@
+//! Network options of the API clients
+//!
+//! Corporate networks often only reach the internet through a proxy, and
+//! inspect TLS with their own certificate authority. The `network` section of
+//! the settings names the proxy and a PEM bundle of extra CA certificates;
+//! the Whisper and GPT clients apply them when building their HTTP client.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::network::NetworkConfig;
+//!
+//! let network = NetworkConfig {
+//!     proxy_url: Some("http://proxy.corp.example:3128".to_string()),
+//!     ca_bundle_path: Some("/etc/ssl/corp-ca.pem".to_string()),
+//! };
+//! let client = network
+//!     .apply(reqwest::Client::builder())
+//!     .unwrap()
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use reqwest::{Certificate, ClientBuilder, Proxy};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors in the network options
+#[derive(Error, Debug)]
+pub enum NetworkError {
+    #[error("Invalid proxy URL '{url}': {message}")]
+    InvalidProxy { url: String, message: String },
+    #[error("Failed to read the CA bundle {path}: {source}")]
+    CaBundleIo {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Invalid CA bundle {path}: {message}")]
+    InvalidCaBundle { path: String, message: String },
+}
+
+/// Result type for network options
+pub type NetworkResult<T> = Result<T, NetworkError>;
+
+/// Proxy and certificate authorities of the API clients (`network` section of
+/// the settings)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Proxy every request goes through (`http://`, `https://` or
+    /// `socks5://`, credentials allowed); the system proxy when unset
+    pub proxy_url: Option<String>,
+    /// PEM file of CA certificates trusted in addition to the system ones
+    pub ca_bundle_path: Option<String>,
+}
+
+impl NetworkConfig {
+    fn proxy_url(&self) -> Option<&str> {
+        self.proxy_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+    }
+
+    fn ca_bundle_path(&self) -> Option<&str> {
+        self.ca_bundle_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+    }
+
+    /// Configure `builder` with the proxy and the CA certificates
+    pub fn apply(&self, mut builder: ClientBuilder) -> NetworkResult<ClientBuilder> {
+        if let Some(url) = self.proxy_url() {
+            let proxy = Proxy::all(url).map_err(|e| NetworkError::InvalidProxy {
+                url: url.to_string(),
+                message: e.to_string(),
+            })?;
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(path) = self.ca_bundle_path() {
+            let pem = std::fs::read(path).map_err(|source| NetworkError::CaBundleIo {
+                path: path.to_string(),
+                source,
+            })?;
+            let certificates =
+                Certificate::from_pem_bundle(&pem).map_err(|e| NetworkError::InvalidCaBundle {
+                    path: path.to_string(),
+                    message: e.to_string(),
+                })?;
+            if certificates.is_empty() {
+                return Err(NetworkError::InvalidCaBundle {
+                    path: path.to_string(),
+                    message: "no certificate found".to_string(),
+                });
+            }
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_apply_network_config() {
+        assert!(NetworkConfig::default()
+            .apply(reqwest::Client::builder())
+            .is_ok());
+
+        let blank = NetworkConfig {
+            proxy_url: Some("  ".to_string()),
+            ca_bundle_path: Some(String::new()),
+        };
+        assert!(blank.apply(reqwest::Client::builder()).is_ok());
+
+        let proxy = NetworkConfig {
+            proxy_url: Some("http://proxy.example:3128".to_string()),
+            ..NetworkConfig::default()
+        };
+        assert!(proxy.apply(reqwest::Client::builder()).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_network_config() {
+        let proxy = NetworkConfig {
+            proxy_url: Some("not a url".to_string()),
+            ..NetworkConfig::default()
+        };
+        assert!(matches!(
+            proxy.apply(reqwest::Client::builder()),
+            Err(NetworkError::InvalidProxy { .. })
+        ));
+
+        let temp_dir = TempDir::new().unwrap();
+        let missing = NetworkConfig {
+            ca_bundle_path: Some(temp_dir.path().join("missing.pem").display().to_string()),
+            ..NetworkConfig::default()
+        };
+        assert!(matches!(
+            missing.apply(reqwest::Client::builder()),
+            Err(NetworkError::CaBundleIo { .. })
+        ));
+
+        let empty_bundle = temp_dir.path().join("empty.pem");
+        std::fs::write(&empty_bundle, "no certificate here").unwrap();
+        let empty = NetworkConfig {
+            ca_bundle_path: Some(empty_bundle.display().to_string()),
+            ..NetworkConfig::default()
+        };
+        assert!(matches!(
+            empty.apply(reqwest::Client::builder()),
+            Err(NetworkError::InvalidCaBundle { .. })
+        ));
+    }
+}
@@ -1,19 +1,29 @@
-//! Notifier service for success, warning, and error toasts
+//! Notifier service for success, warning, error and progress notifications
 //! Provides uniform UX feedback using tauri-plugin-notification
+//!
+//! Each level is routed to a channel by the [`NotificationRouting`] table of
+//! the settings: an in-app toast, a system notification, a sound, or nothing,
+//! so success noise can be silenced while errors still alert.
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
 use std::time::Duration;
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 use tauri_plugin_notification::{NotificationExt, PermissionState};
 use thiserror::Error;
 
+use crate::audio::{playback, AudioClip};
+use crate::services::event_targets::{emit_to_main, MAIN_WINDOW};
+
 /// Notification levels for different types of messages
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum NotificationLevel {
     Success,
     Warning,
     Error,
+    /// Step of a long operation (download, processing...)
+    Progress,
 }
 
 impl NotificationLevel {
@@ -23,6 +33,7 @@ impl NotificationLevel {
             NotificationLevel::Success => Duration::from_secs(3), // 3s for success
             NotificationLevel::Warning => Duration::from_secs(5), // 5s for warnings
             NotificationLevel::Error => Duration::from_secs(7),   // 7s for errors
+            NotificationLevel::Progress => Duration::from_secs(2), // 2s for progress
         }
     }
 
@@ -32,6 +43,7 @@ impl NotificationLevel {
             NotificationLevel::Success => "✅",
             NotificationLevel::Warning => "⚠️",
             NotificationLevel::Error => "❌",
+            NotificationLevel::Progress => "⏳",
         }
     }
 
@@ -41,10 +53,105 @@ impl NotificationLevel {
             NotificationLevel::Success => "Success: ",
             NotificationLevel::Warning => "Warning: ",
             NotificationLevel::Error => "Error: ",
+            NotificationLevel::Progress => "Progress: ",
+        }
+    }
+
+    /// Pitch of the sound played for this level (lower for problems)
+    fn chime_frequency(&self) -> f32 {
+        match self {
+            NotificationLevel::Success => 880.0,
+            NotificationLevel::Warning => 660.0,
+            NotificationLevel::Error => 440.0,
+            NotificationLevel::Progress => 990.0,
+        }
+    }
+}
+
+/// Where notifications of a level are delivered
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationChannel {
+    /// In-app toast in the main window
+    Toast,
+    /// Operating system notification
+    System,
+    /// Short sound, without anything shown
+    Sound,
+    /// Not delivered
+    #[serde(rename = "none")]
+    Silent,
+}
+
+/// Channel of each notification level (`notifications` in settings.json)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct NotificationRouting {
+    pub success: NotificationChannel,
+    pub warning: NotificationChannel,
+    pub error: NotificationChannel,
+    pub progress: NotificationChannel,
+}
+
+/// System notifications for results and problems, toasts for progress
+const DEFAULT_ROUTING: NotificationRouting = NotificationRouting {
+    success: NotificationChannel::System,
+    warning: NotificationChannel::System,
+    error: NotificationChannel::System,
+    progress: NotificationChannel::Toast,
+};
+
+impl Default for NotificationRouting {
+    fn default() -> Self {
+        DEFAULT_ROUTING
+    }
+}
+
+impl NotificationRouting {
+    /// Channel notifications of `level` are delivered to
+    pub fn channel(&self, level: &NotificationLevel) -> NotificationChannel {
+        match level {
+            NotificationLevel::Success => self.success,
+            NotificationLevel::Warning => self.warning,
+            NotificationLevel::Error => self.error,
+            NotificationLevel::Progress => self.progress,
         }
     }
 }
 
+static ROUTING: RwLock<NotificationRouting> = RwLock::new(DEFAULT_ROUTING);
+
+/// Apply the routing table of the settings (at startup and on save)
+pub fn set_notification_routing(routing: NotificationRouting) {
+    if let Ok(mut current) = ROUTING.write() {
+        *current = routing;
+    }
+}
+
+/// Routing table in effect
+pub fn notification_routing() -> NotificationRouting {
+    ROUTING.read().map(|routing| *routing).unwrap_or_default()
+}
+
+/// Short tone with a fade in and out, pitched by `level`
+fn chime(level: &NotificationLevel) -> AudioClip {
+    const SAMPLE_RATE: u32 = 44_100;
+    const FRAMES: usize = SAMPLE_RATE as usize / 6;
+    let frequency = level.chime_frequency();
+    let samples = (0..FRAMES)
+        .map(|frame| {
+            let fade = (frame.min(FRAMES - frame) as f32 / 800.0).min(1.0);
+            let phase = frame as f32 * frequency * 2.0 * std::f32::consts::PI / SAMPLE_RATE as f32;
+            phase.sin() * 0.2 * fade
+        })
+        .collect();
+    AudioClip {
+        samples,
+        channels: 1,
+        sample_rate: SAMPLE_RATE,
+    }
+}
+
 /// Errors that can occur in the notifier service
 #[derive(Debug, Error)]
 pub enum NotifierError {
@@ -81,6 +188,11 @@ pub trait Notifier: Send + Sync {
     async fn error(&self, message: &str) -> NotifierResult<()> {
         self.notify(NotificationLevel::Error, message).await
     }
+
+    /// Send a progress notification (convenience method)
+    async fn progress(&self, message: &str) -> NotifierResult<()> {
+        self.notify(NotificationLevel::Progress, message).await
+    }
 }
 
 /// Tauri-based notification service implementation
@@ -117,26 +229,22 @@ impl TauriNotifierService {
             }),
         }
     }
-}
 
-#[async_trait]
-impl Notifier for TauriNotifierService {
-    async fn notify(&self, level: NotificationLevel, message: &str) -> NotifierResult<()> {
+    /// Show `message` as an operating system notification
+    async fn show_system_notification(
+        &self,
+        level: &NotificationLevel,
+        message: &str,
+    ) -> NotifierResult<()> {
         // Ensure we have permissions
         self.ensure_permissions().await?;
 
-        // Validate message
-        if message.is_empty() {
-            return Err(NotifierError::InvalidParameters {
-                message: "Notification message cannot be empty".to_string(),
-            });
-        }
-
         // Create notification with level-specific formatting
         let title = match level {
             NotificationLevel::Success => "Success",
             NotificationLevel::Warning => "Warning",
             NotificationLevel::Error => "Error",
+            NotificationLevel::Progress => "Progress",
         };
 
         let formatted_message = format!("{}{}", level.accessibility_label(), message);
@@ -158,6 +266,51 @@ impl Notifier for TauriNotifierService {
     }
 }
 
+#[async_trait]
+impl Notifier for TauriNotifierService {
+    async fn notify(&self, level: NotificationLevel, message: &str) -> NotifierResult<()> {
+        // Validate message
+        if message.is_empty() {
+            return Err(NotifierError::InvalidParameters {
+                message: "Notification message cannot be empty".to_string(),
+            });
+        }
+
+        let channel = match notification_routing().channel(&level) {
+            // Toasts need the main window (absent in tray-only mode)
+            NotificationChannel::Toast
+                if self.app_handle.get_webview_window(MAIN_WINDOW).is_none() =>
+            {
+                NotificationChannel::System
+            }
+            channel => channel,
+        };
+        match channel {
+            NotificationChannel::System => self.show_system_notification(&level, message).await,
+            NotificationChannel::Toast => emit_to_main(
+                &self.app_handle,
+                "notification-toast",
+                serde_json::json!({
+                    "level": level,
+                    "message": message,
+                    "duration_ms": level.default_duration().as_millis() as u64,
+                }),
+            )
+            .map_err(|e| NotifierError::SendFailed {
+                message: format!("Failed to show toast: {}", e),
+            }),
+            NotificationChannel::Sound => {
+                tokio::task::spawn_blocking(move || playback::play(chime(&level)).map(|_| ()))
+                    .await
+                    .map_err(|e| e.to_string())
+                    .and_then(|result| result.map_err(|e| e.to_string()))
+                    .map_err(|message| NotifierError::SendFailed { message })
+            }
+            NotificationChannel::Silent => Ok(()),
+        }
+    }
+}
+
 /// Mock notifier service for testing
 pub struct MockNotifierService {
     should_fail: bool,
@@ -263,6 +416,34 @@ mod tests {
         assert_eq!(NotificationLevel::Error.accessibility_label(), "Error: ");
     }
 
+    #[test]
+    fn test_notification_routing() {
+        let routing: NotificationRouting =
+            serde_json::from_str(r#"{ "success": "none", "error": "sound" }"#).unwrap();
+        assert_eq!(
+            routing.channel(&NotificationLevel::Success),
+            NotificationChannel::Silent
+        );
+        assert_eq!(
+            routing.channel(&NotificationLevel::Error),
+            NotificationChannel::Sound
+        );
+        // Levels left out keep their default channel
+        assert_eq!(
+            routing.channel(&NotificationLevel::Warning),
+            NotificationChannel::System
+        );
+        assert_eq!(
+            routing.channel(&NotificationLevel::Progress),
+            NotificationChannel::Toast
+        );
+        assert_eq!(
+            serde_json::to_value(routing).unwrap()["success"],
+            serde_json::json!("none")
+        );
+        assert_eq!(chime(&NotificationLevel::Error).channels, 1);
+    }
+
     #[tokio::test]
     async fn test_mock_notify_success() {
         let notifier = MockNotifierService::new();
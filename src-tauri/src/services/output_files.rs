@@ -0,0 +1,351 @@
+//! Output file naming from user-defined filename templates
+//!
+//! File-based outputs (and later archives) are named from a template such as
+//! `{date}_{profile}_{title}.md`. Templates are parsed when settings are
+//! saved, so an unknown variable or an unclosed brace is reported to the user
+//! right away instead of failing when a workflow completes. Variable values
+//! are sanitized for every supported OS and existing files are never
+//! overwritten: a numeric suffix is appended on collision.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use std::path::Path;
+//! use dicta_clerk_lib::services::{FilenameContext, FilenameTemplate};
+//!
+//! let template = FilenameTemplate::parse("{date}_{profile}_{title}.md").unwrap();
+//! let context = FilenameContext::now("Concise Messages", "Meeting notes for Monday");
+//!
+//! let path = template.write_unique(Path::new("/tmp/dictations"), &context, "text").unwrap();
+//! println!("Saved to {}", path.display());
+//! ```
+
+use chrono::{DateTime, Local};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Default template for saved transcripts
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "{date}_{profile}_{title}.md";
+
+/// Words of the text used for `{title}`
+const TITLE_MAX_WORDS: usize = 6;
+
+/// Longest file name produced (most file systems allow 255 bytes)
+const MAX_FILENAME_CHARS: usize = 200;
+
+/// Errors found while parsing a filename template
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FilenameTemplateError {
+    #[error("Filename template is empty")]
+    Empty,
+    #[error("Unknown variable {{{name}}} (supported: {{date}}, {{time}}, {{profile}}, {{title}})")]
+    UnknownVariable { name: String },
+    #[error("Unclosed '{{' in filename template")]
+    UnclosedBrace,
+    #[error("Unexpected '}}' in filename template")]
+    UnexpectedBrace,
+    #[error("Invalid character {character:?} in filename template")]
+    InvalidCharacter { character: char },
+    #[error("Filename template must contain at least one variable")]
+    NoVariables,
+}
+
+/// A piece of a parsed template
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Variable(Variable),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Variable {
+    Date,
+    Time,
+    Profile,
+    Title,
+}
+
+impl Variable {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "date" => Some(Self::Date),
+            "time" => Some(Self::Time),
+            "profile" => Some(Self::Profile),
+            "title" => Some(Self::Title),
+            _ => None,
+        }
+    }
+}
+
+/// Values substituted into a template
+#[derive(Debug, Clone)]
+pub struct FilenameContext {
+    /// Time of the workflow (`{date}` = YYYY-MM-DD, `{time}` = HH-MM-SS)
+    pub timestamp: DateTime<Local>,
+    /// Name of the profile used
+    pub profile: String,
+    /// Short title, usually the first words of the text
+    pub title: String,
+}
+
+impl FilenameContext {
+    /// Context for the current time with a title taken from `text`
+    pub fn now(profile: &str, text: &str) -> Self {
+        Self {
+            timestamp: Local::now(),
+            profile: profile.to_string(),
+            title: title_from_text(text),
+        }
+    }
+}
+
+/// A validated filename template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilenameTemplate {
+    segments: Vec<Segment>,
+}
+
+impl FilenameTemplate {
+    /// Parse and validate a template
+    pub fn parse(template: &str) -> Result<Self, FilenameTemplateError> {
+        if template.trim().is_empty() {
+            return Err(FilenameTemplateError::Empty);
+        }
+
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some('{') | None => return Err(FilenameTemplateError::UnclosedBrace),
+                            Some(c) => name.push(c),
+                        }
+                    }
+                    let variable = Variable::from_name(name.trim()).ok_or_else(|| {
+                        FilenameTemplateError::UnknownVariable { name: name.clone() }
+                    })?;
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Variable(variable));
+                }
+                '}' => return Err(FilenameTemplateError::UnexpectedBrace),
+                c if is_forbidden(c) => {
+                    return Err(FilenameTemplateError::InvalidCharacter { character: c })
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        if !segments
+            .iter()
+            .any(|segment| matches!(segment, Segment::Variable(_)))
+        {
+            return Err(FilenameTemplateError::NoVariables);
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// File name for the given context (sanitized, without collision handling)
+    pub fn render(&self, context: &FilenameContext) -> String {
+        let mut name = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => name.push_str(text),
+                Segment::Variable(Variable::Date) => {
+                    name.push_str(&context.timestamp.format("%Y-%m-%d").to_string())
+                }
+                Segment::Variable(Variable::Time) => {
+                    name.push_str(&context.timestamp.format("%H-%M-%S").to_string())
+                }
+                Segment::Variable(Variable::Profile) => {
+                    name.push_str(&sanitize_component(&context.profile))
+                }
+                Segment::Variable(Variable::Title) => {
+                    name.push_str(&sanitize_component(&context.title))
+                }
+            }
+        }
+
+        let name = truncate_keeping_extension(name.trim().trim_end_matches('.'));
+        if name.is_empty() || name.starts_with('.') {
+            format!("untitled{}", name)
+        } else {
+            name
+        }
+    }
+
+    /// Write `content` to a new file in `dir`, appending `-2`, `-3`, ... on collision
+    pub fn write_unique(
+        &self,
+        dir: &Path,
+        context: &FilenameContext,
+        content: &str,
+    ) -> std::io::Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        let file_name = self.render(context);
+
+        for attempt in 1u32.. {
+            let path = dir.join(numbered_name(&file_name, attempt));
+            // create_new fails on existing files, so concurrent workflows cannot clobber each other
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    file.write_all(content.as_bytes())?;
+                    file.sync_all()?;
+                    return Ok(path);
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        unreachable!("collision counter exhausted")
+    }
+}
+
+/// First words of `text`, used for `{title}`
+pub fn title_from_text(text: &str) -> String {
+    text.split_whitespace()
+        .take(TITLE_MAX_WORDS)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Characters that are not allowed in file names on at least one OS
+fn is_forbidden(c: char) -> bool {
+    matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') || c.is_control()
+}
+
+/// Make a variable value safe for a file name
+fn sanitize_component(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .filter(|c| !c.is_ascii_punctuation() || matches!(c, '-' | '_' | '\''))
+        .map(|c| if c.is_whitespace() { ' ' } else { c })
+        .filter(|c| !is_forbidden(*c))
+        .collect();
+
+    cleaned.split_whitespace().collect::<Vec<_>>().join("-")
+}
+
+/// Limit the name length without cutting off the extension
+fn truncate_keeping_extension(name: &str) -> String {
+    if name.chars().count() <= MAX_FILENAME_CHARS {
+        return name.to_string();
+    }
+
+    let (stem, extension) = split_extension(name);
+    let keep = MAX_FILENAME_CHARS.saturating_sub(extension.chars().count());
+    let stem: String = stem.chars().take(keep).collect();
+    format!("{}{}", stem.trim_end_matches(['-', '_', ' ']), extension)
+}
+
+/// Name for the given collision attempt (`notes.md`, `notes-2.md`, ...)
+fn numbered_name(file_name: &str, attempt: u32) -> String {
+    if attempt == 1 {
+        return file_name.to_string();
+    }
+    let (stem, extension) = split_extension(file_name);
+    format!("{}-{}{}", stem, attempt, extension)
+}
+
+/// Split `name` into stem and extension (including the dot)
+fn split_extension(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(index) if index > 0 => name.split_at(index),
+        _ => (name, ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn context(profile: &str, title: &str) -> FilenameContext {
+        FilenameContext {
+            timestamp: Local.with_ymd_and_hms(2025, 3, 14, 9, 26, 53).unwrap(),
+            profile: profile.to_string(),
+            title: title.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_templates() {
+        assert_eq!(
+            FilenameTemplate::parse("  "),
+            Err(FilenameTemplateError::Empty)
+        );
+        assert_eq!(
+            FilenameTemplate::parse("{date}_{author}.md"),
+            Err(FilenameTemplateError::UnknownVariable {
+                name: "author".to_string()
+            })
+        );
+        assert_eq!(
+            FilenameTemplate::parse("{date.md"),
+            Err(FilenameTemplateError::UnclosedBrace)
+        );
+        assert_eq!(
+            FilenameTemplate::parse("date}.md"),
+            Err(FilenameTemplateError::UnexpectedBrace)
+        );
+        assert_eq!(
+            FilenameTemplate::parse("notes/{date}.md"),
+            Err(FilenameTemplateError::InvalidCharacter { character: '/' })
+        );
+        assert_eq!(
+            FilenameTemplate::parse("notes.md"),
+            Err(FilenameTemplateError::NoVariables)
+        );
+    }
+
+    #[test]
+    fn test_render_sanitizes_variables() {
+        let template = FilenameTemplate::parse(DEFAULT_FILENAME_TEMPLATE).unwrap();
+        let name = template.render(&context("Email: Work/Home", "Hi Bob, see you at 5?"));
+        assert_eq!(name, "2025-03-14_Email-WorkHome_Hi-Bob-see-you-at-5.md");
+
+        let template = FilenameTemplate::parse("{title}.txt").unwrap();
+        assert_eq!(template.render(&context("p", "...")), "untitled.txt");
+
+        let long_title = "word ".repeat(100);
+        let name = template.render(&context("p", &long_title));
+        assert!(name.chars().count() <= MAX_FILENAME_CHARS);
+        assert!(name.ends_with(".txt"));
+    }
+
+    #[test]
+    fn test_write_unique_appends_counter_on_collision() {
+        let temp_dir = TempDir::new().unwrap();
+        let template = FilenameTemplate::parse("{date}_{time}.md").unwrap();
+        let context = context("p", "t");
+
+        let first = template
+            .write_unique(temp_dir.path(), &context, "first")
+            .unwrap();
+        let second = template
+            .write_unique(temp_dir.path(), &context, "second")
+            .unwrap();
+
+        assert_eq!(first.file_name().unwrap(), "2025-03-14_09-26-53.md");
+        assert_eq!(second.file_name().unwrap(), "2025-03-14_09-26-53-2.md");
+        assert_eq!(std::fs::read_to_string(first).unwrap(), "first");
+        assert_eq!(std::fs::read_to_string(second).unwrap(), "second");
+    }
+}
@@ -0,0 +1,183 @@
+//! PairingStore service for remote-control pairing tokens
+//!
+//! A phone (or any LAN browser) is paired by scanning a QR code that encodes
+//! the local API URL together with a random token. Tokens are persisted so
+//! paired devices keep working across restarts until they are revoked.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use std::path::PathBuf;
+//! use dicta_clerk_lib::services::PairingStore;
+//!
+//! let store = PairingStore::new(PathBuf::from("remote_pairings.json"));
+//! let pairing = store.create(Some("My phone".to_string())).unwrap();
+//!
+//! assert!(store.verify(&pairing.token).unwrap());
+//! store.revoke_all().unwrap();
+//! ```
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+/// File name of the pairing list inside the config directory
+pub const PAIRINGS_FILE: &str = "remote_pairings.json";
+
+/// Number of random bytes in a pairing token (hex encoded)
+const TOKEN_BYTES: usize = 16;
+
+/// Errors that can occur while persisting pairing tokens
+#[derive(Error, Debug)]
+pub enum PairingError {
+    #[error("IO error on {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Invalid pairings file: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Result type for pairing operations
+pub type PairingResult<T> = Result<T, PairingError>;
+
+/// A paired remote device
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Pairing {
+    /// Secret token presented by the device
+    pub token: String,
+    /// Optional user-facing label
+    pub label: Option<String>,
+    /// Unix timestamp (seconds) of the pairing
+    pub created_at: u64,
+}
+
+/// Persistent list of pairing tokens backed by a JSON file
+#[derive(Debug, Clone)]
+pub struct PairingStore {
+    path: PathBuf,
+}
+
+impl PairingStore {
+    /// Create a store backed by the given JSON file
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Create a store in the OS config directory
+    pub fn default_location() -> Self {
+        Self::new(crate::paths::config_dir().join(PAIRINGS_FILE))
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// List all pairings
+    pub fn list(&self) -> PairingResult<Vec<Pairing>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(&self.path).map_err(|e| self.io_error(e))?;
+        if content.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Create and persist a new pairing with a random token
+    pub fn create(&self, label: Option<String>) -> PairingResult<Pairing> {
+        let bytes: [u8; TOKEN_BYTES] = rand::thread_rng().gen();
+        let pairing = Pairing {
+            token: bytes.iter().map(|b| format!("{:02x}", b)).collect(),
+            label,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        };
+
+        let mut pairings = self.list()?;
+        pairings.push(pairing.clone());
+        self.save(&pairings)?;
+
+        Ok(pairing)
+    }
+
+    /// Check whether a token belongs to a paired device
+    pub fn verify(&self, token: &str) -> PairingResult<bool> {
+        Ok(self
+            .list()?
+            .iter()
+            .any(|pairing| constant_time_eq(pairing.token.as_bytes(), token.as_bytes())))
+    }
+
+    /// Revoke every pairing
+    pub fn revoke_all(&self) -> PairingResult<usize> {
+        let count = self.list()?.len();
+        self.save(&[])?;
+        Ok(count)
+    }
+
+    /// Atomically write the list to disk
+    fn save(&self, pairings: &[Pairing]) -> PairingResult<()> {
+        let parent_dir = self.path.parent().unwrap_or_else(|| Path::new("."));
+        if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
+            std::fs::create_dir_all(parent_dir).map_err(|e| self.io_error(e))?;
+        }
+
+        let content = serde_json::to_string_pretty(pairings)?;
+        let mut temp_file = NamedTempFile::new_in(parent_dir).map_err(|e| self.io_error(e))?;
+        temp_file
+            .write_all(content.as_bytes())
+            .map_err(|e| self.io_error(e))?;
+        temp_file
+            .persist(&self.path)
+            .map_err(|e| self.io_error(e.error))?;
+
+        Ok(())
+    }
+
+    fn io_error(&self, source: std::io::Error) -> PairingError {
+        PairingError::Io {
+            path: self.path.to_string_lossy().to_string(),
+            source,
+        }
+    }
+}
+
+/// Compare secrets without short-circuiting on the first mismatch
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_verify_and_revoke() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = PairingStore::new(temp_dir.path().join(PAIRINGS_FILE));
+
+        let pairing = store.create(Some("Phone".to_string())).unwrap();
+        assert_eq!(pairing.token.len(), TOKEN_BYTES * 2);
+
+        let reloaded = PairingStore::new(temp_dir.path().join(PAIRINGS_FILE));
+        assert!(reloaded.verify(&pairing.token).unwrap());
+        assert!(!reloaded.verify("not-a-token").unwrap());
+        assert!(!reloaded.verify("").unwrap());
+
+        assert_eq!(reloaded.revoke_all().unwrap(), 1);
+        assert!(!store.verify(&pairing.token).unwrap());
+    }
+}
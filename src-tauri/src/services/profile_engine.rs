@@ -6,12 +6,13 @@
 //! ## Usage Example
 //!
 //! ```rust,no_run
-//! use dicta_clerk_lib::services::{ProfileEngine, Profile};
+//! use dicta_clerk_lib::services::{ProfileEngine, Profile, ProfileKind};
 //!
 //! let engine = ProfileEngine::new();
 //! let profile = Profile {
 //!     id: "medical".to_string(),
 //!     name: "Medical Transcription".to_string(),
+//!     kind: Some(ProfileKind::Formatting),
 //!     description: Some("Medical profile".to_string()),
 //!     prompt: Some("Format as medical report".to_string()),
 //!     example_input: Some("patient has fever".to_string()),
@@ -19,6 +20,14 @@
 //!     active: true,
 //!     visible: Some(true),
 //!     shortcut: Some("Ctrl+Alt+M".to_string()),
+//!     input_device: None,
+//!     timestamps: None,
+//!     whisper_mode: None,
+//!     mixed_language: None,
+//!     history_retention_hours: None,
+//!     reference_file: None,
+//!     keywords: None,
+//!     locked: None,
 //!     created_at: "2025-01-01T00:00:00Z".to_string(),
 //!     updated_at: "2025-01-01T00:00:00Z".to_string(),
 //! };
@@ -30,6 +39,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
 
+use crate::services::mixed_language::MixedLanguageMode;
+use crate::services::transcript_format::TimestampMode;
+use crate::services::whisper_client::WhisperMode;
+
 /// Configuration for ProfileEngine
 #[derive(Debug, Clone)]
 pub struct ProfileEngineConfig {
@@ -48,6 +61,22 @@ impl Default for ProfileEngineConfig {
     }
 }
 
+/// ID of the clipboard profile in profiles written before profile kinds
+pub const LEGACY_CLIPBOARD_PROFILE_ID: &str = "1";
+
+/// What a profile does with the transcript
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileKind {
+    /// Copy the transcript as is (built in, exactly one)
+    Clipboard,
+    /// Rewrite the transcript following the prompt
+    #[default]
+    Formatting,
+    /// Pull information out of the transcript (action items, fields...)
+    Extraction,
+}
+
 /// Profile structure with strict validation for different profile types
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Profile {
@@ -55,6 +84,9 @@ pub struct Profile {
     pub id: String,
     /// Human-readable profile name
     pub name: String,
+    /// What the profile does (None in profiles written before profile kinds,
+    /// see [`migrate_profile_kinds`])
+    pub kind: Option<ProfileKind>,
     /// Profile description
     pub description: Option<String>,
     /// Profile instruction/prompt for formatting (must be None for clipboard profiles)
@@ -69,6 +101,24 @@ pub struct Profile {
     pub visible: Option<bool>,
     /// Optional keyboard shortcut for quick profile selection
     pub shortcut: Option<String>,
+    /// Optional input device pinned for this profile (falls back to the default device)
+    pub input_device: Option<String>,
+    /// Timestamps in the final text (None = the global `output.timestamps` setting)
+    pub timestamps: Option<TimestampMode>,
+    /// Transcribe or translate to English (None = the global `whisper.mode` setting)
+    pub whisper_mode: Option<WhisperMode>,
+    /// Code-switched speech: segments transcribed again in their own language
+    /// (None = one language per recording)
+    pub mixed_language: Option<MixedLanguageMode>,
+    /// Hours this profile's history entries are kept, 0 = forever
+    /// (None = the global `history.retention_days` setting)
+    pub history_retention_hours: Option<u32>,
+    /// Optional text file (style guide, prior thread...) given to GPT as reference
+    pub reference_file: Option<String>,
+    /// Words or phrases in a transcript that suggest this profile
+    pub keywords: Option<Vec<String>>,
+    /// Distributed by an admin: cannot be edited or deleted locally, only hidden
+    pub locked: Option<bool>,
     /// Profile creation timestamp
     pub created_at: String,
     /// Profile last update timestamp
@@ -77,7 +127,7 @@ pub struct Profile {
 
 /// Trait to define profile behavior based on profile type
 pub trait ProfileBehavior {
-    /// Check if this is a clipboard profile (ID "1")
+    /// Check if this is the clipboard profile (`clipboard` kind)
     fn is_clipboard_profile(&self) -> bool;
 
     /// Check if this profile should use GPT formatting
@@ -89,7 +139,7 @@ pub trait ProfileBehavior {
 
 impl ProfileBehavior for Profile {
     fn is_clipboard_profile(&self) -> bool {
-        self.id == "1"
+        self.kind == Some(ProfileKind::Clipboard)
     }
 
     fn should_use_gpt_formatting(&self) -> bool {
@@ -102,7 +152,7 @@ impl ProfileBehavior for Profile {
         if self.is_clipboard_profile() {
             // Clipboard profile constraints
             if self.name != "Clipboard" {
-                return Err("Clipboard profile (ID='1') must be named 'Clipboard'".to_string());
+                return Err("Clipboard profile must be named 'Clipboard'".to_string());
             }
             if self.prompt.is_some() {
                 return Err("Clipboard profile cannot have a prompt".to_string());
@@ -126,7 +176,7 @@ impl ProfileBehavior for Profile {
 }
 
 /// Collection of profiles with default selection
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ProfileCollection {
     /// List of available profiles
     pub profiles: Vec<Profile>,
@@ -159,6 +209,9 @@ pub enum ProfileError {
 
     #[error("Shortcut conflict")]
     ShortcutConflict,
+
+    #[error("Profile '{name}' is locked: it cannot be {action}, only hidden")]
+    LockedProfile { name: String, action: &'static str },
 }
 
 /// Result type for profile operations
@@ -358,6 +411,42 @@ impl ProfileEngine {
         Ok(())
     }
 
+    /// Validate that saving `updated` over `current` keeps every locked profile
+    /// of `current` as is, apart from its visibility and selection
+    pub fn validate_locked_profiles(
+        &self,
+        current: &ProfileCollection,
+        updated: &ProfileCollection,
+    ) -> ProfileResult<()> {
+        for locked in current
+            .profiles
+            .iter()
+            .filter(|profile| profile.is_locked())
+        {
+            let Some(profile) = updated
+                .profiles
+                .iter()
+                .find(|profile| profile.id == locked.id)
+            else {
+                return Err(ProfileError::LockedProfile {
+                    name: locked.name.clone(),
+                    action: "deleted",
+                });
+            };
+
+            let mut edited = profile.clone();
+            edited.visible = locked.visible;
+            edited.active = locked.active;
+            if &edited != locked {
+                return Err(ProfileError::LockedProfile {
+                    name: locked.name.clone(),
+                    action: "edited",
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Validate shortcut conflicts within a profile collection and against global shortcut
     pub fn validate_shortcut_conflicts(
         &self,
@@ -397,6 +486,11 @@ impl Default for ProfileEngine {
 
 /// Helper constructors for profiles
 impl Profile {
+    /// Whether this profile is locked (read-only)
+    pub fn is_locked(&self) -> bool {
+        self.locked.unwrap_or(false)
+    }
+
     /// Set profile name (mutable)
     pub fn set_name(&mut self, name: String) {
         self.name = name;
@@ -418,47 +512,76 @@ impl Profile {
     }
 }
 
-/// Ensure clipboard profile (Profile 1) always exists in the system
-pub fn ensure_clipboard_profile(profiles: &mut Vec<Profile>) {
-    if !profiles.iter().any(|p| p.id == "1") {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        let timestamp = format!(
-            "2025-01-01T{:02}:{:02}:{:02}Z",
-            (now / 3600) % 24,
-            (now / 60) % 60,
-            now % 60
-        );
+/// Give a kind to the profiles written before profile kinds: the profile with
+/// the legacy clipboard ID becomes the clipboard profile, the others format
+pub fn migrate_profile_kinds(profiles: &mut [Profile]) {
+    for profile in profiles.iter_mut().filter(|p| p.kind.is_none()) {
+        profile.kind = Some(if profile.id == LEGACY_CLIPBOARD_PROFILE_ID {
+            ProfileKind::Clipboard
+        } else {
+            ProfileKind::Formatting
+        });
+    }
+}
 
-        let clipboard_profile = Profile {
-            id: "1".to_string(),
-            name: "Clipboard".to_string(),
-            description: Some(
-                "Copy transcription directly to clipboard without formatting".to_string(),
-            ),
-            prompt: None,
-            example_input: None,
-            example_output: None,
-            active: false,       // Not active by default, but always available
-            visible: Some(true), // Always visible as first profile
-            shortcut: None,
-            created_at: timestamp.clone(),
-            updated_at: timestamp,
-        };
+/// Ensure the clipboard profile always exists in the system (first in the
+/// list when it is created), migrating the profile kinds first
+pub fn ensure_clipboard_profile(profiles: &mut Vec<Profile>) {
+    migrate_profile_kinds(profiles);
+
+    if let Some(clipboard) = profiles.iter_mut().find(|p| p.is_clipboard_profile()) {
+        // Ensure the existing clipboard profile has the correct properties
+        clipboard.name = "Clipboard".to_string();
+        clipboard.description =
+            Some("Copy transcription directly to clipboard without formatting".to_string());
+        clipboard.visible = Some(true); // Always visible
+        return;
+    }
 
-        // Insert at the beginning to ensure it's Profile 1
-        profiles.insert(0, clipboard_profile);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let timestamp = format!(
+        "2025-01-01T{:02}:{:02}:{:02}Z",
+        (now / 3600) % 24,
+        (now / 60) % 60,
+        now % 60
+    );
+    // The legacy ID unless a formatting profile took it
+    let id = if profiles.iter().any(|p| p.id == LEGACY_CLIPBOARD_PROFILE_ID) {
+        "clipboard"
     } else {
-        // Ensure the existing Profile 1 has correct clipboard profile properties
-        if let Some(profile_1) = profiles.iter_mut().find(|p| p.id == "1") {
-            profile_1.name = "Clipboard".to_string();
-            profile_1.description =
-                Some("Copy transcription directly to clipboard without formatting".to_string());
-            profile_1.visible = Some(true); // Always visible
-        }
-    }
+        LEGACY_CLIPBOARD_PROFILE_ID
+    };
+
+    let clipboard_profile = Profile {
+        id: id.to_string(),
+        name: "Clipboard".to_string(),
+        kind: Some(ProfileKind::Clipboard),
+        description: Some(
+            "Copy transcription directly to clipboard without formatting".to_string(),
+        ),
+        prompt: None,
+        example_input: None,
+        example_output: None,
+        active: false,       // Not active by default, but always available
+        visible: Some(true), // Always visible as first profile
+        shortcut: None,
+        input_device: None,
+        timestamps: None,
+        whisper_mode: None,
+        mixed_language: None,
+        history_retention_hours: None,
+        reference_file: None,
+        keywords: None,
+        locked: None,
+        created_at: timestamp.clone(),
+        updated_at: timestamp,
+    };
+
+    // Insert at the beginning to ensure it's the first profile
+    profiles.insert(0, clipboard_profile);
 }
 
 #[cfg(test)]
@@ -469,6 +592,7 @@ mod tests {
         Profile {
             id: "test".to_string(),
             name: "Test Profile".to_string(),
+            kind: Some(ProfileKind::Formatting),
             description: Some("Test description".to_string()),
             prompt: Some("Please format this text: {{transcript}}".to_string()),
             example_input: None,
@@ -476,6 +600,14 @@ mod tests {
             active: true,
             visible: None,
             shortcut: None,
+            input_device: None,
+            timestamps: None,
+            whisper_mode: None,
+            mixed_language: None,
+            history_retention_hours: None,
+            reference_file: None,
+            keywords: None,
+            locked: None,
             created_at: "2025-01-01T00:00:00Z".to_string(),
             updated_at: "2025-01-01T00:00:00Z".to_string(),
         }
@@ -485,6 +617,7 @@ mod tests {
         Profile {
             id: "general".to_string(),
             name: "General Transcription".to_string(),
+            kind: Some(ProfileKind::Formatting),
             description: Some("Standard transcription".to_string()),
             prompt: None,
             example_input: None,
@@ -492,6 +625,14 @@ mod tests {
             active: true,
             visible: None,
             shortcut: None,
+            input_device: None,
+            timestamps: None,
+            whisper_mode: None,
+            mixed_language: None,
+            history_retention_hours: None,
+            reference_file: None,
+            keywords: None,
+            locked: None,
             created_at: "2025-01-01T00:00:00Z".to_string(),
             updated_at: "2025-01-01T00:00:00Z".to_string(),
         }
@@ -501,6 +642,7 @@ mod tests {
         Profile {
             id: "medical".to_string(),
             name: "Medical Profile".to_string(),
+            kind: Some(ProfileKind::Formatting),
             description: Some("Medical formatting".to_string()),
             prompt: Some("Format as medical report".to_string()),
             example_input: Some("patient has fever".to_string()),
@@ -508,6 +650,14 @@ mod tests {
             active: true,
             visible: None,
             shortcut: None,
+            input_device: None,
+            timestamps: None,
+            whisper_mode: None,
+            mixed_language: None,
+            history_retention_hours: None,
+            reference_file: None,
+            keywords: None,
+            locked: None,
             created_at: "2025-01-01T00:00:00Z".to_string(),
             updated_at: "2025-01-01T00:00:00Z".to_string(),
         }
@@ -668,6 +818,38 @@ mod tests {
         assert_eq!(profiles.profiles[0].id, "test");
     }
 
+    #[test]
+    fn test_load_profile_input_device_override() {
+        let engine = ProfileEngine::new();
+        let json = r#"{
+            "profiles": [
+                {
+                    "id": "calls",
+                    "name": "Calls",
+                    "active": true,
+                    "input_device": "USB Headset",
+                    "created_at": "2025-01-01T00:00:00Z",
+                    "updated_at": "2025-01-01T00:00:00Z"
+                },
+                {
+                    "id": "memos",
+                    "name": "Memos",
+                    "active": false,
+                    "created_at": "2025-01-01T00:00:00Z",
+                    "updated_at": "2025-01-01T00:00:00Z"
+                }
+            ],
+            "default_profile_id": "calls"
+        }"#;
+
+        let profiles = engine.load_profiles_from_json(json).unwrap();
+        assert_eq!(
+            profiles.profiles[0].input_device.as_deref(),
+            Some("USB Headset")
+        );
+        assert_eq!(profiles.profiles[1].input_device, None);
+    }
+
     #[test]
     fn test_find_profile_by_id() {
         let engine = ProfileEngine::new();
@@ -902,4 +1084,78 @@ mod tests {
             .validate_shortcut_conflicts(&profiles, Some("Ctrl+Shift+F9"))
             .is_ok());
     }
+
+    #[test]
+    fn test_validate_locked_profiles() {
+        let engine = ProfileEngine::new();
+        let mut locked = create_test_profile();
+        locked.locked = Some(true);
+        let current = ProfileCollection {
+            profiles: vec![locked.clone(), create_transcription_profile()],
+            default_profile_id: "test".to_string(),
+        };
+
+        // Hiding a locked profile and editing the others is allowed
+        let mut updated = current.clone();
+        updated.profiles[0].visible = Some(false);
+        updated.profiles[1].prompt = Some("Summarize".to_string());
+        assert!(engine.validate_locked_profiles(&current, &updated).is_ok());
+
+        let mut edited = current.clone();
+        edited.profiles[0].prompt = Some("Something else".to_string());
+        assert!(matches!(
+            engine.validate_locked_profiles(&current, &edited),
+            Err(ProfileError::LockedProfile {
+                action: "edited",
+                ..
+            })
+        ));
+
+        let mut unlocked = current.clone();
+        unlocked.profiles[0].locked = None;
+        assert!(engine
+            .validate_locked_profiles(&current, &unlocked)
+            .is_err());
+
+        let mut deleted = current.clone();
+        deleted.profiles.remove(0);
+        assert!(matches!(
+            engine.validate_locked_profiles(&current, &deleted),
+            Err(ProfileError::LockedProfile {
+                action: "deleted",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_ensure_clipboard_profile_migrates_kinds() {
+        let mut legacy_clipboard = create_transcription_profile();
+        legacy_clipboard.id = LEGACY_CLIPBOARD_PROFILE_ID.to_string();
+        legacy_clipboard.kind = None;
+        let mut legacy_formatting = create_test_profile();
+        legacy_formatting.kind = None;
+
+        let mut profiles = vec![legacy_formatting, legacy_clipboard];
+        ensure_clipboard_profile(&mut profiles);
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].kind, Some(ProfileKind::Formatting));
+        assert!(profiles[1].is_clipboard_profile());
+        assert_eq!(profiles[1].name, "Clipboard");
+
+        // The clipboard profile is found by kind, whatever its ID
+        let mut renamed = profiles.clone();
+        renamed[1].id = "copy".to_string();
+        ensure_clipboard_profile(&mut renamed);
+        assert_eq!(renamed.len(), 2);
+
+        // A formatting profile holding the legacy ID does not become the clipboard profile
+        let mut taken = vec![create_test_profile()];
+        taken[0].id = LEGACY_CLIPBOARD_PROFILE_ID.to_string();
+        ensure_clipboard_profile(&mut taken);
+        assert_eq!(taken.len(), 2);
+        assert_eq!(taken[0].id, "clipboard");
+        assert!(taken[0].is_clipboard_profile());
+        assert!(!taken[1].is_clipboard_profile());
+    }
 }
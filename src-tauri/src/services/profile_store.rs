@@ -0,0 +1,211 @@
+//! ProfileStore service caching the parsed profiles.json
+//!
+//! Every workflow needs the profiles (orchestrator, shortcut registration,
+//! reformatting), and parsing profiles.json each time is wasted work. The store
+//! keeps the last parsed collection and serves it while the file keeps the same
+//! modification time and size. Saving profiles invalidates it explicitly, so a
+//! save within the file system's timestamp granularity is not missed.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::profile_store::ProfileStore;
+//! use std::path::Path;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let store = ProfileStore::global();
+//! let profiles = store.get(Path::new("profiles.json")).await?;
+//! println!("{} profiles", profiles.profiles.len());
+//!
+//! // After writing profiles.json
+//! store.invalidate();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+use thiserror::Error;
+
+use crate::services::profile_engine::{
+    ensure_clipboard_profile, ProfileCollection, ProfileEngine, ProfileError,
+};
+
+/// Errors while loading profiles
+#[derive(Error, Debug)]
+pub enum ProfileStoreError {
+    #[error("Failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Failed to parse profiles: {0}")]
+    Parse(#[from] ProfileError),
+}
+
+pub type ProfileStoreResult<T> = Result<T, ProfileStoreError>;
+
+/// File state the cached collection was parsed from
+#[derive(Debug, Clone, PartialEq)]
+struct FileStamp {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+/// Cache of the parsed profiles, shared by commands and workflows
+#[derive(Debug, Default)]
+pub struct ProfileStore {
+    cached: Mutex<Option<(FileStamp, ProfileCollection)>>,
+}
+
+impl ProfileStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process-wide store
+    pub fn global() -> &'static ProfileStore {
+        static GLOBAL: OnceLock<ProfileStore> = OnceLock::new();
+        GLOBAL.get_or_init(ProfileStore::new)
+    }
+
+    /// Profiles of `path`, parsed again only when the file changed or the
+    /// cache was invalidated
+    pub async fn get(&self, path: &Path) -> ProfileStoreResult<ProfileCollection> {
+        let stamp = Self::stamp(path).await?;
+        let cached = self.cached.lock().ok().and_then(|cached| {
+            cached
+                .as_ref()
+                .filter(|(cached_stamp, _)| *cached_stamp == stamp)
+                .map(|(_, collection)| collection.clone())
+        });
+        match cached {
+            Some(collection) => Ok(collection),
+            None => self.reload(path).await,
+        }
+    }
+
+    /// Read and parse `path` again, replacing the cached profiles
+    pub async fn reload(&self, path: &Path) -> ProfileStoreResult<ProfileCollection> {
+        let stamp = Self::stamp(path).await?;
+        let content =
+            tokio::fs::read_to_string(path)
+                .await
+                .map_err(|source| ProfileStoreError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                })?;
+        let mut collection = ProfileEngine::new().load_profiles_from_json(&content)?;
+
+        // Ensure the clipboard profile always exists (migrating profile kinds)
+        ensure_clipboard_profile(&mut collection.profiles);
+
+        if let Ok(mut cached) = self.cached.lock() {
+            *cached = Some((stamp, collection.clone()));
+        }
+        Ok(collection)
+    }
+
+    /// Drop the cached profiles (e.g. after profiles.json was written)
+    pub fn invalidate(&self) {
+        if let Ok(mut cached) = self.cached.lock() {
+            *cached = None;
+        }
+    }
+
+    async fn stamp(path: &Path) -> ProfileStoreResult<FileStamp> {
+        let metadata = tokio::fs::metadata(path)
+            .await
+            .map_err(|source| ProfileStoreError::Io {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        Ok(FileStamp {
+            path: path.to_path_buf(),
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::profile_engine::{ProfileBehavior, ProfileKind};
+
+    fn profiles_json(name: &str) -> String {
+        serde_json::json!({
+            "profiles": [{
+                "id": "2",
+                "name": name,
+                "description": null,
+                "prompt": "Format as an email",
+                "example_input": null,
+                "example_output": null,
+                "active": true,
+                "visible": true,
+                "shortcut": null,
+                "created_at": "2025-01-01T00:00:00Z",
+                "updated_at": "2025-01-01T00:00:00Z"
+            }],
+            "default_profile_id": "2"
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_store_serves_cache_until_the_file_changes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("profiles.json");
+        std::fs::write(&path, profiles_json("Email")).unwrap();
+
+        let store = ProfileStore::new();
+        let profiles = store.get(&path).await.unwrap();
+        assert!(profiles
+            .profiles
+            .iter()
+            .any(|profile| profile.name == "Email"));
+        // The clipboard profile is always there
+        assert!(profiles
+            .profiles
+            .iter()
+            .any(|profile| profile.is_clipboard_profile()));
+        assert_eq!(profiles.profiles[1].kind, Some(ProfileKind::Formatting));
+
+        // A change the stamp does not show is not seen until invalidation
+        std::fs::write(&path, profiles_json("Mails")).unwrap();
+        let stamp = ProfileStore::stamp(&path).await.unwrap();
+        if let Some((cached_stamp, _)) = store.cached.lock().unwrap().as_mut() {
+            *cached_stamp = stamp;
+        }
+        let profiles = store.get(&path).await.unwrap();
+        assert!(profiles
+            .profiles
+            .iter()
+            .any(|profile| profile.name == "Email"));
+
+        // Invalidation and file changes (here its size) reload it
+        store.invalidate();
+        let profiles = store.get(&path).await.unwrap();
+        assert!(profiles
+            .profiles
+            .iter()
+            .any(|profile| profile.name == "Mails"));
+        std::fs::write(&path, profiles_json("Meeting notes")).unwrap();
+        let profiles = store.get(&path).await.unwrap();
+        assert!(profiles
+            .profiles
+            .iter()
+            .any(|profile| profile.name == "Meeting notes"));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            store.get(&path).await,
+            Err(ProfileStoreError::Io { .. })
+        ));
+    }
+}
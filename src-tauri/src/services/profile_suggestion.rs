@@ -0,0 +1,242 @@
+//! Keyword-based profile suggestion from the transcript content
+//!
+//! After transcription the transcript is matched against the keywords of each
+//! profile (its `keywords` list, or the significant words of its name and
+//! description when it has none). The profile with clearly more matches than
+//! the others is suggested; depending on the settings it is only offered to
+//! the user or applied to the workflow right away.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::profile_engine::{Profile, ProfileKind};
+//! use dicta_clerk_lib::services::profile_suggestion::suggest_profile;
+//!
+//! let email = Profile {
+//!     id: "2".to_string(),
+//!     name: "Email".to_string(),
+//!     kind: Some(ProfileKind::Formatting),
+//!     description: None,
+//!     prompt: Some("Format as an email".to_string()),
+//!     example_input: None,
+//!     example_output: None,
+//!     active: false,
+//!     visible: Some(true),
+//!     shortcut: None,
+//!     input_device: None,
+//!     timestamps: None,
+//!     whisper_mode: None,
+//!     mixed_language: None,
+//!     history_retention_hours: None,
+//!     reference_file: None,
+//!     keywords: Some(vec!["dear".to_string(), "regards".to_string()]),
+//!     locked: None,
+//!     created_at: "2025-01-01T00:00:00Z".to_string(),
+//!     updated_at: "2025-01-01T00:00:00Z".to_string(),
+//! };
+//!
+//! let suggestion = suggest_profile("dear Tom, see you Monday, best regards", &[email], 2).unwrap();
+//! assert_eq!(suggestion.profile_id, "2");
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::services::profile_engine::{Profile, ProfileBehavior};
+
+/// What happens with a suggested profile
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SuggestionMode {
+    /// No suggestion
+    #[default]
+    Off,
+    /// Emit the suggestion so the UI can offer a one-click switch
+    Suggest,
+    /// Apply the suggested profile to the workflow
+    Auto,
+}
+
+/// Profile suggested for a transcript
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProfileSuggestion {
+    pub profile_id: String,
+    pub profile_name: String,
+    /// Keywords found in the transcript
+    pub matched: Vec<String>,
+}
+
+/// Words too common to tell profiles apart
+const STOP_WORDS: &[&str] = &[
+    "about", "after", "also", "and", "for", "from", "into", "profile", "that", "the", "this",
+    "text", "with", "your",
+];
+
+/// Profile whose keywords the transcript matches at least `min_matches` times
+/// and strictly more than any other profile
+///
+/// The clipboard profile and profiles without a prompt are never suggested.
+pub fn suggest_profile(
+    transcript: &str,
+    profiles: &[Profile],
+    min_matches: usize,
+) -> Option<ProfileSuggestion> {
+    let words = normalized_words(transcript);
+
+    let mut scored: Vec<(usize, &Profile, Vec<String>)> = profiles
+        .iter()
+        .filter(|profile| {
+            !profile.is_clipboard_profile()
+                && profile
+                    .prompt
+                    .as_deref()
+                    .is_some_and(|p| !p.trim().is_empty())
+        })
+        .map(|profile| {
+            let mut hits = 0;
+            let mut matched = Vec::new();
+            for keyword in profile_keywords(profile) {
+                let count = count_phrase(&words, &normalized_words(&keyword));
+                if count > 0 {
+                    hits += count;
+                    matched.push(keyword);
+                }
+            }
+            (hits, profile, matched)
+        })
+        .collect();
+    scored.sort_by_key(|(hits, _, _)| std::cmp::Reverse(*hits));
+
+    let (hits, profile, matched) = scored.first()?;
+    let runner_up = scored.get(1).map_or(0, |(hits, _, _)| *hits);
+    (*hits >= min_matches.max(1) && *hits > runner_up).then(|| ProfileSuggestion {
+        profile_id: profile.id.clone(),
+        profile_name: profile.name.clone(),
+        matched: matched.clone(),
+    })
+}
+
+/// Keywords of a profile: its own list, or the significant words of its name and description
+fn profile_keywords(profile: &Profile) -> Vec<String> {
+    if let Some(keywords) = profile.keywords.as_ref().filter(|k| !k.is_empty()) {
+        return keywords
+            .iter()
+            .map(|keyword| keyword.trim().to_lowercase())
+            .filter(|keyword| !keyword.is_empty())
+            .collect();
+    }
+
+    let source = format!(
+        "{} {}",
+        profile.name,
+        profile.description.as_deref().unwrap_or_default()
+    );
+    let mut keywords: Vec<String> = Vec::new();
+    for word in normalized_words(&source) {
+        if word.chars().count() > 3
+            && !STOP_WORDS.contains(&word.as_str())
+            && !keywords.contains(&word)
+        {
+            keywords.push(word);
+        }
+    }
+    keywords
+}
+
+/// Lowercase words of `text`, without punctuation
+fn normalized_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|word| word.trim_matches('\'').to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Occurrences of the word sequence `phrase` in `words`
+fn count_phrase(words: &[String], phrase: &[String]) -> usize {
+    if phrase.is_empty() {
+        return 0;
+    }
+    words
+        .windows(phrase.len())
+        .filter(|window| *window == phrase)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::profile_engine::ProfileKind;
+
+    fn profile(id: &str, name: &str, description: &str, keywords: Option<&[&str]>) -> Profile {
+        Profile {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind: Some(if name == "Clipboard" {
+                ProfileKind::Clipboard
+            } else {
+                ProfileKind::Formatting
+            }),
+            description: Some(description.to_string()),
+            prompt: Some("Format the text".to_string()),
+            example_input: None,
+            example_output: None,
+            active: false,
+            visible: Some(true),
+            shortcut: None,
+            input_device: None,
+            timestamps: None,
+            whisper_mode: None,
+            mixed_language: None,
+            history_retention_hours: None,
+            reference_file: None,
+            keywords: keywords.map(|k| k.iter().map(|s| s.to_string()).collect()),
+            locked: None,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_suggests_profile_with_most_keyword_matches() {
+        let profiles = vec![
+            profile("1", "Clipboard", "Copy the meeting notes", None),
+            profile(
+                "2",
+                "Email",
+                "",
+                Some(&["dear", "best regards", "attached"]),
+            ),
+            profile("3", "Meeting notes", "Minutes of a meeting", None),
+        ];
+
+        let email = suggest_profile(
+            "Dear Anna, the report is attached. Best regards, Tom",
+            &profiles,
+            2,
+        )
+        .unwrap();
+        assert_eq!(email.profile_id, "2");
+        assert_eq!(email.matched, vec!["dear", "best regards", "attached"]);
+
+        let meeting = suggest_profile(
+            "In today's meeting we agreed on the minutes format",
+            &profiles,
+            2,
+        )
+        .unwrap();
+        assert_eq!(meeting.profile_id, "3");
+        assert_eq!(meeting.matched, vec!["meeting", "minutes"]);
+    }
+
+    #[test]
+    fn test_no_suggestion_below_threshold_or_on_ties() {
+        let profiles = vec![
+            profile("2", "Email", "", Some(&["dear"])),
+            profile("3", "Letter", "", Some(&["dear"])),
+        ];
+
+        assert_eq!(suggest_profile("Dear team", &profiles, 1), None);
+        assert_eq!(suggest_profile("Dear team", &profiles[..1], 2), None);
+        assert!(suggest_profile("Dear team", &profiles[..1], 1).is_some());
+        assert_eq!(suggest_profile("Dear team", &[], 1), None);
+    }
+}
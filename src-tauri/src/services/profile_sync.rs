@@ -0,0 +1,578 @@
+//! ProfileSync service sharing the profile collection with a team
+//!
+//! The profiles are pushed to and pulled from a shared location: a folder
+//! (Dropbox, network share...) or a git remote. Each sync is a three-way merge
+//! per profile between the local collection, the shared one and the collection
+//! of the last sync (the base): a side that did not change since the base
+//! takes the other side's version, and when both changed the most recently
+//! updated profile wins and the conflict is reported.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::profile_sync::{ProfileSync, SharedProfiles};
+//! use dicta_clerk_lib::services::ProfileCollection;
+//!
+//! # fn example(local: ProfileCollection) -> Result<(), Box<dyn std::error::Error>> {
+//! let sync = ProfileSync::new(
+//!     SharedProfiles::Folder("/mnt/team/dictaclerk".into()),
+//!     "profile_sync_base.json".into(),
+//! );
+//! let outcome = sync.sync(&local)?;
+//! println!("{} conflicts", outcome.conflicts.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use thiserror::Error;
+
+use crate::services::profile_engine::{
+    ensure_clipboard_profile, migrate_profile_kinds, Profile, ProfileBehavior, ProfileCollection,
+    ProfileEngine, ProfileError,
+};
+
+/// Name of the shared collection in the folder or repository
+pub const SHARED_PROFILES_FILE: &str = "profiles.json";
+
+/// Collection of the last sync, inside the state directory
+pub const SYNC_BASE_FILE: &str = "profile_sync_base.json";
+
+/// Local clone of the git remote, inside the data directory
+pub const GIT_WORKTREE_DIR: &str = "profile_sync";
+
+/// Errors while syncing profiles
+#[derive(Error, Debug)]
+pub enum ProfileSyncError {
+    #[error("Profile sync I/O error on {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid profiles in {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[error("git {command} failed: {message}")]
+    Git { command: String, message: String },
+
+    #[error("Merged profiles are invalid: {0}")]
+    InvalidMerge(#[from] ProfileError),
+}
+
+pub type ProfileSyncResult<T> = Result<T, ProfileSyncError>;
+
+/// Kind of shared location (`profile_sync.backend` setting)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileSyncBackend {
+    /// A folder synced by other means (Dropbox, network share...)
+    #[default]
+    Folder,
+    /// A git remote, cloned in the data directory
+    Git,
+}
+
+/// Side kept for a profile both sides changed since the last sync
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictResolution {
+    Local,
+    Remote,
+}
+
+/// A profile changed on both sides since the last sync
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProfileConflict {
+    pub profile_id: String,
+    pub profile_name: String,
+    pub kept: ConflictResolution,
+}
+
+/// Result of a three-way merge
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeOutcome {
+    pub merged: ProfileCollection,
+    pub conflicts: Vec<ProfileConflict>,
+}
+
+/// Three-way merge of profile collections, profile by profile
+///
+/// `base` is the collection of the last sync (None before the first one: a
+/// profile on one side only is then an addition, a profile differing on both
+/// sides a conflict). Local profile order comes first, profiles added remotely
+/// follow in their remote order.
+pub fn merge_profiles(
+    base: Option<&ProfileCollection>,
+    local: &ProfileCollection,
+    remote: &ProfileCollection,
+) -> MergeOutcome {
+    let find = |collection: Option<&ProfileCollection>, id: &str| -> Option<Profile> {
+        collection?
+            .profiles
+            .iter()
+            .find(|profile| profile.id == id)
+            .cloned()
+    };
+
+    let mut seen = HashSet::new();
+    let ids: Vec<String> = local
+        .profiles
+        .iter()
+        .chain(&remote.profiles)
+        .chain(
+            base.map(|base| base.profiles.as_slice())
+                .unwrap_or_default(),
+        )
+        .filter(|profile| seen.insert(profile.id.clone()))
+        .map(|profile| profile.id.clone())
+        .collect();
+
+    let mut profiles = Vec::new();
+    let mut conflicts = Vec::new();
+    for id in ids {
+        let base_profile = find(base, &id);
+        let local_profile = find(Some(local), &id);
+        let remote_profile = find(Some(remote), &id);
+
+        // The clipboard profile is built in: never synced
+        let built_in = [&local_profile, &remote_profile]
+            .into_iter()
+            .flatten()
+            .any(|profile| profile.is_clipboard_profile());
+        let kept = if built_in || local_profile == remote_profile || remote_profile == base_profile
+        {
+            local_profile
+        } else if local_profile == base_profile {
+            remote_profile
+        } else {
+            // Both sides changed: an edit wins over a deletion, then the most
+            // recent edit wins (RFC 3339 timestamps compare as strings)
+            let kept_side = match (&local_profile, &remote_profile) {
+                (Some(local), Some(remote)) if remote.updated_at > local.updated_at => {
+                    ConflictResolution::Remote
+                }
+                (None, Some(_)) => ConflictResolution::Remote,
+                _ => ConflictResolution::Local,
+            };
+            let (kept, other) = match kept_side {
+                ConflictResolution::Local => (local_profile, remote_profile),
+                ConflictResolution::Remote => (remote_profile, local_profile),
+            };
+            if let Some(profile) = kept.as_ref().or(other.as_ref()) {
+                conflicts.push(ProfileConflict {
+                    profile_id: id.clone(),
+                    profile_name: profile.name.clone(),
+                    kept: kept_side,
+                });
+            }
+            kept
+        };
+        profiles.extend(kept);
+    }
+    ensure_clipboard_profile(&mut profiles);
+
+    let base_default = base.map(|base| &base.default_profile_id);
+    let mut default_profile_id = if base_default == Some(&local.default_profile_id) {
+        remote.default_profile_id.clone()
+    } else {
+        local.default_profile_id.clone()
+    };
+    if !profiles
+        .iter()
+        .any(|profile| profile.id == default_profile_id)
+    {
+        if let Some(clipboard) = profiles
+            .iter()
+            .find(|profile| profile.is_clipboard_profile())
+        {
+            default_profile_id = clipboard.id.clone();
+        }
+    }
+
+    MergeOutcome {
+        merged: ProfileCollection {
+            profiles,
+            default_profile_id,
+        },
+        conflicts,
+    }
+}
+
+/// Shared location of the team's profiles
+#[derive(Debug, Clone, PartialEq)]
+pub enum SharedProfiles {
+    /// profiles.json in a shared folder
+    Folder(PathBuf),
+    /// profiles.json at the root of a branch of a git remote
+    Git {
+        remote: String,
+        branch: String,
+        worktree: PathBuf,
+    },
+}
+
+impl SharedProfiles {
+    /// Current shared collection (None when nothing was shared yet)
+    pub fn pull(&self) -> ProfileSyncResult<Option<ProfileCollection>> {
+        if let SharedProfiles::Git {
+            remote,
+            branch,
+            worktree,
+        } = self
+        {
+            if !git_fetch(remote, branch, worktree)? {
+                return Ok(None);
+            }
+        }
+        read_collection(&self.file())
+    }
+
+    /// Share `profiles`
+    pub fn push(&self, profiles: &ProfileCollection) -> ProfileSyncResult<()> {
+        write_collection(&self.file(), profiles)?;
+        if let SharedProfiles::Git {
+            branch, worktree, ..
+        } = self
+        {
+            git(worktree, &["add", SHARED_PROFILES_FILE])?;
+            let mut commit = git_identity_args(worktree);
+            commit
+                .extend(["commit", "--quiet", "-m", "Sync DictaClerk profiles"].map(String::from));
+            git(
+                worktree,
+                &commit.iter().map(String::as_str).collect::<Vec<_>>(),
+            )?;
+            git(
+                worktree,
+                &[
+                    "push",
+                    "--quiet",
+                    "origin",
+                    &format!("HEAD:refs/heads/{}", branch),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn file(&self) -> PathBuf {
+        match self {
+            SharedProfiles::Folder(folder) => folder.join(SHARED_PROFILES_FILE),
+            SharedProfiles::Git { worktree, .. } => worktree.join(SHARED_PROFILES_FILE),
+        }
+    }
+}
+
+/// Result of a sync
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncOutcome {
+    /// Collection both sides now hold
+    pub merged: ProfileCollection,
+    /// The local collection must be replaced by `merged`
+    pub local_changed: bool,
+    /// `merged` was pushed to the shared location
+    pub remote_changed: bool,
+    pub conflicts: Vec<ProfileConflict>,
+}
+
+/// Sync of the local profiles with a shared location
+#[derive(Debug, Clone)]
+pub struct ProfileSync {
+    shared: SharedProfiles,
+    base_file: PathBuf,
+}
+
+impl ProfileSync {
+    /// Sync with `shared`, keeping the last synced collection in `base_file`
+    pub fn new(shared: SharedProfiles, base_file: PathBuf) -> Self {
+        Self { shared, base_file }
+    }
+
+    /// Merge the local and shared collections and push the result when it
+    /// differs from the shared one (blocking: runs git and file I/O)
+    pub fn sync(&self, local: &ProfileCollection) -> ProfileSyncResult<SyncOutcome> {
+        let remote = self.shared.pull()?;
+        let base = read_collection(&self.base_file)?;
+
+        let MergeOutcome { merged, conflicts } = match &remote {
+            Some(remote) => merge_profiles(base.as_ref(), local, remote),
+            None => MergeOutcome {
+                merged: local.clone(),
+                conflicts: Vec::new(),
+            },
+        };
+
+        // Nothing is shared or kept as base unless it can be saved locally
+        ProfileEngine::new().validate_profiles_collection(&merged)?;
+
+        let remote_changed = remote.as_ref() != Some(&merged);
+        if remote_changed {
+            self.shared.push(&merged)?;
+        }
+        write_collection(&self.base_file, &merged)?;
+
+        Ok(SyncOutcome {
+            local_changed: &merged != local,
+            remote_changed,
+            merged,
+            conflicts,
+        })
+    }
+}
+
+fn read_collection(path: &Path) -> ProfileSyncResult<Option<ProfileCollection>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(source) => {
+            return Err(ProfileSyncError::Io {
+                path: path.to_path_buf(),
+                source,
+            })
+        }
+    };
+    // Collections shared before profile kinds compare equal once migrated
+    serde_json::from_str(&content)
+        .map(|mut collection: ProfileCollection| {
+            migrate_profile_kinds(&mut collection.profiles);
+            Some(collection)
+        })
+        .map_err(|source| ProfileSyncError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Write through a temporary file so readers of a shared folder never see a
+/// partial collection
+fn write_collection(path: &Path, profiles: &ProfileCollection) -> ProfileSyncResult<()> {
+    let io_error = |source| ProfileSyncError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(io_error)?;
+    }
+    let content =
+        serde_json::to_string_pretty(profiles).map_err(|source| ProfileSyncError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, content).map_err(io_error)?;
+    std::fs::rename(&temp_path, path).map_err(io_error)
+}
+
+/// Run git in `worktree`, returning its standard output
+fn git(worktree: &Path, args: &[&str]) -> ProfileSyncResult<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(worktree)
+        .args(args)
+        .output()
+        .map_err(|e| ProfileSyncError::Git {
+            command: args.first().copied().unwrap_or_default().to_string(),
+            message: e.to_string(),
+        })?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(ProfileSyncError::Git {
+            command: args.first().copied().unwrap_or_default().to_string(),
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        })
+    }
+}
+
+/// Update the local clone to the remote branch; false when the branch does
+/// not exist yet
+fn git_fetch(remote: &str, branch: &str, worktree: &Path) -> ProfileSyncResult<bool> {
+    if !worktree.join(".git").exists() {
+        std::fs::create_dir_all(worktree).map_err(|source| ProfileSyncError::Io {
+            path: worktree.to_path_buf(),
+            source,
+        })?;
+        git(worktree, &["init", "--quiet"])?;
+        git(worktree, &["remote", "add", "origin", remote])?;
+    } else {
+        git(worktree, &["remote", "set-url", "origin", remote])?;
+    }
+
+    match git(worktree, &["fetch", "--quiet", "origin", branch]) {
+        Ok(_) => {}
+        Err(ProfileSyncError::Git { message, .. })
+            if message.contains("couldn't find remote ref") =>
+        {
+            return Ok(false)
+        }
+        Err(e) => return Err(e),
+    }
+    git(
+        worktree,
+        &["checkout", "--quiet", "--force", "--detach", "FETCH_HEAD"],
+    )?;
+    Ok(true)
+}
+
+/// Commit identity for machines without a git identity configured
+fn git_identity_args(worktree: &Path) -> Vec<String> {
+    if git(worktree, &["config", "user.email"]).is_ok_and(|email| !email.trim().is_empty()) {
+        return Vec::new();
+    }
+    [
+        "-c",
+        "user.name=DictaClerk",
+        "-c",
+        "user.email=dictaclerk@localhost",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::profile_engine::ProfileKind;
+
+    fn profile(id: &str, prompt: &str, updated_at: &str) -> Profile {
+        Profile {
+            id: id.to_string(),
+            name: format!("Profile {}", id),
+            kind: Some(ProfileKind::Formatting),
+            description: None,
+            prompt: Some(prompt.to_string()),
+            example_input: None,
+            example_output: None,
+            active: false,
+            visible: Some(true),
+            shortcut: None,
+            input_device: None,
+            timestamps: None,
+            whisper_mode: None,
+            mixed_language: None,
+            history_retention_hours: None,
+            reference_file: None,
+            keywords: None,
+            locked: None,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: updated_at.to_string(),
+        }
+    }
+
+    fn collection(profiles: Vec<Profile>) -> ProfileCollection {
+        let mut profiles = profiles;
+        ensure_clipboard_profile(&mut profiles);
+        ProfileCollection {
+            profiles,
+            default_profile_id: "1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_merge_profiles_three_way() {
+        let t0 = "2025-01-01T00:00:00Z";
+        let base = collection(vec![
+            profile("2", "Email", t0),
+            profile("3", "Notes", t0),
+            profile("4", "Todo", t0),
+            profile("5", "Report", t0),
+        ]);
+        // Local: edits 2 and 5, deletes 4, adds 6
+        let local = collection(vec![
+            profile("2", "Formal email", "2025-02-01T00:00:00Z"),
+            profile("3", "Notes", t0),
+            profile("5", "Short report", "2025-02-01T00:00:00Z"),
+            profile("6", "Tweet", t0),
+        ]);
+        // Remote: edits 3 and 5 (later), keeps 4, adds 7
+        let remote = collection(vec![
+            profile("2", "Email", t0),
+            profile("3", "Meeting notes", "2025-02-02T00:00:00Z"),
+            profile("4", "Todo", t0),
+            profile("5", "Long report", "2025-02-03T00:00:00Z"),
+            profile("7", "Slack", t0),
+        ]);
+
+        let outcome = merge_profiles(Some(&base), &local, &remote);
+        let prompts: Vec<(&str, Option<&str>)> = outcome
+            .merged
+            .profiles
+            .iter()
+            .map(|profile| (profile.id.as_str(), profile.prompt.as_deref()))
+            .collect();
+        assert_eq!(
+            prompts,
+            vec![
+                ("1", None),
+                ("2", Some("Formal email")),
+                ("3", Some("Meeting notes")),
+                ("5", Some("Long report")),
+                ("6", Some("Tweet")),
+                ("7", Some("Slack")),
+            ]
+        );
+        assert_eq!(
+            outcome.conflicts,
+            vec![ProfileConflict {
+                profile_id: "5".to_string(),
+                profile_name: "Profile 5".to_string(),
+                kept: ConflictResolution::Remote,
+            }]
+        );
+
+        // Merging is stable once both sides hold the result
+        let again = merge_profiles(Some(&outcome.merged), &outcome.merged, &outcome.merged);
+        assert_eq!(again.merged, outcome.merged);
+        assert!(again.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_sync_with_shared_folder() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let shared = SharedProfiles::Folder(dir.path().join("team"));
+        let alice = ProfileSync::new(shared.clone(), dir.path().join("alice_base.json"));
+        let bob = ProfileSync::new(shared.clone(), dir.path().join("bob_base.json"));
+        let t0 = "2025-01-01T00:00:00Z";
+
+        // First sync shares the local collection as is
+        let alice_profiles = collection(vec![profile("2", "Email", t0)]);
+        let outcome = alice.sync(&alice_profiles).unwrap();
+        assert!(outcome.remote_changed);
+        assert!(!outcome.local_changed);
+        assert_eq!(shared.pull().unwrap(), Some(alice_profiles.clone()));
+
+        // Bob gets Alice's profile next to his own
+        let bob_profiles = collection(vec![profile("3", "Notes", t0)]);
+        let outcome = bob.sync(&bob_profiles).unwrap();
+        assert!(outcome.local_changed);
+        assert_eq!(outcome.merged.profiles.len(), 3);
+
+        // Alice deletes her profile: the deletion reaches the shared folder
+        let outcome = alice.sync(&outcome.merged).unwrap();
+        let without_email = collection(
+            outcome
+                .merged
+                .profiles
+                .into_iter()
+                .filter(|profile| profile.id != "2")
+                .collect(),
+        );
+        let outcome = alice.sync(&without_email).unwrap();
+        assert!(outcome.remote_changed);
+        assert!(!outcome.local_changed);
+        assert!(shared
+            .pull()
+            .unwrap()
+            .unwrap()
+            .profiles
+            .iter()
+            .all(|profile| profile.id != "2"));
+    }
+}
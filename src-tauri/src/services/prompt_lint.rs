@@ -0,0 +1,278 @@
+//! Prompt linting for formatting profiles
+//!
+//! Estimates how much of the formatting model's context window a profile's
+//! instructions take (prompt, examples and reference document, as sent by the
+//! GPT client) and flags instructions that contradict each other, such as
+//! "be concise" together with "include every detail". Oversized instructions
+//! are errors: the transcript would not fit. Everything else is a warning.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::prompt_lint::lint_profile;
+//! # let profile: dicta_clerk_lib::services::Profile = unimplemented!();
+//!
+//! let lint = lint_profile(&profile, "gpt-4o", None);
+//! for issue in &lint.issues {
+//!     println!("{:?}: {}", issue.severity, issue.message);
+//! }
+//! ```
+
+use serde::Serialize;
+
+use crate::services::context_memory::estimate_tokens;
+use crate::services::profile_engine::Profile;
+use crate::services::reference_context::{reference_section, REFERENCE_TOKEN_BUDGET};
+
+/// Context window assumed for unknown models
+pub const DEFAULT_CONTEXT_TOKENS: usize = 8_192;
+
+/// Share of the context the instructions may take before a warning (the rest
+/// is left for the transcript and the formatted output)
+const MAX_INSTRUCTIONS_SHARE: f64 = 0.5;
+
+/// Phrases that contradict each other when both appear in the instructions
+const CONFLICTING_PHRASES: [(&[&str], &[&str]); 4] = [
+    (
+        &[
+            "concise",
+            "brief",
+            "short",
+            "succinct",
+            "summarize",
+            "summarise",
+        ],
+        &[
+            "every detail",
+            "all details",
+            "all the details",
+            "verbatim",
+            "word for word",
+            "exhaustive",
+            "comprehensive",
+        ],
+    ),
+    (&["formal"], &["casual", "informal", "colloquial", "slang"]),
+    (
+        &["bullet points", "bullet list", "bulleted"],
+        &["prose", "one paragraph", "single paragraph"],
+    ),
+    (&["first person"], &["third person"]),
+];
+
+/// How serious a lint issue is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Warning,
+    /// The profile cannot work as configured
+    Error,
+}
+
+/// Problem found in a profile's instructions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintIssue {
+    pub severity: LintSeverity,
+    pub message: String,
+}
+
+/// Token estimate and issues of a profile's instructions
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PromptLint {
+    pub model: String,
+    pub context_tokens: usize,
+    pub prompt_tokens: usize,
+    /// Example input and output (only sent when both are set)
+    pub example_tokens: usize,
+    /// Reference section, cut to its budget
+    pub reference_tokens: usize,
+    pub total_tokens: usize,
+    pub issues: Vec<LintIssue>,
+}
+
+impl PromptLint {
+    /// Whether any issue is an error
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == LintSeverity::Error)
+    }
+}
+
+/// Context window of a chat model, in tokens
+pub fn context_window(model: &str) -> usize {
+    let model = model.to_ascii_lowercase();
+    if model.starts_with("gpt-4o") || model.starts_with("gpt-4-turbo") {
+        128_000
+    } else if model.starts_with("gpt-4-32k") {
+        32_768
+    } else if model.starts_with("gpt-4") {
+        8_192
+    } else if model.starts_with("gpt-3.5-turbo") {
+        16_385
+    } else {
+        DEFAULT_CONTEXT_TOKENS
+    }
+}
+
+/// Lowercase words of `text` separated by single spaces, padded with spaces
+/// so phrases only match whole words
+fn normalized_words(text: &str) -> String {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+/// First phrase of `phrases` found in `words`
+fn find_phrase<'a>(words: &str, phrases: &[&'a str]) -> Option<&'a str> {
+    phrases
+        .iter()
+        .find(|phrase| words.contains(&format!(" {} ", phrase)))
+        .copied()
+}
+
+/// Lint the instructions of `profile` for `model`, with the text of its
+/// reference document when it has one
+pub fn lint_profile(profile: &Profile, model: &str, reference: Option<&str>) -> PromptLint {
+    let prompt = profile.prompt.as_deref().unwrap_or_default();
+    let example_input = profile.example_input.as_deref().unwrap_or_default();
+    let example_output = profile.example_output.as_deref().unwrap_or_default();
+    let mut issues = Vec::new();
+    let mut warn = |message: String| {
+        issues.push(LintIssue {
+            severity: LintSeverity::Warning,
+            message,
+        })
+    };
+
+    let example_tokens = if example_input.is_empty() || example_output.is_empty() {
+        if !example_input.is_empty() || !example_output.is_empty() {
+            warn(
+                "Only one of the example input and output is set: the example is ignored"
+                    .to_string(),
+            );
+        }
+        0
+    } else {
+        estimate_tokens(example_input) + estimate_tokens(example_output)
+    };
+    let reference_tokens = reference
+        .and_then(|text| reference_section(text, REFERENCE_TOKEN_BUDGET))
+        .map_or(0, |section| estimate_tokens(&section));
+
+    let words = normalized_words(prompt);
+    for (first, second) in CONFLICTING_PHRASES {
+        if let (Some(a), Some(b)) = (find_phrase(&words, first), find_phrase(&words, second)) {
+            warn(format!("Conflicting instructions: \"{}\" and \"{}\"", a, b));
+        }
+    }
+
+    let prompt_tokens = estimate_tokens(prompt);
+    let total_tokens = prompt_tokens + example_tokens + reference_tokens;
+    let context_tokens = context_window(model);
+    if total_tokens > context_tokens {
+        issues.push(LintIssue {
+            severity: LintSeverity::Error,
+            message: format!(
+                "The instructions take about {} tokens, more than the {} tokens of {}",
+                total_tokens, context_tokens, model
+            ),
+        });
+    } else if total_tokens as f64 > context_tokens as f64 * MAX_INSTRUCTIONS_SHARE {
+        issues.push(LintIssue {
+            severity: LintSeverity::Warning,
+            message: format!(
+                "The instructions take about {} of the {} tokens of {}: long transcripts may not fit",
+                total_tokens, context_tokens, model
+            ),
+        });
+    }
+
+    PromptLint {
+        model: model.to_string(),
+        context_tokens,
+        prompt_tokens,
+        example_tokens,
+        reference_tokens,
+        total_tokens,
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::profile_engine::ProfileKind;
+
+    fn profile(prompt: &str, example_input: Option<&str>, example_output: Option<&str>) -> Profile {
+        Profile {
+            id: "2".to_string(),
+            name: "Email".to_string(),
+            kind: Some(ProfileKind::Formatting),
+            description: None,
+            prompt: Some(prompt.to_string()),
+            example_input: example_input.map(str::to_string),
+            example_output: example_output.map(str::to_string),
+            active: false,
+            visible: Some(true),
+            shortcut: None,
+            input_device: None,
+            timestamps: None,
+            whisper_mode: None,
+            mixed_language: None,
+            history_retention_hours: None,
+            reference_file: None,
+            keywords: None,
+            locked: None,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_lint_profile() {
+        let clean = lint_profile(
+            &profile("Format as a polite email.", Some("hi"), Some("Hello,")),
+            "gpt-4o",
+            None,
+        );
+        assert!(clean.issues.is_empty());
+        assert_eq!(clean.context_tokens, 128_000);
+        assert_eq!(clean.prompt_tokens, 7);
+        assert_eq!(clean.example_tokens, 3);
+        assert_eq!(clean.total_tokens, 10);
+
+        // Conflicts match whole words ("informal" does not contain "formal")
+        let conflicting = lint_profile(
+            &profile(
+                "Be concise but include every detail. Keep an informal tone.",
+                Some("hi"),
+                None,
+            ),
+            "gpt-4o",
+            Some("Style guide: sign with the team name."),
+        );
+        let messages: Vec<&str> = conflicting
+            .issues
+            .iter()
+            .map(|issue| issue.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Only one of the example input and output is set: the example is ignored",
+                "Conflicting instructions: \"concise\" and \"every detail\"",
+            ]
+        );
+        assert_eq!(conflicting.example_tokens, 0);
+        assert!(conflicting.reference_tokens > 0);
+        assert!(!conflicting.has_errors());
+
+        let oversized = lint_profile(&profile(&"word ".repeat(8_000), None, None), "gpt-4", None);
+        assert!(oversized.has_errors());
+        assert_eq!(context_window("unknown-model"), DEFAULT_CONTEXT_TOKENS);
+    }
+}
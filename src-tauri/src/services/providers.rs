@@ -0,0 +1,167 @@
+//! Provider registry and capability discovery
+//!
+//! Speech-to-text and LLM providers differ in what they support: Whisper
+//! returns timestamps and can translate, `gpt-4o-transcribe` streams but has
+//! no timestamps, a diarizing model labels speakers, and so on. The registry
+//! describes each configured provider so the frontend can enable or disable
+//! options from the reported features instead of hard-coding them.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::ProviderCapabilities;
+//!
+//! let whisper = ProviderCapabilities::speech_to_text(
+//!     "https://api.openai.com/v1/audio/transcriptions",
+//!     "whisper-1",
+//! );
+//! assert!(whisper.features.timestamps);
+//! assert!(!whisper.features.streaming);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// What a provider is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    /// Speech-to-text (transcription)
+    Stt,
+    /// Language model (formatting)
+    Llm,
+}
+
+/// Optional features a provider may support
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderFeatures {
+    /// Accepts a spoken-language hint
+    pub language_hint: bool,
+    /// Returns segment timestamps
+    pub timestamps: bool,
+    /// Streams partial results
+    pub streaming: bool,
+    /// Labels speakers
+    pub diarization: bool,
+    /// Translates to English (STT) or between languages (LLM)
+    pub translation: bool,
+}
+
+/// A configured provider and its features
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderCapabilities {
+    /// Stable identifier (e.g. "openai", "local")
+    pub id: String,
+    pub name: String,
+    pub kind: ProviderKind,
+    pub model: String,
+    pub features: ProviderFeatures,
+}
+
+impl ProviderCapabilities {
+    /// Capabilities of a cloud speech-to-text endpoint and model
+    pub fn speech_to_text(endpoint: &str, model: &str) -> Self {
+        let (id, name) = if endpoint.contains("api.openai.com") {
+            ("openai", "OpenAI")
+        } else if endpoint.contains("api.groq.com") {
+            ("groq", "Groq")
+        } else {
+            ("custom", "OpenAI-compatible endpoint")
+        };
+
+        let features = if model.starts_with("gpt-4o") && model.ends_with("-diarize") {
+            ProviderFeatures {
+                language_hint: true,
+                timestamps: true,
+                streaming: true,
+                diarization: true,
+                translation: false,
+            }
+        } else if model.starts_with("gpt-4o") {
+            ProviderFeatures {
+                language_hint: true,
+                streaming: true,
+                ..ProviderFeatures::default()
+            }
+        } else {
+            // Whisper models (whisper-1, whisper-large-v3, ...)
+            ProviderFeatures {
+                language_hint: true,
+                timestamps: true,
+                translation: true,
+                ..ProviderFeatures::default()
+            }
+        };
+
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind: ProviderKind::Stt,
+            model: model.to_string(),
+            features,
+        }
+    }
+
+    /// Capabilities of the local whisper.cpp backend
+    pub fn local_speech_to_text(model: &str) -> Self {
+        Self {
+            id: "local".to_string(),
+            name: "Local (whisper.cpp)".to_string(),
+            kind: ProviderKind::Stt,
+            model: model.to_string(),
+            features: ProviderFeatures {
+                language_hint: true,
+                timestamps: true,
+                // English-only models cannot translate
+                translation: !model.ends_with(".en"),
+                ..ProviderFeatures::default()
+            },
+        }
+    }
+
+    /// Capabilities of the OpenAI formatting model
+    pub fn formatting_llm(model: &str) -> Self {
+        Self {
+            id: "openai".to_string(),
+            name: "OpenAI".to_string(),
+            kind: ProviderKind::Llm,
+            model: model.to_string(),
+            features: ProviderFeatures {
+                language_hint: true,
+                translation: true,
+                ..ProviderFeatures::default()
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speech_to_text_features_follow_the_model() {
+        let whisper = ProviderCapabilities::speech_to_text(
+            "https://api.openai.com/v1/audio/transcriptions",
+            "whisper-1",
+        );
+        assert_eq!(whisper.id, "openai");
+        assert!(whisper.features.timestamps && whisper.features.translation);
+
+        let transcribe = ProviderCapabilities::speech_to_text(
+            "https://api.openai.com/v1/audio/transcriptions",
+            "gpt-4o-transcribe",
+        );
+        assert!(transcribe.features.streaming);
+        assert!(!transcribe.features.timestamps && !transcribe.features.diarization);
+
+        let groq = ProviderCapabilities::speech_to_text(
+            "https://api.groq.com/openai/v1/audio/transcriptions",
+            "whisper-large-v3",
+        );
+        assert_eq!(groq.id, "groq");
+
+        let local = ProviderCapabilities::local_speech_to_text("base.en");
+        assert!(local.features.timestamps);
+        assert!(!local.features.translation);
+    }
+}
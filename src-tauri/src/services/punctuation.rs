@@ -0,0 +1,141 @@
+//! Rule-based punctuation and casing restoration
+//!
+//! Some speech-to-text providers return lowercase text without punctuation.
+//! Without GPT formatting (e.g. with the clipboard profile) such transcripts
+//! are hard to use, so this stage restores the basics: every segment becomes
+//! a sentence that starts with a capital letter and ends with a period (or a
+//! question mark when it opens with an interrogative word), and the pronoun
+//! "i" is capitalized. Transcripts that already contain casing or
+//! punctuation are left alone.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::punctuation::{needs_restoration, restore_sentence};
+//!
+//! let text = "can you send me the report i think it is ready";
+//! assert!(needs_restoration(text));
+//! assert_eq!(
+//!     restore_sentence(text),
+//!     "Can you send me the report I think it is ready?"
+//! );
+//! ```
+
+use crate::services::whisper_client::TranscriptionSegment;
+
+/// Words per punctuation mark above which a transcript counts as unpunctuated
+const WORDS_PER_MARK: usize = 20;
+
+/// Words that open a question
+const QUESTION_WORDS: &[&str] = &[
+    "what", "why", "how", "who", "whom", "whose", "where", "when", "which", "is", "are", "am",
+    "was", "were", "do", "does", "did", "can", "could", "would", "should", "will", "shall", "may",
+    "have", "has",
+];
+
+/// Whether `text` looks like raw lowercase, unpunctuated provider output
+pub fn needs_restoration(text: &str) -> bool {
+    let words = text.split_whitespace().count();
+    if words < 3 || text.chars().any(char::is_uppercase) {
+        return false;
+    }
+    let marks = text
+        .chars()
+        .filter(|c| matches!(c, '.' | '!' | '?' | ',' | ';' | ':'))
+        .count();
+    marks * WORDS_PER_MARK < words
+}
+
+/// Capitalize and terminate one sentence
+pub fn restore_sentence(text: &str) -> String {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .enumerate()
+        .map(|(index, word)| {
+            if index == 0 || is_pronoun_i(word) {
+                capitalize(word)
+            } else {
+                word.to_string()
+            }
+        })
+        .collect();
+    let Some(first) = words.first() else {
+        return String::new();
+    };
+
+    let mut sentence = words.join(" ");
+    if !sentence.ends_with(['.', '!', '?', '…']) {
+        let is_question = QUESTION_WORDS.contains(&first.to_lowercase().as_str());
+        sentence.push(if is_question { '?' } else { '.' });
+    }
+    sentence
+}
+
+/// Restore punctuation and casing segment by segment
+///
+/// Each segment becomes (at least) one sentence; the leading space Whisper
+/// puts before segment texts is kept.
+pub fn restore_segments(segments: &mut [TranscriptionSegment]) {
+    for segment in segments.iter_mut() {
+        let restored = restore_sentence(&segment.text);
+        if !restored.is_empty() {
+            segment.text = format!(" {}", restored);
+        }
+    }
+}
+
+/// Whether `word` is the pronoun "i", possibly contracted ("i'm", "i've", ...)
+fn is_pronoun_i(word: &str) -> bool {
+    word == "i" || word.starts_with("i'") || word.starts_with("i’")
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_needs_restoration() {
+        assert!(needs_restoration("so we met with the client yesterday"));
+        assert!(!needs_restoration("So we met with the client yesterday."));
+        assert!(!needs_restoration("ok thanks"));
+        assert!(!needs_restoration("we met, they agreed. next steps follow"));
+    }
+
+    #[test]
+    fn test_restore_sentence() {
+        assert_eq!(
+            restore_sentence(" i'm sure i told them"),
+            "I'm sure I told them."
+        );
+        assert_eq!(
+            restore_sentence("where is the invoice"),
+            "Where is the invoice?"
+        );
+        assert_eq!(restore_sentence("done already."), "Done already.");
+        assert_eq!(restore_sentence("   "), "");
+    }
+
+    #[test]
+    fn test_restore_segments_keeps_leading_space() {
+        let mut segments = vec![TranscriptionSegment {
+            id: 0,
+            start: 0.0,
+            end: 2.0,
+            text: " should we ship it".to_string(),
+            avg_logprob: None,
+            compression_ratio: None,
+            no_speech_prob: None,
+        }];
+
+        restore_segments(&mut segments);
+        assert_eq!(segments[0].text, " Should we ship it?");
+    }
+}
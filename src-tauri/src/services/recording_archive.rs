@@ -0,0 +1,182 @@
+//! RecordingArchive service keeping recordings after their workflow
+//!
+//! The workflow deletes its temporary WAV once processed. When the recording
+//! archive is on, the recording is first copied to the archive directory
+//! (encoded to OGG, or as the original WAV) under a timestamped name, and the
+//! oldest archived recordings beyond the retention count are deleted.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::recording_archive::RecordingArchive;
+//! use std::path::Path;
+//!
+//! let archive = RecordingArchive::new("recordings".into(), 20);
+//! let archived = archive.archive(Path::new("recording.wav"))?;
+//! let pruned = archive.prune()?;
+//! println!("Kept {}, pruned {}", archived.display(), pruned.len());
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::audio::EncoderFormat;
+
+/// Default archive directory, inside the data directory
+pub const RECORDINGS_DIR: &str = "recordings";
+
+/// Name prefix of archived recordings (only these files are ever pruned)
+pub const ARCHIVE_FILE_PREFIX: &str = "recording_";
+
+/// Format recordings are archived in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveFormat {
+    /// OGG/Vorbis (small)
+    #[default]
+    Ogg,
+    /// MP3
+    Mp3,
+    /// FLAC (lossless, smaller than WAV)
+    Flac,
+    /// The original recording (lossless)
+    Wav,
+}
+
+impl ArchiveFormat {
+    /// Encoder format of the archive (None = the WAV is copied as is)
+    pub fn encoder_format(self) -> Option<EncoderFormat> {
+        match self {
+            Self::Ogg => Some(EncoderFormat::Ogg),
+            Self::Mp3 => Some(EncoderFormat::Mp3),
+            Self::Flac => Some(EncoderFormat::Flac),
+            Self::Wav => None,
+        }
+    }
+}
+
+/// Directory of archived recordings with a retention count
+#[derive(Debug, Clone)]
+pub struct RecordingArchive {
+    dir: PathBuf,
+    retention_count: usize,
+}
+
+impl RecordingArchive {
+    /// Create an archive in `dir` keeping the newest `retention_count`
+    /// recordings (0 = keep all)
+    pub fn new(dir: PathBuf, retention_count: u32) -> Self {
+        Self {
+            dir,
+            retention_count: retention_count as usize,
+        }
+    }
+
+    /// Directory of the archive
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Copy a recording into the archive under a timestamped name (with the
+    /// extension of `source`)
+    pub fn archive(&self, source: &Path) -> std::io::Result<PathBuf> {
+        std::fs::create_dir_all(&self.dir)?;
+        let extension = source
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or("wav");
+        let stem = format!(
+            "{}{}",
+            ARCHIVE_FILE_PREFIX,
+            Local::now().format("%Y-%m-%d_%H-%M-%S")
+        );
+
+        let mut target = self.dir.join(format!("{}.{}", stem, extension));
+        let mut counter = 2;
+        while target.exists() {
+            target = self.dir.join(format!("{}_{}.{}", stem, counter, extension));
+            counter += 1;
+        }
+        std::fs::copy(source, &target)?;
+        Ok(target)
+    }
+
+    /// Archived recordings, oldest first
+    pub fn list(&self) -> std::io::Result<Vec<PathBuf>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut recordings = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            let is_recording = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with(ARCHIVE_FILE_PREFIX));
+            if is_recording && entry.file_type()?.is_file() {
+                let modified = entry.metadata()?.modified()?;
+                recordings.push((modified, entry.path()));
+            }
+        }
+        recordings.sort();
+        Ok(recordings.into_iter().map(|(_, path)| path).collect())
+    }
+
+    /// Delete the oldest recordings beyond the retention count; returns the
+    /// deleted files
+    pub fn prune(&self) -> std::io::Result<Vec<PathBuf>> {
+        if self.retention_count == 0 {
+            return Ok(Vec::new());
+        }
+        let recordings = self.list()?;
+        let excess = recordings.len().saturating_sub(self.retention_count);
+        let mut pruned = Vec::with_capacity(excess);
+        for path in recordings.into_iter().take(excess) {
+            std::fs::remove_file(&path)?;
+            pruned.push(path);
+        }
+        Ok(pruned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_archive_keeps_the_newest_recordings() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("recording.ogg");
+        std::fs::write(&source, b"OggS").unwrap();
+        let archive = RecordingArchive::new(dir.path().join("archive"), 2);
+        assert!(archive.list().unwrap().is_empty());
+
+        let mut archived = Vec::new();
+        for age in [30, 20, 10] {
+            let path = archive.archive(&source).unwrap();
+            let file = std::fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(SystemTime::now() - Duration::from_secs(age))
+                .unwrap();
+            archived.push(path);
+        }
+        // Recordings archived within the same second keep unique names
+        assert_ne!(archived[0], archived[1]);
+        assert_ne!(archived[1], archived[2]);
+
+        // Files not created by the archive are left alone
+        std::fs::write(archive.dir().join("notes.txt"), "keep").unwrap();
+
+        assert_eq!(archive.prune().unwrap(), vec![archived[0].clone()]);
+        assert_eq!(archive.list().unwrap(), archived[1..].to_vec());
+        assert!(archive.dir().join("notes.txt").exists());
+
+        let keep_all = RecordingArchive::new(archive.dir().to_path_buf(), 0);
+        assert!(keep_all.prune().unwrap().is_empty());
+    }
+}
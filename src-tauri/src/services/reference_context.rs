@@ -0,0 +1,175 @@
+//! Reference documents given to GPT as formatting context
+//!
+//! A style guide or the email thread being answered makes continuation-style
+//! outputs much better. The reference is a plain text file attached to a
+//! profile, or a file or text attached to a single workflow. Long references
+//! are cut down to their beginning and end so the prompt stays within budget.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::context_memory::estimate_tokens;
+//! use dicta_clerk_lib::services::reference_context::reference_section;
+//!
+//! let thread = "Hi Sam,\nThe shipment left this morning.\n".repeat(500);
+//! let section = reference_section(&thread, 400).unwrap();
+//! assert!(section.contains("[…]"));
+//! assert!(estimate_tokens(&section) <= 400);
+//! ```
+
+use std::path::Path;
+use thiserror::Error;
+
+use crate::services::context_memory::{estimate_tokens, CHARS_PER_TOKEN};
+
+/// Largest reference file accepted
+pub const MAX_REFERENCE_BYTES: u64 = 1024 * 1024;
+
+/// Default size of the reference section in the prompt, in (estimated) tokens
+pub const REFERENCE_TOKEN_BUDGET: usize = 2000;
+
+/// Instructions that introduce the reference document
+const HEADER: &str = "Reference document (match its style and terminology, and stay consistent \
+with it; do not copy it into your output):";
+
+/// Marker for the part of a long reference that was left out
+const OMISSION: &str = "\n[…]\n";
+
+/// Errors that can occur while loading a reference document
+#[derive(Error, Debug)]
+pub enum ReferenceError {
+    #[error("Failed to read reference file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Reference file too large: {size} bytes (max: {max} bytes)")]
+    TooLarge { size: u64, max: u64 },
+
+    #[error("Reference file is not UTF-8 text")]
+    NotText,
+
+    #[error("Reference document is empty")]
+    Empty,
+}
+
+/// Read a reference text file
+pub fn load_reference(path: &Path) -> Result<String, ReferenceError> {
+    let size = std::fs::metadata(path)?.len();
+    if size > MAX_REFERENCE_BYTES {
+        return Err(ReferenceError::TooLarge {
+            size,
+            max: MAX_REFERENCE_BYTES,
+        });
+    }
+    let text = String::from_utf8(std::fs::read(path)?).map_err(|_| ReferenceError::NotText)?;
+    if text.trim().is_empty() {
+        return Err(ReferenceError::Empty);
+    }
+    Ok(text)
+}
+
+/// Prompt section quoting `text`, cut to its beginning and end to fit `token_budget`
+///
+/// Two thirds of the room go to the beginning (where style guides put their
+/// rules) and one third to the end (the latest message of a thread). Returns
+/// None when the text is empty or the budget cannot fit the header.
+pub fn reference_section(text: &str, token_budget: usize) -> Option<String> {
+    let text = text.trim();
+    let overhead = estimate_tokens(HEADER) + 1;
+    if text.is_empty() || overhead >= token_budget {
+        return None;
+    }
+
+    let room = token_budget - overhead;
+    let body = if estimate_tokens(text) <= room {
+        text.to_string()
+    } else {
+        let room = room.saturating_sub(estimate_tokens(OMISSION));
+        let head = head_within(text, room * 2 / 3);
+        let tail = tail_within(text, room - room * 2 / 3);
+        format!("{}{}{}", head, OMISSION, tail)
+    };
+    Some(format!("{}\n{}", HEADER, body))
+}
+
+/// Longest start of `text` within `max_tokens`, cut at a word boundary
+pub(crate) fn head_within(text: &str, max_tokens: usize) -> &str {
+    let max_chars = max_tokens * CHARS_PER_TOKEN;
+    let end = text
+        .char_indices()
+        .nth(max_chars)
+        .map_or(text.len(), |(index, _)| index);
+    let head = &text[..end];
+    match head.rfind(char::is_whitespace) {
+        Some(cut) if end < text.len() => head[..cut].trim_end(),
+        _ => head,
+    }
+}
+
+/// Longest end of `text` within `max_tokens`, cut at a word boundary
+fn tail_within(text: &str, max_tokens: usize) -> &str {
+    let max_chars = max_tokens * CHARS_PER_TOKEN;
+    let total = text.chars().count();
+    if total <= max_chars {
+        return text;
+    }
+    let start = text
+        .char_indices()
+        .nth(total - max_chars)
+        .map_or(text.len(), |(index, _)| index);
+    let tail = &text[start..];
+    match tail.find(char::is_whitespace) {
+        Some(cut) => tail[cut..].trim_start(),
+        None => tail,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_short_reference_is_kept_whole() {
+        let section = reference_section("  Always sign as \"The team\".  ", 500).unwrap();
+        assert!(section.starts_with(HEADER));
+        assert!(section.ends_with("\nAlways sign as \"The team\"."));
+        assert_eq!(reference_section("   ", 500), None);
+        assert_eq!(reference_section("Some text", 10), None);
+    }
+
+    #[test]
+    fn test_long_reference_keeps_beginning_and_end() {
+        let text = format!(
+            "Style guide first rule. {} Latest message last line.",
+            "filler words here ".repeat(2000)
+        );
+
+        for budget in [100, 300, 2000] {
+            let section = reference_section(&text, budget).unwrap();
+            assert!(estimate_tokens(&section) <= budget, "budget {}", budget);
+            assert!(section.contains("\nStyle guide first rule."));
+            assert!(section.ends_with("Latest message last line."));
+            assert!(section.contains(OMISSION));
+        }
+    }
+
+    #[test]
+    fn test_load_reference_rejects_binary_and_empty_files() {
+        let mut text = tempfile::NamedTempFile::new().unwrap();
+        write!(text, "Dear customer,").unwrap();
+        assert_eq!(load_reference(text.path()).unwrap(), "Dear customer,");
+
+        let mut binary = tempfile::NamedTempFile::new().unwrap();
+        binary.write_all(&[0xff, 0xfe, 0x00]).unwrap();
+        assert!(matches!(
+            load_reference(binary.path()),
+            Err(ReferenceError::NotText)
+        ));
+
+        let empty = tempfile::NamedTempFile::new().unwrap();
+        assert!(matches!(
+            load_reference(empty.path()),
+            Err(ReferenceError::Empty)
+        ));
+    }
+}
@@ -0,0 +1,351 @@
+//! ResourceGuard service for local inference backends
+//!
+//! Local Whisper/LLM backends compete with the audio capture thread for CPU
+//! and memory. Before a local job starts, the guard checks free memory and
+//! CPU load and limits how many local jobs run at once. When resources are
+//! short the job should go to the cloud provider instead:
+//! [`GuardedWhisperClient`] does exactly that for transcription.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use dicta_clerk_lib::services::{
+//!     GuardedWhisperClient, OpenAIWhisperClient, ResourceGuard, ResourceGuardConfig,
+//! };
+//!
+//! # fn local_backend() -> Arc<OpenAIWhisperClient> { unimplemented!() }
+//! let guard = Arc::new(ResourceGuard::new(ResourceGuardConfig::default()));
+//! let client = GuardedWhisperClient::new(
+//!     local_backend(),
+//!     Arc::new(OpenAIWhisperClient::new("your-api-key".to_string())),
+//!     guard,
+//! )
+//! .on_fallback(|reason| eprintln!("Using the cloud provider: {}", reason));
+//! ```
+
+use async_trait::async_trait;
+use std::path::Path;
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::services::{TranscriptionResponse, WhisperClient, WhisperMode, WhisperResult};
+
+/// Callback told why a job fell back to the cloud provider
+type FallbackCallback = Box<dyn Fn(&ResourceShortage) + Send + Sync>;
+
+/// Limits for local inference
+#[derive(Debug, Clone)]
+pub struct ResourceGuardConfig {
+    /// Local jobs allowed to run at the same time
+    pub max_concurrent_jobs: usize,
+    /// Free memory required to start a local job (MB)
+    pub min_free_memory_mb: u64,
+    /// Highest 1-minute load average per CPU core at which local jobs start
+    pub max_load_per_core: f64,
+}
+
+impl Default for ResourceGuardConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_jobs: 1,
+            min_free_memory_mb: 2048,
+            max_load_per_core: 0.85,
+        }
+    }
+}
+
+/// Why a local job cannot start
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ResourceShortage {
+    #[error("{running} local inference job(s) already running")]
+    Busy { running: usize },
+
+    #[error("Not enough free memory: {available_mb} MB available, {required_mb} MB required")]
+    Memory { available_mb: u64, required_mb: u64 },
+
+    #[error("CPU too busy: load {load_per_core:.2} per core (max {max_load_per_core:.2})")]
+    Cpu {
+        load_per_core: f64,
+        max_load_per_core: f64,
+    },
+}
+
+/// System resources at a point in time (None = unknown on this platform)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResourceSnapshot {
+    pub available_memory_mb: Option<u64>,
+    pub load_per_core: Option<f64>,
+}
+
+impl ResourceSnapshot {
+    /// Read the current resources (Linux `/proc`; unknown elsewhere)
+    pub fn current() -> Self {
+        let cpu_count = std::thread::available_parallelism()
+            .map(|count| count.get())
+            .unwrap_or(1);
+
+        let available_memory_mb = std::fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|meminfo| {
+                meminfo
+                    .lines()
+                    .find(|line| line.starts_with("MemAvailable:"))
+                    .and_then(|line| line.split_whitespace().nth(1))
+                    .and_then(|kb| kb.parse::<u64>().ok())
+            })
+            .map(|kb| kb / 1024);
+        let load_per_core = std::fs::read_to_string("/proc/loadavg")
+            .ok()
+            .and_then(|loadavg| loadavg.split_whitespace().next()?.parse::<f64>().ok())
+            .map(|load| load / cpu_count as f64);
+
+        Self {
+            available_memory_mb,
+            load_per_core,
+        }
+    }
+}
+
+/// Held while a local job runs; releases its slot when dropped
+#[derive(Debug)]
+pub struct LocalInferencePermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+/// Admission control for local inference jobs
+#[derive(Debug)]
+pub struct ResourceGuard {
+    config: ResourceGuardConfig,
+    slots: Arc<Semaphore>,
+}
+
+impl ResourceGuard {
+    /// Create a guard with the given limits
+    pub fn new(config: ResourceGuardConfig) -> Self {
+        let slots = Arc::new(Semaphore::new(config.max_concurrent_jobs.max(1)));
+        Self { config, slots }
+    }
+
+    /// Guard limits
+    pub fn config(&self) -> &ResourceGuardConfig {
+        &self.config
+    }
+
+    /// Reserve a slot for a local job if the system has room for it
+    pub fn try_acquire(&self) -> Result<LocalInferencePermit, ResourceShortage> {
+        self.try_acquire_with(ResourceSnapshot::current())
+    }
+
+    /// Same as `try_acquire` with an explicit resource snapshot
+    pub fn try_acquire_with(
+        &self,
+        snapshot: ResourceSnapshot,
+    ) -> Result<LocalInferencePermit, ResourceShortage> {
+        if let Some(available_mb) = snapshot.available_memory_mb {
+            if available_mb < self.config.min_free_memory_mb {
+                return Err(ResourceShortage::Memory {
+                    available_mb,
+                    required_mb: self.config.min_free_memory_mb,
+                });
+            }
+        }
+        if let Some(load_per_core) = snapshot.load_per_core {
+            if load_per_core > self.config.max_load_per_core {
+                return Err(ResourceShortage::Cpu {
+                    load_per_core,
+                    max_load_per_core: self.config.max_load_per_core,
+                });
+            }
+        }
+
+        let permit =
+            Arc::clone(&self.slots)
+                .try_acquire_owned()
+                .map_err(|_| ResourceShortage::Busy {
+                    running: self.config.max_concurrent_jobs.max(1),
+                })?;
+        Ok(LocalInferencePermit { _permit: permit })
+    }
+}
+
+/// Transcribes locally when resources allow, otherwise with the cloud client
+pub struct GuardedWhisperClient {
+    local: Arc<dyn WhisperClient + Send + Sync>,
+    cloud: Arc<dyn WhisperClient + Send + Sync>,
+    guard: Arc<ResourceGuard>,
+    on_fallback: Option<FallbackCallback>,
+}
+
+impl GuardedWhisperClient {
+    /// Combine a local backend and a cloud fallback behind a guard
+    pub fn new(
+        local: Arc<dyn WhisperClient + Send + Sync>,
+        cloud: Arc<dyn WhisperClient + Send + Sync>,
+        guard: Arc<ResourceGuard>,
+    ) -> Self {
+        Self {
+            local,
+            cloud,
+            guard,
+            on_fallback: None,
+        }
+    }
+
+    /// Callback invoked when a job falls back to the cloud (e.g. to emit a warning event)
+    pub fn on_fallback(
+        mut self,
+        callback: impl Fn(&ResourceShortage) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_fallback = Some(Box::new(callback));
+        self
+    }
+
+    /// Pick the backend for the next job; the permit must live for the whole job
+    fn select(
+        &self,
+    ) -> (
+        &Arc<dyn WhisperClient + Send + Sync>,
+        Option<LocalInferencePermit>,
+    ) {
+        match self.guard.try_acquire() {
+            Ok(permit) => (&self.local, Some(permit)),
+            Err(shortage) => {
+                eprintln!(
+                    "⚠️ [LOCAL] Falling back to the cloud provider: {}",
+                    shortage
+                );
+                if let Some(ref callback) = self.on_fallback {
+                    callback(&shortage);
+                }
+                (&self.cloud, None)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl WhisperClient for GuardedWhisperClient {
+    async fn transcribe(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+    ) -> WhisperResult<TranscriptionResponse> {
+        let (client, _permit) = self.select();
+        client.transcribe(file_path, prompt).await
+    }
+
+    async fn transcribe_with_segments(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+    ) -> WhisperResult<TranscriptionResponse> {
+        let (client, _permit) = self.select();
+        client.transcribe_with_segments(file_path, prompt).await
+    }
+
+    async fn transcribe_with_language(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+        language: Option<String>,
+    ) -> WhisperResult<TranscriptionResponse> {
+        let (client, _permit) = self.select();
+        client
+            .transcribe_with_language(file_path, prompt, language)
+            .await
+    }
+
+    async fn transcribe_with_mode(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+        language: Option<String>,
+        mode: WhisperMode,
+    ) -> WhisperResult<TranscriptionResponse> {
+        let (client, _permit) = self.select();
+        client
+            .transcribe_with_mode(file_path, prompt, language, mode)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::whisper_client::tests::MockWhisperClient;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const PLENTY: ResourceSnapshot = ResourceSnapshot {
+        available_memory_mb: Some(16 * 1024),
+        load_per_core: Some(0.1),
+    };
+
+    #[test]
+    fn test_guard_checks_memory_cpu_and_concurrency() {
+        let guard = ResourceGuard::new(ResourceGuardConfig::default());
+
+        let low_memory = ResourceSnapshot {
+            available_memory_mb: Some(512),
+            ..PLENTY
+        };
+        assert!(matches!(
+            guard.try_acquire_with(low_memory),
+            Err(ResourceShortage::Memory { .. })
+        ));
+        let busy_cpu = ResourceSnapshot {
+            load_per_core: Some(1.5),
+            ..PLENTY
+        };
+        assert!(matches!(
+            guard.try_acquire_with(busy_cpu),
+            Err(ResourceShortage::Cpu { .. })
+        ));
+
+        let permit = guard.try_acquire_with(PLENTY).unwrap();
+        assert_eq!(
+            guard.try_acquire_with(PLENTY).unwrap_err(),
+            ResourceShortage::Busy { running: 1 }
+        );
+        drop(permit);
+        assert!(guard.try_acquire_with(PLENTY).is_ok());
+
+        // Unknown resources do not block local inference
+        let unknown = ResourceSnapshot {
+            available_memory_mb: None,
+            load_per_core: None,
+        };
+        assert!(guard.try_acquire_with(unknown).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_guarded_client_falls_back_to_cloud() {
+        let audio = tempfile::NamedTempFile::new().unwrap();
+        // An impossible memory requirement forces the fallback
+        let guard = Arc::new(ResourceGuard::new(ResourceGuardConfig {
+            min_free_memory_mb: u64::MAX,
+            ..ResourceGuardConfig::default()
+        }));
+        let fallbacks = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&fallbacks);
+        let client = GuardedWhisperClient::new(
+            Arc::new(MockWhisperClient::new().with_failure()),
+            Arc::new(MockWhisperClient::new()),
+            guard,
+        )
+        .on_fallback(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let result = client.transcribe(audio.path(), None).await;
+
+        // /proc is missing on some platforms: then the (failing) local backend runs
+        if ResourceSnapshot::current().available_memory_mb.is_some() {
+            assert!(result.is_ok());
+            assert_eq!(fallbacks.load(Ordering::SeqCst), 1);
+        } else {
+            assert!(result.is_err());
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! Segment-level re-transcription of low-confidence regions
+//!
+//! Whisper reports, for every segment, the average token log probability, the
+//! compression ratio and the probability that it contains no speech. Segments
+//! whose log probability is low or whose text is suspiciously repetitive are
+//! worth re-sending on their own (with a more accurate model), after which the
+//! improved texts are spliced back in place of the originals. The same log
+//! probabilities give the overall confidence of a transcript.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use dicta_clerk_lib::services::retranscription::{
+//!     low_confidence_segments, splice_segments, DEFAULT_MIN_AVG_LOGPROB,
+//! };
+//! use dicta_clerk_lib::services::TranscriptionSegment;
+//!
+//! let segments = vec![TranscriptionSegment {
+//!     id: 0,
+//!     start: 0.0,
+//!     end: 2.0,
+//!     text: " Hello wold.".to_string(),
+//!     avg_logprob: Some(-1.4),
+//!     compression_ratio: Some(1.1),
+//!     no_speech_prob: Some(0.01),
+//! }];
+//!
+//! let low = low_confidence_segments(&segments, DEFAULT_MIN_AVG_LOGPROB);
+//! assert_eq!(low.len(), 1);
+//!
+//! let replacements = HashMap::from([(0, "Hello world.".to_string())]);
+//! assert_eq!(splice_segments(&segments, &replacements), "Hello world.");
+//! ```
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::services::whisper_client::TranscriptionSegment;
+
+/// Segments whose average log probability is below this are re-transcribed
+/// (the threshold Whisper itself uses to fall back to a higher temperature)
+pub const DEFAULT_MIN_AVG_LOGPROB: f64 = -1.0;
+
+/// Above this compression ratio a segment is likely a repetition loop
+const MAX_COMPRESSION_RATIO: f64 = 2.4;
+
+/// Above this no-speech probability a segment is treated as silence
+const NO_SPEECH_THRESHOLD: f64 = 0.6;
+
+/// Audio kept on each side of a segment when it is cut out
+pub const SLICE_PADDING: Duration = Duration::from_millis(250);
+
+/// Whether a segment should be re-transcribed
+pub fn is_low_confidence(segment: &TranscriptionSegment, min_avg_logprob: f64) -> bool {
+    if segment.no_speech_prob.unwrap_or(0.0) > NO_SPEECH_THRESHOLD {
+        return false;
+    }
+    segment
+        .avg_logprob
+        .is_some_and(|logprob| logprob < min_avg_logprob)
+        || segment
+            .compression_ratio
+            .is_some_and(|ratio| ratio > MAX_COMPRESSION_RATIO)
+}
+
+/// Segments that should be re-transcribed, in order
+pub fn low_confidence_segments(
+    segments: &[TranscriptionSegment],
+    min_avg_logprob: f64,
+) -> Vec<&TranscriptionSegment> {
+    segments
+        .iter()
+        .filter(|segment| is_low_confidence(segment, min_avg_logprob))
+        .collect()
+}
+
+/// Time range of the audio to cut for a segment (padded, never negative)
+pub fn slice_range(segment: &TranscriptionSegment) -> (Duration, Duration) {
+    let start = Duration::from_secs_f64(segment.start.max(0.0)).saturating_sub(SLICE_PADDING);
+    let end = Duration::from_secs_f64(segment.end.max(segment.start).max(0.0)) + SLICE_PADDING;
+    (start, end)
+}
+
+/// Overall confidence of a transcript, from 0 to 1: the token probability of
+/// its segments (`exp(avg_logprob)`), averaged by duration
+///
+/// Silent segments are left out. None when no log probability was reported
+/// (providers other than the OpenAI Whisper API).
+pub fn transcript_confidence(segments: &[TranscriptionSegment]) -> Option<f64> {
+    let mut weighted = 0.0;
+    let mut total_duration = 0.0;
+    for segment in segments {
+        if segment.no_speech_prob.unwrap_or(0.0) > NO_SPEECH_THRESHOLD {
+            continue;
+        }
+        let Some(logprob) = segment.avg_logprob else {
+            continue;
+        };
+        // Segments are weighted by length, with a floor for zero-length ones
+        let duration = (segment.end - segment.start).max(0.1);
+        weighted += logprob.exp().min(1.0) * duration;
+        total_duration += duration;
+    }
+    (total_duration > 0.0).then(|| weighted / total_duration)
+}
+
+/// Rebuild the transcript from its segments, replacing the texts in `replacements` (by segment id)
+pub fn splice_segments(
+    segments: &[TranscriptionSegment],
+    replacements: &HashMap<u32, String>,
+) -> String {
+    segments
+        .iter()
+        .map(|segment| {
+            replacements
+                .get(&segment.id)
+                .unwrap_or(&segment.text)
+                .trim()
+        })
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(id: u32, text: &str, avg_logprob: f64, no_speech_prob: f64) -> TranscriptionSegment {
+        TranscriptionSegment {
+            id,
+            start: id as f64 * 2.0,
+            end: id as f64 * 2.0 + 2.0,
+            text: text.to_string(),
+            avg_logprob: Some(avg_logprob),
+            compression_ratio: Some(1.2),
+            no_speech_prob: Some(no_speech_prob),
+        }
+    }
+
+    #[test]
+    fn test_low_confidence_selection() {
+        let mut repetitive = segment(3, " la la la la", -0.2, 0.0);
+        repetitive.compression_ratio = Some(3.0);
+        let segments = vec![
+            segment(0, " Clear speech.", -0.2, 0.0),
+            segment(1, " Mumbled wrds.", -1.3, 0.1),
+            segment(2, " ...", -1.8, 0.9),
+            repetitive,
+        ];
+
+        let ids: Vec<u32> = low_confidence_segments(&segments, DEFAULT_MIN_AVG_LOGPROB)
+            .iter()
+            .map(|segment| segment.id)
+            .collect();
+        assert_eq!(ids, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_transcript_confidence() {
+        let mut long = segment(1, " Mumbled wrds.", -1.5, 0.0);
+        long.end = long.start + 6.0;
+        let segments = vec![
+            segment(0, " Clear speech.", 0.0, 0.0),
+            long,
+            // Silence does not lower the confidence
+            segment(2, " ...", -3.0, 0.9),
+        ];
+        let confidence = transcript_confidence(&segments).unwrap();
+        let expected = (2.0 + 6.0 * (-1.5f64).exp()) / 8.0;
+        assert!((confidence - expected).abs() < 1e-9);
+
+        let mut unscored = segment(0, " Local model.", 0.0, 0.0);
+        unscored.avg_logprob = None;
+        assert_eq!(transcript_confidence(&[unscored]), None);
+    }
+
+    #[test]
+    fn test_slice_range_is_padded_and_clamped() {
+        let first = segment(0, "", -1.5, 0.0);
+        assert_eq!(
+            slice_range(&first),
+            (Duration::ZERO, Duration::from_millis(2250))
+        );
+        let second = segment(1, "", -1.5, 0.0);
+        assert_eq!(
+            slice_range(&second),
+            (Duration::from_millis(1750), Duration::from_millis(4250))
+        );
+    }
+
+    #[test]
+    fn test_splice_replaces_only_given_segments() {
+        let segments = vec![
+            segment(0, " First part.", -0.2, 0.0),
+            segment(1, " Mumbled wrds.", -1.3, 0.0),
+            segment(2, " Last part.", -0.2, 0.0),
+        ];
+        let replacements = HashMap::from([(1, " Mumbled words. ".to_string())]);
+
+        assert_eq!(
+            splice_segments(&segments, &replacements),
+            "First part. Mumbled words. Last part."
+        );
+        assert_eq!(
+            splice_segments(&segments, &HashMap::new()),
+            "First part. Mumbled wrds. Last part."
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Retry policy of the API requests
+//!
+//! Failed requests are retried with an exponential backoff, randomized by a
+//! jitter so that concurrent requests (chunks of a long recording, queued
+//! jobs) do not all retry at the same instant. When the provider says how long
+//! to wait (`Retry-After` on a 429), that delay is used instead.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::retry_policy::RetryPolicy;
+//! use std::time::Duration;
+//!
+//! let policy = RetryPolicy {
+//!     max_retries: 5,
+//!     base_delay_ms: 500,
+//!     ..RetryPolicy::default()
+//! };
+//! // Waits the 2s asked for by the server
+//! assert_eq!(
+//!     policy.retry_delay(0, Some(Duration::from_secs(2))),
+//!     Some(Duration::from_secs(2))
+//! );
+//! ```
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::header::HeaderMap;
+use std::time::Duration;
+
+/// Delays and number of retries of failed requests
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (default: 3)
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled at each retry (default: 1000)
+    pub base_delay_ms: u64,
+    /// Longest delay between two attempts (default: 30000); a `Retry-After`
+    /// longer than this ends the retries
+    pub max_delay_ms: u64,
+    /// Random spread of the backoff delays, as a fraction of the delay
+    /// (default: 0.2 = ±20%)
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay_ms: 1000,
+            max_delay_ms: 30_000,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Backoff delay before retry `attempt` (0 = first retry), without jitter
+    pub fn backoff_delay(&self, attempt: u32) -> Duration {
+        let delay_ms = self
+            .base_delay_ms
+            .saturating_mul(2_u64.saturating_pow(attempt))
+            .min(self.max_delay_ms);
+        Duration::from_millis(delay_ms)
+    }
+
+    /// Delay before retry `attempt`: the `retry_after` asked for by the
+    /// server, or the backoff delay with jitter; None when the server asks to
+    /// wait longer than `max_delay_ms`
+    pub fn retry_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if let Some(retry_after) = retry_after {
+            return (retry_after <= Duration::from_millis(self.max_delay_ms))
+                .then_some(retry_after);
+        }
+
+        let delay = self.backoff_delay(attempt);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return Some(delay);
+        }
+        let factor = rand::thread_rng().gen_range(1.0 - jitter..=1.0 + jitter);
+        Some(delay.mul_f64(factor))
+    }
+}
+
+/// Delay asked for by a `Retry-After` value: seconds, or an HTTP date
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    // A date in the past means "now"
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Delay asked for by the headers of a response: OpenAI's `retry-after-ms`,
+/// or the standard `Retry-After`
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    if let Some(milliseconds) = header("retry-after-ms").and_then(|ms| ms.trim().parse().ok()) {
+        return Some(Duration::from_millis(milliseconds));
+    }
+    header("retry-after").and_then(|value| parse_retry_after(value, Utc::now()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_backoff_and_jitter() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay_ms: 1000,
+            max_delay_ms: 5000,
+            jitter: 0.0,
+        };
+        assert_eq!(policy.backoff_delay(0), Duration::from_secs(1));
+        assert_eq!(policy.backoff_delay(2), Duration::from_secs(4));
+        assert_eq!(policy.backoff_delay(3), Duration::from_secs(5));
+        assert_eq!(policy.backoff_delay(64), Duration::from_secs(5));
+        assert_eq!(policy.retry_delay(1, None), Some(Duration::from_secs(2)));
+
+        let jittered = RetryPolicy {
+            jitter: 0.5,
+            ..policy
+        };
+        for _ in 0..20 {
+            let delay = jittered.retry_delay(1, None).unwrap();
+            assert!(delay >= Duration::from_secs(1) && delay <= Duration::from_secs(3));
+        }
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let policy = RetryPolicy::default();
+        assert_eq!(
+            policy.retry_delay(0, Some(Duration::from_secs(7))),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(policy.retry_delay(0, Some(Duration::from_secs(600))), None);
+
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2026 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            parse_retry_after(" 12 ", now),
+            Some(Duration::from_secs(12))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2026 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2026 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert("retry-after", HeaderValue::from_static("3"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(3)));
+        headers.insert("retry-after-ms", HeaderValue::from_static("1500"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_millis(1500)));
+    }
+}
@@ -12,10 +12,13 @@ use serde_json;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter};
-use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
 use thiserror::Error;
 use tokio::sync::Mutex;
 
+/// Key of the bookmark shortcut among the registered shortcuts
+const BOOKMARK_KEY: &str = "bookmark";
+
 /// Error types for shortcut operations
 #[derive(Error, Debug)]
 pub enum ShortcutError {
@@ -328,6 +331,60 @@ impl ShortcutMgr {
         }
     }
 
+    /// Register the shortcut bookmarking the recording (replaces the previous one)
+    pub async fn register_bookmark_shortcut(&self, shortcut_str: String) -> ShortcutResult<()> {
+        let shortcut: Shortcut = shortcut_str
+            .parse()
+            .map_err(|e| ShortcutError::ParseError(format!("{}", e)))?;
+
+        if let Some(previous) = self.registered_shortcuts.lock().await.remove(BOOKMARK_KEY) {
+            let _ = self.app_handle.global_shortcut().unregister(previous);
+        }
+
+        let registration_result = self.app_handle.global_shortcut().on_shortcut(
+            shortcut,
+            move |app_handle, _shortcut, event| {
+                // Once per press, not again on release
+                if event.state() != ShortcutState::Pressed {
+                    return;
+                }
+                if let Err(e) = app_handle.emit("global_shortcut_mark_bookmark", ()) {
+                    eprintln!("Failed to emit bookmark shortcut event: {}", e);
+                }
+            },
+        );
+
+        match registration_result {
+            Ok(_) => {
+                let mut shortcuts = self.registered_shortcuts.lock().await;
+                shortcuts.insert(BOOKMARK_KEY.to_string(), shortcut);
+
+                println!(
+                    "Successfully registered bookmark shortcut: {}",
+                    shortcut_str
+                );
+                Ok(())
+            }
+            Err(e) => {
+                let error_msg = format!(
+                    "Failed to register bookmark shortcut '{}': {}",
+                    shortcut_str, e
+                );
+
+                if self.config.show_error_toasts {
+                    if let Err(emit_err) = self.app_handle.emit("shortcut_error", error_msg.clone())
+                    {
+                        eprintln!("Failed to emit shortcut error event: {}", emit_err);
+                    }
+                }
+
+                Err(ShortcutError::ShortcutUnavailable {
+                    shortcut: shortcut_str,
+                })
+            }
+        }
+    }
+
     /// Register all profile shortcuts from a profile collection
     pub async fn register_profile_shortcuts(
         &self,
@@ -30,14 +30,48 @@
 //!
 //! The warning is sent exactly once per encoding session and resets automatically
 //! when encoding completes or encounters an error.
+//!
+//! ## Oversized Recordings
+//!
+//! A recording whose encoded file exceeds the Whisper upload limit (25MB) is
+//! not rejected: [`split_recording`] cuts its WAV file into chunks that each
+//! fit the limit, overlapping by [`CHUNK_OVERLAP_SECONDS`] so no word is cut
+//! in half, and [`stitch_transcriptions`] joins the chunk transcripts back,
+//! dropping the words transcribed twice in the overlaps.
+//!
+//! ## Early Warnings
+//!
+//! While recording, a [`SizeForecaster`] predicts the encoded size from the
+//! elapsed time and reports when it crosses 80% and 95% of the upload limit.
+//! Before the upload, [`SizeGuard::check_upload`] warns when the recording
+//! will be split, and refuses recordings that cannot be split.
 
 use crate::audio::EncodingEvent;
-use crate::services::Notifier;
+use crate::services::{Notifier, TranscriptionResponse};
+use hound::{SampleFormat, WavReader, WavWriter};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::mpsc;
 
+/// Largest file the Whisper API accepts
+pub const WHISPER_UPLOAD_LIMIT_BYTES: u64 = 25 * 1024 * 1024;
+
+/// Audio shared by consecutive chunks of a split recording
+pub const CHUNK_OVERLAP_SECONDS: f64 = 2.0;
+
+/// Share of the upload limit a chunk is planned to take (encoded sizes vary
+/// with the content)
+const CHUNK_SIZE_MARGIN: f64 = 0.9;
+
+/// Most words compared when removing the overlap between two transcripts
+const MAX_OVERLAP_WORDS: usize = 40;
+
+/// Shares of the upload limit (in percent) reported while recording
+pub const EARLY_WARNING_PERCENTS: [u8; 2] = [80, 95];
+
 /// Configuration for SizeGuard
 #[derive(Debug, Clone)]
 pub struct SizeGuardConfig {
@@ -65,6 +99,14 @@ pub enum SizeGuardError {
     ChannelError(String),
     #[error("Service not initialized")]
     NotInitialized,
+    #[error("Recording split failed: {0}")]
+    SplitFailed(String),
+}
+
+impl From<hound::Error> for SizeGuardError {
+    fn from(error: hound::Error) -> Self {
+        SizeGuardError::SplitFailed(error.to_string())
+    }
 }
 
 /// SizeGuard service for monitoring encoding size and triggering warnings
@@ -192,6 +234,324 @@ impl SizeGuard {
     pub fn warning_message(&self) -> &str {
         &self.config.warning_message
     }
+
+    /// Check a recording of `total_frames` at `sample_rate` before its upload,
+    /// its encoded file predicted to take `predicted_bytes`
+    ///
+    /// Recordings over the upload limit are transcribed in chunks: the user is
+    /// warned once. Recordings that cannot be split into chunks that fit are
+    /// refused.
+    pub async fn check_upload(
+        &self,
+        total_frames: u64,
+        sample_rate: u32,
+        predicted_bytes: u64,
+    ) -> Result<UploadCheck, SizeGuardError> {
+        let overlap_frames = (CHUNK_OVERLAP_SECONDS * sample_rate as f64) as u64;
+        let chunks = plan_chunks(
+            total_frames,
+            predicted_bytes,
+            WHISPER_UPLOAD_LIMIT_BYTES,
+            overlap_frames,
+        )?
+        .len();
+        if chunks <= 1 {
+            return Ok(UploadCheck::Fits);
+        }
+
+        if !self.warned.swap(true, Ordering::Relaxed) {
+            let message = format!(
+                "This recording is over the {}MB upload limit: it will be transcribed in {} parts",
+                WHISPER_UPLOAD_LIMIT_BYTES / (1024 * 1024),
+                chunks
+            );
+            self.notifier
+                .warning(&message)
+                .await
+                .map_err(|e| SizeGuardError::NotificationFailed(e.to_string()))?;
+        }
+        Ok(UploadCheck::Split { chunks })
+    }
+}
+
+/// Outcome of [`SizeGuard::check_upload`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum UploadCheck {
+    /// Uploaded as a single file
+    Fits,
+    /// Transcribed in `chunks` overlapping parts
+    Split { chunks: usize },
+}
+
+/// Predicted encoded size of a recording that crossed an early warning share
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SizeForecast {
+    pub predicted_bytes: u64,
+    pub limit_bytes: u64,
+    /// Share of the limit crossed (one of [`EARLY_WARNING_PERCENTS`])
+    pub percent: u8,
+}
+
+/// Predicts the encoded size of a recording as it grows
+#[derive(Debug, Clone)]
+pub struct SizeForecaster {
+    bytes_per_second: f64,
+    limit_bytes: u64,
+    /// Early warning shares already reported
+    reported: usize,
+}
+
+impl SizeForecaster {
+    /// Forecast for an encoder producing `bytes_per_second`
+    pub fn new(bytes_per_second: f64, limit_bytes: u64) -> Self {
+        Self {
+            bytes_per_second,
+            limit_bytes,
+            reported: 0,
+        }
+    }
+
+    /// Predicted encoded size after `elapsed_seconds`
+    pub fn predicted_bytes(&self, elapsed_seconds: f64) -> u64 {
+        (self.bytes_per_second * elapsed_seconds) as u64
+    }
+
+    /// Forecast when the recording crossed a share of the limit not reported
+    /// yet (the highest one when several were crossed at once)
+    pub fn observe(&mut self, elapsed_seconds: f64) -> Option<SizeForecast> {
+        let predicted_bytes = self.predicted_bytes(elapsed_seconds);
+        let crossed = EARLY_WARNING_PERCENTS
+            .iter()
+            .take_while(|&&percent| {
+                predicted_bytes as f64 >= self.limit_bytes as f64 * f64::from(percent) / 100.0
+            })
+            .count();
+        if crossed <= self.reported {
+            return None;
+        }
+        self.reported = crossed;
+        Some(SizeForecast {
+            predicted_bytes,
+            limit_bytes: self.limit_bytes,
+            percent: EARLY_WARNING_PERCENTS[crossed - 1],
+        })
+    }
+}
+
+/// Chunk of a split recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordingChunk {
+    pub path: PathBuf,
+    /// Start of the chunk in the recording
+    pub offset_seconds: f64,
+}
+
+/// Frame ranges of the chunks of a recording of `total_frames` whose encoded
+/// file takes `encoded_size` bytes, so each chunk fits `limit` bytes
+///
+/// Consecutive ranges overlap by `overlap_frames`. A single range covers
+/// recordings that already fit.
+pub fn plan_chunks(
+    total_frames: u64,
+    encoded_size: u64,
+    limit: u64,
+    overlap_frames: u64,
+) -> Result<Vec<std::ops::Range<u64>>, SizeGuardError> {
+    if total_frames == 0 || encoded_size <= limit {
+        return Ok(std::iter::once(0..total_frames).collect());
+    }
+    let bytes_per_frame = encoded_size as f64 / total_frames as f64;
+    let max_frames = (limit as f64 * CHUNK_SIZE_MARGIN / bytes_per_frame) as u64;
+    if max_frames <= overlap_frames * 2 {
+        return Err(SizeGuardError::SplitFailed(format!(
+            "chunks of {} frames cannot overlap by {} frames",
+            max_frames, overlap_frames
+        )));
+    }
+    Ok(plan_time_chunks(total_frames, max_frames, overlap_frames))
+}
+
+/// Frame ranges of `chunk_frames` (the last one shorter) covering a recording
+/// of `total_frames`, consecutive ranges overlapping by `overlap_frames`
+pub fn plan_time_chunks(
+    total_frames: u64,
+    chunk_frames: u64,
+    overlap_frames: u64,
+) -> Vec<std::ops::Range<u64>> {
+    let step = chunk_frames.saturating_sub(overlap_frames).max(1);
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    loop {
+        let end = (start + chunk_frames.max(1)).min(total_frames);
+        chunks.push(start..end);
+        if end == total_frames {
+            return chunks;
+        }
+        start += step;
+    }
+}
+
+/// Split the WAV recording at `wav_path`, whose encoded file takes
+/// `encoded_size` bytes, into overlapping chunks that each fit `limit` bytes
+/// once encoded
+///
+/// The chunks are written to `output_dir` in recording order.
+pub fn split_recording(
+    wav_path: &Path,
+    encoded_size: u64,
+    limit: u64,
+    output_dir: &Path,
+) -> Result<Vec<RecordingChunk>, SizeGuardError> {
+    let reader = WavReader::open(wav_path)?;
+    let spec = reader.spec();
+    let total_frames = reader.len() as u64 / spec.channels.max(1) as u64;
+    let overlap_frames = (CHUNK_OVERLAP_SECONDS * spec.sample_rate as f64) as u64;
+    let ranges = plan_chunks(total_frames, encoded_size, limit, overlap_frames)?;
+    write_chunks(reader, &ranges, output_dir)
+}
+
+/// Split the WAV recording at `wav_path` into overlapping chunks of
+/// `chunk_seconds`, written to `output_dir` in recording order
+pub fn split_recording_by_duration(
+    wav_path: &Path,
+    chunk_seconds: f64,
+    output_dir: &Path,
+) -> Result<Vec<RecordingChunk>, SizeGuardError> {
+    let reader = WavReader::open(wav_path)?;
+    let spec = reader.spec();
+    let total_frames = reader.len() as u64 / spec.channels.max(1) as u64;
+    let overlap_frames = (CHUNK_OVERLAP_SECONDS * spec.sample_rate as f64) as u64;
+    let chunk_frames = (chunk_seconds * spec.sample_rate as f64) as u64;
+    if chunk_frames <= overlap_frames * 2 {
+        return Err(SizeGuardError::SplitFailed(format!(
+            "chunks of {}s cannot overlap by {}s",
+            chunk_seconds, CHUNK_OVERLAP_SECONDS
+        )));
+    }
+    let ranges = plan_time_chunks(total_frames, chunk_frames, overlap_frames);
+    write_chunks(reader, &ranges, output_dir)
+}
+
+/// Write the frame `ranges` of a recording to WAV files in `output_dir`
+fn write_chunks(
+    mut reader: WavReader<std::io::BufReader<std::fs::File>>,
+    ranges: &[std::ops::Range<u64>],
+    output_dir: &Path,
+) -> Result<Vec<RecordingChunk>, SizeGuardError> {
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as u64;
+    let mut chunks = Vec::with_capacity(ranges.len());
+    for (index, range) in ranges.iter().enumerate() {
+        reader
+            .seek(range.start as u32)
+            .map_err(hound::Error::IoError)?;
+        let path = output_dir.join(format!("chunk-{:03}.wav", index));
+        let mut writer = WavWriter::create(&path, spec)?;
+        let samples = ((range.end - range.start) * channels) as usize;
+        match spec.sample_format {
+            SampleFormat::Float => {
+                for sample in reader.samples::<f32>().take(samples) {
+                    writer.write_sample(sample?)?;
+                }
+            }
+            SampleFormat::Int => {
+                for sample in reader.samples::<i32>().take(samples) {
+                    writer.write_sample(sample?)?;
+                }
+            }
+        }
+        writer.finalize()?;
+        chunks.push(RecordingChunk {
+            path,
+            offset_seconds: range.start as f64 / spec.sample_rate as f64,
+        });
+    }
+    Ok(chunks)
+}
+
+/// Word compared across transcripts (case and punctuation ignored)
+fn comparable_word(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+/// Join consecutive chunk transcripts, removing the words the end of each
+/// transcript shares with the start of the next one
+pub fn stitch_transcripts<S: AsRef<str>>(parts: &[S]) -> String {
+    let mut words: Vec<&str> = Vec::new();
+    for part in parts {
+        let next: Vec<&str> = part.as_ref().split_whitespace().collect();
+        let longest = MAX_OVERLAP_WORDS.min(words.len()).min(next.len());
+        let overlap = (1..=longest)
+            .rev()
+            .find(|&count| {
+                words[words.len() - count..]
+                    .iter()
+                    .zip(&next[..count])
+                    .all(|(a, b)| comparable_word(a) == comparable_word(b))
+            })
+            .unwrap_or(0);
+        words.extend_from_slice(&next[overlap..]);
+    }
+    words.join(" ")
+}
+
+/// Join the transcriptions of the chunks of a recording, given in order with
+/// the offset of their chunk
+///
+/// Segment and word times are moved to the recording's timeline, and the
+/// segments and words of an overlap are kept from the earlier chunk only.
+pub fn stitch_transcriptions(parts: Vec<(f64, TranscriptionResponse)>) -> TranscriptionResponse {
+    let text = stitch_transcripts(
+        &parts
+            .iter()
+            .map(|(_, part)| part.text.as_str())
+            .collect::<Vec<_>>(),
+    );
+    let language = parts.iter().find_map(|(_, part)| part.language.clone());
+    let duration = parts
+        .last()
+        .and_then(|(offset, part)| part.duration.map(|duration| offset + duration));
+
+    let mut segments = Vec::new();
+    let mut words = Vec::new();
+    let mut covered_until = 0.0;
+    for (offset, part) in &parts {
+        let covered_by_earlier = covered_until;
+        for segment in part.segments.iter().flatten() {
+            let mut segment = segment.clone();
+            segment.start += offset;
+            segment.end += offset;
+            if segment.end <= covered_until {
+                continue;
+            }
+            segment.id = segments.len() as u32;
+            covered_until = segment.end;
+            segments.push(segment);
+        }
+        // Words of an overlap are kept from the earlier chunk, like segments
+        for word in part.words.iter().flatten() {
+            let mut word = word.clone();
+            word.start += offset;
+            word.end += offset;
+            if word.end <= covered_by_earlier {
+                continue;
+            }
+            covered_until = f64::max(covered_until, word.end);
+            words.push(word);
+        }
+    }
+    let has_segments = parts.iter().any(|(_, part)| part.segments.is_some());
+    let has_words = parts.iter().any(|(_, part)| part.words.is_some());
+
+    TranscriptionResponse {
+        text,
+        language,
+        duration,
+        segments: has_segments.then_some(segments),
+        words: has_words.then_some(words),
+    }
 }
 
 #[cfg(test)]
@@ -349,7 +709,9 @@ mod tests {
                 final_info: crate::audio::OggInfo {
                     size_estimate: 24 * 1024 * 1024,
                     path: std::path::PathBuf::from("/tmp/test.ogg"),
+                    format: crate::audio::EncoderFormat::Ogg,
                     actual_size: Some(24 * 1024 * 1024),
+                    noise_suppressed: false,
                 },
             })
             .unwrap();
@@ -489,4 +851,171 @@ mod tests {
         // Should warn exactly once when crossing 23MB
         assert_eq!(test_notifier.warn_count(), 1);
     }
+
+    #[test]
+    fn test_plan_chunks() {
+        // Fits: a single chunk
+        assert_eq!(plan_chunks(1000, 10, 100, 50).unwrap(), vec![0..1000]);
+
+        // 1000 frames of 1 byte, 100-byte limit: chunks of 90 frames, 10 shared
+        let chunks = plan_chunks(1000, 1000, 100, 10).unwrap();
+        assert_eq!(chunks[0], 0..90);
+        assert_eq!(chunks[1], 80..170);
+        assert_eq!(chunks.last().unwrap().end, 1000);
+        assert!(chunks.iter().all(|chunk| chunk.end - chunk.start <= 90));
+
+        // Chunks too short for the overlap
+        assert!(plan_chunks(1000, 1000, 10, 10).is_err());
+    }
+
+    #[test]
+    fn test_split_recording() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let wav_path = dir.path().join("long.wav");
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 1000,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        let mut writer = WavWriter::create(&wav_path, spec).unwrap();
+        for sample in 0..10_000 {
+            writer.write_sample(sample as i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        // 1 byte per frame, 5000-byte limit: 4500-frame chunks sharing 2000 frames
+        let chunks = split_recording(&wav_path, 10_000, 5_000, dir.path()).unwrap();
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[1].offset_seconds, 2.5);
+        let second: Vec<i16> = WavReader::open(&chunks[1].path)
+            .unwrap()
+            .samples::<i16>()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(second.len(), 4500);
+        assert_eq!(second[0], 2500);
+
+        // 5 s chunks sharing 2 s: a chunk every 3 s
+        let by_duration = dir.path().join("by_duration");
+        std::fs::create_dir(&by_duration).unwrap();
+        let chunks = split_recording_by_duration(&wav_path, 5.0, &by_duration).unwrap();
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.offset_seconds)
+                .collect::<Vec<_>>(),
+            vec![0.0, 3.0, 6.0]
+        );
+        assert!(split_recording_by_duration(&wav_path, 3.0, &by_duration).is_err());
+    }
+
+    #[test]
+    fn test_stitch_transcriptions() {
+        assert_eq!(
+            stitch_transcripts(&[
+                "The quarterly report is ready. Send it to",
+                "send it to Anna before Friday.",
+                "No overlap here."
+            ]),
+            "The quarterly report is ready. Send it to Anna before Friday. No overlap here."
+        );
+
+        let segment = |start: f64, end: f64, text: &str| crate::services::TranscriptionSegment {
+            id: 0,
+            start,
+            end,
+            text: text.to_string(),
+            avg_logprob: None,
+            compression_ratio: None,
+            no_speech_prob: None,
+        };
+        let word = |start: f64, end: f64, text: &str| crate::services::TranscriptionWord {
+            word: text.to_string(),
+            start,
+            end,
+        };
+        let stitched = stitch_transcriptions(vec![
+            (
+                0.0,
+                TranscriptionResponse {
+                    text: "Hello there.".to_string(),
+                    language: Some("en".to_string()),
+                    duration: Some(10.0),
+                    segments: Some(vec![segment(0.0, 10.0, "Hello there.")]),
+                    words: Some(vec![word(0.0, 1.0, "Hello"), word(1.0, 2.0, "there.")]),
+                },
+            ),
+            (
+                8.0,
+                TranscriptionResponse {
+                    text: "there. Bye.".to_string(),
+                    language: None,
+                    duration: Some(5.0),
+                    segments: Some(vec![segment(0.0, 2.0, "there."), segment(2.0, 5.0, "Bye.")]),
+                    words: Some(vec![word(1.0, 2.0, "there."), word(2.5, 3.0, "Bye.")]),
+                },
+            ),
+        ]);
+        assert_eq!(stitched.text, "Hello there. Bye.");
+        assert_eq!(stitched.language.as_deref(), Some("en"));
+        assert_eq!(stitched.duration, Some(13.0));
+        let segments = stitched.segments.unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!((segments[1].id, segments[1].start), (1, 10.0));
+        let words = stitched.words.unwrap();
+        assert_eq!(words.len(), 3);
+        assert_eq!((words[2].word.as_str(), words[2].start), ("Bye.", 10.5));
+    }
+
+    #[test]
+    fn test_size_forecaster() {
+        // 1000 bytes per second, 100000-byte limit
+        let mut forecaster = SizeForecaster::new(1000.0, 100_000);
+        assert_eq!(forecaster.observe(10.0), None);
+        assert_eq!(forecaster.observe(80.0).unwrap().percent, 80);
+        assert_eq!(forecaster.observe(81.0), None);
+        let forecast = forecaster.observe(96.0).unwrap();
+        assert_eq!((forecast.percent, forecast.predicted_bytes), (95, 96_000));
+        assert_eq!(forecaster.observe(120.0), None);
+
+        // A jump past both shares reports the highest once
+        let mut forecaster = SizeForecaster::new(1000.0, 100_000);
+        assert_eq!(forecaster.observe(99.0).unwrap().percent, 95);
+        assert_eq!(forecaster.observe(99.5), None);
+    }
+
+    #[tokio::test]
+    async fn test_check_upload() {
+        let notifier = Arc::new(MockNotifierService::new());
+        let size_guard = SizeGuard::new(notifier.clone());
+        let limit = WHISPER_UPLOAD_LIMIT_BYTES;
+
+        assert_eq!(
+            size_guard
+                .check_upload(48_000 * 60, 48_000, limit / 2)
+                .await
+                .unwrap(),
+            UploadCheck::Fits
+        );
+        assert!(notifier.get_sent_notifications().is_empty());
+
+        // An hour at 60MB: split, with a single warning
+        let check = size_guard
+            .check_upload(48_000 * 3600, 48_000, 60 * 1024 * 1024)
+            .await
+            .unwrap();
+        assert!(matches!(check, UploadCheck::Split { chunks } if chunks >= 3));
+        size_guard
+            .check_upload(48_000 * 3600, 48_000, 60 * 1024 * 1024)
+            .await
+            .unwrap();
+        assert_eq!(notifier.get_sent_notifications().len(), 1);
+
+        // Too dense to fit the chunk overlap: refused
+        assert!(size_guard
+            .check_upload(48_000 * 10, 48_000, limit * 100)
+            .await
+            .is_err());
+    }
 }
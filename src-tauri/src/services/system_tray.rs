@@ -7,6 +7,7 @@
 //! - Position persistence
 //! - Integration with global shortcuts
 //! - Startup notifications
+//! - Tray-only mode, where the menu replaces the main window
 
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -18,6 +19,8 @@ use tauri::{
 use thiserror::Error;
 use tokio::sync::Mutex;
 
+use crate::services::notifier::{Notifier, TauriNotifierService};
+
 /// Error types for system tray operations
 #[derive(Error, Debug)]
 pub enum SystemTrayError {
@@ -37,6 +40,9 @@ pub enum SystemTrayError {
     TauriError(#[from] tauri::Error),
 }
 
+/// ID of the tray icon
+const TRAY_ID: &str = "main-tray";
+
 /// Result type for system tray operations
 pub type SystemTrayResult<T> = Result<T, SystemTrayError>;
 
@@ -73,6 +79,8 @@ pub struct SystemTrayConfig {
     pub persist_window_position: bool,
     /// First launch behavior
     pub is_first_launch: bool,
+    /// Tray-only mode: no main window, the menu records and opens settings
+    pub tray_only: bool,
 }
 
 impl Default for SystemTrayConfig {
@@ -82,6 +90,7 @@ impl Default for SystemTrayConfig {
             global_shortcut: "CmdOrCtrl+Shift+F9".to_string(),
             persist_window_position: true,
             is_first_launch: false,
+            tray_only: false,
         }
     }
 }
@@ -109,62 +118,33 @@ impl SystemTrayService {
         }
     }
 
-    /// Get the app data directory for storing files
-    fn get_app_data_dir(&self) -> Result<std::path::PathBuf, SystemTrayError> {
-        // Try to get the app local data directory using Tauri's path API
-        match self.app_handle.path().app_local_data_dir() {
-            Ok(dir) => {
-                // Ensure the directory exists
-                if let Err(e) = std::fs::create_dir_all(&dir) {
-                    eprintln!("Warning: Failed to create app data directory: {}", e);
-                }
-                Ok(dir)
-            }
-            Err(_) => {
-                // Fallback: try to find settings.json location or use current directory
-                let settings_paths = vec![
-                    std::path::PathBuf::from("settings.json"),
-                    std::path::PathBuf::from("../settings.json"),
-                    std::path::PathBuf::from("../../settings.json"),
-                ];
-
-                for path in &settings_paths {
-                    if path.exists() {
-                        if let Some(parent) = path.parent() {
-                            return Ok(parent.to_path_buf());
-                        }
-                    }
-                }
+    /// Get the path for window state file
+    ///
+    /// A file left in the Tauri app data directory by older versions is moved
+    /// to the state directory.
+    fn get_window_state_path(&self) -> Result<std::path::PathBuf, SystemTrayError> {
+        let window_state_path = crate::paths::window_state_file();
+        if let Some(parent) = window_state_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                SystemTrayError::PositionPersistenceError(format!(
+                    "Failed to create {}: {}",
+                    parent.display(),
+                    e
+                ))
+            })?;
+        }
 
-                // Last resort: use current directory but only in release mode
-                #[cfg(debug_assertions)]
-                {
-                    // In development, avoid using current directory which might be src-tauri
-                    // Use temp directory or home directory instead
-                    if let Ok(home_dir) = std::env::var("HOME") {
-                        let app_dir = std::path::PathBuf::from(home_dir).join(".dicta-clerk");
-                        if let Err(e) = std::fs::create_dir_all(&app_dir) {
-                            eprintln!("Warning: Failed to create home app directory: {}", e);
-                        }
-                        Ok(app_dir)
-                    } else {
-                        // Use temp directory as last resort
-                        Ok(std::env::temp_dir().join("dicta-clerk"))
+        if !window_state_path.exists() {
+            if let Ok(legacy_dir) = self.app_handle.path().app_local_data_dir() {
+                let legacy_path = legacy_dir.join(crate::paths::WINDOW_STATE_FILE);
+                if legacy_path.exists() {
+                    if let Err(e) = std::fs::rename(&legacy_path, &window_state_path) {
+                        eprintln!("Warning: Failed to migrate window state: {}", e);
                     }
                 }
-
-                #[cfg(not(debug_assertions))]
-                {
-                    Ok(std::path::PathBuf::from("."))
-                }
             }
         }
-    }
-
-    /// Get the path for window state file
-    fn get_window_state_path(&self) -> Result<std::path::PathBuf, SystemTrayError> {
-        let app_dir = self.get_app_data_dir()?;
-        Ok(app_dir.join("window_state.json"))
+        Ok(window_state_path)
     }
 
     /// Initialize the system tray
@@ -191,25 +171,47 @@ impl SystemTrayService {
     /// Create the system tray icon with context menu
     async fn create_tray_icon(&self) -> SystemTrayResult<()> {
         // Create menu items
-        let show_hide = MenuItem::with_id(
-            &self.app_handle,
-            "show_hide",
-            "Show/Hide",
-            true,
-            None::<&str>,
-        )?;
         let separator = PredefinedMenuItem::separator(&self.app_handle)?;
         let quit = MenuItem::with_id(&self.app_handle, "quit", "Quit", true, None::<&str>)?;
 
-        // Create menu
-        let menu = Menu::with_items(&self.app_handle, &[&show_hide, &separator, &quit])?;
+        // Create menu (without a main window, recording and settings are
+        // reached from the menu)
+        let menu = if self.config.tray_only {
+            let toggle_record = MenuItem::with_id(
+                &self.app_handle,
+                "toggle_record",
+                "Start/Stop Recording",
+                true,
+                None::<&str>,
+            )?;
+            let settings = MenuItem::with_id(
+                &self.app_handle,
+                "settings",
+                "Settings...",
+                true,
+                None::<&str>,
+            )?;
+            Menu::with_items(
+                &self.app_handle,
+                &[&toggle_record, &settings, &separator, &quit],
+            )?
+        } else {
+            let show_hide = MenuItem::with_id(
+                &self.app_handle,
+                "show_hide",
+                "Show/Hide",
+                true,
+                None::<&str>,
+            )?;
+            Menu::with_items(&self.app_handle, &[&show_hide, &separator, &quit])?
+        };
 
         // Clone app handle for the event handler
         let app_handle_clone = self.app_handle.clone();
         let service_self = self.clone();
 
         // Create tray icon
-        let _tray = TrayIconBuilder::with_id("main-tray")
+        let _tray = TrayIconBuilder::with_id(TRAY_ID)
             .tooltip("DictaClerk")
             .menu(&menu)
             .on_menu_event(move |_app, event| {
@@ -262,6 +264,13 @@ impl SystemTrayService {
             "show_hide" => {
                 self.toggle_main_window().await?;
             }
+            // Routed by the backend listeners, like the global shortcut
+            "toggle_record" => {
+                self.app_handle.emit("global_shortcut_toggle_record", ())?;
+            }
+            "settings" => {
+                self.app_handle.emit("tray_open_settings", ())?;
+            }
             "quit" => {
                 self.quit_application().await?;
             }
@@ -272,14 +281,22 @@ impl SystemTrayService {
 
     /// Show startup notification
     async fn show_startup_notification(&self) -> SystemTrayResult<()> {
+        let message = format!(
+            "DictaClerk running - press {} to start",
+            self.config.global_shortcut
+        );
+
+        // No window listens in tray-only mode
+        if self.config.tray_only {
+            let notifier = TauriNotifierService::new(self.app_handle.clone());
+            if let Err(e) = notifier.success(&message).await {
+                eprintln!("Failed to show startup notification: {}", e);
+            }
+            return Ok(());
+        }
+
         // Emit system notification for UI only (not stateful)
-        if let Err(e) = self.app_handle.emit(
-            "system_notification",
-            format!(
-                "DictaClerk running - press {} to start",
-                self.config.global_shortcut
-            ),
-        ) {
+        if let Err(e) = self.app_handle.emit("system_notification", message) {
             eprintln!("Failed to emit startup notification: {}", e);
         }
         Ok(())
@@ -493,11 +510,18 @@ impl SystemTrayService {
         Ok(())
     }
 
-    /// Update tray tooltip with current status
+    /// Update tray tooltip with current status ("" = no status)
     pub async fn update_tray_status(&self, status: &str) -> SystemTrayResult<()> {
-        // Note: In Tauri 2.x, updating tray tooltip requires reconstructing the tray
-        // For now, we'll just log the status update
         println!("Tray status: {}", status);
+        let tooltip = if status.is_empty() {
+            "DictaClerk".to_string()
+        } else {
+            format!("DictaClerk - {}", status)
+        };
+        if let Some(tray) = self.app_handle.tray_by_id(TRAY_ID) {
+            tray.set_tooltip(Some(tooltip))
+                .map_err(|e| SystemTrayError::TrayUpdateFailed(e.to_string()))?;
+        }
         Ok(())
     }
 }
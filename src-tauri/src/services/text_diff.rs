@@ -0,0 +1,168 @@
+//! Word-level diff between two texts
+//!
+//! Used to show how two formatting results differ (profile comparison). The
+//! texts are split into words and whitespace runs, and the longest common
+//! subsequence of those tokens gives the unchanged parts; the rest is marked
+//! as deleted (only in the first text) or inserted (only in the second).
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::text_diff::{diff_words, DiffOp};
+//!
+//! let diff = diff_words("Hi John, thanks", "Hello John, thanks");
+//! assert_eq!(diff[0].op, DiffOp::Delete);
+//! assert_eq!(diff[0].text, "Hi");
+//! assert_eq!(diff[1].op, DiffOp::Insert);
+//! assert_eq!(diff[1].text, "Hello");
+//! ```
+
+use serde::Serialize;
+
+/// Largest token table compared word by word; longer texts are diffed as a
+/// whole (keeps the quadratic table in the megabytes)
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Kind of a diff segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffOp {
+    /// In both texts
+    Equal,
+    /// Only in the first text
+    Delete,
+    /// Only in the second text
+    Insert,
+}
+
+/// Run of text with the same diff kind
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffSegment {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+/// Words and whitespace runs of `text`, in order
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut in_whitespace = None;
+    for (index, c) in text.char_indices() {
+        let whitespace = c.is_whitespace();
+        if in_whitespace.is_some_and(|previous| previous != whitespace) {
+            tokens.push(&text[start..index]);
+            start = index;
+        }
+        in_whitespace = Some(whitespace);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+/// Diff `old` against `new` word by word
+pub fn diff_words(old: &str, new: &str) -> Vec<DiffSegment> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+
+    let mut segments: Vec<DiffSegment> = Vec::new();
+    let mut push = |op: DiffOp, token: &str| match segments.last_mut() {
+        Some(last) if last.op == op => last.text.push_str(token),
+        _ => segments.push(DiffSegment {
+            op,
+            text: token.to_string(),
+        }),
+    };
+
+    if (old_tokens.len() + 1) * (new_tokens.len() + 1) > MAX_DIFF_CELLS {
+        if old == new {
+            push(DiffOp::Equal, old);
+        } else {
+            push(DiffOp::Delete, old);
+            push(DiffOp::Insert, new);
+        }
+        return segments;
+    }
+
+    // lcs[i][j]: length of the common subsequence of old[i..] and new[j..]
+    let columns = new_tokens.len() + 1;
+    let mut lcs = vec![0u32; (old_tokens.len() + 1) * columns];
+    for i in (0..old_tokens.len()).rev() {
+        for j in (0..new_tokens.len()).rev() {
+            lcs[i * columns + j] = if old_tokens[i] == new_tokens[j] {
+                lcs[(i + 1) * columns + j + 1] + 1
+            } else {
+                lcs[(i + 1) * columns + j].max(lcs[i * columns + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old_tokens.len() && j < new_tokens.len() {
+        if old_tokens[i] == new_tokens[j] {
+            push(DiffOp::Equal, old_tokens[i]);
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * columns + j] >= lcs[i * columns + j + 1] {
+            push(DiffOp::Delete, old_tokens[i]);
+            i += 1;
+        } else {
+            push(DiffOp::Insert, new_tokens[j]);
+            j += 1;
+        }
+    }
+    for token in &old_tokens[i..] {
+        push(DiffOp::Delete, token);
+    }
+    for token in &new_tokens[j..] {
+        push(DiffOp::Insert, token);
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(diff: &[DiffSegment]) -> String {
+        diff.iter()
+            .map(|segment| match segment.op {
+                DiffOp::Equal => segment.text.clone(),
+                DiffOp::Delete => format!("[-{}-]", segment.text),
+                DiffOp::Insert => format!("{{+{}+}}", segment.text),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_words() {
+        let old = "Dear John,\nthanks for the quick reply.";
+        let new = "Hi John,\nthanks a lot for the reply!";
+        let diff = diff_words(old, new);
+        assert_eq!(
+            render(&diff),
+            "[-Dear-]{+Hi+} John,\nthanks {+a lot +}for the [-quick reply.-]{+reply!+}"
+        );
+
+        // Both texts are rebuilt from the segments
+        let side = |skip: DiffOp| -> String {
+            diff.iter()
+                .filter(|segment| segment.op != skip)
+                .map(|segment| segment.text.as_str())
+                .collect()
+        };
+        assert_eq!(side(DiffOp::Insert), old);
+        assert_eq!(side(DiffOp::Delete), new);
+
+        assert_eq!(
+            diff_words("same text", "same text"),
+            vec![DiffSegment {
+                op: DiffOp::Equal,
+                text: "same text".to_string(),
+            }]
+        );
+        assert!(diff_words("", "").is_empty());
+        assert_eq!(render(&diff_words("", "new")), "{+new+}");
+    }
+}
@@ -0,0 +1,260 @@
+//! Local transcript layout built from segment timing
+//!
+//! Whisper segments carry start and end times, which are enough to mark the
+//! transcript with `[hh:mm:ss]` timestamps, either before every segment or at
+//! paragraph boundaries (a new paragraph starts after a long pause). This is
+//! what meeting minutes and interview logs need, without involving GPT.
+//!
+//! The same timing splits unformatted transcripts into paragraphs
+//! ([`paragraphize`]): a paragraph ends at a sentence boundary followed by a
+//! long pause, or by a shorter one once the paragraph has grown long.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::transcript_format::{timestamped_transcript, TimestampMode};
+//! use dicta_clerk_lib::services::TranscriptionSegment;
+//!
+//! let segments = vec![TranscriptionSegment {
+//!     id: 0,
+//!     start: 83.0,
+//!     end: 85.0,
+//!     text: " Next item.".to_string(),
+//!     avg_logprob: None,
+//!     compression_ratio: None,
+//!     no_speech_prob: None,
+//! }];
+//!
+//! let text = timestamped_transcript(&segments, TimestampMode::Segment).unwrap();
+//! assert_eq!(text, "[00:01:23] Next item.");
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::services::whisper_client::TranscriptionSegment;
+
+/// Silence (seconds) between two segments that starts a new paragraph
+pub const PARAGRAPH_PAUSE_SECONDS: f64 = 2.0;
+
+/// Shorter pause that ends a paragraph once it has `MAX_SENTENCES_PER_PARAGRAPH` sentences
+const SENTENCE_PAUSE_SECONDS: f64 = 0.7;
+
+/// Sentences after which a paragraph is split at the next short pause
+const MAX_SENTENCES_PER_PARAGRAPH: usize = 5;
+
+/// Where timestamps are inserted in the final text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampMode {
+    /// No timestamps
+    #[default]
+    Off,
+    /// One timestamp at the start of each paragraph
+    Paragraph,
+    /// One timestamp per segment, each on its own line
+    Segment,
+}
+
+/// `[hh:mm:ss]` label of a time offset
+pub fn format_timestamp(seconds: f64) -> String {
+    let total = seconds.max(0.0).floor() as u64;
+    format!(
+        "[{:02}:{:02}:{:02}]",
+        total / 3600,
+        (total / 60) % 60,
+        total % 60
+    )
+}
+
+/// Group segments into paragraphs, starting a new one after a pause of `min_pause` seconds
+pub fn paragraphs(
+    segments: &[TranscriptionSegment],
+    min_pause: f64,
+) -> Vec<Vec<&TranscriptionSegment>> {
+    let mut paragraphs: Vec<Vec<&TranscriptionSegment>> = Vec::new();
+    for segment in segments {
+        match paragraphs.last_mut() {
+            Some(current)
+                if current
+                    .last()
+                    .is_some_and(|last| segment.start - last.end < min_pause) =>
+            {
+                current.push(segment)
+            }
+            _ => paragraphs.push(vec![segment]),
+        }
+    }
+    paragraphs
+}
+
+/// Transcript marked with timestamps, or None when `mode` is off or there are no segments
+pub fn timestamped_transcript(
+    segments: &[TranscriptionSegment],
+    mode: TimestampMode,
+) -> Option<String> {
+    if segments.is_empty() {
+        return None;
+    }
+
+    let lines: Vec<String> = match mode {
+        TimestampMode::Off => return None,
+        TimestampMode::Segment => segments
+            .iter()
+            .filter(|segment| !segment.text.trim().is_empty())
+            .map(|segment| {
+                format!(
+                    "{} {}",
+                    format_timestamp(segment.start),
+                    segment.text.trim()
+                )
+            })
+            .collect(),
+        TimestampMode::Paragraph => paragraphs(segments, PARAGRAPH_PAUSE_SECONDS)
+            .into_iter()
+            .filter_map(|paragraph| {
+                let text = join_segments(&paragraph);
+                (!text.is_empty())
+                    .then(|| format!("{} {}", format_timestamp(paragraph[0].start), text))
+            })
+            .collect(),
+    };
+
+    let separator = if mode == TimestampMode::Paragraph {
+        "\n\n"
+    } else {
+        "\n"
+    };
+    Some(lines.join(separator))
+}
+
+/// Transcript split into paragraphs at pauses that follow a sentence end
+///
+/// Returns None when there are no segments or everything fits in one paragraph.
+pub fn paragraphize(segments: &[TranscriptionSegment]) -> Option<String> {
+    let mut paragraphs: Vec<Vec<&TranscriptionSegment>> = Vec::new();
+    let mut sentences = 0;
+    for segment in segments {
+        let split = match paragraphs.last().and_then(|current| current.last()) {
+            Some(previous) if ends_sentence(&previous.text) => {
+                let pause = segment.start - previous.end;
+                pause >= PARAGRAPH_PAUSE_SECONDS
+                    || (pause >= SENTENCE_PAUSE_SECONDS && sentences >= MAX_SENTENCES_PER_PARAGRAPH)
+            }
+            Some(_) => false,
+            None => true,
+        };
+        if split {
+            paragraphs.push(Vec::new());
+            sentences = 0;
+        }
+        sentences += count_sentences(&segment.text);
+        if let Some(current) = paragraphs.last_mut() {
+            current.push(segment);
+        }
+    }
+
+    let paragraphs: Vec<String> = paragraphs
+        .iter()
+        .map(|paragraph| join_segments(paragraph))
+        .filter(|text| !text.is_empty())
+        .collect();
+    (paragraphs.len() > 1).then(|| paragraphs.join("\n\n"))
+}
+
+/// Whether `text` ends with sentence punctuation (closing quotes allowed)
+fn ends_sentence(text: &str) -> bool {
+    text.trim_end()
+        .trim_end_matches(['"', '\'', ')', '»', '”'])
+        .ends_with(['.', '!', '?', '…'])
+}
+
+/// Number of sentence endings in `text`
+fn count_sentences(text: &str) -> usize {
+    text.split_whitespace()
+        .filter(|word| ends_sentence(word))
+        .count()
+}
+
+/// Texts of consecutive segments joined with single spaces
+fn join_segments(segments: &[&TranscriptionSegment]) -> String {
+    segments
+        .iter()
+        .map(|segment| segment.text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(id: u32, start: f64, end: f64, text: &str) -> TranscriptionSegment {
+        TranscriptionSegment {
+            id,
+            start,
+            end,
+            text: text.to_string(),
+            avg_logprob: None,
+            compression_ratio: None,
+            no_speech_prob: None,
+        }
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0.0), "[00:00:00]");
+        assert_eq!(format_timestamp(83.9), "[00:01:23]");
+        assert_eq!(format_timestamp(3725.0), "[01:02:05]");
+    }
+
+    #[test]
+    fn test_timestamped_transcript_modes() {
+        let segments = vec![
+            segment(0, 0.0, 3.0, " Welcome everyone."),
+            segment(1, 3.2, 6.0, " First, the budget."),
+            segment(2, 9.5, 12.0, " Next item."),
+        ];
+
+        assert_eq!(timestamped_transcript(&segments, TimestampMode::Off), None);
+        assert_eq!(
+            timestamped_transcript(&segments, TimestampMode::Segment).unwrap(),
+            "[00:00:00] Welcome everyone.\n[00:00:03] First, the budget.\n[00:00:09] Next item."
+        );
+        assert_eq!(
+            timestamped_transcript(&segments, TimestampMode::Paragraph).unwrap(),
+            "[00:00:00] Welcome everyone. First, the budget.\n\n[00:00:09] Next item."
+        );
+        assert_eq!(timestamped_transcript(&[], TimestampMode::Segment), None);
+    }
+
+    #[test]
+    fn test_paragraphize_splits_at_pauses_after_sentences() {
+        let segments = vec![
+            segment(0, 0.0, 3.0, " Thanks for the report."),
+            segment(1, 3.1, 5.0, " I have two remarks"),
+            // Long pause mid-sentence: no split
+            segment(2, 8.0, 10.0, " about the budget."),
+            segment(3, 13.0, 15.0, " Second, the schedule."),
+        ];
+
+        assert_eq!(
+            paragraphize(&segments).unwrap(),
+            "Thanks for the report. I have two remarks about the budget.\n\nSecond, the schedule."
+        );
+        assert_eq!(paragraphize(&segments[..2]), None);
+        assert_eq!(paragraphize(&[]), None);
+    }
+
+    #[test]
+    fn test_paragraphize_splits_long_paragraphs_at_short_pauses() {
+        let segments: Vec<_> = (0..7)
+            .map(|i| segment(i, i as f64 * 3.0, i as f64 * 3.0 + 2.0, " One sentence."))
+            .collect();
+
+        let text = paragraphize(&segments).unwrap();
+        let paragraphs: Vec<&str> = text.split("\n\n").collect();
+        assert_eq!(paragraphs.len(), 2);
+        assert_eq!(paragraphs[0].matches("One sentence.").count(), 5);
+    }
+}
@@ -0,0 +1,293 @@
+//! Queue of background transcription jobs
+//!
+//! Recordings imported in bulk are queued instead of going one by one through
+//! the dictation workflow. The queue only schedules: it hands out the next job
+//! by priority (oldest first within a priority) while fewer than
+//! `max_concurrent_jobs` run, and keeps the status and result of every job
+//! until they are cleared. Running the jobs is left to the caller.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::transcription_queue::{JobPriority, TranscriptionQueue};
+//! use std::path::PathBuf;
+//!
+//! let mut queue = TranscriptionQueue::new(2);
+//! queue.enqueue(PathBuf::from("meeting.wav"), JobPriority::Normal, None);
+//! queue.enqueue(PathBuf::from("urgent.wav"), JobPriority::High, None);
+//!
+//! while let Some(job) = queue.start_next() {
+//!     // ... transcribe job.path, then report the outcome
+//!     queue.complete(&job.id, Ok("Transcript".to_string()));
+//! }
+//! ```
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Errors in queue operations
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum QueueError {
+    #[error("Transcription job not found: {0}")]
+    NotFound(String),
+    #[error("Transcription job {0} has already started")]
+    AlreadyStarted(String),
+}
+
+/// Result type for queue operations
+pub type QueueResult<T> = Result<T, QueueError>;
+
+/// Priority of a job; higher priorities start first
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum JobPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+/// Lifecycle of a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    /// Removed from the queue before it started
+    Cancelled,
+}
+
+impl JobStatus {
+    /// The job will not change anymore
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// A recording to transcribe (`transcription-job-status` event payload)
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionJob {
+    pub id: String,
+    /// WAV recording to transcribe
+    pub path: PathBuf,
+    pub priority: JobPriority,
+    /// Whisper prompt sent with the recording
+    pub prompt: Option<String>,
+    pub status: JobStatus,
+    /// RFC 3339 time the job was queued
+    pub queued_at: String,
+    /// Transcript, once completed
+    pub transcript: Option<String>,
+    /// Reason of the failure, once failed
+    pub error: Option<String>,
+    /// Enqueue order, which breaks priority ties
+    #[serde(skip)]
+    sequence: u64,
+}
+
+/// Scheduler of the transcription jobs
+#[derive(Debug)]
+pub struct TranscriptionQueue {
+    jobs: Vec<TranscriptionJob>,
+    max_concurrent_jobs: usize,
+    next_sequence: u64,
+}
+
+impl TranscriptionQueue {
+    /// Create an empty queue running up to `max_concurrent_jobs` jobs at once
+    pub fn new(max_concurrent_jobs: usize) -> Self {
+        Self {
+            jobs: Vec::new(),
+            max_concurrent_jobs: max_concurrent_jobs.max(1),
+            next_sequence: 0,
+        }
+    }
+
+    /// Change the concurrency limit; running jobs are left to finish
+    pub fn set_max_concurrent_jobs(&mut self, max_concurrent_jobs: usize) {
+        self.max_concurrent_jobs = max_concurrent_jobs.max(1);
+    }
+
+    /// Jobs allowed to run at the same time
+    pub fn max_concurrent_jobs(&self) -> usize {
+        self.max_concurrent_jobs
+    }
+
+    /// Add a job for `path`; returns it as queued
+    pub fn enqueue(
+        &mut self,
+        path: PathBuf,
+        priority: JobPriority,
+        prompt: Option<String>,
+    ) -> TranscriptionJob {
+        let job = TranscriptionJob {
+            id: uuid::Uuid::new_v4().to_string(),
+            path,
+            priority,
+            prompt,
+            status: JobStatus::Queued,
+            queued_at: Local::now().to_rfc3339(),
+            transcript: None,
+            error: None,
+            sequence: self.next_sequence,
+        };
+        self.next_sequence += 1;
+        self.jobs.push(job.clone());
+        job
+    }
+
+    /// Mark the next job as running and return it, unless the concurrency
+    /// limit is reached or nothing is queued
+    pub fn start_next(&mut self) -> Option<TranscriptionJob> {
+        if self.running() >= self.max_concurrent_jobs {
+            return None;
+        }
+        let job = self
+            .jobs
+            .iter_mut()
+            .filter(|job| job.status == JobStatus::Queued)
+            .max_by(|a, b| {
+                a.priority
+                    .cmp(&b.priority)
+                    .then_with(|| b.sequence.cmp(&a.sequence))
+            })?;
+        job.status = JobStatus::Running;
+        Some(job.clone())
+    }
+
+    /// Record the outcome of a running job; returns it as finished
+    pub fn complete(
+        &mut self,
+        id: &str,
+        result: Result<String, String>,
+    ) -> QueueResult<TranscriptionJob> {
+        let job = self.job_mut(id)?;
+        match result {
+            Ok(transcript) => {
+                job.status = JobStatus::Completed;
+                job.transcript = Some(transcript);
+            }
+            Err(error) => {
+                job.status = JobStatus::Failed;
+                job.error = Some(error);
+            }
+        }
+        Ok(job.clone())
+    }
+
+    /// Cancel a job that has not started yet
+    pub fn cancel(&mut self, id: &str) -> QueueResult<TranscriptionJob> {
+        let job = self.job_mut(id)?;
+        match job.status {
+            JobStatus::Queued => {
+                job.status = JobStatus::Cancelled;
+                Ok(job.clone())
+            }
+            JobStatus::Cancelled => Ok(job.clone()),
+            _ => Err(QueueError::AlreadyStarted(id.to_string())),
+        }
+    }
+
+    /// Forget the finished jobs; returns how many were removed
+    pub fn clear_finished(&mut self) -> usize {
+        let before = self.jobs.len();
+        self.jobs.retain(|job| !job.status.is_finished());
+        before - self.jobs.len()
+    }
+
+    /// Every job, in enqueue order
+    pub fn jobs(&self) -> &[TranscriptionJob] {
+        &self.jobs
+    }
+
+    /// Jobs currently running
+    pub fn running(&self) -> usize {
+        self.jobs
+            .iter()
+            .filter(|job| job.status == JobStatus::Running)
+            .count()
+    }
+
+    fn job_mut(&mut self, id: &str) -> QueueResult<&mut TranscriptionJob> {
+        self.jobs
+            .iter_mut()
+            .find(|job| job.id == id)
+            .ok_or_else(|| QueueError::NotFound(id.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enqueue(queue: &mut TranscriptionQueue, name: &str, priority: JobPriority) -> String {
+        queue.enqueue(PathBuf::from(name), priority, None).id
+    }
+
+    #[test]
+    fn test_priority_order_and_concurrency() {
+        let mut queue = TranscriptionQueue::new(2);
+        enqueue(&mut queue, "first.wav", JobPriority::Normal);
+        enqueue(&mut queue, "second.wav", JobPriority::Normal);
+        enqueue(&mut queue, "background.wav", JobPriority::Low);
+        enqueue(&mut queue, "urgent.wav", JobPriority::High);
+
+        let urgent = queue.start_next().unwrap();
+        assert_eq!(urgent.path, PathBuf::from("urgent.wav"));
+        assert_eq!(urgent.status, JobStatus::Running);
+        let first = queue.start_next().unwrap();
+        assert_eq!(first.path, PathBuf::from("first.wav"));
+        // Two jobs running: the limit is reached
+        assert!(queue.start_next().is_none());
+
+        let done = queue
+            .complete(&urgent.id, Ok("Transcript".to_string()))
+            .unwrap();
+        assert_eq!(done.status, JobStatus::Completed);
+        assert_eq!(done.transcript.as_deref(), Some("Transcript"));
+        assert_eq!(
+            queue.start_next().unwrap().path,
+            PathBuf::from("second.wav")
+        );
+
+        let failed = queue
+            .complete(&first.id, Err("Upload failed".to_string()))
+            .unwrap();
+        assert_eq!(failed.status, JobStatus::Failed);
+        assert_eq!(
+            queue.start_next().unwrap().path,
+            PathBuf::from("background.wav")
+        );
+        assert!(queue.start_next().is_none());
+        assert_eq!(queue.running(), 2);
+    }
+
+    #[test]
+    fn test_cancel_and_clear() {
+        let mut queue = TranscriptionQueue::new(1);
+        let running = enqueue(&mut queue, "running.wav", JobPriority::Normal);
+        let waiting = enqueue(&mut queue, "waiting.wav", JobPriority::Normal);
+        queue.start_next().unwrap();
+
+        assert_eq!(
+            queue.cancel(&running).unwrap_err(),
+            QueueError::AlreadyStarted(running.clone())
+        );
+        assert_eq!(queue.cancel(&waiting).unwrap().status, JobStatus::Cancelled);
+        assert_eq!(
+            queue.cancel("missing").unwrap_err(),
+            QueueError::NotFound("missing".to_string())
+        );
+
+        queue.complete(&running, Ok(String::new())).unwrap();
+        assert!(queue.start_next().is_none());
+        assert_eq!(queue.clear_finished(), 2);
+        assert!(queue.jobs().is_empty());
+    }
+}
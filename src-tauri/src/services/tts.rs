@@ -0,0 +1,312 @@
+//! Text-to-speech for proof-listening the final text
+//!
+//! Two backends are supported: the operating system's speech synthesizer
+//! (`say` on macOS, Speech Dispatcher or eSpeak on Linux, `System.Speech` on
+//! Windows) and the OpenAI speech endpoint, whose audio is played through
+//! [`crate::audio::playback`]. Either way the result is a [`Speech`] that can
+//! be polled and stopped.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::tts::{speak_with_system, TtsOptions};
+//!
+//! let mut speech = speak_with_system("Hello", &TtsOptions::default()).unwrap();
+//! speech.stop();
+//! ```
+
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::audio::playback::{self, AudioClip, PlaybackError, PlaybackHandle};
+
+/// OpenAI speech endpoint
+pub const OPENAI_SPEECH_ENDPOINT: &str = "https://api.openai.com/v1/audio/speech";
+
+/// OpenAI speech model
+const OPENAI_SPEECH_MODEL: &str = "tts-1";
+
+/// OpenAI voice used when none is configured
+const OPENAI_DEFAULT_VOICE: &str = "alloy";
+
+/// Speaking rate range accepted by the backends (1.0 = normal)
+pub const MIN_SPEED: f32 = 0.25;
+pub const MAX_SPEED: f32 = 4.0;
+
+/// Words per minute of the macOS and eSpeak synthesizers at normal speed
+const BASE_WORDS_PER_MINUTE: f32 = 175.0;
+
+/// Errors that can occur while speaking
+#[derive(Error, Debug)]
+pub enum TtsError {
+    #[error("Nothing to speak")]
+    EmptyText,
+    #[error("No speech synthesizer found ({0})")]
+    SynthesizerUnavailable(String),
+    #[error("Speech synthesizer failed: {0}")]
+    Synthesizer(String),
+    #[error("OpenAI API key is not configured")]
+    MissingApiKey,
+    #[error("Speech request failed: {0}")]
+    Request(String),
+    #[error("Playback failed: {0}")]
+    Playback(#[from] PlaybackError),
+}
+
+/// Result type for text-to-speech operations
+pub type TtsResult<T> = Result<T, TtsError>;
+
+/// Speech backend
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TtsProvider {
+    /// Speech synthesizer of the operating system
+    #[default]
+    System,
+    /// OpenAI text-to-speech API
+    Openai,
+}
+
+/// Voice and speaking rate
+#[derive(Debug, Clone, PartialEq)]
+pub struct TtsOptions {
+    /// Voice name (None = backend default)
+    pub voice: Option<String>,
+    /// Speaking rate multiplier (1.0 = normal)
+    pub speed: f32,
+}
+
+impl Default for TtsOptions {
+    fn default() -> Self {
+        Self {
+            voice: None,
+            speed: 1.0,
+        }
+    }
+}
+
+impl TtsOptions {
+    fn speed(&self) -> f32 {
+        self.speed.clamp(MIN_SPEED, MAX_SPEED)
+    }
+
+    fn voice(&self) -> Option<&str> {
+        self.voice
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    }
+}
+
+/// Speech in progress
+#[derive(Debug)]
+pub enum Speech {
+    /// System synthesizer process
+    Process(Child),
+    /// Synthesized audio being played
+    Audio(PlaybackHandle),
+}
+
+impl Speech {
+    /// Whether speaking ended or was stopped
+    pub fn is_finished(&mut self) -> bool {
+        match self {
+            Speech::Process(child) => !matches!(child.try_wait(), Ok(None)),
+            Speech::Audio(handle) => handle.is_finished(),
+        }
+    }
+
+    /// Stop speaking
+    pub fn stop(&mut self) {
+        match self {
+            Speech::Process(child) => {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+            Speech::Audio(handle) => handle.stop(),
+        }
+    }
+}
+
+/// Speak `text` with the operating system's synthesizer
+pub fn speak_with_system(text: &str, options: &TtsOptions) -> TtsResult<Speech> {
+    if text.trim().is_empty() {
+        return Err(TtsError::EmptyText);
+    }
+
+    let mut tried = Vec::new();
+    for mut command in system_speech_commands(options) {
+        let program = command.get_program().to_string_lossy().into_owned();
+        let spawned = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                tried.push(program);
+                continue;
+            }
+            Err(e) => return Err(TtsError::Synthesizer(e.to_string())),
+        };
+
+        // The text goes through stdin so it is never parsed as arguments
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(text.as_bytes())
+                .map_err(|e| TtsError::Synthesizer(e.to_string()))?;
+        }
+        return Ok(Speech::Process(child));
+    }
+
+    Err(TtsError::SynthesizerUnavailable(format!(
+        "tried {}",
+        tried.join(", ")
+    )))
+}
+
+/// Candidate synthesizer commands for this platform, in order of preference
+fn system_speech_commands(options: &TtsOptions) -> Vec<Command> {
+    let speed = options.speed();
+    let words_per_minute = (BASE_WORDS_PER_MINUTE * speed).round() as u32;
+
+    #[cfg(target_os = "macos")]
+    {
+        let mut say = Command::new("say");
+        say.args(["-r", &words_per_minute.to_string(), "-f", "-"]);
+        if let Some(voice) = options.voice() {
+            say.args(["-v", voice]);
+        }
+        vec![say]
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let _ = words_per_minute;
+        let rate = ((speed - 1.0) * 10.0).round().clamp(-10.0, 10.0) as i32;
+        let select_voice = options
+            .voice()
+            .map(|voice| format!("$s.SelectVoice('{}'); ", voice.replace('\'', "''")))
+            .unwrap_or_default();
+        let script = format!(
+            "Add-Type -AssemblyName System.Speech; \
+             $s = New-Object System.Speech.Synthesis.SpeechSynthesizer; \
+             {}$s.Rate = {}; $s.Speak([Console]::In.ReadToEnd())",
+            select_voice, rate
+        );
+        let mut powershell = Command::new("powershell");
+        powershell.args(["-NoProfile", "-NonInteractive", "-Command", &script]);
+        vec![powershell]
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let rate = ((speed - 1.0) * 100.0).round().clamp(-100.0, 100.0) as i32;
+        let mut spd_say = Command::new("spd-say");
+        spd_say.args(["--wait", "--pipe-mode", "-r", &rate.to_string()]);
+        if let Some(voice) = options.voice() {
+            spd_say.args(["-y", voice]);
+        }
+
+        let mut espeak = Command::new("espeak");
+        espeak.args(["--stdin", "-s", &words_per_minute.to_string()]);
+        if let Some(voice) = options.voice() {
+            espeak.args(["-v", voice]);
+        }
+        vec![spd_say, espeak]
+    }
+}
+
+/// Synthesize `text` with the OpenAI speech endpoint, returning WAV bytes
+pub async fn synthesize_with_openai(
+    api_key: &str,
+    text: &str,
+    options: &TtsOptions,
+) -> TtsResult<Vec<u8>> {
+    if text.trim().is_empty() {
+        return Err(TtsError::EmptyText);
+    }
+    if api_key.trim().is_empty() {
+        return Err(TtsError::MissingApiKey);
+    }
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(60))
+        .build()
+        .map_err(|e| TtsError::Request(e.to_string()))?;
+    let response = client
+        .post(OPENAI_SPEECH_ENDPOINT)
+        .bearer_auth(api_key)
+        .json(&speech_request_body(text, options))
+        .send()
+        .await
+        .map_err(|e| TtsError::Request(e.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(TtsError::Request(format!("HTTP {}: {}", status, body)));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| TtsError::Request(e.to_string()))?;
+    Ok(bytes.to_vec())
+}
+
+/// Speak `text` with the OpenAI voices
+pub async fn speak_with_openai(
+    api_key: &str,
+    text: &str,
+    options: &TtsOptions,
+) -> TtsResult<Speech> {
+    let wav = synthesize_with_openai(api_key, text, options).await?;
+    let clip = AudioClip::from_wav_bytes(&wav)?;
+    Ok(Speech::Audio(playback::play(clip)?))
+}
+
+/// JSON body of an OpenAI speech request
+fn speech_request_body(text: &str, options: &TtsOptions) -> serde_json::Value {
+    serde_json::json!({
+        "model": OPENAI_SPEECH_MODEL,
+        "input": text,
+        "voice": options.voice().unwrap_or(OPENAI_DEFAULT_VOICE),
+        "speed": options.speed(),
+        "response_format": "wav",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speech_request_body() {
+        let body = speech_request_body("Hello", &TtsOptions::default());
+        assert_eq!(body["voice"], OPENAI_DEFAULT_VOICE);
+        assert_eq!(body["speed"], 1.0);
+        assert_eq!(body["response_format"], "wav");
+
+        let options = TtsOptions {
+            voice: Some(" nova ".to_string()),
+            speed: 10.0,
+        };
+        let body = speech_request_body("Hello", &options);
+        assert_eq!(body["voice"], "nova");
+        assert_eq!(body["speed"], MAX_SPEED);
+    }
+
+    #[test]
+    fn test_empty_text_is_rejected() {
+        assert!(matches!(
+            speak_with_system("  ", &TtsOptions::default()),
+            Err(TtsError::EmptyText)
+        ));
+    }
+}
@@ -0,0 +1,306 @@
+//! Usage tracking of the transcription API
+//!
+//! Every recording transcribed with the OpenAI API adds its audio duration and
+//! estimated cost to the day it was transcribed, in `usage.json` next to
+//! profiles.json. Days are summed into months for the statistics, so users can
+//! keep an eye on their OpenAI spend. The cost is an estimate: it applies the
+//! published per-minute price of the model to the audio duration.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use chrono::Local;
+//! use dicta_clerk_lib::services::usage::{whisper_price_per_minute, UsageTracker};
+//! use dicta_clerk_lib::services::WhisperProvider;
+//! use std::path::PathBuf;
+//!
+//! let tracker = UsageTracker::new(PathBuf::from("usage.json"));
+//! let today = Local::now().date_naive();
+//! let price = whisper_price_per_minute(WhisperProvider::OpenAi, "whisper-1");
+//! tracker.record(today, 90.0, price).unwrap();
+//!
+//! let stats = tracker.stats(today).unwrap();
+//! println!("{:.1} min, ${:.3} this month", stats.this_month.minutes(), stats.this_month.cost_usd);
+//! ```
+
+use chrono::{Datelike, Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+use crate::services::whisper_client::WhisperProvider;
+
+/// File name of the usage log, next to profiles.json
+pub const USAGE_FILE: &str = "usage.json";
+
+/// Price of `whisper-1` and `gpt-4o-transcribe`, in USD per minute of audio
+pub const WHISPER_PRICE_PER_MINUTE_USD: f64 = 0.006;
+
+/// Price of `gpt-4o-mini-transcribe`, in USD per minute of audio
+pub const MINI_TRANSCRIBE_PRICE_PER_MINUTE_USD: f64 = 0.003;
+
+/// Days listed in the statistics, today included
+pub const STATS_DAYS: u64 = 31;
+
+/// Errors that can occur while reading or writing the usage log
+#[derive(Error, Debug)]
+pub enum UsageError {
+    #[error("IO error on {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Invalid usage file: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Result type for usage operations
+pub type UsageResult<T> = Result<T, UsageError>;
+
+/// Audio transcribed and its estimated cost
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct Usage {
+    /// Transcription requests (one per recording, whatever its chunks)
+    pub requests: u32,
+    /// Audio duration sent to the API, in seconds
+    pub seconds: f64,
+    pub cost_usd: f64,
+}
+
+impl Usage {
+    /// Audio duration in minutes
+    pub fn minutes(&self) -> f64 {
+        self.seconds / 60.0
+    }
+
+    fn add(&mut self, other: &Usage) {
+        self.requests += other.requests;
+        self.seconds += other.seconds;
+        self.cost_usd += other.cost_usd;
+    }
+}
+
+/// Usage of one day
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DailyUsage {
+    /// `YYYY-MM-DD`
+    pub day: String,
+    pub usage: Usage,
+}
+
+/// Usage of one month
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MonthlyUsage {
+    /// `YYYY-MM`
+    pub month: String,
+    pub usage: Usage,
+}
+
+/// Usage statistics for the settings window
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct UsageStats {
+    pub today: Usage,
+    pub this_month: Usage,
+    pub total: Usage,
+    /// The last `STATS_DAYS` days, oldest first (days without usage included)
+    pub daily: Vec<DailyUsage>,
+    /// Every month with usage, oldest first
+    pub monthly: Vec<MonthlyUsage>,
+}
+
+/// Usage log as stored on disk: usage per `YYYY-MM-DD` day
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UsageLog {
+    #[serde(default)]
+    pub days: BTreeMap<String, Usage>,
+}
+
+/// Estimated price of a minute of audio transcribed by `model` (free offline)
+pub fn whisper_price_per_minute(provider: WhisperProvider, model: &str) -> f64 {
+    match provider {
+        WhisperProvider::Local => 0.0,
+        WhisperProvider::OpenAi if model.contains("mini") => MINI_TRANSCRIBE_PRICE_PER_MINUTE_USD,
+        WhisperProvider::OpenAi => WHISPER_PRICE_PER_MINUTE_USD,
+    }
+}
+
+/// Duration of a WAV recording in seconds
+pub fn wav_duration_seconds(path: &Path) -> Option<f64> {
+    let reader = hound::WavReader::open(path).ok()?;
+    let sample_rate = reader.spec().sample_rate;
+    (sample_rate > 0).then(|| f64::from(reader.duration()) / f64::from(sample_rate))
+}
+
+fn day_key(day: NaiveDate) -> String {
+    day.format("%Y-%m-%d").to_string()
+}
+
+/// Usage log file store
+#[derive(Debug, Clone)]
+pub struct UsageTracker {
+    path: PathBuf,
+}
+
+impl UsageTracker {
+    /// Create a tracker backed by the given JSON file
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Load the usage log (empty when the file does not exist)
+    pub fn load(&self) -> UsageResult<UsageLog> {
+        if !self.path.exists() {
+            return Ok(UsageLog::default());
+        }
+
+        let content = std::fs::read_to_string(&self.path).map_err(|e| self.io_error(e))?;
+        if content.trim().is_empty() {
+            return Ok(UsageLog::default());
+        }
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Add a transcription of `seconds` of audio to `day`; returns its
+    /// estimated cost
+    pub fn record(&self, day: NaiveDate, seconds: f64, price_per_minute: f64) -> UsageResult<f64> {
+        let seconds = seconds.max(0.0);
+        let cost_usd = seconds / 60.0 * price_per_minute;
+        let mut log = self.load()?;
+        log.days.entry(day_key(day)).or_default().add(&Usage {
+            requests: 1,
+            seconds,
+            cost_usd,
+        });
+        self.save(&log)?;
+        Ok(cost_usd)
+    }
+
+    /// Statistics as of `today`
+    pub fn stats(&self, today: NaiveDate) -> UsageResult<UsageStats> {
+        Ok(compile_stats(&self.load()?, today))
+    }
+
+    fn save(&self, log: &UsageLog) -> UsageResult<()> {
+        let parent_dir = self.path.parent().unwrap_or_else(|| Path::new("."));
+        if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
+            std::fs::create_dir_all(parent_dir).map_err(|e| self.io_error(e))?;
+        }
+
+        let content = serde_json::to_string_pretty(log)?;
+        let mut temp_file = NamedTempFile::new_in(parent_dir).map_err(|e| self.io_error(e))?;
+        temp_file
+            .write_all(content.as_bytes())
+            .map_err(|e| self.io_error(e))?;
+        temp_file
+            .persist(&self.path)
+            .map_err(|e| self.io_error(e.error))?;
+        Ok(())
+    }
+
+    fn io_error(&self, source: std::io::Error) -> UsageError {
+        UsageError::Io {
+            path: self.path.to_string_lossy().to_string(),
+            source,
+        }
+    }
+}
+
+/// Sum the usage log into statistics as of `today`
+pub fn compile_stats(log: &UsageLog, today: NaiveDate) -> UsageStats {
+    let this_month = format!("{:04}-{:02}", today.year(), today.month());
+    let mut total = Usage::default();
+    let mut months: BTreeMap<String, Usage> = BTreeMap::new();
+    for (day, usage) in &log.days {
+        total.add(usage);
+        // Day keys are `YYYY-MM-DD`, their first 7 characters the month
+        if let Some(month) = day.get(..7) {
+            months.entry(month.to_string()).or_default().add(usage);
+        }
+    }
+
+    let daily = (0..STATS_DAYS)
+        .rev()
+        .filter_map(|ago| today.checked_sub_days(Days::new(ago)))
+        .map(|day| {
+            let day = day_key(day);
+            let usage = log.days.get(&day).copied().unwrap_or_default();
+            DailyUsage { day, usage }
+        })
+        .collect();
+
+    UsageStats {
+        today: log.days.get(&day_key(today)).copied().unwrap_or_default(),
+        this_month: months.get(&this_month).copied().unwrap_or_default(),
+        total,
+        daily,
+        monthly: months
+            .into_iter()
+            .map(|(month, usage)| MonthlyUsage { month, usage })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn date(day: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(day, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_record_and_stats() {
+        let temp_dir = TempDir::new().unwrap();
+        let tracker = UsageTracker::new(temp_dir.path().join(USAGE_FILE));
+        assert_eq!(tracker.load().unwrap(), UsageLog::default());
+
+        let cost = tracker
+            .record(date("2026-09-30"), 120.0, WHISPER_PRICE_PER_MINUTE_USD)
+            .unwrap();
+        assert!((cost - 0.012).abs() < 1e-9);
+        tracker
+            .record(date("2026-10-02"), 60.0, WHISPER_PRICE_PER_MINUTE_USD)
+            .unwrap();
+        tracker.record(date("2026-10-02"), 30.0, 0.0).unwrap();
+
+        let stats = tracker.stats(date("2026-10-02")).unwrap();
+        assert_eq!(stats.today.requests, 2);
+        assert!((stats.today.minutes() - 1.5).abs() < 1e-9);
+        assert!((stats.today.cost_usd - 0.006).abs() < 1e-9);
+        assert_eq!(stats.this_month.requests, 2);
+        assert_eq!(stats.total.requests, 3);
+        assert!((stats.total.cost_usd - 0.018).abs() < 1e-9);
+
+        assert_eq!(stats.daily.len(), STATS_DAYS as usize);
+        assert_eq!(stats.daily.last().unwrap().day, "2026-10-02");
+        assert_eq!(stats.daily[STATS_DAYS as usize - 3].usage.requests, 1);
+        assert_eq!(
+            stats
+                .monthly
+                .iter()
+                .map(|month| month.month.as_str())
+                .collect::<Vec<_>>(),
+            vec!["2026-09", "2026-10"]
+        );
+    }
+
+    #[test]
+    fn test_whisper_price_per_minute() {
+        assert_eq!(
+            whisper_price_per_minute(WhisperProvider::OpenAi, "whisper-1"),
+            WHISPER_PRICE_PER_MINUTE_USD
+        );
+        assert_eq!(
+            whisper_price_per_minute(WhisperProvider::OpenAi, "gpt-4o-mini-transcribe"),
+            MINI_TRANSCRIBE_PRICE_PER_MINUTE_USD
+        );
+        assert_eq!(
+            whisper_price_per_minute(WhisperProvider::Local, "whisper-1"),
+            0.0
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! Custom vocabulary biasing transcription
+//!
+//! Names, jargon and acronyms Whisper cannot know are kept in a user-managed
+//! list, `vocabulary.json` next to profiles.json. Every transcription takes the
+//! terms as a bias: both providers only expose the prompt for it (the `prompt`
+//! field of the OpenAI API, `--prompt` for whisper.cpp), so the terms are
+//! appended to the Whisper prompt and kept first when it is trimmed. A term can
+//! also list what it `sounds_like`: those spellings are replaced with the term
+//! in the transcript, like the entries of the correction dictionary.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::vocabulary::{Vocabulary, VocabularyTerm};
+//!
+//! let vocabulary = Vocabulary {
+//!     terms: vec![VocabularyTerm {
+//!         term: "DictaClerk".to_string(),
+//!         sounds_like: vec!["dicta clerk".to_string()],
+//!     }],
+//! };
+//! assert_eq!(vocabulary.bias_terms(&[]), vec!["DictaClerk"]);
+//! assert_eq!(vocabulary.corrections()[0].heard, "dicta clerk");
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+use crate::services::corrections::{self, Correction};
+
+/// File name of the vocabulary, next to profiles.json
+pub const VOCABULARY_FILE: &str = "vocabulary.json";
+
+/// Terms a vocabulary holds at most
+pub const MAX_VOCABULARY_TERMS: usize = 200;
+
+/// Errors that can occur while loading or saving the vocabulary
+#[derive(Error, Debug)]
+pub enum VocabularyError {
+    #[error("IO error on {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Invalid vocabulary file: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("The vocabulary holds at most {MAX_VOCABULARY_TERMS} terms")]
+    TooManyTerms,
+}
+
+/// Result type for vocabulary operations
+pub type VocabularyResult<T> = Result<T, VocabularyError>;
+
+/// A name, jargon word or acronym to spell as written
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VocabularyTerm {
+    pub term: String,
+    /// Spellings Whisper produces instead, replaced with the term
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sounds_like: Vec<String>,
+}
+
+/// User-managed vocabulary
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Vocabulary {
+    #[serde(default)]
+    pub terms: Vec<VocabularyTerm>,
+}
+
+impl Vocabulary {
+    /// Vocabulary with trimmed terms, without empty or duplicate terms
+    /// (ASCII case ignored, the first spelling is kept)
+    pub fn normalized(self) -> Self {
+        let mut terms: Vec<VocabularyTerm> = Vec::new();
+        for term in self.terms {
+            let name = term.term.trim().to_string();
+            if name.is_empty()
+                || terms
+                    .iter()
+                    .any(|kept| kept.term.eq_ignore_ascii_case(&name))
+            {
+                continue;
+            }
+            let mut sounds_like: Vec<String> = Vec::new();
+            for spelling in term.sounds_like {
+                let spelling = spelling.trim().to_string();
+                if !spelling.is_empty() && spelling != name && !sounds_like.contains(&spelling) {
+                    sounds_like.push(spelling);
+                }
+            }
+            terms.push(VocabularyTerm {
+                term: name,
+                sounds_like,
+            });
+        }
+        Self { terms }
+    }
+
+    /// Terms biasing transcription: the vocabulary first, then the correct
+    /// spellings of the correction dictionary, without duplicates
+    pub fn bias_terms(&self, corrections: &[Correction]) -> Vec<String> {
+        let mut terms: Vec<String> = self.terms.iter().map(|term| term.term.clone()).collect();
+        for term in corrections::vocabulary(corrections) {
+            if !terms.contains(&term) {
+                terms.push(term);
+            }
+        }
+        terms
+    }
+
+    /// Replacements of the `sounds_like` spellings, applied like corrections
+    pub fn corrections(&self) -> Vec<Correction> {
+        self.terms
+            .iter()
+            .flat_map(|term| {
+                term.sounds_like.iter().map(|spelling| Correction {
+                    heard: spelling.clone(),
+                    correct: term.term.clone(),
+                    count: 0,
+                    updated_at: 0,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Vocabulary file store
+#[derive(Debug, Clone)]
+pub struct VocabularyStore {
+    path: PathBuf,
+}
+
+impl VocabularyStore {
+    /// Create a store backed by the given JSON file
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Load the vocabulary (empty when the file does not exist)
+    pub fn load(&self) -> VocabularyResult<Vocabulary> {
+        if !self.path.exists() {
+            return Ok(Vocabulary::default());
+        }
+
+        let content = std::fs::read_to_string(&self.path).map_err(|e| self.io_error(e))?;
+        if content.trim().is_empty() {
+            return Ok(Vocabulary::default());
+        }
+        let vocabulary: Vocabulary = serde_json::from_str(&content)?;
+        Ok(vocabulary.normalized())
+    }
+
+    /// Atomically write the normalized vocabulary and return it
+    pub fn save(&self, vocabulary: Vocabulary) -> VocabularyResult<Vocabulary> {
+        let vocabulary = vocabulary.normalized();
+        if vocabulary.terms.len() > MAX_VOCABULARY_TERMS {
+            return Err(VocabularyError::TooManyTerms);
+        }
+
+        let parent_dir = self.path.parent().unwrap_or_else(|| Path::new("."));
+        if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
+            std::fs::create_dir_all(parent_dir).map_err(|e| self.io_error(e))?;
+        }
+
+        let content = serde_json::to_string_pretty(&vocabulary)?;
+        let mut temp_file = NamedTempFile::new_in(parent_dir).map_err(|e| self.io_error(e))?;
+        temp_file
+            .write_all(content.as_bytes())
+            .map_err(|e| self.io_error(e))?;
+        temp_file
+            .persist(&self.path)
+            .map_err(|e| self.io_error(e.error))?;
+        Ok(vocabulary)
+    }
+
+    fn io_error(&self, source: std::io::Error) -> VocabularyError {
+        VocabularyError::Io {
+            path: self.path.to_string_lossy().to_string(),
+            source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn term(term: &str, sounds_like: &[&str]) -> VocabularyTerm {
+        VocabularyTerm {
+            term: term.to_string(),
+            sounds_like: sounds_like.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_vocabulary_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = VocabularyStore::new(temp_dir.path().join(VOCABULARY_FILE));
+        assert_eq!(store.load().unwrap(), Vocabulary::default());
+
+        let saved = store
+            .save(Vocabulary {
+                terms: vec![
+                    term(" Kubernetes ", &["cooper netties", "", "Kubernetes"]),
+                    term("kubernetes", &[]),
+                    term("  ", &[]),
+                    term("ACME-42", &[]),
+                ],
+            })
+            .unwrap();
+        assert_eq!(
+            saved.terms,
+            vec![
+                term("Kubernetes", &["cooper netties"]),
+                term("ACME-42", &[])
+            ]
+        );
+        assert_eq!(store.load().unwrap(), saved);
+
+        let too_many = Vocabulary {
+            terms: (0..=MAX_VOCABULARY_TERMS)
+                .map(|i| term(&format!("Term{}", i), &[]))
+                .collect(),
+        };
+        assert!(matches!(
+            store.save(too_many),
+            Err(VocabularyError::TooManyTerms)
+        ));
+    }
+
+    #[test]
+    fn test_bias_terms_and_corrections() {
+        let vocabulary = Vocabulary {
+            terms: vec![
+                term("Kubernetes", &["cooper netties"]),
+                term("ACME-42", &[]),
+            ],
+        };
+        let flagged = Correction {
+            heard: "dicta clerk".to_string(),
+            correct: "DictaClerk".to_string(),
+            count: 2,
+            updated_at: 0,
+        };
+        let duplicate = Correction {
+            correct: "ACME-42".to_string(),
+            ..flagged.clone()
+        };
+        assert_eq!(
+            vocabulary.bias_terms(&[flagged, duplicate]),
+            vec!["Kubernetes", "ACME-42", "DictaClerk"]
+        );
+
+        let corrections = vocabulary.corrections();
+        assert_eq!(corrections.len(), 1);
+        assert_eq!(corrections[0].correct, "Kubernetes");
+    }
+}
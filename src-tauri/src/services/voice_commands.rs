@@ -0,0 +1,260 @@
+//! Spoken commands for hands-free operation
+//!
+//! A small grammar of phrases controls the app without the keyboard:
+//!
+//! - "stop recording" at the end of the speech stops the recording
+//! - "cancel recording" or "scratch that" at the end, or a dictation that is
+//!   only "cancel", discards it
+//! - "use <name> profile" or "switch to <name> profile" at the start formats
+//!   the dictation with that profile
+//!
+//! Commands are matched on whole words, ignoring case and punctuation, and are
+//! removed from the transcript (and its segments) before it is processed.
+//! Live captions use the same grammar to act while still recording.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::voice_commands::{leading_command, VoiceCommand};
+//!
+//! let found = leading_command("Use email profile. Dear Anna, thanks.").unwrap();
+//! assert_eq!(found.command, VoiceCommand::UseProfile("email".to_string()));
+//! assert_eq!(found.words, 3);
+//! ```
+
+use serde::Serialize;
+
+use crate::services::whisper_client::{TranscriptionResponse, TranscriptionSegment};
+
+/// Longest profile name accepted in "use <name> profile", in words
+const MAX_PROFILE_NAME_WORDS: usize = 4;
+
+/// A command recognized in the speech
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "command", content = "argument", rename_all = "snake_case")]
+pub enum VoiceCommand {
+    /// Stop the recording and process it
+    StopRecording,
+    /// Discard the recording
+    Cancel,
+    /// Format the dictation with the named profile
+    UseProfile(String),
+}
+
+/// A command and the number of transcript words it spans
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandMatch {
+    pub command: VoiceCommand,
+    pub words: usize,
+}
+
+/// Command at the start of `text` ("use <name> profile", or a lone "cancel")
+pub fn leading_command(text: &str) -> Option<CommandMatch> {
+    let words = normalized_words(text);
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+
+    if matches!(words.as_slice(), ["cancel"] | ["cancel", "that"]) {
+        return Some(CommandMatch {
+            command: VoiceCommand::Cancel,
+            words: words.len(),
+        });
+    }
+
+    let name_start = match words.as_slice() {
+        ["use", ..] => 1,
+        ["switch", "to", ..] => 2,
+        _ => return None,
+    };
+    let profile_at = words
+        .iter()
+        .skip(name_start)
+        .take(MAX_PROFILE_NAME_WORDS + 1)
+        .position(|word| *word == "profile")?
+        + name_start;
+    let name = words[name_start..profile_at]
+        .iter()
+        .copied()
+        .filter(|word| *word != "the")
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!name.is_empty()).then_some(CommandMatch {
+        command: VoiceCommand::UseProfile(name),
+        words: profile_at + 1,
+    })
+}
+
+/// Command at the end of `text` ("stop recording", "cancel recording", "scratch that")
+pub fn trailing_command(text: &str) -> Option<CommandMatch> {
+    let words = normalized_words(text);
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    let command = match words.as_slice() {
+        [.., "stop", "recording"] => VoiceCommand::StopRecording,
+        [.., "cancel", "recording"] | [.., "scratch", "that"] => VoiceCommand::Cancel,
+        _ => return None,
+    };
+    Some(CommandMatch { command, words: 2 })
+}
+
+/// Remove the commands from a transcript, returning them in order
+///
+/// The text and the segments are stripped alike, so that stages rebuilding
+/// the text from segments do not bring the commands back.
+pub fn take_voice_commands(transcript: &mut TranscriptionResponse) -> Vec<VoiceCommand> {
+    let mut commands = Vec::new();
+
+    if let Some(found) = leading_command(&transcript.text) {
+        strip_words(transcript, found.words, 0);
+        commands.push(found.command);
+    }
+    if let Some(found) = trailing_command(&transcript.text) {
+        strip_words(transcript, 0, found.words);
+        commands.push(found.command);
+    }
+    commands
+}
+
+/// Remove `leading` words from the start and `trailing` words from the end
+fn strip_words(transcript: &mut TranscriptionResponse, leading: usize, trailing: usize) {
+    transcript.text = strip_text(&transcript.text, leading, trailing);
+    if let Some(segments) = transcript.segments.as_mut() {
+        strip_segments(segments, leading, trailing);
+    }
+}
+
+fn strip_text(text: &str, leading: usize, trailing: usize) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let end = words.len().saturating_sub(trailing);
+    let kept = words.get(leading..end).unwrap_or_default().join(" ");
+    // Drop what the command left behind (", " or ". " before the dictation)
+    kept.trim_start_matches(|c: char| c.is_ascii_punctuation() || c.is_whitespace())
+        .to_string()
+}
+
+fn strip_segments(segments: &mut Vec<TranscriptionSegment>, leading: usize, trailing: usize) {
+    let mut remaining = leading;
+    for segment in segments.iter_mut() {
+        if remaining == 0 {
+            break;
+        }
+        let count = segment.text.split_whitespace().count();
+        let stripped = count.min(remaining);
+        segment.text = format!(" {}", strip_text(&segment.text, stripped, 0));
+        remaining -= stripped;
+    }
+
+    let mut remaining = trailing;
+    for segment in segments.iter_mut().rev() {
+        if remaining == 0 {
+            break;
+        }
+        let count = segment.text.split_whitespace().count();
+        let stripped = count.min(remaining);
+        let text = strip_text(&segment.text, 0, stripped);
+        segment.text = format!(" {}", text.trim_end_matches([',', ';', ':']));
+        remaining -= stripped;
+    }
+
+    segments.retain(|segment| !segment.text.trim().is_empty());
+}
+
+/// Lowercase words without punctuation
+fn normalized_words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(id: u32, text: &str) -> TranscriptionSegment {
+        TranscriptionSegment {
+            id,
+            start: id as f64,
+            end: id as f64 + 1.0,
+            text: text.to_string(),
+            avg_logprob: None,
+            compression_ratio: None,
+            no_speech_prob: None,
+        }
+    }
+
+    #[test]
+    fn test_command_grammar() {
+        assert_eq!(
+            leading_command("Switch to the Meeting Notes profile, please.")
+                .unwrap()
+                .command,
+            VoiceCommand::UseProfile("meeting notes".to_string())
+        );
+        assert_eq!(
+            leading_command("Cancel.").unwrap().command,
+            VoiceCommand::Cancel
+        );
+        assert_eq!(leading_command("Cancel the meeting on Friday."), None);
+        assert_eq!(
+            leading_command("Use a larger font in the profile section"),
+            None
+        );
+        assert_eq!(leading_command("We use profile pictures"), None);
+
+        assert_eq!(
+            trailing_command("That's all. Stop recording.").unwrap(),
+            CommandMatch {
+                command: VoiceCommand::StopRecording,
+                words: 2
+            }
+        );
+        assert_eq!(
+            trailing_command("Oops, scratch that!").unwrap().command,
+            VoiceCommand::Cancel
+        );
+        assert_eq!(trailing_command("We had to cancel."), None);
+    }
+
+    #[test]
+    fn test_take_voice_commands_strips_text_and_segments() {
+        let mut transcript = TranscriptionResponse {
+            text: "Use email profile. Dear Anna, the report is ready. Stop recording.".to_string(),
+            language: None,
+            duration: None,
+            segments: Some(vec![
+                segment(0, " Use email profile."),
+                segment(1, " Dear Anna, the report is ready."),
+                segment(2, " Stop recording."),
+            ]),
+            words: None,
+        };
+
+        let commands = take_voice_commands(&mut transcript);
+        assert_eq!(
+            commands,
+            vec![
+                VoiceCommand::UseProfile("email".to_string()),
+                VoiceCommand::StopRecording
+            ]
+        );
+        assert_eq!(transcript.text, "Dear Anna, the report is ready.");
+        let segments = transcript.segments.unwrap();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].text, " Dear Anna, the report is ready.");
+    }
+
+    #[test]
+    fn test_take_voice_commands_ignores_plain_dictation() {
+        let mut transcript = TranscriptionResponse {
+            text: "Please cancel my subscription.".to_string(),
+            language: None,
+            duration: None,
+            segments: None,
+            words: None,
+        };
+        assert!(take_voice_commands(&mut transcript).is_empty());
+        assert_eq!(transcript.text, "Please cancel my subscription.");
+    }
+}
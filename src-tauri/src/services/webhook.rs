@@ -0,0 +1,97 @@
+//! Delivering dictated text to a webhook
+//!
+//! The text is POSTed as JSON ([`WebhookPayload`]) to an HTTP(S) endpoint,
+//! e.g. an automation service or a notes app integration.
+//!
+//! ## Usage Example
+//!
+//! ```rust,no_run
+//! use dicta_clerk_lib::services::webhook::{post_text, WebhookPayload};
+//!
+//! # async fn example() {
+//! let payload = WebhookPayload {
+//!     text: "Meeting notes".to_string(),
+//!     profile_id: Some("2".to_string()),
+//!     history_entry_id: None,
+//!     created_at: None,
+//! };
+//! post_text("https://example.com/hooks/dictation", &payload).await.unwrap();
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::Serialize;
+use thiserror::Error;
+
+/// Time allowed for the endpoint to answer
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Errors that can occur while delivering to a webhook
+#[derive(Error, Debug)]
+pub enum WebhookError {
+    #[error("Invalid webhook URL '{0}': use an http:// or https:// address")]
+    InvalidUrl(String),
+    #[error("Webhook request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Webhook answered with HTTP {0}")]
+    Status(u16),
+}
+
+/// Result type for webhook operations
+pub type WebhookResult<T> = Result<T, WebhookError>;
+
+/// JSON body sent to the webhook
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WebhookPayload {
+    pub text: String,
+    /// Profile the text was formatted with
+    pub profile_id: Option<String>,
+    /// History entry the text was rendered from
+    pub history_entry_id: Option<String>,
+    /// Unix timestamp (seconds) of the dictation
+    pub created_at: Option<u64>,
+}
+
+/// Check that a webhook URL is an HTTP(S) address
+pub fn validate_webhook_url(url: &str) -> WebhookResult<()> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| WebhookError::InvalidUrl(url.to_string()))?;
+    if rest.is_empty() || rest.starts_with('/') || rest.contains(char::is_whitespace) {
+        return Err(WebhookError::InvalidUrl(url.to_string()));
+    }
+    Ok(())
+}
+
+/// POST the payload to the webhook
+pub async fn post_text(url: &str, payload: &WebhookPayload) -> WebhookResult<()> {
+    validate_webhook_url(url)?;
+    let response = Client::new()
+        .post(url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .json(payload)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(WebhookError::Status(response.status().as_u16()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_webhook_url() {
+        assert!(validate_webhook_url("https://example.com/hook").is_ok());
+        assert!(validate_webhook_url("http://localhost:8080").is_ok());
+        assert!(validate_webhook_url("ftp://example.com").is_err());
+        assert!(validate_webhook_url("https://").is_err());
+        assert!(validate_webhook_url("https:///path").is_err());
+        assert!(validate_webhook_url("https://exa mple.com").is_err());
+    }
+}
@@ -0,0 +1,418 @@
+//! Weekly summary of the dictations
+//!
+//! A report covers a week of the history, Monday to Sunday in the local
+//! calendar: the dictations and words per day, the profiles used most and the
+//! notable transcripts (the longest ones, with an excerpt). It is rendered as
+//! Markdown or HTML and saved as `week-<monday>.md`/`.html` in the reports
+//! directory.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use dicta_clerk_lib::services::weekly_report::{
+//!     compile_report, render_report, week_start, ReportFormat,
+//! };
+//! use std::collections::HashMap;
+//!
+//! let monday = week_start(NaiveDate::from_ymd_opt(2026, 3, 12).unwrap());
+//! let report = compile_report(&[], &HashMap::new(), monday, 3);
+//! assert_eq!(report.total_words, 0);
+//! assert!(render_report(&report, ReportFormat::Markdown).starts_with("# Weekly report"));
+//! ```
+
+use chrono::{Datelike, Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::services::goals::{count_words, local_day};
+use crate::services::HistoryEntry;
+
+/// Directory of the reports, inside the data directory
+pub const REPORTS_DIR: &str = "reports";
+
+/// Characters of a notable transcript quoted in the report
+const EXCERPT_CHARS: usize = 280;
+
+/// Format of the rendered report
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    /// File extension of the format
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+        }
+    }
+}
+
+/// Dictations of a day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DayUsage {
+    pub date: NaiveDate,
+    pub dictations: u32,
+    pub words: u64,
+}
+
+/// Use of a profile over the week
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProfileUsage {
+    /// None for dictations without a profile
+    pub profile_id: Option<String>,
+    pub name: String,
+    pub dictations: u32,
+    pub words: u64,
+}
+
+/// A notable transcript of the week
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Highlight {
+    pub entry_id: String,
+    /// Unix timestamp (seconds) of the dictation
+    pub created_at: u64,
+    pub profile_name: String,
+    pub words: u64,
+    /// Beginning of the final text
+    pub excerpt: String,
+}
+
+/// Usage and highlights of a week
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeeklyReport {
+    /// Monday of the week
+    pub week_start: NaiveDate,
+    /// Sunday of the week
+    pub week_end: NaiveDate,
+    pub dictations: u32,
+    pub total_words: u64,
+    /// The seven days of the week, Monday first
+    pub days: Vec<DayUsage>,
+    /// Profiles by number of dictations, most used first
+    pub top_profiles: Vec<ProfileUsage>,
+    /// Longest transcripts, longest first
+    pub highlights: Vec<Highlight>,
+}
+
+/// File name of the report of the week starting on `week_start`
+pub fn report_file_name(week_start: NaiveDate, format: ReportFormat) -> String {
+    format!("week-{}.{}", week_start, format.extension())
+}
+
+/// Monday of the week of a day
+pub fn week_start(day: NaiveDate) -> NaiveDate {
+    day - Days::new(u64::from(day.weekday().num_days_from_monday()))
+}
+
+/// Report of the week starting on `week_start` from the history entries
+///
+/// `profile_names` maps profile IDs to their names (unknown IDs are shown as
+/// is); at most `max_highlights` transcripts are quoted.
+pub fn compile_report(
+    entries: &[HistoryEntry],
+    profile_names: &HashMap<String, String>,
+    week_start: NaiveDate,
+    max_highlights: usize,
+) -> WeeklyReport {
+    let mut days: Vec<DayUsage> = week_start
+        .iter_days()
+        .take(7)
+        .map(|date| DayUsage {
+            date,
+            dictations: 0,
+            words: 0,
+        })
+        .collect();
+    let week_end = days[6].date;
+    let profile_name = |profile_id: Option<&String>| match profile_id {
+        Some(id) => profile_names.get(id).cloned().unwrap_or_else(|| id.clone()),
+        None => "No profile".to_string(),
+    };
+
+    let mut top_profiles: Vec<ProfileUsage> = Vec::new();
+    let mut highlights: Vec<Highlight> = Vec::new();
+    for entry in entries {
+        let Some(date) = local_day(entry.created_at) else {
+            continue;
+        };
+        let Some(day) = days.iter_mut().find(|day| day.date == date) else {
+            continue;
+        };
+        let words = count_words(&entry.final_text);
+        day.dictations += 1;
+        day.words += words;
+
+        match top_profiles
+            .iter_mut()
+            .find(|usage| usage.profile_id == entry.profile_id)
+        {
+            Some(usage) => {
+                usage.dictations += 1;
+                usage.words += words;
+            }
+            None => top_profiles.push(ProfileUsage {
+                profile_id: entry.profile_id.clone(),
+                name: profile_name(entry.profile_id.as_ref()),
+                dictations: 1,
+                words,
+            }),
+        }
+        if words > 0 {
+            highlights.push(Highlight {
+                entry_id: entry.id.clone(),
+                created_at: entry.created_at,
+                profile_name: profile_name(entry.profile_id.as_ref()),
+                words,
+                excerpt: excerpt(&entry.final_text),
+            });
+        }
+    }
+
+    top_profiles.sort_by(|a, b| {
+        b.dictations
+            .cmp(&a.dictations)
+            .then(b.words.cmp(&a.words))
+            .then(a.name.cmp(&b.name))
+    });
+    highlights.sort_by(|a, b| b.words.cmp(&a.words).then(a.created_at.cmp(&b.created_at)));
+    highlights.truncate(max_highlights);
+
+    WeeklyReport {
+        week_start,
+        week_end,
+        dictations: days.iter().map(|day| day.dictations).sum(),
+        total_words: days.iter().map(|day| day.words).sum(),
+        days,
+        top_profiles,
+        highlights,
+    }
+}
+
+/// Render the report as Markdown or HTML
+pub fn render_report(report: &WeeklyReport, format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Markdown => render_markdown(report),
+        ReportFormat::Html => render_html(report),
+    }
+}
+
+fn render_markdown(report: &WeeklyReport) -> String {
+    let mut out = format!(
+        "# Weekly report: {} to {}\n\n{} dictation(s), {} word(s)\n\n## Days\n\n| Day | Dictations | Words |\n| --- | ---: | ---: |\n",
+        report.week_start, report.week_end, report.dictations, report.total_words
+    );
+    for day in &report.days {
+        out.push_str(&format!(
+            "| {} | {} | {} |\n",
+            day.date.format("%a %Y-%m-%d"),
+            day.dictations,
+            day.words
+        ));
+    }
+
+    out.push_str("\n## Top profiles\n\n");
+    if report.top_profiles.is_empty() {
+        out.push_str("No dictations this week.\n");
+    }
+    for usage in &report.top_profiles {
+        out.push_str(&format!(
+            "- **{}**: {} dictation(s), {} word(s)\n",
+            usage.name, usage.dictations, usage.words
+        ));
+    }
+
+    if !report.highlights.is_empty() {
+        out.push_str("\n## Highlights\n");
+        for highlight in &report.highlights {
+            out.push_str(&format!(
+                "\n### {} ({} words)\n\n",
+                highlight.profile_name, highlight.words
+            ));
+            for line in highlight.excerpt.lines() {
+                out.push_str(&format!("> {}\n", line));
+            }
+        }
+    }
+    out
+}
+
+fn render_html(report: &WeeklyReport) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Weekly report {start}</title></head>\n<body>\n<h1>Weekly report: {start} to {end}</h1>\n<p>{dictations} dictation(s), {words} word(s)</p>\n<h2>Days</h2>\n<table>\n<tr><th>Day</th><th>Dictations</th><th>Words</th></tr>\n",
+        start = report.week_start,
+        end = report.week_end,
+        dictations = report.dictations,
+        words = report.total_words
+    );
+    for day in &report.days {
+        out.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            day.date.format("%a %Y-%m-%d"),
+            day.dictations,
+            day.words
+        ));
+    }
+    out.push_str("</table>\n<h2>Top profiles</h2>\n");
+    if report.top_profiles.is_empty() {
+        out.push_str("<p>No dictations this week.</p>\n");
+    } else {
+        out.push_str("<ul>\n");
+        for usage in &report.top_profiles {
+            out.push_str(&format!(
+                "<li><strong>{}</strong>: {} dictation(s), {} word(s)</li>\n",
+                escape_html(&usage.name),
+                usage.dictations,
+                usage.words
+            ));
+        }
+        out.push_str("</ul>\n");
+    }
+
+    if !report.highlights.is_empty() {
+        out.push_str("<h2>Highlights</h2>\n");
+        for highlight in &report.highlights {
+            out.push_str(&format!(
+                "<h3>{} ({} words)</h3>\n<blockquote>{}</blockquote>\n",
+                escape_html(&highlight.profile_name),
+                highlight.words,
+                escape_html(&highlight.excerpt).replace('\n', "<br>\n")
+            ));
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Beginning of a text, cut at a word boundary
+fn excerpt(text: &str) -> String {
+    let text = text.trim();
+    if text.chars().count() <= EXCERPT_CHARS {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(EXCERPT_CHARS).collect();
+    let cut = match cut.rfind(char::is_whitespace) {
+        Some(end) => &cut[..end],
+        None => &cut,
+    };
+    format!("{}…", cut.trim_end())
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, TimeZone};
+
+    fn entry(day: u32, hour: u32, profile_id: Option<&str>, text: &str) -> HistoryEntry {
+        let created_at = Local
+            .with_ymd_and_hms(2026, 3, day, hour, 0, 0)
+            .unwrap()
+            .timestamp() as u64;
+        HistoryEntry {
+            id: created_at.to_string(),
+            created_at,
+            final_text: text.to_string(),
+            original_transcript: text.to_string(),
+            profile_id: profile_id.map(str::to_string),
+            has_audio: false,
+            segments: Vec::new(),
+            focus_session_id: None,
+            workflow_id: None,
+            edited_at: None,
+        }
+    }
+
+    #[test]
+    fn test_week_start_is_monday() {
+        let monday = NaiveDate::from_ymd_opt(2026, 3, 9).unwrap();
+        assert_eq!(week_start(monday), monday);
+        assert_eq!(
+            week_start(NaiveDate::from_ymd_opt(2026, 3, 15).unwrap()),
+            monday
+        );
+    }
+
+    #[test]
+    fn test_compile_report_counts_the_week_only() {
+        let entries = vec![
+            // Sunday of the previous week
+            entry(8, 12, Some("2"), "before the week"),
+            entry(9, 9, Some("2"), "Dear team, the release is ready"),
+            entry(10, 9, Some("3"), "short note"),
+            entry(12, 18, Some("2"), "one two"),
+            entry(15, 23, None, "a quick reminder to self"),
+            // Monday of the next week
+            entry(16, 0, Some("3"), "after the week"),
+        ];
+        let names = HashMap::from([("2".to_string(), "Email".to_string())]);
+        let report = compile_report(
+            &entries,
+            &names,
+            NaiveDate::from_ymd_opt(2026, 3, 9).unwrap(),
+            2,
+        );
+
+        assert_eq!(
+            report.week_end,
+            NaiveDate::from_ymd_opt(2026, 3, 15).unwrap()
+        );
+        assert_eq!(report.dictations, 4);
+        assert_eq!(report.total_words, 15);
+        assert_eq!(report.days.len(), 7);
+        assert_eq!(report.days[0].words, 6);
+        assert_eq!(report.days[6].dictations, 1);
+
+        assert_eq!(report.top_profiles[0].name, "Email");
+        assert_eq!(report.top_profiles[0].dictations, 2);
+        assert_eq!(report.top_profiles[1].name, "No profile");
+        assert_eq!(report.top_profiles[2].name, "3");
+
+        assert_eq!(report.highlights.len(), 2);
+        assert_eq!(report.highlights[0].words, 6);
+        assert_eq!(report.highlights[1].excerpt, "a quick reminder to self");
+        assert_eq!(
+            report_file_name(report.week_start, ReportFormat::Html),
+            "week-2026-03-09.html"
+        );
+    }
+
+    #[test]
+    fn test_render_report_formats() {
+        let entries = vec![entry(10, 9, Some("2"), "Fish & <chips>")];
+        let report = compile_report(
+            &entries,
+            &HashMap::new(),
+            NaiveDate::from_ymd_opt(2026, 3, 9).unwrap(),
+            3,
+        );
+
+        let markdown = render_report(&report, ReportFormat::Markdown);
+        assert!(markdown.starts_with("# Weekly report: 2026-03-09 to 2026-03-15"));
+        assert!(markdown.contains("| Tue 2026-03-10 | 1 | 2 |"));
+        assert!(markdown.contains("> Fish & <chips>"));
+
+        let html = render_report(&report, ReportFormat::Html);
+        assert!(html.contains("<blockquote>Fish &amp; &lt;chips&gt;</blockquote>"));
+        assert!(html.ends_with("</html>\n"));
+    }
+
+    #[test]
+    fn test_excerpt_cuts_at_a_word() {
+        let text = "word ".repeat(100);
+        let cut = excerpt(&text);
+        assert!(cut.ends_with("word…"));
+        assert!(cut.chars().count() <= EXCERPT_CHARS + 1);
+    }
+}
@@ -1,7 +1,9 @@
 //! WhisperClient service for audio transcription via OpenAI Whisper API
 //!
 //! This service provides async function to transcribe encoded audio via OpenAI Whisper API.
-//! It supports OGG files up to 25MB with exponential backoff retry logic for 5xx errors.
+//! It supports OGG files up to 25MB, retrying 5xx errors, network errors and
+//! rate limits with the client's [`RetryPolicy`] (backoff with jitter, or the
+//! server's `Retry-After`).
 //!
 //! ## Usage Example
 //!
@@ -32,6 +34,106 @@ use thiserror::Error;
 use tokio::fs::File;
 use tokio::io::AsyncReadExt;
 
+use crate::audio::EncoderFormat;
+use crate::services::api_limiter::ApiLimiter;
+use crate::services::network::NetworkConfig;
+use crate::services::retry_policy::{self, RetryPolicy};
+use crate::services::whisper_prompt::vocabulary_terms;
+use crate::services::workflow_trace;
+
+/// Highest sampling temperature the API accepts
+pub const MAX_TEMPERATURE: f32 = 1.0;
+
+/// Slowest upload the request timeout allows for by default, in kbit/s
+pub const DEFAULT_MIN_UPLOAD_KBPS: u32 = 256;
+
+/// Whether speech is transcribed in its own language or translated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WhisperMode {
+    /// Text in the spoken language (`/audio/transcriptions`)
+    #[default]
+    Transcribe,
+    /// English text whatever the spoken language (`/audio/translations`)
+    Translate,
+}
+
+/// Format Whisper answers in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WhisperResponseFormat {
+    /// Text with the detected language, duration and segments (needed for
+    /// timestamps, confidence and word timings)
+    #[default]
+    VerboseJson,
+    /// Text only, as JSON
+    Json,
+    /// Plain text
+    Text,
+}
+
+impl WhisperResponseFormat {
+    /// Value of the `response_format` field
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WhisperResponseFormat::VerboseJson => "verbose_json",
+            WhisperResponseFormat::Json => "json",
+            WhisperResponseFormat::Text => "text",
+        }
+    }
+}
+
+/// What of the prompt given to a transcription is sent to Whisper
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptStrategy {
+    /// The whole prompt (profile prompt and vocabulary)
+    #[default]
+    Full,
+    /// Only the names, acronyms and jargon found in the prompt, so the
+    /// instructions do not steer the style of the transcript
+    Vocabulary,
+    /// No prompt at all
+    None,
+}
+
+impl PromptStrategy {
+    /// Prompt to send for the given one
+    pub fn apply(self, prompt: Option<&str>) -> Option<String> {
+        let prompt = prompt.filter(|prompt| !prompt.trim().is_empty())?;
+        match self {
+            PromptStrategy::Full => Some(prompt.to_string()),
+            PromptStrategy::Vocabulary => {
+                Some(vocabulary_terms(prompt).join(", ")).filter(|terms| !terms.is_empty())
+            }
+            PromptStrategy::None => None,
+        }
+    }
+}
+
+/// Backend transcribing the recordings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WhisperProvider {
+    /// The OpenAI API (or a compatible endpoint)
+    #[default]
+    #[serde(rename = "openai")]
+    OpenAi,
+    /// whisper.cpp on this machine (see `local_inference`)
+    Local,
+}
+
+/// Translation endpoint next to a transcription endpoint
+pub fn translation_endpoint(endpoint: &str) -> String {
+    match endpoint
+        .trim_end_matches('/')
+        .strip_suffix("/transcriptions")
+    {
+        Some(base) => format!("{}/translations", base),
+        None => endpoint.to_string(),
+    }
+}
+
 /// Configuration for WhisperClient
 #[derive(Debug, Clone)]
 pub struct WhisperClientConfig {
@@ -41,14 +143,30 @@ pub struct WhisperClientConfig {
     pub endpoint: String,
     /// Model name to use (default: "whisper-1")
     pub model: String,
-    /// Request timeout in seconds (default: 30)
+    /// Request timeout in seconds, on top of the upload time (default: 30)
     pub timeout_seconds: u64,
+    /// Slowest upload bandwidth to allow for, in kbit/s (default: 256); the
+    /// request timeout grows with the file size accordingly (0 = fixed timeout)
+    pub min_upload_kbps: u32,
     /// Maximum file size in bytes (default: 25MB)
     pub max_file_size: u64,
-    /// Maximum number of retries for 5xx errors (default: 3)
-    pub max_retries: u32,
-    /// Base delay for exponential backoff in milliseconds (default: 1000)
-    pub retry_base_delay_ms: u64,
+    /// Retries of server errors, network errors and rate limits (default:
+    /// 3 retries from 1s, with jitter)
+    pub retry: RetryPolicy,
+    /// Ask for the timing of each word as well as each segment (default: false)
+    pub word_timestamps: bool,
+    /// Transcribe or translate to English (default: transcribe); the
+    /// translation endpoint is derived from `endpoint`
+    pub mode: WhisperMode,
+    /// Sampling temperature, 0.0-1.0 (default: None, the API's choice); 0.0
+    /// makes the transcription deterministic
+    pub temperature: Option<f32>,
+    /// Format Whisper answers in (default: verbose_json)
+    pub response_format: WhisperResponseFormat,
+    /// What of the prompt is sent (default: full)
+    pub prompt_strategy: PromptStrategy,
+    /// Proxy and extra CA certificates (default: none, the system proxy)
+    pub network: NetworkConfig,
 }
 
 impl Default for WhisperClientConfig {
@@ -58,9 +176,15 @@ impl Default for WhisperClientConfig {
             endpoint: "https://api.openai.com/v1/audio/transcriptions".to_string(),
             model: "whisper-1".to_string(),
             timeout_seconds: 30,
+            min_upload_kbps: DEFAULT_MIN_UPLOAD_KBPS,
             max_file_size: 25 * 1024 * 1024, // 25MB as per requirements
-            max_retries: 3,
-            retry_base_delay_ms: 1000,
+            retry: RetryPolicy::default(),
+            word_timestamps: false,
+            mode: WhisperMode::Transcribe,
+            temperature: None,
+            response_format: WhisperResponseFormat::VerboseJson,
+            prompt_strategy: PromptStrategy::Full,
+            network: NetworkConfig::default(),
         }
     }
 }
@@ -76,10 +200,23 @@ pub struct TranscriptionResponse {
     pub duration: Option<f64>,
     /// Optional segments (when using verbose_json format)
     pub segments: Option<Vec<TranscriptionSegment>>,
+    /// Optional word timings (when word timestamps are requested)
+    pub words: Option<Vec<TranscriptionWord>>,
+}
+
+/// Timing of a word from a verbose Whisper response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptionWord {
+    /// The word, without surrounding spaces
+    pub word: String,
+    /// Start time in seconds
+    pub start: f64,
+    /// End time in seconds
+    pub end: f64,
 }
 
 /// Segment information from verbose Whisper response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TranscriptionSegment {
     /// Segment ID
     pub id: u32,
@@ -119,12 +256,28 @@ pub enum WhisperError {
     InvalidResponse(String),
 
     #[error("Rate limited (HTTP 429): {message}")]
-    RateLimit { message: String },
+    RateLimit {
+        message: String,
+        /// Delay asked for by the server before retrying
+        retry_after: Option<Duration>,
+    },
 
     #[error("Timeout error: Request took longer than {timeout_seconds}s")]
     Timeout { timeout_seconds: u64 },
 }
 
+impl WhisperClientConfig {
+    /// Timeout of a request uploading `file_size` bytes: the time to upload
+    /// them at `min_upload_kbps`, plus `timeout_seconds` for the transcription
+    pub fn request_timeout_seconds(&self, file_size: u64) -> u64 {
+        if self.min_upload_kbps == 0 {
+            return self.timeout_seconds;
+        }
+        let upload_seconds = (file_size * 8).div_ceil(u64::from(self.min_upload_kbps) * 1000);
+        self.timeout_seconds + upload_seconds
+    }
+}
+
 /// Result type for Whisper operations
 pub type WhisperResult<T> = Result<T, WhisperError>;
 
@@ -143,12 +296,123 @@ pub trait WhisperClient: Send + Sync {
     /// # Requirements
     /// * File must be ≤ 25MB (returns FileTooLarge error otherwise)
     /// * Should complete within 15s wall time for ≤10s audio
-    /// * Implements exponential backoff for 5xx responses (up to 3 retries)
+    /// * Retries server errors, network errors and rate limits (see [`RetryPolicy`])
     async fn transcribe(
         &self,
         file_path: &Path,
         prompt: Option<String>,
     ) -> WhisperResult<TranscriptionResponse>;
+
+    /// Transcribe an audio file and request per-segment timestamps
+    ///
+    /// Clients that cannot provide segments fall back to `transcribe`, in which
+    /// case `segments` is `None`.
+    async fn transcribe_with_segments(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+    ) -> WhisperResult<TranscriptionResponse> {
+        self.transcribe(file_path, prompt).await
+    }
+
+    /// Transcribe with segment timestamps, forcing the spoken language
+    ///
+    /// `language` is an ISO-639-1 code (e.g. "fr"); `None` lets the model detect
+    /// it. Clients that cannot force a language ignore it.
+    async fn transcribe_with_language(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+        _language: Option<String>,
+    ) -> WhisperResult<TranscriptionResponse> {
+        self.transcribe_with_segments(file_path, prompt).await
+    }
+
+    /// Transcribe with segment timestamps in the given mode, whatever mode the
+    /// client is configured with
+    ///
+    /// The language is ignored when translating. Clients that cannot
+    /// translate return an error for `WhisperMode::Translate`.
+    async fn transcribe_with_mode(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+        language: Option<String>,
+        mode: WhisperMode,
+    ) -> WhisperResult<TranscriptionResponse> {
+        match mode {
+            WhisperMode::Transcribe => {
+                self.transcribe_with_language(file_path, prompt, language)
+                    .await
+            }
+            WhisperMode::Translate => Err(WhisperError::Api(
+                "Translation is not supported by this transcription provider".to_string(),
+            )),
+        }
+    }
+}
+
+/// Adapter whose `transcribe` requests segment timestamps from the wrapped client
+///
+/// Lets segment-aware callers reuse helpers written against `transcribe`.
+pub struct SegmentedWhisperClient<'a> {
+    client: &'a dyn WhisperClient,
+    language: Option<String>,
+    mode: Option<WhisperMode>,
+}
+
+impl<'a> SegmentedWhisperClient<'a> {
+    /// Wrap a client, letting it detect the spoken language
+    pub fn new(client: &'a dyn WhisperClient) -> Self {
+        Self {
+            client,
+            language: None,
+            mode: None,
+        }
+    }
+
+    /// Force the spoken language (ISO-639-1 code) of every request
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Force the mode of every request (None = the client's configured mode)
+    pub fn with_mode(mut self, mode: Option<WhisperMode>) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+#[async_trait]
+impl WhisperClient for SegmentedWhisperClient<'_> {
+    async fn transcribe(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+    ) -> WhisperResult<TranscriptionResponse> {
+        match self.mode {
+            Some(mode) => {
+                self.client
+                    .transcribe_with_mode(file_path, prompt, self.language.clone(), mode)
+                    .await
+            }
+            None => {
+                self.client
+                    .transcribe_with_language(file_path, prompt, self.language.clone())
+                    .await
+            }
+        }
+    }
+}
+
+/// Normalize a spoken-language code, or None when it is not an ISO-639 code
+///
+/// Whisper expects lowercase ISO-639-1 codes ("en", "fr", ...); three-letter
+/// codes are accepted for providers that use ISO-639-3.
+pub fn normalize_language_code(code: &str) -> Option<String> {
+    let code = code.trim().to_lowercase();
+    ((2..=3).contains(&code.len()) && code.chars().all(|c| c.is_ascii_lowercase())).then_some(code)
 }
 
 /// Request data for building forms on retry
@@ -157,6 +421,9 @@ struct RequestData {
     file_content: Vec<u8>,
     file_name: String,
     prompt: Option<String>,
+    /// ISO-639-1 code of the spoken language, detected by the model when None
+    language: Option<String>,
+    mode: WhisperMode,
 }
 
 /// OpenAI Whisper API client implementation
@@ -184,13 +451,24 @@ impl OpenAIWhisperClient {
     }
 
     /// Create a new WhisperClient with custom configuration
+    ///
+    /// Panics when the HTTP client cannot be built; use `try_with_config`
+    /// when the configuration has network options.
     pub fn with_config(config: WhisperClientConfig) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_seconds))
+        Self::try_with_config(config).expect("Failed to create HTTP client")
+    }
+
+    /// Create a new WhisperClient with custom configuration, failing on
+    /// invalid network options (proxy URL, CA bundle)
+    pub fn try_with_config(config: WhisperClientConfig) -> WhisperResult<Self> {
+        let client = config
+            .network
+            .apply(Client::builder().timeout(Duration::from_secs(config.timeout_seconds)))
+            .map_err(|e| WhisperError::Network(e.to_string()))?
             .build()
-            .expect("Failed to create HTTP client");
+            .map_err(|e| WhisperError::Network(format!("Failed to create HTTP client: {}", e)))?;
 
-        Self { config, client }
+        Ok(Self { config, client })
     }
 
     /// Validate file size against maximum allowed
@@ -220,19 +498,53 @@ impl OpenAIWhisperClient {
     fn build_multipart_form(&self, request_data: &RequestData) -> WhisperResult<multipart::Form> {
         let mut form = multipart::Form::new()
             .text("model", self.config.model.clone())
-            .text("response_format", "json");
+            .text("response_format", self.config.response_format.as_str());
+
+        if let Some(temperature) = self.config.temperature {
+            form = form.text(
+                "temperature",
+                temperature.clamp(0.0, MAX_TEMPERATURE).to_string(),
+            );
+        }
 
-        // Add the audio file
+        // Word timings come in addition to (not instead of) segment timings;
+        // translations only have segments, and only verbose responses have either
+        if self.config.word_timestamps
+            && request_data.mode == WhisperMode::Transcribe
+            && self.config.response_format == WhisperResponseFormat::VerboseJson
+        {
+            form = form
+                .text("timestamp_granularities[]", "segment")
+                .text("timestamp_granularities[]", "word");
+        }
+
+        // Add the audio file, typed by its extension (OGG when unknown)
+        let format = Path::new(&request_data.file_name)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(EncoderFormat::from_extension)
+            .unwrap_or_default();
         let file_part = multipart::Part::bytes(request_data.file_content.clone())
             .file_name(request_data.file_name.clone())
-            .mime_str("audio/ogg")
+            .mime_str(format.mime_type())
             .map_err(|e| WhisperError::Api(format!("Failed to create file part: {}", e)))?;
 
         form = form.part("file", file_part);
 
-        // Add prompt if provided
-        if let Some(ref prompt_text) = request_data.prompt {
-            form = form.text("prompt", prompt_text.clone());
+        // Add prompt if provided (as far as the prompt strategy keeps it)
+        if let Some(prompt_text) = self
+            .config
+            .prompt_strategy
+            .apply(request_data.prompt.as_deref())
+        {
+            form = form.text("prompt", prompt_text);
+        }
+
+        // Force the spoken language if requested (translations are always English)
+        if let Some(ref language) = request_data.language {
+            if request_data.mode == WhisperMode::Transcribe {
+                form = form.text("language", language.clone());
+            }
         }
 
         Ok(form)
@@ -242,27 +554,42 @@ impl OpenAIWhisperClient {
     async fn execute_single_request(
         &self,
         form: multipart::Form,
+        mode: WhisperMode,
+        timeout_seconds: u64,
     ) -> WhisperResult<TranscriptionResponse> {
-        let response = self
+        let endpoint = match mode {
+            WhisperMode::Transcribe => self.config.endpoint.clone(),
+            WhisperMode::Translate => translation_endpoint(&self.config.endpoint),
+        };
+        // Held until the response is read (concurrency and per-minute caps)
+        let _permit = ApiLimiter::global().acquire().await;
+        let mut request = self
             .client
-            .post(&self.config.endpoint)
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
-            .multipart(form)
-            .send()
-            .await
-            .map_err(|e| {
-                if e.is_timeout() {
-                    WhisperError::Timeout {
-                        timeout_seconds: self.config.timeout_seconds,
-                    }
-                } else if e.is_connect() || e.is_request() {
-                    WhisperError::Network(format!("Request failed: {}", e))
-                } else {
-                    WhisperError::Network(format!("Unknown network error: {}", e))
-                }
-            })?;
+            .post(&endpoint)
+            .timeout(Duration::from_secs(timeout_seconds))
+            .header("Authorization", format!("Bearer {}", self.config.api_key));
+        if let Some(workflow_id) = workflow_trace::current_workflow_id() {
+            request = request.header(workflow_trace::REQUEST_ID_HEADER, workflow_id);
+        }
+        let response = request.multipart(form).send().await.map_err(|e| {
+            if e.is_timeout() {
+                WhisperError::Timeout { timeout_seconds }
+            } else if e.is_connect() || e.is_request() {
+                WhisperError::Network(format!("Request failed: {}", e))
+            } else {
+                WhisperError::Network(format!("Unknown network error: {}", e))
+            }
+        })?;
 
         let status = response.status();
+        workflow_trace::log_api_response(
+            "Transcription",
+            status.as_u16(),
+            response
+                .headers()
+                .get("x-request-id")
+                .and_then(|value| value.to_str().ok()),
+        );
 
         match status {
             StatusCode::OK => {
@@ -270,6 +597,15 @@ impl OpenAIWhisperClient {
                     WhisperError::Network(format!("Failed to read response: {}", e))
                 })?;
 
+                if self.config.response_format == WhisperResponseFormat::Text {
+                    return Ok(TranscriptionResponse {
+                        text: response_text.trim().to_string(),
+                        language: None,
+                        duration: None,
+                        segments: None,
+                        words: None,
+                    });
+                }
                 serde_json::from_str::<TranscriptionResponse>(&response_text).map_err(|e| {
                     WhisperError::InvalidResponse(format!(
                         "Failed to parse JSON response: {}. Response: {}",
@@ -278,12 +614,14 @@ impl OpenAIWhisperClient {
                 })
             }
             StatusCode::TOO_MANY_REQUESTS => {
+                let retry_after = retry_policy::retry_after(response.headers());
                 let error_text = response
                     .text()
                     .await
                     .unwrap_or_else(|_| "Rate limited".to_string());
                 Err(WhisperError::RateLimit {
                     message: error_text,
+                    retry_after,
                 })
             }
             status if status.is_client_error() => {
@@ -327,33 +665,64 @@ impl OpenAIWhisperClient {
         request_data: RequestData,
     ) -> WhisperResult<TranscriptionResponse> {
         let mut last_error = None;
-
-        for attempt in 0..=self.config.max_retries {
+        let timeout_seconds = self
+            .config
+            .request_timeout_seconds(request_data.file_content.len() as u64);
+        log::debug!(
+            "{}Transcription timeout: {}s for {} bytes",
+            workflow_trace::log_prefix(),
+            timeout_seconds,
+            request_data.file_content.len()
+        );
+
+        let retry = self.config.retry;
+        for attempt in 0..=retry.max_retries {
             // Build a fresh form for each attempt
             let form = self.build_multipart_form(&request_data)?;
 
-            match self.execute_single_request(form).await {
+            match self
+                .execute_single_request(form, request_data.mode, timeout_seconds)
+                .await
+            {
                 Ok(response) => return Ok(response),
                 Err(error) => {
-                    last_error = Some(error);
-
-                    // Check if we should retry
-                    if let Some(ref err) = last_error {
-                        let should_retry = match err {
-                            WhisperError::Server { status, .. } if *status >= 500 => true,
-                            WhisperError::Network(_) => true,
-                            WhisperError::Timeout { .. } => true,
-                            _ => false,
-                        };
-
-                        if !should_retry || attempt >= self.config.max_retries {
-                            break;
+                    // Check if we should retry, and after how long
+                    let retry_after = match &error {
+                        WhisperError::Server { status, .. } if *status >= 500 => Some(None),
+                        WhisperError::Network(_) => Some(None),
+                        WhisperError::Timeout { .. } => Some(None),
+                        // An exhausted quota does not come back by waiting
+                        WhisperError::RateLimit { message, .. }
+                            if message.contains("insufficient_quota") =>
+                        {
+                            None
                         }
+                        WhisperError::RateLimit { retry_after, .. } => Some(*retry_after),
+                        _ => None,
+                    };
+                    last_error = Some(error);
 
-                        // Exponential backoff delay
-                        let delay_ms = self.config.retry_base_delay_ms * 2_u64.pow(attempt);
-                        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                    let Some(retry_after) = retry_after else {
+                        break;
+                    };
+                    if attempt >= retry.max_retries {
+                        break;
                     }
+                    let Some(delay) = retry.retry_delay(attempt, retry_after) else {
+                        log::debug!(
+                            "{}Retry-After of {:?} exceeds the longest retry delay, giving up",
+                            workflow_trace::log_prefix(),
+                            retry_after
+                        );
+                        break;
+                    };
+                    log::debug!(
+                        "{}Transcription attempt {} failed, retrying in {:?}",
+                        workflow_trace::log_prefix(),
+                        attempt + 1,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
                 }
             }
         }
@@ -361,14 +730,14 @@ impl OpenAIWhisperClient {
         Err(last_error
             .unwrap_or_else(|| WhisperError::Api("Unknown error during retry loop".to_string())))
     }
-}
 
-#[async_trait]
-impl WhisperClient for OpenAIWhisperClient {
-    async fn transcribe(
+    /// Validate, read and send a file
+    async fn transcribe_file(
         &self,
         file_path: &Path,
         prompt: Option<String>,
+        language: Option<String>,
+        mode: WhisperMode,
     ) -> WhisperResult<TranscriptionResponse> {
         // Validate file size
         let _file_size = self.validate_file_size(file_path).await?;
@@ -388,6 +757,8 @@ impl WhisperClient for OpenAIWhisperClient {
             file_content,
             file_name,
             prompt,
+            language,
+            mode,
         };
 
         // Execute request with retries
@@ -395,6 +766,95 @@ impl WhisperClient for OpenAIWhisperClient {
     }
 }
 
+#[async_trait]
+impl WhisperClient for OpenAIWhisperClient {
+    async fn transcribe(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+    ) -> WhisperResult<TranscriptionResponse> {
+        self.transcribe_file(file_path, prompt, None, self.config.mode)
+            .await
+    }
+
+    async fn transcribe_with_segments(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+    ) -> WhisperResult<TranscriptionResponse> {
+        self.transcribe_file(file_path, prompt, None, self.config.mode)
+            .await
+    }
+
+    async fn transcribe_with_language(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+        language: Option<String>,
+    ) -> WhisperResult<TranscriptionResponse> {
+        self.transcribe_file(file_path, prompt, language, self.config.mode)
+            .await
+    }
+
+    async fn transcribe_with_mode(
+        &self,
+        file_path: &Path,
+        prompt: Option<String>,
+        language: Option<String>,
+        mode: WhisperMode,
+    ) -> WhisperResult<TranscriptionResponse> {
+        self.transcribe_file(file_path, prompt, language, mode)
+            .await
+    }
+}
+
+/// Whisper client used by the workflow dry-run mode
+///
+/// Never calls the API: it checks the encoded file exists and returns a canned
+/// transcript describing it, so the rest of the pipeline can run at no cost.
+#[derive(Debug, Clone, Default)]
+pub struct DryRunWhisperClient {
+    /// Simulated API latency in milliseconds
+    pub response_delay_ms: u64,
+}
+
+impl DryRunWhisperClient {
+    /// Create a new dry-run client with a simulated latency
+    pub fn new(response_delay_ms: u64) -> Self {
+        Self { response_delay_ms }
+    }
+}
+
+#[async_trait]
+impl WhisperClient for DryRunWhisperClient {
+    async fn transcribe(
+        &self,
+        file_path: &Path,
+        _prompt: Option<String>,
+    ) -> WhisperResult<TranscriptionResponse> {
+        if self.response_delay_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(self.response_delay_ms)).await;
+        }
+
+        let metadata = tokio::fs::metadata(file_path).await?;
+
+        Ok(TranscriptionResponse {
+            text: format!(
+                "[Dry run] Mock transcription of {} ({} bytes encoded)",
+                file_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                metadata.len()
+            ),
+            language: None,
+            duration: None,
+            segments: None,
+            words: None,
+        })
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -481,6 +941,7 @@ pub mod tests {
                     compression_ratio: Some(1.5),
                     no_speech_prob: Some(0.01),
                 }]),
+                words: None,
             })
         }
     }
@@ -583,6 +1044,8 @@ pub mod tests {
             file_content: vec![1, 2, 3, 4],
             file_name: "test.ogg".to_string(),
             prompt: Some("test prompt".to_string()),
+            language: Some("fr".to_string()),
+            mode: WhisperMode::Transcribe,
         };
 
         let cloned = request_data.clone();
@@ -591,6 +1054,49 @@ pub mod tests {
         assert_eq!(request_data.prompt, cloned.prompt);
     }
 
+    #[test]
+    fn test_verbose_json_response_parsing() {
+        let body = r#"{
+            "task": "transcribe",
+            "language": "french",
+            "duration": 4.2,
+            "text": "Bonjour à tous.",
+            "segments": [{
+                "id": 0, "seek": 0, "start": 0.0, "end": 4.2, "text": " Bonjour à tous.",
+                "tokens": [50364, 2],
+                "temperature": 0.0, "avg_logprob": -0.2,
+                "compression_ratio": 0.9, "no_speech_prob": 0.01
+            }],
+            "words": [
+                {"word": "Bonjour", "start": 0.0, "end": 0.8},
+                {"word": "à", "start": 0.8, "end": 1.0},
+                {"word": "tous", "start": 1.0, "end": 1.6}
+            ]
+        }"#;
+        let response: TranscriptionResponse = serde_json::from_str(body).unwrap();
+        assert_eq!(response.language.as_deref(), Some("french"));
+        assert_eq!(response.duration, Some(4.2));
+        assert_eq!(response.segments.unwrap()[0].end, 4.2);
+        let words = response.words.unwrap();
+        assert_eq!(words.len(), 3);
+        assert_eq!(words[2].word, "tous");
+        assert_eq!(words[2].start, 1.0);
+
+        // Without word timestamps requested, no words come back
+        let response: TranscriptionResponse =
+            serde_json::from_str(r#"{"text": "Bonjour à tous."}"#).unwrap();
+        assert!(response.words.is_none());
+    }
+
+    #[test]
+    fn test_normalize_language_code() {
+        assert_eq!(normalize_language_code(" FR "), Some("fr".to_string()));
+        assert_eq!(normalize_language_code("yue"), Some("yue".to_string()));
+        assert_eq!(normalize_language_code("french"), None);
+        assert_eq!(normalize_language_code("e1"), None);
+        assert_eq!(normalize_language_code(""), None);
+    }
+
     #[tokio::test]
     async fn test_multipart_form_building() {
         let client = OpenAIWhisperClient::new("test-key".to_string());
@@ -598,9 +1104,133 @@ pub mod tests {
             file_content: vec![1, 2, 3, 4],
             file_name: "test.ogg".to_string(),
             prompt: Some("test prompt".to_string()),
+            language: Some("fr".to_string()),
+            mode: WhisperMode::Transcribe,
         };
 
         let form_result = client.build_multipart_form(&request_data);
         assert!(form_result.is_ok());
+
+        let client = OpenAIWhisperClient::with_config(WhisperClientConfig {
+            word_timestamps: true,
+            ..WhisperClientConfig::default()
+        });
+        assert!(client.build_multipart_form(&request_data).is_ok());
+
+        let translation = RequestData {
+            mode: WhisperMode::Translate,
+            ..request_data
+        };
+        assert!(client.build_multipart_form(&translation).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_translation_mode() {
+        assert_eq!(
+            translation_endpoint("https://api.openai.com/v1/audio/transcriptions"),
+            "https://api.openai.com/v1/audio/translations"
+        );
+        assert_eq!(
+            translation_endpoint("http://localhost:8080/inference"),
+            "http://localhost:8080/inference"
+        );
+        assert_eq!(
+            serde_json::from_str::<WhisperMode>(r#""translate""#).unwrap(),
+            WhisperMode::Translate
+        );
+
+        // Clients without a translation endpoint refuse to translate
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("speech.ogg");
+        fs::write(&file_path, b"fake ogg data").await.unwrap();
+        let client = MockWhisperClient::new();
+        let segmented = SegmentedWhisperClient::new(&client);
+        assert!(segmented.transcribe(&file_path, None).await.is_ok());
+        let translating =
+            SegmentedWhisperClient::new(&client).with_mode(Some(WhisperMode::Translate));
+        assert!(matches!(
+            translating.transcribe(&file_path, None).await,
+            Err(WhisperError::Api(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_client_never_calls_api() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("dry_run.ogg");
+        fs::write(&file_path, b"fake ogg data").await.unwrap();
+
+        let client = DryRunWhisperClient::default();
+        let result = client.transcribe(&file_path, None).await.unwrap();
+        assert!(result.text.starts_with("[Dry run]"));
+        assert!(result.text.contains("13 bytes"));
+
+        // Missing file still surfaces as a file error
+        let missing = temp_dir.path().join("missing.ogg");
+        assert!(matches!(
+            client.transcribe(&missing, None).await,
+            Err(WhisperError::FileIo(_))
+        ));
+    }
+
+    #[test]
+    fn test_request_timeout_scales_with_file_size() {
+        let config = WhisperClientConfig::default();
+        assert_eq!(config.request_timeout_seconds(0), 30);
+        // 20MB at 256 kbit/s take 656s to upload
+        assert_eq!(config.request_timeout_seconds(20 * 1024 * 1024), 30 + 656);
+
+        let fixed = WhisperClientConfig {
+            min_upload_kbps: 0,
+            ..WhisperClientConfig::default()
+        };
+        assert_eq!(fixed.request_timeout_seconds(20 * 1024 * 1024), 30);
+    }
+
+    #[test]
+    fn test_prompt_strategy() {
+        let prompt = "Meeting notes about Kubernetes and the GDPR audit";
+
+        assert_eq!(
+            PromptStrategy::Full.apply(Some(prompt)).as_deref(),
+            Some(prompt)
+        );
+        let vocabulary = PromptStrategy::Vocabulary.apply(Some(prompt)).unwrap();
+        assert!(vocabulary.contains("Kubernetes"));
+        assert!(vocabulary.contains("GDPR"));
+        assert!(!vocabulary.contains("notes"));
+        assert_eq!(PromptStrategy::Vocabulary.apply(Some("just words")), None);
+        assert_eq!(PromptStrategy::None.apply(Some(prompt)), None);
+        assert_eq!(PromptStrategy::Full.apply(Some("  ")), None);
+        assert_eq!(PromptStrategy::Full.apply(None), None);
+    }
+
+    #[test]
+    fn test_tuning_parameters() {
+        assert_eq!(
+            serde_json::from_str::<WhisperResponseFormat>("\"verbose_json\"").unwrap(),
+            WhisperResponseFormat::VerboseJson
+        );
+        assert_eq!(WhisperResponseFormat::Text.as_str(), "text");
+        assert_eq!(
+            serde_json::from_str::<PromptStrategy>("\"vocabulary\"").unwrap(),
+            PromptStrategy::Vocabulary
+        );
+
+        let client = OpenAIWhisperClient::with_config(WhisperClientConfig {
+            temperature: Some(2.0),
+            response_format: WhisperResponseFormat::Text,
+            prompt_strategy: PromptStrategy::None,
+            word_timestamps: true,
+            ..WhisperClientConfig::default()
+        });
+        let request_data = RequestData {
+            file_content: vec![1, 2, 3, 4],
+            file_name: "test.ogg".to_string(),
+            prompt: Some("Kubernetes".to_string()),
+            language: None,
+            mode: WhisperMode::Transcribe,
+        };
+        assert!(client.build_multipart_form(&request_data).is_ok());
     }
 }
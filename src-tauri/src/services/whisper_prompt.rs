@@ -0,0 +1,173 @@
+//! Length guard for the Whisper transcription prompt
+//!
+//! Whisper only reads about the last 224 tokens of its prompt and silently
+//! drops the rest, so a long profile prompt loses the names and jargon it was
+//! meant to teach. A prompt over the limit is rebuilt here instead: the
+//! vocabulary terms (profile keywords, then names, acronyms and product codes
+//! found in the prompt) come first in priority and are placed at the end,
+//! where Whisper pays the most attention, and the start of the prompt fills
+//! the remaining room. The caller is told what was cut.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::whisper_prompt::{fit_whisper_prompt, WHISPER_PROMPT_MAX_TOKENS};
+//!
+//! let prompt = format!("{} Mention Kubernetes and ACME-42.", "Write clean prose. ".repeat(100));
+//! let fitted = fit_whisper_prompt(&prompt, &["DictaClerk".to_string()], WHISPER_PROMPT_MAX_TOKENS);
+//! assert!(fitted.truncation.is_some());
+//! assert!(fitted.text.ends_with("DictaClerk, Kubernetes, ACME-42"));
+//! ```
+
+use serde::Serialize;
+
+use crate::services::context_memory::estimate_tokens;
+use crate::services::reference_context::head_within;
+
+/// Prompt tokens Whisper reads
+pub const WHISPER_PROMPT_MAX_TOKENS: usize = 224;
+
+/// What was cut from a prompt over the limit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PromptTruncation {
+    pub original_tokens: usize,
+    pub kept_tokens: usize,
+    /// Vocabulary terms kept at the end of the prompt
+    pub kept_terms: Vec<String>,
+    /// Vocabulary terms that did not fit
+    pub dropped_terms: Vec<String>,
+}
+
+/// Prompt sent to Whisper
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WhisperPrompt {
+    pub text: String,
+    /// Set when the prompt had to be shortened
+    pub truncation: Option<PromptTruncation>,
+}
+
+/// Words of `text` Whisper is likely to misspell: words with digits or inner
+/// capitals (acronyms, product codes) and capitalized words that do not start
+/// a sentence (names), in order and without duplicates
+pub fn vocabulary_terms(text: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    let mut sentence_start = true;
+    for raw in text.split_whitespace() {
+        let word = raw.trim_matches(|c: char| !c.is_alphanumeric());
+        let is_term = word.chars().any(|c| c.is_ascii_digit())
+            || word.chars().skip(1).any(char::is_uppercase)
+            || (!sentence_start && word.chars().next().is_some_and(char::is_uppercase));
+        if is_term && word.chars().count() > 1 && !terms.iter().any(|term| term == word) {
+            terms.push(word.to_string());
+        }
+        if !word.is_empty() {
+            sentence_start = raw.ends_with(['.', '!', '?', ':']);
+        }
+    }
+    terms
+}
+
+/// Fit `prompt` within `max_tokens`, keeping `vocabulary` and then the terms
+/// found in the prompt before its text
+pub fn fit_whisper_prompt(prompt: &str, vocabulary: &[String], max_tokens: usize) -> WhisperPrompt {
+    let original_tokens = estimate_tokens(prompt);
+    if original_tokens <= max_tokens {
+        return WhisperPrompt {
+            text: prompt.to_string(),
+            truncation: None,
+        };
+    }
+
+    let mut candidates: Vec<String> = Vec::new();
+    for term in vocabulary.iter().cloned().chain(vocabulary_terms(prompt)) {
+        let term = term.trim().to_string();
+        if !term.is_empty() && !candidates.contains(&term) {
+            candidates.push(term);
+        }
+    }
+
+    let mut terms_line = String::new();
+    let mut kept_terms = Vec::new();
+    let mut dropped_terms = Vec::new();
+    for term in candidates {
+        let candidate = if terms_line.is_empty() {
+            term.clone()
+        } else {
+            format!("{}, {}", terms_line, term)
+        };
+        if estimate_tokens(&candidate) <= max_tokens {
+            terms_line = candidate;
+            kept_terms.push(term);
+        } else {
+            dropped_terms.push(term);
+        }
+    }
+
+    // The start of the prompt in the room left (one token for the line break)
+    let room = max_tokens.saturating_sub(estimate_tokens(&terms_line) + 1);
+    let head = if room == 0 {
+        ""
+    } else {
+        head_within(prompt.trim(), room)
+    };
+    let text = match (head.is_empty(), terms_line.is_empty()) {
+        (true, _) => terms_line,
+        (false, true) => head.to_string(),
+        (false, false) => format!("{}\n{}", head, terms_line),
+    };
+
+    WhisperPrompt {
+        truncation: Some(PromptTruncation {
+            original_tokens,
+            kept_tokens: estimate_tokens(&text),
+            kept_terms,
+            dropped_terms,
+        }),
+        text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vocabulary_terms() {
+        assert_eq!(
+            vocabulary_terms(
+                "Send it to Anna Kowalski. The Q3 report uses gRPC, not REST. Anna agrees."
+            ),
+            vec!["Anna", "Kowalski", "Q3", "gRPC", "REST"]
+        );
+    }
+
+    #[test]
+    fn test_fit_whisper_prompt() {
+        let short = fit_whisper_prompt("Meeting notes with Anna.", &[], 224);
+        assert_eq!(short.text, "Meeting notes with Anna.");
+        assert_eq!(short.truncation, None);
+
+        let prompt = format!(
+            "{}Ask Anna Kowalski about the Orion rollout.",
+            "Format the text as tidy meeting notes. ".repeat(40)
+        );
+        let fitted = fit_whisper_prompt(&prompt, &["Zephyr".to_string()], 60);
+        assert!(estimate_tokens(&fitted.text) <= 60);
+        assert!(fitted
+            .text
+            .starts_with("Format the text as tidy meeting notes."));
+        assert!(fitted.text.ends_with("\nZephyr, Anna, Kowalski, Orion"));
+        let truncation = fitted.truncation.unwrap();
+        assert_eq!(truncation.original_tokens, estimate_tokens(&prompt));
+        assert_eq!(truncation.kept_tokens, estimate_tokens(&fitted.text));
+        assert!(truncation.dropped_terms.is_empty());
+
+        // Vocabulary beyond the limit is reported as dropped
+        let vocabulary: Vec<String> = (0..40).map(|index| format!("Term{}", index)).collect();
+        let fitted = fit_whisper_prompt(&prompt, &vocabulary, 60);
+        assert!(estimate_tokens(&fitted.text) <= 60);
+        let truncation = fitted.truncation.unwrap();
+        assert_eq!(truncation.kept_terms[0], "Term0");
+        assert!(truncation.dropped_terms.contains(&"Orion".to_string()));
+    }
+}
@@ -0,0 +1,128 @@
+//! Correlation ID of the current dictation workflow
+//!
+//! A workflow ID (UUID v4) is assigned when a recording starts and kept until
+//! the next recording starts. The pipeline's log lines are prefixed with it
+//! ([`workflow_log!`](crate::workflow_log)), event payloads and API requests
+//! carry it, and it is stored with the history entry or failed workflow, so a
+//! single dictation can be followed end to end. Retrying a failed workflow
+//! resumes its ID.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use dicta_clerk_lib::services::workflow_trace::{begin_workflow, current_workflow_id, tag_payload};
+//!
+//! let id = begin_workflow();
+//! assert_eq!(current_workflow_id(), Some(id.clone()));
+//!
+//! let payload = tag_payload(serde_json::json!({ "job_id": "42" }));
+//! assert_eq!(payload["workflow_id"], id.as_str());
+//! ```
+
+use std::sync::RwLock;
+
+/// Header carrying the workflow ID on API requests (echoed in OpenAI's logs)
+pub const REQUEST_ID_HEADER: &str = "X-Client-Request-Id";
+
+/// Characters of the ID shown in log lines (a prefix of the full ID)
+const LOG_ID_LEN: usize = 8;
+
+static CURRENT: RwLock<Option<String>> = RwLock::new(None);
+
+/// Assign a new workflow ID (at recording start) and return it
+pub fn begin_workflow() -> String {
+    let id = uuid::Uuid::new_v4().to_string();
+    if let Ok(mut current) = CURRENT.write() {
+        *current = Some(id.clone());
+    }
+    id
+}
+
+/// Make `id` the current workflow again (e.g. when retrying it)
+pub fn resume_workflow(id: &str) {
+    if let Ok(mut current) = CURRENT.write() {
+        *current = Some(id.to_string());
+    }
+}
+
+/// ID of the current workflow (None before the first recording)
+pub fn current_workflow_id() -> Option<String> {
+    CURRENT.read().ok().and_then(|current| current.clone())
+}
+
+/// Prefix of the log lines of the current workflow ("" without one)
+pub fn log_prefix() -> String {
+    match current_workflow_id() {
+        Some(id) => format!("[wf {}] ", &id[..LOG_ID_LEN.min(id.len())]),
+        None => String::new(),
+    }
+}
+
+/// Add the current workflow ID to an object payload
+///
+/// Other payloads (strings, numbers...) are returned unchanged, as the
+/// frontend reads them as they are.
+pub fn tag_payload(payload: serde_json::Value) -> serde_json::Value {
+    match (payload, current_workflow_id()) {
+        (serde_json::Value::Object(mut fields), Some(id)) => {
+            fields
+                .entry("workflow_id")
+                .or_insert(serde_json::Value::String(id));
+            serde_json::Value::Object(fields)
+        }
+        (payload, _) => payload,
+    }
+}
+
+/// Debug record of an API response of the current workflow, with the request
+/// ID the provider assigned (to quote in support requests)
+pub fn log_api_response(api: &str, status: u16, provider_request_id: Option<&str>) {
+    log::debug!(
+        "{}{} API responded {} (workflow {}, request {})",
+        log_prefix(),
+        api,
+        status,
+        current_workflow_id().as_deref().unwrap_or("none"),
+        provider_request_id.unwrap_or("unknown")
+    );
+}
+
+/// `eprintln!` prefixed with the current workflow ID
+#[macro_export]
+macro_rules! workflow_log {
+    ($($arg:tt)*) => {
+        eprintln!(
+            "{}{}",
+            $crate::services::workflow_trace::log_prefix(),
+            format_args!($($arg)*)
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workflow_trace() {
+        let id = begin_workflow();
+        assert_eq!(id.len(), 36);
+        assert_eq!(current_workflow_id().as_deref(), Some(id.as_str()));
+        assert_eq!(log_prefix(), format!("[wf {}] ", &id[..8]));
+
+        let tagged = tag_payload(serde_json::json!({ "seconds": 3 }));
+        assert_eq!(tagged["workflow_id"], id.as_str());
+        assert_eq!(tagged["seconds"], 3);
+        assert_eq!(
+            tag_payload(serde_json::json!("plain")),
+            serde_json::json!("plain")
+        );
+        // An ID already in the payload is kept
+        let kept = tag_payload(serde_json::json!({ "workflow_id": "earlier" }));
+        assert_eq!(kept["workflow_id"], "earlier");
+
+        resume_workflow("retried");
+        assert_eq!(current_workflow_id().as_deref(), Some("retried"));
+        assert_ne!(begin_workflow(), id);
+    }
+}
@@ -14,6 +14,6 @@ pub type AppStateMachineState =
 
 // Re-export the global state machine types
 pub use recording_state_machine::{
-    AppEvent, AppState, AppStateChanged, AppStateMachineBuilder, StateMachineError,
-    StateMachineResult,
+    AppEvent, AppState, AppStateChanged, AppStateMachineBuilder, StateHook, StateKind,
+    StateMachineError, StateMachineResult, StateTransition, StopReason,
 };
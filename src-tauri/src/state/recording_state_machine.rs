@@ -1,9 +1,17 @@
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
 use std::path::PathBuf;
-use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::{AppHandle, Emitter};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
 use thiserror::Error;
 
+use crate::services::event_targets::{self, EventAudience};
+use crate::services::notifier::{Notifier, TauriNotifierService};
+use crate::services::workflow_trace;
+
 /// Global application state machine for DictaClerk
 ///
 /// This state machine manages all aspects of the application including:
@@ -23,7 +31,23 @@ pub enum AppState {
     /// Main window is forced visible during recording
     Recording { started_at: SystemTime },
 
+    /// Recording paused: the session (and its WAV file) stays open until
+    /// it is resumed, stopped or cancelled
+    RecordingPaused {
+        started_at: SystemTime,
+        paused_at: SystemTime,
+    },
+
     // === PROCESSING STATES (Sequential) ===
+    /// Recording stopped, waiting for the user to confirm the transcription
+    /// (`workflow.confirm_before_transcription`)
+    AwaitingConfirmation {
+        wav_path: PathBuf,
+        /// Bookmarks of the recording, placed once it is transcribed
+        bookmarks: Vec<Duration>,
+        stopped_at: SystemTime,
+    },
+
     /// Transcribing audio with Whisper API
     ProcessingTranscription {
         wav_path: PathBuf,
@@ -92,6 +116,48 @@ pub enum AppState {
         error: String,
         main_window_visible: bool,
     },
+
+    /// The input device was lost while recording and no other device could
+    /// take over - the user can acknowledge or start a new recording
+    RecordingDeviceError {
+        error: String,
+        main_window_visible: bool,
+    },
+}
+
+/// Variant of an [`AppState`] without its data, which hooks are registered on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StateKind {
+    Idle,
+    Recording,
+    RecordingPaused,
+    AwaitingConfirmation,
+    ProcessingTranscription,
+    ProcessingGPTFormatting,
+    ProcessingClipboard,
+    ProcessingComplete,
+    SettingsWindowOpen,
+    NewProfileEditorOpen,
+    EditProfileEditorOpen,
+    TranscriptionError,
+    GPTFormattingError,
+    ClipboardError,
+    ProfileValidationError,
+    RecordingDeviceError,
+}
+
+/// Why a recording was stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StopReason {
+    /// Stop button, shortcut, tray or voice command
+    #[default]
+    Manual,
+    /// Voice activity detection heard only silence for the configured time
+    Silence,
+    /// The recording reached `audio.max_recording_seconds`
+    #[serde(rename = "max_duration")]
+    MaxDuration,
 }
 
 /// Events that can trigger state transitions
@@ -101,13 +167,25 @@ pub enum AppEvent {
     /// Start a new recording session
     StartRecording,
     /// Stop recording and begin processing
-    StopRecording,
+    StopRecording { reason: StopReason },
     /// Cancel recording without processing
     CancelRecording,
     /// Toggle recording state (start if idle, stop if recording)
     ToggleRecording,
     /// Start recording from system tray interaction
     StartRecordingFromTray,
+    /// Pause the recording without ending the session
+    PauseRecording,
+    /// Resume a paused recording
+    ResumeRecording,
+    /// The input device disconnected while recording; `switched_to` is the
+    /// device the recording continues on (None = the recording could not go on)
+    RecordingDeviceLost {
+        device: String,
+        switched_to: Option<String>,
+    },
+    /// The audio capture could not start on entering the recording state
+    RecordingStartFailed { error: String },
 
     // === WINDOW MANAGEMENT EVENTS ===
     /// Show the main window
@@ -137,6 +215,17 @@ pub enum AppEvent {
     /// Select/activate a profile (ignored during recording)
     SelectProfile { profile_id: String },
 
+    // === CONFIRMATION EVENTS ===
+    /// Hold the recording until the user confirms its transcription
+    HoldForConfirmation {
+        wav_path: PathBuf,
+        bookmarks: Vec<Duration>,
+    },
+    /// Transcribe the held recording
+    ConfirmTranscription,
+    /// Drop the held recording without transcribing it
+    DiscardRecording,
+
     // === PROCESSING COMPLETION EVENTS ===
     /// Transcription completed successfully
     TranscriptionComplete { transcript: String },
@@ -148,6 +237,8 @@ pub enum AppEvent {
     ReformatWithProfile { profile_id: String },
     /// Skip GPT formatting and go directly to clipboard (for profiles without formatting)
     SkipFormattingToClipboard { transcript: String },
+    /// Skip the clipboard copy and complete processing (clipboard auto-copy disabled)
+    SkipClipboardCopy,
 
     // === ERROR EVENTS ===
     /// Transcription failed
@@ -181,6 +272,9 @@ pub struct AppStateChanged {
     pub timestamp: u64,
     /// Additional context data
     pub context: serde_json::Value,
+    /// Workflow the change belongs to (None before the first recording)
+    #[serde(default)]
+    pub workflow_id: Option<String>,
 }
 
 /// Errors that can occur in the state machine
@@ -205,6 +299,80 @@ pub enum StateMachineError {
 
 pub type StateMachineResult<T> = Result<T, StateMachineError>;
 
+/// Transition a hook runs for
+#[derive(Debug, Clone)]
+pub struct StateTransition {
+    pub from: AppState,
+    pub to: AppState,
+    pub event: AppEvent,
+}
+
+/// Side effect run when the state machine enters or leaves a kind of state
+///
+/// Hooks run in registration order once the new state is set and emitted,
+/// whichever command sent the event. The state machine stays locked while
+/// they run: a hook must not send events to it. A failing hook is logged and
+/// does not undo the transition.
+pub type StateHook =
+    Arc<dyn Fn(AppHandle, StateTransition) -> BoxFuture<'static, Result<(), String>> + Send + Sync>;
+
+/// Whether a hook runs when its kind of state is left or entered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HookPhase {
+    Exit,
+    Entry,
+}
+
+/// Entry and exit hooks per kind of state
+#[derive(Clone, Default)]
+struct StateHooks {
+    on_enter: HashMap<StateKind, Vec<StateHook>>,
+    on_exit: HashMap<StateKind, Vec<StateHook>>,
+}
+
+impl StateHooks {
+    fn add(&mut self, phase: HookPhase, kind: StateKind, hook: StateHook) {
+        let hooks = match phase {
+            HookPhase::Exit => &mut self.on_exit,
+            HookPhase::Entry => &mut self.on_enter,
+        };
+        hooks.entry(kind).or_default().push(hook);
+    }
+
+    /// Hooks to run for `transitions`, in order: for each transition, the
+    /// exit hooks of the state left then the entry hooks of the state
+    /// entered (nothing when the kind of state did not change)
+    fn select<'a>(
+        &'a self,
+        transitions: &'a [StateTransition],
+    ) -> Vec<(HookPhase, StateKind, &'a StateHook, &'a StateTransition)> {
+        let mut selected = Vec::new();
+        for transition in transitions {
+            let (from, to) = (transition.from.kind(), transition.to.kind());
+            if from == to {
+                continue;
+            }
+            let exit_hooks = self.on_exit.get(&from).into_iter().flatten();
+            let entry_hooks = self.on_enter.get(&to).into_iter().flatten();
+            selected.extend(
+                exit_hooks
+                    .map(|hook| (HookPhase::Exit, from, hook, transition))
+                    .chain(entry_hooks.map(|hook| (HookPhase::Entry, to, hook, transition))),
+            );
+        }
+        selected
+    }
+}
+
+/// Wrap an async callback into a [`StateHook`]
+fn state_hook<F, Fut>(hook: F) -> StateHook
+where
+    F: Fn(AppHandle, StateTransition) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), String>> + Send + 'static,
+{
+    Arc::new(move |app_handle, transition| Box::pin(hook(app_handle, transition)))
+}
+
 /// Global application state machine
 pub struct AppStateMachine {
     /// Current state of the application
@@ -213,6 +381,87 @@ pub struct AppStateMachine {
     app_handle: AppHandle,
     /// Whether to emit state changes to frontend
     emit_events: bool,
+    /// Tray-only mode: there is no main window to show, nor to acknowledge
+    /// errors from
+    tray_only: bool,
+    /// Side effects of entering and leaving states
+    hooks: StateHooks,
+}
+
+impl AppState {
+    /// Variant of the state
+    pub fn kind(&self) -> StateKind {
+        match self {
+            AppState::Idle { .. } => StateKind::Idle,
+            AppState::Recording { .. } => StateKind::Recording,
+            AppState::RecordingPaused { .. } => StateKind::RecordingPaused,
+            AppState::AwaitingConfirmation { .. } => StateKind::AwaitingConfirmation,
+            AppState::ProcessingTranscription { .. } => StateKind::ProcessingTranscription,
+            AppState::ProcessingGPTFormatting { .. } => StateKind::ProcessingGPTFormatting,
+            AppState::ProcessingClipboard { .. } => StateKind::ProcessingClipboard,
+            AppState::ProcessingComplete { .. } => StateKind::ProcessingComplete,
+            AppState::SettingsWindowOpen { .. } => StateKind::SettingsWindowOpen,
+            AppState::NewProfileEditorOpen { .. } => StateKind::NewProfileEditorOpen,
+            AppState::EditProfileEditorOpen { .. } => StateKind::EditProfileEditorOpen,
+            AppState::TranscriptionError { .. } => StateKind::TranscriptionError,
+            AppState::GPTFormattingError { .. } => StateKind::GPTFormattingError,
+            AppState::ClipboardError { .. } => StateKind::ClipboardError,
+            AppState::ProfileValidationError { .. } => StateKind::ProfileValidationError,
+            AppState::RecordingDeviceError { .. } => StateKind::RecordingDeviceError,
+        }
+    }
+
+    /// The same state with the main window marked hidden
+    fn with_main_window_hidden(self) -> Self {
+        match self {
+            AppState::Idle { .. } => AppState::Idle {
+                main_window_visible: false,
+            },
+            AppState::TranscriptionError {
+                error, wav_path, ..
+            } => AppState::TranscriptionError {
+                error,
+                wav_path,
+                main_window_visible: false,
+            },
+            AppState::GPTFormattingError {
+                error, transcript, ..
+            } => AppState::GPTFormattingError {
+                error,
+                transcript,
+                main_window_visible: false,
+            },
+            AppState::ClipboardError { error, text, .. } => AppState::ClipboardError {
+                error,
+                text,
+                main_window_visible: false,
+            },
+            AppState::ProfileValidationError { error, .. } => AppState::ProfileValidationError {
+                error,
+                main_window_visible: false,
+            },
+            AppState::RecordingDeviceError { error, .. } => AppState::RecordingDeviceError {
+                error,
+                main_window_visible: false,
+            },
+            AppState::SettingsWindowOpen { previous_state } => AppState::SettingsWindowOpen {
+                previous_state: Box::new(previous_state.with_main_window_hidden()),
+            },
+            state => state,
+        }
+    }
+
+    /// Error of an error state
+    fn error_message(&self) -> Option<&str> {
+        match self {
+            AppState::TranscriptionError { error, .. }
+            | AppState::GPTFormattingError { error, .. }
+            | AppState::ClipboardError { error, .. }
+            | AppState::ProfileValidationError { error, .. }
+            | AppState::RecordingDeviceError { error, .. } => Some(error),
+            _ => None,
+        }
+    }
 }
 
 impl AppStateMachine {
@@ -224,6 +473,8 @@ impl AppStateMachine {
             },
             app_handle,
             emit_events: true,
+            tray_only: false,
+            hooks: StateHooks::default(),
         }
     }
 
@@ -232,9 +483,17 @@ impl AppStateMachine {
         &self.current_state
     }
 
-    /// Check if the application is currently recording
+    /// Check if the application is currently recording (paused sessions included)
     pub fn is_recording(&self) -> bool {
-        matches!(self.current_state, AppState::Recording { .. })
+        matches!(
+            self.current_state,
+            AppState::Recording { .. } | AppState::RecordingPaused { .. }
+        )
+    }
+
+    /// Check if the recording is paused
+    pub fn is_paused(&self) -> bool {
+        matches!(self.current_state, AppState::RecordingPaused { .. })
     }
 
     /// Check if the application is processing
@@ -249,14 +508,19 @@ impl AppStateMachine {
 
     /// Check if the main window should be visible
     pub fn is_main_window_visible(&self) -> bool {
+        if self.tray_only {
+            return false;
+        }
         match &self.current_state {
             AppState::Idle {
                 main_window_visible,
             } => *main_window_visible,
             AppState::Recording { .. } => true, // Always visible during recording
+            AppState::RecordingPaused { .. } => true,
             AppState::ProcessingTranscription { .. } => true,
             AppState::ProcessingGPTFormatting { .. } => true,
             AppState::ProcessingClipboard { .. } => true,
+            AppState::AwaitingConfirmation { .. } => true,
             AppState::ProcessingComplete { .. } => true, // Always visible when complete
             AppState::SettingsWindowOpen { previous_state } => {
                 // Check the previous state
@@ -285,6 +549,10 @@ impl AppStateMachine {
                 main_window_visible,
                 ..
             } => *main_window_visible,
+            AppState::RecordingDeviceError {
+                main_window_visible,
+                ..
+            } => *main_window_visible,
         }
     }
 
@@ -298,10 +566,92 @@ impl AppStateMachine {
         )
     }
 
+    /// Why `event` repeats what `state` already reflects, when it does
+    ///
+    /// Rapid shortcut presses and tray clicks send the same event twice: the
+    /// second one is ignored instead of failing as an invalid transition.
+    /// Only events that are invalid in `state` are listed.
+    pub fn duplicate_event_reason(state: &AppState, event: &AppEvent) -> Option<&'static str> {
+        match (state, event) {
+            (
+                AppState::Recording { .. } | AppState::RecordingPaused { .. },
+                AppEvent::StartRecording | AppEvent::StartRecordingFromTray,
+            ) => Some("already recording"),
+            (AppState::RecordingPaused { .. }, AppEvent::PauseRecording) => {
+                Some("recording already paused")
+            }
+            (AppState::Recording { .. }, AppEvent::ResumeRecording) => Some("recording not paused"),
+            (
+                AppState::AwaitingConfirmation { .. }
+                | AppState::ProcessingTranscription { .. }
+                | AppState::ProcessingGPTFormatting { .. }
+                | AppState::ProcessingClipboard { .. },
+                AppEvent::StopRecording { .. },
+            ) => Some("recording already stopped"),
+            (AppState::ProcessingTranscription { .. }, AppEvent::ConfirmTranscription) => {
+                Some("transcription already confirmed")
+            }
+            (AppState::Idle { .. }, AppEvent::CancelRecording | AppEvent::DiscardRecording) => {
+                Some("no recording to drop")
+            }
+            (AppState::Idle { .. }, AppEvent::CloseSettingsWindow) => {
+                Some("settings window already closed")
+            }
+            (AppState::Idle { .. }, AppEvent::AcknowledgeError) => {
+                Some("error already acknowledged")
+            }
+            _ => None,
+        }
+    }
+
     /// Process an event and transition to new state
+    ///
+    /// Duplicate events (see [`Self::duplicate_event_reason`]) leave the state
+    /// as it is and emit `duplicate-event-ignored`.
     pub async fn process_event(&mut self, event: AppEvent) -> StateMachineResult<()> {
+        if let Some(reason) = Self::duplicate_event_reason(&self.current_state, &event) {
+            eprintln!(
+                "⚠️  [STATE-MACHINE] Ignoring duplicate {:?} ({})",
+                event, reason
+            );
+            if self.emit_events {
+                if let Err(e) = self.emit_event(
+                    "duplicate-event-ignored",
+                    serde_json::json!({
+                        "event": format!("{:?}", event),
+                        "current_state": format!("{:?}", self.current_state),
+                        "reason": reason,
+                    }),
+                ) {
+                    eprintln!("⚠️  [STATE-MACHINE] {}", e);
+                }
+            }
+            return Ok(());
+        }
+
         let previous_state = self.current_state.clone();
-        let new_state = self.validate_and_compute_new_state(&event)?;
+        let mut new_state = self.validate_and_compute_new_state(&event)?;
+        if self.tray_only {
+            new_state = new_state.with_main_window_hidden();
+        }
+
+        // A new recording starts a new workflow (resuming or coming back
+        // from a window keeps the current one)
+        if matches!(new_state, AppState::Recording { .. })
+            && !matches!(
+                previous_state,
+                AppState::Recording { .. } | AppState::RecordingPaused { .. }
+            )
+            && matches!(
+                event,
+                AppEvent::StartRecording
+                    | AppEvent::ToggleRecording
+                    | AppEvent::StartRecordingFromTray
+            )
+        {
+            let workflow_id = workflow_trace::begin_workflow();
+            eprintln!("🧵 Workflow {} started", workflow_id);
+        }
 
         // Update state
         self.current_state = new_state;
@@ -311,9 +661,78 @@ impl AppStateMachine {
             self.emit_state_change(&previous_state, &event).await?;
         }
 
+        let mut transitions = vec![StateTransition {
+            from: previous_state,
+            to: self.current_state.clone(),
+            event: event.clone(),
+        }];
+        if self.tray_only {
+            transitions.extend(self.settle_without_window(&event).await?);
+        }
+        self.run_hooks(&transitions).await;
+
         Ok(())
     }
 
+    /// Tray-only mode: report the outcome of a workflow with a notification,
+    /// and acknowledge errors right away as no window can; returns the
+    /// acknowledgement, if any
+    async fn settle_without_window(
+        &mut self,
+        event: &AppEvent,
+    ) -> StateMachineResult<Option<StateTransition>> {
+        let notifier = TauriNotifierService::new(self.app_handle.clone());
+        if let Some(acknowledged) = Self::acknowledge_without_window(&self.current_state)? {
+            if let Some(error) = self.current_state.error_message() {
+                let message = error.to_string();
+                tauri::async_runtime::spawn(async move {
+                    let _ = notifier.error(&message).await;
+                });
+            }
+
+            let previous_state = std::mem::replace(&mut self.current_state, acknowledged);
+            if self.emit_events {
+                self.emit_state_change(&previous_state, &AppEvent::AcknowledgeError)
+                    .await?;
+            }
+            return Ok(Some(StateTransition {
+                from: previous_state,
+                to: self.current_state.clone(),
+                event: AppEvent::AcknowledgeError,
+            }));
+        }
+        if matches!(event, AppEvent::ClipboardCopyComplete)
+            && matches!(self.current_state, AppState::ProcessingComplete { .. })
+        {
+            tauri::async_runtime::spawn(async move {
+                let _ = notifier.success("Copied to the clipboard").await;
+            });
+        }
+        Ok(None)
+    }
+
+    /// State an error state is acknowledged to when there is no window to
+    /// acknowledge it from (None when `state` is not an error)
+    fn acknowledge_without_window(state: &AppState) -> StateMachineResult<Option<AppState>> {
+        if state.error_message().is_none() {
+            return Ok(None);
+        }
+        let acknowledged = Self::validate_transition_static(state, &AppEvent::AcknowledgeError)?;
+        Ok(Some(acknowledged.with_main_window_hidden()))
+    }
+
+    /// Run the hooks of `transitions` (see [`StateHooks::select`])
+    async fn run_hooks(&self, transitions: &[StateTransition]) {
+        for (phase, kind, hook, transition) in self.hooks.select(transitions) {
+            if let Err(e) = hook(self.app_handle.clone(), transition.clone()).await {
+                eprintln!(
+                    "⚠️  [STATE-MACHINE] {:?} {:?} hook failed on {:?}: {}",
+                    kind, phase, transition.event, e
+                );
+            }
+        }
+    }
+
     /// Validate transition and compute new state
     fn validate_and_compute_new_state(&self, event: &AppEvent) -> StateMachineResult<AppState> {
         let current_time = SystemTime::now();
@@ -371,7 +790,7 @@ impl AppStateMachine {
             }
 
             // === FROM RECORDING STATE ===
-            (AppState::Recording { started_at: _ }, AppEvent::StopRecording) => {
+            (AppState::Recording { started_at: _ }, AppEvent::StopRecording { .. }) => {
                 // Note: wav_path would be provided by the audio system
                 Ok(AppState::ProcessingTranscription {
                     wav_path: PathBuf::from("/tmp/recording.wav"), // Placeholder
@@ -400,6 +819,91 @@ impl AppStateMachine {
             (AppState::Recording { .. }, AppEvent::SelectProfile { .. }) => {
                 Ok(self.current_state.clone()) // Ignore profile changes during recording
             }
+            (AppState::Recording { started_at }, AppEvent::PauseRecording) => {
+                Ok(AppState::RecordingPaused {
+                    started_at: *started_at,
+                    paused_at: current_time,
+                })
+            }
+
+            // === FROM RECORDING PAUSED STATE ===
+            (AppState::RecordingPaused { started_at, .. }, AppEvent::ResumeRecording) => {
+                Ok(AppState::Recording {
+                    started_at: *started_at,
+                })
+            }
+            (
+                AppState::RecordingPaused { .. },
+                AppEvent::StopRecording { .. } | AppEvent::ToggleRecording,
+            ) => {
+                // Stopping a paused session transcribes what was recorded so far
+                Ok(AppState::ProcessingTranscription {
+                    wav_path: PathBuf::from("/tmp/recording.wav"), // Placeholder
+                    started_at: current_time,
+                })
+            }
+            (AppState::RecordingPaused { .. }, AppEvent::CancelRecording) => Ok(AppState::Idle {
+                main_window_visible: true,
+            }),
+            (AppState::RecordingPaused { .. }, AppEvent::OpenSettingsWindow) => {
+                Ok(AppState::SettingsWindowOpen {
+                    previous_state: Box::new(AppState::Idle {
+                        main_window_visible: true,
+                    }),
+                })
+            }
+            (AppState::RecordingPaused { .. }, AppEvent::SelectProfile { .. }) => {
+                Ok(self.current_state.clone()) // Ignore profile changes during recording
+            }
+
+            // === DEVICE LOSS WHILE RECORDING ===
+            (
+                AppState::Recording { .. } | AppState::RecordingPaused { .. },
+                AppEvent::RecordingDeviceLost {
+                    switched_to: Some(_),
+                    ..
+                },
+            ) => Ok(self.current_state.clone()), // The recording goes on with the other device
+            (
+                AppState::Recording { .. } | AppState::RecordingPaused { .. },
+                AppEvent::RecordingDeviceLost {
+                    device,
+                    switched_to: None,
+                },
+            ) => Ok(AppState::RecordingDeviceError {
+                error: format!("Recording device '{}' was disconnected", device),
+                main_window_visible: true,
+            }),
+            (AppState::Recording { .. }, AppEvent::RecordingStartFailed { error }) => {
+                Ok(AppState::RecordingDeviceError {
+                    error: format!("Failed to start recording: {}", error),
+                    main_window_visible: true,
+                })
+            }
+
+            // === CONFIRMATION BEFORE TRANSCRIPTION ===
+            (
+                AppState::ProcessingTranscription { .. },
+                AppEvent::HoldForConfirmation {
+                    wav_path,
+                    bookmarks,
+                },
+            ) => Ok(AppState::AwaitingConfirmation {
+                wav_path: wav_path.clone(),
+                bookmarks: bookmarks.clone(),
+                stopped_at: current_time,
+            }),
+            (AppState::AwaitingConfirmation { wav_path, .. }, AppEvent::ConfirmTranscription) => {
+                Ok(AppState::ProcessingTranscription {
+                    wav_path: wav_path.clone(),
+                    started_at: current_time,
+                })
+            }
+            (AppState::AwaitingConfirmation { .. }, AppEvent::DiscardRecording) => {
+                Ok(AppState::Idle {
+                    main_window_visible: true,
+                })
+            }
 
             // === FROM PROCESSING STATES ===
             // Opening settings from processing states - force cancel processing and set previous_state to Idle
@@ -524,6 +1028,19 @@ impl AppStateMachine {
                 text: text.clone(),
                 main_window_visible: true,
             }),
+            (
+                AppState::ProcessingClipboard {
+                    original_transcript,
+                    text,
+                    ..
+                },
+                AppEvent::SkipClipboardCopy,
+            ) => Ok(AppState::ProcessingComplete {
+                original_transcript: original_transcript.clone(),
+                final_text: text.clone(),
+                profile_id: None,
+                completed_at: current_time,
+            }),
 
             // === FROM PROCESSING COMPLETE ===
             (AppState::ProcessingComplete { .. }, AppEvent::OpenSettingsWindow) => {
@@ -683,6 +1200,25 @@ impl AppStateMachine {
             ) => Ok(AppState::Idle {
                 main_window_visible: *main_window_visible,
             }),
+            (
+                AppState::RecordingDeviceError {
+                    main_window_visible,
+                    ..
+                },
+                AppEvent::AcknowledgeError,
+            ) => Ok(AppState::Idle {
+                main_window_visible: *main_window_visible,
+            }),
+            // Recoverable: a new recording can start right away (e.g. once
+            // the device is plugged back in)
+            (
+                AppState::RecordingDeviceError { .. },
+                AppEvent::StartRecording
+                | AppEvent::ToggleRecording
+                | AppEvent::StartRecordingFromTray,
+            ) => Ok(AppState::Recording {
+                started_at: current_time,
+            }),
 
             // === UNIVERSAL EVENTS ===
             (_, AppEvent::Reset) => Ok(AppState::Idle {
@@ -714,30 +1250,53 @@ impl AppStateMachine {
             current_state: format!("{:?}", self.current_state),
             event: format!("{:?}", event),
             timestamp,
+            workflow_id: workflow_trace::current_workflow_id(),
             context: serde_json::json!({
                 "is_recording": self.is_recording(),
+                "is_paused": self.is_paused(),
                 "is_processing": self.is_processing(),
                 "main_window_visible": self.is_main_window_visible(),
                 "has_modal_window": self.has_modal_window_open(),
+                "stop_reason": match event {
+                    AppEvent::StopRecording { reason } => Some(*reason),
+                    _ => None,
+                },
             }),
         };
 
-        self.app_handle
-            .emit("app-state-changed", &state_change)
+        event_targets::broadcast(&self.app_handle, "app-state-changed", &state_change)
             .map_err(|e| StateMachineError::EmitFailed(e.to_string()))?;
 
         Ok(())
     }
 
-    /// Emit a custom event to the frontend
+    /// Emit a custom event to every window
+    ///
+    /// Object payloads get the current `workflow_id`.
     pub fn emit_event<T: serde::Serialize + Clone>(
         &self,
         event_name: &str,
         payload: T,
     ) -> Result<(), String> {
-        self.app_handle
-            .emit(event_name, payload)
-            .map_err(|e| format!("Failed to emit event '{}': {}", event_name, e))
+        self.emit_event_to(EventAudience::Broadcast, event_name, payload)
+    }
+
+    /// Emit a custom event to the windows of `audience` only
+    pub fn emit_event_to<T: serde::Serialize + Clone>(
+        &self,
+        audience: EventAudience,
+        event_name: &str,
+        payload: T,
+    ) -> Result<(), String> {
+        let payload = serde_json::to_value(payload)
+            .map_err(|e| format!("Failed to serialize event '{}': {}", event_name, e))?;
+        event_targets::emit_targeted(
+            &self.app_handle,
+            audience,
+            event_name,
+            workflow_trace::tag_payload(payload),
+        )
+        .map_err(|e| format!("Failed to emit event '{}': {}", event_name, e))
     }
 
     /// Static method to validate a transition (for testing)
@@ -800,7 +1359,7 @@ impl AppStateMachine {
             }
 
             // === FROM RECORDING STATE ===
-            (AppState::Recording { started_at: _ }, AppEvent::StopRecording) => {
+            (AppState::Recording { started_at: _ }, AppEvent::StopRecording { .. }) => {
                 // Note: wav_path would be provided by the audio system
                 Ok(AppState::ProcessingTranscription {
                     wav_path: PathBuf::from("/tmp/recording.wav"), // Placeholder
@@ -829,6 +1388,91 @@ impl AppStateMachine {
             (AppState::Recording { .. }, AppEvent::SelectProfile { .. }) => {
                 Ok(current_state.clone()) // Ignore profile changes during recording
             }
+            (AppState::Recording { started_at }, AppEvent::PauseRecording) => {
+                Ok(AppState::RecordingPaused {
+                    started_at: *started_at,
+                    paused_at: current_time,
+                })
+            }
+
+            // === FROM RECORDING PAUSED STATE ===
+            (AppState::RecordingPaused { started_at, .. }, AppEvent::ResumeRecording) => {
+                Ok(AppState::Recording {
+                    started_at: *started_at,
+                })
+            }
+            (
+                AppState::RecordingPaused { .. },
+                AppEvent::StopRecording { .. } | AppEvent::ToggleRecording,
+            ) => {
+                // Stopping a paused session transcribes what was recorded so far
+                Ok(AppState::ProcessingTranscription {
+                    wav_path: PathBuf::from("/tmp/recording.wav"), // Placeholder
+                    started_at: current_time,
+                })
+            }
+            (AppState::RecordingPaused { .. }, AppEvent::CancelRecording) => Ok(AppState::Idle {
+                main_window_visible: true,
+            }),
+            (AppState::RecordingPaused { .. }, AppEvent::OpenSettingsWindow) => {
+                Ok(AppState::SettingsWindowOpen {
+                    previous_state: Box::new(AppState::Idle {
+                        main_window_visible: true,
+                    }),
+                })
+            }
+            (AppState::RecordingPaused { .. }, AppEvent::SelectProfile { .. }) => {
+                Ok(current_state.clone()) // Ignore profile changes during recording
+            }
+
+            // === DEVICE LOSS WHILE RECORDING ===
+            (
+                AppState::Recording { .. } | AppState::RecordingPaused { .. },
+                AppEvent::RecordingDeviceLost {
+                    switched_to: Some(_),
+                    ..
+                },
+            ) => Ok(current_state.clone()), // The recording goes on with the other device
+            (
+                AppState::Recording { .. } | AppState::RecordingPaused { .. },
+                AppEvent::RecordingDeviceLost {
+                    device,
+                    switched_to: None,
+                },
+            ) => Ok(AppState::RecordingDeviceError {
+                error: format!("Recording device '{}' was disconnected", device),
+                main_window_visible: true,
+            }),
+            (AppState::Recording { .. }, AppEvent::RecordingStartFailed { error }) => {
+                Ok(AppState::RecordingDeviceError {
+                    error: format!("Failed to start recording: {}", error),
+                    main_window_visible: true,
+                })
+            }
+
+            // === CONFIRMATION BEFORE TRANSCRIPTION ===
+            (
+                AppState::ProcessingTranscription { .. },
+                AppEvent::HoldForConfirmation {
+                    wav_path,
+                    bookmarks,
+                },
+            ) => Ok(AppState::AwaitingConfirmation {
+                wav_path: wav_path.clone(),
+                bookmarks: bookmarks.clone(),
+                stopped_at: current_time,
+            }),
+            (AppState::AwaitingConfirmation { wav_path, .. }, AppEvent::ConfirmTranscription) => {
+                Ok(AppState::ProcessingTranscription {
+                    wav_path: wav_path.clone(),
+                    started_at: current_time,
+                })
+            }
+            (AppState::AwaitingConfirmation { .. }, AppEvent::DiscardRecording) => {
+                Ok(AppState::Idle {
+                    main_window_visible: true,
+                })
+            }
 
             // === FROM PROCESSING STATES ===
             // Opening settings from processing states - force cancel processing and set previous_state to Idle
@@ -953,6 +1597,19 @@ impl AppStateMachine {
                 text: text.clone(),
                 main_window_visible: true,
             }),
+            (
+                AppState::ProcessingClipboard {
+                    original_transcript,
+                    text,
+                    ..
+                },
+                AppEvent::SkipClipboardCopy,
+            ) => Ok(AppState::ProcessingComplete {
+                original_transcript: original_transcript.clone(),
+                final_text: text.clone(),
+                profile_id: None,
+                completed_at: current_time,
+            }),
 
             // === FROM PROCESSING COMPLETE ===
             (AppState::ProcessingComplete { .. }, AppEvent::OpenSettingsWindow) => {
@@ -1112,6 +1769,25 @@ impl AppStateMachine {
             ) => Ok(AppState::Idle {
                 main_window_visible: *main_window_visible,
             }),
+            (
+                AppState::RecordingDeviceError {
+                    main_window_visible,
+                    ..
+                },
+                AppEvent::AcknowledgeError,
+            ) => Ok(AppState::Idle {
+                main_window_visible: *main_window_visible,
+            }),
+            // Recoverable: a new recording can start right away (e.g. once
+            // the device is plugged back in)
+            (
+                AppState::RecordingDeviceError { .. },
+                AppEvent::StartRecording
+                | AppEvent::ToggleRecording
+                | AppEvent::StartRecordingFromTray,
+            ) => Ok(AppState::Recording {
+                started_at: current_time,
+            }),
 
             // === UNIVERSAL EVENTS ===
             (_, AppEvent::Reset) => Ok(AppState::Idle {
@@ -1132,6 +1808,8 @@ impl AppStateMachine {
 pub struct AppStateMachineBuilder {
     initial_state: Option<AppState>,
     emit_events: bool,
+    tray_only: bool,
+    hooks: StateHooks,
 }
 
 impl AppStateMachineBuilder {
@@ -1139,6 +1817,8 @@ impl AppStateMachineBuilder {
         Self {
             initial_state: None,
             emit_events: true,
+            tray_only: false,
+            hooks: StateHooks::default(),
         }
     }
 
@@ -1152,14 +1832,45 @@ impl AppStateMachineBuilder {
         self
     }
 
+    /// Run without the main window (`ui.tray_only`)
+    pub fn with_tray_only(mut self, tray_only: bool) -> Self {
+        self.tray_only = tray_only;
+        self
+    }
+
+    /// Run `hook` each time the state machine enters a state of `kind`
+    pub fn with_entry_hook<F, Fut>(mut self, kind: StateKind, hook: F) -> Self
+    where
+        F: Fn(AppHandle, StateTransition) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.hooks.add(HookPhase::Entry, kind, state_hook(hook));
+        self
+    }
+
+    /// Run `hook` each time the state machine leaves a state of `kind`
+    pub fn with_exit_hook<F, Fut>(mut self, kind: StateKind, hook: F) -> Self
+    where
+        F: Fn(AppHandle, StateTransition) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.hooks.add(HookPhase::Exit, kind, state_hook(hook));
+        self
+    }
+
     pub fn build(self, app_handle: AppHandle) -> AppStateMachine {
         let mut machine = AppStateMachine::new(app_handle);
 
         if let Some(initial_state) = self.initial_state {
             machine.current_state = initial_state;
         }
+        if self.tray_only {
+            machine.current_state = machine.current_state.with_main_window_hidden();
+        }
 
         machine.emit_events = self.emit_events;
+        machine.tray_only = self.tray_only;
+        machine.hooks = self.hooks;
         machine
     }
 }
@@ -1191,7 +1902,9 @@ mod tests {
         let recording_state = AppState::Recording {
             started_at: SystemTime::now(),
         };
-        let event = AppEvent::StopRecording;
+        let event = AppEvent::StopRecording {
+            reason: StopReason::Manual,
+        };
 
         let result = AppStateMachine::validate_transition_static(&recording_state, &event);
         assert!(result.is_ok());
@@ -1201,6 +1914,31 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_silence_stop_processes_like_manual_stop() {
+        let recording_state = AppState::Recording {
+            started_at: SystemTime::now(),
+        };
+        let event = AppEvent::StopRecording {
+            reason: StopReason::Silence,
+        };
+
+        let result = AppStateMachine::validate_transition_static(&recording_state, &event);
+        assert!(matches!(
+            result,
+            Ok(AppState::ProcessingTranscription { .. })
+        ));
+        assert_eq!(
+            serde_json::to_value(StopReason::Silence).unwrap(),
+            serde_json::json!("silence")
+        );
+        assert_eq!(
+            serde_json::to_value(StopReason::MaxDuration).unwrap(),
+            serde_json::json!("max_duration")
+        );
+        assert_eq!(StopReason::default(), StopReason::Manual);
+    }
+
     #[test]
     fn test_profile_selection_ignored_during_recording() {
         let recording_state = AppState::Recording {
@@ -1216,6 +1954,156 @@ mod tests {
         assert!(matches!(result.unwrap(), AppState::Recording { .. }));
     }
 
+    #[test]
+    fn test_pause_and_resume_recording() {
+        let started_at = SystemTime::now();
+        let recording_state = AppState::Recording { started_at };
+
+        let paused = AppStateMachine::validate_transition_static(
+            &recording_state,
+            &AppEvent::PauseRecording,
+        )
+        .unwrap();
+        assert!(matches!(
+            paused,
+            AppState::RecordingPaused { started_at: s, .. } if s == started_at
+        ));
+
+        // Resuming keeps the session start time
+        let resumed =
+            AppStateMachine::validate_transition_static(&paused, &AppEvent::ResumeRecording)
+                .unwrap();
+        assert_eq!(resumed, AppState::Recording { started_at });
+
+        // A paused session can be stopped (and transcribed) or cancelled
+        assert!(matches!(
+            AppStateMachine::validate_transition_static(
+                &paused,
+                &AppEvent::StopRecording {
+                    reason: StopReason::Manual
+                }
+            ),
+            Ok(AppState::ProcessingTranscription { .. })
+        ));
+        assert!(matches!(
+            AppStateMachine::validate_transition_static(&paused, &AppEvent::ToggleRecording),
+            Ok(AppState::ProcessingTranscription { .. })
+        ));
+        assert!(matches!(
+            AppStateMachine::validate_transition_static(&paused, &AppEvent::CancelRecording),
+            Ok(AppState::Idle { .. })
+        ));
+
+        // Only a running recording can be paused, only a paused one resumed
+        let idle_state = AppState::Idle {
+            main_window_visible: true,
+        };
+        assert!(AppStateMachine::validate_transition_static(
+            &idle_state,
+            &AppEvent::PauseRecording
+        )
+        .is_err());
+        assert!(AppStateMachine::validate_transition_static(
+            &recording_state,
+            &AppEvent::ResumeRecording
+        )
+        .is_err());
+        assert!(
+            AppStateMachine::validate_transition_static(&paused, &AppEvent::PauseRecording)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_confirmation_before_transcription() {
+        let processing = AppState::ProcessingTranscription {
+            wav_path: PathBuf::from("/tmp/recording.wav"),
+            started_at: SystemTime::now(),
+        };
+        let hold = AppEvent::HoldForConfirmation {
+            wav_path: PathBuf::from("/tmp/recording.wav"),
+            bookmarks: vec![Duration::from_secs(4)],
+        };
+        let awaiting = AppStateMachine::validate_transition_static(&processing, &hold).unwrap();
+        assert!(matches!(
+            &awaiting,
+            AppState::AwaitingConfirmation { wav_path, bookmarks, .. }
+                if wav_path == &PathBuf::from("/tmp/recording.wav")
+                    && bookmarks == &[Duration::from_secs(4)]
+        ));
+
+        let confirmed =
+            AppStateMachine::validate_transition_static(&awaiting, &AppEvent::ConfirmTranscription);
+        assert!(matches!(
+            confirmed,
+            Ok(AppState::ProcessingTranscription { wav_path, .. }) if wav_path == std::path::Path::new("/tmp/recording.wav")
+        ));
+        let discarded =
+            AppStateMachine::validate_transition_static(&awaiting, &AppEvent::DiscardRecording);
+        assert!(matches!(discarded, Ok(AppState::Idle { .. })));
+
+        // A new recording cannot start while one waits for confirmation
+        assert!(
+            AppStateMachine::validate_transition_static(&awaiting, &AppEvent::StartRecording)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_recording_device_lost() {
+        let started_at = SystemTime::now();
+        let recording_state = AppState::Recording { started_at };
+
+        // Another device took over: the recording goes on
+        let switched = AppEvent::RecordingDeviceLost {
+            device: "USB Mic".to_string(),
+            switched_to: Some("Built-in Microphone".to_string()),
+        };
+        assert_eq!(
+            AppStateMachine::validate_transition_static(&recording_state, &switched).unwrap(),
+            recording_state
+        );
+
+        // No device left: recoverable error
+        let stalled = AppEvent::RecordingDeviceLost {
+            device: "USB Mic".to_string(),
+            switched_to: None,
+        };
+        let paused = AppState::RecordingPaused {
+            started_at,
+            paused_at: started_at,
+        };
+        for state in [&recording_state, &paused] {
+            let error_state = AppStateMachine::validate_transition_static(state, &stalled).unwrap();
+            assert!(matches!(
+                &error_state,
+                AppState::RecordingDeviceError { error, main_window_visible: true }
+                    if error.contains("USB Mic")
+            ));
+        }
+
+        let error_state = AppState::RecordingDeviceError {
+            error: "Recording device 'USB Mic' was disconnected".to_string(),
+            main_window_visible: true,
+        };
+        assert!(matches!(
+            AppStateMachine::validate_transition_static(&error_state, &AppEvent::AcknowledgeError),
+            Ok(AppState::Idle {
+                main_window_visible: true
+            })
+        ));
+        assert!(matches!(
+            AppStateMachine::validate_transition_static(&error_state, &AppEvent::StartRecording),
+            Ok(AppState::Recording { .. })
+        ));
+
+        // Only a recording can lose its device
+        let idle_state = AppState::Idle {
+            main_window_visible: true,
+        };
+        assert!(AppStateMachine::validate_transition_static(&idle_state, &stalled).is_err());
+    }
+
     #[test]
     fn test_window_visibility_logic() {
         // Test that recording always shows main window
@@ -1406,4 +2294,249 @@ mod tests {
             panic!("Expected ProcessingClipboard state");
         }
     }
+
+    #[test]
+    fn test_skip_clipboard_copy() {
+        // Test workflow when clipboard auto-copy is disabled globally
+        let clipboard_state = AppState::ProcessingClipboard {
+            original_transcript: "Raw transcript".to_string(),
+            text: "Formatted text".to_string(),
+            started_at: SystemTime::now(),
+        };
+
+        let result = AppStateMachine::validate_transition_static(
+            &clipboard_state,
+            &AppEvent::SkipClipboardCopy,
+        );
+        assert!(result.is_ok());
+
+        if let AppState::ProcessingComplete {
+            original_transcript,
+            final_text,
+            ..
+        } = result.unwrap()
+        {
+            assert_eq!(original_transcript, "Raw transcript");
+            assert_eq!(final_text, "Formatted text");
+        } else {
+            panic!("Expected ProcessingComplete state");
+        }
+
+        // Skipping the clipboard is only valid while in the clipboard stage
+        let idle_state = AppState::Idle {
+            main_window_visible: true,
+        };
+        assert!(AppStateMachine::validate_transition_static(
+            &idle_state,
+            &AppEvent::SkipClipboardCopy
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_main_window_hidden_in_tray_only_mode() {
+        let settings = AppState::SettingsWindowOpen {
+            previous_state: Box::new(AppState::Idle {
+                main_window_visible: true,
+            }),
+        };
+        assert_eq!(
+            settings.with_main_window_hidden(),
+            AppState::SettingsWindowOpen {
+                previous_state: Box::new(AppState::Idle {
+                    main_window_visible: false,
+                }),
+            }
+        );
+
+        let error = AppState::TranscriptionError {
+            error: "Network error".to_string(),
+            wav_path: PathBuf::from("/tmp/recording.wav"),
+            main_window_visible: true,
+        }
+        .with_main_window_hidden();
+        assert_eq!(error.error_message(), Some("Network error"));
+        assert_eq!(
+            AppStateMachine::validate_transition_static(&error, &AppEvent::AcknowledgeError)
+                .unwrap(),
+            AppState::Idle {
+                main_window_visible: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_duplicate_events_ignored() {
+        let recording = AppState::Recording {
+            started_at: SystemTime::now(),
+        };
+        let transcribing = AppState::ProcessingTranscription {
+            wav_path: PathBuf::from("/tmp/recording.wav"),
+            started_at: SystemTime::now(),
+        };
+        let idle = AppState::Idle {
+            main_window_visible: true,
+        };
+        let stop = AppEvent::StopRecording {
+            reason: StopReason::Manual,
+        };
+
+        let duplicates = [
+            (&recording, AppEvent::StartRecording),
+            (&recording, AppEvent::StartRecordingFromTray),
+            (&recording, AppEvent::ResumeRecording),
+            (&transcribing, stop.clone()),
+            (&transcribing, AppEvent::ConfirmTranscription),
+            (&idle, AppEvent::CancelRecording),
+            (&idle, AppEvent::AcknowledgeError),
+        ];
+        for (state, event) in duplicates {
+            assert!(
+                AppStateMachine::duplicate_event_reason(state, &event).is_some(),
+                "{:?} in {:?} should be a duplicate",
+                event,
+                state
+            );
+            // Only events that would otherwise fail are suppressed
+            assert!(AppStateMachine::validate_transition_static(state, &event).is_err());
+        }
+
+        // Events that change the state are never suppressed
+        assert!(AppStateMachine::duplicate_event_reason(&recording, &stop).is_none());
+        assert!(
+            AppStateMachine::duplicate_event_reason(&recording, &AppEvent::ToggleRecording)
+                .is_none()
+        );
+        assert!(
+            AppStateMachine::duplicate_event_reason(&idle, &AppEvent::StartRecording).is_none()
+        );
+        assert!(AppStateMachine::duplicate_event_reason(&transcribing, &AppEvent::Reset).is_none());
+    }
+
+    #[test]
+    fn test_hooks_run_exit_then_entry_for_kind_changes() {
+        let hook = || state_hook(|_, _| async { Ok(()) });
+        let (leave_recording, enter_transcription, enter_idle) = (hook(), hook(), hook());
+        let mut hooks = StateHooks::default();
+        hooks.add(
+            HookPhase::Entry,
+            StateKind::ProcessingTranscription,
+            enter_transcription.clone(),
+        );
+        hooks.add(
+            HookPhase::Exit,
+            StateKind::Recording,
+            leave_recording.clone(),
+        );
+        hooks.add(HookPhase::Entry, StateKind::Idle, enter_idle.clone());
+
+        let recording = AppState::Recording {
+            started_at: SystemTime::now(),
+        };
+        let transcribing = AppState::ProcessingTranscription {
+            wav_path: PathBuf::from("/tmp/recording.wav"),
+            started_at: SystemTime::now(),
+        };
+        let transitions = [
+            // Same kind of state: no hook
+            StateTransition {
+                from: recording.clone(),
+                to: recording.clone(),
+                event: AppEvent::SelectProfile {
+                    profile_id: "1".to_string(),
+                },
+            },
+            StateTransition {
+                from: recording,
+                to: transcribing,
+                event: AppEvent::StopRecording {
+                    reason: StopReason::Manual,
+                },
+            },
+        ];
+        let selected: Vec<_> = hooks
+            .select(&transitions)
+            .into_iter()
+            .map(|(phase, kind, hook, _)| (phase, kind, hook.clone()))
+            .collect();
+        assert_eq!(selected.len(), 2);
+        assert_eq!(
+            (selected[0].0, selected[0].1),
+            (HookPhase::Exit, StateKind::Recording)
+        );
+        assert!(Arc::ptr_eq(&selected[0].2, &leave_recording));
+        assert_eq!(
+            (selected[1].0, selected[1].1),
+            (HookPhase::Entry, StateKind::ProcessingTranscription)
+        );
+        assert!(Arc::ptr_eq(&selected[1].2, &enter_transcription));
+
+        // Tray-only mode acknowledges errors itself: the hooks of the
+        // acknowledgement run after those of the error
+        let error = AppState::TranscriptionError {
+            error: "Network error".to_string(),
+            wav_path: PathBuf::from("/tmp/recording.wav"),
+            main_window_visible: false,
+        };
+        let acknowledged = AppStateMachine::acknowledge_without_window(&error)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            acknowledged,
+            AppState::Idle {
+                main_window_visible: false,
+            }
+        );
+        let transitions = [
+            StateTransition {
+                from: transitions[1].to.clone(),
+                to: error.clone(),
+                event: AppEvent::TranscriptionError {
+                    error: "Network error".to_string(),
+                },
+            },
+            StateTransition {
+                from: error,
+                to: acknowledged.clone(),
+                event: AppEvent::AcknowledgeError,
+            },
+        ];
+        let selected = hooks.select(&transitions);
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].0, HookPhase::Entry);
+        assert!(Arc::ptr_eq(selected[0].2, &enter_idle));
+        assert_eq!(selected[0].3.event, AppEvent::AcknowledgeError);
+        assert!(AppStateMachine::acknowledge_without_window(&acknowledged)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_state_kind_ignores_state_data() {
+        let recording = AppState::Recording {
+            started_at: SystemTime::now(),
+        };
+        let resumed = AppStateMachine::validate_transition_static(
+            &AppState::RecordingPaused {
+                started_at: SystemTime::now(),
+                paused_at: SystemTime::now(),
+            },
+            &AppEvent::ResumeRecording,
+        )
+        .unwrap();
+        assert_eq!(recording.kind(), StateKind::Recording);
+        assert_eq!(resumed.kind(), recording.kind());
+
+        // Window states are their own kind, whatever they were opened from
+        let settings = AppState::SettingsWindowOpen {
+            previous_state: Box::new(recording),
+        };
+        assert_eq!(settings.kind(), StateKind::SettingsWindowOpen);
+        assert_eq!(
+            AppStateMachine::validate_transition_static(&settings, &AppEvent::Reset)
+                .unwrap()
+                .kind(),
+            StateKind::Idle
+        );
+    }
 }
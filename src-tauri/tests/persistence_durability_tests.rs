@@ -3,10 +3,23 @@
 //! These tests verify atomic writes, error handling, rollback functionality,
 //! and ensure settings/profiles survive application restarts.
 
+use dicta_clerk_lib::audio::{CaptureSource, EncoderApplication, EncoderFormat};
 use dicta_clerk_lib::commands::settings::{
-    AudioSettings, EncodingSettings, SettingsConfig, UiSettings, WhisperSettings,
+    AudioSettings, BookmarkSettings, ClipboardSettings, ContextMemorySettings, ContinuousSettings,
+    EncodingSettings, FormattingSettings, GoalSettings, HistorySettings, InputIntegrationSettings,
+    InterviewSettings, LiveCaptionSettings, LocalApiSettings, LocalInferenceSettings,
+    LongRecordingSettings, OutputSettings, ProfileSuggestionSettings, ProfileSyncSettings,
+    RecordingArchiveSettings, SettingsConfig, TranscriptionQueueSettings, TtsSettings, UiSettings,
+    VoiceCommandSettings, WeeklyReportSettings, WhisperSettings, WorkflowSettings,
+};
+use dicta_clerk_lib::services::api_limiter::ApiLimits;
+use dicta_clerk_lib::services::feature_flags::FeatureFlags;
+use dicta_clerk_lib::services::network::NetworkConfig;
+use dicta_clerk_lib::services::notifier::NotificationRouting;
+use dicta_clerk_lib::services::profile_engine::{Profile, ProfileCollection, ProfileKind};
+use dicta_clerk_lib::services::{
+    PromptStrategy, WhisperMode, WhisperProvider, WhisperResponseFormat,
 };
-use dicta_clerk_lib::services::profile_engine::{Profile, ProfileCollection};
 use serial_test::serial;
 use std::{env, fs, path::Path, path::PathBuf};
 use tempfile::TempDir;
@@ -21,19 +34,73 @@ fn create_test_settings() -> SettingsConfig {
             model: "whisper-1".to_string(),
             timeout_seconds: 30,
             max_retries: 3,
+            word_timestamps: false,
+            mode: WhisperMode::Transcribe,
+            provider: WhisperProvider::OpenAi,
+            temperature: Some(0.0),
+            response_format: WhisperResponseFormat::VerboseJson,
+            prompt_strategy: PromptStrategy::Vocabulary,
+            min_upload_kbps: 128,
+            retry_base_delay_ms: 500,
+            retry_max_delay_ms: 20_000,
         },
         audio: AudioSettings {
             input_device: None,
             sample_rate: 44100,
             buffer_size: 1024,
+            gain_db: 0.0,
+            auto_gain: false,
+            pause_media: false,
+            auto_stop_silence_seconds: 0,
+            max_recording_seconds: 0,
+            pre_roll_seconds: 0.0,
+            capture_source: CaptureSource::Microphone,
+            noise_suppression: false,
+            loudness_normalization: false,
+            target_lufs: -18.0,
         },
         encoding: EncodingSettings {
             bitrate: 32000,
             size_limit_mb: 23,
+            complexity: 10,
+            application: EncoderApplication::Audio,
+            dtx: false,
+            downmix_to_mono: true,
+            format: EncoderFormat::Ogg,
+            vbr: true,
         },
         ui: UiSettings {
             theme: "auto".to_string(),
             auto_start_recording: false,
+            tray_only: false,
+        },
+        formatting: FormattingSettings::default(),
+        clipboard: ClipboardSettings::default(),
+        workflow: WorkflowSettings::default(),
+        local_api: LocalApiSettings::default(),
+        live_captions: LiveCaptionSettings::default(),
+        interview: InterviewSettings::default(),
+        local_inference: LocalInferenceSettings::default(),
+        output: OutputSettings::default(),
+        history: HistorySettings::default(),
+        recording_archive: RecordingArchiveSettings::default(),
+        tts: TtsSettings::default(),
+        context_memory: ContextMemorySettings::default(),
+        profile_suggestion: ProfileSuggestionSettings::default(),
+        profile_sync: ProfileSyncSettings::default(),
+        voice_commands: VoiceCommandSettings::default(),
+        input_integration: InputIntegrationSettings::default(),
+        goals: GoalSettings::default(),
+        features: FeatureFlags::default(),
+        api_limits: ApiLimits::default(),
+        notifications: NotificationRouting::default(),
+        continuous: ContinuousSettings::default(),
+        bookmarks: BookmarkSettings::default(),
+        long_recordings: LongRecordingSettings::default(),
+        weekly_report: WeeklyReportSettings::default(),
+        network: NetworkConfig::default(),
+        transcription_queue: TranscriptionQueueSettings {
+            max_concurrent_jobs: 3,
         },
         global_shortcut: "Ctrl+Shift+F9".to_string(),
     }
@@ -44,6 +111,7 @@ fn create_test_profile() -> Profile {
     Profile {
         id: "test-profile".to_string(),
         name: "Test Profile".to_string(),
+        kind: Some(ProfileKind::Formatting),
         description: Some("Test profile for durability tests".to_string()),
         prompt: Some("Test prompt".to_string()),
         example_input: Some("test input".to_string()),
@@ -51,6 +119,14 @@ fn create_test_profile() -> Profile {
         active: true,
         visible: Some(true),
         shortcut: Some("Ctrl+Alt+T".to_string()),
+        input_device: None,
+        timestamps: None,
+        whisper_mode: None,
+        mixed_language: None,
+        history_retention_hours: None,
+        reference_file: None,
+        keywords: None,
+        locked: None,
         created_at: "2025-01-01T00:00:00Z".to_string(),
         updated_at: "2025-01-01T00:00:00Z".to_string(),
     }
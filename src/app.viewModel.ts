@@ -66,33 +66,19 @@ export function useAppViewModel() {
       console.log('🚀 [APP-INIT] Starting backend service initialization...')
       console.time('backend-services-init')
 
-      // Initialize state machine first
-      console.log('📡 [APP-INIT] Step 1: Initializing state machine...')
-      await invoke('init_state_machine')
-      console.log('✅ [APP-INIT] State machine initialized successfully')
-
-      // Initialize audio capture early to fix first-launch issue
-      console.log('🎙️ [APP-INIT] Step 2: Initializing audio capture...')
-      await invoke('init_audio_capture')
-      console.log('✅ [APP-INIT] Audio capture initialized successfully')
-
-      // Verify audio capture is working
-      console.log('🔍 [APP-INIT] Step 2.1: Verifying audio capture...')
-      const isRecording = await invoke('is_recording')
-      console.log('📊 [APP-INIT] Audio capture verification:', { isRecording })
-
-      // Skip shortcut manager - it's already initialized elsewhere
-      console.log(
-        '⌨️ [APP-INIT] Step 3: Skipping shortcut manager (already initialized)...'
-      )
-
-      // Initialize system tray
-      console.log('🗂️ [APP-INIT] Step 4: Initializing system tray...')
-      await invoke('init_system_tray', {
-        showStartupNotification: false, // Don't show notification on first launch
-        isFirstLaunch: true,
-      })
-      console.log('✅ [APP-INIT] System tray initialized successfully')
+      // One backend call initializes the services in order (idempotent)
+      console.log('📡 [APP-INIT] Bootstrapping backend services...')
+      const report = await invoke<{
+        ok: boolean
+        subsystems: { subsystem: string; status: string; message: string }[]
+      }>('bootstrap_app', { isFirstLaunch: true })
+      console.log('📊 [APP-INIT] Bootstrap report:', report)
+      if (!report.ok) {
+        console.warn(
+          '⚠️ [APP-INIT] Some backend services failed to start:',
+          report.subsystems.filter(subsystem => subsystem.status === 'failed')
+        )
+      }
 
       console.timeEnd('backend-services-init')
       console.log('🎉 [APP-INIT] All backend services initialized successfully')
@@ -122,6 +122,17 @@ const AppStateDemo: React.FC = () => {
                 <div className="p-2 bg-gray-50 rounded text-sm">
                   {state.appState.originalTranscript}
                 </div>
+                {(state.appState.detectedLanguage ||
+                  state.appState.audioDuration !== null) && (
+                  <div className="mt-1 text-xs text-gray-500">
+                    {state.appState.detectedLanguage &&
+                      `Detected language: ${state.appState.detectedLanguage}`}
+                    {state.appState.audioDuration !== null &&
+                      ` · ${state.appState.audioDuration.toFixed(1)}s`}
+                    {state.appState.transcriptSegments &&
+                      ` · ${state.appState.transcriptSegments.length} segments`}
+                  </div>
+                )}
               </div>
             )}
 
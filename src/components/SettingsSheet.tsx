@@ -20,7 +20,7 @@ import { useSortable } from '@dnd-kit/sortable'
 import { CSS } from '@dnd-kit/utilities'
 import ProfileEditorWindow from '../windows/ProfileEditorWindow'
 import { useSettingsSheetViewModel } from './settingsSheet.viewModel'
-import { Profile } from '@/store/slices/appSlice'
+import { Profile, isClipboardProfile } from '@/store/slices/appSlice'
 
 interface SettingsSheetProps {
   onClose: () => void
@@ -200,6 +200,7 @@ const SettingsSheet: React.FC<SettingsSheetProps> = ({ onClose }) => {
   const saveButtonRef = useRef<HTMLButtonElement>(null)
   const firstInputRef = useRef<HTMLInputElement>(null)
   const [isDragActive, setIsDragActive] = useState(false)
+  const userProfiles = state.profiles.filter((p) => !isClipboardProfile(p))
 
   useEffect(onMount, [])
 
@@ -505,7 +506,7 @@ const SettingsSheet: React.FC<SettingsSheetProps> = ({ onClose }) => {
               <div className="section-header">
                 <h3>Custom Profiles</h3>
                 <span className="profile-count">
-                  {state.profiles.filter((p) => p.id !== '1').length}/4
+                  {userProfiles.length}/4
                 </span>
               </div>
 
@@ -515,9 +516,7 @@ const SettingsSheet: React.FC<SettingsSheetProps> = ({ onClose }) => {
                 onDragEnd={handleDragEnd}
               >
                 <SortableContext
-                  items={state.profiles
-                    .filter((p) => p.id !== '1')
-                    .map((p) => p.id)}
+                  items={userProfiles.map((p) => p.id)}
                   strategy={verticalListSortingStrategy}
                 >
                   <div
@@ -526,24 +525,19 @@ const SettingsSheet: React.FC<SettingsSheetProps> = ({ onClose }) => {
                     aria-labelledby="profiles-label"
                     data-dragging={isDragActive}
                   >
-                    {state.profiles
-                      .filter((p) => p.id !== '1')
-                      .map((profile) => (
-                        <SortableProfileRow
-                          key={profile.id}
-                          profile={profile}
-                          visibleProfilesCount={state.visibleProfilesCount}
-                          onToggleVisibility={actions.toggleProfileVisibility}
-                          onEdit={actions.navigateToEditor}
-                          onKeyboardReorder={handleKeyboardReorder}
-                          isDragActive={isDragActive}
-                          allProfiles={state.profiles.filter(
-                            (p) => p.id !== '1'
-                          )}
-                        />
-                      ))}
-                    {state.profiles.filter((p) => p.id !== '1').length ===
-                      0 && (
+                    {userProfiles.map((profile) => (
+                      <SortableProfileRow
+                        key={profile.id}
+                        profile={profile}
+                        visibleProfilesCount={state.visibleProfilesCount}
+                        onToggleVisibility={actions.toggleProfileVisibility}
+                        onEdit={actions.navigateToEditor}
+                        onKeyboardReorder={handleKeyboardReorder}
+                        isDragActive={isDragActive}
+                        allProfiles={userProfiles}
+                      />
+                    ))}
+                    {userProfiles.length === 0 && (
                       <div className="empty-profiles-message" role="status">
                         No custom profiles yet. Create your first profile below.
                       </div>
@@ -552,7 +546,7 @@ const SettingsSheet: React.FC<SettingsSheetProps> = ({ onClose }) => {
                 </SortableContext>
               </DndContext>
 
-              {state.profiles.filter((p) => p.id !== '1').length < 4 && (
+              {userProfiles.length < 4 && (
                 <Button
                   type="button"
                   className="add-profile-button"
@@ -560,8 +554,7 @@ const SettingsSheet: React.FC<SettingsSheetProps> = ({ onClose }) => {
                   aria-label="Add new profile"
                 >
                   <Plus size={16} />
-                  Add Profile (
-                  {state.profiles.filter((p) => p.id !== '1').length}/4)
+                  Add Profile ({userProfiles.length}/4)
                 </Button>
               )}
             </div>
@@ -93,6 +93,7 @@ export function useSettingsSheetViewModel(onClose: () => void) {
     isLoading: isLoadingProfiles,
     error: profilesError,
     loadProfiles,
+    isClipboardProfile,
   } = useProfiles()
 
   const visibleProfilesCount = profiles.filter((p) => p.visible).length
@@ -419,11 +420,21 @@ export function useSettingsSheetViewModel(onClose: () => void) {
           }
         }
 
-        // Reinitialize Whisper client if API key is provided
-        if (
-          settings.whisper.api_key &&
-          settings.whisper.api_key.trim() !== ''
-        ) {
+        const hasApiKey =
+          !!settings.whisper.api_key && settings.whisper.api_key.trim() !== ''
+        // Reinitialize Whisper client if API key is provided (the local
+        // provider transcribes without one)
+        if (!hasApiKey && settings.whisper.provider === 'local') {
+          try {
+            await invoke('init_whisper_client', { apiKey: '' })
+            console.log('Local Whisper client reinitialized')
+          } catch (whisperError) {
+            console.error(
+              'Failed to reinitialize Whisper client:',
+              whisperError
+            )
+          }
+        } else if (hasApiKey) {
           try {
             await invoke('init_whisper_client', {
               apiKey: settings.whisper.api_key,
@@ -540,7 +551,10 @@ export function useSettingsSheetViewModel(onClose: () => void) {
 
         const profileCollection = {
           profiles: updatedProfiles,
-          default_profile_id: '1',
+          default_profile_id:
+            updatedProfiles.find((p) => isClipboardProfile(p.id))?.id ||
+            updatedProfiles[0]?.id ||
+            '',
         }
 
         await invoke('save_profiles', { profiles: profileCollection })
@@ -561,7 +575,7 @@ export function useSettingsSheetViewModel(onClose: () => void) {
     },
     deleteProfile: async (profileId: string) => {
       // Prevent deletion of clipboard profile
-      if (profileId === '1') {
+      if (isClipboardProfile(profileId)) {
         const errorMessage = 'Cannot delete the clipboard profile'
         setSaveError(errorMessage)
         toast.error('Cannot delete profile', {
@@ -571,6 +585,17 @@ export function useSettingsSheetViewModel(onClose: () => void) {
         return
       }
 
+      // Locked profiles are distributed by an admin and can only be hidden
+      if (profiles.find((p) => p.id === profileId)?.locked) {
+        const errorMessage = 'This profile is locked: it can only be hidden'
+        setSaveError(errorMessage)
+        toast.error('Cannot delete profile', {
+          description: errorMessage,
+          duration: 5000,
+        })
+        return
+      }
+
       try {
         const profileToDelete = profiles.find((p) => p.id === profileId)
         const updatedProfiles = profiles.filter((p) => p.id !== profileId)
@@ -613,21 +638,21 @@ export function useSettingsSheetViewModel(onClose: () => void) {
     },
     toggleProfileVisibility: async (profileId: string, visible: boolean) => {
       // Prevent hiding clipboard profile
-      if (profileId === '1' && !visible) {
+      if (isClipboardProfile(profileId) && !visible) {
         setSaveError('Clipboard profile must always be visible')
         return
       }
 
       // Count visible profiles excluding clipboard profile for the 4-profile limit
       const visibleUserProfiles = profiles.filter(
-        (p) => p.visible && p.id !== '1'
+        (p) => p.visible && !isClipboardProfile(p.id)
       )
       const visibleCount = visibleUserProfiles.length
 
       try {
         let updatedProfiles = [...profiles]
 
-        if (visible && visibleCount >= 4 && profileId !== '1') {
+        if (visible && visibleCount >= 4 && !isClipboardProfile(profileId)) {
           // If trying to make visible but already at 4 user profiles limit, hide the oldest visible user profile first
           if (visibleUserProfiles.length > 0) {
             // Find the user profile that was updated longest ago (oldest)
@@ -675,15 +700,15 @@ export function useSettingsSheetViewModel(onClose: () => void) {
     },
     reorderProfiles: async (activeId: string, overId: string) => {
       // Prevent reordering clipboard profile
-      if (activeId === '1' || overId === '1') {
+      if (isClipboardProfile(activeId) || isClipboardProfile(overId)) {
         setSaveError('Cannot reorder the clipboard profile')
         return
       }
 
       try {
         // Only reorder non-clipboard profiles
-        const userProfiles = profiles.filter((p) => p.id !== '1')
-        const clipboardProfile = profiles.find((p) => p.id === '1')
+        const userProfiles = profiles.filter((p) => !isClipboardProfile(p.id))
+        const clipboardProfile = profiles.find((p) => isClipboardProfile(p.id))
 
         // Find the indices of the profiles being reordered within user profiles
         const activeIndex = userProfiles.findIndex((p) => p.id === activeId)
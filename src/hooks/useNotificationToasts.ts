@@ -0,0 +1,53 @@
+import { useEffect } from 'react'
+import { toast } from 'sonner'
+import { listenInWindow } from '../lib/windowEvents'
+
+interface NotificationToast {
+  level: 'Success' | 'Warning' | 'Error' | 'Progress'
+  message: string
+  duration_ms: number
+}
+
+/**
+ * Hook showing the backend notifications routed to in-app toasts
+ * (`notifications` in settings)
+ */
+export const useNotificationToasts = () => {
+  useEffect(() => {
+    let unlisten: (() => void) | undefined
+    let cancelled = false
+
+    listenInWindow<NotificationToast>('notification-toast', (event) => {
+      const { level, message, duration_ms } = event.payload
+      const options = { duration: duration_ms }
+      switch (level) {
+        case 'Success':
+          toast.success(message, options)
+          break
+        case 'Warning':
+          toast.warning(message, options)
+          break
+        case 'Error':
+          toast.error(message, options)
+          break
+        default:
+          toast.info(message, options)
+      }
+    })
+      .then((stop) => {
+        if (cancelled) {
+          stop()
+        } else {
+          unlisten = stop
+        }
+      })
+      .catch((error) =>
+        console.error('Failed to listen to notification toasts:', error)
+      )
+
+    return () => {
+      cancelled = true
+      unlisten?.()
+    }
+  }, [])
+}
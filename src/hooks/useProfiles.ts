@@ -4,7 +4,7 @@ import { useBackendCommands } from './useBackendCommands'
 import {
   selectVisibleProfiles,
   selectEditableProfiles,
-  selectIsClipboardProfile,
+  selectClipboardProfileId,
   selectActiveProfile,
   Profile,
 } from '../store/slices/appSlice'
@@ -38,11 +38,15 @@ export const useProfiles = (): UseProfilesReturn => {
   const activeProfile = useAppSelector(selectActiveProfile)
   const visibleProfiles = useAppSelector(selectVisibleProfiles)
   const editableProfiles = useAppSelector(selectEditableProfiles)
+  const clipboardProfileId = useAppSelector(selectClipboardProfileId)
 
   // Helper functions
-  const isClipboardProfile = useCallback((profileId: string): boolean => {
-    return selectIsClipboardProfile(profileId)
-  }, [])
+  const isClipboardProfile = useCallback(
+    (profileId: string): boolean => {
+      return profileId === clipboardProfileId
+    },
+    [clipboardProfileId]
+  )
 
   const getVisibleProfiles = useCallback((): Profile[] => {
     return visibleProfiles
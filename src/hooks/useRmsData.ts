@@ -1,6 +1,6 @@
 import { useEffect, useState, useRef, useCallback } from 'react'
-import { listen } from '@tauri-apps/api/event'
 import { invoke } from '@tauri-apps/api/core'
+import { listenInWindow } from '../lib/windowEvents'
 
 interface RmsData {
   /** Current RMS value (0.0 to 1.0) */
@@ -141,7 +141,7 @@ export const useRmsData = (options: UseRmsDataOptions = {}): RmsData => {
 
         // Listen for RMS events with better error handling
         console.log('👂 [RMS-HOOK] Setting up RMS event listener...')
-        unlistenRms = await listen<number>('rms', (event) => {
+        unlistenRms = await listenInWindow<number>('rms', (event) => {
           if (isCleanedUp) return // Ignore events after cleanup
 
           console.log('📊 [RMS-HOOK] Received RMS event:', {
@@ -228,6 +228,12 @@ export const useRmsData = (options: UseRmsDataOptions = {}): RmsData => {
       if (retryTimeoutId) {
         clearTimeout(retryTimeoutId)
       }
+      // Stop the backend emitting levels nobody displays
+      if (isSubscribedRef.current) {
+        invoke('unsubscribe_rms').catch((error) => {
+          console.warn('⚠️ [RMS-HOOK] Failed to unsubscribe from RMS:', error)
+        })
+      }
       isSubscribedRef.current = false
       isSetupRef.current = false
     }
@@ -0,0 +1,23 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { EventCallback, UnlistenFn } from '@tauri-apps/api/event'
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow'
+
+/**
+ * Listen to a backend event in the current window only.
+ *
+ * The window subscribes to the event, so events sent to other windows (or
+ * that this window does not listen to) are not delivered to it.
+ */
+export async function listenInWindow<T>(
+  event: string,
+  handler: EventCallback<T>
+): Promise<UnlistenFn> {
+  await invoke('subscribe_window_events', { events: [event] })
+  const unlisten = await getCurrentWebviewWindow().listen<T>(event, handler)
+  return () => {
+    unlisten()
+    invoke('unsubscribe_window_events', { events: [event] }).catch((error) =>
+      console.warn(`Failed to unsubscribe from ${event}:`, error)
+    )
+  }
+}
@@ -129,6 +129,29 @@ describe('appSlice', () => {
       expect(state.mainWindowVisible).toBe(true)
     })
 
+    it('should not mistake a recording device error for a recording', () => {
+      const store = createTestStore()
+      const backendEvent: BackendStateEvent = {
+        previous_state: 'Recording { started_at: SystemTime { .. } }',
+        current_state:
+          'RecordingDeviceError { error: "Failed to start recording: no input device", main_window_visible: true }',
+        event: 'RecordingStartFailed { error: "no input device" }',
+        timestamp: Date.now(),
+        context: {
+          is_recording: false,
+          is_processing: false,
+          main_window_visible: true,
+          has_modal_window: false,
+        },
+      }
+
+      store.dispatch(appSlice.actions.backendStateChanged(backendEvent))
+      const state = store.getState().app
+
+      expect(state.status).toBe('error-recording-device')
+      expect(state.recordingStartTime).toBe(null)
+    })
+
     it('should clear error when transitioning away from error state', () => {
       const initialState = new AppStateBuilder()
         .errorState('transcription')
@@ -248,6 +271,25 @@ describe('appSlice', () => {
       expect(state.profileId).toBe('test-profile')
     })
 
+    it('should keep what Whisper detected', () => {
+      const store = createTestStore()
+      store.dispatch(
+        appSlice.actions.setProcessingData({
+          originalTranscript: 'Bonjour',
+          detectedLanguage: 'french',
+          audioDuration: 1.5,
+          transcriptSegments: [{ id: 0, start: 0, end: 1.5, text: 'Bonjour' }],
+          transcriptWords: [{ word: 'Bonjour', start: 0.1, end: 1.2 }],
+        })
+      )
+
+      const state = store.getState().app
+      expect(state.detectedLanguage).toBe('french')
+      expect(state.audioDuration).toBe(1.5)
+      expect(state.transcriptSegments).toHaveLength(1)
+      expect(state.transcriptWords?.[0].end).toBe(1.2)
+    })
+
     it('should update only provided fields', () => {
       const initialState = new AppStateBuilder()
         .withTranscript('Existing transcript', 'Existing final')
@@ -14,6 +14,7 @@ import {
   profileSelected,
   BackendStateEvent,
   ProfileCollection,
+  isClipboardProfile,
   addError,
   updateProcessingProgress,
   updateClipboard,
@@ -22,6 +23,8 @@ import {
   setAutoRecoveryMode,
   AppError,
   ProcessingProgress,
+  TranscriptSegment,
+  TranscriptWord,
   WindowState,
   clearErrors,
 } from './slices/appSlice'
@@ -65,8 +68,8 @@ export const setupBackendSync = (dispatch: AppDispatch): BackendCommands => {
 
       dispatch(setProfiles(profileData.profiles))
 
-      // Set active profile to clipboard profile (ID "1") first, then fallback to default or first active profile
-      const clipboardProfile = profileData.profiles.find((p) => p.id === '1')
+      // Set active profile to clipboard profile first, then fallback to default or first active profile
+      const clipboardProfile = profileData.profiles.find(isClipboardProfile)
       const defaultProfile = profileData.profiles.find(
         (p) => p.id === profileData.default_profile_id
       )
@@ -142,6 +145,10 @@ export const setupBackendSync = (dispatch: AppDispatch): BackendCommands => {
         original_transcript?: string
         final_text?: string
         profile_id?: string
+        language?: string | null
+        duration?: number | null
+        segments?: TranscriptSegment[] | null
+        words?: TranscriptWord[] | null
       }>('processing-data-updated', (event) => {
         console.log('📊 [BACKEND-SYNC] Processing data updated:', event.payload)
         dispatch(
@@ -149,6 +156,10 @@ export const setupBackendSync = (dispatch: AppDispatch): BackendCommands => {
             originalTranscript: event.payload.original_transcript || null,
             finalText: event.payload.final_text || null,
             profileId: event.payload.profile_id || null,
+            detectedLanguage: event.payload.language ?? null,
+            audioDuration: event.payload.duration ?? null,
+            transcriptSegments: event.payload.segments ?? null,
+            transcriptWords: event.payload.words ?? null,
           })
         )
       })
@@ -285,10 +296,8 @@ export const setupBackendSync = (dispatch: AppDispatch): BackendCommands => {
       await listen<ProfileCollection>('profiles-updated', (event) => {
         dispatch(setProfiles(event.payload.profiles))
 
-        // Set active profile to clipboard profile (ID "1") first, then fallback to default or first active profile
-        const clipboardProfile = event.payload.profiles.find(
-          (p) => p.id === '1'
-        )
+        // Set active profile to clipboard profile first, then fallback to default or first active profile
+        const clipboardProfile = event.payload.profiles.find(isClipboardProfile)
         const defaultProfile = event.payload.profiles.find(
           (p) => p.id === event.payload.default_profile_id
         )
@@ -1,9 +1,13 @@
 import { createSlice, PayloadAction } from '@reduxjs/toolkit'
 
 // Profile interface (moving from ProfileContext to Redux)
+export type ProfileKind = 'clipboard' | 'formatting' | 'extraction'
+
 export interface Profile {
   id: string
   name: string
+  /** What the profile does (the backend migrates profiles without one) */
+  kind?: ProfileKind
   description?: string
   prompt?: string
   example_input?: string
@@ -11,6 +15,8 @@ export interface Profile {
   active: boolean
   visible?: boolean
   shortcut?: string
+  /** Distributed by an admin: can only be hidden */
+  locked?: boolean
   created_at: string
   updated_at: string
 }
@@ -24,6 +30,7 @@ export interface ProfileCollection {
 export type AppStatus =
   | 'idle'
   | 'recording'
+  | 'awaiting-confirmation'
   | 'processing-transcription'
   | 'processing-gpt-formatting'
   | 'processing-clipboard'
@@ -35,6 +42,7 @@ export type AppStatus =
   | 'error-gpt-formatting'
   | 'error-clipboard'
   | 'error-profile-validation'
+  | 'error-recording-device'
 
 // Advanced error types for better error handling
 export interface AppError {
@@ -57,6 +65,21 @@ export interface ProcessingProgress {
   message?: string
 }
 
+// Transcript segment with its timing (seconds from the recording start)
+export interface TranscriptSegment {
+  id: number
+  start: number
+  end: number
+  text: string
+}
+
+// Transcript word with its timing (only when word timestamps are enabled)
+export interface TranscriptWord {
+  word: string
+  start: number
+  end: number
+}
+
 // Clipboard state for advanced clipboard integration
 export interface ClipboardState {
   lastCopiedText: string | null
@@ -104,6 +127,11 @@ export interface AppState {
   originalTranscript: string | null
   finalText: string | null
   profileId: string | null
+  // What Whisper detected for the transcript (verbose_json response)
+  detectedLanguage: string | null
+  audioDuration: number | null
+  transcriptSegments: TranscriptSegment[] | null
+  transcriptWords: TranscriptWord[] | null
   processingProgress: ProcessingProgress | null
 
   // Profile management (moved from ProfileContext)
@@ -142,6 +170,9 @@ export interface BackendStateEvent {
     is_processing: boolean
     main_window_visible: boolean
     has_modal_window: boolean
+    // Set on StopRecording transitions: 'silence' for the VAD auto-stop,
+    // 'max_duration' when audio.max_recording_seconds was reached
+    stop_reason?: 'manual' | 'silence' | 'max_duration' | null
   }
 }
 
@@ -169,6 +200,10 @@ const initialState: AppState = {
   originalTranscript: null,
   finalText: null,
   profileId: null,
+  detectedLanguage: null,
+  audioDuration: null,
+  transcriptSegments: null,
+  transcriptWords: null,
   processingProgress: null,
   profiles: [],
   activeProfileId: null,
@@ -191,15 +226,15 @@ const initialState: AppState = {
 }
 
 // Helper function to check if a profile is the clipboard profile
-const isClipboardProfile = (profileId: string): boolean => {
-  return profileId === '1'
+export const isClipboardProfile = (profile: Profile): boolean => {
+  return profile.kind === 'clipboard'
 }
 
 // Helper function to get visible profiles (clipboard + up to 4 user profiles)
 const getVisibleProfiles = (profiles: Profile[]): Profile[] => {
-  const clipboardProfile = profiles.find((p) => p.id === '1')
+  const clipboardProfile = profiles.find(isClipboardProfile)
   const userProfiles = profiles
-    .filter((p) => p.id !== '1' && p.visible === true)
+    .filter((p) => !isClipboardProfile(p) && p.visible === true)
     .slice(0, 4)
 
   return clipboardProfile ? [clipboardProfile, ...userProfiles] : userProfiles
@@ -207,13 +242,18 @@ const getVisibleProfiles = (profiles: Profile[]): Profile[] => {
 
 // Helper function to get editable profiles (excluding clipboard, max 4)
 const getEditableProfiles = (profiles: Profile[]): Profile[] => {
-  return profiles.filter((p) => p.id !== '1').slice(0, 4)
+  return profiles.filter((p) => !isClipboardProfile(p)).slice(0, 4)
 }
 
 // Map backend states to frontend states
 const mapBackendToFrontendStatus = (backendState: string): AppStatus => {
   if (backendState.includes('Idle')) return 'idle'
+  // Before the 'Recording' match: the device error names it too
+  if (backendState.includes('RecordingDeviceError'))
+    return 'error-recording-device'
   if (backendState.includes('Recording')) return 'recording'
+  if (backendState.includes('AwaitingConfirmation'))
+    return 'awaiting-confirmation'
   if (backendState.includes('ProcessingTranscription'))
     return 'processing-transcription'
   if (backendState.includes('ProcessingGPTFormatting'))
@@ -353,6 +393,10 @@ export const appSlice = createSlice({
         state.originalTranscript = null
         state.finalText = null
         state.profileId = null
+        state.detectedLanguage = null
+        state.audioDuration = null
+        state.transcriptSegments = null
+        state.transcriptWords = null
         state.processingProgress = null
       }
     },
@@ -444,13 +488,31 @@ export const appSlice = createSlice({
         originalTranscript?: string | null
         finalText?: string | null
         profileId?: string | null
+        detectedLanguage?: string | null
+        audioDuration?: number | null
+        transcriptSegments?: TranscriptSegment[] | null
+        transcriptWords?: TranscriptWord[] | null
       }>
     ) => {
-      const { originalTranscript, finalText, profileId } = action.payload
+      const {
+        originalTranscript,
+        finalText,
+        profileId,
+        detectedLanguage,
+        audioDuration,
+        transcriptSegments,
+        transcriptWords,
+      } = action.payload
       if (originalTranscript !== undefined)
         state.originalTranscript = originalTranscript
       if (finalText !== undefined) state.finalText = finalText
       if (profileId !== undefined) state.profileId = profileId
+      if (detectedLanguage !== undefined)
+        state.detectedLanguage = detectedLanguage
+      if (audioDuration !== undefined) state.audioDuration = audioDuration
+      if (transcriptSegments !== undefined)
+        state.transcriptSegments = transcriptSegments
+      if (transcriptWords !== undefined) state.transcriptWords = transcriptWords
     },
 
     // Set error message (legacy support)
@@ -478,8 +540,10 @@ export const selectEditableProfiles = (state: { app: AppState }): Profile[] => {
   return getEditableProfiles(state.app.profiles)
 }
 
-export const selectIsClipboardProfile = (profileId: string): boolean => {
-  return isClipboardProfile(profileId)
+export const selectClipboardProfileId = (state: {
+  app: AppState
+}): string | null => {
+  return state.app.profiles.find(isClipboardProfile)?.id ?? null
 }
 
 export const selectActiveProfile = (state: {
@@ -28,6 +28,10 @@ export class AppStateBuilder {
       originalTranscript: null,
       finalText: null,
       profileId: null,
+      detectedLanguage: null,
+      audioDuration: null,
+      transcriptSegments: null,
+      transcriptWords: null,
       processingProgress: null,
       profiles: [],
       activeProfileId: null,
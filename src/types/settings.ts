@@ -9,6 +9,15 @@ export interface WhisperSettings {
   model: string
   timeout_seconds: number
   max_retries: number
+  word_timestamps?: boolean
+  mode?: 'transcribe' | 'translate'
+  provider?: 'openai' | 'local'
+  temperature?: number | null
+  response_format?: 'verbose_json' | 'json' | 'text'
+  prompt_strategy?: 'full' | 'vocabulary' | 'none'
+  min_upload_kbps?: number
+  retry_base_delay_ms?: number
+  retry_max_delay_ms?: number
 }
 
 export interface AudioSettings {
@@ -25,6 +34,8 @@ export interface EncodingSettings {
 export interface UiSettings {
   theme: string
   auto_start_recording: boolean
+  /** Never create the main window (applied at launch) */
+  tray_only?: boolean
 }
 
 /**
@@ -9,12 +9,14 @@ import SettingsButton from '../components/SettingsButton'
 import { useMainWindowViewModel } from './mainWindow.viewModel'
 import { Toaster } from '@/components/ui/sonner'
 import { useProcessingToasts } from '../hooks/useProcessingToasts'
+import { useNotificationToasts } from '../hooks/useNotificationToasts'
 
 export default function MainWindow() {
   const { onMount } = useMainWindowViewModel()
 
   // Initialize processing toasts
   useProcessingToasts()
+  useNotificationToasts()
 
   useEffect(onMount, [onMount])
 
@@ -84,11 +84,17 @@ export function useMainWindowViewModel() {
         console.log('Loaded settings for client initialization')
 
         if (settings && typeof settings === 'object' && 'whisper' in settings) {
-          const whisperSettings = settings.whisper as { api_key?: string }
-          if (
-            whisperSettings.api_key &&
-            whisperSettings.api_key.trim() !== ''
-          ) {
+          const whisperSettings = settings.whisper as {
+            api_key?: string
+            provider?: string
+          }
+          const hasApiKey =
+            !!whisperSettings.api_key && whisperSettings.api_key.trim() !== ''
+          // The local provider transcribes without an API key
+          if (!hasApiKey && whisperSettings.provider === 'local') {
+            await invoke('init_whisper_client', { apiKey: '' })
+            console.log('Local Whisper client initialized successfully')
+          } else if (hasApiKey) {
             // Initialize Whisper client
             await invoke('init_whisper_client', {
               apiKey: whisperSettings.api_key,